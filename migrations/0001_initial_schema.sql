@@ -0,0 +1,78 @@
+-- Schema previously created at startup by the repositories. Every statement is
+-- idempotent so databases created before the migrations are adopted as is.
+
+CREATE TABLE IF NOT EXISTS person (
+    uid CHAR(36) PRIMARY KEY,
+    name CHAR(50),
+    first_name CHAR(50),
+    birth_date DATE,
+    trust_score SMALLINT,
+    lie_quantity BIGINT,
+    CONSTRAINT unique_identity UNIQUE (name, first_name, birth_date)
+);
+ALTER TABLE person ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS speech (
+    uid CHAR(36) PRIMARY KEY,
+    name VARCHAR,
+    date TIMESTAMPTZ,
+    media VARCHAR,
+    status VARCHAR,
+    CONSTRAINT unique_speech UNIQUE (name, date, media)
+);
+ALTER TABLE speech ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE TABLE IF NOT EXISTS sentence (
+    uid CHAR(36) PRIMARY KEY,
+    speech_uid CHAR(36),
+    speaker CHAR(36),
+    text VARCHAR,
+    interrupted BOOLEAN,
+    index INT,
+    CONSTRAINT FK_SentenceSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid),
+    CONSTRAINT FK_SentencePerson FOREIGN KEY (speaker) REFERENCES person(uid)
+);
+
+CREATE TABLE IF NOT EXISTS speech_person (
+    speech_uid CHAR(36),
+    speaker CHAR(36),
+    CONSTRAINT FK_SentenceSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid),
+    CONSTRAINT FK_SentencePerson FOREIGN KEY (speaker) REFERENCES person(uid)
+);
+
+CREATE TABLE IF NOT EXISTS speech_slug (
+    slug VARCHAR PRIMARY KEY,
+    speech_uid CHAR(36) NOT NULL,
+    current BOOLEAN NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT FK_SlugSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid)
+);
+
+CREATE TABLE IF NOT EXISTS speech_revision (
+    speech_uid CHAR(36),
+    revision INT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    snapshot JSONB NOT NULL,
+    PRIMARY KEY (speech_uid, revision),
+    CONSTRAINT FK_RevisionSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid)
+);
+
+CREATE TABLE IF NOT EXISTS label (
+    uid CHAR(36) PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    owner_id VARCHAR
+);
+-- Shared labels have no owner, the name must still be unique among them.
+CREATE UNIQUE INDEX IF NOT EXISTS unique_label ON label (name, COALESCE(owner_id, ''));
+
+CREATE TABLE IF NOT EXISTS speech_label (
+    speech_uid CHAR(36) REFERENCES speech(uid),
+    label_uid CHAR(36) REFERENCES label(uid),
+    PRIMARY KEY (speech_uid, label_uid)
+);
+
+CREATE TABLE IF NOT EXISTS person_label (
+    person_uid CHAR(36) REFERENCES person(uid),
+    label_uid CHAR(36) REFERENCES label(uid),
+    PRIMARY KEY (person_uid, label_uid)
+);
@@ -0,0 +1,53 @@
+-- Store every uid as a native UUID instead of CHAR(36). The foreign keys are dropped
+-- while both sides of each reference change type, then recreated.
+
+ALTER TABLE sentence
+    DROP CONSTRAINT FK_SentenceSpeech,
+    DROP CONSTRAINT FK_SentencePerson;
+ALTER TABLE speech_person
+    DROP CONSTRAINT FK_SentenceSpeech,
+    DROP CONSTRAINT FK_SentencePerson;
+ALTER TABLE speech_slug DROP CONSTRAINT FK_SlugSpeech;
+ALTER TABLE speech_revision DROP CONSTRAINT FK_RevisionSpeech;
+ALTER TABLE speech_label
+    DROP CONSTRAINT speech_label_speech_uid_fkey,
+    DROP CONSTRAINT speech_label_label_uid_fkey;
+ALTER TABLE person_label
+    DROP CONSTRAINT person_label_person_uid_fkey,
+    DROP CONSTRAINT person_label_label_uid_fkey;
+
+ALTER TABLE person ALTER COLUMN uid TYPE UUID USING uid::TEXT::UUID;
+ALTER TABLE speech ALTER COLUMN uid TYPE UUID USING uid::TEXT::UUID;
+ALTER TABLE label ALTER COLUMN uid TYPE UUID USING uid::TEXT::UUID;
+ALTER TABLE sentence
+    ALTER COLUMN uid TYPE UUID USING uid::TEXT::UUID,
+    ALTER COLUMN speech_uid TYPE UUID USING speech_uid::TEXT::UUID,
+    ALTER COLUMN speaker TYPE UUID USING speaker::TEXT::UUID;
+ALTER TABLE speech_person
+    ALTER COLUMN speech_uid TYPE UUID USING speech_uid::TEXT::UUID,
+    ALTER COLUMN speaker TYPE UUID USING speaker::TEXT::UUID;
+ALTER TABLE speech_slug ALTER COLUMN speech_uid TYPE UUID USING speech_uid::TEXT::UUID;
+ALTER TABLE speech_revision ALTER COLUMN speech_uid TYPE UUID USING speech_uid::TEXT::UUID;
+ALTER TABLE speech_label
+    ALTER COLUMN speech_uid TYPE UUID USING speech_uid::TEXT::UUID,
+    ALTER COLUMN label_uid TYPE UUID USING label_uid::TEXT::UUID;
+ALTER TABLE person_label
+    ALTER COLUMN person_uid TYPE UUID USING person_uid::TEXT::UUID,
+    ALTER COLUMN label_uid TYPE UUID USING label_uid::TEXT::UUID;
+
+ALTER TABLE sentence
+    ADD CONSTRAINT FK_SentenceSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid),
+    ADD CONSTRAINT FK_SentencePerson FOREIGN KEY (speaker) REFERENCES person(uid);
+ALTER TABLE speech_person
+    ADD CONSTRAINT FK_SentenceSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid),
+    ADD CONSTRAINT FK_SentencePerson FOREIGN KEY (speaker) REFERENCES person(uid);
+ALTER TABLE speech_slug
+    ADD CONSTRAINT FK_SlugSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid);
+ALTER TABLE speech_revision
+    ADD CONSTRAINT FK_RevisionSpeech FOREIGN KEY (speech_uid) REFERENCES speech(uid);
+ALTER TABLE speech_label
+    ADD CONSTRAINT speech_label_speech_uid_fkey FOREIGN KEY (speech_uid) REFERENCES speech(uid),
+    ADD CONSTRAINT speech_label_label_uid_fkey FOREIGN KEY (label_uid) REFERENCES label(uid);
+ALTER TABLE person_label
+    ADD CONSTRAINT person_label_person_uid_fkey FOREIGN KEY (person_uid) REFERENCES person(uid),
+    ADD CONSTRAINT person_label_label_uid_fkey FOREIGN KEY (label_uid) REFERENCES label(uid);
@@ -0,0 +1,5 @@
+-- Language of the speech (ISO 639-3), either provided or detected from the sentences.
+ALTER TABLE speech
+    ADD COLUMN language VARCHAR(3),
+    ADD COLUMN language_confidence DOUBLE PRECISION,
+    ADD COLUMN mixed_language BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,8 @@
+-- Machine translations of the sentences, one row per sentence and target language.
+-- Sentences are replaced when a speech is updated, their translations go with them.
+CREATE TABLE sentence_translation (
+    sentence_uid UUID REFERENCES sentence(uid) ON DELETE CASCADE,
+    language VARCHAR(5),
+    text VARCHAR NOT NULL,
+    PRIMARY KEY (sentence_uid, language)
+);
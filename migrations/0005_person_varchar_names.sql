@@ -0,0 +1,4 @@
+-- CHAR(50) pads names with spaces, store them as VARCHAR(50) without the padding.
+ALTER TABLE person
+    ALTER COLUMN name TYPE VARCHAR(50) USING TRIM(name),
+    ALTER COLUMN first_name TYPE VARCHAR(50) USING TRIM(first_name);
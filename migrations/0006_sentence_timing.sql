@@ -0,0 +1,7 @@
+-- Optional position of each sentence in the media, in milliseconds from its start.
+ALTER TABLE sentence
+    ADD COLUMN start_ms INT,
+    ADD COLUMN end_ms INT,
+    ADD CONSTRAINT sentence_timing CHECK (
+        (start_ms IS NULL AND end_ms IS NULL) OR (start_ms >= 0 AND end_ms >= start_ms)
+    );
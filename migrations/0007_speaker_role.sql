@@ -0,0 +1,4 @@
+-- Part played by each speaker in a speech, moderators are left out of the statistics.
+ALTER TABLE speech_person
+    ADD COLUMN role VARCHAR(10) NOT NULL DEFAULT 'panelist'
+        CHECK (role IN ('moderator', 'panelist', 'guest'));
@@ -0,0 +1,26 @@
+-- Newsrooms hosted on the deployment. Rows without organization belong to the default
+-- organization, the one of the tokens carrying no organization_id claim.
+CREATE TABLE organization (
+    uid UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE person ADD COLUMN org_uid UUID REFERENCES organization(uid);
+ALTER TABLE speech ADD COLUMN org_uid UUID REFERENCES organization(uid);
+ALTER TABLE label ADD COLUMN org_uid UUID REFERENCES organization(uid);
+
+CREATE INDEX person_org_uid ON person (org_uid);
+CREATE INDEX speech_org_uid ON speech (org_uid);
+CREATE INDEX label_org_uid ON label (org_uid);
+
+-- The same person, speech or label may exist once in every organization.
+ALTER TABLE person DROP CONSTRAINT unique_identity;
+CREATE UNIQUE INDEX unique_identity ON person
+    (name, first_name, birth_date, COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
+ALTER TABLE speech DROP CONSTRAINT unique_speech;
+CREATE UNIQUE INDEX unique_speech ON speech
+    (name, date, media, COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
+DROP INDEX unique_label;
+CREATE UNIQUE INDEX unique_label ON label
+    (name, COALESCE(owner_id, ''), COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
@@ -0,0 +1,18 @@
+-- Topics speeches are browsed by. A tag may narrow down a parent tag, e.g. "asylum"
+-- under "immigration", so the tags form a taxonomy.
+CREATE TABLE tag (
+    uid UUID PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    parent_uid UUID REFERENCES tag(uid),
+    org_uid UUID REFERENCES organization(uid)
+);
+CREATE UNIQUE INDEX unique_tag ON tag
+    (LOWER(name), COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
+CREATE INDEX tag_parent_uid ON tag (parent_uid);
+
+CREATE TABLE speech_tag (
+    speech_uid UUID REFERENCES speech(uid),
+    tag_uid UUID REFERENCES tag(uid),
+    PRIMARY KEY (speech_uid, tag_uid)
+);
+CREATE INDEX speech_tag_tag_uid ON speech_tag (tag_uid);
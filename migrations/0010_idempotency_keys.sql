@@ -0,0 +1,16 @@
+-- Responses of the POST requests sent with an Idempotency-Key header, replayed when a
+-- client retries the same request. The organization is not a foreign key so the keys do
+-- not prevent an organization from being deleted, they expire anyway.
+CREATE TABLE idempotency_key (
+    key VARCHAR(255) NOT NULL,
+    org_uid UUID,
+    -- SHA-256 of the method, path and body of the request, in hexadecimal.
+    request_hash CHAR(64) NOT NULL,
+    -- Both NULL while the first request is being processed.
+    response_status SMALLINT,
+    response_body TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE UNIQUE INDEX unique_idempotency_key ON idempotency_key
+    (key, COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
+CREATE INDEX idempotency_key_created_at ON idempotency_key (created_at);
@@ -0,0 +1,8 @@
+-- Position each user has read a speech up to, so reading resumes there on any device.
+CREATE TABLE speech_read_progress (
+    user_id VARCHAR NOT NULL,
+    speech_uid UUID NOT NULL REFERENCES speech(uid),
+    sentence_index INT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, speech_uid)
+);
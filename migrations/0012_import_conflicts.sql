@@ -0,0 +1,18 @@
+-- Speeches of a bulk import that could not be created, kept with the speech to create
+-- until they are resolved and the import is applied again.
+CREATE TABLE import_conflict (
+    uid UUID PRIMARY KEY,
+    import_uid UUID NOT NULL,
+    position INT NOT NULL,
+    kind VARCHAR NOT NULL CHECK (kind IN ('duplicate_speech', 'unknown_speaker')),
+    -- Speech already stored for a duplicate, unknown person for an unknown speaker.
+    reference UUID,
+    speech_uid UUID NOT NULL,
+    speech JSONB NOT NULL,
+    resolution VARCHAR CHECK (resolution IN ('use_existing', 'create_new', 'skip')),
+    resolution_uid UUID,
+    org_uid UUID REFERENCES organization(uid),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX import_conflict_import_uid ON import_conflict (import_uid, position);
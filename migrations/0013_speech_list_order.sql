@@ -0,0 +1,2 @@
+-- Order of the speech lists, newest first, the uid breaking the ties.
+CREATE INDEX speech_org_date_uid ON speech (org_uid, date DESC, uid);
@@ -0,0 +1,10 @@
+-- Cluster of each speech delivering the same text as others, recomputed periodically.
+-- The speeches similar to no other have no row.
+CREATE TABLE speech_cluster (
+    speech_uid UUID PRIMARY KEY REFERENCES speech(uid),
+    cluster_uid UUID NOT NULL,
+    org_uid UUID REFERENCES organization(uid),
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX speech_cluster_cluster_uid ON speech_cluster (cluster_uid);
@@ -0,0 +1,2 @@
+-- Sentences of a speech in order, read for each speech of a list to count them.
+CREATE INDEX sentence_speech_uid_index ON sentence (speech_uid, index);
@@ -0,0 +1,13 @@
+-- Personal data found in the sentences of a speech, to be reviewed by an editor.
+CREATE TABLE pii_flag (
+    uid UUID PRIMARY KEY,
+    speech_uid UUID NOT NULL REFERENCES speech(uid),
+    sentence_index INT NOT NULL,
+    kind VARCHAR NOT NULL CHECK (kind IN ('phone_number', 'email_address', 'postal_address', 'minor_name')),
+    excerpt VARCHAR NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    reviewed_by VARCHAR,
+    reviewed_at TIMESTAMPTZ,
+    -- A single flag by excerpt, so a reviewed excerpt is not flagged again.
+    CONSTRAINT pii_flag_excerpt UNIQUE (speech_uid, kind, excerpt)
+);
@@ -0,0 +1,12 @@
+-- Source media of a speech (audio, video...), stored in the object storage under
+-- `storage_key`.
+CREATE TABLE speech_attachment (
+    uid UUID PRIMARY KEY,
+    speech_uid UUID NOT NULL REFERENCES speech(uid),
+    file_name VARCHAR NOT NULL,
+    content_type VARCHAR NOT NULL,
+    size BIGINT NOT NULL,
+    storage_key VARCHAR NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX speech_attachment_speech_uid ON speech_attachment (speech_uid);
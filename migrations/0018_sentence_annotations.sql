@@ -0,0 +1,13 @@
+-- Language and sentiment of the sentences of a speech, computed in the background once
+-- the speech is created or its sentences edited.
+CREATE TABLE sentence_annotation (
+    sentence_uid UUID PRIMARY KEY,
+    speech_uid UUID NOT NULL REFERENCES speech(uid),
+    sentence_index INT NOT NULL,
+    language VARCHAR,
+    sentiment VARCHAR CHECK (sentiment IN ('positive', 'neutral', 'negative')),
+    -- From -1 (negative) to 1 (positive), set with the sentiment.
+    sentiment_score DOUBLE PRECISION,
+    annotated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX sentence_annotation_speech_uid ON sentence_annotation (speech_uid);
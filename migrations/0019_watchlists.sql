@@ -0,0 +1,18 @@
+-- Time each speech was stored, the watchlists reporting the speeches stored since their
+-- last check. The speeches stored before are dated by this migration.
+ALTER TABLE speech ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+CREATE INDEX speech_org_created_at ON speech (org_uid, created_at);
+
+-- Queries saved by a user to follow the new speeches matching them.
+CREATE TABLE watchlist (
+    uid UUID PRIMARY KEY,
+    owner_id VARCHAR NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    speakers UUID[] NOT NULL DEFAULT '{}',
+    keywords TEXT[] NOT NULL DEFAULT '{}',
+    media TEXT[] NOT NULL DEFAULT '{}',
+    last_checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    org_uid UUID REFERENCES organization(uid)
+);
+CREATE UNIQUE INDEX unique_watchlist ON watchlist
+    (owner_id, LOWER(name), COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
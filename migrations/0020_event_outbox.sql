@@ -0,0 +1,24 @@
+-- Domain events waiting to be published to the broker, written in the transaction of the
+-- change they describe. `transaction_id` is the id of that transaction, the relay only
+-- reads the events of the transactions older than every running one so an event is never
+-- committed behind the offset of the relay.
+CREATE TABLE event_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    transaction_id BIGINT NOT NULL DEFAULT pg_current_xact_id()::TEXT::BIGINT,
+    topic VARCHAR NOT NULL,
+    entity_uid UUID NOT NULL,
+    kind VARCHAR NOT NULL,
+    payload JSONB NOT NULL,
+    org_uid UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+CREATE INDEX event_outbox_position ON event_outbox (transaction_id, id);
+
+-- Last event published by each relay, and the instance running it until the lease ends.
+CREATE TABLE event_outbox_offset (
+    relay VARCHAR PRIMARY KEY,
+    transaction_id BIGINT NOT NULL DEFAULT 0,
+    event_id BIGINT NOT NULL DEFAULT 0,
+    leased_by VARCHAR,
+    leased_until TIMESTAMPTZ
+);
@@ -0,0 +1,11 @@
+-- Runs of the maintenance actions started by the administrators, kept so their outcome
+-- can be read from any instance.
+CREATE TABLE maintenance_job (
+    uid UUID PRIMARY KEY,
+    action VARCHAR NOT NULL,
+    status VARCHAR NOT NULL CHECK (status IN ('running', 'succeeded', 'failed')),
+    affected BIGINT,
+    error VARCHAR,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ
+);
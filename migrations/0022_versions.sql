@@ -0,0 +1,4 @@
+-- Version of the speeches and the persons, incremented by every update. An update sent
+-- with the version read by the client is refused once another one has been saved.
+ALTER TABLE speech ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE person ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
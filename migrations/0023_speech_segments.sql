@@ -0,0 +1,11 @@
+-- Named ranges of sentences of a speech (chapters), from `start_index` to `end_index`
+-- included. The segments of a speech never share a sentence, which the repository checks
+-- under the lock of the speech row.
+CREATE TABLE speech_segment (
+    uid UUID PRIMARY KEY,
+    speech_uid UUID NOT NULL REFERENCES speech(uid),
+    title VARCHAR(200) NOT NULL,
+    start_index INT NOT NULL CHECK (start_index >= 0),
+    end_index INT NOT NULL CHECK (end_index >= start_index)
+);
+CREATE INDEX speech_segment_speech_uid ON speech_segment (speech_uid, start_index);
@@ -0,0 +1,2 @@
+-- Sentences of a person across the speeches, searched for the statements of the person.
+CREATE INDEX sentence_speaker_index ON sentence (speaker, speech_uid, index);
@@ -0,0 +1,9 @@
+-- Review of the transcription of the sentences, a flagged sentence blocking the
+-- validation of its speech.
+ALTER TABLE sentence
+    ADD COLUMN review_status VARCHAR NOT NULL DEFAULT 'unreviewed' CHECK (review_status IN ('unreviewed', 'approved', 'flagged')),
+    ADD COLUMN review_comment VARCHAR,
+    ADD COLUMN reviewed_by VARCHAR,
+    ADD COLUMN reviewed_at TIMESTAMPTZ;
+-- The reviewers list the flagged sentences of a speech.
+CREATE INDEX sentence_flagged ON sentence (speech_uid, index) WHERE review_status = 'flagged';
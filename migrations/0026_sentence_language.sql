@@ -0,0 +1,7 @@
+-- ISO 639-3 code of the language of a sentence, when it is not the language of its
+-- speech, e.g. a speaker answering in another language.
+ALTER TABLE sentence
+    ADD COLUMN language VARCHAR(3);
+-- The speech lists filtered by language also match the speeches with sentences in it.
+CREATE INDEX speech_language ON speech (language);
+CREATE INDEX sentence_language ON sentence (language, speech_uid) WHERE language IS NOT NULL;
@@ -0,0 +1,25 @@
+-- Runs of the operations done in the background, e.g. a bulk import or a maintenance
+-- action, kept so their progress and outcome can be read from any instance.
+CREATE TABLE job (
+    uid UUID PRIMARY KEY,
+    kind VARCHAR NOT NULL,
+    status VARCHAR NOT NULL CHECK (status IN ('running', 'succeeded', 'failed')),
+    -- Items done, out of the total when it is known.
+    processed BIGINT NOT NULL DEFAULT 0,
+    total BIGINT,
+    result JSONB,
+    error VARCHAR,
+    org_uid UUID REFERENCES organization(uid),
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ
+);
+CREATE INDEX job_org_status_started_at ON job (org_uid, status, started_at DESC);
+
+-- The maintenance jobs become jobs of the default organization, the rows they affected
+-- being their result.
+INSERT INTO job (uid, kind, status, result, error, started_at, finished_at)
+SELECT uid, action, status,
+    CASE WHEN affected IS NULL THEN NULL ELSE jsonb_build_object('affected', affected) END,
+    error, started_at, finished_at
+FROM maintenance_job;
+DROP TABLE maintenance_job;
@@ -0,0 +1,10 @@
+-- The keys are claimed by a user, a key sent by another user of the organization being
+-- another key. The keys stored before have no user and are only left to expire.
+ALTER TABLE idempotency_key ADD COLUMN user_id VARCHAR(255) NOT NULL DEFAULT '';
+-- Time the request processing the key started. A key whose request never stored its
+-- response, the client having left or the instance having stopped, is claimed again once
+-- the deadline of the request is over.
+ALTER TABLE idempotency_key ADD COLUMN locked_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+DROP INDEX unique_idempotency_key;
+CREATE UNIQUE INDEX unique_idempotency_key ON idempotency_key
+    (key, user_id, COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
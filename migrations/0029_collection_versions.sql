@@ -0,0 +1,8 @@
+-- Version of the speech and person collections of each organization, bumped by every
+-- change made to them and sent with their lists. A missing row is the version 0.
+CREATE TABLE collection_version (
+    org_uid UUID REFERENCES organization(uid),
+    version BIGINT NOT NULL
+);
+CREATE UNIQUE INDEX unique_collection_version ON collection_version
+    (COALESCE(org_uid, '00000000-0000-0000-0000-000000000000'));
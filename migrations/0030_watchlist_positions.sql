@@ -0,0 +1,11 @@
+-- Transaction which stored each speech. The watchlists report the speeches in the order
+-- they were stored, only up to the oldest transaction still running, so a speech
+-- committed after the check is never placed before the speeches reported.
+ALTER TABLE speech ADD COLUMN transaction_id BIGINT NOT NULL DEFAULT pg_current_xact_id()::TEXT::BIGINT;
+CREATE INDEX speech_org_transaction ON speech (org_uid, transaction_id, uid);
+
+-- Position of the last speech reported by each watchlist, a new watchlist reporting the
+-- speeches stored after its creation. The speeches stored before this migration are
+-- reported to none of the existing watchlists.
+ALTER TABLE watchlist ADD COLUMN last_transaction_id BIGINT NOT NULL DEFAULT pg_current_xact_id()::TEXT::BIGINT;
+ALTER TABLE watchlist ADD COLUMN last_speech_uid UUID NOT NULL DEFAULT 'ffffffff-ffff-ffff-ffff-ffffffffffff';
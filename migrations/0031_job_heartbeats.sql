@@ -0,0 +1,4 @@
+-- Last time a job was known to run, its operation saving it periodically. A running job
+-- left behind for too long, e.g. by an instance that stopped, is failed.
+ALTER TABLE job ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+UPDATE job SET updated_at = COALESCE(finished_at, started_at);
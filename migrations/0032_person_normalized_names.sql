@@ -0,0 +1,10 @@
+-- Names of the persons as compared to find the duplicates, normalized by the application
+-- so the comparison does not depend on the encoding of the database. The persons stored
+-- so far are normalized the same way by SQL.
+ALTER TABLE person
+    ADD COLUMN normalized_name VARCHAR NOT NULL DEFAULT '',
+    ADD COLUMN normalized_first_name VARCHAR NOT NULL DEFAULT '';
+UPDATE person SET
+    normalized_name = REGEXP_REPLACE(TRANSLATE(LOWER(name), 'àâäéèêëîïôöùûüç', 'aaaeeeeiioouuuc'), '[^a-z]', '', 'g'),
+    normalized_first_name = REGEXP_REPLACE(TRANSLATE(LOWER(first_name), 'àâäéèêëîïôöùûüç', 'aaaeeeeiioouuuc'), '[^a-z]', '', 'g');
+CREATE INDEX person_normalized_name ON person (org_uid, normalized_name) WHERE deleted_at IS NULL;
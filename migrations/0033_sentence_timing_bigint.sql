@@ -0,0 +1,5 @@
+-- The positions of the sentences are stored as unsigned 32 bits integers, which do not
+-- fit an INT past 24 days of media.
+ALTER TABLE sentence
+    ALTER COLUMN start_ms TYPE BIGINT,
+    ALTER COLUMN end_ms TYPE BIGINT;
@@ -0,0 +1,149 @@
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::domain::{
+    annotation::AnnotationManager,
+    organization::OrganizationManager,
+    speech::{
+        event::{SpeechEvent, SpeechEventKind},
+        manager::SpeechManager,
+    },
+};
+
+/// Speeches read at once when looking for the ones left without annotations.
+const BACKFILL_BATCH_SIZE: u16 = 100;
+
+/// Annotates again the sentences of the speech of the organization.
+async fn annotate_speech(
+    organization: Option<Uuid>,
+    speech_uid: Uuid,
+    speech_manager: &SpeechManager,
+    annotation_manager: &AnnotationManager,
+) {
+    let speech = match speech_manager
+        .for_organization(organization)
+        .get_speech_by_id(speech_uid)
+        .await
+    {
+        Ok(speech) => speech,
+        Err(e) => {
+            println!(
+                "An error occured while reading the speech {} to annotate: {:?}",
+                speech_uid, e
+            );
+            return;
+        }
+    };
+    if let Err(e) = annotation_manager
+        .for_organization(organization)
+        .annotate_speech(&speech)
+        .await
+    {
+        println!(
+            "An error occured while annotating the speech {}: {:?}",
+            speech_uid, e
+        );
+    }
+}
+
+/// Annotates the speeches of every organization having a sentence not annotated, their
+/// events having been missed. Each speech is tried once, a failing one waiting for the
+/// next backfill.
+async fn annotate_missed_speeches(
+    speech_manager: &SpeechManager,
+    annotation_manager: &AnnotationManager,
+    organization_manager: &OrganizationManager,
+) {
+    let organizations = match organization_manager.get_organizations().await {
+        Ok(organizations) => organizations,
+        Err(e) => {
+            println!(
+                "An error occured while listing organizations to annotate speeches: {:?}",
+                e
+            );
+            return;
+        }
+    };
+    // The default organization comes first.
+    let organizations = std::iter::once(None).chain(
+        organizations
+            .iter()
+            .map(|organization| Some(*organization.uid())),
+    );
+    for organization in organizations {
+        let annotations = annotation_manager.for_organization(organization);
+        let mut after: Option<Uuid> = None;
+        loop {
+            let speeches = match annotations
+                .get_unannotated_speeches(after.as_ref(), BACKFILL_BATCH_SIZE)
+                .await
+            {
+                Ok(speeches) => speeches,
+                Err(e) => {
+                    println!(
+                        "An error occured while looking for the speeches of {:?} to annotate: {:?}",
+                        organization, e
+                    );
+                    break;
+                }
+            };
+            for speech_uid in &speeches {
+                annotate_speech(
+                    organization,
+                    *speech_uid,
+                    speech_manager,
+                    annotation_manager,
+                )
+                .await;
+            }
+            match speeches.last() {
+                Some(last) if speeches.len() == BACKFILL_BATCH_SIZE as usize => after = Some(*last),
+                _ => break,
+            }
+        }
+    }
+}
+
+/// Starts the background task annotating the sentences of the speeches once they are
+/// created or their sentences edited, one speech at a time. The speeches left without
+/// annotations are annotated first, and again whenever the task falls behind and misses
+/// events.
+pub fn start_sentence_annotation(
+    speech_manager: SpeechManager,
+    annotation_manager: AnnotationManager,
+    organization_manager: OrganizationManager,
+    mut events: broadcast::Receiver<SpeechEvent>,
+) {
+    tokio::spawn(async move {
+        annotate_missed_speeches(&speech_manager, &annotation_manager, &organization_manager).await;
+        loop {
+            match events.recv().await {
+                Ok(event) => match event.kind() {
+                    SpeechEventKind::Created | SpeechEventKind::SentencesEdited => {
+                        annotate_speech(
+                            event.organization().copied(),
+                            *event.speech(),
+                            &speech_manager,
+                            &annotation_manager,
+                        )
+                        .await
+                    }
+                    _ => {}
+                },
+                Err(RecvError::Lagged(missed)) => {
+                    println!(
+                        "{} speech events were missed, annotating the speeches left behind",
+                        missed
+                    );
+                    annotate_missed_speeches(
+                        &speech_manager,
+                        &annotation_manager,
+                        &organization_manager,
+                    )
+                    .await;
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    });
+}
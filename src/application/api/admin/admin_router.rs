@@ -0,0 +1,287 @@
+use std::collections::HashMap;
+
+use chrono::Duration;
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{value, Map, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::{
+        api::{
+            error::ErrorCode,
+            router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+            token::{AuthToken, Permissions},
+        },
+        maintenance::start_maintenance,
+    },
+    domain::{
+        job::{Job, JobRepositoryError},
+        maintenance::MaintenanceAction,
+        metrics::{repository_error_summary, MAX_SUMMARY_PERIOD},
+        stats::{ActiveSpeaker, DailyCount, StatsRepositoryError, SystemStats},
+    },
+};
+
+impl From<StatsRepositoryError> for HttpError<'static> {
+    fn from(value: StatsRepositoryError) -> Self {
+        match value {
+            StatsRepositoryError::InternalError(e) => {
+                println!("An internal error occured while reading the Stats: {}", e);
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetErrorSummaryOutput {
+    period: String,
+    from: String,
+    to: String,
+    total: u64,
+    /// Number of errors by class, e.g. `timeout` or `constraint_violation`.
+    errors: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct MaintenanceInput {
+    /// One of `reindex_search`, `recompute_scores`, `vacuum_orphans` or `backup`.
+    action: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetMaintenanceJobOutput {
+    uid: String,
+    action: String,
+    status: String,
+    affected: Option<u64>,
+    error: Option<String>,
+    started_at: String,
+    finished_at: Option<String>,
+}
+
+impl From<Job> for GetMaintenanceJobOutput {
+    fn from(value: Job) -> Self {
+        Self {
+            uid: value.uid.to_string(),
+            action: value.kind.to_string(),
+            status: value.status.to_string(),
+            affected: value
+                .result
+                .as_ref()
+                .and_then(|result| result.get("affected"))
+                .and_then(Value::as_u64),
+            error: value.error,
+            started_at: value.started_at.to_rfc3339(),
+            finished_at: value.finished_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+fn maintenance_job_value(job: Job) -> Result<Value, HttpError<'static>> {
+    value::to_value(GetMaintenanceJobOutput::from(job)).map_err(|e| {
+        println!(
+            "An internal error occured while converting maintenance job to value: {:?}",
+            e
+        );
+        INTERNAL_ERROR
+    })
+}
+
+/// Starts the action in the background, returning its job still running.
+async fn start_maintenance_job(
+    action: MaintenanceAction,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    if action == MaintenanceAction::Backup && !managers.maintenance_manager.backups_enabled() {
+        return Err(HttpError::new(ErrorCode::BackupsUnavailable));
+    }
+    let job = start_maintenance(
+        action,
+        &managers.job_manager,
+        managers.maintenance_manager.clone(),
+        managers.speech_manager.clone(),
+        managers.organization_manager.clone(),
+    )
+    .await?;
+    maintenance_job_value(job)
+}
+
+#[derive(Serialize)]
+struct GetDailyCountOutput {
+    day: String,
+    count: u64,
+}
+
+impl From<DailyCount> for GetDailyCountOutput {
+    fn from(value: DailyCount) -> Self {
+        Self {
+            day: value.day.to_string(),
+            count: value.count,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetActiveSpeakerOutput {
+    uid: String,
+    name: String,
+    first_name: String,
+    speeches: u64,
+    sentences: u64,
+}
+
+impl From<ActiveSpeaker> for GetActiveSpeakerOutput {
+    fn from(value: ActiveSpeaker) -> Self {
+        Self {
+            uid: value.uid.to_string(),
+            name: value.name,
+            first_name: value.first_name,
+            speeches: value.speeches,
+            sentences: value.sentences,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetStatsOutput {
+    persons: u64,
+    speeches: u64,
+    /// Number of speeches by status, e.g. `LIVE` or `VALIDATED`.
+    speeches_by_status: Map<String, Value>,
+    sentences: u64,
+    average_sentences: f64,
+    /// Speeches created on each of the last 30 days, the oldest first.
+    speeches_per_day: Vec<GetDailyCountOutput>,
+    top_speakers: Vec<GetActiveSpeakerOutput>,
+}
+
+impl From<SystemStats> for GetStatsOutput {
+    fn from(value: SystemStats) -> Self {
+        Self {
+            persons: value.persons,
+            speeches: value.speeches(),
+            average_sentences: value.average_sentences(),
+            speeches_by_status: value
+                .speeches_by_status
+                .iter()
+                .map(|(status, count)| (status.to_string(), Value::from(*count)))
+                .collect(),
+            sentences: value.sentences,
+            speeches_per_day: value
+                .speeches_per_day
+                .into_iter()
+                .map(GetDailyCountOutput::from)
+                .collect(),
+            top_speakers: value
+                .top_speakers
+                .into_iter()
+                .map(GetActiveSpeakerOutput::from)
+                .collect(),
+        }
+    }
+}
+
+const INVALID_PERIOD_ERROR: HttpError = HttpError::new(ErrorCode::InvalidPeriodParam);
+
+/// Reads the `period` query parameter, a number of minutes (`15m`) or hours (`1h`) of at
+/// most a day. The last hour is used when missing.
+fn extract_period(
+    query_params: &HashMap<String, String>,
+) -> Result<(String, Duration), HttpError<'static>> {
+    let raw = match query_params.get("period") {
+        Some(raw) => raw,
+        None => return Ok(("1h".to_owned(), Duration::hours(1))),
+    };
+    let (amount, unit) = raw.split_at(raw.len().saturating_sub(1));
+    let amount = amount.parse::<i64>().map_err(|_| INVALID_PERIOD_ERROR)?;
+    let period = match unit {
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        _ => return Err(INVALID_PERIOD_ERROR),
+    };
+    if amount <= 0 || period > MAX_SUMMARY_PERIOD {
+        return Err(INVALID_PERIOD_ERROR);
+    }
+    Ok((raw.clone(), period))
+}
+
+/// Operation routes, reserved to the administrators.
+pub async fn router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    if !token.permissions().contains(&Permissions::Admin) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::GET, ["errors", "summary"]) => {
+            // Tells an overloaded database from clients sending conflicting data.
+            let (raw_period, period) = extract_period(query_params)?;
+            let summary = repository_error_summary(period);
+            let output = GetErrorSummaryOutput {
+                period: raw_period,
+                from: summary.from.to_rfc3339(),
+                to: summary.to.to_rfc3339(),
+                total: summary.counts.iter().map(|(_, count)| count).sum(),
+                errors: summary
+                    .counts
+                    .iter()
+                    .map(|(class, count)| (class.as_str().to_owned(), Value::from(*count)))
+                    .collect(),
+            };
+            Ok(value::to_value(output).map_err(|e| {
+                println!(
+                    "An internal error occured while converting error summary to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::GET, ["stats"]) => {
+            let stats = managers.stats_manager.get_stats().await?;
+            Ok(value::to_value(GetStatsOutput::from(stats)).map_err(|e| {
+                println!(
+                    "An internal error occured while converting stats to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, ["maintenance"]) => {
+            let input: MaintenanceInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let action = MaintenanceAction::try_from(input.action.as_str())
+                .map_err(|_| HttpError::new(ErrorCode::InvalidMaintenanceAction))?;
+            // The action reaches every organization, it runs in the background and its
+            // job is polled.
+            start_maintenance_job(action, managers).await
+        }
+        (&Method::POST, ["backup"]) => {
+            // Same job as the nightly backup, polled as a maintenance job.
+            start_maintenance_job(MaintenanceAction::Backup, managers).await
+        }
+        (&Method::GET, ["maintenance", uid]) => {
+            let uid = Uuid::parse_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            // Also polled as a job, the maintenance jobs keep their own route and errors.
+            let job = match managers.job_manager.get_job(&uid).await {
+                Ok(job) if job.kind.maintenance_action().is_some() => job,
+                Ok(_) | Err(JobRepositoryError::JobNotFound) => {
+                    return Err(HttpError::new(ErrorCode::MaintenanceJobNotFound))
+                }
+                Err(e) => return Err(e.into()),
+            };
+            maintenance_job_value(job)
+        }
+        _ => Err(NOT_FOUND_ERROR),
+    }
+}
@@ -0,0 +1,141 @@
+use hyper::{
+    header::{self, HeaderValue},
+    Method, Response,
+};
+
+/// Kind of content served by a route, each kind being cached by the clients according
+/// to its own `Cache-Control` policy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CacheClass {
+    /// Content which never changes once created, e.g. a revision of a transcript.
+    Immutable,
+    /// Lists and counts, changing as soon as an entity is created or updated.
+    Listing,
+    /// Administration data, never kept by the clients.
+    Admin,
+}
+
+/// Routes whose successful responses carry a cache policy, `*` matching any path
+/// segment. The other routes are left to the clients' defaults.
+const ROUTE_CACHE_CLASSES: &[(Method, &str, CacheClass)] = &[
+    (Method::GET, "speech/*/revisions/*", CacheClass::Immutable),
+    (Method::GET, "speech", CacheClass::Listing),
+    (Method::GET, "speech/count", CacheClass::Listing),
+    (Method::GET, "speech/aggregate", CacheClass::Listing),
+    (Method::GET, "speech/*/revisions", CacheClass::Listing),
+    (
+        Method::GET,
+        "speech/imports/*/conflicts",
+        CacheClass::Listing,
+    ),
+    (Method::GET, "person", CacheClass::Listing),
+    (Method::GET, "person/count", CacheClass::Listing),
+    (Method::GET, "labels", CacheClass::Listing),
+    (Method::GET, "tags", CacheClass::Listing),
+    (Method::GET, "watchlists", CacheClass::Listing),
+    (Method::GET, "opendata/summary", CacheClass::Listing),
+    (Method::GET, "organizations", CacheClass::Admin),
+    (Method::GET, "admin/errors/summary", CacheClass::Admin),
+    (Method::GET, "admin/maintenance/*", CacheClass::Admin),
+    // The download URLs expire, they must not outlive them in a cache.
+    (
+        Method::GET,
+        "speech/*/attachments/*/download",
+        CacheClass::Admin,
+    ),
+    (Method::GET, "organizations/*", CacheClass::Admin),
+    // Each fetch moves the last check of the watchlist, a cached response would hide the
+    // speeches stored since.
+    (Method::GET, "watchlists/*/speeches", CacheClass::Admin),
+];
+
+/// Finds the cache class of the route targeted by the request, `path` being the whole
+/// request path.
+pub fn route_cache_class(method: &Method, path: &str) -> Option<CacheClass> {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .skip(1)
+        .collect::<Vec<&str>>();
+    ROUTE_CACHE_CLASSES
+        .iter()
+        .find(|(route_method, pattern, _)| {
+            route_method == method && {
+                let pattern = pattern.split('/').collect::<Vec<&str>>();
+                pattern.len() == segments.len()
+                    && pattern
+                        .iter()
+                        .zip(&segments)
+                        .all(|(expected, segment)| *expected == "*" || expected == segment)
+            }
+        })
+        .map(|(_, _, class)| *class)
+}
+
+/// `Cache-Control` value sent for each cache class.
+#[derive(Debug, Clone)]
+pub struct CachePolicies {
+    pub immutable: HeaderValue,
+    pub listing: HeaderValue,
+    pub admin: HeaderValue,
+}
+
+impl Default for CachePolicies {
+    fn default() -> Self {
+        Self {
+            // The responses depend on the token, shared caches must not keep them.
+            immutable: HeaderValue::from_static("private, max-age=31536000, immutable"),
+            listing: HeaderValue::from_static("private, max-age=30"),
+            admin: HeaderValue::from_static("no-store"),
+        }
+    }
+}
+
+impl CachePolicies {
+    fn policy(&self, class: CacheClass) -> &HeaderValue {
+        match class {
+            CacheClass::Immutable => &self.immutable,
+            CacheClass::Listing => &self.listing,
+            CacheClass::Admin => &self.admin,
+        }
+    }
+
+    /// Sets the policy of the class on a successful response, unless the route already
+    /// chose its own.
+    pub fn apply<B>(&self, class: Option<CacheClass>, response: &mut Response<B>) {
+        let class = match class {
+            Some(class) if response.status().is_success() => class,
+            _ => return,
+        };
+        if !response.headers().contains_key(header::CACHE_CONTROL) {
+            response
+                .headers_mut()
+                .insert(header::CACHE_CONTROL, self.policy(class).clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+
+    use super::{route_cache_class, CacheClass};
+
+    #[test]
+    fn route_cache_class_matches_the_registry() {
+        assert_eq!(
+            route_cache_class(&Method::GET, "/api/speech/1234/revisions/2"),
+            Some(CacheClass::Immutable)
+        );
+        assert_eq!(
+            route_cache_class(&Method::GET, "/api/speech/"),
+            Some(CacheClass::Listing)
+        );
+        assert_eq!(
+            route_cache_class(&Method::GET, "/api/organizations/1234"),
+            Some(CacheClass::Admin)
+        );
+        assert_eq!(route_cache_class(&Method::GET, "/api/speech/1234"), None);
+        assert_eq!(route_cache_class(&Method::POST, "/api/speech"), None);
+    }
+}
@@ -0,0 +1,70 @@
+use hyper::{header::HeaderMap, Method, Response, StatusCode};
+use serde_json::Value;
+
+use crate::domain::collection::CollectionVersions;
+
+use super::{
+    router::{full, BoxBody},
+    token::{AuthToken, Permissions},
+};
+
+/// Header carrying the version of the speech and person collections a list was read at.
+pub const COLLECTION_VERSION_HEADER: &str = "Collection-Version";
+/// Header sent by a client with the collection version of the list it cached.
+pub const IF_COLLECTION_VERSION_HEADER: &str = "If-Collection-Version";
+
+/// List routes answered with the version of their collection, with the permission
+/// required to read them.
+const VERSIONED_ROUTES: &[(&str, Permissions)] = &[
+    ("speech", Permissions::GetSpeech),
+    ("person", Permissions::GetPerson),
+];
+
+/// Version of the collections listed by the request, `path` being the whole request path,
+/// `versions` those of the organization of the token. Only the lists the token may read
+/// carry a version, the others being refused anyway. A version which cannot be read is
+/// not sent, the list being sent in full.
+pub async fn collection_version(
+    method: &Method,
+    path: &str,
+    token: &AuthToken,
+    versions: &CollectionVersions,
+) -> Option<u64> {
+    let route = path.trim_matches('/').strip_prefix("api/")?;
+    VERSIONED_ROUTES.iter().find(|(versioned, permission)| {
+        method == Method::GET && *versioned == route && token.permissions().contains(permission)
+    })?;
+    match versions.current().await {
+        Ok(version) => Some(version),
+        Err(e) => {
+            println!("Cannot read the collection version: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Whether the client sent the version of the list it cached and it is still current.
+pub fn is_cached_version(headers: &HeaderMap, version: u64) -> bool {
+    headers
+        .get(IF_COLLECTION_VERSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        == Some(version)
+}
+
+/// Answers a client whose cached list is still current.
+pub fn not_modified_response(version: u64) -> Response<BoxBody> {
+    Response::builder()
+        .status(StatusCode::NOT_MODIFIED)
+        .header(COLLECTION_VERSION_HEADER, version)
+        .body(full(""))
+        .expect("Should not fail")
+}
+
+/// Adds the version to the body of a list sent as an object, as its `collectionVersion`
+/// field. The version is sent in a header as well, for the lists sent as an array.
+pub fn add_collection_version_field(body: &mut Value, version: u64) {
+    if let Value::Object(body) = body {
+        body.insert("collectionVersion".to_owned(), Value::from(version));
+    }
+}
@@ -0,0 +1,85 @@
+use std::{future::Future, time::Duration};
+
+use tokio::time::{self, Instant};
+
+use super::{
+    error::{ErrorCode, HttpError},
+    load_shed::LoadClass,
+};
+
+/// Time the requests of each load class may take until their response is ready, in
+/// milliseconds, zero disabling the deadline. The streaming routes have none.
+#[derive(Debug, Clone)]
+pub struct RequestDeadlines {
+    pub interactive: u64,
+    pub analytics: u64,
+    pub export: u64,
+}
+
+impl Default for RequestDeadlines {
+    fn default() -> Self {
+        Self {
+            interactive: 10000,
+            analytics: 60000,
+            export: 60000,
+        }
+    }
+}
+
+impl RequestDeadlines {
+    /// Deadline of a request of the class received now, if any.
+    pub fn deadline(&self, class: LoadClass) -> Option<Instant> {
+        let milliseconds = match class {
+            LoadClass::Interactive => self.interactive,
+            LoadClass::Analytics => self.analytics,
+            LoadClass::Export => self.export,
+            LoadClass::Streaming => return None,
+        };
+        match milliseconds {
+            0 => None,
+            _ => Some(Instant::now() + Duration::from_millis(milliseconds)),
+        }
+    }
+}
+
+/// Fails with a 504 when the future is not done by the deadline, dropping it.
+pub async fn within_deadline<T>(
+    deadline: Option<Instant>,
+    future: impl Future<Output = Result<T, HttpError<'static>>>,
+) -> Result<T, HttpError<'static>> {
+    match deadline {
+        Some(deadline) => time::timeout_at(deadline, future)
+            .await
+            .unwrap_or(Err(HttpError::new(ErrorCode::RequestTimedOut))),
+        None => future.await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::time;
+
+    use super::{within_deadline, RequestDeadlines};
+    use crate::application::api::{error::ErrorCode, load_shed::LoadClass};
+
+    #[tokio::test]
+    async fn late_request_fails_with_a_timeout() {
+        let deadlines = RequestDeadlines {
+            interactive: 10,
+            analytics: 0,
+            export: 10,
+        };
+        assert!(deadlines.deadline(LoadClass::Streaming).is_none());
+        assert!(deadlines.deadline(LoadClass::Analytics).is_none());
+        let late = within_deadline(deadlines.deadline(LoadClass::Interactive), async {
+            time::sleep(Duration::from_millis(100)).await;
+            Ok(())
+        })
+        .await;
+        assert_eq!(late.unwrap_err().code(), ErrorCode::RequestTimedOut);
+        let early = within_deadline(deadlines.deadline(LoadClass::Export), async { Ok(1) }).await;
+        assert_eq!(early.unwrap(), 1);
+    }
+}
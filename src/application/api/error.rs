@@ -0,0 +1,848 @@
+use std::borrow::Cow;
+
+use hyper::{
+    header::{self, HeaderMap, HeaderName},
+    Response,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::{
+    router::{full, BoxBody},
+    validation::{FieldError, LocalizedFieldError},
+};
+
+/// Declares the `ErrorCode` enum together with its catalog entry, so an error code cannot
+/// be returned by the API without being listed by `GET /api/errors`, nor without a message
+/// in every language.
+macro_rules! error_codes {
+    ($($name:ident => ($status:expr, $retryable:expr, $description:expr) {
+        en: $en:expr,
+        fr: $fr:expr,
+    },)*) => {
+        /// Every error code the API can return. The serialized name is the `error` field of
+        /// the error responses and must never change once released.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+        pub enum ErrorCode {
+            $($name,)*
+        }
+
+        impl ErrorCode {
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$name,)*];
+
+            pub const fn as_str(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$name => stringify!($name),)*
+                }
+            }
+
+            /// HTTP status sent along with the error.
+            pub const fn status(&self) -> u16 {
+                match self {
+                    $(ErrorCode::$name => $status,)*
+                }
+            }
+
+            /// Whether the same request may succeed if it is sent again later.
+            pub const fn retryable(&self) -> bool {
+                match self {
+                    $(ErrorCode::$name => $retryable,)*
+                }
+            }
+
+            pub const fn description(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$name => $description,)*
+                }
+            }
+
+            /// Message sent to the client in the `details` field of the error.
+            pub const fn message(&self, language: Language) -> &'static str {
+                match (self, language) {
+                    $((ErrorCode::$name, Language::En) => $en,)*
+                    $((ErrorCode::$name, Language::Fr) => $fr,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    InternalError => (500, true, "An unexpected error occured on the server side.") {
+        en: "An internal error occured, please contact our technical service",
+        fr: "Une erreur interne est survenue, veuillez contacter notre service technique",
+    },
+    ServerOverloaded => (503, true, "Too many requests of the same kind are being served, the request should be sent again after the Retry-After delay.") {
+        en: "Too many requests of this kind are being served, please try again later",
+        fr: "Trop de requêtes de ce type sont en cours de traitement, veuillez réessayer plus tard",
+    },
+    RequestTimedOut => (504, true, "The request was not served within the deadline of its route, its changes may have been applied or not.") {
+        en: "The request took too long to be served, please try again later",
+        fr: "La requête a pris trop de temps à être traitée, veuillez réessayer plus tard",
+    },
+    NotFound => (404, false, "The requested route or resource does not exist.") {
+        en: "The requested resource is not found",
+        fr: "La ressource demandée est introuvable",
+    },
+    AccessDenied => (403, false, "The token does not grant the permission required by the route.") {
+        en: "You cannot access this resource",
+        fr: "Vous ne pouvez pas accéder à cette ressource",
+    },
+    AuthenticationRequired => (401, false, "The route requires a token granting a permission, the request was sent without one.") {
+        en: "You must be authenticated to access this resource",
+        fr: "Vous devez être authentifié pour accéder à cette ressource",
+    },
+    InvalidRoute => (400, false, "The route does not start with /api.") {
+        en: "The route format seems invalid",
+        fr: "Le format de la route semble invalide",
+    },
+    InvalidToken => (400, false, "The bearer token is malformed, expired or not signed by the identity provider.") {
+        en: "The token you provided is invalid",
+        fr: "Le jeton fourni est invalide",
+    },
+    AuthenticationUnavailable => (503, true, "The keys of the identity provider have not been fetched yet, the token cannot be checked.") {
+        en: "The identity provider cannot be reached, please try again later",
+        fr: "Le fournisseur d'identité est injoignable, veuillez réessayer plus tard",
+    },
+    PayloadTooLarge => (413, false, "The request body exceeds the maximum size accepted by the server.") {
+        en: "The request body is larger than the server accepts",
+        fr: "Le corps de la requête dépasse la taille acceptée par le serveur",
+    },
+    UnsupportedMediaType => (415, false, "The request body is not sent with the content type expected by the route.") {
+        en: "The request body is not sent with the content type expected, application/json, application/x-ndjson for the streamed sentences or multipart/form-data for the attachments",
+        fr: "Le corps de la requête n'a pas le type de contenu attendu, application/json, application/x-ndjson pour les phrases diffusées en direct ou multipart/form-data pour les pièces jointes",
+    },
+    InvalidJson => (400, false, "The request body is not valid JSON.") {
+        en: "The request body is not valid JSON",
+        fr: "Le corps de la requête n'est pas un JSON valide",
+    },
+    InvalidFormat => (400, false, "The request body does not match the expected format.") {
+        en: "The body format is invalid. Please refer to the documentation",
+        fr: "Le format du corps de la requête est invalide. Veuillez consulter la documentation",
+    },
+    ValidationFailed => (422, false, "Some fields of the request body are invalid, each one being listed in errors with its path and the reason.") {
+        en: "Some fields of the body are invalid",
+        fr: "Certains champs du corps de la requête sont invalides",
+    },
+    InvalidUid => (400, false, "A uid in the path or in the body is not a valid UUID.") {
+        en: "The uid provided seems invalid, please check it again",
+        fr: "L'identifiant fourni semble invalide, veuillez le vérifier",
+    },
+    InvalidArrayParam => (400, false, "An array query parameter is not formatted as [a,b,c].") {
+        en: "The array query parameter given is an invalid format",
+        fr: "Le paramètre de requête de type tableau a un format invalide",
+    },
+    InvalidPageParam => (400, false, "The page query parameter is not a positive integer.") {
+        en: "The page parameter provided must be an integer > 0",
+        fr: "Le paramètre page doit être un entier > 0",
+    },
+    InvalidCursorParam => (400, false, "The since query parameter is not a cursor returned by a previous poll.") {
+        en: "The since parameter must be the cursor returned by a previous poll",
+        fr: "Le paramètre since doit être le curseur renvoyé par une interrogation précédente",
+    },
+    InvalidTimeoutParam => (400, false, "The timeout query parameter is not a number of seconds such as 30s, of at most 60s.") {
+        en: "The timeout parameter must be a number of seconds such as 30s, of at most 60s",
+        fr: "Le paramètre timeout doit être un nombre de secondes tel que 30s, d'au plus 60s",
+    },
+    InvalidQuantityParam => (400, false, "The quantity query parameter is not a positive integer.") {
+        en: "The quantity parameter provided must be an integer > 0",
+        fr: "Le paramètre quantity doit être un entier > 0",
+    },
+    InvalidCompareParam => (400, false, "The a or b query parameter is missing or not a speech uid.") {
+        en: "The a and b parameters must be the uids of the speeches to compare",
+        fr: "Les paramètres a et b doivent être les uids des discours à comparer",
+    },
+    InvalidContextParam => (400, false, "The context query parameter is not an integer between 0 and 10.") {
+        en: "The context parameter provided must be an integer between 0 and 10",
+        fr: "Le paramètre context doit être un entier compris entre 0 et 10",
+    },
+    InvalidFieldsParam => (400, false, "The fields query parameter is not a list of the fields sent by the route, such as uid,name,date.") {
+        en: "The fields parameter provided must be a list of the fields of the response, such as uid,name,date",
+        fr: "Le paramètre fields doit être une liste de champs de la réponse, tels que uid,name,date",
+    },
+    InvalidSearchParam => (400, false, "The q query parameter holding the text searched is missing or empty.") {
+        en: "The q parameter must give the text to search",
+        fr: "Le paramètre q doit donner le texte à rechercher",
+    },
+    InvalidDateRangeParam => (400, false, "The from or to query parameter is not a date such as 2024-01-01, or from is after to.") {
+        en: "The from and to parameters must be dates such as 2024-01-01, from not being after to",
+        fr: "Les paramètres from et to doivent être des dates telles que 2024-01-01, from ne pouvant être après to",
+    },
+    InvalidFilterParam => (400, false, "The filter query parameter is not a list of conditions such as media==TF1;date>=2024-01-01.") {
+        en: "The filter parameter provided must be a list of conditions such as media==TF1;date>=2024-01-01",
+        fr: "Le paramètre filter doit être une liste de conditions telle que media==TF1;date>=2024-01-01",
+    },
+    InvalidLanguageParam => (400, false, "The language query parameter is not an ISO 639-3 code such as fra or eng.") {
+        en: "The language parameter provided must be an ISO 639-3 code such as fra or eng",
+        fr: "Le paramètre language doit être un code ISO 639-3 tel que fra ou eng",
+    },
+    InvalidGranularityParam => (400, false, "The granularity query parameter is not one of week, month, quarter or year.") {
+        en: "The granularity parameter provided must be one of week, month, quarter or year",
+        fr: "Le paramètre granularity doit valoir week, month, quarter ou year",
+    },
+    InvalidGroupByParam => (400, false, "The group_by query parameter is missing or not one of media, speaker or month.") {
+        en: "The group_by parameter provided must be one of media, speaker or month",
+        fr: "Le paramètre group_by doit valoir media, speaker ou month",
+    },
+    InvalidIncludeDeletedParam => (400, false, "The include_deleted query parameter is not a boolean.") {
+        en: "The include_deleted parameter provided must be true or false",
+        fr: "Le paramètre include_deleted doit valoir true ou false",
+    },
+    InvalidIncludeModeratorsParam => (400, false, "The include_moderators query parameter is not a boolean.") {
+        en: "The include_moderators parameter provided must be true or false",
+        fr: "Le paramètre include_moderators doit valoir true ou false",
+    },
+    InvalidStatusParam => (400, false, "The status query parameter is not one of LIVE, PENDING or VALIDATED.") {
+        en: "The status parameter provided must be LIVE, PENDING or VALIDATED",
+        fr: "Le paramètre status doit valoir LIVE, PENDING ou VALIDATED",
+    },
+    InvalidPeriodParam => (400, false, "The period query parameter is not a duration such as 15m or 1h, of at most 24h.") {
+        en: "The period parameter must be a duration such as 15m or 1h, of at most 24h",
+        fr: "Le paramètre period doit être une durée telle que 15m ou 1h, d'au plus 24h",
+    },
+    InvalidReviewStatusParam => (400, false, "The review_status query parameter is not one of unreviewed, approved or flagged.") {
+        en: "The review_status parameter provided must be unreviewed, approved or flagged",
+        fr: "Le paramètre review_status doit valoir unreviewed, approved ou flagged",
+    },
+    InvalidAsyncParam => (400, false, "The async query parameter is not a boolean.") {
+        en: "The async parameter must be true or false",
+        fr: "Le paramètre async doit valoir true ou false",
+    },
+    InvalidCreatePersonsParam => (400, false, "The create_persons query parameter is not a boolean.") {
+        en: "The create_persons parameter must be true or false",
+        fr: "Le paramètre create_persons doit valoir true ou false",
+    },
+    InvalidOnConflictParam => (400, false, "The on_conflict query parameter is not one of ignore or update.") {
+        en: "The on_conflict parameter provided must be ignore or update",
+        fr: "Le paramètre on_conflict doit valoir ignore ou update",
+    },
+    InvalidStrategyParam => (400, false, "The strategy query parameter is not one of restrict, anonymize or cascade.") {
+        en: "The strategy parameter provided must be restrict, anonymize or cascade",
+        fr: "Le paramètre strategy doit valoir restrict, anonymize ou cascade",
+    },
+    InvalidJobStatusParam => (400, false, "The status query parameter is not one of running, succeeded or failed.") {
+        en: "The status parameter provided must be running, succeeded or failed",
+        fr: "Le paramètre status doit valoir running, succeeded ou failed",
+    },
+    InvalidMaintenanceAction => (400, false, "The maintenance action is not one of reindex_search, recompute_scores, vacuum_orphans or backup.") {
+        en: "The action must be one of reindex_search, recompute_scores, vacuum_orphans or backup",
+        fr: "L'action doit être reindex_search, recompute_scores, vacuum_orphans ou backup",
+    },
+    BackupsUnavailable => (503, false, "No backup target is configured on this server.") {
+        en: "Backups are not enabled on this server",
+        fr: "Les sauvegardes ne sont pas activées sur ce serveur",
+    },
+    InvalidSpeakerRole => (400, false, "A speaker role is not one of moderator, panelist or guest.") {
+        en: "The role provided must be one of moderator, panelist or guest",
+        fr: "Le rôle fourni doit être moderator, panelist ou guest",
+    },
+    InvalidDate => (400, false, "A date is not a valid ISO 8601 date.") {
+        en: "The date provided is invalid. Please be sure to provide an ISO 8601 date",
+        fr: "La date fournie est invalide. Veuillez fournir une date ISO 8601",
+    },
+    InvalidBirthDate => (400, false, "The birth date is not a valid ISO 8601 date.") {
+        en: "The birth date supplied has an invalid format",
+        fr: "La date de naissance fournie a un format invalide",
+    },
+    InvalidSpeakersUid => (400, false, "One of the speakers uid is not a valid UUID.") {
+        en: "One of the speaker uids provided is invalid or is not one of the speakers",
+        fr: "L'un des identifiants d'orateur fournis est invalide ou n'est pas celui d'un orateur",
+    },
+    InvalidExportFormat => (400, false, "The export format is not supported by the exported resource.") {
+        en: "The export format is not supported, the speeches are exported as txt, srt or json and the persons as csv",
+        fr: "Le format d'export n'est pas pris en charge, les discours sont exportés en txt, srt ou json et les personnes en csv",
+    },
+    InvalidLabelName => (400, false, "The label name is empty.") {
+        en: "The label name cannot be empty",
+        fr: "Le nom de l'étiquette ne peut pas être vide",
+    },
+    InvalidLanguage => (400, false, "The language code is not valid.") {
+        en: "The language provided must be a BCP 47 tag or an ISO 639 code such as fr, pt-BR or fra",
+        fr: "La langue fournie doit être une étiquette BCP 47 ou un code ISO 639 tel que fr, pt-BR ou fra",
+    },
+    InvalidLangParam => (400, false, "The lang query parameter is not a known BCP 47 tag or ISO 639-3 code.") {
+        en: "The lang parameter must be a language such as fr, pt-BR or fra",
+        fr: "Le paramètre lang doit être une langue telle que fr, pt-BR ou fra",
+    },
+    InvalidTimestamps => (400, false, "A sentence has only one of start and end, or ends before it starts.") {
+        en: "A sentence must have both a start and an end, the end not being before the start",
+        fr: "Une phrase doit avoir un début et une fin, la fin ne précédant pas le début",
+    },
+    InvalidRevision => (400, false, "The revision number is not a positive integer.") {
+        en: "The revision provided must be an integer > 0",
+        fr: "La révision fournie doit être un entier > 0",
+    },
+    InvalidIdempotencyKey => (400, false, "The Idempotency-Key header is empty or longer than 255 characters.") {
+        en: "The Idempotency-Key header must hold between 1 and 255 visible ASCII characters",
+        fr: "L'en-tête Idempotency-Key doit contenir entre 1 et 255 caractères ASCII visibles",
+    },
+    IdempotencyKeyReused => (422, false, "The Idempotency-Key has already been used with a different request.") {
+        en: "The Idempotency-Key has already been used with a different request",
+        fr: "L'Idempotency-Key a déjà été utilisée avec une autre requête",
+    },
+    IdempotencyKeyInProgress => (409, true, "The first request sent with the Idempotency-Key is still being processed. The key is claimed again once the deadline of that request is over.") {
+        en: "The request sent first with this Idempotency-Key is still being processed",
+        fr: "La première requête envoyée avec cette Idempotency-Key est toujours en cours de traitement",
+    },
+    VersionRequired => (428, false, "An update must give the version of the resource it was made from, in the If-Match header or in the version field of the body.") {
+        en: "The version of the resource you updated must be given in the If-Match header or in the version field",
+        fr: "La version de la ressource modifiée doit être donnée dans l'en-tête If-Match ou dans le champ version",
+    },
+    InvalidVersion => (400, false, "The If-Match header or the version field is not a version number.") {
+        en: "The version given is invalid",
+        fr: "La version fournie est invalide",
+    },
+    VersionMismatch => (412, false, "The resource was updated since the version given in the If-Match header, it must be read again before updating it.") {
+        en: "The resource has been updated by someone else since you read it",
+        fr: "La ressource a été modifiée par quelqu'un d'autre depuis que vous l'avez lue",
+    },
+    VersionConflict => (409, false, "The resource was updated since the version given in the version field, it must be read again before updating it.") {
+        en: "The resource has been updated by someone else since you read it",
+        fr: "La ressource a été modifiée par quelqu'un d'autre depuis que vous l'avez lue",
+    },
+    PersonNotFound => (404, false, "The person does not exist or has been deleted.") {
+        en: "The person requested is not found",
+        fr: "La personne demandée est introuvable",
+    },
+    PersonAlreadyExists => (409, false, "A person with the same uid already exists.") {
+        en: "The person you try to create already exists",
+        fr: "La personne que vous essayez de créer existe déjà",
+    },
+    PersonReferenced => (409, false, "The person speaks in speeches not deleted, the context gives their number. The strategy query parameter anonymizes or removes their sentences.") {
+        en: "The person still speaks in some speeches and cannot be deleted",
+        fr: "La personne parle encore dans des discours et ne peut pas être supprimée",
+    },
+    PersonMergedIntoItself => (400, false, "The person and the duplicate to merge into it are the same.") {
+        en: "A person cannot be merged into itself",
+        fr: "Une personne ne peut pas être fusionnée avec elle-même",
+    },
+    SpeechNotFound => (404, false, "The speech does not exist or has been deleted.") {
+        en: "The speech requested is not found",
+        fr: "Le discours demandé est introuvable",
+    },
+    SpeechAlreadyExists => (409, false, "A speech with the same uid already exists.") {
+        en: "The speech you try to create already exists",
+        fr: "Le discours que vous essayez de créer existe déjà",
+    },
+    SpeakerNotFound => (404, false, "The person is not a speaker of the speech.") {
+        en: "The person is not a speaker of this speech",
+        fr: "La personne n'est pas un orateur de ce discours",
+    },
+    UnknownSpeakers => (422, false, "Some speakers of the speech or of its sentences are not persons of the organization, their uids being given in the context.") {
+        en: "Some speakers of the speech do not exist",
+        fr: "Certains orateurs du discours n'existent pas",
+    },
+    UnknownOrators => (422, false, "Some orators of the imported sitting match no person of the organization, their names being given in the context. They are created with create_persons=true.") {
+        en: "Some orators of the sitting are not known persons",
+        fr: "Certains orateurs de la séance ne sont pas des personnes connues",
+    },
+    SpeakerAlreadyExists => (409, false, "The person is already a speaker of the speech.") {
+        en: "The person is already a speaker of this speech",
+        fr: "La personne est déjà un orateur de ce discours",
+    },
+    SpeakerHasSentences => (409, false, "The speaker still has sentences in the speech, they must be reassigned before removing the speaker.") {
+        en: "The speaker still has sentences in this speech, reassign them first",
+        fr: "L'orateur a encore des phrases dans ce discours, réattribuez-les d'abord",
+    },
+    RevisionNotFound => (404, false, "The speech has no revision with this number.") {
+        en: "The revision requested is not found for this speech",
+        fr: "La révision demandée est introuvable pour ce discours",
+    },
+    SentenceNotFound => (404, false, "The speech has no sentence at the index or with the uid given.") {
+        en: "The speech has no sentence at this index or with this uid",
+        fr: "Le discours n'a pas de phrase à cet index ou avec cet identifiant",
+    },
+    ImportConflictNotFound => (404, false, "The import has no conflict with this uid, or it has been applied.") {
+        en: "The import has no conflict with this uid",
+        fr: "L'import n'a pas de conflit avec cet identifiant",
+    },
+    InvalidImportResolution => (400, false, "The resolution is not use_existing, create_new or skip, or does not fit the conflict.") {
+        en: "The resolution must be use_existing, create_new or skip, and fit the conflict",
+        fr: "La résolution doit être use_existing, create_new ou skip, et correspondre au conflit",
+    },
+    SpeechNotPending => (409, false, "The speech is not pending review.") {
+        en: "Only a speech pending review can be validated",
+        fr: "Seul un discours en attente de relecture peut être validé",
+    },
+    SpeechNotLive => (409, false, "The speech is not live: it was not created as live or has been finalized.") {
+        en: "The speech is not live or has already been finalized",
+        fr: "Le discours n'est pas en direct ou a déjà été finalisé",
+    },
+    LiveStreamInProgress => (409, true, "Another producer is already streaming the sentences of the speech.") {
+        en: "The sentences of this speech are already being streamed",
+        fr: "Les phrases de ce discours sont déjà diffusées en direct",
+    },
+    WebSocketRequired => (426, false, "The route only serves WebSocket connections, the request must ask for the upgrade.") {
+        en: "This route must be opened as a WebSocket",
+        fr: "Cette route doit être ouverte en WebSocket",
+    },
+    SentenceOutOfOrder => (409, false, "A streamed sentence is not the next sentence of the speech, the context gives the seq expected.") {
+        en: "The streamed sentence is not the next sentence of the speech",
+        fr: "La phrase diffusée n'est pas la phrase suivante du discours",
+    },
+    UnsupportedTranslationLanguage => (400, false, "The translation provider does not support the requested language.") {
+        en: "The translation provider does not support this language",
+        fr: "Le fournisseur de traduction ne prend pas en charge cette langue",
+    },
+    TranslationUnavailable => (503, false, "No translation provider is configured on this server.") {
+        en: "Translations are not enabled on this server",
+        fr: "Les traductions ne sont pas activées sur ce serveur",
+    },
+    TranslationFailed => (502, true, "The translation provider failed to translate the speech.") {
+        en: "The speech could not be translated, please try again later",
+        fr: "Le discours n'a pas pu être traduit, veuillez réessayer plus tard",
+    },
+    PiiNotReviewed => (409, false, "The speech has personal data flags waiting for review, the context gives their number.") {
+        en: "The personal data flags of the speech must be reviewed before validating it",
+        fr: "Les signalements de données personnelles du discours doivent être relus avant de le valider",
+    },
+    SentencesFlagged => (409, false, "The speech has sentences flagged by a reviewer, the context gives their number.") {
+        en: "The flagged sentences of the speech must be fixed before validating it",
+        fr: "Les phrases signalées du discours doivent être corrigées avant de le valider",
+    },
+    PiiFlagNotFound => (404, false, "The speech has no personal data flag with this uid.") {
+        en: "The speech has no personal data flag with this uid",
+        fr: "Le discours n'a pas de signalement de données personnelles avec cet identifiant",
+    },
+    PiiDetectionFailed => (502, true, "The personal data detector failed to screen the sentences, the speech was not stored.") {
+        en: "The sentences could not be screened for personal data, please try again later",
+        fr: "Les phrases n'ont pas pu être analysées à la recherche de données personnelles, veuillez réessayer plus tard",
+    },
+    AttachmentNotFound => (404, false, "The speech has no attachment with this uid.") {
+        en: "The speech has no attachment with this uid",
+        fr: "Le discours n'a pas de pièce jointe avec cet identifiant",
+    },
+    AttachmentsUnavailable => (503, false, "No attachment storage is configured on this server.") {
+        en: "Attachments are not enabled on this server",
+        fr: "Les pièces jointes ne sont pas activées sur ce serveur",
+    },
+    AttachmentStorageFailed => (502, true, "The attachment storage failed to store or sign the attachment, an upload is not kept.") {
+        en: "The attachment storage cannot be reached, please try again later",
+        fr: "Le stockage des pièces jointes est injoignable, veuillez réessayer plus tard",
+    },
+    SegmentNotFound => (404, false, "The speech has no segment with this uid.") {
+        en: "The speech has no segment with this uid",
+        fr: "Le discours n'a pas de segment avec cet identifiant",
+    },
+    SegmentOutOfBounds => (422, false, "The segment ends before it starts or after the last sentence of the speech.") {
+        en: "The segment must cover sentences of the speech, from its start to its end",
+        fr: "Le segment doit couvrir des phrases du discours, de son début à sa fin",
+    },
+    SegmentOverlap => (409, false, "The segment shares sentences with another segment of the speech, whose uid is given in the context.") {
+        en: "The segment overlaps another segment of the speech",
+        fr: "Le segment chevauche un autre segment du discours",
+    },
+    LabelNotFound => (404, false, "The label does not exist or is not visible to the user.") {
+        en: "The label requested is not found",
+        fr: "L'étiquette demandée est introuvable",
+    },
+    LabelAlreadyExists => (409, false, "A label with the same name already exists.") {
+        en: "The label you try to create already exists",
+        fr: "L'étiquette que vous essayez de créer existe déjà",
+    },
+    InvalidTagName => (400, false, "The tag name is empty or longer than 100 characters.") {
+        en: "The tag name must contain between 1 and 100 characters",
+        fr: "Le nom du tag doit contenir entre 1 et 100 caractères",
+    },
+    TagNotFound => (404, false, "The tag, or the parent tag given, does not exist.") {
+        en: "The tag requested, or its parent, is not found",
+        fr: "Le tag demandé, ou son parent, est introuvable",
+    },
+    TagAlreadyExists => (409, false, "A tag with the same name already exists.") {
+        en: "The tag you try to create already exists",
+        fr: "Le tag que vous essayez de créer existe déjà",
+    },
+    WatchlistNotFound => (404, false, "The watchlist does not exist or belongs to another user.") {
+        en: "The watchlist requested is not found",
+        fr: "La liste de suivi demandée est introuvable",
+    },
+    WatchlistAlreadyExists => (409, false, "The user already has a watchlist with the same name.") {
+        en: "You already have a watchlist with this name",
+        fr: "Vous avez déjà une liste de suivi portant ce nom",
+    },
+    MaintenanceJobNotFound => (404, false, "The maintenance job does not exist.") {
+        en: "The maintenance job requested is not found",
+        fr: "La tâche de maintenance demandée est introuvable",
+    },
+    JobNotFound => (404, false, "The job does not exist or belongs to another organization.") {
+        en: "The job requested is not found",
+        fr: "La tâche demandée est introuvable",
+    },
+    InvalidOrganizationName => (400, false, "The organization name is empty or longer than 100 characters.") {
+        en: "The organization name must be between 1 and 100 characters",
+        fr: "Le nom de l'organisation doit contenir entre 1 et 100 caractères",
+    },
+    OrganizationNotFound => (404, false, "The organization does not exist.") {
+        en: "The organization requested is not found",
+        fr: "L'organisation demandée est introuvable",
+    },
+    OrganizationAlreadyExists => (409, false, "An organization with the same name already exists.") {
+        en: "An organization with this name already exists",
+        fr: "Une organisation portant ce nom existe déjà",
+    },
+    OrganizationNotEmpty => (409, false, "The organization still owns persons, speeches or labels.") {
+        en: "The organization still owns data, delete it first",
+        fr: "L'organisation possède encore des données, supprimez-les d'abord",
+    },
+}
+
+/// Splits the value of an `Accept` or `Accept-Language` header into its ranges along with
+/// their quality, `1` when not given and `0` when invalid.
+fn weighted_ranges(header: Option<&str>) -> impl Iterator<Item = (&str, f32)> {
+    header.unwrap_or_default().split(',').map(|range| {
+        let mut parts = range.split(';');
+        let range = parts.next().unwrap_or_default().trim();
+        let quality = parts
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())
+            .unwrap_or(0.0);
+        (range, quality)
+    })
+}
+
+/// Languages the error messages are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    En,
+    Fr,
+}
+
+impl Language {
+    /// Picks the language preferred by the client among those supported, from the value of
+    /// its `Accept-Language` header (`fr-CH, fr;q=0.9, en;q=0.8`). English is used when the
+    /// header is missing or names no supported language.
+    pub fn from_accept_language(accept_language: Option<&str>) -> Self {
+        let mut preferred = (Language::default(), 0.0);
+        for (tag, quality) in weighted_ranges(accept_language) {
+            let language = match tag.split('-').next().unwrap_or_default() {
+                primary if primary.eq_ignore_ascii_case("en") => Language::En,
+                primary if primary.eq_ignore_ascii_case("fr") => Language::Fr,
+                _ => continue,
+            };
+            if quality > preferred.1 {
+                preferred = (language, quality);
+            }
+        }
+        preferred.0
+    }
+}
+
+/// Format of the error responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// RFC 7807 problem details, sent as `application/problem+json`.
+    #[default]
+    Problem,
+    /// Envelope sent before the problem details, kept for the clients accepting
+    /// `application/json` only.
+    Legacy,
+}
+
+impl ErrorFormat {
+    /// Picks the format from the value of the `Accept` header. The legacy envelope is only
+    /// sent when `application/json` is preferred to `application/problem+json`, the
+    /// clients accepting any type getting the problem details.
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        let (mut problem, mut json) = (None, None);
+        for (media_type, quality) in weighted_ranges(accept) {
+            if media_type.eq_ignore_ascii_case("application/problem+json") {
+                problem = Some(quality);
+            } else if media_type.eq_ignore_ascii_case("application/json") {
+                json = Some(quality);
+            }
+        }
+        match (problem, json) {
+            (None, Some(json)) if json > 0.0 => ErrorFormat::Legacy,
+            (Some(problem), Some(json)) if json > problem => ErrorFormat::Legacy,
+            _ => ErrorFormat::Problem,
+        }
+    }
+}
+
+/// How the errors of a request are written, negotiated from its headers.
+#[derive(Debug, Clone)]
+pub struct ErrorRendering {
+    pub language: Language,
+    pub format: ErrorFormat,
+    pub request_id: String,
+    /// Path of the request, the `instance` of the problem details.
+    pub instance: String,
+}
+
+impl ErrorRendering {
+    pub fn from_request(headers: &HeaderMap, path: &str, request_id: &str) -> Self {
+        let value = |name: HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+        Self {
+            language: Language::from_accept_language(value(header::ACCEPT_LANGUAGE)),
+            format: ErrorFormat::from_accept(value(header::ACCEPT)),
+            request_id: request_id.to_owned(),
+            instance: path.to_owned(),
+        }
+    }
+}
+
+/// Error returned by a route, sent to the client as RFC 7807 problem details:
+///
+/// ```json
+/// {
+///     "type": "/api/errors/ValidationFailed",
+///     "title": "Some fields of the body are invalid",
+///     "status": 422,
+///     "detail": "...",
+///     "instance": "/api/speech",
+///     "error": "ValidationFailed",
+///     "errors": [{ "field": "sentences[3].speaker", "message": "..." }],
+///     "request_id": "0b8f1c2e-..."
+/// }
+/// ```
+///
+/// `type` is the catalog entry of the error, `error` its stable code listed by
+/// `GET /api/errors`, and `title` its message in the language asked with
+/// `Accept-Language`. `detail` gives the specifics of this occurrence, e.g. the parser
+/// error of an invalid body, untranslated and only sent when known; `errors` lists the
+/// invalid fields of the body, with a message in the same language. `instance` is the
+/// path of the request and `request_id`, also sent in the `X-Request-Id` header,
+/// identifies it in the server logs.
+///
+/// The clients preferring `application/json` in their `Accept` header get the legacy
+/// envelope instead:
+///
+/// ```json
+/// {
+///     "code": 422,
+///     "error": "ValidationFailed",
+///     "details": "Some fields of the body are invalid",
+///     "context": "...",
+///     "errors": [{ "field": "sentences[3].speaker", "message": "..." }],
+///     "request_id": "0b8f1c2e-..."
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HttpError<'a> {
+    error: ErrorCode,
+    context: Option<Cow<'a, str>>,
+    errors: Vec<FieldError>,
+}
+
+impl<'a> HttpError<'a> {
+    pub const fn new(error: ErrorCode) -> Self {
+        HttpError {
+            error,
+            context: None,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Builds an error along with the specifics of this occurrence.
+    pub fn with_context(error: ErrorCode, context: String) -> HttpError<'static> {
+        HttpError {
+            error,
+            context: Some(Cow::Owned(context)),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Builds an error listing the invalid fields of the body.
+    pub fn with_field_errors(error: ErrorCode, errors: Vec<FieldError>) -> HttpError<'static> {
+        HttpError {
+            error,
+            context: None,
+            errors,
+        }
+    }
+
+    /// Adds to the error specifics known by the caller, after those already given.
+    pub fn add_context(self, context: &str) -> HttpError<'static> {
+        let context = match self.context {
+            Some(previous) => format!("{}. {}", previous, context),
+            None => context.to_owned(),
+        };
+        HttpError {
+            error: self.error,
+            context: Some(Cow::Owned(context)),
+            errors: self.errors,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.error
+    }
+
+    /// Builds the response sent to the client, in the format and the language negotiated.
+    pub fn into_response(self, rendering: &ErrorRendering) -> Response<BoxBody> {
+        let (content_type, body) = match rendering.format {
+            ErrorFormat::Problem => (
+                "application/problem+json",
+                self.to_problem_json(
+                    rendering.language,
+                    &rendering.request_id,
+                    &rendering.instance,
+                ),
+            ),
+            ErrorFormat::Legacy => (
+                "application/json",
+                self.to_json(rendering.language, &rendering.request_id),
+            ),
+        };
+        let mut response = Response::builder()
+            .status(self.error.status())
+            .header(header::CONTENT_TYPE, content_type);
+        if self.error == ErrorCode::AuthenticationRequired {
+            response = response.header(header::WWW_AUTHENTICATE, "Bearer");
+        }
+        response
+            .body(full(body.to_string()))
+            .expect("Should not fail")
+    }
+
+    /// Legacy envelope sent to the client, e.g. within a WebSocket frame once the
+    /// connection is upgraded.
+    pub fn to_json(&self, language: Language, request_id: &str) -> Value {
+        let envelope = ErrorEnvelope {
+            code: self.error.status(),
+            error: self.error,
+            details: self.error.message(language),
+            context: self.context.as_deref(),
+            errors: self.localized_errors(language),
+            request_id,
+        };
+        serde_json::to_value(&envelope).expect("Should not fail")
+    }
+
+    /// Invalid fields of the body, their messages in the language of the client.
+    fn localized_errors(&self, language: Language) -> Vec<LocalizedFieldError> {
+        self.errors
+            .iter()
+            .map(|error| error.localize(language))
+            .collect()
+    }
+
+    /// Problem details of the error, `instance` being the path of the request.
+    pub fn to_problem_json(&self, language: Language, request_id: &str, instance: &str) -> Value {
+        let problem = ProblemDetails {
+            problem_type: error_type(self.error),
+            title: self.error.message(language),
+            status: self.error.status(),
+            detail: self.context.as_deref(),
+            instance,
+            error: self.error,
+            errors: self.localized_errors(language),
+            request_id,
+        };
+        serde_json::to_value(&problem).expect("Should not fail")
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorEnvelope<'a> {
+    code: u16,
+    error: ErrorCode,
+    details: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<LocalizedFieldError>,
+    request_id: &'a str,
+}
+
+#[derive(Serialize)]
+struct ProblemDetails<'a> {
+    #[serde(rename = "type")]
+    problem_type: String,
+    title: &'static str,
+    status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<&'a str>,
+    instance: &'a str,
+    error: ErrorCode,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    errors: Vec<LocalizedFieldError>,
+    request_id: &'a str,
+}
+
+/// Type of the problem details of an error, the path of its catalog entry.
+fn error_type(code: ErrorCode) -> String {
+    format!("/api/errors/{}", code.as_str())
+}
+
+pub const INTERNAL_ERROR: HttpError = HttpError::new(ErrorCode::InternalError);
+
+pub const NOT_FOUND_ERROR: HttpError = HttpError::new(ErrorCode::NotFound);
+
+pub const ACCESS_DENIED_ERROR: HttpError = HttpError::new(ErrorCode::AccessDenied);
+
+fn catalog_entry(code: &ErrorCode) -> Value {
+    json!({
+        "code": code.as_str(),
+        "type": error_type(*code),
+        "status": code.status(),
+        "description": code.description(),
+        "retryable": code.retryable(),
+        "messages": {
+            "en": code.message(Language::En),
+            "fr": code.message(Language::Fr),
+        },
+    })
+}
+
+/// Catalog of the error codes, served by `GET /api/errors`.
+pub fn error_catalog() -> Value {
+    Value::Array(ErrorCode::ALL.iter().map(catalog_entry).collect())
+}
+
+/// Catalog entry of an error code, served by `GET /api/errors/{code}` which is the `type`
+/// of its problem details.
+pub fn error_catalog_entry(code: &str) -> Option<Value> {
+    ErrorCode::ALL
+        .iter()
+        .find(|known| known.as_str() == code)
+        .map(catalog_entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorCode, ErrorFormat, HttpError, Language};
+
+    #[test]
+    fn accept_language_picks_the_preferred_supported_language() {
+        assert_eq!(Language::from_accept_language(None), Language::En);
+        assert_eq!(
+            Language::from_accept_language(Some("fr-CH, fr;q=0.9, en;q=0.8")),
+            Language::Fr
+        );
+        assert_eq!(
+            Language::from_accept_language(Some("de, en;q=0.5, fr;q=0.7")),
+            Language::Fr
+        );
+        assert_eq!(
+            Language::from_accept_language(Some("fr;q=0, de")),
+            Language::En
+        );
+        assert_eq!(
+            ErrorCode::PersonNotFound.message(Language::Fr),
+            "La personne demandée est introuvable"
+        );
+    }
+
+    #[test]
+    fn problem_details_are_sent_unless_json_is_preferred() {
+        assert_eq!(ErrorFormat::from_accept(None), ErrorFormat::Problem);
+        assert_eq!(ErrorFormat::from_accept(Some("*/*")), ErrorFormat::Problem);
+        assert_eq!(
+            ErrorFormat::from_accept(Some("application/json")),
+            ErrorFormat::Legacy
+        );
+        assert_eq!(
+            ErrorFormat::from_accept(Some("application/problem+json, application/json;q=0.9")),
+            ErrorFormat::Problem
+        );
+        let error = HttpError::with_context(ErrorCode::PersonNotFound, "Deleted".to_owned());
+        let problem = error.to_problem_json(Language::En, "42", "/api/person/1");
+        assert_eq!(problem["type"], "/api/errors/PersonNotFound");
+        assert_eq!(problem["status"], 404);
+        assert_eq!(problem["detail"], "Deleted");
+        assert_eq!(problem["instance"], "/api/person/1");
+        assert_eq!(problem["request_id"], "42");
+    }
+}
@@ -0,0 +1,115 @@
+use std::{collections::HashMap, time::Duration};
+
+use hyper::Method;
+use serde::Serialize;
+use serde_json::{value, Value};
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        speech::speech_router::GetSpeechEvent,
+        token::{AuthToken, Permissions},
+    },
+    domain::speech::event::SpeechEvent,
+};
+
+/// Longest a poll waits for events, below the timeouts of the usual proxies.
+const MAX_POLL_TIMEOUT: Duration = Duration::from_secs(60);
+
+const DEFAULT_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+const INVALID_TIMEOUT_ERROR: HttpError = HttpError::new(ErrorCode::InvalidTimeoutParam);
+
+#[derive(Serialize)]
+struct GetPolledEvent {
+    cursor: u64,
+    event: String,
+    #[serde(flatten)]
+    data: GetSpeechEvent,
+}
+
+impl From<(u64, SpeechEvent)> for GetPolledEvent {
+    fn from((cursor, event): (u64, SpeechEvent)) -> Self {
+        Self {
+            cursor,
+            event: event.kind().to_string(),
+            data: event.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetPollOutput {
+    /// Cursor to send as `since` with the next poll.
+    cursor: u64,
+    /// Whether events have been forgotten since the cursor sent, the client must reload
+    /// the speeches it follows.
+    missed: bool,
+    events: Vec<GetPolledEvent>,
+}
+
+/// Reads the `timeout` query parameter, a number of seconds (`30s` or `30`) of at most
+/// `MAX_POLL_TIMEOUT`.
+fn extract_timeout(query_params: &HashMap<String, String>) -> Result<Duration, HttpError<'static>> {
+    let raw = match query_params.get("timeout") {
+        Some(raw) => raw,
+        None => return Ok(DEFAULT_POLL_TIMEOUT),
+    };
+    let seconds = raw
+        .strip_suffix('s')
+        .unwrap_or(raw)
+        .parse::<u64>()
+        .map_err(|_| INVALID_TIMEOUT_ERROR)?;
+    let timeout = Duration::from_secs(seconds);
+    if timeout > MAX_POLL_TIMEOUT {
+        return Err(INVALID_TIMEOUT_ERROR);
+    }
+    Ok(timeout)
+}
+
+/// Long polling of the speech lifecycle events, for the clients which cannot keep the
+/// connection of `GET /api/speech/events` open.
+pub async fn router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    method: &Method,
+    token: &AuthToken,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::GET, ["poll"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let since = match query_params.get("since") {
+                Some(since) => Some(
+                    since
+                        .parse::<u64>()
+                        .map_err(|_| HttpError::new(ErrorCode::InvalidCursorParam))?,
+                ),
+                None => None,
+            };
+            let timeout = extract_timeout(query_params)?;
+            let batch = managers
+                .speech_manager
+                .poll_events(since, timeout)
+                .await
+                .ok_or(NOT_FOUND_ERROR)?;
+            let output = GetPollOutput {
+                cursor: batch.cursor,
+                missed: batch.missed,
+                events: batch.events.into_iter().map(GetPolledEvent::from).collect(),
+            };
+            Ok(value::to_value(output).map_err(|e| {
+                println!(
+                    "An internal error occured while converting polled events to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        _ => Err(NOT_FOUND_ERROR),
+    }
+}
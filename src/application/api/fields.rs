@@ -0,0 +1,108 @@
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+use super::{error::ErrorCode, filter::percent_decode, router::HttpError};
+
+/// Fields of the entities a GET route sends, chosen with the `fields` query parameter, e.g.
+/// `fields=uid,name,date`. Every field is sent without the parameter.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FieldSelection {
+    fields: Option<HashSet<String>>,
+}
+
+impl FieldSelection {
+    /// Reads the `fields` query parameter, `known` being the fields the route can send.
+    pub fn from_query(
+        query_params: &HashMap<String, String>,
+        known: &[&str],
+    ) -> Result<Self, HttpError<'static>> {
+        let raw = match query_params.get("fields") {
+            Some(raw) => percent_decode(raw)?,
+            None => return Ok(Self::default()),
+        };
+        let mut fields = HashSet::new();
+        for field in raw.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            if !known.contains(&field) {
+                return Err(HttpError::with_context(
+                    ErrorCode::InvalidFieldsParam,
+                    format!(
+                        "Unknown field {}, expected one of {}",
+                        field,
+                        known.join(", ")
+                    ),
+                ));
+            }
+            fields.insert(field.to_owned());
+        }
+        if fields.is_empty() {
+            return Err(HttpError::new(ErrorCode::InvalidFieldsParam));
+        }
+        Ok(Self {
+            fields: Some(fields),
+        })
+    }
+
+    /// Whether the field is sent, the routes skipping the loading of the others.
+    pub fn includes(&self, field: &str) -> bool {
+        self.fields
+            .as_ref()
+            .is_none_or(|fields| fields.contains(field))
+    }
+
+    /// Keeps the selected fields of the entity, or of each entity of a list.
+    pub fn project(&self, value: Value) -> Value {
+        let fields = match &self.fields {
+            Some(fields) => fields,
+            None => return value,
+        };
+        match value {
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.project(item)).collect())
+            }
+            Value::Object(mut object) => {
+                object.retain(|key, _| fields.contains(key));
+                Value::Object(object)
+            }
+            value => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::FieldSelection;
+
+    const KNOWN: &[&str] = &["uid", "name", "date", "speakers"];
+
+    fn selection(raw: &str) -> Option<FieldSelection> {
+        let query_params = HashMap::from([("fields".to_owned(), raw.to_owned())]);
+        FieldSelection::from_query(&query_params, KNOWN).ok()
+    }
+
+    #[test]
+    fn keeps_the_selected_fields() {
+        let selected = selection("uid,%20name").unwrap();
+        assert!(selected.includes("name"));
+        assert!(!selected.includes("speakers"));
+        assert_eq!(
+            selected.project(json!([
+                { "uid": "a", "name": "A", "speakers": [] },
+                { "uid": "b", "name": "B", "date": "2024-01-01" },
+            ])),
+            json!([{ "uid": "a", "name": "A" }, { "uid": "b", "name": "B" }])
+        );
+        let all = FieldSelection::from_query(&HashMap::new(), KNOWN).unwrap();
+        assert!(all.includes("speakers"));
+        assert_eq!(
+            all.project(json!({ "uid": "a", "speakers": [] })),
+            json!({ "uid": "a", "speakers": [] })
+        );
+        assert_eq!(selection("uid,sentences"), None);
+        assert_eq!(selection(","), None);
+    }
+}
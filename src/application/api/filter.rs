@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::domain::filter::{
+    FilterCondition, FilterField, FilterOperator, FilterSpec, FilterValue, FilterValueKind,
+};
+
+use super::{error::ErrorCode, router::HttpError};
+
+/// Largest number of conditions of a filter.
+const MAX_FILTER_CONDITIONS: usize = 16;
+
+/// Operators of the expressions, the two characters ones first so `>=` is not read as `>`.
+const OPERATORS: &[(&str, FilterOperator)] = &[
+    ("==", FilterOperator::Eq),
+    ("!=", FilterOperator::Ne),
+    (">=", FilterOperator::Ge),
+    ("<=", FilterOperator::Le),
+    (">", FilterOperator::Gt),
+    ("<", FilterOperator::Lt),
+];
+
+fn invalid_filter(context: String) -> HttpError<'static> {
+    HttpError::with_context(ErrorCode::InvalidFilterParam, context)
+}
+
+/// Decodes the `%XX` escapes and the `+` of a query parameter value.
+pub(super) fn percent_decode(raw: &str) -> Result<String, HttpError<'static>> {
+    let mut bytes = Vec::with_capacity(raw.len());
+    let mut raw_bytes = raw.bytes();
+    while let Some(byte) = raw_bytes.next() {
+        match byte {
+            b'%' => {
+                let escape = [raw_bytes.next(), raw_bytes.next()];
+                let decoded = match escape {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+                    _ => None,
+                };
+                bytes.push(
+                    decoded.ok_or_else(|| invalid_filter("Invalid percent escape".to_owned()))?,
+                );
+            }
+            b'+' => bytes.push(b' '),
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_filter("The filter is not UTF-8".to_owned()))
+}
+
+fn parse_condition<F: FilterField>(
+    expression: &str,
+) -> Result<FilterCondition<F>, HttpError<'static>> {
+    let (start, symbol, operator) = expression
+        .char_indices()
+        .find_map(|(i, _)| {
+            OPERATORS
+                .iter()
+                .find(|(symbol, _)| expression[i..].starts_with(symbol))
+                .map(|(symbol, operator)| (i, *symbol, *operator))
+        })
+        .ok_or_else(|| invalid_filter(format!("{} has no operator", expression)))?;
+    let name = expression[..start].trim();
+    let raw_value = expression[start + symbol.len()..].trim();
+    let field =
+        F::from_name(name).ok_or_else(|| invalid_filter(format!("Unknown field {}", name)))?;
+    let kind = field.kind();
+    if !kind.accepts(operator) {
+        return Err(invalid_filter(format!(
+            "{} cannot be compared with {}",
+            name, symbol
+        )));
+    }
+    let value = match kind {
+        FilterValueKind::Text if !raw_value.is_empty() => FilterValue::Text(raw_value.to_owned()),
+        FilterValueKind::Choice(choices) if choices.contains(&raw_value) => {
+            FilterValue::Text(raw_value.to_owned())
+        }
+        FilterValueKind::Date => FilterValue::Date(
+            NaiveDate::parse_from_str(raw_value, "%Y-%m-%d").map_err(|_| {
+                invalid_filter(format!("{} is not a date such as 2024-01-31", raw_value))
+            })?,
+        ),
+        FilterValueKind::Number => FilterValue::Number(
+            raw_value
+                .parse()
+                .map_err(|_| invalid_filter(format!("{} is not an integer", raw_value)))?,
+        ),
+        _ => return Err(invalid_filter(format!("Invalid value for {}", name))),
+    };
+    Ok(FilterCondition {
+        field,
+        operator,
+        value,
+    })
+}
+
+/// Reads the `filter` query parameter of the list routes, conditions separated by `;`
+/// that must all be met, e.g. `media==TF1;date>=2024-01-01;status==VALIDATED`. The
+/// operators are `==`, `!=`, `>`, `>=`, `<` and `<=`, the text fields only accepting the
+/// first two.
+pub fn extract_filter_spec<F: FilterField>(
+    query_params: &HashMap<String, String>,
+) -> Result<FilterSpec<F>, HttpError<'static>> {
+    let raw = match query_params.get("filter") {
+        Some(raw) => percent_decode(raw)?,
+        None => return Ok(FilterSpec::default()),
+    };
+    let conditions = raw
+        .split(';')
+        .filter(|expression| !expression.trim().is_empty())
+        .map(parse_condition)
+        .collect::<Result<Vec<FilterCondition<F>>, HttpError<'static>>>()?;
+    if conditions.len() > MAX_FILTER_CONDITIONS {
+        return Err(invalid_filter(format!(
+            "A filter has at most {} conditions",
+            MAX_FILTER_CONDITIONS
+        )));
+    }
+    Ok(FilterSpec { conditions })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDate;
+
+    use super::extract_filter_spec;
+    use crate::domain::{
+        filter::{FilterCondition, FilterField, FilterOperator, FilterSpec, FilterValue},
+        person::PersonField,
+        speech::speech_repository::SpeechField,
+    };
+
+    fn filter<F: FilterField>(raw: &str) -> Option<FilterSpec<F>> {
+        let query_params = HashMap::from([("filter".to_owned(), raw.to_owned())]);
+        extract_filter_spec(&query_params).ok()
+    }
+
+    #[test]
+    fn parses_the_conditions_of_a_filter() {
+        assert_eq!(
+            filter::<SpeechField>("media==TF1;date%3E=2024-01-01;status!=LIVE"),
+            Some(FilterSpec {
+                conditions: vec![
+                    FilterCondition {
+                        field: SpeechField::Media,
+                        operator: FilterOperator::Eq,
+                        value: FilterValue::Text("TF1".to_owned()),
+                    },
+                    FilterCondition {
+                        field: SpeechField::Date,
+                        operator: FilterOperator::Ge,
+                        value: FilterValue::Date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+                    },
+                    FilterCondition {
+                        field: SpeechField::Status,
+                        operator: FilterOperator::Ne,
+                        value: FilterValue::Text("LIVE".to_owned()),
+                    },
+                ]
+            })
+        );
+        assert_eq!(
+            filter::<PersonField>("name==Le+Pen;trustScore<3"),
+            Some(FilterSpec {
+                conditions: vec![
+                    FilterCondition {
+                        field: PersonField::Name,
+                        operator: FilterOperator::Eq,
+                        value: FilterValue::Text("Le Pen".to_owned()),
+                    },
+                    FilterCondition {
+                        field: PersonField::TrustScore,
+                        operator: FilterOperator::Lt,
+                        value: FilterValue::Number(3),
+                    },
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_the_invalid_conditions() {
+        assert_eq!(filter::<SpeechField>("media>TF1"), None);
+        assert_eq!(filter::<SpeechField>("status==DRAFT"), None);
+        assert_eq!(filter::<SpeechField>("date>=yesterday"), None);
+        assert_eq!(filter::<SpeechField>("speaker==abc"), None);
+        assert_eq!(filter::<SpeechField>("media"), None);
+        assert_eq!(filter::<PersonField>("trustScore>=high"), None);
+    }
+}
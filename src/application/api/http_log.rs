@@ -0,0 +1,200 @@
+use hyper::{header::HeaderMap, Method, StatusCode};
+use serde_json::{Map, Value};
+use tokio::time::Instant;
+
+/// Value written in place of the sensitive headers and fields.
+const REDACTED: &str = "[REDACTED]";
+
+/// Headers never written in the logs.
+const REDACTED_HEADERS: &[&str] = &["authorization", "cookie", "set-cookie"];
+
+/// Fields of the JSON bodies never written in the logs, compared without their case and
+/// their underscores so `birthDate` and `birth_date` both match.
+const REDACTED_FIELDS: &[&str] = &["birthdate"];
+
+/// Detail of the audit log written for each request.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HttpLogLevel {
+    /// Nothing is written.
+    #[default]
+    Off,
+    /// Method, path, status and latency of the requests.
+    Requests,
+    /// The requests along with their headers and their JSON bodies, redacted.
+    Bodies,
+}
+
+/// Audit log of the HTTP requests, written through `tracing` under the `http` target.
+#[derive(Debug, Clone)]
+pub struct HttpLogConfig {
+    pub level: HttpLogLevel,
+    /// Bodies longer than this number of bytes are truncated in the logs.
+    pub max_body_length: usize,
+}
+
+impl Default for HttpLogConfig {
+    fn default() -> Self {
+        Self {
+            level: HttpLogLevel::Off,
+            max_body_length: 2048,
+        }
+    }
+}
+
+/// Log of a request, written once its response is ready.
+pub struct HttpExchangeLog {
+    level: HttpLogLevel,
+    max_body_length: usize,
+    request_id: String,
+    method: Method,
+    path: String,
+    received_at: Instant,
+    request_headers: Option<String>,
+    request_body: Option<String>,
+    response_body: Option<String>,
+}
+
+impl HttpExchangeLog {
+    pub fn new(
+        config: &HttpLogConfig,
+        request_id: &str,
+        method: &Method,
+        path: &str,
+        headers: &HeaderMap,
+    ) -> Self {
+        let request_headers = match config.level {
+            HttpLogLevel::Bodies => Some(redacted_headers(headers)),
+            _ => None,
+        };
+        Self {
+            level: config.level,
+            max_body_length: config.max_body_length,
+            request_id: request_id.to_owned(),
+            method: method.clone(),
+            path: path.to_owned(),
+            received_at: Instant::now(),
+            request_headers,
+            request_body: None,
+            response_body: None,
+        }
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Keeps the JSON body of the request when the bodies are logged.
+    pub fn record_request_body(&mut self, body: &Value) {
+        if self.level == HttpLogLevel::Bodies && !body.is_null() {
+            self.request_body = Some(redacted_body(body, self.max_body_length));
+        }
+    }
+
+    /// Keeps the JSON body of the response when the bodies are logged. The streamed
+    /// responses are left out.
+    pub fn record_response_body(&mut self, body: &Value) {
+        if self.level == HttpLogLevel::Bodies && !body.is_null() {
+            self.response_body = Some(redacted_body(body, self.max_body_length));
+        }
+    }
+
+    /// Writes the log of the request answered with the status.
+    pub fn write(self, status: StatusCode) {
+        if self.level == HttpLogLevel::Off {
+            return;
+        }
+        tracing::info!(
+            target: "http",
+            request_id = %self.request_id,
+            method = %self.method,
+            path = %self.path,
+            status = status.as_u16(),
+            latency_ms = self.received_at.elapsed().as_millis() as u64,
+            request_headers = self.request_headers,
+            request_body = self.request_body,
+            response_body = self.response_body,
+            "request served"
+        );
+    }
+}
+
+/// Serializes the headers, the sensitive ones being redacted.
+fn redacted_headers(headers: &HeaderMap) -> String {
+    let headers = headers
+        .iter()
+        .map(|(name, value)| {
+            let value = match REDACTED_HEADERS.contains(&name.as_str()) {
+                true => REDACTED.to_owned(),
+                false => String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            };
+            (name.to_string(), Value::String(value))
+        })
+        .collect::<Map<String, Value>>();
+    Value::Object(headers).to_string()
+}
+
+/// Serializes the body, its sensitive fields being redacted at any depth, truncated on a
+/// character boundary past `max_length` bytes.
+fn redacted_body(body: &Value, max_length: usize) -> String {
+    let mut body = body.clone();
+    redact_fields(&mut body);
+    let mut body = body.to_string();
+    if body.len() > max_length {
+        let mut end = max_length;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+        body.push_str("...");
+    }
+    body
+}
+
+fn redact_fields(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                let name = name.replace('_', "").to_lowercase();
+                if REDACTED_FIELDS.contains(&name.as_str()) {
+                    *field = Value::String(REDACTED.to_owned());
+                } else {
+                    redact_fields(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_fields),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+    use serde_json::json;
+
+    use super::{redacted_body, redacted_headers};
+
+    #[test]
+    fn sensitive_headers_and_fields_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let headers = redacted_headers(&headers);
+        assert!(!headers.contains("secret"));
+        assert!(headers.contains("application/json"));
+        let body = json!({
+            "name": "Doe",
+            "birthDate": "1970-01-01",
+            "people": [{ "birth_date": "1980-01-01" }]
+        });
+        let body = redacted_body(&body, 1024);
+        assert!(!body.contains("1970") && !body.contains("1980"));
+        assert!(body.contains("Doe"));
+    }
+
+    #[test]
+    fn long_bodies_are_truncated_on_a_character() {
+        let body = redacted_body(&json!("ééééé"), 4);
+        assert_eq!(body, "\"é...");
+    }
+}
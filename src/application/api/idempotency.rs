@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use hyper::{header::HeaderMap, Response};
+use tokio::time::Instant;
+
+use crate::domain::idempotency::{IdempotencyManager, IdempotencyRepositoryError, StoredResponse};
+
+use super::{
+    error::ErrorCode,
+    router::{full, BoxBody, HttpError, RouteResponse, INTERNAL_ERROR},
+    token::{AuthToken, Permissions},
+};
+
+/// Maximum length of an idempotency key, in characters.
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// Time a key stays claimed by a request without deadline, in seconds.
+const UNBOUNDED_REQUEST_LEASE: u64 = 10 * 60;
+
+/// Permissions the POST routes check, checked again before replaying their response. The
+/// other routes only check the user, the keys being those of the user anyway.
+const ROUTE_PERMISSIONS: &[(&str, &[Permissions])] = &[
+    ("person", &[Permissions::CreatePerson]),
+    ("person/bulk", &[Permissions::CreatePerson]),
+    (
+        "person/*/merge/*",
+        &[Permissions::UpdatePerson, Permissions::DeletePerson],
+    ),
+    ("person/*/restore", &[Permissions::DeletePerson]),
+    ("person/*/labels/*", &[Permissions::UpdatePerson]),
+    ("speech", &[Permissions::CreateSpeech]),
+    ("speech/check-duplicates", &[Permissions::CreateSpeech]),
+    ("speech/imports", &[Permissions::CreateSpeech]),
+    ("speech/imports/*/apply", &[Permissions::CreateSpeech]),
+    ("speech/*/validate", &[Permissions::UpdateSpeech]),
+    ("speech/*/pii-flags/*/review", &[Permissions::UpdateSpeech]),
+    ("speech/*/sentences/*/review", &[Permissions::UpdateSpeech]),
+    ("speech/*/finalize", &[Permissions::UpdateSpeech]),
+    ("speech/*/revert/*", &[Permissions::UpdateSpeech]),
+    ("speech/*/speakers/*", &[Permissions::UpdateSpeech]),
+    ("speech/*/speakers/*/reassign", &[Permissions::UpdateSpeech]),
+    ("speech/*/segments", &[Permissions::UpdateSpeech]),
+    ("speech/*/labels/*", &[Permissions::UpdateSpeech]),
+    ("speech/*/restore", &[Permissions::DeleteSpeech]),
+    ("tags", &[Permissions::UpdateSpeech]),
+    ("tags/*", &[Permissions::UpdateSpeech]),
+    ("watchlists", &[Permissions::GetSpeech]),
+    ("organizations", &[Permissions::ManageOrganizations]),
+    ("admin/*", &[Permissions::Admin]),
+];
+
+impl From<IdempotencyRepositoryError> for HttpError<'static> {
+    fn from(value: IdempotencyRepositoryError) -> Self {
+        match value {
+            IdempotencyRepositoryError::KeyReused => {
+                HttpError::new(ErrorCode::IdempotencyKeyReused)
+            }
+            IdempotencyRepositoryError::KeyInProgress => {
+                HttpError::new(ErrorCode::IdempotencyKeyInProgress)
+            }
+            IdempotencyRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on idempotency keys: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+/// Reads the `Idempotency-Key` header, if any.
+pub fn extract_idempotency_key(headers: &HeaderMap) -> Result<Option<String>, HttpError<'static>> {
+    let key = match headers.get("Idempotency-Key") {
+        Some(key) => key.to_str().unwrap_or_default().trim(),
+        None => return Ok(None),
+    };
+    if key.is_empty() || key.chars().count() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err(HttpError::new(ErrorCode::InvalidIdempotencyKey));
+    }
+    Ok(Some(key.to_owned()))
+}
+
+/// Time the key of a request is claimed for: the request cannot be processed past its
+/// deadline, its key is claimed again by a retry after.
+pub fn idempotency_lease(deadline: Option<Instant>) -> Duration {
+    match deadline {
+        Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+        None => Duration::from_secs(UNBOUNDED_REQUEST_LEASE),
+    }
+}
+
+/// Whether the user still holds the permissions of the POST route, `path` being the whole
+/// request path, before being sent the response stored for the route.
+pub fn may_replay(path: &str, token: &AuthToken) -> bool {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .skip(1)
+        .collect::<Vec<&str>>();
+    ROUTE_PERMISSIONS
+        .iter()
+        .find(|(pattern, _)| {
+            let pattern = pattern.split('/').collect::<Vec<&str>>();
+            pattern.len() == segments.len()
+                && pattern
+                    .iter()
+                    .zip(&segments)
+                    .all(|(expected, segment)| *expected == "*" || expected == segment)
+        })
+        .map_or(token.is_authenticated(), |(_, permissions)| {
+            permissions
+                .iter()
+                .all(|permission| token.permissions().contains(permission))
+        })
+}
+
+/// Builds the response sent again to a retried request.
+pub fn replayed_response(stored: StoredResponse) -> Response<BoxBody> {
+    Response::builder()
+        .status(stored.status)
+        .header("Idempotent-Replayed", "true")
+        .body(full(stored.body))
+        .expect("Should not fail")
+}
+
+/// Stores the response of a request sent with a key. Only the JSON responses of the
+/// successful requests are replayed, the key of a failed request is released so the
+/// client can retry it.
+pub async fn record_response(
+    idempotency_manager: &IdempotencyManager,
+    key: &str,
+    response: &Result<RouteResponse, HttpError<'static>>,
+) {
+    let result = match response {
+        Ok(RouteResponse::Json(value)) => {
+            idempotency_manager
+                .complete_key(
+                    key,
+                    &StoredResponse {
+                        status: 200,
+                        body: value.to_string(),
+                    },
+                )
+                .await
+        }
+        _ => idempotency_manager.release_key(key).await,
+    };
+    if let Err(e) = result {
+        println!(
+            "An internal error occured while storing the response of the idempotency key {}: {:?}",
+            key, e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::may_replay;
+    use crate::application::api::token::{AuthToken, Permissions};
+
+    #[test]
+    fn replay_requires_the_permissions_of_the_route() {
+        let token = |permissions| AuthToken::_new(Some("user".to_owned()), None, permissions, None);
+        let creator = token(vec![Permissions::CreateSpeech]);
+        assert!(may_replay("/api/speech", &creator));
+        assert!(may_replay("/api/speech/imports", &creator));
+        assert!(!may_replay("/api/speech/1234/validate", &creator));
+        assert!(!may_replay("/api/speech", &token(Vec::new())));
+        assert!(may_replay("/api/labels", &token(Vec::new())));
+    }
+}
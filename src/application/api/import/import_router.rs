@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use hyper::{
+    body,
+    header::{self, HeaderMap},
+};
+use serde::Serialize;
+use serde_json::value;
+use uuid::Uuid;
+
+use crate::application::{
+    api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, RouteResponse, ACCESS_DENIED_ERROR, INTERNAL_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    assemblee_nationale::{
+        import_sitting, AssembleeNationaleImportError, AssembleeNationaleReport,
+    },
+};
+
+impl From<AssembleeNationaleImportError> for HttpError<'static> {
+    fn from(value: AssembleeNationaleImportError) -> Self {
+        match value {
+            AssembleeNationaleImportError::InvalidXml(e) => {
+                HttpError::with_context(ErrorCode::InvalidFormat, e)
+            }
+            AssembleeNationaleImportError::UnknownOrators(orators) => {
+                HttpError::with_context(ErrorCode::UnknownOrators, orators.join(", "))
+            }
+            AssembleeNationaleImportError::PersonError(e) => e.into(),
+            AssembleeNationaleImportError::SpeechError(e) => e.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetImportedOrator {
+    orator: String,
+    person: String,
+}
+
+impl From<(String, Uuid)> for GetImportedOrator {
+    fn from((orator, person): (String, Uuid)) -> Self {
+        Self {
+            orator,
+            person: person.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetAssembleeNationaleReport {
+    speech: String,
+    interventions: usize,
+    sentences: usize,
+    /// Orators speaking through a person already stored.
+    matched: Vec<GetImportedOrator>,
+    /// Orators a person was created for.
+    created: Vec<GetImportedOrator>,
+}
+
+impl From<AssembleeNationaleReport> for GetAssembleeNationaleReport {
+    fn from(value: AssembleeNationaleReport) -> Self {
+        Self {
+            speech: value.speech.to_string(),
+            interventions: value.interventions,
+            sentences: value.sentences,
+            matched: value.matched.into_iter().map(|o| o.into()).collect(),
+            created: value.created.into_iter().map(|o| o.into()).collect(),
+        }
+    }
+}
+
+/// Imports the documents sent to `/api/import`, read as XML instead of JSON. Only the
+/// compte-rendus of the Assemblée nationale are imported so far.
+pub async fn router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    headers: &HeaderMap,
+    token: &AuthToken,
+    body: body::Incoming,
+    managers: &Managers,
+    max_body_size: usize,
+) -> Result<RouteResponse, HttpError<'static>> {
+    if path != "assemblee-nationale" {
+        return Err(HttpError::new(ErrorCode::NotFound));
+    }
+    if !token.permissions().contains(&Permissions::CreateSpeech) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let create_persons = match query_params.get("create_persons") {
+        Some(v) => v
+            .parse::<bool>()
+            .map_err(|_| HttpError::new(ErrorCode::InvalidCreatePersonsParam))?,
+        None => false,
+    };
+    if create_persons && !token.permissions().contains(&Permissions::CreatePerson) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let is_xml = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|mime| {
+            let mime = mime.trim();
+            mime.eq_ignore_ascii_case("application/xml") || mime.eq_ignore_ascii_case("text/xml")
+        });
+    if !is_xml {
+        return Err(HttpError::new(ErrorCode::UnsupportedMediaType));
+    }
+    let xml = Limited::new(body, max_body_size)
+        .collect()
+        .await
+        .map_err(|e| {
+            if e.downcast_ref::<LengthLimitError>().is_some() {
+                return HttpError::with_context(
+                    ErrorCode::PayloadTooLarge,
+                    format!("The request body must not exceed {} bytes", max_body_size),
+                );
+            }
+            println!("An internal error occured while getting the body : {:?}", e);
+            INTERNAL_ERROR
+        })?
+        .to_bytes();
+    let xml = std::str::from_utf8(&xml).map_err(|_| {
+        HttpError::with_context(ErrorCode::InvalidFormat, "The body is not UTF-8".to_owned())
+    })?;
+    let report: GetAssembleeNationaleReport = import_sitting(
+        xml,
+        create_persons,
+        &managers.person_manager,
+        &managers.speech_manager,
+    )
+    .await?
+    .into();
+    Ok(value::to_value(report)
+        .map_err(|e| {
+            println!(
+                "An internal error occured while converting import report to value: {:?}",
+                e
+            );
+            INTERNAL_ERROR
+        })?
+        .into())
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use hyper::Method;
+use serde::Serialize;
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    domain::job::{Job, JobKind, JobRepositoryError, JobStatus},
+};
+
+/// Every kind of job, listed when the token may see them.
+const JOB_KINDS: [JobKind; 5] = [
+    JobKind::SpeechImport,
+    JobKind::ReindexSearch,
+    JobKind::RecomputeScores,
+    JobKind::VacuumOrphans,
+    JobKind::Backup,
+];
+
+impl From<JobRepositoryError> for HttpError<'static> {
+    fn from(value: JobRepositoryError) -> Self {
+        match value {
+            JobRepositoryError::JobNotFound => HttpError::new(ErrorCode::JobNotFound),
+            JobRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Jobs: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetJobProgress {
+    processed: u64,
+    /// Missing while the operation does not know how many items it has to do.
+    total: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetJobOutput {
+    uid: String,
+    kind: String,
+    status: String,
+    progress: GetJobProgress,
+    result: Option<Value>,
+    error: Option<String>,
+    started_at: String,
+    updated_at: String,
+    finished_at: Option<String>,
+}
+
+impl From<Job> for GetJobOutput {
+    fn from(value: Job) -> Self {
+        Self {
+            uid: value.uid.to_string(),
+            kind: value.kind.to_string(),
+            status: value.status.to_string(),
+            progress: GetJobProgress {
+                processed: value.processed,
+                total: value.total,
+            },
+            result: value.result,
+            error: value.error,
+            started_at: value.started_at.to_rfc3339(),
+            updated_at: value.updated_at.to_rfc3339(),
+            finished_at: value.finished_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// Permission needed to follow the jobs of the kind, the one starting them.
+fn required_permission(kind: JobKind) -> Permissions {
+    match kind {
+        JobKind::SpeechImport => Permissions::CreateSpeech,
+        JobKind::ReindexSearch
+        | JobKind::RecomputeScores
+        | JobKind::VacuumOrphans
+        | JobKind::Backup => Permissions::Admin,
+    }
+}
+
+/// Job as sent to the client polling it, e.g. right after starting it.
+pub fn job_value(job: Job) -> Result<Value, HttpError<'static>> {
+    value::to_value(GetJobOutput::from(job)).map_err(|e| {
+        println!(
+            "An internal error occured while converting job to value: {:?}",
+            e
+        );
+        INTERNAL_ERROR
+    })
+}
+
+/// Jobs of the organization, each one only reachable with the permission starting it.
+pub async fn router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    method: &Method,
+    token: &AuthToken,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    let kinds = JOB_KINDS
+        .into_iter()
+        .filter(|kind| token.permissions().contains(&required_permission(*kind)))
+        .collect::<Vec<JobKind>>();
+    if kinds.is_empty() {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::GET, [""]) => {
+            let status = match query_params.get("status") {
+                Some(status) => Some(
+                    JobStatus::try_from(status.as_str())
+                        .map_err(|_| HttpError::new(ErrorCode::InvalidJobStatusParam))?,
+                ),
+                None => None,
+            };
+            let page_raw = match query_params.get("page") {
+                Some(v) => v,
+                None => &"0".to_owned(),
+            };
+            let quantity_raw = match query_params.get("quantity") {
+                Some(v) => v,
+                None => &"10".to_owned(),
+            };
+            let page = page_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidPageParam))?;
+            let quantity = quantity_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?;
+            let jobs: Vec<GetJobOutput> = managers
+                .job_manager
+                .get_jobs(&kinds, status, page, quantity)
+                .await?
+                .into_iter()
+                .map(GetJobOutput::from)
+                .collect();
+            Ok(value::to_value(jobs).map_err(|e| {
+                println!(
+                    "An internal error occured while converting jobs to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::GET, [uid]) => {
+            let uid = Uuid::parse_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let job = managers.job_manager.get_job(&uid).await?;
+            // The jobs the token may not follow are not told apart from the missing ones.
+            if !kinds.contains(&job.kind) {
+                return Err(HttpError::new(ErrorCode::JobNotFound));
+            }
+            job_value(job)
+        }
+        _ => Err(NOT_FOUND_ERROR),
+    }
+}
@@ -1,10 +1,34 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::{stream::BoxStream, StreamExt};
+use hyper::header::{HeaderMap, CACHE_CONTROL};
 use jsonwebtoken::DecodingKey;
-use lazy_static::lazy_static;
+use rand::Rng;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use tokio::sync::{Notify, RwLock};
+use uuid::Uuid;
+
+// Intervalle entre deux rafraîchissements réussis des clés quand Keycloak n'envoie pas
+// de `Cache-Control`, c'est aussi la durée maximale acceptée depuis cet en-tête
+const REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+// Durée minimale entre deux rafraîchissements, même avec un `max-age` plus court ou un
+// `no-cache`
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+// Bornes du délai entre deux tentatives après un échec
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+// Délai minimal entre deux rafraîchissements forcés par un `kid` inconnu, pour qu'un
+// client envoyant des `kid` au hasard ne puisse pas inonder Keycloak
+const FORCED_REFRESH_COOLDOWN: Duration = Duration::from_secs(10);
+// Les instances se réveillent jusqu'à quelques secondes après l'expiration des clés, la
+// première interroge Keycloak et les suivantes trouvent ses clés dans le cache partagé
+const REFRESH_SPREAD: Duration = Duration::from_secs(5);
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
 
 // Structure des certificats Keycloak
 #[derive(Deserialize)]
@@ -21,39 +45,352 @@ struct KeycloakKey {
     kty: String, // Key type (e.g., RSA)
 }
 
+/// Les clés n'ont encore jamais pu être récupérées, le token ne peut pas être vérifié.
+#[derive(Debug)]
+pub struct KeysUnavailable;
+
+/// Source des clés de vérification des tokens.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Clé correspondant à un `kid`, `None` quand elle est inconnue.
+    async fn get_key(&self, kid: &str) -> Result<Option<DecodingKey>, KeysUnavailable>;
+    /// État du cache des clés, exposé par `GET /api/readyz`.
+    async fn state(&self) -> KeyCacheState;
+}
+
+/// Origine des clés actuellement en cache.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeySource {
+    Keycloak,
+    SharedCache,
+}
+
+/// État du cache des clés d'une instance.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyCacheState {
+    /// Vrai dès qu'une récupération a réussi, les tokens pouvant alors être vérifiés.
+    pub loaded: bool,
+    pub key_count: usize,
+    pub source: Option<KeySource>,
+    pub refreshed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Vrai quand les clés sont partagées entre les instances.
+    pub shared_cache: bool,
+    /// Dernière erreur de rafraîchissement, effacée au rafraîchissement suivant réussi.
+    pub last_error: Option<String>,
+}
+
+/// Fournisseur sans clé, tous les tokens sont refusés comme si Keycloak était
+/// injoignable.
+pub struct NoKeyProvider;
+
+#[async_trait]
+impl KeyProvider for NoKeyProvider {
+    async fn get_key(&self, _kid: &str) -> Result<Option<DecodingKey>, KeysUnavailable> {
+        Err(KeysUnavailable)
+    }
+
+    async fn state(&self) -> KeyCacheState {
+        KeyCacheState::default()
+    }
+}
+
+/// JWKS brut tel que partagé entre les instances.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedKeys {
+    pub certs: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Cache des clés partagé entre les instances, pour qu'une seule interroge Keycloak et
+/// que toutes apprennent une rotation des clés dès qu'elle est vue.
+#[async_trait]
+pub trait SharedKeyCache: Send + Sync {
+    async fn get(&self) -> Result<Option<SharedKeys>, BoxError>;
+    /// Enregistre les clés jusqu'à leur expiration.
+    async fn set(&self, keys: &SharedKeys) -> Result<(), BoxError>;
+    /// Prévient les autres instances que les clés ont changé, `origin` identifiant
+    /// l'instance qui publie.
+    async fn publish_invalidation(&self, origin: &str) -> Result<(), BoxError>;
+    /// Flux des instances ayant publié une invalidation, le sien compris.
+    async fn invalidations(&self) -> Result<BoxStream<'static, String>, BoxError>;
+}
+
 // Structure pour gérer le cache des clés
+#[derive(Default)]
 struct CachedKeys {
-    keys: HashMap<String, DecodingKey>, // Les clés sont stockées ici
-    last_fetched: Instant,              // Dernière récupération des clés
+    keys: Option<HashMap<String, DecodingKey>>, // `None` tant qu'aucune récupération n'a réussi
+    source: Option<KeySource>,
+    refreshed_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    last_forced_refresh: Option<Instant>, // Dernier rafraîchissement forcé par un `kid` inconnu
+    last_error: Option<String>,
 }
 
-// Initialisation d'un cache global
-lazy_static! {
-    static ref KEYCLOAK_KEYS_CACHE: Mutex<CachedKeys> = Mutex::new(CachedKeys {
-        keys: HashMap::new(),
-        last_fetched: Instant::now() - Duration::from_secs(3600), // Initialisé à il y a 1h
-    });
+// Clés récupérées, pas encore en cache
+struct FetchedKeys {
+    keys: HashMap<String, DecodingKey>,
+    source: KeySource,
+    expires_at: DateTime<Utc>,
 }
 
-/// Fonction pour récupérer les clés Keycloak avec mise en cache
-pub async fn get_keycloak_keys() -> Result<HashMap<String, DecodingKey>, Box<dyn std::error::Error>>
-{
-    let mut cache = KEYCLOAK_KEYS_CACHE.lock().await;
+/// Clés lues depuis l'URL JWKS de Keycloak, gardées en mémoire jusqu'à l'expiration
+/// annoncée par son `Cache-Control` et éventuellement partagées entre les instances.
+pub struct KeycloakKeyProvider {
+    certs_url: String,
+    cache: RwLock<CachedKeys>,
+    shared_cache: Option<Box<dyn SharedKeyCache>>,
+    // Identifie l'instance dans les invalidations qu'elle publie
+    instance_id: String,
+    // Réveille la tâche de rafraîchissement quand une autre instance a changé les clés
+    invalidated: Notify,
+}
 
-    // Vérifiez si le cache est expiré (par exemple, 1 heure)
-    if cache.last_fetched.elapsed() < Duration::from_secs(3600) {
-        return Ok(cache.keys.clone());
+impl KeycloakKeyProvider {
+    pub fn new(certs_url: &str) -> Self {
+        Self {
+            certs_url: certs_url.to_owned(),
+            cache: RwLock::new(CachedKeys::default()),
+            shared_cache: None,
+            instance_id: Uuid::new_v4().to_string(),
+            invalidated: Notify::new(),
+        }
     }
 
-    // Construire l'URL JWKS (JSON Web Key Set) de Keycloak
-    let jwks_url = format!("{}", std::env::var("KEYCLOAK_CERTS_URL")?);
+    /// Partage les clés entre les instances au travers du cache donné.
+    pub fn with_shared_cache(mut self, shared_cache: Box<dyn SharedKeyCache>) -> Self {
+        self.shared_cache = Some(shared_cache);
+        self
+    }
 
+    /// Lance les tâches de fond qui rafraîchissent les clés. Tant qu'un rafraîchissement
+    /// échoue, les dernières clés récupérées continuent d'être utilisées et une nouvelle
+    /// tentative est faite après un délai croissant.
+    pub fn start_refresh(self: Arc<Self>) {
+        if self.shared_cache.is_some() {
+            tokio::spawn(self.clone().follow_invalidations());
+        }
+        tokio::spawn(async move {
+            let mut retry_delay = MIN_RETRY_DELAY;
+            loop {
+                match self.refresh(true).await {
+                    Ok(until_expiry) => {
+                        retry_delay = MIN_RETRY_DELAY;
+                        let until_refresh = until_expiry + with_jitter(REFRESH_SPREAD);
+                        tokio::select! {
+                            _ = tokio::time::sleep(until_refresh) => {}
+                            _ = self.invalidated.notified() => {}
+                        }
+                    }
+                    Err(e) => {
+                        println!("An error occured while refreshing keycloak keys: {:?}", e);
+                        tokio::time::sleep(with_jitter(retry_delay)).await;
+                        retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+                    }
+                }
+            }
+        });
+    }
+
+    // Écoute les invalidations des autres instances, en se réabonnant quand la connexion
+    // au cache partagé est perdue
+    async fn follow_invalidations(self: Arc<Self>) {
+        let shared_cache = match &self.shared_cache {
+            Some(shared_cache) => shared_cache,
+            None => return,
+        };
+        loop {
+            match shared_cache.invalidations().await {
+                Ok(mut origins) => {
+                    while let Some(origin) = origins.next().await {
+                        if origin != self.instance_id {
+                            self.invalidated.notify_one();
+                        }
+                    }
+                    println!("The subscription to the keycloak keys invalidations was lost");
+                }
+                Err(e) => println!(
+                    "An error occured while subscribing to the keycloak keys invalidations: {:?}",
+                    e
+                ),
+            }
+            tokio::time::sleep(with_jitter(MAX_RETRY_DELAY)).await;
+        }
+    }
+
+    // Rafraîchit les clés, depuis le cache partagé s'il en a de valides et que
+    // `prefer_shared` est vrai, sinon depuis Keycloak. Renvoie le délai avant leur
+    // expiration.
+    async fn refresh(&self, prefer_shared: bool) -> Result<Duration, BoxError> {
+        let fetched = match self.read_shared_keys(prefer_shared).await {
+            Some(fetched) => fetched,
+            None => match self.fetch_and_share().await {
+                Ok(fetched) => fetched,
+                Err(e) => {
+                    self.cache.write().await.last_error = Some(e.to_string());
+                    return Err(e);
+                }
+            },
+        };
+        let until_expiry = (fetched.expires_at - Utc::now())
+            .to_std()
+            .unwrap_or_default()
+            .max(MIN_REFRESH_INTERVAL);
+        let changed = {
+            let mut cache = self.cache.write().await;
+            let changed = cache.keys.as_ref().is_some_and(|keys| {
+                keys.len() != fetched.keys.len()
+                    || fetched.keys.keys().any(|kid| !keys.contains_key(kid))
+            });
+            cache.keys = Some(fetched.keys);
+            cache.source = Some(fetched.source);
+            cache.refreshed_at = Some(Utc::now());
+            cache.expires_at = Some(fetched.expires_at);
+            cache.last_error = None;
+            changed
+        };
+        // Les autres instances relisent le cache partagé sans attendre l'expiration de
+        // leurs clés
+        if let (true, KeySource::Keycloak, Some(shared_cache)) =
+            (changed, fetched.source, &self.shared_cache)
+        {
+            if let Err(e) = shared_cache.publish_invalidation(&self.instance_id).await {
+                println!(
+                    "An error occured while publishing the keycloak keys invalidation: {:?}",
+                    e
+                );
+            }
+        }
+        Ok(until_expiry)
+    }
+
+    // Clés du cache partagé, `None` quand il est absent, vide, expiré ou injoignable
+    async fn read_shared_keys(&self, prefer_shared: bool) -> Option<FetchedKeys> {
+        let shared_cache = self.shared_cache.as_ref().filter(|_| prefer_shared)?;
+        let shared = match shared_cache.get().await {
+            Ok(shared) => shared?,
+            Err(e) => {
+                println!(
+                    "An error occured while reading the shared keycloak keys: {:?}",
+                    e
+                );
+                return None;
+            }
+        };
+        if shared.expires_at <= Utc::now() {
+            return None;
+        }
+        match parse_keycloak_keys(&shared.certs) {
+            Ok(keys) => Some(FetchedKeys {
+                keys,
+                source: KeySource::SharedCache,
+                expires_at: shared.expires_at,
+            }),
+            Err(e) => {
+                println!("The shared keycloak keys are invalid: {:?}", e);
+                None
+            }
+        }
+    }
+
+    // Récupère les clés depuis Keycloak et les partage avec les autres instances
+    async fn fetch_and_share(&self) -> Result<FetchedKeys, BoxError> {
+        let (certs, max_age) = fetch_keycloak_certs(&self.certs_url).await?;
+        let keys = parse_keycloak_keys(&certs)?;
+        let expires_at = Utc::now() + chrono::Duration::seconds(max_age.as_secs() as i64);
+        if let Some(shared_cache) = &self.shared_cache {
+            let shared = SharedKeys { certs, expires_at };
+            if let Err(e) = shared_cache.set(&shared).await {
+                println!("An error occured while sharing the keycloak keys: {:?}", e);
+            }
+        }
+        Ok(FetchedKeys {
+            keys,
+            source: KeySource::Keycloak,
+            expires_at,
+        })
+    }
+
+    async fn uses_shared_cache(&self) -> bool {
+        self.cache.read().await.source == Some(KeySource::SharedCache)
+    }
+
+    async fn cached_key(&self, kid: &str) -> Option<DecodingKey> {
+        let cache = self.cache.read().await;
+        cache.keys.as_ref().and_then(|keys| keys.get(kid)).cloned()
+    }
+}
+
+#[async_trait]
+impl KeyProvider for KeycloakKeyProvider {
+    /// Un `kid` inconnu déclenche un unique rafraîchissement forcé, pour accepter les
+    /// tokens signés par une clé ajoutée depuis le dernier rafraîchissement. Le cache
+    /// partagé est lu en premier, une autre instance ayant pu voir la nouvelle clé.
+    async fn get_key(&self, kid: &str) -> Result<Option<DecodingKey>, KeysUnavailable> {
+        if let Some(key) = self.cached_key(kid).await {
+            return Ok(Some(key));
+        }
+
+        // Le verrou n'est pas gardé pendant la requête, pour ne pas bloquer les autres tokens
+        let cooling_down = {
+            let mut cache = self.cache.write().await;
+            let cooling_down = cache
+                .last_forced_refresh
+                .is_some_and(|last| last.elapsed() < FORCED_REFRESH_COOLDOWN);
+            if !cooling_down {
+                cache.last_forced_refresh = Some(Instant::now());
+            }
+            cooling_down
+        };
+        if !cooling_down {
+            match self.refresh(true).await {
+                // Le cache partagé peut ne pas encore connaître la nouvelle clé
+                Ok(_) if self.cached_key(kid).await.is_none() && self.uses_shared_cache().await => {
+                    if let Err(e) = self.refresh(false).await {
+                        println!("An error occured while refreshing keycloak keys: {:?}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => println!("An error occured while refreshing keycloak keys: {:?}", e),
+            }
+        }
+        match &self.cache.read().await.keys {
+            Some(keys) => Ok(keys.get(kid).cloned()),
+            None => Err(KeysUnavailable),
+        }
+    }
+
+    async fn state(&self) -> KeyCacheState {
+        let cache = self.cache.read().await;
+        KeyCacheState {
+            loaded: cache.keys.is_some(),
+            key_count: cache.keys.as_ref().map_or(0, HashMap::len),
+            source: cache.source,
+            refreshed_at: cache.refreshed_at,
+            expires_at: cache.expires_at,
+            shared_cache: self.shared_cache.is_some(),
+            last_error: cache.last_error.clone(),
+        }
+    }
+}
+
+// Récupère le JWKS brut et la durée pendant laquelle il peut être gardé
+async fn fetch_keycloak_certs(jwks_url: &str) -> Result<(String, Duration), BoxError> {
     // Effectuer une requête HTTP pour récupérer les clés
-    let client = Client::new();
-    let response = client.get(&jwks_url).send().await?;
-    let keycloak_certs: KeycloakCerts = response.json().await?;
+    let client = Client::builder().timeout(Duration::from_secs(5)).build()?;
+    let response = client.get(jwks_url).send().await?.error_for_status()?;
+    let max_age = cache_max_age(response.headers())
+        .unwrap_or(REFRESH_INTERVAL)
+        .clamp(MIN_REFRESH_INTERVAL, REFRESH_INTERVAL);
+    Ok((response.text().await?, max_age))
+}
 
-    // Transformer les clés en un format utilisable par la bibliothèque jsonwebtoken
+// Transformer les clés en un format utilisable par la bibliothèque jsonwebtoken
+fn parse_keycloak_keys(certs: &str) -> Result<HashMap<String, DecodingKey>, BoxError> {
+    let keycloak_certs: KeycloakCerts = serde_json::from_str(certs)?;
     let mut keys = HashMap::new();
     for key in keycloak_certs.keys {
         if key.kty == "RSA" {
@@ -61,10 +398,62 @@ pub async fn get_keycloak_keys() -> Result<HashMap<String, DecodingKey>, Box<dyn
             keys.insert(key.kid, decoding_key);
         }
     }
+    Ok(keys)
+}
+
+// Durée de validité annoncée par le `Cache-Control` de Keycloak : `no-cache` et
+// `no-store` valent zéro, `None` quand l'en-tête ne donne pas de `max-age`
+fn cache_max_age(headers: &HeaderMap) -> Option<Duration> {
+    let cache_control = headers.get(CACHE_CONTROL)?.to_str().ok()?;
+    let mut max_age = None;
+    for directive in cache_control.split(',').map(str::trim) {
+        let (name, value) = directive.split_once('=').unwrap_or((directive, ""));
+        match name.to_ascii_lowercase().as_str() {
+            "no-cache" | "no-store" => return Some(Duration::ZERO),
+            "max-age" => {
+                max_age = value
+                    .trim_matches('"')
+                    .parse()
+                    .ok()
+                    .map(Duration::from_secs)
+            }
+            _ => {}
+        }
+    }
+    max_age
+}
+
+// Ajoute jusqu'à 50% d'aléa au délai, pour que les instances ne réessaient pas toutes
+// en même temps
+fn with_jitter(delay: Duration) -> Duration {
+    delay.mul_f64(1.0 + rand::thread_rng().gen_range(0.0..0.5))
+}
 
-    // Mettre à jour le cache
-    cache.keys = keys.clone();
-    cache.last_fetched = Instant::now();
+#[cfg(test)]
+mod tests {
+    use hyper::header::HeaderValue;
 
-    Ok(keys)
+    use super::*;
+
+    fn headers(cache_control: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static(cache_control));
+        headers
+    }
+
+    #[test]
+    fn reads_the_max_age_of_the_cache_control_header() {
+        assert_eq!(
+            cache_max_age(&headers("public, max-age=300")),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(cache_max_age(&headers("no-cache")), Some(Duration::ZERO));
+        assert_eq!(
+            cache_max_age(&headers("max-age=300, no-store")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(cache_max_age(&headers("public")), None);
+        assert_eq!(cache_max_age(&headers("max-age=soon")), None);
+        assert_eq!(cache_max_age(&HeaderMap::new()), None);
+    }
 }
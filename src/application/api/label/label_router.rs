@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        filter::percent_decode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    domain::label::{Label, LabelManager, LabelRepositoryError, LabelTarget},
+};
+
+impl From<LabelRepositoryError> for HttpError<'static> {
+    fn from(value: LabelRepositoryError) -> Self {
+        match value {
+            LabelRepositoryError::LabelNotFound => HttpError::new(ErrorCode::LabelNotFound),
+            LabelRepositoryError::LabelAlreadyExists => {
+                HttpError::new(ErrorCode::LabelAlreadyExists)
+            }
+            LabelRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Labels: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateLabelInput {
+    name: String,
+    #[serde(default)]
+    shared: bool,
+}
+
+#[derive(Serialize)]
+pub struct GetLabelOutput {
+    uid: String,
+    name: String,
+    shared: bool,
+}
+
+impl From<Label> for GetLabelOutput {
+    fn from(value: Label) -> Self {
+        Self {
+            uid: value.uid().to_string(),
+            name: value.name().clone(),
+            shared: value.is_shared(),
+        }
+    }
+}
+
+pub async fn router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    let label_manager = &managers.label_manager;
+    match (method, path) {
+        (&Method::GET, "") => {
+            if !token.permissions().contains(&Permissions::GetSpeech)
+                && !token.permissions().contains(&Permissions::GetPerson)
+            {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let prefix = match query_params.get("prefix") {
+                Some(v) => percent_decode(v)?,
+                None => String::new(),
+            };
+            let quantity = match query_params.get("quantity") {
+                Some(v) => v
+                    .parse::<u16>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?,
+                None => 10,
+            };
+            let labels: Vec<GetLabelOutput> = label_manager
+                .get_labels(&prefix, &token.user_id(), quantity)
+                .await?
+                .into_iter()
+                .map(|l| l.into())
+                .collect();
+            Ok(value::to_value(labels).map_err(|e| {
+                println!(
+                    "An internal error occured while converting labels to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, "") => {
+            if !token.is_authenticated() {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let input: CreateLabelInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let name = input.name.trim();
+            if name.is_empty() {
+                return Err(HttpError::new(ErrorCode::InvalidLabelName));
+            }
+            // Shared labels are visible to everyone, only editors can create them.
+            if input.shared
+                && !token.permissions().contains(&Permissions::UpdateSpeech)
+                && !token.permissions().contains(&Permissions::UpdatePerson)
+            {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let owner = match input.shared {
+                true => None,
+                false => Some(token.user_id()),
+            };
+            let label = Label::new(Uuid::new_v4(), name, owner);
+            label_manager.create_label(label.clone()).await?;
+            Ok(value::to_value(GetLabelOutput::from(label)).map_err(|e| {
+                println!(
+                    "An internal error occured while converting label to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
+
+/// Handles `{uid}/labels` and `{uid}/labels/{label_uid}` sub routes of speeches and persons.
+pub async fn entity_labels_router(
+    target: LabelTarget,
+    label_uid: Option<&str>,
+    method: &Method,
+    token: &AuthToken,
+    label_manager: &LabelManager,
+) -> Result<Value, HttpError<'static>> {
+    let (get_permission, update_permission) = match target {
+        LabelTarget::Speech(_) => (Permissions::GetSpeech, Permissions::UpdateSpeech),
+        LabelTarget::Person(_) => (Permissions::GetPerson, Permissions::UpdatePerson),
+    };
+    let label_uid = match label_uid {
+        Some(label_uid) => {
+            Some(Uuid::parse_str(label_uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?)
+        }
+        None => None,
+    };
+    match (method, label_uid) {
+        (&Method::GET, None) => {
+            if !token.permissions().contains(&get_permission) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let labels: Vec<GetLabelOutput> = label_manager
+                .get_labels_of(target, &token.user_id())
+                .await?
+                .into_iter()
+                .map(|l| l.into())
+                .collect();
+            Ok(value::to_value(labels).map_err(|e| {
+                println!(
+                    "An internal error occured while converting labels to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, Some(label_uid)) => {
+            if !token.permissions().contains(&update_permission) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            label_manager
+                .attach_label(target, &label_uid, &token.user_id())
+                .await?;
+            Ok(Value::Null)
+        }
+        (&Method::DELETE, Some(label_uid)) => {
+            if !token.permissions().contains(&update_permission) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            label_manager
+                .detach_label(target, &label_uid, &token.user_id())
+                .await?;
+            Ok(Value::Null)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use hyper::Method;
+    use serde_json::Value;
+    use uuid::Uuid;
+
+    use super::router;
+    use crate::{
+        application::api::token::{AuthToken, Permissions},
+        domain::label::Label,
+        test_support::test_database,
+    };
+
+    #[tokio::test]
+    async fn labels_are_listed_by_decoded_prefix() {
+        let database = test_database().await;
+        let managers = database.managers();
+        for name in ["Santé publique", "Sécurité"] {
+            managers
+                .label_manager
+                .create_label(Label::new(Uuid::new_v4(), name, None))
+                .await
+                .unwrap();
+        }
+        let query_params = HashMap::from([("prefix".to_owned(), "Sant%C3%A9+p".to_owned())]);
+        let labels = router(
+            "",
+            &query_params,
+            &Method::GET,
+            &AuthToken::_new(None, None, vec![Permissions::GetSpeech], None),
+            Value::Null,
+            &managers,
+        )
+        .await
+        .unwrap();
+        let names: Vec<&str> = labels
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|l| l["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Santé publique"]);
+    }
+}
@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use http_body_util::BodyExt;
+use hyper::{header, Method, Response};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
+
+use super::{
+    error::{ErrorCode, ErrorRendering, HttpError},
+    router::BoxBody,
+};
+
+/// Cost of the requests of a route, each class having its own concurrency limit so the
+/// heavy queries cannot starve the transcript edition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoadClass {
+    /// Reads and edits of a single entity, and the lists.
+    Interactive,
+    /// Aggregations over many speeches or persons.
+    Analytics,
+    /// Whole speeches or person lists sent as files.
+    Export,
+    /// Connections kept open to receive events, never limited.
+    Streaming,
+}
+
+/// Routes whose requests are not interactive, `*` matching any path segment. The other
+/// routes are interactive.
+const ROUTE_LOAD_CLASSES: &[(Method, &str, LoadClass)] = &[
+    (Method::GET, "speech/*/export", LoadClass::Export),
+    (Method::GET, "person/export", LoadClass::Export),
+    (Method::POST, "speech/*/attachments", LoadClass::Export),
+    (Method::GET, "speech/*/analytics", LoadClass::Analytics),
+    (Method::GET, "speech/*/sentiment", LoadClass::Analytics),
+    (Method::GET, "speech/*/keywords", LoadClass::Analytics),
+    (Method::GET, "person/*/stats", LoadClass::Analytics),
+    (Method::GET, "person/*/timeline", LoadClass::Analytics),
+    (Method::GET, "person/*/statements", LoadClass::Analytics),
+    (Method::GET, "person/duplicates", LoadClass::Analytics),
+    (Method::GET, "opendata/summary", LoadClass::Analytics),
+    (Method::GET, "speech/clusters", LoadClass::Analytics),
+    (Method::GET, "speech/compare", LoadClass::Analytics),
+    (Method::GET, "speech/aggregate", LoadClass::Analytics),
+    (Method::GET, "admin/stats", LoadClass::Analytics),
+    (Method::GET, "watchlists/*/speeches", LoadClass::Analytics),
+    (
+        Method::POST,
+        "speech/check-duplicates",
+        LoadClass::Analytics,
+    ),
+    (Method::POST, "speech/imports", LoadClass::Analytics),
+    (Method::POST, "person/bulk", LoadClass::Analytics),
+    (Method::POST, "speech/imports/*/apply", LoadClass::Analytics),
+    (Method::GET, "speech/events", LoadClass::Streaming),
+    (Method::GET, "events/poll", LoadClass::Streaming),
+    (
+        Method::GET,
+        "speech/*/sentences/stream",
+        LoadClass::Streaming,
+    ),
+    (
+        Method::POST,
+        "speech/*/sentences/stream",
+        LoadClass::Streaming,
+    ),
+    (Method::GET, "speech/*/live", LoadClass::Streaming),
+];
+
+/// Finds the load class of the route targeted by the request, `path` being the whole
+/// request path.
+pub fn route_load_class(method: &Method, path: &str) -> LoadClass {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .skip(1)
+        .collect::<Vec<&str>>();
+    ROUTE_LOAD_CLASSES
+        .iter()
+        .find(|(route_method, pattern, _)| {
+            route_method == method && {
+                let pattern = pattern.split('/').collect::<Vec<&str>>();
+                pattern.len() == segments.len()
+                    && pattern
+                        .iter()
+                        .zip(&segments)
+                        .all(|(expected, segment)| *expected == "*" || expected == segment)
+            }
+        })
+        .map(|(_, _, class)| *class)
+        .unwrap_or(LoadClass::Interactive)
+}
+
+/// Number of requests of each class served at the same time, the others being rejected
+/// with a 503.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimits {
+    pub interactive: usize,
+    pub analytics: usize,
+    pub export: usize,
+}
+
+impl Default for ConcurrencyLimits {
+    fn default() -> Self {
+        Self {
+            interactive: 256,
+            analytics: 16,
+            export: 4,
+        }
+    }
+}
+
+/// Delay suggested to the clients rejected because their class is saturated, in seconds.
+const RETRY_AFTER: u64 = 1;
+
+/// Rejects the requests of a class once its limit is reached instead of queuing them.
+#[derive(Clone)]
+pub struct LoadShedder {
+    interactive: Arc<Semaphore>,
+    analytics: Arc<Semaphore>,
+    export: Arc<Semaphore>,
+}
+
+impl LoadShedder {
+    pub fn new(limits: &ConcurrencyLimits) -> Self {
+        Self {
+            interactive: Arc::new(Semaphore::new(limits.interactive)),
+            analytics: Arc::new(Semaphore::new(limits.analytics)),
+            export: Arc::new(Semaphore::new(limits.export)),
+        }
+    }
+
+    /// Takes a slot of the class, released when the permit is dropped. `None` is returned
+    /// for the streaming routes, which take no slot.
+    pub fn try_acquire(
+        &self,
+        class: LoadClass,
+    ) -> Result<Option<OwnedSemaphorePermit>, TryAcquireError> {
+        let semaphore = match class {
+            LoadClass::Interactive => &self.interactive,
+            LoadClass::Analytics => &self.analytics,
+            LoadClass::Export => &self.export,
+            LoadClass::Streaming => return Ok(None),
+        };
+        semaphore.clone().try_acquire_owned().map(Some)
+    }
+}
+
+/// Response sent to a request rejected because its class is saturated.
+pub fn overloaded_response(rendering: &ErrorRendering) -> Response<BoxBody> {
+    let mut response = HttpError::new(ErrorCode::ServerOverloaded).into_response(rendering);
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, RETRY_AFTER.into());
+    response
+}
+
+/// Keeps the slot taken until the body is sent, an export being mostly streamed after its
+/// response is returned.
+pub fn hold_until_sent(
+    response: Response<BoxBody>,
+    permit: Option<OwnedSemaphorePermit>,
+) -> Response<BoxBody> {
+    match permit {
+        Some(permit) => response.map(|body| {
+            body.map_frame(move |frame| {
+                let _ = &permit;
+                frame
+            })
+            .boxed()
+        }),
+        None => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Method;
+
+    use super::{route_load_class, ConcurrencyLimits, LoadClass, LoadShedder};
+
+    #[test]
+    fn saturated_class_does_not_block_the_others() {
+        assert_eq!(
+            route_load_class(&Method::GET, "/api/speech/1234/export"),
+            LoadClass::Export
+        );
+        assert_eq!(
+            route_load_class(&Method::GET, "/api/speech/1234"),
+            LoadClass::Interactive
+        );
+        let shedder = LoadShedder::new(&ConcurrencyLimits {
+            interactive: 1,
+            analytics: 1,
+            export: 1,
+        });
+        let export = shedder.try_acquire(LoadClass::Export).unwrap();
+        assert!(shedder.try_acquire(LoadClass::Export).is_err());
+        assert!(shedder.try_acquire(LoadClass::Interactive).is_ok());
+        assert!(shedder.try_acquire(LoadClass::Streaming).unwrap().is_none());
+        drop(export);
+        assert!(shedder.try_acquire(LoadClass::Export).is_ok());
+    }
+}
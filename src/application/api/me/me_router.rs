@@ -0,0 +1,129 @@
+use std::str::FromStr;
+
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, NOT_FOUND_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    domain::person::PersonRepositoryError,
+};
+
+/// Maximum number of actions checked by a single request.
+const MAX_PERMISSION_CHECKS: usize = 100;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateReadProgressInput {
+    /// Position of the last sentence read, starting at 0.
+    sentence_index: u32,
+}
+
+#[derive(Deserialize)]
+struct CanInput {
+    checks: Vec<PermissionCheckInput>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct PermissionCheckInput {
+    /// Name of a permission, e.g. `DeleteSpeech`.
+    action: String,
+    /// Uid of the speech or person the action applies to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PermissionCheckOutput {
+    #[serde(flatten)]
+    check: PermissionCheckInput,
+    allowed: bool,
+}
+
+/// Whether the user may perform the action, on the resource if one is given. An unknown
+/// action or a resource the user cannot see is denied.
+async fn is_allowed(
+    check: &PermissionCheckInput,
+    token: &AuthToken,
+    managers: &Managers,
+) -> Result<bool, HttpError<'static>> {
+    let permission = match Permissions::from_str(&check.action) {
+        Ok(permission) => permission,
+        Err(_) => return Ok(false),
+    };
+    if !token.permissions().contains(&permission) {
+        return Ok(false);
+    }
+    let resource = match &check.resource {
+        Some(resource) => match Uuid::from_str(resource) {
+            Ok(resource) => resource,
+            Err(_) => return Ok(false),
+        },
+        None => return Ok(true),
+    };
+    match permission {
+        Permissions::GetSpeech | Permissions::UpdateSpeech | Permissions::DeleteSpeech => {
+            Ok(managers.speech_manager.speech_exists(resource).await?)
+        }
+        Permissions::GetPerson | Permissions::UpdatePerson | Permissions::DeletePerson => {
+            match managers.person_manager.get_person_by_id(&resource).await {
+                Ok(_) => Ok(true),
+                Err(PersonRepositoryError::PersonNotFound) => Ok(false),
+                Err(e) => Err(e.into()),
+            }
+        }
+        _ => Ok(true),
+    }
+}
+
+/// Routes about the user calling them.
+pub async fn router(
+    path: &str,
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::POST, ["can"]) => {
+            // Lets the clients show only the actions the user may perform.
+            let input: CanInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            if input.checks.len() > MAX_PERMISSION_CHECKS {
+                return Err(HttpError::with_context(
+                    ErrorCode::InvalidFormat,
+                    format!(
+                        "At most {} actions can be checked at once",
+                        MAX_PERMISSION_CHECKS
+                    ),
+                ));
+            }
+            let mut results = Vec::new();
+            for check in input.checks {
+                let allowed = is_allowed(&check, token, managers).await?;
+                results.push(PermissionCheckOutput { check, allowed });
+            }
+            Ok(json!({ "results": results }))
+        }
+        (&Method::PUT, ["progress", uid]) => {
+            if !token.is_authenticated() || !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let input: UpdateReadProgressInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            managers
+                .speech_manager
+                .set_read_progress(&token.user_id(), uid, input.sentence_index)
+                .await?;
+            Ok(Value::Null)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
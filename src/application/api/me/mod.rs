@@ -0,0 +1 @@
+pub mod me_router;
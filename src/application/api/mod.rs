@@ -1,5 +1,30 @@
+pub mod admin;
+pub mod cache;
+pub mod collection;
+pub mod deadline;
+pub mod error;
+pub mod events;
+pub mod fields;
+pub mod filter;
+pub mod http_log;
+pub mod idempotency;
+#[cfg(feature = "assemblee-nationale")]
+pub mod import;
+pub mod job;
 pub mod keycloak;
+pub mod label;
+pub mod load_shed;
+pub mod me;
+pub mod opendata;
+pub mod organization;
 pub mod person;
+pub mod precondition;
+#[cfg(feature = "redis")]
+pub mod redis_keys;
 pub mod router;
 pub mod speech;
+pub mod sse;
+pub mod tag;
 pub mod token;
+pub mod validation;
+pub mod watchlist;
@@ -0,0 +1 @@
+pub mod opendata_router;
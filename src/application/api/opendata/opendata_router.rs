@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use hyper::{header, Method, Response};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::application::api::router::{
+    full, HttpError, Managers, RouteResponse, INTERNAL_ERROR, NOT_FOUND_ERROR,
+};
+
+/// License the open data is published under, sent in the body and in a `Link` header.
+const LICENSE_URL: &str = "https://www.etalab.gouv.fr/licence-ouverte-open-licence";
+/// The summary is computed at most once per period, whoever asks for it.
+const SUMMARY_CACHE_DURATION: Duration = Duration::from_secs(3600);
+
+lazy_static! {
+    static ref SUMMARY_CACHE: Mutex<Option<(Instant, String)>> = Mutex::new(None);
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetOpenDataSummary {
+    license: &'static str,
+    generated_at: String,
+    speeches_by_media_month: Vec<GetMonthlySpeechCount>,
+}
+
+#[derive(Serialize)]
+struct GetMonthlySpeechCount {
+    media: String,
+    /// Month formatted as YYYY-MM.
+    month: String,
+    speeches: u64,
+}
+
+/// Public routes, answered without checking the token. They only expose aggregated
+/// statistics from which no person can be identified.
+pub async fn router(
+    path: &str,
+    method: &Method,
+    managers: &Managers,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::GET, ["summary"]) => {
+            let mut cache = SUMMARY_CACHE.lock().await;
+            let body = match cache.as_ref() {
+                Some((computed_at, body)) if computed_at.elapsed() < SUMMARY_CACHE_DURATION => {
+                    body.clone()
+                }
+                _ => {
+                    let body = build_summary(managers, Utc::now()).await?;
+                    *cache = Some((Instant::now(), body.clone()));
+                    body
+                }
+            };
+            let max_age = SUMMARY_CACHE_DURATION.as_secs();
+            Ok(RouteResponse::Raw(
+                Response::builder()
+                    .status(200)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(
+                        header::CACHE_CONTROL,
+                        format!("public, max-age={}", max_age),
+                    )
+                    .header(header::LINK, format!("<{}>; rel=\"license\"", LICENSE_URL))
+                    .body(full(body))
+                    .expect("Should not fail"),
+            ))
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
+
+async fn build_summary(
+    managers: &Managers,
+    generated_at: DateTime<Utc>,
+) -> Result<String, HttpError<'static>> {
+    let speeches_by_media_month = managers
+        .speech_manager
+        .count_speech_by_media_month()
+        .await?
+        .into_iter()
+        .map(|count| GetMonthlySpeechCount {
+            media: count.media().clone(),
+            month: count.month().format("%Y-%m").to_string(),
+            speeches: count.speeches(),
+        })
+        .collect();
+    let summary = GetOpenDataSummary {
+        license: LICENSE_URL,
+        generated_at: generated_at.to_rfc3339(),
+        speeches_by_media_month,
+    };
+    serde_json::to_string(&summary).map_err(|e| {
+        println!(
+            "An internal error occured while converting the open data summary: {:?}",
+            e
+        );
+        INTERNAL_ERROR
+    })
+}
@@ -0,0 +1,156 @@
+use chrono::Utc;
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    domain::organization::{Organization, OrganizationRepositoryError},
+};
+
+impl From<OrganizationRepositoryError> for HttpError<'static> {
+    fn from(value: OrganizationRepositoryError) -> Self {
+        match value {
+            OrganizationRepositoryError::OrganizationNotFound => {
+                HttpError::new(ErrorCode::OrganizationNotFound)
+            }
+            OrganizationRepositoryError::OrganizationAlreadyExists => {
+                HttpError::new(ErrorCode::OrganizationAlreadyExists)
+            }
+            OrganizationRepositoryError::OrganizationNotEmpty => {
+                HttpError::new(ErrorCode::OrganizationNotEmpty)
+            }
+            OrganizationRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Organizations: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OrganizationInput {
+    name: String,
+}
+
+impl OrganizationInput {
+    fn name(&self) -> Result<&str, HttpError<'static>> {
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(HttpError::new(ErrorCode::InvalidOrganizationName));
+        }
+        Ok(name)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetOrganizationOutput {
+    uid: String,
+    name: String,
+    created_at: String,
+}
+
+impl From<Organization> for GetOrganizationOutput {
+    fn from(value: Organization) -> Self {
+        Self {
+            uid: value.uid().to_string(),
+            name: value.name().clone(),
+            created_at: value.created_at().to_rfc3339(),
+        }
+    }
+}
+
+/// Administration of the organizations hosted on the deployment, reserved to the users
+/// allowed to manage them whatever their own organization.
+pub async fn router(
+    path: &str,
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    if !token
+        .permissions()
+        .contains(&Permissions::ManageOrganizations)
+    {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let organization_manager = &managers.organization_manager;
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::GET, [""]) => {
+            let organizations: Vec<GetOrganizationOutput> = organization_manager
+                .get_organizations()
+                .await?
+                .into_iter()
+                .map(|o| o.into())
+                .collect();
+            Ok(value::to_value(organizations).map_err(|e| {
+                println!(
+                    "An internal error occured while converting organizations to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, [""]) => {
+            let input: OrganizationInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let organization = Organization::new(Uuid::new_v4(), input.name()?, Utc::now());
+            organization_manager
+                .create_organization(organization.clone())
+                .await?;
+            Ok(
+                value::to_value(GetOrganizationOutput::from(organization)).map_err(|e| {
+                    println!(
+                        "An internal error occured while converting organization to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?,
+            )
+        }
+        (&Method::GET, [uid]) => {
+            let uid = parse_organization_uid(uid)?;
+            let organization: GetOrganizationOutput = organization_manager
+                .get_organization_by_id(&uid)
+                .await?
+                .into();
+            Ok(value::to_value(organization).map_err(|e| {
+                println!(
+                    "An internal error occured while converting organization to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::PUT, [uid]) => {
+            let uid = parse_organization_uid(uid)?;
+            let input: OrganizationInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            organization_manager
+                .rename_organization(&uid, input.name()?)
+                .await?;
+            Ok(Value::Null)
+        }
+        (&Method::DELETE, [uid]) => {
+            let uid = parse_organization_uid(uid)?;
+            organization_manager.delete_organization(&uid).await?;
+            Ok(Value::Null)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
+
+fn parse_organization_uid(uid: &str) -> Result<Uuid, HttpError<'static>> {
+    Uuid::parse_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))
+}
@@ -0,0 +1,129 @@
+use std::io;
+
+use bytes::Bytes;
+use futures_util::stream;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::{body::Frame, header, Response};
+use tokio::sync::mpsc;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{BoxBody, HttpError},
+    },
+    domain::person::{Person, PersonManager},
+};
+
+/// Number of persons fetched from the repository for each chunk of the export.
+const EXPORT_BATCH_SIZE: u16 = 500;
+
+const CSV_HEADER: &str = "uid,name,first_name,birth_date,trust_score,lie_quantity,speech_count\r\n";
+
+/// Checks the requested format, CSV being the only one available for the persons.
+pub fn check_export_format(format: Option<&String>) -> Result<(), HttpError<'static>> {
+    match format.map(|f| f.as_str()) {
+        None | Some("csv") => Ok(()),
+        Some(_) => Err(HttpError::new(ErrorCode::InvalidExportFormat)),
+    }
+}
+
+/// Batches rendered ahead of the client, the export waits for it past this number.
+const EXPORT_BUFFERED_BATCHES: usize = 2;
+
+/// Builds the CSV download of every person. The persons are fetched batch by batch as
+/// the client reads the body, so the whole list is never held in memory. The first batch
+/// is fetched beforehand so a failing repository is still reported with an error status;
+/// a later failure ends the body with an error, the client seeing an aborted download
+/// rather than a complete one.
+pub async fn export_people(
+    person_manager: PersonManager,
+) -> Result<Response<BoxBody>, HttpError<'static>> {
+    let mut batch = person_manager
+        .get_people_with_speech_count(None, EXPORT_BATCH_SIZE)
+        .await?;
+    let (sender, receiver) = mpsc::channel::<Result<Bytes, io::Error>>(EXPORT_BUFFERED_BATCHES);
+    tokio::spawn(async move {
+        if sender.send(Ok(Bytes::from(CSV_HEADER))).await.is_err() {
+            return;
+        }
+        loop {
+            let chunk = batch
+                .iter()
+                .map(|(person, speech_count)| render_row(person, *speech_count))
+                .collect::<String>();
+            // Sending fails once the client is gone.
+            if sender.send(Ok(Bytes::from(chunk))).await.is_err() {
+                return;
+            }
+            let last = match batch.last() {
+                Some((person, _)) if batch.len() == EXPORT_BATCH_SIZE as usize => *person.uid(),
+                _ => return,
+            };
+            batch = match person_manager
+                .get_people_with_speech_count(Some(last), EXPORT_BATCH_SIZE)
+                .await
+            {
+                Ok(batch) => batch,
+                Err(e) => {
+                    println!(
+                        "An internal error occured while exporting persons after {}: {:?}",
+                        last, e
+                    );
+                    let _ = sender
+                        .send(Err(io::Error::other("The export of the persons failed")))
+                        .await;
+                    return;
+                }
+            };
+        }
+    });
+    let body = stream::unfold(receiver, |mut receiver| async move {
+        let chunk = receiver.recv().await?;
+        Some((chunk.map(Frame::data), receiver))
+    });
+    Ok(Response::builder()
+        .status(200)
+        .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"people.csv\"",
+        )
+        .body(BodyExt::boxed(StreamBody::new(body)))
+        .expect("Should not fail"))
+}
+
+fn render_row(person: &Person, speech_count: u64) -> String {
+    format!(
+        "{},{},{},{},{},{},{}\r\n",
+        person.uid(),
+        escape_field(person.name()),
+        escape_field(person.first_name()),
+        person.birth_date(),
+        person.trust_score(),
+        person.lie_quantity(),
+        speech_count
+    )
+}
+
+/// Quotes the field when it holds a separator, a quote or a line break, doubling its
+/// quotes as RFC 4180 requires.
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_field;
+
+    #[test]
+    fn escape_field_quotes_only_when_needed() {
+        assert_eq!(escape_field("Dupont"), "Dupont");
+        assert_eq!(escape_field("Dupont, Jean"), "\"Dupont, Jean\"");
+        assert_eq!(escape_field("Jean \"JJ\""), "\"Jean \"\"JJ\"\"\"");
+        assert_eq!(escape_field("Jean\nPierre"), "\"Jean\nPierre\"");
+    }
+}
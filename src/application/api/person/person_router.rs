@@ -1,37 +1,86 @@
 use std::{collections::HashMap, str::FromStr};
 
-use chrono::NaiveDate;
-use hyper::Method;
+use chrono::{NaiveDate, Utc};
+use hyper::{
+    header::{self, HeaderMap},
+    Method,
+};
 use serde::Deserialize;
-use serde_json::{value, Value};
+use serde_json::{json, value, Value};
 use uuid::Uuid;
 
+use super::export::{check_export_format, export_people};
 use crate::{
     application::api::{
-        router::{HttpError, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        error::{ErrorCode, Language},
+        fields::FieldSelection,
+        filter::{extract_filter_spec, percent_decode},
+        label::label_router::entity_labels_router,
+        precondition::ExpectedVersion,
+        router::{
+            extract_include_deleted, extract_include_moderators, extract_language,
+            extract_on_conflict, extract_uid_array_in_query, HttpError, Managers, RouteResponse,
+            ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR,
+        },
         token::{AuthToken, Permissions},
+        validation::{field, FieldProblem, LocalizedFieldError, Validation},
+    },
+    domain::{
+        label::LabelTarget,
+        person::{DeleteStrategy, Person, PersonDuplicate, PersonFilter, PersonRepositoryError},
+        speech::{analytics::TimelineGranularity, statement::StatementQuery},
     },
-    domain::person::{Person, PersonManager, PersonRepositoryError},
 };
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct CreatePersonInput {
+pub struct CreatePersonInput {
     name: String,
     first_name: String,
     birth_date: String,
 }
+impl CreatePersonInput {
+    pub fn new(name: &str, first_name: &str, birth_date: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            first_name: first_name.to_owned(),
+            birth_date: birth_date.to_owned(),
+        }
+    }
+
+    /// Checks the fields of the person found at `path` in the body.
+    pub fn validate(&self, validation: &mut Validation, path: &str) {
+        validation.check(
+            !self.name.trim().is_empty(),
+            field(path, "name"),
+            FieldProblem::EmptyName,
+        );
+        validation.check(
+            !self.first_name.trim().is_empty(),
+            field(path, "firstName"),
+            FieldProblem::EmptyFirstName,
+        );
+        match NaiveDate::from_str(&self.birth_date) {
+            Ok(date) => validation.check(
+                date <= Utc::now().date_naive(),
+                field(path, "birthDate"),
+                FieldProblem::FutureBirthDate,
+            ),
+            Err(e) => validation.reject(
+                field(path, "birthDate"),
+                FieldProblem::InvalidBirthDate,
+                e.to_string(),
+            ),
+        }
+    }
+}
+
 impl TryFrom<CreatePersonInput> for Person {
     type Error = HttpError<'static>;
 
     fn try_from(value: CreatePersonInput) -> Result<Self, Self::Error> {
-        let birth_date = NaiveDate::from_str(&value.birth_date).map_err(|_| {
-            HttpError::new(
-                400,
-                "InvalidBirthDate",
-                "The birth date supplied has an invalid format",
-            )
-        })?;
+        let birth_date = NaiveDate::from_str(&value.birth_date)
+            .map_err(|_| HttpError::new(ErrorCode::InvalidBirthDate))?;
         Ok(Person::new(
             Uuid::new_v4(),
             &value.name,
@@ -43,6 +92,33 @@ impl TryFrom<CreatePersonInput> for Person {
     }
 }
 
+/// Maximum number of persons sent by a single bulk creation.
+const MAX_BULK_PERSONS: usize = 1000;
+
+/// Outcome of a person of a bulk creation, at the same index as the person in the body.
+#[derive(serde::Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum GetBulkPersonResult {
+    Created {
+        uid: String,
+    },
+    /// A person with the same identity is already stored, `uid` being its uid.
+    Duplicate {
+        uid: String,
+    },
+    Invalid {
+        errors: Vec<LocalizedFieldError>,
+    },
+}
+
+#[derive(serde::Serialize)]
+struct GetBulkPersonOutput {
+    created: usize,
+    duplicates: usize,
+    invalid: usize,
+    results: Vec<GetBulkPersonResult>,
+}
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 struct GetPeopleOutput {
@@ -50,6 +126,16 @@ struct GetPeopleOutput {
     nb_person: u64,
 }
 
+/// Fields of `GetPersonOutput`, selectable with the `fields` query parameter.
+const PERSON_FIELDS: &[&str] = &[
+    "uid",
+    "name",
+    "firstName",
+    "birthDate",
+    "trustScore",
+    "version",
+];
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 struct GetPersonOutput {
@@ -58,6 +144,8 @@ struct GetPersonOutput {
     first_name: String,
     birth_date: String,
     trust_score: u8,
+    /// Version to send back in the `If-Match` header of an update.
+    version: u32,
 }
 
 impl From<Person> for GetPersonOutput {
@@ -68,6 +156,32 @@ impl From<Person> for GetPersonOutput {
             first_name: value.first_name().clone(),
             birth_date: value.birth_date().to_string(),
             trust_score: value.trust_score(),
+            version: value.version(),
+        };
+    }
+}
+
+/// Probable duplicate of a person, with the path merging it into the person.
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetPersonDuplicateOutput {
+    person: GetPersonOutput,
+    duplicate: GetPersonOutput,
+    score: f64,
+    merge: String,
+}
+
+impl From<PersonDuplicate> for GetPersonDuplicateOutput {
+    fn from(value: PersonDuplicate) -> Self {
+        return Self {
+            merge: format!(
+                "/api/person/{}/merge/{}",
+                value.person.uid(),
+                value.duplicate.uid()
+            ),
+            person: value.person.into(),
+            duplicate: value.duplicate.into(),
+            score: value.score,
         };
     }
 }
@@ -75,13 +189,14 @@ impl From<Person> for GetPersonOutput {
 impl From<PersonRepositoryError> for HttpError<'static> {
     fn from(value: PersonRepositoryError) -> Self {
         match value {
-            PersonRepositoryError::PersonNotFound => {
-                HttpError::new(404, "PersonNotFound", "The person requested is not found")
+            PersonRepositoryError::PersonNotFound => HttpError::new(ErrorCode::PersonNotFound),
+            PersonRepositoryError::PersonAlreadyExists => {
+                HttpError::new(ErrorCode::PersonAlreadyExists)
             }
-            PersonRepositoryError::PersonAlreadyExists => HttpError::new(
-                409,
-                "PersonAlreadyExists",
-                "The person you try to create already exists.",
+            PersonRepositoryError::VersionMismatch => HttpError::new(ErrorCode::VersionConflict),
+            PersonRepositoryError::PersonReferenced(speeches) => HttpError::with_context(
+                ErrorCode::PersonReferenced,
+                format!("The person speaks in {} speeches", speeches),
             ),
             PersonRepositoryError::InternalError(e) => {
                 println!(
@@ -94,33 +209,215 @@ impl From<PersonRepositoryError> for HttpError<'static> {
     }
 }
 
+/// Reads the `strategy` of a deletion, the person is only deleted when no speech
+/// references it by default.
+fn extract_delete_strategy(
+    query_params: &HashMap<String, String>,
+) -> Result<DeleteStrategy, HttpError<'static>> {
+    match query_params.get("strategy") {
+        Some(strategy) => DeleteStrategy::try_from(strategy.as_str())
+            .map_err(|_| HttpError::new(ErrorCode::InvalidStrategyParam)),
+        None => Ok(DeleteStrategy::default()),
+    }
+}
+
+/// Reads the filters shared by the person list and count routes.
+fn extract_person_filter(
+    query_params: &HashMap<String, String>,
+    token: &AuthToken,
+) -> Result<PersonFilter, HttpError<'static>> {
+    Ok(PersonFilter {
+        labels: extract_uid_array_in_query("labels", query_params)?,
+        label_reader: token.user_id(),
+        spec: extract_filter_spec(query_params)?,
+        include_deleted: extract_include_deleted(query_params, token)?,
+    })
+}
+
+/// Reads the text searched in the `q` query parameter, the days of the speeches in `from`
+/// and `to`, both included, and the language of the sentences in `lang`.
+fn extract_statement_query(
+    query_params: &HashMap<String, String>,
+) -> Result<StatementQuery, HttpError<'static>> {
+    let text = match query_params.get("q") {
+        Some(q) => percent_decode(q)?.trim().to_owned(),
+        None => String::new(),
+    };
+    if text.is_empty() {
+        return Err(HttpError::new(ErrorCode::InvalidSearchParam));
+    }
+    let day = |name: &str| -> Result<Option<NaiveDate>, HttpError<'static>> {
+        query_params
+            .get(name)
+            .map(|v| NaiveDate::from_str(v))
+            .transpose()
+            .map_err(|_| HttpError::new(ErrorCode::InvalidDateRangeParam))
+    };
+    let (from, to) = (day("from")?, day("to")?);
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(HttpError::new(ErrorCode::InvalidDateRangeParam));
+        }
+    }
+    Ok(StatementQuery {
+        text,
+        from,
+        to,
+        language: extract_language(query_params)?,
+    })
+}
+
+/// Routes an anonymous user may read in the public read mode.
+fn is_public_route(method: &Method, path: &[&str]) -> bool {
+    match (method, path) {
+        (&Method::GET, [""] | ["count"]) => true,
+        (&Method::GET, [uid]) => Uuid::from_str(uid).is_ok(),
+        _ => false,
+    }
+}
+
 pub async fn router(
     path: &str,
     query_params: &HashMap<String, String>,
     method: &Method,
+    headers: &HeaderMap,
     token: &AuthToken,
     body: Value,
-    person_manager: &PersonManager,
-) -> Result<Value, HttpError<'static>> {
-    match (method, path) {
-        (&Method::POST, "") => {
+    managers: &Managers,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let person_manager = &managers.person_manager;
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    if token.is_public_reader() && !is_public_route(method, &splitted_path) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    match (method, splitted_path.as_slice()) {
+        (&Method::POST, [""]) => {
             if !token.permissions().contains(&Permissions::CreatePerson) {
                 return Err(ACCESS_DENIED_ERROR);
             }
-            let create_person_input: CreatePersonInput =
-                serde_json::from_value(body).map_err(|_| {
-                    HttpError::new(
-                        400,
-                        "InvalidFormat",
-                        "The body format is invalid. Please refer to the documentation",
+            let create_person_input: CreatePersonInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let mut validation = Validation::default();
+            create_person_input.validate(&mut validation, "");
+            validation.into_result()?;
+            let on_conflict = extract_on_conflict(query_params)?;
+            let person = create_person_input.try_into()?;
+            match on_conflict {
+                Some(on_conflict) => {
+                    let outcome = person_manager.upsert_person(person, on_conflict).await?;
+                    Ok(
+                        json!({ "uid": outcome.uid().to_string(), "outcome": outcome.to_string() })
+                            .into(),
                     )
-                })?;
-            person_manager
-                .create_person(create_person_input.try_into()?)
-                .await?;
-            Ok(Value::Null)
+                }
+                None => {
+                    person_manager.create_person(person).await?;
+                    Ok(Value::Null.into())
+                }
+            }
+        }
+        (&Method::POST, ["bulk"]) => {
+            if !token.permissions().contains(&Permissions::CreatePerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let inputs: Vec<Value> = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            if inputs.len() > MAX_BULK_PERSONS {
+                return Err(HttpError::with_context(
+                    ErrorCode::InvalidFormat,
+                    format!(
+                        "At most {} persons can be created at once",
+                        MAX_BULK_PERSONS
+                    ),
+                ));
+            }
+            // The invalid persons are reported and the others created anyway.
+            let language = Language::from_accept_language(
+                headers
+                    .get(header::ACCEPT_LANGUAGE)
+                    .and_then(|v| v.to_str().ok()),
+            );
+            let mut results = Vec::with_capacity(inputs.len());
+            let mut people = Vec::new();
+            for input in inputs {
+                let mut validation = Validation::default();
+                match serde_json::from_value::<CreatePersonInput>(input) {
+                    Ok(input) => {
+                        input.validate(&mut validation, "");
+                        if validation.is_valid() {
+                            people.push(Person::try_from(input)?);
+                            results.push(None);
+                            continue;
+                        }
+                    }
+                    Err(e) => validation.reject("", FieldProblem::InvalidFormat, e.to_string()),
+                }
+                results.push(Some(GetBulkPersonResult::Invalid {
+                    errors: validation
+                        .into_errors()
+                        .iter()
+                        .map(|error| error.localize(language))
+                        .collect(),
+                }));
+            }
+            let mut stored = person_manager
+                .create_people(&people)
+                .await?
+                .into_iter()
+                .zip(&people);
+            let mut output = GetBulkPersonOutput {
+                created: 0,
+                duplicates: 0,
+                invalid: 0,
+                results: Vec::with_capacity(results.len()),
+            };
+            for result in results {
+                let result = match result {
+                    Some(invalid) => {
+                        output.invalid += 1;
+                        invalid
+                    }
+                    None => match stored.next() {
+                        Some((Some(uid), person)) if &uid == person.uid() => {
+                            output.created += 1;
+                            GetBulkPersonResult::Created {
+                                uid: uid.to_string(),
+                            }
+                        }
+                        Some((Some(uid), _)) => {
+                            output.duplicates += 1;
+                            GetBulkPersonResult::Duplicate {
+                                uid: uid.to_string(),
+                            }
+                        }
+                        Some((None, _)) => {
+                            output.invalid += 1;
+                            let mut validation = Validation::default();
+                            validation.check(false, "", FieldProblem::DeletedIdentity);
+                            GetBulkPersonResult::Invalid {
+                                errors: validation
+                                    .into_errors()
+                                    .iter()
+                                    .map(|error| error.localize(language))
+                                    .collect(),
+                            }
+                        }
+                        None => return Err(INTERNAL_ERROR),
+                    },
+                };
+                output.results.push(result);
+            }
+            Ok(value::to_value(output)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting the bulk creation to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
         }
-        (&Method::GET, "") => {
+        (&Method::GET, [""]) => {
             if !token.permissions().contains(&Permissions::GetPerson) {
                 return Err(ACCESS_DENIED_ERROR);
             }
@@ -133,21 +430,15 @@ pub async fn router(
                 Some(v) => v,
                 None => &"10".to_owned(),
             };
-            let page = page_raw.parse::<u16>().map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidPageParam",
-                    "The page parameter provided must be an integer > 0",
-                )
-            })?;
-            let quantity = quantity_raw.parse::<u16>().map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidQuantityParam",
-                    "The quantity parameter provided must be an integer > 0",
-                )
-            })?;
-            let get_people_response = person_manager.get_people(page, quantity).await?;
+            let page = page_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidPageParam))?;
+            let quantity = quantity_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?;
+            let filter = extract_person_filter(query_params, token)?;
+            let fields = FieldSelection::from_query(query_params, PERSON_FIELDS)?;
+            let get_people_response = person_manager.get_people(page, quantity, &filter).await?;
             let people: Vec<GetPersonOutput> = get_people_response
                 .people
                 .into_iter()
@@ -157,26 +448,130 @@ pub async fn router(
                 people,
                 nb_person: get_people_response.nb_person,
             };
-            return Ok(value::to_value(json_response).map_err(|e| {
+            let mut response_body = value::to_value(json_response).map_err(|e| {
                 println!(
                     "An internal error occured while converting persons to value: {:?}",
                     e
                 );
                 INTERNAL_ERROR
-            })?);
+            })?;
+            // The fields select those of each person, not of the page.
+            if let Some(people) = response_body.get_mut("people") {
+                *people = fields.project(people.take());
+            }
+            return Ok(response_body.into());
+        }
+        (&Method::GET, ["count"]) => {
+            if !token.permissions().contains(&Permissions::GetPerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let filter = extract_person_filter(query_params, token)?;
+            let count = person_manager.count_people(&filter).await?;
+            Ok(json!({ "count": count }).into())
         }
-        (&Method::GET, _) => {
+        (&Method::GET, ["export"]) => {
+            if !token.permissions().contains(&Permissions::GetPerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            check_export_format(query_params.get("format"))?;
+            Ok(RouteResponse::Raw(
+                export_people(person_manager.clone()).await?,
+            ))
+        }
+        (&Method::GET, ["duplicates"]) => {
+            if !token.permissions().contains(&Permissions::Admin) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Pairs of persons to review before merging them
+            let quantity = match query_params.get("quantity") {
+                Some(v) => v
+                    .parse::<u16>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?,
+                None => 50,
+            };
+            let duplicates: Vec<GetPersonDuplicateOutput> = person_manager
+                .find_duplicate_people(quantity)
+                .await?
+                .into_iter()
+                .map(GetPersonDuplicateOutput::from)
+                .collect();
+            Ok(value::to_value(duplicates)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting duplicates to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::GET, [uid]) => {
             if !token.permissions().contains(&Permissions::GetPerson) {
                 return Err(ACCESS_DENIED_ERROR);
             }
             // Get a specific person
-            let uid_proposed = Uuid::from_str(path).map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidUID",
-                    "The UID you provided seems not to ba a valid UUIDv4",
-                )
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let fields = FieldSelection::from_query(query_params, PERSON_FIELDS)?;
+            let person_found: GetPersonOutput =
+                person_manager.get_person_by_id(&uid_proposed).await?.into();
+            let response_body = value::to_value(person_found).map_err(|e| {
+                println!(
+                    "An internal error occured while converting person to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
             })?;
+            Ok(fields.project(response_body).into())
+        }
+        (&Method::PUT, [uid]) => {
+            if !token.permissions().contains(&Permissions::UpdatePerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Update the identity of a specific person
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let expected_version = ExpectedVersion::from_request(headers, &body)?;
+            let update_person_input: CreatePersonInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let mut validation = Validation::default();
+            update_person_input.validate(&mut validation, "");
+            validation.into_result()?;
+            let input: Person = update_person_input.try_into()?;
+            // The trust score and the lie quantity are not editable by the client.
+            let current = person_manager.get_person_by_id(&uid_proposed).await?;
+            let person = Person::new(
+                uid_proposed,
+                input.name(),
+                input.first_name(),
+                *input.birth_date(),
+                current.trust_score(),
+                current.lie_quantity(),
+            );
+            person_manager
+                .update_person(person, Some(expected_version.version()))
+                .await
+                .map_err(|e| expected_version.map_error(e.into()))?;
+            Ok(Value::Null.into())
+        }
+        (&Method::POST, [uid, "merge", duplicate_uid]) => {
+            // The duplicate is deleted once merged.
+            if !token.permissions().contains(&Permissions::UpdatePerson)
+                || !token.permissions().contains(&Permissions::DeletePerson)
+            {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let duplicate_uid =
+                Uuid::from_str(duplicate_uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            if uid_proposed == duplicate_uid {
+                return Err(HttpError::new(ErrorCode::PersonMergedIntoItself));
+            }
+            let speeches = person_manager
+                .merge_person(&uid_proposed, &duplicate_uid)
+                .await?;
+            managers.speech_manager.speakers_changed(&speeches).await?;
             let person_found: GetPersonOutput =
                 person_manager.get_person_by_id(&uid_proposed).await?.into();
             let response_body = value::to_value(person_found).map_err(|e| {
@@ -186,23 +581,250 @@ pub async fn router(
                 );
                 INTERNAL_ERROR
             })?;
-            Ok(response_body)
+            Ok(response_body.into())
         }
-        (&Method::DELETE, _) => {
+        (&Method::GET, [uid, "stats"]) => {
+            if !token.permissions().contains(&Permissions::GetPerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Talk-time of the person across their speeches
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            person_manager.get_person_by_id(&uid_proposed).await?;
+            let stats = managers
+                .speech_manager
+                .get_speaker_stats(uid_proposed, extract_include_moderators(query_params)?)
+                .await?;
+            Ok(json!({
+                "speeches": stats.speeches(),
+                "sentences": stats.sentences(),
+                "words": stats.words(),
+                "spokenDuration": stats.spoken_duration(),
+            })
+            .into())
+        }
+        (&Method::GET, [uid, "timeline"]) => {
+            if !token.permissions().contains(&Permissions::GetPerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Activity of the person by period, e.g. to chart it over a legislature
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let granularity = match query_params.get("granularity") {
+                Some(v) => TimelineGranularity::try_from(v.as_str())
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidGranularityParam))?,
+                None => TimelineGranularity::default(),
+            };
+            person_manager.get_person_by_id(&uid_proposed).await?;
+            let periods: Vec<Value> = managers
+                .speech_manager
+                .get_speaker_timeline(
+                    uid_proposed,
+                    granularity,
+                    extract_include_moderators(query_params)?,
+                )
+                .await?
+                .iter()
+                .map(|period| {
+                    json!({
+                        "start": period.start().to_string(),
+                        "speeches": period.speeches(),
+                        "sentences": period.sentences(),
+                    })
+                })
+                .collect();
+            Ok(json!({
+                "granularity": granularity.to_string(),
+                "periods": periods,
+            })
+            .into())
+        }
+        (&Method::GET, [uid, "statements"]) => {
+            if !token.permissions().contains(&Permissions::GetPerson)
+                || !token.permissions().contains(&Permissions::GetSpeech)
+            {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Sentences of the person about a subject, across their speeches
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let query = extract_statement_query(query_params)?;
+            let page = match query_params.get("page") {
+                Some(v) => v
+                    .parse::<u16>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidPageParam))?,
+                None => 0,
+            };
+            let quantity = match query_params.get("quantity") {
+                Some(v) => v
+                    .parse::<u16>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?,
+                None => 10,
+            };
+            person_manager.get_person_by_id(&uid_proposed).await?;
+            let statements: Vec<Value> = managers
+                .speech_manager
+                .search_statements(uid_proposed, &query, page, quantity)
+                .await?
+                .iter()
+                .map(|statement| {
+                    json!({
+                        "uid": statement.sentence().uid().to_string(),
+                        "text": statement.sentence().text(),
+                        "index": statement.index(),
+                        "role": statement.role().to_string(),
+                        "speech": {
+                            "uid": statement.speech_uid().to_string(),
+                            "name": statement.speech_name(),
+                            "date": statement.speech_date().to_rfc3339(),
+                            "media": statement.media(),
+                        },
+                    })
+                })
+                .collect();
+            Ok(json!({ "statements": statements }).into())
+        }
+        (&Method::DELETE, [uid]) => {
             if !token.permissions().contains(&Permissions::DeletePerson) {
                 return Err(ACCESS_DENIED_ERROR);
             }
             // Delete a specific person
-            let uid_proposed = Uuid::from_str(path).map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidUID",
-                    "The UID you provided seems not to ba a valid UUIDv4",
-                )
-            })?;
-            person_manager.delete_person(&uid_proposed).await?;
-            Ok(Value::Null)
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let strategy = extract_delete_strategy(query_params)?;
+            person_manager
+                .delete_person(&uid_proposed, strategy)
+                .await?;
+            Ok(Value::Null.into())
+        }
+        (method, [uid, "labels", label_uid @ ..]) if label_uid.len() <= 1 => {
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            entity_labels_router(
+                LabelTarget::Person(uid_proposed),
+                label_uid.first().copied(),
+                method,
+                token,
+                &managers.label_manager,
+            )
+            .await
+            .map(RouteResponse::from)
+        }
+        (&Method::POST, [uid, "restore"]) => {
+            if !token.permissions().contains(&Permissions::DeletePerson) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Restore a soft deleted person
+            let uid_proposed =
+                Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            person_manager.restore_person(&uid_proposed).await?;
+            Ok(Value::Null.into())
         }
         (_, _) => return Err(NOT_FOUND_ERROR),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use hyper::{header::HeaderMap, Method};
+    use serde_json::{json, Value};
+
+    use super::router;
+    use crate::{
+        application::api::{
+            error::ErrorCode,
+            router::{HttpError, Managers, RouteResponse},
+            token::{AuthToken, Permissions},
+        },
+        test_support::{test_database, PersonBuilder},
+    };
+
+    async fn get_duplicates(
+        managers: &Managers,
+        permission: Permissions,
+    ) -> Result<RouteResponse, HttpError<'static>> {
+        router(
+            "duplicates",
+            &HashMap::new(),
+            &Method::GET,
+            &HeaderMap::new(),
+            &AuthToken::_new(None, None, vec![permission], None),
+            Value::Null,
+            managers,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn duplicates_are_reported_to_the_administrators() {
+        let database = test_database().await;
+        let managers = database.managers();
+        for first_name in ["Jean-Luc", "Jean Luc"] {
+            database
+                .create_person(
+                    PersonBuilder::new()
+                        .with_name("Mélenchon")
+                        .with_first_name(first_name),
+                )
+                .await;
+        }
+        let denied = get_duplicates(&managers, Permissions::GetPerson).await;
+        assert_eq!(
+            denied.err().map(|e| e.code()),
+            Some(ErrorCode::AccessDenied)
+        );
+        let duplicates = match get_duplicates(&managers, Permissions::Admin).await {
+            Ok(RouteResponse::Json(Value::Array(duplicates))) => duplicates,
+            _ => panic!("The duplicates are not listed"),
+        };
+        assert_eq!(duplicates.len(), 1);
+        assert!(duplicates[0]["merge"]
+            .as_str()
+            .unwrap()
+            .starts_with("/api/person/"));
+    }
+
+    #[tokio::test]
+    async fn bulk_creation_reports_each_person() {
+        let database = test_database().await;
+        let managers = database.managers();
+        let stored = database
+            .create_person(
+                PersonBuilder::new()
+                    .with_name("Dupont")
+                    .with_first_name("Jean")
+                    .with_birth_date("1970-01-01"),
+            )
+            .await;
+        let body = json!([
+            { "name": "Durand", "firstName": "Marie", "birthDate": "1980-02-03" },
+            { "name": "Dupont", "firstName": "Jean", "birthDate": "1970-01-01" },
+            { "name": " ", "firstName": "Paul", "birthDate": "1990-04-05" },
+        ]);
+        let output = match router(
+            "bulk",
+            &HashMap::new(),
+            &Method::POST,
+            &HeaderMap::new(),
+            &AuthToken::_new(None, None, vec![Permissions::CreatePerson], None),
+            body,
+            &managers,
+        )
+        .await
+        {
+            Ok(RouteResponse::Json(output)) => output,
+            _ => panic!("The persons are not created"),
+        };
+        assert_eq!(output["created"], 1);
+        assert_eq!(output["duplicates"], 1);
+        assert_eq!(output["invalid"], 1);
+        let results = output["results"].as_array().unwrap();
+        assert_eq!(results[0]["status"], "created");
+        assert_eq!(results[1]["status"], "duplicate");
+        assert_eq!(results[1]["uid"], stored.uid().to_string());
+        assert_eq!(results[2]["status"], "invalid");
+        assert_eq!(results[2]["errors"][0]["field"], "name");
+    }
+}
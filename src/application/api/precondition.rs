@@ -0,0 +1,84 @@
+use hyper::header::{self, HeaderMap};
+use serde_json::Value;
+
+use super::{error::ErrorCode, router::HttpError};
+
+/// Version of a speech or a person the client read before updating it. Two editors saving
+/// the same resource cannot overwrite each other: the second update is refused.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExpectedVersion {
+    /// Given in the `If-Match` header, a stale version fails the precondition (412).
+    IfMatch(u32),
+    /// Given in the `version` field of the body, a stale version is a conflict (409).
+    Body(u32),
+}
+
+impl ExpectedVersion {
+    /// Reads the version from the `If-Match` header, either `3`, `"3"` or `W/"3"`, or
+    /// from the `version` field of the body. An update without any is refused.
+    pub fn from_request(headers: &HeaderMap, body: &Value) -> Result<Self, HttpError<'static>> {
+        if let Some(if_match) = headers.get(header::IF_MATCH) {
+            let version = if_match.to_str().unwrap_or_default().trim();
+            let version = version.strip_prefix("W/").unwrap_or(version);
+            let version = version
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(version);
+            return version
+                .parse::<u32>()
+                .map(ExpectedVersion::IfMatch)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidVersion));
+        }
+        match body.get("version") {
+            Some(version) => version
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .map(ExpectedVersion::Body)
+                .ok_or(HttpError::new(ErrorCode::InvalidVersion)),
+            None => Err(HttpError::new(ErrorCode::VersionRequired)),
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        match self {
+            ExpectedVersion::IfMatch(version) | ExpectedVersion::Body(version) => *version,
+        }
+    }
+
+    /// Reports a stale version sent in `If-Match` as a failed precondition, the other
+    /// errors of the update being kept.
+    pub fn map_error(&self, error: HttpError<'static>) -> HttpError<'static> {
+        match self {
+            ExpectedVersion::IfMatch(_) if error.code() == ErrorCode::VersionConflict => {
+                HttpError::new(ErrorCode::VersionMismatch)
+            }
+            _ => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::{HeaderMap, HeaderValue, IF_MATCH};
+    use serde_json::json;
+
+    use super::ExpectedVersion;
+
+    #[test]
+    fn version_is_read_from_if_match_then_from_the_body() {
+        let mut headers = HeaderMap::new();
+        let body = json!({ "name": "Debate", "version": 4 });
+        assert_eq!(
+            ExpectedVersion::from_request(&headers, &body).ok(),
+            Some(ExpectedVersion::Body(4))
+        );
+        assert!(ExpectedVersion::from_request(&headers, &json!({})).is_err());
+        headers.insert(IF_MATCH, HeaderValue::from_static("W/\"7\""));
+        assert_eq!(
+            ExpectedVersion::from_request(&headers, &body).ok(),
+            Some(ExpectedVersion::IfMatch(7))
+        );
+        headers.insert(IF_MATCH, HeaderValue::from_static("*"));
+        assert!(ExpectedVersion::from_request(&headers, &body).is_err());
+    }
+}
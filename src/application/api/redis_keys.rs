@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use futures_util::{stream::BoxStream, StreamExt};
+use redis::{AsyncCommands, Client};
+
+use super::keycloak::{BoxError, SharedKeyCache, SharedKeys};
+
+// Clé des certificats partagés et canal des invalidations
+const KEYS_KEY: &str = "keycloak:keys";
+const INVALIDATIONS_CHANNEL: &str = "keycloak:keys:invalidations";
+
+/// Cache des clés Keycloak partagé au travers de Redis : les certificats y sont gardés
+/// jusqu'à leur expiration et les rotations sont publiées sur un canal.
+pub struct RedisKeyCache {
+    client: Client,
+}
+
+impl RedisKeyCache {
+    pub fn new(url: &str) -> Result<Self, redis::RedisError> {
+        Ok(Self {
+            client: Client::open(url)?,
+        })
+    }
+}
+
+#[async_trait]
+impl SharedKeyCache for RedisKeyCache {
+    async fn get(&self) -> Result<Option<SharedKeys>, BoxError> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        let value: Option<String> = connection.get(KEYS_KEY).await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, keys: &SharedKeys) -> Result<(), BoxError> {
+        let ttl = (keys.expires_at - chrono::Utc::now()).num_seconds().max(1) as u64;
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        connection
+            .set_ex::<_, _, ()>(KEYS_KEY, serde_json::to_string(keys)?, ttl)
+            .await?;
+        Ok(())
+    }
+
+    async fn publish_invalidation(&self, origin: &str) -> Result<(), BoxError> {
+        let mut connection = self.client.get_multiplexed_async_connection().await?;
+        connection
+            .publish::<_, _, ()>(INVALIDATIONS_CHANNEL, origin)
+            .await?;
+        Ok(())
+    }
+
+    async fn invalidations(&self) -> Result<BoxStream<'static, String>, BoxError> {
+        let mut pubsub = self.client.get_async_pubsub().await?;
+        pubsub.subscribe(INVALIDATIONS_CHANNEL).await?;
+        Ok(pubsub
+            .into_on_message()
+            .filter_map(|message| async move { message.get_payload::<String>().ok() })
+            .boxed())
+    }
+}
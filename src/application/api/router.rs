@@ -1,143 +1,478 @@
-use std::{collections::HashMap, io::Error, net::SocketAddr};
+use std::{
+    collections::HashMap, io::Error, net::SocketAddr, str::FromStr, sync::Arc, time::Duration,
+};
 
 use bytes::Bytes;
-use http_body_util::{BodyExt, Full};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
 use hyper::{
-    body::{self, Buf},
-    header::{self, HeaderValue, AUTHORIZATION},
+    body,
+    header::{self, HeaderMap, HeaderName, HeaderValue},
     server::conn::http1,
     Method, Request, Response,
 };
 use hyper_util::{rt::TokioIo, service::TowerToHyperService};
-use jsonwebtoken::{decode_header, Algorithm, DecodingKey, Validation};
-use serde::Serialize;
-use serde_json::Value;
-use tokio::net::TcpListener;
+use jsonwebtoken::{decode_header, Algorithm, Validation};
+use serde_json::{json, Value};
+use tokio::{net::TcpListener, sync::broadcast, time::Instant};
 use tower::ServiceBuilder;
 use tower_http::cors::{AllowOrigin, CorsLayer};
+use uuid::Uuid;
 
 use crate::{
-    application::api::{person::person_router, speech::speech_router},
-    domain::{person::PersonManager, speech::manager::SpeechManager},
+    application::{
+        api::{
+            admin::admin_router,
+            events::events_router,
+            job::job_router,
+            label::label_router,
+            me::me_router,
+            opendata::opendata_router,
+            organization::organization_router,
+            person::person_router,
+            speech::{attachment_router, live_router, speech_router},
+            tag::tag_router,
+            watchlist::watchlist_router,
+        },
+        config::{AppConfig, CorsConfig},
+    },
+    domain::{
+        annotation::AnnotationManager,
+        attachment::AttachmentManager,
+        collection::CollectionVersions,
+        idempotency::{IdempotencyClaim, IdempotencyManager},
+        job::JobManager,
+        label::LabelManager,
+        maintenance::MaintenanceManager,
+        organization::OrganizationManager,
+        person::{PersonEvent, PersonManager},
+        segment::SegmentManager,
+        speech::{
+            event::SpeechEvent, event_log::SpeechEventLog, language::language_code,
+            manager::SpeechManager,
+        },
+        stats::StatsManager,
+        tag::TagManager,
+        upsert::OnConflict,
+        watchlist::WatchlistManager,
+    },
 };
 
-use super::{keycloak::get_keycloak_keys, token::AuthToken};
+#[cfg(feature = "assemblee-nationale")]
+use super::import::import_router;
+use super::{
+    cache::{route_cache_class, CachePolicies},
+    collection::{
+        add_collection_version_field, collection_version, is_cached_version, not_modified_response,
+        COLLECTION_VERSION_HEADER,
+    },
+    deadline::{within_deadline, RequestDeadlines},
+    error::{error_catalog, error_catalog_entry, ErrorCode, ErrorRendering},
+    http_log::{HttpExchangeLog, HttpLogConfig},
+    idempotency::{
+        extract_idempotency_key, idempotency_lease, may_replay, record_response, replayed_response,
+    },
+    keycloak::{KeyProvider, NoKeyProvider},
+    load_shed::{
+        hold_until_sent, overloaded_response, route_load_class, ConcurrencyLimits, LoadShedder,
+    },
+    token::{AuthToken, Permissions},
+};
 
-type BoxBody = http_body_util::combinators::BoxBody<Bytes, hyper::Error>;
+// The errors are defined by the catalog, the routers keep importing them from here.
+pub use super::error::{HttpError, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR};
 
-#[derive(Debug, Serialize)]
-pub struct HttpError<'a> {
-    code: u16,
-    error: &'a str,
-    details: &'a str,
-}
-impl<'a> HttpError<'a> {
-    pub fn new(code: u16, error: &'a str, details: &'a str) -> Self {
-        HttpError {
-            code,
-            error,
-            details,
-        }
-    }
-}
+pub type BoxBody = http_body_util::combinators::BoxBody<Bytes, std::io::Error>;
 
-pub const INTERNAL_ERROR: HttpError = HttpError {
-    code: 500,
-    error: "InternalError",
-    details: "An internal error occured, please contact our technical service",
-};
+/// Maximum size of a request body accepted by default, in bytes.
+pub const DEFAULT_MAX_BODY_SIZE: usize = 1024 * 1024;
 
-pub const NOT_FOUND_ERROR: HttpError = HttpError {
-    code: 404,
-    error: "NotFound",
-    details: "The requested resource is not found",
-};
+/// Address the server listens on by default.
+pub const DEFAULT_SERVER_ADDRESS: ([u8; 4], u16) = ([0, 0, 0, 0], 3000);
 
-pub const ACCESS_DENIED_ERROR: HttpError = HttpError {
-    code: 403,
-    error: "AccessDenied",
-    details: "You cannot access to this ressource",
-};
+/// Response produced by a sub router: either a JSON value serialized by the
+/// main router, or a fully built response (custom headers, streamed body...).
+pub enum RouteResponse {
+    Json(Value),
+    Raw(Response<BoxBody>),
+}
 
-#[derive(Debug)]
+impl From<Value> for RouteResponse {
+    fn from(value: Value) -> Self {
+        RouteResponse::Json(value)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
 pub enum APIError {
+    /// The server cannot run as configured, e.g. its address is already in use.
+    #[error("Invalid server configuration: {0}")]
     ConfigurationError(String),
+    /// The listener stopped accepting the connections.
+    #[error("Cannot accept the connections: {0}")]
+    ConnectionError(Error),
+    #[error("The request failed with {0:?}")]
     RequestError(HttpError<'static>),
 }
 
-impl From<APIError> for Response<BoxBody> {
-    fn from(value: APIError) -> Self {
-        match value {
-            APIError::RequestError(err) => {
-                return Response::builder()
-                    .status(err.code)
-                    .body(full(serde_json::to_string(&err).expect("Should not fail")))
-                    .expect("Should not fail");
-            }
-            _ => {
-                panic!("A fatal error occured")
+impl APIError {
+    /// Builds the response sent to the client of a failed request.
+    pub fn into_response(self, rendering: &ErrorRendering) -> Response<BoxBody> {
+        match self {
+            APIError::RequestError(err) => err.into_response(rendering),
+            // Only the server itself fails this way, the request is answered and the
+            // connection kept.
+            e => {
+                println!("An internal error occured while routing a request: {}", e);
+                INTERNAL_ERROR.into_response(rendering)
             }
         }
     }
 }
 
+/// Header identifying a request, sent back with its response and printed in the logs.
+/// The id sent by the client is kept, e.g. one set by a gateway, otherwise one is
+/// generated.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest request id taken from the client, in characters.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+fn extract_request_id(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LENGTH)
+        .map(str::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+fn with_request_id(mut response: Response<BoxBody>, request_id: &str) -> Response<BoxBody> {
+    if let Ok(request_id) = HeaderValue::from_str(request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, request_id);
+    }
+    response
+}
+
+/// Domain managers shared by every sub router.
+#[derive(Clone)]
+pub struct Managers {
+    pub person_manager: PersonManager,
+    pub speech_manager: SpeechManager,
+    pub label_manager: LabelManager,
+    pub tag_manager: TagManager,
+    pub organization_manager: OrganizationManager,
+    pub idempotency_manager: IdempotencyManager,
+    pub attachment_manager: AttachmentManager,
+    pub annotation_manager: AnnotationManager,
+    pub watchlist_manager: WatchlistManager,
+    pub maintenance_manager: MaintenanceManager,
+    pub job_manager: JobManager,
+    pub segment_manager: SegmentManager,
+    pub stats_manager: StatsManager,
+    /// Versions of the speech and person collections sent with their lists, bumped by the
+    /// person, speech, label and tag managers.
+    pub collection_versions: CollectionVersions,
+}
+
+impl Managers {
+    /// Returns managers whose operations only reach the data of the organization,
+    /// `None` being the default organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            person_manager: self.person_manager.for_organization(organization),
+            speech_manager: self.speech_manager.for_organization(organization),
+            label_manager: self.label_manager.for_organization(organization),
+            tag_manager: self.tag_manager.for_organization(organization),
+            organization_manager: self.organization_manager.clone(),
+            idempotency_manager: self.idempotency_manager.for_organization(organization),
+            attachment_manager: self.attachment_manager.for_organization(organization),
+            annotation_manager: self.annotation_manager.for_organization(organization),
+            watchlist_manager: self.watchlist_manager.for_organization(organization),
+            maintenance_manager: self.maintenance_manager.clone(),
+            job_manager: self.job_manager.for_organization(organization),
+            segment_manager: self.segment_manager.for_organization(organization),
+            stats_manager: self.stats_manager.for_organization(organization),
+            collection_versions: self.collection_versions.for_organization(organization),
+        }
+    }
+
+    /// Returns the managers publishing the speech events to the channel and the log, and
+    /// bumping the versions of the collections changed by the person, speech, label and tag
+    /// managers.
+    pub fn with_events(
+        mut self,
+        speech_events: broadcast::Sender<SpeechEvent>,
+        event_log: SpeechEventLog,
+    ) -> Self {
+        let (person_events, _) = broadcast::channel::<PersonEvent>(PERSON_EVENTS_BACKLOG);
+        let collection_versions = self.collection_versions.clone();
+        self.speech_manager = self
+            .speech_manager
+            .with_events(speech_events)
+            .with_event_log(event_log)
+            .with_collection_versions(collection_versions.clone());
+        self.person_manager = self
+            .person_manager
+            .with_events(person_events)
+            .with_collection_versions(collection_versions.clone());
+        self.label_manager = self
+            .label_manager
+            .with_collection_versions(collection_versions.clone());
+        self.tag_manager = self
+            .tag_manager
+            .with_collection_versions(collection_versions);
+        self
+    }
+
+    /// Returns managers whose reads only reach the validated speeches, for the anonymous
+    /// readers of the public read mode.
+    pub fn for_public_read(&self) -> Self {
+        Self {
+            speech_manager: self.speech_manager.validated_only(),
+            ..self.clone()
+        }
+    }
+}
+
+/// Speech lifecycle events kept for a client which falls behind, it is disconnected past
+/// this number.
+const SPEECH_EVENTS_BACKLOG: usize = 256;
+
+/// Person events kept for a follower which falls behind.
+const PERSON_EVENTS_BACKLOG: usize = 256;
+
+/// Settings checking the tokens of the requests.
+#[derive(Clone)]
+struct Authenticator {
+    /// Whether the validated speeches and the persons may be read without a token.
+    public_read: bool,
+    /// Permissions granted to the requests sent without a token in the public read mode.
+    anonymous_permissions: Vec<Permissions>,
+    /// Keys checking the signature of the tokens.
+    key_provider: Arc<dyn KeyProvider>,
+}
+
 pub struct MainRouter {
-    person_manager: PersonManager,
-    speech_manager: SpeechManager,
+    managers: Managers,
+    address: SocketAddr,
+    max_body_size: usize,
+    cache_policies: CachePolicies,
+    cors: CorsConfig,
+    load_shedder: LoadShedder,
+    request_deadlines: RequestDeadlines,
+    authenticator: Authenticator,
+    http_log: HttpLogConfig,
+    /// Lifecycle events published by the speech managers, streamed by
+    /// `GET /api/speech/events`.
+    speech_events: broadcast::Sender<SpeechEvent>,
 }
 
 impl MainRouter {
-    pub fn new(person_manager: PersonManager, speech_manager: SpeechManager) -> Self {
+    /// Creates the router. Must be called within a Tokio runtime, the event log following
+    /// the events of the managers in a background task.
+    pub fn new(managers: Managers) -> Self {
+        let (speech_events, _) = broadcast::channel(SPEECH_EVENTS_BACKLOG);
+        let event_log = SpeechEventLog::default();
+        tokio::spawn(event_log.clone().follow(speech_events.subscribe()));
         return Self {
-            person_manager,
-            speech_manager,
+            managers: managers.with_events(speech_events.clone(), event_log),
+            address: SocketAddr::from(DEFAULT_SERVER_ADDRESS),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            cache_policies: CachePolicies::default(),
+            cors: CorsConfig::default(),
+            load_shedder: LoadShedder::new(&ConcurrencyLimits::default()),
+            request_deadlines: RequestDeadlines::default(),
+            authenticator: Authenticator {
+                public_read: false,
+                anonymous_permissions: Vec::new(),
+                key_provider: Arc::new(NoKeyProvider),
+            },
+            http_log: HttpLogConfig::default(),
+            speech_events,
         };
     }
 
+    /// Subscribes to the lifecycle events of the speeches of every organization, e.g. for
+    /// a service embedding this API.
+    pub fn subscribe_speech_events(&self) -> broadcast::Receiver<SpeechEvent> {
+        self.speech_events.subscribe()
+    }
+
+    /// Applies the server, authentication, CORS and cache sections of the configuration.
+    pub fn with_config(self, config: &AppConfig) -> Self {
+        self.with_address(config.server.address)
+            .with_max_body_size(config.server.max_body_size)
+            .with_concurrency_limits(config.server.concurrency_limits.clone())
+            .with_request_deadlines(config.server.request_deadlines.clone())
+            .with_http_log(config.server.http_log.clone())
+            .with_public_read(
+                config.auth.public_read_enabled,
+                config.auth.anonymous_permissions.clone(),
+            )
+            .with_cors(config.cors.clone())
+            .with_cache_policies(config.cache.clone())
+    }
+
+    /// Sets the address the server listens on.
+    pub fn with_address(mut self, address: SocketAddr) -> Self {
+        self.address = address;
+        self
+    }
+
+    /// Sets the maximum size of a request body, larger bodies are rejected with a 413.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Sets the cross-origin requests accepted from the browsers.
+    pub fn with_cors(mut self, cors: CorsConfig) -> Self {
+        self.cors = cors;
+        self
+    }
+
+    /// Sets the number of requests of each load class served at the same time.
+    pub fn with_concurrency_limits(mut self, limits: ConcurrencyLimits) -> Self {
+        self.load_shedder = LoadShedder::new(&limits);
+        self
+    }
+
+    /// Sets the time the requests of each load class may take before a 504 is sent.
+    pub fn with_request_deadlines(mut self, deadlines: RequestDeadlines) -> Self {
+        self.request_deadlines = deadlines;
+        self
+    }
+
+    /// Enables the public read mode: the requests sent without a token may read the
+    /// validated speeches and the persons of the default organization, as far as the
+    /// permissions granted to them allow, the other routes still requiring a token.
+    pub fn with_public_read(
+        mut self,
+        public_read: bool,
+        anonymous_permissions: Vec<Permissions>,
+    ) -> Self {
+        self.authenticator.public_read = public_read;
+        self.authenticator.anonymous_permissions = anonymous_permissions;
+        self
+    }
+
+    /// Sets the keys checking the tokens. Without them every token is rejected with a 503.
+    pub fn with_key_provider(mut self, key_provider: Arc<dyn KeyProvider>) -> Self {
+        self.authenticator.key_provider = key_provider;
+        self
+    }
+
+    /// Sets the `Cache-Control` policies sent on the responses of the cached routes.
+    pub fn with_cache_policies(mut self, cache_policies: CachePolicies) -> Self {
+        self.cache_policies = cache_policies;
+        self
+    }
+
+    /// Sets the audit log written for each request, disabled by default.
+    pub fn with_http_log(mut self, http_log: HttpLogConfig) -> Self {
+        self.http_log = http_log;
+        self
+    }
+
     pub async fn run(&self) -> Result<(), APIError> {
-        let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
-        let listener = TcpListener::bind(addr)
-            .await
-            .map_err(|e| APIError::ConfigurationError(e.to_string()))?;
+        let listener = TcpListener::bind(self.address).await.map_err(|e| {
+            APIError::ConfigurationError(format!(
+                "Cannot listen on {}, check SERVER_ADDRESS: {}",
+                self.address, e
+            ))
+        })?;
+        self.run_with_listener(listener).await
+    }
+
+    /// Serves the API on an already bound listener, e.g. one bound on an ephemeral port
+    /// (`127.0.0.1:0`) by an integration test or by a service embedding this API.
+    pub async fn run_with_listener(&self, listener: TcpListener) -> Result<(), APIError> {
+        let cors = cors_layer(&self.cors);
         // We start a loop to continuously accept incoming connections
         loop {
-            let (stream, _) = listener
-                .accept()
-                .await
-                .map_err(|e| APIError::ConfigurationError(e.to_string()))?;
+            let (stream, _) = listener.accept().await.map_err(APIError::ConnectionError)?;
 
             // Use an adapter to access something implementing `tokio::io` traits as if they implement
             // `hyper::rt` IO traits.
             let io = TokioIo::new(stream);
 
-            let person_manager_cloned = self.person_manager.clone();
-            let speech_manager_cloned = self.speech_manager.clone();
+            let managers_cloned = self.managers.clone();
+            let max_body_size = self.max_body_size;
+            let cache_policies = self.cache_policies.clone();
+            let cors = cors.clone();
+            let load_shedder = self.load_shedder.clone();
+            let request_deadlines = self.request_deadlines.clone();
+            let authenticator = self.authenticator.clone();
+            let http_log = self.http_log.clone();
             tokio::task::spawn(async move {
-                let cors = CorsLayer::new()
-                    .allow_origin(AllowOrigin::any()) // Autoriser toutes les origines (pour le développement)
-                    .allow_methods(vec![Method::GET, Method::POST, Method::OPTIONS]) // Autoriser certaines méthodes HTTP
-                    .allow_headers(vec![header::CONTENT_TYPE, AUTHORIZATION]);
-                let service = ServiceBuilder::new().layer(cors).service_fn(|r| {
-                    let person_manager_cloned = person_manager_cloned.clone();
-                    let speech_manager_cloned = speech_manager_cloned.clone();
-                    async {
-                        let res =
-                            match route_requests(r, person_manager_cloned, speech_manager_cloned)
+                let service =
+                    ServiceBuilder::new()
+                        .layer(cors)
+                        .service_fn(|r: Request<body::Incoming>| {
+                            let managers_cloned = managers_cloned.clone();
+                            let cache_policies = cache_policies.clone();
+                            let authenticator = authenticator.clone();
+                            let cache_class = route_cache_class(r.method(), r.uri().path());
+                            let load_class = route_load_class(r.method(), r.uri().path());
+                            // Taken before the body is read, a rejected request costs
+                            // nothing.
+                            let permit = load_shedder.try_acquire(load_class);
+                            let deadline = request_deadlines.deadline(load_class);
+                            let request_id = extract_request_id(r.headers());
+                            let rendering = ErrorRendering::from_request(
+                                r.headers(),
+                                r.uri().path(),
+                                &request_id,
+                            );
+                            let mut exchange = HttpExchangeLog::new(
+                                &http_log,
+                                &request_id,
+                                r.method(),
+                                r.uri().path(),
+                                r.headers(),
+                            );
+                            async move {
+                                let permit = match permit {
+                                    Ok(permit) => permit,
+                                    Err(_) => {
+                                        let res = overloaded_response(&rendering);
+                                        exchange.write(res.status());
+                                        return Ok(with_request_id(res, &request_id));
+                                    }
+                                };
+                                let mut res = match route_requests(
+                                    r,
+                                    managers_cloned,
+                                    max_body_size,
+                                    &mut exchange,
+                                    deadline,
+                                    &authenticator,
+                                )
                                 .await
-                            {
-                                Ok(r) => r,
-                                Err(e) => e.into(),
-                            };
-                        Ok::<
-                            Response<
-                                http_body_util::combinators::BoxBody<bytes::Bytes, hyper::Error>,
-                            >,
-                            Error,
-                        >(res)
-                    }
-                });
+                                {
+                                    Ok(r) => r,
+                                    Err(e) => e.into_response(&rendering),
+                                };
+                                exchange.write(res.status());
+                                cache_policies.apply(cache_class, &mut res);
+                                let res =
+                                    hold_until_sent(with_request_id(res, &request_id), permit);
+                                Ok::<
+                                    Response<
+                                        http_body_util::combinators::BoxBody<
+                                            bytes::Bytes,
+                                            std::io::Error,
+                                        >,
+                                    >,
+                                    Error,
+                                >(res)
+                            }
+                        });
                 if let Err(err) = http1::Builder::new()
                     .serve_connection(io, TowerToHyperService::new(service))
+                    // The live sessions are upgraded to WebSockets.
+                    .with_upgrades()
                     .await
                 {
                     eprintln!("Error serving connection: {:?}", err);
@@ -147,99 +482,414 @@ impl MainRouter {
     }
 }
 
+fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let allow_origin = match &config.allowed_origins {
+        Some(origins) => AllowOrigin::list(origins.clone()),
+        None => AllowOrigin::any(),
+    };
+    let cors = CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(config.allowed_methods.clone())
+        .allow_headers(config.allowed_headers.clone())
+        .expose_headers([
+            HeaderName::from_static("collection-version"),
+            HeaderName::from_static(REQUEST_ID_HEADER),
+        ])
+        .allow_credentials(config.allow_credentials);
+    match config.max_age {
+        Some(max_age) => cors.max_age(Duration::from_secs(max_age)),
+        None => cors,
+    }
+}
+
 async fn route_requests(
-    request: Request<body::Incoming>,
-    person_manager: PersonManager,
-    speech_manager: SpeechManager,
+    mut request: Request<body::Incoming>,
+    managers: Managers,
+    max_body_size: usize,
+    exchange: &mut HttpExchangeLog,
+    deadline: Option<Instant>,
+    authenticator: &Authenticator,
 ) -> Result<Response<BoxBody>, APIError> {
+    let request_id = exchange.request_id().to_owned();
+    let request_id = request_id.as_str();
     let path = request.uri().path().to_string();
     let params = match request.uri().query() {
         Some(val) => val.to_string(),
         None => Default::default(),
     };
     let method = request.method().clone();
-    println!("Request {}:{}", method.as_str(), path);
+    println!("Request {} {}:{}", request_id, method.as_str(), path);
     let headers = request.headers().clone();
-    let whole_body = request
-        .collect()
+    // A live session takes over the connection once upgraded to a WebSocket.
+    let upgrade = if live_router::is_websocket_route(&method, &path) {
+        Some(hyper::upgrade::on(&mut request))
+    } else {
+        None
+    };
+    // Live transcripts, NDJSON speeches and attachments are streamed, their body is handed
+    // to the route instead of read, and they last as long as the client sends it.
+    let (body, stream, deadline) = if upgrade.is_some() {
+        (Value::Null, None, None)
+    } else if live_router::is_streamed_route(&method, &path, &headers)
+        || attachment_router::is_upload_route(&method, &path)
+        || is_import_route(&method, &path)
+    {
+        (Value::Null, Some(request.into_body()), None)
+    } else {
+        let body = within_deadline(
+            deadline,
+            read_json_body(request, &method, &headers, max_body_size),
+        )
         .await
-        .map_err(|e| {
-            println!("An internal error occured while getting the body : {:?}", e);
-            APIError::RequestError(INTERNAL_ERROR)
-        })?
-        .aggregate();
-    let body: serde_json::Value =
-        serde_json::from_reader(whole_body.reader()).unwrap_or(serde_json::Value::Null);
+        .map_err(APIError::RequestError)?;
+        exchange.record_request_body(&body);
+        (body, None, deadline)
+    };
     let mut splitted_path = path.split("/").skip(1);
     match splitted_path.next() {
         Some(api_str) => {
             if api_str != "api" {
-                return Err(APIError::RequestError(HttpError {
-                    code: 400,
-                    error: "InvalidRoute",
-                    details: "The route format seems invalid",
-                }));
+                return Err(APIError::RequestError(HttpError::new(
+                    ErrorCode::InvalidRoute,
+                )));
             }
         }
         None => return Err(APIError::RequestError(NOT_FOUND_ERROR)),
     }
     let query_params = get_query_params_from_raw(&params);
-    let keycloak_keys = get_keycloak_keys().await.map_err(|e| {
-        println!("An internal error occured while getting keycloak: {:?}", e);
-        APIError::RequestError(INTERNAL_ERROR)
-    })?;
     let token = extract_token(
         headers
             .get("Authorization")
             .unwrap_or(&HeaderValue::from_static(""))
             .to_str()
             .unwrap_or(""),
-        keycloak_keys,
+        authenticator,
     )
+    .await
     .map_err(|e| APIError::RequestError(e))?;
-    let resp = match splitted_path.next() {
-        Some(val) => {
-            let partial_path = &splitted_path.collect::<Vec<&str>>().join("/");
-            match val {
-                "person" => {
-                    person_router::router(
+    // The open data routes always publish the default organization, whoever asks.
+    let managers = match splitted_path.clone().next() {
+        Some("opendata") => managers,
+        _ => managers.for_organization(token.organization()),
+    };
+    // Anonymous readers only reach the person and speech routers, each one checking the
+    // route is public, and never the streamed routes.
+    let managers = if token.is_public_reader() {
+        let public_router = matches!(
+            splitted_path.clone().next(),
+            Some("person" | "speech" | "opendata" | "health" | "readyz" | "errors")
+        );
+        if stream.is_some() || upgrade.is_some() || !public_router {
+            return Err(APIError::RequestError(authentication_required(
+                ACCESS_DENIED_ERROR,
+                &token,
+            )));
+        }
+        managers.for_public_read()
+    } else {
+        managers
+    };
+    // The version is read before the list so a change made meanwhile bumps it past the
+    // version sent. A client sending back the version of its cached list gets a 304 as long
+    // as nothing changed.
+    let collection_version =
+        collection_version(&method, &path, &token, &managers.collection_versions).await;
+    if let Some(version) = collection_version {
+        if is_cached_version(&headers, version) {
+            return Ok(not_modified_response(version));
+        }
+    }
+    // A retried POST request sent with the same Idempotency-Key gets the first response.
+    let idempotency_key = match (&method, &stream) {
+        (&Method::POST, None) => {
+            extract_idempotency_key(&headers).map_err(APIError::RequestError)?
+        }
+        _ => None,
+    };
+    // The keys are those of the user, whose permissions are checked again before replaying.
+    let idempotency_manager = managers.idempotency_manager.for_user(&token.user_id());
+    if let Some(key) = &idempotency_key {
+        let request = format!("{} {}?{}\n{}", method, path, params, body);
+        let claim = idempotency_manager
+            .claim_key(key, request.as_bytes(), idempotency_lease(deadline))
+            .await
+            .map_err(|e| APIError::RequestError(e.into()))?;
+        if let IdempotencyClaim::Replay(stored) = claim {
+            if !may_replay(&path, &token) {
+                return Err(APIError::RequestError(authentication_required(
+                    ACCESS_DENIED_ERROR,
+                    &token,
+                )));
+            }
+            return Ok(replayed_response(stored));
+        }
+    }
+    let routed = async {
+        match splitted_path.next() {
+            Some(val) => {
+                let partial_path = &splitted_path.collect::<Vec<&str>>().join("/");
+                match val {
+                    "person" => {
+                        person_router::router(
+                            partial_path,
+                            &query_params,
+                            &method,
+                            &headers,
+                            &token,
+                            body,
+                            &managers,
+                        )
+                        .await
+                    }
+                    "speech" => match (upgrade, stream) {
+                        (Some(upgrade), _) => {
+                            live_router::websocket_router(
+                                partial_path,
+                                &query_params,
+                                &headers,
+                                &token,
+                                upgrade,
+                                &managers,
+                                request_id,
+                            )
+                            .await
+                        }
+                        (None, Some(stream))
+                            if attachment_router::is_upload_route(&method, &path) =>
+                        {
+                            attachment_router::upload_router(
+                                partial_path,
+                                &headers,
+                                &token,
+                                stream,
+                                &managers,
+                            )
+                            .await
+                        }
+                        (None, Some(stream)) => {
+                            live_router::router(
+                                partial_path,
+                                &method,
+                                &headers,
+                                &token,
+                                stream,
+                                &managers,
+                                max_body_size,
+                            )
+                            .await
+                        }
+                        (None, None) => {
+                            speech_router::router(
+                                partial_path,
+                                &query_params,
+                                &method,
+                                &headers,
+                                &token,
+                                body,
+                                &managers,
+                            )
+                            .await
+                        }
+                    },
+                    "labels" => label_router::router(
                         partial_path,
                         &query_params,
                         &method,
                         &token,
                         body,
-                        &person_manager,
+                        &managers,
                     )
                     .await
-                }
-                "speech" => {
-                    speech_router::router(
+                    .map(RouteResponse::from),
+                    "tags" => tag_router::router(partial_path, &method, &token, body, &managers)
+                        .await
+                        .map(RouteResponse::from),
+                    "watchlists" => watchlist_router::router(
                         partial_path,
                         &query_params,
                         &method,
                         &token,
                         body,
-                        &speech_manager,
+                        &managers,
                     )
                     .await
+                    .map(RouteResponse::from),
+                    "me" => me_router::router(partial_path, &method, &token, body, &managers)
+                        .await
+                        .map(RouteResponse::from),
+                    "opendata" => opendata_router::router(partial_path, &method, &managers).await,
+                    "organizations" => {
+                        organization_router::router(partial_path, &method, &token, body, &managers)
+                            .await
+                            .map(RouteResponse::from)
+                    }
+                    "events" => events_router::router(
+                        partial_path,
+                        &query_params,
+                        &method,
+                        &token,
+                        &managers,
+                    )
+                    .await
+                    .map(RouteResponse::from),
+                    "jobs" => {
+                        job_router::router(partial_path, &query_params, &method, &token, &managers)
+                            .await
+                            .map(RouteResponse::from)
+                    }
+                    "admin" => admin_router::router(
+                        partial_path,
+                        &query_params,
+                        &method,
+                        &token,
+                        body,
+                        &managers,
+                    )
+                    .await
+                    .map(RouteResponse::from),
+                    #[cfg(feature = "assemblee-nationale")]
+                    "import" => match stream {
+                        Some(stream) => {
+                            import_router::router(
+                                partial_path,
+                                &query_params,
+                                &headers,
+                                &token,
+                                stream,
+                                &managers,
+                                max_body_size,
+                            )
+                            .await
+                        }
+                        None => Err(NOT_FOUND_ERROR),
+                    },
+                    "health" => Ok(RouteResponse::Json(Value::Null)),
+                    "readyz" => Ok(readiness(authenticator.key_provider.as_ref()).await),
+                    "errors" => match partial_path.as_str() {
+                        "" => Ok(RouteResponse::Json(error_catalog())),
+                        code => error_catalog_entry(code)
+                            .map(RouteResponse::Json)
+                            .ok_or(NOT_FOUND_ERROR),
+                    },
+                    _ => Err(NOT_FOUND_ERROR),
                 }
-                "health" => Ok(Value::Null),
-                _ => return Err(APIError::RequestError(NOT_FOUND_ERROR)),
             }
+            None => Err(NOT_FOUND_ERROR),
         }
-        None => return Err(APIError::RequestError(NOT_FOUND_ERROR)),
+    };
+    let resp = within_deadline(deadline, routed)
+        .await
+        .map_err(|e| authentication_required(e, &token));
+    if let Some(key) = &idempotency_key {
+        record_response(&idempotency_manager, key, &resp).await;
     }
-    .map_err(|e| {
-        println!("An error occured: {:?}", e);
+    let resp = resp.map_err(|e| {
+        println!("An error occured on request {}: {:?}", request_id, e);
         APIError::RequestError(e)
     })?;
-    return Ok(Response::builder()
-        .status(200)
-        .body(full(serde_json::to_string(&resp).unwrap()))
-        .unwrap());
+    let mut response = match resp {
+        RouteResponse::Json(mut resp) => {
+            if let Some(version) = collection_version {
+                add_collection_version_field(&mut resp, version);
+            }
+            exchange.record_response_body(&resp);
+            Response::builder()
+                .status(200)
+                .body(full(serde_json::to_string(&resp).unwrap()))
+                .unwrap()
+        }
+        RouteResponse::Raw(resp) => resp,
+    };
+    if let Some(version) = collection_version {
+        response
+            .headers_mut()
+            .insert(COLLECTION_VERSION_HEADER, HeaderValue::from(version));
+    }
+    Ok(response)
+}
+
+/// Whether the request sends a document to import, handed to the import route instead of
+/// read as JSON. Only built with the `assemblee-nationale` feature.
+fn is_import_route(method: &Method, path: &str) -> bool {
+    cfg!(feature = "assemblee-nationale")
+        && method == Method::POST
+        && path.starts_with("/api/import/")
+}
+
+/// Asks a request sent without a token to authenticate (401) rather than denying it (403).
+fn authentication_required(error: HttpError<'static>, token: &AuthToken) -> HttpError<'static> {
+    match error.code() {
+        ErrorCode::AccessDenied if token.is_anonymous() => {
+            HttpError::new(ErrorCode::AuthenticationRequired)
+        }
+        _ => error,
+    }
+}
+
+/// Readiness of the instance, sent with a 503 until the keys checking the tokens are
+/// fetched, along with the state of their cache.
+async fn readiness(key_provider: &dyn KeyProvider) -> RouteResponse {
+    let keys = key_provider.state().await;
+    let status = if keys.loaded { 200 } else { 503 };
+    let body = json!({ "ready": keys.loaded, "keys": keys });
+    RouteResponse::Raw(
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(full(body.to_string()))
+            .unwrap(),
+    )
+}
+
+/// Reads the whole request body as JSON. Bodies larger than `max_body_size` are rejected
+/// without being buffered, and a non empty body sent with POST, PUT or PATCH must be
+/// declared as `application/json`. An empty body is read as `null`.
+async fn read_json_body(
+    request: Request<body::Incoming>,
+    method: &Method,
+    headers: &HeaderMap,
+    max_body_size: usize,
+) -> Result<Value, HttpError<'static>> {
+    let payload_too_large = HttpError::with_context(
+        ErrorCode::PayloadTooLarge,
+        format!("The request body must not exceed {} bytes", max_body_size),
+    );
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if content_length.is_some_and(|length| length > max_body_size) {
+        return Err(payload_too_large);
+    }
+    let whole_body = Limited::new(request.into_body(), max_body_size)
+        .collect()
+        .await
+        .map_err(|e| {
+            if e.downcast_ref::<LengthLimitError>().is_some() {
+                return payload_too_large;
+            }
+            println!("An internal error occured while getting the body : {:?}", e);
+            INTERNAL_ERROR
+        })?
+        .to_bytes();
+    if whole_body.is_empty() {
+        return Ok(Value::Null);
+    }
+    if [Method::POST, Method::PUT, Method::PATCH].contains(method) {
+        let is_json = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(';').next())
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+        if !is_json {
+            return Err(HttpError::new(ErrorCode::UnsupportedMediaType));
+        }
+    }
+    serde_json::from_slice(&whole_body)
+        .map_err(|e| HttpError::with_context(ErrorCode::InvalidJson, e.to_string()))
 }
 
-fn full<T: Into<Bytes>>(chunk: T) -> BoxBody {
+pub fn full<T: Into<Bytes>>(chunk: T) -> BoxBody {
     Full::new(chunk.into())
         .map_err(|never| match never {})
         .boxed()
@@ -249,7 +899,8 @@ fn get_query_params_from_raw(raw_params: &str) -> HashMap<String, String> {
     let mut query_params = HashMap::new();
     let query_params_list = raw_params.split("&");
     for query_param in query_params_list {
-        let mut param_splitted = query_param.split("=");
+        // The values may hold `=`, e.g. the conditions of a filter.
+        let mut param_splitted = query_param.splitn(2, "=");
         let var = param_splitted.next();
         let val = param_splitted.next();
         if var.is_some() && val.is_some() {
@@ -259,13 +910,106 @@ fn get_query_params_from_raw(raw_params: &str) -> HashMap<String, String> {
     query_params
 }
 
-fn extract_token(
+pub fn extract_array_in_query(
+    array_field: &str,
+    query_params: &HashMap<String, String>,
+) -> Result<Vec<String>, HttpError<'static>> {
+    let array_raw = match query_params.get(array_field) {
+        Some(v) => v,
+        None => {
+            return Ok(Vec::new());
+        }
+    };
+    let array_decomposed = match array_raw.split("%5B").skip(1).next() {
+        Some(v) => v,
+        None => return Err(HttpError::new(ErrorCode::InvalidArrayParam)),
+    };
+    let array_decomposed = match array_decomposed.split("%5D").next() {
+        Some(v) => v,
+        None => return Err(HttpError::new(ErrorCode::InvalidArrayParam)),
+    };
+    return Ok(array_decomposed
+        .split(",")
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>());
+}
+
+/// Parses an array query parameter (`field=[uid1,uid2]`) made of uids.
+pub fn extract_uid_array_in_query(
+    array_field: &str,
+    query_params: &HashMap<String, String>,
+) -> Result<Vec<Uuid>, HttpError<'static>> {
+    let mut uids = Vec::new();
+    for uid in extract_array_in_query(array_field, query_params)? {
+        uids.push(Uuid::from_str(&uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?);
+    }
+    Ok(uids)
+}
+
+/// Reads the `include_deleted` list flag. Listing soft deleted entities is reserved to
+/// administrators.
+pub fn extract_include_deleted(
+    query_params: &HashMap<String, String>,
+    token: &AuthToken,
+) -> Result<bool, HttpError<'static>> {
+    let include_deleted = match query_params.get("include_deleted") {
+        Some(v) => v
+            .parse::<bool>()
+            .map_err(|_| HttpError::new(ErrorCode::InvalidIncludeDeletedParam))?,
+        None => false,
+    };
+    if include_deleted && !token.permissions().contains(&Permissions::Admin) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    Ok(include_deleted)
+}
+
+/// Reads the `on_conflict` mode of a creation. Without it, creating an entity already
+/// stored fails.
+pub fn extract_on_conflict(
+    query_params: &HashMap<String, String>,
+) -> Result<Option<OnConflict>, HttpError<'static>> {
+    query_params
+        .get("on_conflict")
+        .map(|v| {
+            OnConflict::try_from(v.as_str())
+                .map_err(|_| HttpError::new(ErrorCode::InvalidOnConflictParam))
+        })
+        .transpose()
+}
+
+/// Reads the `lang` filter, a BCP 47 tag or an ISO 639-3 code, as an ISO 639-3 code.
+pub fn extract_language(
+    query_params: &HashMap<String, String>,
+) -> Result<Option<String>, HttpError<'static>> {
+    query_params
+        .get("lang")
+        .map(|v| language_code(v).ok_or(HttpError::new(ErrorCode::InvalidLangParam)))
+        .transpose()
+}
+
+/// Reads the `include_moderators` statistics flag, moderators are left out by default.
+pub fn extract_include_moderators(
+    query_params: &HashMap<String, String>,
+) -> Result<bool, HttpError<'static>> {
+    match query_params.get("include_moderators") {
+        Some(v) => v
+            .parse::<bool>()
+            .map_err(|_| HttpError::new(ErrorCode::InvalidIncludeModeratorsParam)),
+        None => Ok(false),
+    }
+}
+
+async fn extract_token(
     raw_token: &str,
-    keys: HashMap<String, DecodingKey>,
+    authenticator: &Authenticator,
 ) -> Result<AuthToken, HttpError<'static>> {
-    let invalid_token = HttpError::new(400, "InvalidToken", "The token you provided is invalid");
+    let invalid_token = HttpError::new(ErrorCode::InvalidToken);
     if raw_token.is_empty() {
-        return Ok(AuthToken::default());
+        return Ok(AuthToken::anonymous(
+            authenticator.public_read,
+            &authenticator.anonymous_permissions,
+        ));
     }
     let token_part = match raw_token.split("Bearer ").skip(1).next() {
         Some(token) => token,
@@ -283,11 +1027,12 @@ fn extract_token(
         None => return Err(invalid_token),
     };
     // Trouver la clé correspondant au `kid`
-    let decoding_key = match keys.get(&kid) {
-        Some(key) => key,
-        None => return Err(invalid_token),
+    let decoding_key = match authenticator.key_provider.get_key(&kid).await {
+        Ok(Some(key)) => key,
+        Ok(None) => return Err(invalid_token),
+        Err(_) => return Err(HttpError::new(ErrorCode::AuthenticationUnavailable)),
     };
-    let decoded = match jsonwebtoken::decode(token_part, decoding_key, &validation) {
+    let decoded = match jsonwebtoken::decode(token_part, &decoding_key, &validation) {
         Ok(res) => res.claims,
         Err(e) => {
             println!("Token error : {:?}", e);
@@ -297,3 +1042,66 @@ fn extract_token(
 
     Ok(decoded)
 }
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header, header::HeaderMap, StatusCode};
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+
+    use super::{APIError, MainRouter};
+    use crate::{
+        application::api::{error::ErrorRendering, token::Permissions},
+        test_support::test_database,
+    };
+
+    #[tokio::test]
+    async fn anonymous_requests_to_private_routes_must_authenticate() {
+        let database = test_database().await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = MainRouter::new(database.managers())
+            .with_public_read(true, vec![Permissions::GetSpeech, Permissions::GetPerson]);
+        tokio::spawn(async move { router.run_with_listener(listener).await });
+        let client = reqwest::Client::new();
+        let public = client
+            .get(format!("http://{}/api/speech", address))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(public.status(), StatusCode::OK);
+        let private = client
+            .get(format!("http://{}/api/label", address))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(private.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            private.headers().get(header::WWW_AUTHENTICATE).unwrap(),
+            "Bearer"
+        );
+        let body: Value = private.json().await.unwrap();
+        assert_eq!(body["error"], "AuthenticationRequired");
+    }
+
+    #[tokio::test]
+    async fn an_address_in_use_is_a_configuration_error() {
+        let database = test_database().await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let router =
+            MainRouter::new(database.managers()).with_address(listener.local_addr().unwrap());
+        match router.run().await {
+            Err(e @ APIError::ConfigurationError(_)) => {
+                assert!(e.to_string().contains("SERVER_ADDRESS"))
+            }
+            _ => panic!("The router runs on an address in use"),
+        }
+    }
+
+    #[test]
+    fn a_server_error_answers_the_request() {
+        let rendering = ErrorRendering::from_request(&HeaderMap::new(), "/api/speech", "request");
+        let response = APIError::ConfigurationError("broken".to_owned()).into_response(&rendering);
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
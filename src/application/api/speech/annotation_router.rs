@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, INTERNAL_ERROR},
+    },
+    domain::{
+        annotation::{
+            Annotation, AnnotationRepositoryError, SentimentFilter, SentimentSummary,
+            SpeechSentiment,
+        },
+        speech::Speech,
+    },
+};
+
+impl From<AnnotationRepositoryError> for HttpError<'static> {
+    fn from(value: AnnotationRepositoryError) -> Self {
+        match value {
+            AnnotationRepositoryError::SpeechNotFound => HttpError::new(ErrorCode::SpeechNotFound),
+            AnnotationRepositoryError::AnnotatorError(e) => {
+                println!("Annotator Error: {:?}", e);
+                INTERNAL_ERROR
+            }
+            AnnotationRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Annotations: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSentenceAnnotation {
+    sentence: String,
+    /// ISO 639-3 code of the language of the sentence, if detected.
+    language: Option<String>,
+    /// positive, neutral or negative, if known.
+    sentiment: Option<String>,
+    /// From -1 (negative) to 1 (positive).
+    sentiment_score: Option<f64>,
+    annotated_at: String,
+}
+
+impl From<Annotation> for GetSentenceAnnotation {
+    fn from(value: Annotation) -> Self {
+        Self {
+            sentence: value.sentence_uid.to_string(),
+            language: value.language,
+            sentiment: value.sentiment.map(|s| s.label.to_string()),
+            sentiment_score: value.sentiment.map(|s| s.score),
+            annotated_at: value.annotated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSentimentSummary {
+    sentences: u32,
+    /// Sentences whose sentiment is known.
+    annotated: u32,
+    positive: u32,
+    neutral: u32,
+    negative: u32,
+    average_score: Option<f64>,
+}
+
+impl From<&SentimentSummary> for GetSentimentSummary {
+    fn from(value: &SentimentSummary) -> Self {
+        Self {
+            sentences: value.sentences,
+            annotated: value.annotated,
+            positive: value.positive,
+            neutral: value.neutral,
+            negative: value.negative,
+            average_score: value.average_score(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSpeakerSentiment {
+    speaker: String,
+    speaker_name: String,
+    role: String,
+    #[serde(flatten)]
+    summary: GetSentimentSummary,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSpeechSentiment {
+    #[serde(flatten)]
+    overall: GetSentimentSummary,
+    speakers: Vec<GetSpeakerSentiment>,
+}
+
+impl GetSpeechSentiment {
+    pub fn new(
+        sentiment: &SpeechSentiment,
+        speech: &Speech,
+        speaker_names: &HashMap<Uuid, String>,
+    ) -> Self {
+        Self {
+            overall: GetSentimentSummary::from(&sentiment.overall),
+            speakers: sentiment
+                .speakers
+                .iter()
+                .map(|(speaker, summary)| GetSpeakerSentiment {
+                    speaker: speaker.to_string(),
+                    speaker_name: speaker_names
+                        .get(speaker)
+                        .cloned()
+                        .unwrap_or_else(|| speaker.to_string()),
+                    role: speech.speaker_role(speaker).to_string(),
+                    summary: summary.into(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Reads the `speaker` and `language` query parameters of `GET /api/speech/{uid}/sentiment`.
+pub fn extract_sentiment_filter(
+    query_params: &HashMap<String, String>,
+) -> Result<SentimentFilter, HttpError<'static>> {
+    let speaker = match query_params.get("speaker") {
+        Some(speaker) => {
+            Some(Uuid::parse_str(speaker).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?)
+        }
+        None => None,
+    };
+    let language = match query_params.get("language") {
+        Some(language) => {
+            let language = language.to_lowercase();
+            if language.len() != 3 || !language.chars().all(|c| c.is_ascii_lowercase()) {
+                return Err(HttpError::new(ErrorCode::InvalidLanguageParam));
+            }
+            Some(language)
+        }
+        None => None,
+    };
+    Ok(SentimentFilter { speaker, language })
+}
@@ -0,0 +1,217 @@
+use std::str::FromStr;
+
+use futures_util::StreamExt;
+use http_body_util::BodyExt;
+use hyper::{
+    body,
+    header::{self, HeaderMap},
+    Method,
+};
+use serde::Serialize;
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, RouteResponse, ACCESS_DENIED_ERROR, INTERNAL_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    domain::attachment::{
+        Attachment, AttachmentManager, AttachmentRepositoryError, AttachmentStorageError,
+    },
+};
+
+/// Name of the multipart field holding the content of an uploaded attachment.
+const ATTACHMENT_FIELD: &str = "file";
+
+impl From<AttachmentRepositoryError> for HttpError<'static> {
+    fn from(value: AttachmentRepositoryError) -> Self {
+        match value {
+            AttachmentRepositoryError::SpeechNotFound => HttpError::new(ErrorCode::SpeechNotFound),
+            AttachmentRepositoryError::AttachmentNotFound => {
+                HttpError::new(ErrorCode::AttachmentNotFound)
+            }
+            AttachmentRepositoryError::StorageError(AttachmentStorageError::Unavailable) => {
+                HttpError::new(ErrorCode::AttachmentsUnavailable)
+            }
+            AttachmentRepositoryError::StorageError(AttachmentStorageError::TooLarge) => {
+                HttpError::new(ErrorCode::PayloadTooLarge)
+            }
+            AttachmentRepositoryError::StorageError(AttachmentStorageError::InvalidContent(e)) => {
+                HttpError::with_context(ErrorCode::InvalidFormat, e)
+            }
+            AttachmentRepositoryError::StorageError(AttachmentStorageError::ProviderError(e)) => {
+                println!("Attachment Storage Error: {}", e);
+                HttpError::new(ErrorCode::AttachmentStorageFailed)
+            }
+            AttachmentRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Attachments: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetAttachment {
+    uid: String,
+    file_name: String,
+    content_type: String,
+    /// Size of the content, in bytes.
+    size: u64,
+    created_at: String,
+}
+
+impl From<Attachment> for GetAttachment {
+    fn from(value: Attachment) -> Self {
+        Self {
+            uid: value.uid().to_string(),
+            file_name: value.file_name().clone(),
+            content_type: value.content_type().clone(),
+            size: value.size(),
+            created_at: value.created_at().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetAttachmentDownload {
+    #[serde(flatten)]
+    attachment: GetAttachment,
+    /// URL the content is downloaded from, without the bearer token.
+    url: String,
+    expires_at: String,
+}
+
+/// Whether the request uploads an attachment, its multipart body being streamed to the
+/// attachment storage instead of read.
+pub fn is_upload_route(method: &Method, path: &str) -> bool {
+    method == Method::POST
+        && matches!(
+            path.split("/").collect::<Vec<&str>>().as_slice(),
+            ["", "api", "speech", _, "attachments"]
+        )
+}
+
+/// Uploads the attachment sent as the `file` field of a `multipart/form-data` body to
+/// `POST /api/speech/{uid}/attachments`.
+pub async fn upload_router(
+    path: &str,
+    headers: &HeaderMap,
+    token: &AuthToken,
+    body: body::Incoming,
+    managers: &Managers,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let uid = match path.split("/").collect::<Vec<&str>>().as_slice() {
+        [uid, "attachments"] => {
+            Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?
+        }
+        _ => return Err(HttpError::new(ErrorCode::NotFound)),
+    };
+    if !token.permissions().contains(&Permissions::UpdateSpeech) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let boundary = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| multer::parse_boundary(v).ok())
+        .ok_or(HttpError::new(ErrorCode::UnsupportedMediaType))?;
+    let mut multipart = multer::Multipart::new(body.into_data_stream(), boundary);
+    loop {
+        let field = multipart
+            .next_field()
+            .await
+            .map_err(|e| HttpError::with_context(ErrorCode::InvalidFormat, e.to_string()))?;
+        let field = match field {
+            Some(field) if field.name() == Some(ATTACHMENT_FIELD) => field,
+            // The other fields are skipped.
+            Some(_) => continue,
+            None => {
+                return Err(HttpError::with_context(
+                    ErrorCode::InvalidFormat,
+                    format!("The body has no {} field", ATTACHMENT_FIELD),
+                ))
+            }
+        };
+        let file_name = field.file_name().unwrap_or("attachment").to_owned();
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.to_string())
+            .unwrap_or("application/octet-stream".to_owned());
+        let content = field
+            .map(|chunk| chunk.map_err(|e| AttachmentStorageError::InvalidContent(e.to_string())))
+            .boxed();
+        let attachment: GetAttachment = managers
+            .attachment_manager
+            .upload_attachment(&uid, &file_name, &content_type, content)
+            .await?
+            .into();
+        return Ok(value::to_value(attachment)
+            .map_err(|e| {
+                println!(
+                    "An internal error occured while converting attachment to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?
+            .into());
+    }
+}
+
+/// Routes of the attachments of a speech read from `/api/speech/{uid}/attachments`.
+pub async fn speech_attachments_router(
+    speech_uid: Uuid,
+    path: &[&str],
+    method: &Method,
+    token: &AuthToken,
+    attachment_manager: &AttachmentManager,
+) -> Result<Value, HttpError<'static>> {
+    match (method, path) {
+        (&Method::GET, []) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let attachments: Vec<GetAttachment> = attachment_manager
+                .get_attachments(&speech_uid)
+                .await?
+                .into_iter()
+                .map(GetAttachment::from)
+                .collect();
+            Ok(value::to_value(attachments).map_err(|e| {
+                println!(
+                    "An internal error occured while converting attachments to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::GET, [uid, "download"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let (attachment, url, expires_at) = attachment_manager
+                .get_download_url(&speech_uid, &uid)
+                .await?;
+            let download = GetAttachmentDownload {
+                attachment: attachment.into(),
+                url,
+                expires_at: expires_at.to_rfc3339(),
+            };
+            Ok(value::to_value(download).map_err(|e| {
+                println!(
+                    "An internal error occured while converting attachment download to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        _ => Err(HttpError::new(ErrorCode::NotFound)),
+    }
+}
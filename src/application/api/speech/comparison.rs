@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::domain::speech::{
+    comparison::{ComparedSpeech, SpeechComparison},
+    Speech,
+};
+
+use super::speech_router::GetSpeechSentence;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSpeakerSentenceCount {
+    speaker: String,
+    speaker_name: String,
+    sentences: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetComparedSpeech {
+    uid: String,
+    name: String,
+    date: String,
+    media: String,
+    /// Milliseconds from the first to the last timed sentence, only known when the
+    /// sentences are timed.
+    duration: Option<u64>,
+    sentence_count: u64,
+    words: u64,
+    sentences_by_speaker: Vec<GetSpeakerSentenceCount>,
+    sentences: Vec<GetSpeechSentence>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSharedTerm {
+    term: String,
+    count_a: u64,
+    count_b: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSharedSpeaker {
+    speaker: String,
+    speaker_name: String,
+}
+
+/// Two speeches side by side, along with what they have in common.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSpeechComparison {
+    a: GetComparedSpeech,
+    b: GetComparedSpeech,
+    shared_vocabulary: Vec<GetSharedTerm>,
+    shared_speakers: Vec<GetSharedSpeaker>,
+}
+
+fn speaker_name(speaker: &Uuid, speaker_names: &HashMap<Uuid, String>) -> String {
+    speaker_names
+        .get(speaker)
+        .cloned()
+        .unwrap_or_else(|| speaker.to_string())
+}
+
+fn compared_speech(
+    speech: Speech,
+    compared: ComparedSpeech,
+    speaker_names: &HashMap<Uuid, String>,
+) -> GetComparedSpeech {
+    GetComparedSpeech {
+        uid: speech.uid().to_string(),
+        name: speech.name().clone(),
+        date: speech.date().to_rfc3339(),
+        media: speech.media().clone(),
+        duration: compared.duration,
+        sentence_count: compared.sentences,
+        words: compared.words,
+        sentences_by_speaker: compared
+            .sentences_by_speaker
+            .iter()
+            .map(|(speaker, sentences)| GetSpeakerSentenceCount {
+                speaker: speaker.to_string(),
+                speaker_name: speaker_name(speaker, speaker_names),
+                sentences: *sentences,
+            })
+            .collect(),
+        sentences: speech
+            .sentences()
+            .iter()
+            .map(|s| GetSpeechSentence::from(s.clone()))
+            .collect(),
+    }
+}
+
+impl GetSpeechComparison {
+    pub fn new(
+        a: Speech,
+        b: Speech,
+        comparison: SpeechComparison,
+        speaker_names: &HashMap<Uuid, String>,
+    ) -> Self {
+        Self {
+            a: compared_speech(a, comparison.a, speaker_names),
+            b: compared_speech(b, comparison.b, speaker_names),
+            shared_vocabulary: comparison
+                .shared_vocabulary
+                .into_iter()
+                .map(|term| GetSharedTerm {
+                    term: term.term,
+                    count_a: term.count_a,
+                    count_b: term.count_b,
+                })
+                .collect(),
+            shared_speakers: comparison
+                .shared_speakers
+                .iter()
+                .map(|speaker| GetSharedSpeaker {
+                    speaker: speaker.to_string(),
+                    speaker_name: speaker_name(speaker, speaker_names),
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use futures_util::stream;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::{body::Frame, header, Response};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{BoxBody, HttpError},
+    },
+    domain::speech::{sentence::Sentence, Speech},
+};
+
+/// Average speaking pace used to build subtitle timings, in milliseconds per word.
+const MILLIS_PER_WORD: u64 = 400;
+/// Minimum duration a subtitle stays on screen, in milliseconds.
+const MIN_CUE_DURATION: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Txt,
+    Srt,
+    Json,
+}
+
+impl TryFrom<&str> for ExportFormat {
+    type Error = HttpError<'static>;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "txt" => Ok(Self::Txt),
+            "srt" => Ok(Self::Srt),
+            "json" => Ok(Self::Json),
+            _ => Err(HttpError::new(ErrorCode::InvalidExportFormat)),
+        }
+    }
+}
+
+impl ExportFormat {
+    fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Txt => "text/plain; charset=utf-8",
+            ExportFormat::Srt => "application/x-subrip; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Txt => "txt",
+            ExportFormat::Srt => "srt",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Builds the download response of a speech. Each sentence is rendered lazily while the
+/// body is polled, so the whole document is never held in memory.
+pub fn export_speech(
+    speech: Speech,
+    speaker_names: HashMap<Uuid, String>,
+    format: ExportFormat,
+) -> Response<BoxBody> {
+    let header = render_header(&speech, format);
+    let footer = render_footer(format);
+    let mut elapsed = 0;
+    let chunks = speech
+        .sentences()
+        .to_vec()
+        .into_iter()
+        .enumerate()
+        .map(move |(idx, sentence)| {
+            let speaker = speaker_names
+                .get(sentence.speaker())
+                .cloned()
+                .unwrap_or_else(|| sentence.speaker().to_string());
+            render_sentence(idx, &sentence, &speaker, &mut elapsed, format)
+        });
+    let body = stream::iter(
+        std::iter::once(header)
+            .chain(chunks)
+            .chain(std::iter::once(footer))
+            .filter(|chunk| !chunk.is_empty())
+            .map(|chunk| Ok::<_, std::io::Error>(Frame::data(Bytes::from(chunk)))),
+    );
+    Response::builder()
+        .status(200)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"speech-{}.{}\"",
+                speech.uid(),
+                format.extension()
+            ),
+        )
+        .body(StreamBody::new(body).boxed())
+        .expect("Should not fail")
+}
+
+fn render_header(speech: &Speech, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Txt => format!(
+            "{}\n{} - {}\n\n",
+            speech.name(),
+            speech.media(),
+            speech.date().to_rfc3339()
+        ),
+        ExportFormat::Srt => String::new(),
+        ExportFormat::Json => {
+            let header = json!({
+                "uid": speech.uid().to_string(),
+                "name": speech.name(),
+                "date": speech.date().to_rfc3339(),
+                "media": speech.media(),
+            })
+            .to_string();
+            // Reopen the object to append the sentences array.
+            format!("{},\"sentences\":[", &header[..header.len() - 1])
+        }
+    }
+}
+
+fn render_footer(format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Json => "]}".to_owned(),
+        _ => String::new(),
+    }
+}
+
+fn render_sentence(
+    idx: usize,
+    sentence: &Sentence,
+    speaker: &str,
+    elapsed: &mut u64,
+    format: ExportFormat,
+) -> String {
+    match format {
+        ExportFormat::Txt => format!("{}: {}\n", speaker, sentence.text()),
+        ExportFormat::Srt => {
+            // Untimed sentences are estimated from their length, after the previous cue.
+            let (start, end) = match sentence.timing() {
+                Some(timing) => (timing.start as u64, timing.end as u64),
+                None => {
+                    let words = sentence.text().split_whitespace().count() as u64;
+                    (
+                        *elapsed,
+                        *elapsed + (words * MILLIS_PER_WORD).max(MIN_CUE_DURATION),
+                    )
+                }
+            };
+            *elapsed = end;
+            format!(
+                "{}\n{} --> {}\n{}: {}\n\n",
+                idx + 1,
+                srt_timestamp(start),
+                srt_timestamp(end),
+                speaker,
+                sentence.text()
+            )
+        }
+        ExportFormat::Json => {
+            let sentence = json!({
+                "uid": sentence.uid().to_string(),
+                "speaker": sentence.speaker().to_string(),
+                "speakerName": speaker,
+                "text": sentence.text(),
+                "interrupted": sentence.interrupted(),
+                "start": sentence.timing().map(|t| t.start),
+                "end": sentence.timing().map(|t| t.end),
+            });
+            if idx == 0 {
+                sentence.to_string()
+            } else {
+                format!(",{}", sentence)
+            }
+        }
+    }
+}
+
+fn srt_timestamp(millis: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        millis / 3_600_000,
+        (millis / 60_000) % 60,
+        (millis / 1000) % 60,
+        millis % 1000
+    )
+}
@@ -0,0 +1,691 @@
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use http_body_util::BodyExt;
+use hyper::{
+    body,
+    header::{self, HeaderMap},
+    upgrade::OnUpgrade,
+    Method, Response,
+};
+use hyper_util::rt::TokioIo;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    time,
+};
+use tokio_tungstenite::{
+    tungstenite::{
+        handshake::derive_accept_key,
+        protocol::{frame::coding::CloseCode, CloseFrame, Role, WebSocketConfig},
+        Message,
+    },
+    WebSocketStream,
+};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::{ErrorCode, Language},
+        router::{
+            full, BoxBody, HttpError, Managers, RouteResponse, ACCESS_DENIED_ERROR, INTERNAL_ERROR,
+        },
+        sse::{event, event_stream},
+        token::{AuthToken, Permissions},
+        validation::Validation,
+    },
+    domain::speech::{
+        live::{LiveSentence, LiveSession},
+        Speech, SpeechStatus,
+    },
+};
+
+use super::speech_router::{CreateSpeechInput, CreateSpeechSentenceInput, GetSpeechSentence};
+
+/// Delay after which the streamed sentences are flushed to the database.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+/// Number of streamed sentences flushed without waiting for the delay.
+const MAX_PENDING_SENTENCES: usize = 50;
+/// Number of sentences of a streamed speech creation stored by each transaction.
+const MAX_PENDING_CREATED_SENTENCES: usize = 1000;
+/// Largest frame sent to a live session over a WebSocket, in bytes.
+const MAX_SENTENCE_FRAME_SIZE: usize = 64 * 1024;
+
+#[derive(Deserialize)]
+struct LiveSentenceInput {
+    /// Position of the sentence in the speech, starting at 0. The sentence follows the
+    /// last one received when missing.
+    seq: Option<u32>,
+    #[serde(flatten)]
+    sentence: CreateSpeechSentenceInput,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LiveIngestionSummary {
+    received: u64,
+    appended: u64,
+    next_seq: u32,
+}
+
+#[derive(Serialize)]
+struct StreamedSpeechSummary {
+    uid: String,
+    sentences: u32,
+}
+
+#[derive(Serialize)]
+struct GetLiveSentence {
+    seq: u32,
+    #[serde(flatten)]
+    sentence: GetSpeechSentence,
+}
+
+/// Frame sent to the clients of a live session opened with `GET /api/speech/{uid}/live`.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum LiveFrame {
+    /// Sentence appended to the speech, by the producer of the session or another one.
+    Sentence(GetLiveSentence),
+    /// The producer left and the speech was finalized, as by `POST /api/speech/{uid}/finalize`.
+    #[serde(rename_all = "camelCase")]
+    Finalized { received: usize, sentences: usize },
+    /// A frame of the client was rejected, the session going on.
+    Error { error: Value },
+}
+
+fn is_ndjson(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/x-ndjson"))
+}
+
+/// Whether the body of the request is streamed and must not be read as a whole: the
+/// requests to `/api/speech/{uid}/sentences/stream`, and the speeches created with
+/// `POST /api/speech` as NDJSON.
+pub fn is_streamed_route(method: &Method, path: &str, headers: &HeaderMap) -> bool {
+    match path.split("/").collect::<Vec<&str>>().as_slice() {
+        ["", "api", "speech", _, "sentences", "stream"] => true,
+        ["", "api", "speech"] | ["", "api", "speech", ""] => {
+            method == Method::POST && is_ndjson(headers)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the request opens a live session over a WebSocket, the connection being
+/// upgraded instead of its body read.
+pub fn is_websocket_route(method: &Method, path: &str) -> bool {
+    method == Method::GET
+        && matches!(
+            path.split("/").collect::<Vec<&str>>().as_slice(),
+            ["", "api", "speech", _, "live"]
+        )
+}
+
+/// Splits a streamed body into lines, without reading it as a whole.
+struct Lines {
+    body: body::Incoming,
+    buffer: Vec<u8>,
+    max_line_size: usize,
+}
+
+impl Lines {
+    fn new(body: body::Incoming, max_line_size: usize) -> Self {
+        Self {
+            body,
+            buffer: Vec::new(),
+            max_line_size,
+        }
+    }
+
+    /// Reads the next line, `None` being returned at the end of the body. Nothing is lost
+    /// when the read is cancelled, e.g. by a `select!`.
+    async fn next(&mut self) -> Result<Option<Vec<u8>>, HttpError<'static>> {
+        loop {
+            if let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+                return Ok(Some(self.buffer.drain(..=end).collect()));
+            }
+            if self.buffer.len() > self.max_line_size {
+                return Err(HttpError::with_context(
+                    ErrorCode::PayloadTooLarge,
+                    format!(
+                        "A streamed line must not exceed {} bytes",
+                        self.max_line_size
+                    ),
+                ));
+            }
+            match self.body.frame().await {
+                Some(Ok(frame)) => {
+                    if let Ok(data) = frame.into_data() {
+                        self.buffer.extend_from_slice(&data);
+                    }
+                }
+                Some(Err(e)) => {
+                    println!("An internal error occured while getting the body : {:?}", e);
+                    return Err(INTERNAL_ERROR);
+                }
+                None if self.buffer.is_empty() => return Ok(None),
+                // The last line may not end with a line break.
+                None => return Ok(Some(std::mem::take(&mut self.buffer))),
+            }
+        }
+    }
+}
+
+/// Live transcript routes. The producer streams sentences as NDJSON, one sentence by line,
+/// and the viewers receive them as server-sent events.
+pub async fn router(
+    path: &str,
+    method: &Method,
+    headers: &HeaderMap,
+    token: &AuthToken,
+    body: body::Incoming,
+    managers: &Managers,
+    max_line_size: usize,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    let uid = match splitted_path.as_slice() {
+        [""] => return create_streamed_speech(token, body, managers, max_line_size).await,
+        [uid, "sentences", "stream"] => {
+            Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?
+        }
+        _ => return Err(HttpError::new(ErrorCode::NotFound)),
+    };
+    match *method {
+        Method::POST => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            if !is_ndjson(headers) {
+                return Err(HttpError::new(ErrorCode::UnsupportedMediaType));
+            }
+            let speech_manager = &managers.speech_manager;
+            let mut session = speech_manager.open_live_session(uid).await?;
+            let mut summary = LiveIngestionSummary {
+                received: 0,
+                appended: 0,
+                next_seq: session.next_seq(),
+            };
+            // The sentences received before an error are still stored, the producer
+            // resumes from the seq given in the summary or in the error.
+            let result = ingest_sentences(
+                &mut session,
+                &mut Lines::new(body, max_line_size),
+                managers,
+                MAX_PENDING_SENTENCES,
+                &mut summary,
+            )
+            .await;
+            let flushed = speech_manager.flush_live_session(&session).await;
+            result?;
+            flushed?;
+            summary.next_seq = session.next_seq();
+            Ok(serde_json::to_value(summary)
+                .map_err(|e| {
+                    println!("An internal error occured while converting summary: {}", e);
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        Method::GET => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            // Viewers reconnecting only get the sentences they missed.
+            let after = headers
+                .get("Last-Event-ID")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u32>().ok());
+            let (sentences, receiver) = managers
+                .speech_manager
+                .watch_live_sentences(uid, after)
+                .await?;
+            Ok(RouteResponse::Raw(sentences_event_stream(
+                sentences, after, receiver,
+            )))
+        }
+        _ => Err(HttpError::new(ErrorCode::NotFound)),
+    }
+}
+
+/// Creates a speech sent as NDJSON, for the transcripts too large to be read as one body:
+/// the first line holds the speech as sent to `POST /api/speech`, the next ones its
+/// sentences. The speech is stored live from the first line, its sentences being appended
+/// by chunks as they are read, then it waits for review.
+///
+/// When a sentence cannot be stored, the speech is left live with the sentences read
+/// before it, the error giving its uid so the rest can be streamed to
+/// `/api/speech/{uid}/sentences/stream` before finalizing it.
+async fn create_streamed_speech(
+    token: &AuthToken,
+    body: body::Incoming,
+    managers: &Managers,
+    max_line_size: usize,
+) -> Result<RouteResponse, HttpError<'static>> {
+    if !token.permissions().contains(&Permissions::CreateSpeech) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let speech_manager = &managers.speech_manager;
+    let mut lines = Lines::new(body, max_line_size);
+    let header = loop {
+        match lines.next().await? {
+            Some(line) if line.trim_ascii().is_empty() => continue,
+            Some(line) => break line,
+            None => return Err(HttpError::new(ErrorCode::InvalidFormat)),
+        }
+    };
+    let not_a_speech = |e: serde_json::Error| {
+        HttpError::with_context(
+            ErrorCode::InvalidFormat,
+            format!("The first line is not a speech: {}", e),
+        )
+    };
+    let mut header: Value = serde_json::from_slice(&header).map_err(not_a_speech)?;
+    // The sentences of the speech follow its first line.
+    if let Some(header) = header.as_object_mut() {
+        header
+            .entry("sentences")
+            .or_insert_with(|| Value::Array(Vec::new()));
+    }
+    let input: CreateSpeechInput = serde_json::from_value(header).map_err(not_a_speech)?;
+    let mut validation = Validation::default();
+    input.validate(&mut validation, "");
+    validation.into_result()?;
+    let mut speech: Speech = input.try_into()?;
+    speech.update_speech_status(SpeechStatus::Live);
+    let uid = *speech.uid();
+    speech_manager.create_speech(speech).await?;
+    let mut session = speech_manager.open_live_session(uid).await?;
+    let mut summary = LiveIngestionSummary {
+        received: 0,
+        appended: 0,
+        next_seq: session.next_seq(),
+    };
+    let result = ingest_sentences(
+        &mut session,
+        &mut lines,
+        managers,
+        MAX_PENDING_CREATED_SENTENCES,
+        &mut summary,
+    )
+    .await;
+    let flushed = speech_manager
+        .flush_live_session(&session)
+        .await
+        .map_err(HttpError::from);
+    let sentences = session.next_seq();
+    drop(session);
+    if let Err(e) = result.and(flushed) {
+        return Err(e.add_context(&format!(
+            "The speech {} is kept live, the sentences from the seq {} may be streamed to /api/speech/{}/sentences/stream",
+            uid, sentences, uid
+        )));
+    }
+    speech_manager.finish_speech_ingestion(uid).await?;
+    Ok(serde_json::to_value(StreamedSpeechSummary {
+        uid: uid.to_string(),
+        sentences,
+    })
+    .map_err(|e| {
+        println!("An internal error occured while converting summary: {}", e);
+        INTERNAL_ERROR
+    })?
+    .into())
+}
+
+/// Reads the NDJSON sentences line by line, flushing the session periodically or once
+/// `max_pending` sentences are waiting.
+async fn ingest_sentences(
+    session: &mut LiveSession,
+    lines: &mut Lines,
+    managers: &Managers,
+    max_pending: usize,
+    summary: &mut LiveIngestionSummary,
+) -> Result<(), HttpError<'static>> {
+    let speech_manager = &managers.speech_manager;
+    let mut flush = time::interval(FLUSH_INTERVAL);
+    loop {
+        tokio::select! {
+            line = lines.next() => match line? {
+                Some(line) => {
+                    ingest_line(session, &line, summary)?;
+                    if session.pending_len() >= max_pending {
+                        speech_manager.flush_live_session(session).await?;
+                    }
+                }
+                None => return Ok(()),
+            },
+            _ = flush.tick() => speech_manager.flush_live_session(session).await?,
+            // The speech is being finalized, the sentences received so far are kept.
+            _ = session.stopped() => return Ok(()),
+        }
+    }
+}
+
+fn ingest_line(
+    session: &mut LiveSession,
+    line: &[u8],
+    summary: &mut LiveIngestionSummary,
+) -> Result<(), HttpError<'static>> {
+    if line.trim_ascii().is_empty() {
+        return Ok(());
+    }
+    let input: LiveSentenceInput = serde_json::from_slice(line).map_err(|e| {
+        HttpError::with_context(
+            ErrorCode::InvalidFormat,
+            format!(
+                "The sentence following the seq {} is invalid: {}",
+                summary.next_seq, e
+            ),
+        )
+    })?;
+    summary.received += 1;
+    let seq = input.seq.unwrap_or(session.next_seq());
+    if session.push(seq, input.sentence.try_into()?)? {
+        summary.appended += 1;
+    }
+    summary.next_seq = session.next_seq();
+    Ok(())
+}
+
+/// Streams the sentences as server-sent events, the `id` of an event being the seq of
+/// the sentence.
+fn sentences_event_stream(
+    sentences: Vec<LiveSentence>,
+    after: Option<u32>,
+    receiver: broadcast::Receiver<LiveSentence>,
+) -> Response<BoxBody> {
+    let mut next_seq = sentences
+        .last()
+        .map(|s| s.seq() + 1)
+        .or(after.map(|after| after + 1))
+        .unwrap_or_default();
+    let backlog = sentences.into_iter().map(sentence_event).collect();
+    event_stream(backlog, receiver, move |sentence| {
+        // Already sent from the backlog.
+        if sentence.seq() < next_seq {
+            return None;
+        }
+        next_seq = sentence.seq() + 1;
+        Some(sentence_event(sentence))
+    })
+}
+
+fn sentence_event(sentence: LiveSentence) -> Bytes {
+    let seq = sentence.seq();
+    event(
+        Some(seq),
+        "sentence",
+        &GetLiveSentence {
+            seq,
+            sentence: sentence.sentence().clone().into(),
+        },
+    )
+}
+
+/// Opens a live session over a WebSocket on `GET /api/speech/{uid}/live`. Every client
+/// receives the sentences of the speech as `sentence` frames, from the seq following the
+/// `after` query parameter, then as they are appended. A client with `UpdateSpeech` may
+/// also send sentences as text frames, as the lines streamed to
+/// `/api/speech/{uid}/sentences/stream`: it becomes the producer of the speech, and the
+/// speech is finalized once it closes the session.
+pub async fn websocket_router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    headers: &HeaderMap,
+    token: &AuthToken,
+    upgrade: OnUpgrade,
+    managers: &Managers,
+    request_id: &str,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let uid = match path.split("/").collect::<Vec<&str>>().as_slice() {
+        [uid, "live"] => Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?,
+        _ => return Err(HttpError::new(ErrorCode::NotFound)),
+    };
+    if !token.permissions().contains(&Permissions::GetSpeech) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let is_websocket = headers
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let key = match headers.get(header::SEC_WEBSOCKET_KEY) {
+        Some(key) if is_websocket => key,
+        _ => return Err(HttpError::new(ErrorCode::WebSocketRequired)),
+    };
+    let after = match query_params.get("after") {
+        Some(v) => Some(v.parse::<u32>().map_err(|_| {
+            HttpError::with_context(
+                ErrorCode::InvalidFormat,
+                "The after parameter must be the seq of a sentence".to_owned(),
+            )
+        })?),
+        None => None,
+    };
+    // Read before upgrading, an unknown speech is answered with a 404.
+    let (sentences, receiver) = managers
+        .speech_manager
+        .watch_live_sentences(uid, after)
+        .await?;
+    let session = LiveSocketSession {
+        uid,
+        can_push: token.permissions().contains(&Permissions::UpdateSpeech),
+        managers: managers.clone(),
+        language: Language::from_accept_language(
+            headers
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|v| v.to_str().ok()),
+        ),
+        request_id: request_id.to_owned(),
+    };
+    tokio::spawn(async move {
+        let upgraded = match upgrade.await {
+            Ok(upgraded) => upgraded,
+            Err(e) => {
+                println!("An error occured while upgrading to a WebSocket: {:?}", e);
+                return;
+            }
+        };
+        let config = WebSocketConfig {
+            max_message_size: Some(MAX_SENTENCE_FRAME_SIZE),
+            ..Default::default()
+        };
+        let socket =
+            WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, Some(config))
+                .await;
+        if let Err(e) = session.run(socket, sentences, after, receiver).await {
+            println!("An error occured on the live session of {}: {:?}", uid, e);
+        }
+    });
+    Ok(RouteResponse::Raw(
+        Response::builder()
+            .status(101)
+            .header(header::CONNECTION, "upgrade")
+            .header(header::UPGRADE, "websocket")
+            .header(
+                header::SEC_WEBSOCKET_ACCEPT,
+                derive_accept_key(key.as_bytes()),
+            )
+            .body(full(""))
+            .map_err(|e| {
+                println!(
+                    "An internal error occured while building the upgrade: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?,
+    ))
+}
+
+/// Client of a live session, a viewer until it sends a sentence.
+struct LiveSocketSession {
+    uid: Uuid,
+    can_push: bool,
+    managers: Managers,
+    language: Language,
+    request_id: String,
+}
+
+type LiveSocket = WebSocketStream<TokioIo<hyper::upgrade::Upgraded>>;
+
+impl LiveSocketSession {
+    async fn run(
+        &self,
+        mut socket: LiveSocket,
+        sentences: Vec<LiveSentence>,
+        after: Option<u32>,
+        mut receiver: broadcast::Receiver<LiveSentence>,
+    ) -> Result<(), tokio_tungstenite::tungstenite::Error> {
+        let speech_manager = &self.managers.speech_manager;
+        let mut next_seq = sentences
+            .last()
+            .map(|s| s.seq() + 1)
+            .or(after.map(|after| after + 1))
+            .unwrap_or_default();
+        for sentence in sentences {
+            socket.feed(sentence_frame(sentence)).await?;
+        }
+        socket.flush().await?;
+        // Opened by the first sentence sent by the client.
+        let mut session: Option<LiveSession> = None;
+        let mut summary = LiveIngestionSummary {
+            received: 0,
+            appended: 0,
+            next_seq: 0,
+        };
+        let mut flush = time::interval(FLUSH_INTERVAL);
+        loop {
+            tokio::select! {
+                message = socket.next() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        let pushed = self.push(&mut session, text.as_bytes(), &mut summary).await;
+                        if let Err(e) = pushed {
+                            socket.send(self.error_frame(e)).await?;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    // Pings are answered by the socket itself.
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        println!("An error occured on the live session of {}: {:?}", self.uid, e);
+                        break;
+                    }
+                },
+                sentence = receiver.recv() => match sentence {
+                    // Already sent from the backlog.
+                    Ok(sentence) if sentence.seq() < next_seq => {}
+                    Ok(sentence) => {
+                        next_seq = sentence.seq() + 1;
+                        socket.send(sentence_frame(sentence)).await?;
+                    }
+                    Err(RecvError::Lagged(_)) => {
+                        socket
+                            .send(Message::Close(Some(CloseFrame {
+                                code: CloseCode::Again,
+                                reason: "The client fell behind the live transcript".into(),
+                            })))
+                            .await?;
+                        break;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = flush.tick(), if session.is_some() => {
+                    if let Some(live) = &session {
+                        if let Err(e) = speech_manager.flush_live_session(live).await {
+                            socket.send(self.error_frame(e.into())).await?;
+                        }
+                    }
+                },
+                // The speech is being finalized by someone else, the sentences received so
+                // far are kept and the client goes on as a viewer.
+                _ = stopped(&session), if session.is_some() => {
+                    if let Some(live) = session.take() {
+                        if let Err(e) = speech_manager.flush_live_session(&live).await {
+                            socket.send(self.error_frame(e.into())).await?;
+                        }
+                    }
+                },
+            }
+        }
+        // The producer leaving ends the live transcript.
+        if let Some(live) = session.take() {
+            let flushed = speech_manager.flush_live_session(&live).await;
+            // Released first, the finalization waiting for the producer to leave.
+            drop(live);
+            let finalized = match flushed {
+                Ok(()) => speech_manager.finalize_live_speech(self.uid).await,
+                Err(e) => Err(e),
+            };
+            let frame = match finalized {
+                Ok((received, sentences)) => json_frame(&LiveFrame::Finalized {
+                    received,
+                    sentences,
+                }),
+                Err(e) => self.error_frame(e.into()),
+            };
+            // The client may already be gone.
+            let _ = socket.send(frame).await;
+        }
+        let _ = socket.close(None).await;
+        Ok(())
+    }
+
+    /// Appends a sentence sent by the client, opening the live session of the speech on
+    /// the first one.
+    async fn push(
+        &self,
+        session: &mut Option<LiveSession>,
+        frame: &[u8],
+        summary: &mut LiveIngestionSummary,
+    ) -> Result<(), HttpError<'static>> {
+        if !self.can_push {
+            return Err(ACCESS_DENIED_ERROR);
+        }
+        let speech_manager = &self.managers.speech_manager;
+        let live = match session.take() {
+            Some(live) => live,
+            None => {
+                let live = speech_manager.open_live_session(self.uid).await?;
+                summary.next_seq = live.next_seq();
+                live
+            }
+        };
+        let live = session.insert(live);
+        ingest_line(live, frame, summary)?;
+        if live.pending_len() >= MAX_PENDING_SENTENCES {
+            speech_manager.flush_live_session(live).await?;
+        }
+        Ok(())
+    }
+
+    fn error_frame(&self, error: HttpError<'static>) -> Message {
+        json_frame(&LiveFrame::Error {
+            error: error.to_json(self.language, &self.request_id),
+        })
+    }
+}
+
+/// Resolves once the producer is asked to stop, never without a session.
+async fn stopped(session: &Option<LiveSession>) {
+    match session {
+        Some(session) => session.stopped().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn sentence_frame(sentence: LiveSentence) -> Message {
+    let seq = sentence.seq();
+    json_frame(&LiveFrame::Sentence(GetLiveSentence {
+        seq,
+        sentence: sentence.sentence().clone().into(),
+    }))
+}
+
+fn json_frame(frame: &LiveFrame) -> Message {
+    Message::Text(serde_json::to_string(frame).expect("Should not fail"))
+}
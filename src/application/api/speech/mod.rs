@@ -1 +1,7 @@
+pub mod annotation_router;
+pub mod attachment_router;
+pub mod comparison;
+pub mod export;
+pub mod live_router;
+pub mod segment_router;
 pub mod speech_router;
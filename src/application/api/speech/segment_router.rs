@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, ACCESS_DENIED_ERROR, INTERNAL_ERROR},
+        token::{AuthToken, Permissions},
+        validation::{field, FieldProblem, Validation},
+    },
+    domain::segment::{Segment, SegmentManager, SegmentRepositoryError},
+};
+
+impl From<SegmentRepositoryError> for HttpError<'static> {
+    fn from(value: SegmentRepositoryError) -> Self {
+        match value {
+            SegmentRepositoryError::SpeechNotFound => HttpError::new(ErrorCode::SpeechNotFound),
+            SegmentRepositoryError::SegmentNotFound => HttpError::new(ErrorCode::SegmentNotFound),
+            SegmentRepositoryError::OutOfBounds => HttpError::new(ErrorCode::SegmentOutOfBounds),
+            SegmentRepositoryError::Overlap(uid) => {
+                HttpError::with_context(ErrorCode::SegmentOverlap, uid.to_string())
+            }
+            SegmentRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Segments: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentInput {
+    title: String,
+    /// Index of the first sentence of the segment.
+    start_index: u32,
+    /// Index of the last sentence of the segment, included.
+    end_index: u32,
+}
+
+impl SegmentInput {
+    /// Returns the segment of the speech described by the input, once valid. Its bounds
+    /// are checked against the sentences by the repository.
+    fn segment(self, uid: Uuid, speech_uid: Uuid) -> Result<Segment, HttpError<'static>> {
+        let mut validation = Validation::default();
+        let title = self.title.trim();
+        validation.check(
+            !title.is_empty() && title.chars().count() <= 200,
+            field("", "title"),
+            FieldProblem::InvalidSegmentTitle,
+        );
+        validation.check(
+            self.start_index <= self.end_index,
+            field("", "endIndex"),
+            FieldProblem::SegmentEndsBeforeStart,
+        );
+        validation.into_result()?;
+        Ok(Segment::new(
+            uid,
+            speech_uid,
+            title,
+            self.start_index,
+            self.end_index,
+        ))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSegment {
+    uid: String,
+    title: String,
+    start_index: u32,
+    end_index: u32,
+}
+
+impl From<Segment> for GetSegment {
+    fn from(value: Segment) -> Self {
+        Self {
+            uid: value.uid().to_string(),
+            title: value.title().clone(),
+            start_index: value.start_index(),
+            end_index: value.end_index(),
+        }
+    }
+}
+
+fn segment_to_value(segment: Segment) -> Result<Value, HttpError<'static>> {
+    value::to_value(GetSegment::from(segment)).map_err(|e| {
+        println!(
+            "An internal error occured while converting segment to value: {:?}",
+            e
+        );
+        INTERNAL_ERROR
+    })
+}
+
+/// Routes of the segments of a speech read from `/api/speech/{uid}/segments`.
+pub async fn speech_segments_router(
+    speech_uid: Uuid,
+    path: &[&str],
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    segment_manager: &SegmentManager,
+) -> Result<Value, HttpError<'static>> {
+    match (method, path) {
+        (&Method::GET, []) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let segments: Vec<GetSegment> = segment_manager
+                .get_segments(&speech_uid)
+                .await?
+                .into_iter()
+                .map(GetSegment::from)
+                .collect();
+            Ok(value::to_value(segments).map_err(|e| {
+                println!(
+                    "An internal error occured while converting segments to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, []) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let input: SegmentInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let segment = input.segment(Uuid::new_v4(), speech_uid)?;
+            segment_manager.create_segment(segment.clone()).await?;
+            segment_to_value(segment)
+        }
+        (&Method::GET, [uid]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            segment_to_value(segment_manager.get_segment(&speech_uid, &uid).await?)
+        }
+        (&Method::PUT, [uid]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let input: SegmentInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let segment = input.segment(uid, speech_uid)?;
+            segment_manager.update_segment(segment.clone()).await?;
+            segment_to_value(segment)
+        }
+        (&Method::DELETE, [uid]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            segment_manager.delete_segment(&speech_uid, &uid).await?;
+            Ok(Value::Null)
+        }
+        _ => Err(HttpError::new(ErrorCode::NotFound)),
+    }
+}
@@ -1,36 +1,152 @@
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
 
-use chrono::DateTime;
-use hyper::Method;
+use chrono::{DateTime, Utc};
+use hyper::{header::HeaderMap, Method, Response};
 use serde::{Deserialize, Serialize};
-use serde_json::{value, Value};
+use serde_json::{json, value, Value};
 use uuid::Uuid;
 
 use crate::{
     application::api::{
-        router::{HttpError, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        error::ErrorCode,
+        fields::FieldSelection,
+        filter::extract_filter_spec,
+        job::job_router::job_value,
+        label::label_router::entity_labels_router,
+        person::person_router::CreatePersonInput,
+        precondition::ExpectedVersion,
+        router::{
+            extract_include_deleted, extract_include_moderators, extract_language,
+            extract_on_conflict, extract_uid_array_in_query, full, HttpError, Managers,
+            RouteResponse, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR,
+        },
+        sse::{event, event_stream},
+        tag::tag_router::speech_tags_router,
         token::{AuthToken, Permissions},
+        validation::{field, FieldProblem, Validation},
     },
-    domain::speech::{
-        manager::SpeechManager, sentence::Sentence, speech_repository::SpeechRepositoryError,
-        Speech, SpeechStatus,
+    domain::{
+        job::JobKind,
+        label::LabelTarget,
+        person::{PersonManager, PersonRepositoryError},
+        pii::PiiDetectorError,
+        speech::{
+            analytics::{SpeechGroupCount, SpeechGrouping},
+            event::SpeechEvent,
+            import::{ImportConflict, ImportReport, ImportResolution},
+            language::{language_code, language_tag, SpeechLanguage},
+            manager::SpeechManager,
+            pii_flag::PiiFlag,
+            progress::ReadProgress,
+            quote::{citation, Quote},
+            revision::SpeechRevision,
+            sentence::{ReviewStatus, Sentence, SentenceTiming},
+            speech_repository::{
+                SpeechFilter, SpeechIdentity, SpeechProjection, SpeechRepositoryError,
+                SpeechSummary,
+            },
+            SpeakerRole, Speech, SpeechStatus,
+        },
+        translation::TranslatorError,
     },
 };
 
+use super::{
+    annotation_router::{extract_sentiment_filter, GetSentenceAnnotation, GetSpeechSentiment},
+    attachment_router::speech_attachments_router,
+    comparison::GetSpeechComparison,
+    export::{export_speech, ExportFormat},
+    segment_router::{speech_segments_router, GetSegment},
+};
+
+/// Largest number of sentences quoted on each side of a quote.
+const MAX_QUOTE_CONTEXT: usize = 10;
+
+/// Sentences of a speech sent by default, the following ones being read with the
+/// `sentence_page` query parameter.
+const DEFAULT_SENTENCE_QUANTITY: u16 = 500;
+
+/// Keywords of a speech sent by default, enough for a word cloud.
+const DEFAULT_KEYWORD_QUANTITY: u16 = 30;
+
 impl From<SpeechRepositoryError> for HttpError<'static> {
     fn from(value: SpeechRepositoryError) -> Self {
         match value {
             SpeechRepositoryError::PersonError(person_repository_error) => {
                 person_repository_error.into()
             }
-            SpeechRepositoryError::SpeechNotFound => {
-                HttpError::new(404, "SpeechNotFound", "The speech requested is not found")
+            SpeechRepositoryError::SpeechNotFound => HttpError::new(ErrorCode::SpeechNotFound),
+            SpeechRepositoryError::VersionMismatch => HttpError::new(ErrorCode::VersionConflict),
+            SpeechRepositoryError::SpeechAlreadyExists => {
+                HttpError::new(ErrorCode::SpeechAlreadyExists)
+            }
+            SpeechRepositoryError::SpeakerNotFound => HttpError::new(ErrorCode::SpeakerNotFound),
+            SpeechRepositoryError::UnknownSpeakers(speakers) => HttpError::with_context(
+                ErrorCode::UnknownSpeakers,
+                format!(
+                    "Unknown speakers: {}",
+                    speakers
+                        .iter()
+                        .map(|speaker| speaker.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            ),
+            SpeechRepositoryError::SpeakerAlreadyExists => {
+                HttpError::new(ErrorCode::SpeakerAlreadyExists)
+            }
+            SpeechRepositoryError::SpeakerHasSentences => {
+                HttpError::new(ErrorCode::SpeakerHasSentences)
+            }
+            SpeechRepositoryError::RevisionNotFound => HttpError::new(ErrorCode::RevisionNotFound),
+            SpeechRepositoryError::SpeechNotLive => HttpError::new(ErrorCode::SpeechNotLive),
+            SpeechRepositoryError::SpeechNotPending => HttpError::new(ErrorCode::SpeechNotPending),
+            SpeechRepositoryError::LiveStreamInProgress => {
+                HttpError::new(ErrorCode::LiveStreamInProgress)
+            }
+            SpeechRepositoryError::SentenceOutOfOrder(expected) => HttpError::with_context(
+                ErrorCode::SentenceOutOfOrder,
+                format!("The next sentence expected has the seq {}", expected),
+            ),
+            SpeechRepositoryError::SentenceNotFound => HttpError::new(ErrorCode::SentenceNotFound),
+            SpeechRepositoryError::ImportConflictNotFound => {
+                HttpError::new(ErrorCode::ImportConflictNotFound)
+            }
+            SpeechRepositoryError::InvalidImportResolution(reason) => {
+                HttpError::with_context(ErrorCode::InvalidImportResolution, reason)
+            }
+            SpeechRepositoryError::TranslationError(TranslatorError::Unavailable) => {
+                HttpError::new(ErrorCode::TranslationUnavailable)
+            }
+            SpeechRepositoryError::TranslationError(TranslatorError::UnsupportedLanguage) => {
+                HttpError::new(ErrorCode::UnsupportedTranslationLanguage)
             }
-            SpeechRepositoryError::SpeechAlreadyExists => HttpError::new(
-                409,
-                "SpeechAlreadyExists",
-                "The speech you try to create already exists.",
+            SpeechRepositoryError::TranslationError(TranslatorError::ProviderError(e)) => {
+                println!("Translation Error: {}", e);
+                HttpError::new(ErrorCode::TranslationFailed)
+            }
+            SpeechRepositoryError::PiiNotReviewed(pending) => HttpError::with_context(
+                ErrorCode::PiiNotReviewed,
+                format!(
+                    "{} personal data flags of the speech must be reviewed before validating it",
+                    pending
+                ),
+            ),
+            SpeechRepositoryError::PiiFlagNotFound => HttpError::new(ErrorCode::PiiFlagNotFound),
+            SpeechRepositoryError::SentencesFlagged(flagged) => HttpError::with_context(
+                ErrorCode::SentencesFlagged,
+                format!(
+                    "{} sentences of the speech are flagged and must be fixed before validating it",
+                    flagged
+                ),
             ),
+            SpeechRepositoryError::PiiDetectionError(PiiDetectorError::ProviderError(e)) => {
+                println!("PII Detection Error: {}", e);
+                HttpError::new(ErrorCode::PiiDetectionFailed)
+            }
             SpeechRepositoryError::InternalError(e) => {
                 println!("Internal Error: {}", e);
                 INTERNAL_ERROR
@@ -44,21 +160,36 @@ pub struct CreateSpeechSentenceInput {
     speaker: String,
     text: String,
     interrupted: bool,
+    /// Position of the sentence in the media, in milliseconds.
+    start: Option<u32>,
+    end: Option<u32>,
+    /// BCP 47 tag or ISO 639-3 code, when the sentence is not in the language of the
+    /// speech.
+    language: Option<String>,
 }
 
 impl TryFrom<CreateSpeechSentenceInput> for Sentence {
     type Error = HttpError<'static>;
 
     fn try_from(value: CreateSpeechSentenceInput) -> Result<Self, Self::Error> {
-        let speaker_id = Uuid::from_str(&value.speaker).map_err(|_| {
-            HttpError::new(400, "InvalidUID", "A speaker uid have an invalid format")
-        })?;
-        return Ok(Self::new(
-            &Uuid::new_v4(),
-            &speaker_id,
-            &value.text,
-            value.interrupted,
-        ));
+        let speaker_id =
+            Uuid::from_str(&value.speaker).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+        let timing = match (value.start, value.end) {
+            (Some(start), Some(end)) if start <= end => Some(SentenceTiming { start, end }),
+            (None, None) => None,
+            _ => return Err(HttpError::new(ErrorCode::InvalidTimestamps)),
+        };
+        let language = match value.language {
+            Some(tag) => {
+                Some(language_code(&tag).ok_or(HttpError::new(ErrorCode::InvalidLanguage))?)
+            }
+            None => None,
+        };
+        return Ok(
+            Self::new(&Uuid::new_v4(), &speaker_id, &value.text, value.interrupted)
+                .with_timing(timing)
+                .with_language(language),
+        );
     }
 }
 
@@ -69,6 +200,76 @@ pub struct CreateSpeechInput {
     speakers: Vec<String>,
     sentences: Vec<CreateSpeechSentenceInput>,
     media: String,
+    /// BCP 47 tag or ISO 639-3 code, detected from the sentences when missing.
+    language: Option<String>,
+    /// Roles by speaker uid, the speakers missing are panelists.
+    #[serde(default)]
+    roles: HashMap<String, String>,
+    /// Whether the sentences are then streamed by a live captioning pipeline, until the
+    /// speech is finalized.
+    #[serde(default)]
+    live: bool,
+}
+
+/// Maximum number of speakers of a speech.
+const MAX_SPEAKERS: usize = 100;
+
+impl CreateSpeechInput {
+    /// Checks the fields of the speech found at `path` in the body.
+    pub fn validate(&self, validation: &mut Validation, path: &str) {
+        validation.check(
+            !self.name.trim().is_empty(),
+            field(path, "name"),
+            FieldProblem::EmptyName,
+        );
+        validation.check(
+            DateTime::<Utc>::from_str(&self.date).is_ok(),
+            field(path, "date"),
+            FieldProblem::InvalidDate,
+        );
+        validation.check(
+            self.speakers.len() <= MAX_SPEAKERS,
+            field(path, "speakers"),
+            FieldProblem::TooManySpeakers { max: MAX_SPEAKERS },
+        );
+        let mut speakers = Vec::with_capacity(self.speakers.len());
+        for (index, speaker) in self.speakers.iter().enumerate() {
+            let uid = Uuid::from_str(speaker).ok();
+            validation.check(
+                uid.is_some(),
+                field(path, &format!("speakers[{}]", index)),
+                FieldProblem::InvalidSpeakerUid,
+            );
+            speakers.extend(uid);
+        }
+        for speaker in self.roles.keys() {
+            validation.check(
+                Uuid::from_str(speaker).is_ok_and(|speaker| speakers.contains(&speaker)),
+                field(&field(path, "roles"), speaker),
+                FieldProblem::SpeakerNotInSpeech,
+            );
+        }
+        for (index, sentence) in self.sentences.iter().enumerate() {
+            let path = field(path, &format!("sentences[{}]", index));
+            validation.check(
+                !sentence.text.trim().is_empty(),
+                field(&path, "text"),
+                FieldProblem::EmptyText,
+            );
+            match Uuid::from_str(&sentence.speaker) {
+                Ok(speaker) => validation.check(
+                    speakers.contains(&speaker),
+                    field(&path, "speaker"),
+                    FieldProblem::SpeakerNotInSpeech,
+                ),
+                Err(e) => validation.reject(
+                    field(&path, "speaker"),
+                    FieldProblem::InvalidSpeakerUid,
+                    e.to_string(),
+                ),
+            }
+        }
+    }
 }
 
 impl TryFrom<CreateSpeechInput> for Speech {
@@ -79,41 +280,229 @@ impl TryFrom<CreateSpeechInput> for Speech {
         for s in value.sentences {
             sentences.push(s.try_into()?);
         }
-        let date = DateTime::from_str(&value.date).map_err(|_| {
-            HttpError::new(
-                400,
-                "InvalidDate",
-                "The date provided is invalid. Please be sure to provide an ISO 8601 date.",
-            )
-        })?;
+        let date =
+            DateTime::from_str(&value.date).map_err(|_| HttpError::new(ErrorCode::InvalidDate))?;
         let mut speakers = Vec::new();
         for speaker in value.speakers {
-            speakers.push(Uuid::from_str(&speaker).map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidSpeakersUid",
-                    "One of the speaker uid provided have an invalid format",
-                )
-            })?);
+            speakers.push(
+                Uuid::from_str(&speaker)
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidSpeakersUid))?,
+            );
         }
-        return Ok(Self::new(
+        let language = match value.language {
+            Some(code) => Some(
+                SpeechLanguage::provided(&code)
+                    .ok_or(HttpError::new(ErrorCode::InvalidLanguage))?,
+            ),
+            None => None,
+        };
+        let mut speech = Self::new(
             &Uuid::new_v4(),
             &value.name,
             date,
             &speakers,
             &sentences,
             &value.media,
-            SpeechStatus::Pending,
-        ));
+            match value.live {
+                true => SpeechStatus::Live,
+                false => SpeechStatus::Pending,
+            },
+        );
+        for (speaker, role) in value.roles {
+            let speaker = Uuid::from_str(&speaker)
+                .ok()
+                .filter(|speaker| speakers.contains(speaker))
+                .ok_or(HttpError::new(ErrorCode::InvalidSpeakersUid))?;
+            speech.update_speaker_role(&speaker, parse_speaker_role(&role)?);
+        }
+        speech.update_language(language);
+        return Ok(speech);
+    }
+}
+
+#[derive(Deserialize)]
+struct UpdateSpeakerInput {
+    role: String,
+}
+
+#[derive(Deserialize, Default)]
+struct AddSpeakerInput {
+    role: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReassignSentencesInput {
+    to: Uuid,
+}
+
+/// Maximum number of speeches checked by a single duplicates request.
+const MAX_DUPLICATE_CHECKS: usize = 1000;
+
+#[derive(Deserialize)]
+struct CheckDuplicatesInput {
+    speeches: Vec<SpeechIdentityInput>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct SpeechIdentityInput {
+    name: String,
+    date: String,
+    media: String,
+}
+
+impl TryFrom<&SpeechIdentityInput> for SpeechIdentity {
+    type Error = HttpError<'static>;
+
+    fn try_from(value: &SpeechIdentityInput) -> Result<Self, Self::Error> {
+        let date =
+            DateTime::from_str(&value.date).map_err(|_| HttpError::new(ErrorCode::InvalidDate))?;
+        Ok(Self {
+            name: value.name.clone(),
+            date,
+            media: value.media.clone(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct GetSpeechDuplicate {
+    #[serde(flatten)]
+    speech: SpeechIdentityInput,
+    exists: bool,
+    /// Set when the existing speech is visible to the user.
+    uid: Option<String>,
+}
+
+/// Maximum number of speeches sent by a single import.
+const MAX_IMPORT_SPEECHES: usize = 1000;
+
+#[derive(Deserialize)]
+struct ImportSpeechesInput {
+    speeches: Vec<CreateSpeechInput>,
+}
+
+#[derive(Deserialize)]
+struct ResolveImportConflictInput {
+    /// use_existing, create_new or skip.
+    resolution: String,
+    /// Stored speech kept, or existing person replacing the unknown speaker.
+    uid: Option<String>,
+    /// Name of a duplicate created anyway.
+    name: Option<String>,
+    /// Person created in place of the unknown speaker.
+    person: Option<CreatePersonInput>,
+}
+
+#[derive(Serialize)]
+struct GetImportResolution {
+    action: String,
+    uid: Option<String>,
+}
+
+impl From<&ImportResolution> for GetImportResolution {
+    fn from(value: &ImportResolution) -> Self {
+        Self {
+            action: value.to_string(),
+            uid: value.uid().map(|uid| uid.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetPiiFlag {
+    uid: String,
+    sentence_index: u32,
+    /// phone_number, email_address, postal_address or minor_name.
+    kind: String,
+    excerpt: String,
+    created_at: String,
+    reviewed_by: Option<String>,
+    reviewed_at: Option<String>,
+}
+
+impl From<PiiFlag> for GetPiiFlag {
+    fn from(value: PiiFlag) -> Self {
+        Self {
+            uid: value.uid.to_string(),
+            sentence_index: value.sentence_index,
+            kind: value.kind.to_string(),
+            excerpt: value.excerpt,
+            created_at: value.created_at.to_rfc3339(),
+            reviewed_by: value.reviewed_by,
+            reviewed_at: value.reviewed_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetImportConflict {
+    uid: String,
+    position: u32,
+    /// duplicate_speech or unknown_speaker.
+    kind: String,
+    /// Stored speech for a duplicate, when visible, or the unknown speaker.
+    reference: Option<String>,
+    speech: GetSpeechById,
+    proposed_resolution: GetImportResolution,
+    resolution: Option<GetImportResolution>,
+}
+
+impl From<ImportConflict> for GetImportConflict {
+    fn from(value: ImportConflict) -> Self {
+        Self {
+            uid: value.uid.to_string(),
+            position: value.position,
+            kind: value.kind.to_string(),
+            reference: value.reference.map(|uid| uid.to_string()),
+            proposed_resolution: GetImportResolution::from(&value.proposed_resolution()),
+            resolution: value.resolution.as_ref().map(GetImportResolution::from),
+            speech: value.speech.into(),
+        }
     }
 }
 
 #[derive(Serialize)]
-struct GetSpeechSentence {
+struct GetImportReport {
+    import: String,
+    created: Vec<String>,
+    existing: Vec<String>,
+    skipped: u32,
+    conflicts: Vec<GetImportConflict>,
+}
+
+impl From<ImportReport> for GetImportReport {
+    fn from(value: ImportReport) -> Self {
+        Self {
+            import: value.import_uid.to_string(),
+            created: value.created.iter().map(|uid| uid.to_string()).collect(),
+            existing: value.existing.iter().map(|uid| uid.to_string()).collect(),
+            skipped: value.skipped,
+            conflicts: value
+                .conflicts
+                .into_iter()
+                .map(GetImportConflict::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GetSpeechSentence {
     uid: String,
     speaker: String,
     text: String,
     interrupted: bool,
+    start: Option<u32>,
+    end: Option<u32>,
+    /// unreviewed, approved or flagged.
+    review_status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    review_comment: Option<String>,
+    /// BCP 47 tag of the language of the sentence, when it is not the one of the speech.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
 }
 
 impl From<Sentence> for GetSpeechSentence {
@@ -123,18 +512,187 @@ impl From<Sentence> for GetSpeechSentence {
             speaker: value.speaker().to_string(),
             text: value.text().clone(),
             interrupted: value.interrupted(),
+            start: value.timing().map(|t| t.start),
+            end: value.timing().map(|t| t.end),
+            review_status: value.review().status.to_string(),
+            review_comment: value.review().comment.clone(),
+            language: value.language().map(String::as_str).map(language_tag),
         };
     }
 }
 
+#[derive(Deserialize)]
+struct ReviewSentenceInput {
+    /// unreviewed, approved or flagged.
+    status: String,
+    comment: Option<String>,
+}
+
+/// Longest comment of a reviewer on a sentence, in characters.
+const MAX_REVIEW_COMMENT_LENGTH: usize = 2000;
+
+#[derive(Serialize)]
+struct GetSentenceReview {
+    #[serde(flatten)]
+    sentence: GetSpeechSentence,
+    reviewed_by: Option<String>,
+    reviewed_at: Option<String>,
+}
+
+impl From<Sentence> for GetSentenceReview {
+    fn from(value: Sentence) -> Self {
+        Self {
+            reviewed_by: value.review().reviewed_by.clone(),
+            reviewed_at: value.review().reviewed_at.map(|at| at.to_rfc3339()),
+            sentence: value.into(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetQuoteSentence {
+    uid: String,
+    speaker: String,
+    speaker_name: String,
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetQuoteContext {
+    before: Vec<GetQuoteSentence>,
+    after: Vec<GetQuoteSentence>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetQuote {
+    uid: String,
+    /// Position of the sentence in the speech, starting at 0.
+    index: usize,
+    speaker: String,
+    speaker_name: String,
+    text: String,
+    start: Option<u32>,
+    end: Option<u32>,
+    speech: String,
+    speech_name: String,
+    media: String,
+    date: String,
+    /// Citation of the quote, ready to be shared.
+    citation: String,
+    context: GetQuoteContext,
+}
+
+impl GetQuote {
+    fn new(speech: &Speech, quote: Quote, speaker_names: &HashMap<Uuid, String>) -> Self {
+        let speaker_name = |sentence: &Sentence| {
+            speaker_names
+                .get(sentence.speaker())
+                .cloned()
+                .unwrap_or_else(|| sentence.speaker().to_string())
+        };
+        let context_sentence = |sentence: &Sentence| GetQuoteSentence {
+            uid: sentence.uid().to_string(),
+            speaker: sentence.speaker().to_string(),
+            speaker_name: speaker_name(sentence),
+            text: sentence.text().clone(),
+        };
+        let sentence = quote.sentence();
+        Self {
+            uid: sentence.uid().to_string(),
+            index: quote.index(),
+            speaker: sentence.speaker().to_string(),
+            speaker_name: speaker_name(sentence),
+            text: sentence.text().clone(),
+            start: sentence.timing().map(|t| t.start),
+            end: sentence.timing().map(|t| t.end),
+            speech: speech.uid().to_string(),
+            speech_name: speech.name().clone(),
+            media: speech.media().clone(),
+            date: speech.date().to_rfc3339(),
+            citation: citation(
+                &speaker_name(sentence),
+                sentence.text(),
+                speech.name(),
+                speech.media(),
+                speech.date(),
+            ),
+            context: GetQuoteContext {
+                before: quote.before().iter().map(context_sentence).collect(),
+                after: quote.after().iter().map(context_sentence).collect(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct GetSpeechLanguage {
+    /// ISO 639-3 code.
+    code: String,
+    /// BCP 47 tag.
+    tag: String,
+    confidence: Option<f64>,
+    mixed: bool,
+}
+
+impl From<&SpeechLanguage> for GetSpeechLanguage {
+    fn from(value: &SpeechLanguage) -> Self {
+        Self {
+            code: value.code().clone(),
+            tag: value.tag(),
+            confidence: value.confidence(),
+            mixed: value.mixed(),
+        }
+    }
+}
+
+/// Fields of `GetSpeechById`, selectable with the `fields` query parameter.
+const SPEECH_FIELDS: &[&str] = &[
+    "uid",
+    "name",
+    "date",
+    "media",
+    "status",
+    "speakers",
+    "roles",
+    "sentences",
+    "language",
+    "translation",
+    "read_progress",
+    "annotations",
+    "segments",
+    "total_sentences",
+    "version",
+];
+
 #[derive(Serialize)]
 struct GetSpeechById {
     uid: String,
     name: String,
     date: String,
     media: String,
+    /// LIVE, PENDING or VALIDATED.
+    status: String,
     speakers: Vec<String>,
+    roles: HashMap<String, String>,
     sentences: Vec<GetSpeechSentence>,
+    language: Option<GetSpeechLanguage>,
+    /// Language the sentences are translated to, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translation: Option<String>,
+    /// Where the authenticated user stopped reading, if they started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    read_progress: Option<GetReadProgress>,
+    /// Language and sentiment of the sentences annotated so far.
+    annotations: Vec<GetSentenceAnnotation>,
+    /// Named ranges of sentences, in the order of the sentences.
+    segments: Vec<GetSegment>,
+    /// Number of sentences of the speech, `sentences` only holding those of the page.
+    total_sentences: u64,
+    /// Version to send back in the `If-Match` header of an update.
+    version: u32,
 }
 
 impl From<Speech> for GetSpeechById {
@@ -144,33 +702,195 @@ impl From<Speech> for GetSpeechById {
             name: value.name().clone(),
             date: value.date().to_rfc3339(),
             media: value.media().clone(),
+            status: value.speech_status().to_string(),
             speakers: value.speakers().iter().map(|v| v.to_string()).collect(),
+            roles: speaker_roles(&value),
             sentences: value
                 .sentences()
                 .iter()
                 .map(|e| GetSpeechSentence::from(e.clone()))
                 .collect(),
+            language: value.language().map(GetSpeechLanguage::from),
+            translation: None,
+            read_progress: None,
+            annotations: Vec::new(),
+            segments: Vec::new(),
+            total_sentences: value.sentences().len() as u64,
+            version: value.version(),
         }
     }
 }
 
 #[derive(Serialize)]
-struct GetSpeech {
+struct GetSpeechBySlug {
+    /// Current slug of the speech, to redirect clients using a former one.
+    slug: String,
+    #[serde(flatten)]
+    speech: GetSpeechById,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSpeakerAnalytics {
+    speaker: String,
+    speaker_name: String,
+    role: String,
+    sentences: u64,
+    words: u64,
+    share: f64,
+    interruptions_made: u64,
+    interruptions_received: u64,
+    /// Milliseconds spent speaking, only known when the sentences are timed.
+    spoken_duration: Option<u64>,
+    duration_share: Option<f64>,
+}
+
+/// Fields of `GetSpeech`, selectable with the `fields` query parameter of the list.
+const SPEECH_LIST_FIELDS: &[&str] = &[
+    "uid",
+    "name",
+    "date",
+    "speakers",
+    "roles",
+    "media",
+    "status",
+    "language",
+    "sentence_count",
+    "preview",
+    "read_progress",
+];
+
+#[derive(Serialize)]
+pub struct GetSpeech {
     uid: String,
     name: String,
     date: String,
     speakers: Vec<String>,
+    roles: HashMap<String, String>,
     media: String,
+    status: String,
+    language: Option<GetSpeechLanguage>,
+    sentence_count: u32,
+    /// Start of the first sentence, null for a speech without sentences.
+    preview: Option<String>,
+    /// Where the authenticated user stopped reading, if they started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    read_progress: Option<GetReadProgress>,
 }
 
-impl From<Speech> for GetSpeech {
-    fn from(value: Speech) -> Self {
+#[derive(Serialize)]
+struct GetSpeechGroupCount {
+    /// The media, the uid of the speaker or the month as `2024-03`.
+    key: String,
+    count: u64,
+}
+
+impl From<&SpeechGroupCount> for GetSpeechGroupCount {
+    fn from(value: &SpeechGroupCount) -> Self {
+        Self {
+            key: value.key().clone(),
+            count: value.speeches(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSpeechAggregation {
+    group_by: String,
+    groups: Vec<GetSpeechGroupCount>,
+}
+
+#[derive(Serialize)]
+struct GetReadProgress {
+    sentence_index: u32,
+    updated_at: String,
+}
+
+impl From<&ReadProgress> for GetReadProgress {
+    fn from(value: &ReadProgress) -> Self {
+        Self {
+            sentence_index: value.sentence_index(),
+            updated_at: value.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+impl From<SpeechSummary> for GetSpeech {
+    fn from(value: SpeechSummary) -> Self {
+        let SpeechSummary {
+            speech: value,
+            sentence_count,
+            preview,
+            ..
+        } = value;
         Self {
             uid: value.uid().to_string(),
             name: value.name().clone(),
             date: value.date().to_rfc3339(),
             media: value.media().clone(),
+            status: value.speech_status().to_string(),
             speakers: value.speakers().iter().map(|v| v.to_string()).collect(),
+            roles: speaker_roles(&value),
+            language: value.language().map(GetSpeechLanguage::from),
+            sentence_count,
+            preview,
+            read_progress: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GetSpeechEvent {
+    speech: String,
+    status: String,
+    speakers: Vec<String>,
+    at: String,
+}
+
+impl From<SpeechEvent> for GetSpeechEvent {
+    fn from(value: SpeechEvent) -> Self {
+        Self {
+            speech: value.speech().to_string(),
+            status: value.status().to_string(),
+            speakers: value.speakers().iter().map(|v| v.to_string()).collect(),
+            at: value.at().to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSpeechRevisionSummary {
+    revision: u32,
+    created_at: String,
+    name: String,
+}
+
+impl From<SpeechRevision> for GetSpeechRevisionSummary {
+    fn from(value: SpeechRevision) -> Self {
+        Self {
+            revision: value.revision(),
+            created_at: value.created_at().to_rfc3339(),
+            name: value.speech().name().clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetSpeechRevision {
+    revision: u32,
+    created_at: String,
+    speech: GetSpeechById,
+}
+
+impl From<SpeechRevision> for GetSpeechRevision {
+    fn from(value: SpeechRevision) -> Self {
+        Self {
+            revision: value.revision(),
+            created_at: value.created_at().to_rfc3339(),
+            speech: value.into_speech().into(),
         }
     }
 }
@@ -179,29 +899,44 @@ pub async fn router(
     path: &str,
     query_params: &HashMap<String, String>,
     method: &Method,
+    headers: &HeaderMap,
     token: &AuthToken,
     body: Value,
-    speech_manager: &SpeechManager,
-) -> Result<Value, HttpError<'static>> {
-    match (method, path) {
-        (&Method::POST, "") => {
+    managers: &Managers,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let speech_manager = &managers.speech_manager;
+    let person_manager = &managers.person_manager;
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    if token.is_public_reader() && !is_public_route(method, &splitted_path) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    match (method, splitted_path.as_slice()) {
+        (&Method::POST, [""]) => {
             if !token.permissions().contains(&Permissions::CreateSpeech) {
                 return Err(ACCESS_DENIED_ERROR);
             }
-            let create_speech_input: CreateSpeechInput =
-                serde_json::from_value(body).map_err(|_| {
-                    HttpError::new(
-                        400,
-                        "InvalidFormat",
-                        "The body format is invalid. Please refer to the documentation",
+            let create_speech_input: CreateSpeechInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let mut validation = Validation::default();
+            create_speech_input.validate(&mut validation, "");
+            validation.into_result()?;
+            let on_conflict = extract_on_conflict(query_params)?;
+            let speech = create_speech_input.try_into()?;
+            match on_conflict {
+                Some(on_conflict) => {
+                    let outcome = speech_manager.upsert_speech(speech, on_conflict).await?;
+                    Ok(
+                        json!({ "uid": outcome.uid().to_string(), "outcome": outcome.to_string() })
+                            .into(),
                     )
-                })?;
-            speech_manager
-                .create_speech(create_speech_input.try_into()?)
-                .await?;
-            Ok(Value::Null)
+                }
+                None => {
+                    speech_manager.create_speech(speech).await?;
+                    Ok(Value::Null.into())
+                }
+            }
         }
-        (&Method::GET, "") => {
+        (&Method::GET, [""]) => {
             if !token.permissions().contains(&Permissions::GetSpeech) {
                 return Err(ACCESS_DENIED_ERROR);
             }
@@ -214,117 +949,1107 @@ pub async fn router(
                 Some(v) => v,
                 None => &"10".to_owned(),
             };
-            let speakers_raw = extract_array_in_query("speakers", query_params)?;
-            let page = page_raw.parse::<u16>().map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidPageParam",
-                    "The page parameter provided must be an integer > 0",
-                )
-            })?;
-            let quantity = quantity_raw.parse::<u16>().map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidQuantityParam",
-                    "The quantity parameter provided must be an integer > 0",
+            let filter = extract_speech_filter(query_params, token)?;
+            let page = page_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidPageParam))?;
+            let quantity = quantity_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?;
+            let fields = FieldSelection::from_query(query_params, SPEECH_LIST_FIELDS)?;
+            let projection = SpeechProjection {
+                speakers: fields.includes("speakers") || fields.includes("roles"),
+                sentence_summary: fields.includes("sentence_count") || fields.includes("preview"),
+            };
+            let speeches = speech_manager
+                .get_speech(page, quantity, &filter, projection)
+                .await?;
+            let progress = if fields.includes("read_progress") {
+                let uids = speeches
+                    .iter()
+                    .map(|s| *s.speech.uid())
+                    .collect::<Vec<Uuid>>();
+                get_read_progress(token, speech_manager, &uids).await?
+            } else {
+                HashMap::new()
+            };
+            let speech: Vec<GetSpeech> = speeches
+                .into_iter()
+                .map(|s| {
+                    let read_progress = progress.get(s.speech.uid()).map(GetReadProgress::from);
+                    GetSpeech {
+                        read_progress,
+                        ..s.into()
+                    }
+                })
+                .collect();
+
+            Ok(fields
+                .project(value::to_value(speech).map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speeches to value: {}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?)
+                .into())
+        }
+        (&Method::POST, ["check-duplicates"]) => {
+            if !token.permissions().contains(&Permissions::CreateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let input: CheckDuplicatesInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            if input.speeches.len() > MAX_DUPLICATE_CHECKS {
+                return Err(HttpError::with_context(
+                    ErrorCode::InvalidFormat,
+                    format!(
+                        "At most {} speeches can be checked at once",
+                        MAX_DUPLICATE_CHECKS
+                    ),
+                ));
+            }
+            let identities = input
+                .speeches
+                .iter()
+                .map(SpeechIdentity::try_from)
+                .collect::<Result<Vec<SpeechIdentity>, _>>()?;
+            let duplicates = speech_manager
+                .find_duplicate_speeches(&identities)
+                .await?
+                .into_iter()
+                .zip(input.speeches)
+                .map(|(duplicate, speech)| GetSpeechDuplicate {
+                    speech,
+                    exists: duplicate.exists,
+                    uid: duplicate.uid.map(|uid| uid.to_string()),
+                })
+                .collect::<Vec<GetSpeechDuplicate>>();
+            Ok(json!({ "speeches": duplicates }).into())
+        }
+        (&Method::POST, ["imports"]) => {
+            if !token.permissions().contains(&Permissions::CreateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let speeches = import_speeches_from_body(body)?;
+            let run_async = match query_params.get("async") {
+                Some(v) => v
+                    .parse::<bool>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidAsyncParam))?,
+                None => false,
+            };
+            if run_async {
+                return start_import_job(speeches, managers).await;
+            }
+            let report = speech_manager
+                .import_speeches(speeches, person_manager, None)
+                .await?;
+            import_report_to_value(report)
+        }
+        (&Method::GET, ["imports", import, "conflicts"]) => {
+            if !token.permissions().contains(&Permissions::CreateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let import =
+                Uuid::from_str(import).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let conflicts = speech_manager
+                .get_import_conflicts(import)
+                .await?
+                .into_iter()
+                .map(GetImportConflict::from)
+                .collect::<Vec<GetImportConflict>>();
+            Ok(json!({ "conflicts": conflicts }).into())
+        }
+        (&Method::PUT, ["imports", import, "conflicts", uid]) => {
+            if !token.permissions().contains(&Permissions::CreateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let import =
+                Uuid::from_str(import).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let input: ResolveImportConflictInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let resolution = match input.resolution.as_str() {
+                "use_existing" => {
+                    let existing = input
+                        .uid
+                        .as_deref()
+                        .and_then(|uid| Uuid::from_str(uid).ok())
+                        .ok_or(HttpError::new(ErrorCode::InvalidUid))?;
+                    ImportResolution::UseExisting(existing)
+                }
+                "create_new" => ImportResolution::CreateNew,
+                "skip" => ImportResolution::Skip,
+                _ => return Err(HttpError::new(ErrorCode::InvalidImportResolution)),
+            };
+            let mut validation = Validation::default();
+            if let Some(name) = &input.name {
+                validation.check(!name.trim().is_empty(), "name", FieldProblem::EmptyName);
+            }
+            if let Some(person) = &input.person {
+                person.validate(&mut validation, "person");
+            }
+            validation.into_result()?;
+            let new_person = match input.person {
+                Some(person) => Some(person.try_into()?),
+                None => None,
+            };
+            let conflict = speech_manager
+                .resolve_import_conflict(
+                    import,
+                    uid,
+                    resolution,
+                    input.name,
+                    new_person,
+                    person_manager,
                 )
-            })?;
-
-            let mut speakers_uid = Vec::new();
-            for speaker_uid in speakers_raw {
-                speakers_uid.push(Uuid::from_str(&speaker_uid).map_err(|_| {
-                    HttpError::new(
-                        400,
-                        "InvalidUid",
-                        "The uid provided seems invalid, please check it again",
-                    )
-                })?);
+                .await?;
+            Ok(value::to_value(GetImportConflict::from(conflict))
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting import conflict to value: {}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::POST, ["imports", import, "apply"]) => {
+            if !token.permissions().contains(&Permissions::CreateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let import =
+                Uuid::from_str(import).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let report = speech_manager.apply_import(import, person_manager).await?;
+            import_report_to_value(report)
+        }
+        (&Method::GET, ["events"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let speakers = extract_uid_array_in_query("speakers", query_params)?;
+            let status = match query_params.get("status") {
+                Some(status) => Some(
+                    SpeechStatus::try_from(status.as_str())
+                        .map_err(|_| HttpError::new(ErrorCode::InvalidStatusParam))?,
+                ),
+                None => None,
+            };
+            let receiver = speech_manager.subscribe_events().ok_or(NOT_FOUND_ERROR)?;
+            let organization = speech_manager.organization().copied();
+            Ok(RouteResponse::Raw(event_stream(
+                Vec::new(),
+                receiver,
+                move |e: SpeechEvent| {
+                    let visible = e.organization() == organization.as_ref()
+                        && (speakers.is_empty()
+                            || e.speakers().iter().any(|s| speakers.contains(s)))
+                        && status.as_ref().is_none_or(|status| e.status() == status);
+                    visible.then(|| event(None::<u32>, e.kind(), &GetSpeechEvent::from(e)))
+                },
+            )))
+        }
+        (&Method::GET, ["count"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let filter = extract_speech_filter(query_params, token)?;
+            let count = speech_manager.count_speech(&filter).await?;
+            Ok(json!({ "count": count }).into())
+        }
+        (&Method::GET, ["aggregate"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let grouping = query_params
+                .get("group_by")
+                .and_then(|v| SpeechGrouping::try_from(v.as_str()).ok())
+                .ok_or(HttpError::new(ErrorCode::InvalidGroupByParam))?;
+            let filter = extract_speech_filter(query_params, token)?;
+            let groups = speech_manager
+                .count_speech_by_group(&filter, grouping)
+                .await?;
+            let output = GetSpeechAggregation {
+                group_by: grouping.to_string(),
+                groups: groups.iter().map(GetSpeechGroupCount::from).collect(),
+            };
+            Ok(value::to_value(output)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speech aggregation to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::GET, ["clusters"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let page_raw = match query_params.get("page") {
+                Some(v) => v,
+                None => &"0".to_owned(),
+            };
+            let quantity_raw = match query_params.get("quantity") {
+                Some(v) => v,
+                None => &"10".to_owned(),
+            };
+            let page = page_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidPageParam))?;
+            let quantity = quantity_raw
+                .parse::<u16>()
+                .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?;
+            let clusters = speech_manager.get_speech_clusters(page, quantity).await?;
+            Ok(json!({
+                "clusters": clusters
+                    .into_iter()
+                    .map(|cluster| json!({ "uid": cluster.uid, "speeches": cluster.speeches }))
+                    .collect::<Vec<Value>>(),
+            })
+            .into())
+        }
+        (&Method::GET, ["compare"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let compared = |param: &str| {
+                query_params
+                    .get(param)
+                    .and_then(|uid| Uuid::from_str(uid).ok())
+                    .ok_or(HttpError::new(ErrorCode::InvalidCompareParam))
+            };
+            let (a, b, comparison) = speech_manager
+                .compare_speeches(compared("a")?, compared("b")?)
+                .await?;
+            let mut speaker_names = resolve_speaker_names(&a, person_manager).await?;
+            speaker_names.extend(resolve_speaker_names(&b, person_manager).await?);
+            Ok(
+                value::to_value(GetSpeechComparison::new(a, b, comparison, &speaker_names))
+                    .map_err(|e| {
+                        println!(
+                            "An internal error occured while converting speech comparison: {:?}",
+                            e
+                        );
+                        INTERNAL_ERROR
+                    })?
+                    .into(),
+            )
+        }
+        (&Method::GET, ["slug", slug]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let (page, quantity) = extract_sentence_page(query_params)?;
+            let (speech, total_sentences, current_slug) = speech_manager
+                .get_speech_by_slug(slug, page, quantity)
+                .await?;
+            let progress = get_read_progress(token, speech_manager, &[*speech.uid()]).await?;
+            let read_progress = progress.get(speech.uid()).map(GetReadProgress::from);
+            let annotations = get_sentence_annotations(&speech, managers).await?;
+            let segments = get_segments(speech.uid(), managers).await?;
+            let speech_found = GetSpeechBySlug {
+                slug: current_slug,
+                speech: GetSpeechById {
+                    read_progress,
+                    annotations,
+                    segments,
+                    total_sentences,
+                    ..speech.into()
+                },
+            };
+            Ok(value::to_value(speech_found)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speech by slug: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::GET, [uid, "analytics"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
             }
-            let speech: Vec<GetSpeech> = speech_manager
-                .get_speech(page, quantity, &speakers_uid)
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let speech = speech_manager.get_speech_by_id(uid).await?;
+            let speaker_names = resolve_speaker_names(&speech, person_manager).await?;
+            let analytics: Vec<GetSpeakerAnalytics> = speech_manager
+                .get_speech_analytics(uid, extract_include_moderators(query_params)?)
                 .await?
                 .into_iter()
-                .map(|s| s.into())
+                .map(|a| GetSpeakerAnalytics {
+                    speaker: a.speaker().to_string(),
+                    speaker_name: speaker_names
+                        .get(a.speaker())
+                        .cloned()
+                        .unwrap_or_else(|| a.speaker().to_string()),
+                    role: speech.speaker_role(a.speaker()).to_string(),
+                    sentences: a.sentences(),
+                    words: a.words(),
+                    share: a.share(),
+                    interruptions_made: a.interruptions_made(),
+                    interruptions_received: a.interruptions_received(),
+                    spoken_duration: a.spoken_duration(),
+                    duration_share: a.duration_share(),
+                })
                 .collect();
-
-            Ok(value::to_value(speech).map_err(|e| {
-                println!(
-                    "An internal error occured while converting speeches to value: {}",
-                    e
-                );
-                INTERNAL_ERROR
-            })?)
+            Ok(value::to_value(analytics)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speech analytics: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::GET, [uid, "keywords"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let quantity = match query_params.get("quantity") {
+                Some(v) => v
+                    .parse::<u16>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?,
+                None => DEFAULT_KEYWORD_QUANTITY,
+            };
+            let language = extract_language(query_params)?;
+            let keywords: Vec<Value> = speech_manager
+                .get_speech_keywords(uid, quantity as usize, language.as_deref())
+                .await?
+                .iter()
+                .map(|keyword| json!({ "term": keyword.term(), "count": keyword.count() }))
+                .collect();
+            Ok(json!({ "keywords": keywords }).into())
+        }
+        (&Method::GET, [uid, "sentiment"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let filter = extract_sentiment_filter(query_params)?;
+            let speech = speech_manager.get_speech_by_id(uid).await?;
+            let speaker_names = resolve_speaker_names(&speech, person_manager).await?;
+            let sentiment = managers
+                .annotation_manager
+                .get_sentiment(&speech, &filter)
+                .await?;
+            Ok(
+                value::to_value(GetSpeechSentiment::new(&sentiment, &speech, &speaker_names))
+                    .map_err(|e| {
+                        println!(
+                            "An internal error occured while converting speech sentiment: {:?}",
+                            e
+                        );
+                        INTERNAL_ERROR
+                    })?
+                    .into(),
+            )
+        }
+        (&Method::GET, [uid, "sentences", sentence, "quote"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let sentence =
+                Uuid::from_str(sentence).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let context = match query_params.get("context") {
+                Some(v) => match v.parse::<usize>() {
+                    Ok(context) if context <= MAX_QUOTE_CONTEXT => context,
+                    _ => return Err(HttpError::new(ErrorCode::InvalidContextParam)),
+                },
+                None => 0,
+            };
+            let (speech, quote) = speech_manager.get_quote(uid, sentence, context).await?;
+            let speaker_names = resolve_speaker_names(&speech, person_manager).await?;
+            Ok(
+                value::to_value(GetQuote::new(&speech, quote, &speaker_names))
+                    .map_err(|e| {
+                        println!("An internal error occured while converting quote: {:?}", e);
+                        INTERNAL_ERROR
+                    })?
+                    .into(),
+            )
+        }
+        (&Method::GET, [uid, "cluster"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            // A speech similar to no other is alone in its cluster.
+            Ok(match speech_manager.get_speech_cluster(uid).await? {
+                Some(cluster) => json!({ "cluster": cluster.uid, "speeches": cluster.speeches }),
+                None => json!({ "cluster": Value::Null, "speeches": [uid] }),
+            }
+            .into())
         }
-        (&Method::GET, _) => {
+        (&Method::GET, [uid, "export"]) => {
             if !token.permissions().contains(&Permissions::GetSpeech) {
                 return Err(ACCESS_DENIED_ERROR);
             }
-            let uid = Uuid::from_str(path).map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidUid",
-                    "The uid provided seems invalid, please check it again",
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let format = match query_params.get("format") {
+                Some(v) => ExportFormat::try_from(v.as_str())?,
+                None => ExportFormat::Txt,
+            };
+            let speech = speech_manager.get_speech_by_id(uid).await?;
+            let speaker_names = resolve_speaker_names(&speech, person_manager).await?;
+            Ok(RouteResponse::Raw(export_speech(
+                speech,
+                speaker_names,
+                format,
+            )))
+        }
+        (&Method::HEAD, [uid]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            if !speech_manager.speech_exists(uid).await? {
+                return Err(SpeechRepositoryError::SpeechNotFound.into());
+            }
+            Ok(RouteResponse::Raw(
+                Response::builder()
+                    .status(200)
+                    .body(full(""))
+                    .expect("Should not fail"),
+            ))
+        }
+        (&Method::GET, [uid]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let (page, quantity) = extract_sentence_page(query_params)?;
+            let fields = FieldSelection::from_query(query_params, SPEECH_FIELDS)?;
+            let review_status = extract_review_status(query_params)?;
+            let (speech, total_sentences, translation) = match query_params.get("lang") {
+                Some(lang) => {
+                    let lang = parse_translation_language(lang)?;
+                    let (speech, total_sentences) = speech_manager
+                        .get_translated_speech(uid, &lang, page, quantity, review_status)
+                        .await?;
+                    (speech, total_sentences, Some(lang))
+                }
+                None => {
+                    let (speech, total_sentences) = speech_manager
+                        .get_speech_page(uid, page, quantity, review_status)
+                        .await?;
+                    (speech, total_sentences, None)
+                }
+            };
+            // The parts of the speech stored apart are only read when they are sent.
+            let read_progress = if fields.includes("read_progress") {
+                get_read_progress(token, speech_manager, &[uid])
+                    .await?
+                    .get(&uid)
+                    .map(GetReadProgress::from)
+            } else {
+                None
+            };
+            let annotations = if fields.includes("annotations") {
+                get_sentence_annotations(&speech, managers).await?
+            } else {
+                Vec::new()
+            };
+            let segments = if fields.includes("segments") {
+                get_segments(&uid, managers).await?
+            } else {
+                Vec::new()
+            };
+            let speech_found = GetSpeechById {
+                translation,
+                total_sentences,
+                read_progress,
+                annotations,
+                segments,
+                ..speech.into()
+            };
+            Ok(fields
+                .project(value::to_value(speech_found).map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speech by id: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?)
+                .into())
+        }
+        (&Method::PUT, [uid]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let expected_version = ExpectedVersion::from_request(headers, &body)?;
+            let update_speech_input: CreateSpeechInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let mut validation = Validation::default();
+            update_speech_input.validate(&mut validation, "");
+            validation.into_result()?;
+            let input: Speech = update_speech_input.try_into()?;
+            // The processing status is not editable by the client.
+            let current = speech_manager.get_speech_by_id(uid).await?;
+            let mut speech = Speech::new(
+                &uid,
+                input.name(),
+                *input.date(),
+                input.speakers(),
+                input.sentences(),
+                input.media(),
+                current.speech_status().clone(),
+            );
+            // Speakers kept without a role in the body keep their current role.
+            for speaker in input.speakers() {
+                let role = match input.speaker_roles().get(speaker) {
+                    Some(role) => *role,
+                    None => current.speaker_role(speaker),
+                };
+                speech.update_speaker_role(speaker, role);
+            }
+            speech_manager
+                .update_speech(speech, Some(expected_version.version()))
+                .await
+                .map_err(|e| expected_version.map_error(e.into()))?;
+            Ok(Value::Null.into())
+        }
+        (&Method::POST, [uid, "validate"]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            speech_manager.validate_speech(uid).await?;
+            Ok(Value::Null.into())
+        }
+        (&Method::GET, [uid, "pii-flags"]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let flags: Vec<GetPiiFlag> = speech_manager
+                .get_pii_flags(uid)
+                .await?
+                .into_iter()
+                .map(GetPiiFlag::from)
+                .collect();
+            Ok(value::to_value(flags)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting pii flags to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::POST, [uid, "pii-flags", flag, "review"]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let flag = Uuid::from_str(flag).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let flag = speech_manager
+                .review_pii_flag(uid, flag, &token.user_id())
+                .await?;
+            Ok(value::to_value(GetPiiFlag::from(flag))
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting pii flag to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::POST, [uid, "sentences", sentence, "review"]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let sentence =
+                Uuid::from_str(sentence).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let input: ReviewSentenceInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let status = ReviewStatus::try_from(input.status.as_str());
+            let comment = input
+                .comment
+                .map(|comment| comment.trim().to_owned())
+                .filter(|comment| !comment.is_empty());
+            let mut validation = Validation::default();
+            validation.check(status.is_ok(), "status", FieldProblem::InvalidReviewStatus);
+            validation.check(
+                comment
+                    .as_ref()
+                    .is_none_or(|c| c.chars().count() <= MAX_REVIEW_COMMENT_LENGTH),
+                "comment",
+                FieldProblem::ReviewCommentTooLong,
+            );
+            validation.into_result()?;
+            let sentence = speech_manager
+                .review_sentence(
+                    uid,
+                    sentence,
+                    status.unwrap_or_default(),
+                    comment,
+                    &token.user_id(),
                 )
-            })?;
-            let speech_found: GetSpeechById = speech_manager.get_speech_by_id(uid).await?.into();
-            Ok(value::to_value(speech_found).map_err(|e| {
-                println!(
-                    "An internal error occured while converting speech by id: {:?}",
-                    e
-                );
-                INTERNAL_ERROR
-            })?)
+                .await?;
+            Ok(value::to_value(GetSentenceReview::from(sentence))
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting sentence review to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::POST, [uid, "finalize"]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let (received, kept) = speech_manager.finalize_live_speech(uid).await?;
+            Ok(json!({ "sentencesReceived": received, "sentencesKept": kept }).into())
+        }
+        (&Method::GET, [uid, "revisions"]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let revisions: Vec<GetSpeechRevisionSummary> = speech_manager
+                .get_speech_revisions(uid)
+                .await?
+                .into_iter()
+                .map(|r| r.into())
+                .collect();
+            Ok(value::to_value(revisions)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speech revisions: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::GET, [uid, "revisions", revision]) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let revision = parse_revision(revision)?;
+            let revision: GetSpeechRevision = speech_manager
+                .get_speech_revision(uid, revision)
+                .await?
+                .into();
+            Ok(value::to_value(revision)
+                .map_err(|e| {
+                    println!(
+                        "An internal error occured while converting speech revision: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?
+                .into())
+        }
+        (&Method::POST, [uid, "revert", revision]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let revision = parse_revision(revision)?;
+            speech_manager.revert_speech(uid, revision).await?;
+            Ok(Value::Null.into())
         }
-        (&Method::DELETE, _) => {
+        (&Method::DELETE, [uid]) => {
             if !token.permissions().contains(&Permissions::DeleteSpeech) {
                 return Err(ACCESS_DENIED_ERROR);
             }
-            let uid = Uuid::from_str(path).map_err(|_| {
-                HttpError::new(
-                    400,
-                    "InvalidUid",
-                    "The uid provided seems invalid, please check it again",
-                )
-            })?;
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
             speech_manager.delete_speech(uid).await?;
-            Ok(Value::Null)
+            Ok(Value::Null.into())
+        }
+        (method, [uid, "labels", label_uid @ ..]) if label_uid.len() <= 1 => {
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            Ok(entity_labels_router(
+                LabelTarget::Speech(uid),
+                label_uid.first().copied(),
+                method,
+                token,
+                &managers.label_manager,
+            )
+            .await?
+            .into())
+        }
+        (method, [uid, "attachments", attachment_path @ ..]) => {
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            Ok(speech_attachments_router(
+                uid,
+                attachment_path,
+                method,
+                token,
+                &managers.attachment_manager,
+            )
+            .await?
+            .into())
+        }
+        (method, [uid, "segments", segment_path @ ..]) if segment_path.len() <= 1 => {
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            Ok(speech_segments_router(
+                uid,
+                segment_path,
+                method,
+                token,
+                body,
+                &managers.segment_manager,
+            )
+            .await?
+            .into())
+        }
+        (method, [uid, "tags", tag_uid @ ..]) if tag_uid.len() <= 1 => {
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            Ok(speech_tags_router(
+                uid,
+                tag_uid.first().copied(),
+                method,
+                token,
+                &managers.tag_manager,
+            )
+            .await?
+            .into())
+        }
+        (&Method::PATCH, [uid, "speakers", speaker]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let speaker =
+                Uuid::from_str(speaker).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let expected_version = ExpectedVersion::from_request(headers, &body)?;
+            let input: UpdateSpeakerInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            speech_manager
+                .update_speaker_role(
+                    uid,
+                    speaker,
+                    parse_speaker_role(&input.role)?,
+                    Some(expected_version.version()),
+                )
+                .await
+                .map_err(|e| expected_version.map_error(e.into()))?;
+            Ok(Value::Null.into())
+        }
+        (&Method::POST, [uid, "speakers", speaker]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let speaker =
+                Uuid::from_str(speaker).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            // The body is optional, the speaker joins as a panelist by default.
+            let input: AddSpeakerInput = match body {
+                Value::Null => AddSpeakerInput::default(),
+                body => serde_json::from_value(body)
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?,
+            };
+            let role = match input.role {
+                Some(role) => parse_speaker_role(&role)?,
+                None => SpeakerRole::Panelist,
+            };
+            speech_manager.add_speaker(uid, speaker, role).await?;
+            Ok(Value::Null.into())
+        }
+        (&Method::DELETE, [uid, "speakers", speaker]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let speaker =
+                Uuid::from_str(speaker).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            speech_manager.remove_speaker(uid, speaker).await?;
+            Ok(Value::Null.into())
+        }
+        (&Method::POST, [uid, "speakers", speaker, "reassign"]) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let speaker =
+                Uuid::from_str(speaker).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            let input: ReassignSentencesInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let reassigned = speech_manager
+                .reassign_sentences(uid, speaker, input.to)
+                .await?;
+            Ok(json!({ "reassigned": reassigned }).into())
+        }
+        (&Method::POST, [uid, "restore"]) => {
+            if !token.permissions().contains(&Permissions::DeleteSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let uid = Uuid::from_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?;
+            speech_manager.restore_speech(uid).await?;
+            Ok(Value::Null.into())
         }
         (_, _) => return Err(NOT_FOUND_ERROR),
     }
 }
 
-fn extract_array_in_query(
-    array_field: &str,
-    query_params: &HashMap<String, String>,
-) -> Result<Vec<String>, HttpError<'static>> {
-    let array_raw = match query_params.get(array_field) {
-        Some(v) => v,
-        None => {
-            return Ok(Vec::new());
+/// Validates a translation target: an ISO 639-1 code, optionally with a region ("pt-br").
+fn parse_translation_language(lang: &str) -> Result<String, HttpError<'static>> {
+    let lang = lang.to_lowercase();
+    let mut parts = lang.split('-');
+    let valid = parts
+        .next()
+        .is_some_and(|code| code.len() == 2 && code.chars().all(|c| c.is_ascii_lowercase()))
+        && parts.all(|region| region.len() == 2 && region.chars().all(|c| c.is_ascii_lowercase()))
+        && lang.len() <= 5;
+    if !valid {
+        return Err(HttpError::new(ErrorCode::InvalidLanguage));
+    }
+    Ok(lang)
+}
+
+fn parse_speaker_role(role: &str) -> Result<SpeakerRole, HttpError<'static>> {
+    SpeakerRole::try_from(role).map_err(|_| HttpError::new(ErrorCode::InvalidSpeakerRole))
+}
+
+/// Role of every speaker of the speech by speaker uid.
+fn speaker_roles(speech: &Speech) -> HashMap<String, String> {
+    speech
+        .speakers()
+        .iter()
+        .map(|speaker| {
+            (
+                speaker.to_string(),
+                speech.speaker_role(speaker).to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Routes an anonymous user may read in the public read mode, their queries only reaching
+/// the validated speeches.
+fn is_public_route(method: &Method, path: &[&str]) -> bool {
+    match (method, path) {
+        (&Method::GET, [""] | ["count"] | ["aggregate"] | ["compare"] | ["slug", _]) => true,
+        (
+            &Method::GET,
+            [uid]
+            | [uid, "analytics" | "export" | "keywords" | "sentiment"]
+            | [uid, "sentences", _, "quote"],
+        )
+        | (&Method::HEAD, [uid]) => Uuid::from_str(uid).is_ok(),
+        _ => false,
+    }
+}
+
+fn parse_revision(revision: &str) -> Result<u32, HttpError<'static>> {
+    revision
+        .parse::<u32>()
+        .map_err(|_| HttpError::new(ErrorCode::InvalidRevision))
+}
+
+/// Reads the speeches of a bulk import, sent as `{"speeches": [...]}` with each speech
+/// as it is created alone.
+pub fn import_speeches_from_body(body: Value) -> Result<Vec<Speech>, HttpError<'static>> {
+    let input: ImportSpeechesInput =
+        serde_json::from_value(body).map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+    if input.speeches.len() > MAX_IMPORT_SPEECHES {
+        return Err(HttpError::with_context(
+            ErrorCode::InvalidFormat,
+            format!(
+                "At most {} speeches can be imported at once",
+                MAX_IMPORT_SPEECHES
+            ),
+        ));
+    }
+    let mut validation = Validation::default();
+    for (index, speech) in input.speeches.iter().enumerate() {
+        speech.validate(&mut validation, &format!("speeches[{}]", index));
+    }
+    validation.into_result()?;
+    input
+        .speeches
+        .into_iter()
+        .map(Speech::try_from)
+        .collect::<Result<Vec<Speech>, _>>()
+}
+
+/// Creates the speeches of a bulk import in a background task, returning its job still
+/// running. The import report is the result of the job once it succeeded.
+async fn start_import_job(
+    speeches: Vec<Speech>,
+    managers: &Managers,
+) -> Result<RouteResponse, HttpError<'static>> {
+    let mut progress = managers
+        .job_manager
+        .start_job(JobKind::SpeechImport)
+        .await?;
+    let running = progress.job().clone();
+    let speech_manager = managers.speech_manager.clone();
+    let person_manager = managers.person_manager.clone();
+    tokio::spawn(async move {
+        let outcome = match speech_manager
+            .import_speeches(speeches, &person_manager, Some(&mut progress))
+            .await
+        {
+            Ok(report) => value::to_value(GetImportReport::from(report)).map_err(|e| e.to_string()),
+            Err(e) => Err(format!("{:?}", e)),
+        };
+        if let Err(e) = progress.finish(outcome).await {
+            println!(
+                "An error occured while recording the outcome of an import job: {:?}",
+                e
+            );
         }
+    });
+    Ok(job_value(running)?.into())
+}
+
+fn import_report_to_value(report: ImportReport) -> Result<RouteResponse, HttpError<'static>> {
+    Ok(value::to_value(GetImportReport::from(report))
+        .map_err(|e| {
+            println!(
+                "An internal error occured while converting import report to value: {}",
+                e
+            );
+            INTERNAL_ERROR
+        })?
+        .into())
+}
+
+/// Reads the filters shared by the speech list and count routes.
+fn extract_speech_filter(
+    query_params: &HashMap<String, String>,
+    token: &AuthToken,
+) -> Result<SpeechFilter, HttpError<'static>> {
+    Ok(SpeechFilter {
+        speakers: extract_uid_array_in_query("speakers", query_params)?,
+        labels: extract_uid_array_in_query("labels", query_params)?,
+        label_reader: token.user_id(),
+        tags: extract_uid_array_in_query("tags", query_params)?,
+        role: match query_params.get("role") {
+            Some(role) => Some(parse_speaker_role(role)?),
+            None => None,
+        },
+        spec: extract_filter_spec(query_params)?,
+        language: extract_language(query_params)?,
+        include_deleted: extract_include_deleted(query_params, token)?,
+        ..Default::default()
+    })
+}
+
+/// Resolves the display name ("First name Name") of every speaker of the speech.
+/// Speakers that cannot be found anymore are rendered with their uid.
+/// Reading positions of the user in the speeches, none for an anonymous user.
+async fn get_read_progress(
+    token: &AuthToken,
+    speech_manager: &SpeechManager,
+    uids: &[Uuid],
+) -> Result<HashMap<Uuid, ReadProgress>, HttpError<'static>> {
+    if !token.is_authenticated() {
+        return Ok(HashMap::new());
+    }
+    Ok(speech_manager
+        .get_read_progress(&token.user_id(), uids)
+        .await?)
+}
+
+/// Returns the annotations of the sentences read of the speech, the speech being already
+/// read.
+async fn get_sentence_annotations(
+    speech: &Speech,
+    managers: &Managers,
+) -> Result<Vec<GetSentenceAnnotation>, HttpError<'static>> {
+    let sentences = speech
+        .sentences()
+        .iter()
+        .map(|s| s.uid())
+        .collect::<HashSet<&Uuid>>();
+    Ok(managers
+        .annotation_manager
+        .get_annotations(speech.uid())
+        .await?
+        .into_iter()
+        .filter(|a| sentences.contains(&a.sentence_uid))
+        .map(GetSentenceAnnotation::from)
+        .collect())
+}
+
+/// Reads the review status the sentences of a speech are filtered by from the
+/// `review_status` query parameter.
+fn extract_review_status(
+    query_params: &HashMap<String, String>,
+) -> Result<Option<ReviewStatus>, HttpError<'static>> {
+    match query_params.get("review_status") {
+        Some(status) => Ok(Some(
+            ReviewStatus::try_from(status.to_lowercase().as_str())
+                .map_err(|_| HttpError::new(ErrorCode::InvalidReviewStatusParam))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Reads the page of the sentences of a speech from the `sentence_page` (0 by default)
+/// and `sentence_quantity` query parameters.
+fn extract_sentence_page(
+    query_params: &HashMap<String, String>,
+) -> Result<(u16, u16), HttpError<'static>> {
+    let page = match query_params.get("sentence_page") {
+        Some(page) => page
+            .parse::<u16>()
+            .map_err(|_| HttpError::new(ErrorCode::InvalidPageParam))?,
+        None => 0,
     };
-    let array_decomposed = match array_raw.split("%5B").skip(1).next() {
-        Some(v) => v,
-        None => {
-            return Err(HttpError::new(
-                400,
-                "InvalidArrayParam",
-                "The array query parameter given is an invalid format.",
-            ))
-        }
+    let quantity = match query_params.get("sentence_quantity") {
+        Some(quantity) => quantity
+            .parse::<u16>()
+            .ok()
+            .filter(|quantity| *quantity > 0)
+            .ok_or(HttpError::new(ErrorCode::InvalidQuantityParam))?,
+        None => DEFAULT_SENTENCE_QUANTITY,
     };
-    let array_decomposed = match array_decomposed.split("%5D").next() {
-        Some(v) => v,
-        None => {
-            return Err(HttpError::new(
-                400,
-                "InvalidArrayParam",
-                "The array query parameter given is an invalid format.",
-            ))
+    Ok((page, quantity))
+}
+
+/// Returns the segments of the speech, the speech being already read.
+async fn get_segments(
+    uid: &Uuid,
+    managers: &Managers,
+) -> Result<Vec<GetSegment>, HttpError<'static>> {
+    Ok(managers
+        .segment_manager
+        .get_segments(uid)
+        .await?
+        .into_iter()
+        .map(GetSegment::from)
+        .collect())
+}
+
+async fn resolve_speaker_names(
+    speech: &Speech,
+    person_manager: &PersonManager,
+) -> Result<HashMap<Uuid, String>, HttpError<'static>> {
+    let mut speaker_names = HashMap::new();
+    let speakers = speech
+        .speakers()
+        .iter()
+        .chain(speech.sentences().iter().map(|s| s.speaker()));
+    for speaker in speakers {
+        if speaker_names.contains_key(speaker) {
+            continue;
         }
-    };
-    return Ok(array_decomposed
-        .split(",")
-        .map(|v| v.to_string())
-        .collect::<Vec<String>>());
+        let name = match person_manager.get_person_by_id(speaker).await {
+            Ok(person) => format!("{} {}", person.first_name(), person.name()),
+            Err(PersonRepositoryError::PersonNotFound) => speaker.to_string(),
+            Err(e) => return Err(e.into()),
+        };
+        speaker_names.insert(*speaker, name);
+    }
+    Ok(speaker_names)
 }
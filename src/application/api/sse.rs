@@ -0,0 +1,60 @@
+use std::{fmt::Display, time::Duration};
+
+use bytes::Bytes;
+use futures_util::{stream, StreamExt};
+use http_body_util::{BodyExt, StreamBody};
+use hyper::{body::Frame, header, Response};
+use serde::Serialize;
+use tokio::{sync::broadcast, time};
+
+use super::router::BoxBody;
+
+/// Delay between two comments sent to keep the connections open.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Builds a server-sent events response. The `backlog` events are sent first, then each
+/// message received that `to_event` turns into an event. A client falling too far behind
+/// is disconnected and may reconnect.
+pub fn event_stream<T, F>(
+    backlog: Vec<Bytes>,
+    receiver: broadcast::Receiver<T>,
+    to_event: F,
+) -> Response<BoxBody>
+where
+    T: Clone + Send + 'static,
+    F: FnMut(T) -> Option<Bytes> + Send + Sync + 'static,
+{
+    let live = stream::unfold(
+        (receiver, to_event),
+        |(mut receiver, mut to_event)| async move {
+            match time::timeout(KEEP_ALIVE_INTERVAL, receiver.recv()).await {
+                Err(_) => Some((Some(Bytes::from(": keep-alive\n\n")), (receiver, to_event))),
+                Ok(Ok(message)) => {
+                    let event = to_event(message);
+                    Some((event, (receiver, to_event)))
+                }
+                Ok(Err(_)) => None,
+            }
+        },
+    );
+    let events = stream::iter(backlog.into_iter().map(Some))
+        .chain(live)
+        .filter_map(|event| async move { event })
+        .map(|event| Ok::<_, std::io::Error>(Frame::data(event)));
+    Response::builder()
+        .status(200)
+        .header(header::CONTENT_TYPE, "text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(BodyExt::boxed(StreamBody::new(events)))
+        .expect("Should not fail")
+}
+
+/// Formats one event, `id` being the value sent back by the client in `Last-Event-ID`
+/// when it reconnects.
+pub fn event(id: Option<impl Display>, name: impl Display, data: &impl Serialize) -> Bytes {
+    let data = serde_json::to_string(data).expect("Should not fail");
+    match id {
+        Some(id) => Bytes::from(format!("id: {}\nevent: {}\ndata: {}\n\n", id, name, data)),
+        None => Bytes::from(format!("event: {}\ndata: {}\n\n", name, data)),
+    }
+}
@@ -0,0 +1,166 @@
+use std::str::FromStr;
+
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        token::{AuthToken, Permissions},
+    },
+    domain::tag::{Tag, TagManager, TagRepositoryError},
+};
+
+impl From<TagRepositoryError> for HttpError<'static> {
+    fn from(value: TagRepositoryError) -> Self {
+        match value {
+            TagRepositoryError::TagNotFound => HttpError::new(ErrorCode::TagNotFound),
+            TagRepositoryError::ParentTagNotFound => HttpError::new(ErrorCode::TagNotFound),
+            TagRepositoryError::SpeechNotFound => HttpError::new(ErrorCode::SpeechNotFound),
+            TagRepositoryError::TagAlreadyExists => HttpError::new(ErrorCode::TagAlreadyExists),
+            TagRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Tags: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateTagInput {
+    name: String,
+    /// Uid of the broader tag this one narrows down.
+    parent: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct GetTagOutput {
+    uid: String,
+    name: String,
+    parent: Option<String>,
+}
+
+impl From<Tag> for GetTagOutput {
+    fn from(value: Tag) -> Self {
+        Self {
+            uid: value.uid().to_string(),
+            name: value.name().clone(),
+            parent: value.parent().map(|p| p.to_string()),
+        }
+    }
+}
+
+pub async fn router(
+    path: &str,
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    let tag_manager = &managers.tag_manager;
+    match (method, path) {
+        (&Method::GET, "") => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let tags: Vec<GetTagOutput> = tag_manager
+                .get_tags()
+                .await?
+                .into_iter()
+                .map(|t| t.into())
+                .collect();
+            Ok(value::to_value(tags).map_err(|e| {
+                println!(
+                    "An internal error occured while converting tags to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, "") => {
+            // The taxonomy is shared by the whole organization, only editors extend it.
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let input: CreateTagInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let name = input.name.trim();
+            if name.is_empty() || name.chars().count() > 100 {
+                return Err(HttpError::new(ErrorCode::InvalidTagName));
+            }
+            let parent = match input.parent {
+                Some(parent) => Some(
+                    Uuid::from_str(&parent).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?,
+                ),
+                None => None,
+            };
+            let tag = Tag::new(Uuid::new_v4(), name, parent);
+            tag_manager.create_tag(tag.clone()).await?;
+            Ok(value::to_value(GetTagOutput::from(tag)).map_err(|e| {
+                println!(
+                    "An internal error occured while converting tag to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
+
+/// Handles the `{uid}/tags` and `{uid}/tags/{tag_uid}` sub routes of speeches.
+pub async fn speech_tags_router(
+    speech_uid: Uuid,
+    tag_uid: Option<&str>,
+    method: &Method,
+    token: &AuthToken,
+    tag_manager: &TagManager,
+) -> Result<Value, HttpError<'static>> {
+    let tag_uid = match tag_uid {
+        Some(tag_uid) => {
+            Some(Uuid::parse_str(tag_uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))?)
+        }
+        None => None,
+    };
+    match (method, tag_uid) {
+        (&Method::GET, None) => {
+            if !token.permissions().contains(&Permissions::GetSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            let tags: Vec<GetTagOutput> = tag_manager
+                .get_speech_tags(&speech_uid)
+                .await?
+                .into_iter()
+                .map(|t| t.into())
+                .collect();
+            Ok(value::to_value(tags).map_err(|e| {
+                println!(
+                    "An internal error occured while converting tags to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, Some(tag_uid)) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            tag_manager.attach_tag(&speech_uid, &tag_uid).await?;
+            Ok(Value::Null)
+        }
+        (&Method::DELETE, Some(tag_uid)) => {
+            if !token.permissions().contains(&Permissions::UpdateSpeech) {
+                return Err(ACCESS_DENIED_ERROR);
+            }
+            tag_manager.detach_tag(&speech_uid, &tag_uid).await?;
+            Ok(Value::Null)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
@@ -1,8 +1,9 @@
 use std::str::FromStr;
 
 use serde::Deserialize;
+use uuid::Uuid;
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub enum Permissions {
     GetSpeech,
     CreateSpeech,
@@ -12,6 +13,9 @@ pub enum Permissions {
     CreatePerson,
     UpdatePerson,
     DeletePerson,
+    Admin,
+    /// Manages the organizations hosted on the deployment.
+    ManageOrganizations,
 }
 
 impl FromStr for Permissions {
@@ -27,6 +31,8 @@ impl FromStr for Permissions {
             "CreatePerson" => Ok(Permissions::CreatePerson),
             "UpdatePerson" => Ok(Permissions::UpdatePerson),
             "DeletePerson" => Ok(Permissions::DeletePerson),
+            "Admin" => Ok(Permissions::Admin),
+            "ManageOrganizations" => Ok(Permissions::ManageOrganizations),
             _ => Err(format!("Invalid permission: {}", s)),
         }
     }
@@ -37,38 +43,74 @@ pub struct AuthToken {
     _user_id: Option<String>,
     _username: Option<String>,
     permissions: Vec<Permissions>,
+    /// Organization of the user, the default organization when missing.
+    organization_id: Option<Uuid>,
+    /// Whether the request is read anonymously in the public read mode.
+    #[serde(skip)]
+    public_reader: bool,
+    /// Whether the request was sent without a token.
+    #[serde(skip)]
+    anonymous: bool,
 }
 
-impl Default for AuthToken {
-    fn default() -> Self {
+impl AuthToken {
+    /// Token of a request sent without one. Anonymous users are only granted the
+    /// permissions configured for them, none by default, and only when the public read
+    /// mode is enabled: they may then read the validated speeches and the persons of the
+    /// default organization.
+    pub fn anonymous(public_read: bool, permissions: &[Permissions]) -> Self {
         Self {
             _user_id: Default::default(),
             _username: Default::default(),
-            permissions: vec![Permissions::GetPerson, Permissions::GetSpeech],
+            permissions: if public_read {
+                permissions.to_vec()
+            } else {
+                Vec::new()
+            },
+            organization_id: None,
+            public_reader: public_read,
+            anonymous: true,
         }
     }
-}
 
-impl AuthToken {
     pub fn _new(
         user_id: Option<String>,
         username: Option<String>,
         permissions: Vec<Permissions>,
+        organization_id: Option<Uuid>,
     ) -> Self {
         return Self {
             _user_id: user_id,
             _username: username,
             permissions,
+            organization_id,
+            public_reader: false,
+            anonymous: false,
         };
     }
 
-    pub fn _user_id(&self) -> String {
+    pub fn user_id(&self) -> String {
         return self._user_id.clone().unwrap_or("anonymous".to_owned());
     }
+    pub fn is_authenticated(&self) -> bool {
+        self._user_id.is_some()
+    }
+    /// Whether the request was sent without a token, its denials asking to authenticate.
+    pub fn is_anonymous(&self) -> bool {
+        self.anonymous
+    }
+    /// Whether the request is read anonymously in the public read mode, only reaching the
+    /// public routes.
+    pub fn is_public_reader(&self) -> bool {
+        self.public_reader
+    }
     pub fn _username(&self) -> String {
         return self._username.clone().unwrap_or("Unknown_user".to_owned());
     }
     pub fn permissions(&self) -> &Vec<Permissions> {
         return &self.permissions;
     }
+    pub fn organization(&self) -> Option<Uuid> {
+        self.organization_id
+    }
 }
@@ -0,0 +1,220 @@
+use serde::Serialize;
+
+use super::{
+    error::{ErrorCode, Language},
+    router::HttpError,
+};
+
+/// Declares the `FieldProblem` enum together with its message in every language, as the
+/// error codes are, so a field cannot be reported without a message for each of them. The
+/// parameters of a problem are named in its messages.
+macro_rules! field_problems {
+    ($($name:ident $(($($param:ident: $type:ty),*))? {
+        en: $en:literal,
+        fr: $fr:literal,
+    },)*) => {
+        /// Reason a field of a request body is invalid.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum FieldProblem {
+            $($name $({ $($param: $type),* })?,)*
+        }
+
+        impl FieldProblem {
+            /// Message sent to the client along with the path of the field.
+            pub fn message(&self, language: Language) -> String {
+                match (self, language) {
+                    $((FieldProblem::$name $({ $($param),* })?, Language::En) => format!($en),)*
+                    $((FieldProblem::$name $({ $($param),* })?, Language::Fr) => format!($fr),)*
+                }
+            }
+        }
+    };
+}
+
+field_problems! {
+    InvalidFormat {
+        en: "The value does not match the expected format",
+        fr: "La valeur ne correspond pas au format attendu",
+    },
+    EmptyName {
+        en: "The name must not be empty",
+        fr: "Le nom ne doit pas être vide",
+    },
+    EmptyFirstName {
+        en: "The first name must not be empty",
+        fr: "Le prénom ne doit pas être vide",
+    },
+    InvalidBirthDate {
+        en: "The birth date must be an ISO 8601 date",
+        fr: "La date de naissance doit être une date ISO 8601",
+    },
+    FutureBirthDate {
+        en: "The birth date must not be in the future",
+        fr: "La date de naissance ne doit pas être dans le futur",
+    },
+    InvalidDate {
+        en: "The date must be an ISO 8601 date",
+        fr: "La date doit être une date ISO 8601",
+    },
+    TooManySpeakers(max: usize) {
+        en: "A speech has at most {max} speakers",
+        fr: "Un discours a au plus {max} orateurs",
+    },
+    EmptyText {
+        en: "The text must not be empty",
+        fr: "Le texte ne doit pas être vide",
+    },
+    SpeakerNotInSpeech {
+        en: "The speaker must be one of the speakers of the speech",
+        fr: "L'orateur doit être l'un des orateurs du discours",
+    },
+    InvalidReviewStatus {
+        en: "The status must be unreviewed, approved or flagged",
+        fr: "Le statut doit être unreviewed, approved ou flagged",
+    },
+    ReviewCommentTooLong {
+        en: "A comment has at most 2000 characters",
+        fr: "Un commentaire a au plus 2000 caractères",
+    },
+    InvalidSegmentTitle {
+        en: "The title must contain between 1 and 200 characters",
+        fr: "Le titre doit contenir entre 1 et 200 caractères",
+    },
+    SegmentEndsBeforeStart {
+        en: "The segment must not end before it starts",
+        fr: "Le segment ne doit pas finir avant de commencer",
+    },
+    InvalidWatchlistName {
+        en: "The name must contain between 1 and 100 characters",
+        fr: "Le nom doit contenir entre 1 et 100 caractères",
+    },
+    InvalidSpeakerUid {
+        en: "The speaker must be a uid",
+        fr: "L'orateur doit être un uid",
+    },
+    DeletedIdentity {
+        en: "A deleted person has this identity, it must be restored instead",
+        fr: "Une personne supprimée a cette identité, elle doit être restaurée à la place",
+    },
+    EmptyKeyword {
+        en: "The keyword must not be empty",
+        fr: "Le mot-clé ne doit pas être vide",
+    },
+}
+
+/// Invalid field of a request body, `field` being its path in the body, e.g.
+/// `sentences[3].speaker`.
+#[derive(Debug, Clone)]
+pub struct FieldError {
+    field: String,
+    problem: FieldProblem,
+    /// Specifics of this occurrence, e.g. the parser error, untranslated.
+    detail: Option<String>,
+}
+
+impl FieldError {
+    /// Field error as sent to the client, its message in the language of the client.
+    pub fn localize(&self, language: Language) -> LocalizedFieldError {
+        LocalizedFieldError {
+            field: self.field.clone(),
+            message: self.problem.message(language),
+            detail: self.detail.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocalizedFieldError {
+    field: String,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+/// Collects every invalid field of a request body, so the client can fix them all at once
+/// instead of one per request.
+#[derive(Default)]
+pub struct Validation {
+    errors: Vec<FieldError>,
+}
+
+impl Validation {
+    /// Records an error on the field unless `valid` holds.
+    pub fn check(&mut self, valid: bool, field: impl Into<String>, problem: FieldProblem) {
+        if !valid {
+            self.errors.push(FieldError {
+                field: field.into(),
+                problem,
+                detail: None,
+            });
+        }
+    }
+
+    /// Records an error on the field along with its specifics.
+    pub fn reject(&mut self, field: impl Into<String>, problem: FieldProblem, detail: String) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            problem,
+            detail: Some(detail),
+        });
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the invalid fields recorded, e.g. to report them per item of a batch.
+    pub fn into_errors(self) -> Vec<FieldError> {
+        self.errors
+    }
+
+    /// Fails with a 422 listing the invalid fields, if any.
+    pub fn into_result(self) -> Result<(), HttpError<'static>> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        Err(HttpError::with_field_errors(
+            ErrorCode::ValidationFailed,
+            self.errors,
+        ))
+    }
+}
+
+/// Path of a field of an object found at `path`, the body itself when `path` is empty.
+pub fn field(path: &str, name: &str) -> String {
+    match path {
+        "" => name.to_owned(),
+        _ => format!("{}.{}", path, name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{field, FieldProblem, Language, Validation};
+
+    #[test]
+    fn validation_collects_every_invalid_field() {
+        let mut validation = Validation::default();
+        validation.check(true, "name", FieldProblem::EmptyName);
+        validation.check(false, field("", "name"), FieldProblem::EmptyName);
+        validation.check(
+            false,
+            field("sentences[3]", "speaker"),
+            FieldProblem::SpeakerNotInSpeech,
+        );
+        assert_eq!(validation.errors.len(), 2);
+        assert_eq!(validation.errors[1].field, "sentences[3].speaker");
+        assert_eq!(
+            serde_json::to_value(validation.errors[0].localize(Language::Fr)).unwrap(),
+            json!({ "field": "name", "message": "Le nom ne doit pas être vide" })
+        );
+        assert!(validation.into_result().is_err());
+        assert!(Validation::default().into_result().is_ok());
+        assert_eq!(
+            FieldProblem::TooManySpeakers { max: 100 }.message(Language::Fr),
+            "Un discours a au plus 100 orateurs"
+        );
+    }
+}
@@ -0,0 +1 @@
+pub mod watchlist_router;
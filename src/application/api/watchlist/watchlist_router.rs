@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use hyper::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::{value, Value};
+use uuid::Uuid;
+
+use crate::{
+    application::api::{
+        error::ErrorCode,
+        router::{HttpError, Managers, ACCESS_DENIED_ERROR, INTERNAL_ERROR, NOT_FOUND_ERROR},
+        speech::speech_router::GetSpeech,
+        token::{AuthToken, Permissions},
+        validation::{field, FieldProblem, Validation},
+    },
+    domain::{
+        speech::speech_repository::SpeechProjection,
+        watchlist::{Watchlist, WatchlistRepositoryError},
+    },
+};
+
+/// Most speeches sent by a check of a watchlist, the next ones being sent by the next
+/// checks.
+const MAX_WATCHLIST_SPEECHES: u16 = 100;
+
+impl From<WatchlistRepositoryError> for HttpError<'static> {
+    fn from(value: WatchlistRepositoryError) -> Self {
+        match value {
+            WatchlistRepositoryError::WatchlistNotFound => {
+                HttpError::new(ErrorCode::WatchlistNotFound)
+            }
+            WatchlistRepositoryError::WatchlistAlreadyExists => {
+                HttpError::new(ErrorCode::WatchlistAlreadyExists)
+            }
+            WatchlistRepositoryError::InternalError(e) => {
+                println!(
+                    "An internal error occured while making an action on Watchlists: {}",
+                    e
+                );
+                INTERNAL_ERROR
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct WatchlistInput {
+    name: String,
+    /// Uids of the persons followed, any speaker when empty.
+    #[serde(default)]
+    speakers: Vec<String>,
+    /// Words looked for in the names and the sentences of the speeches.
+    #[serde(default)]
+    keywords: Vec<String>,
+    #[serde(default)]
+    media: Vec<String>,
+}
+
+/// Criteria of a watchlist read from its input, once valid.
+struct WatchlistCriteria {
+    name: String,
+    speakers: Vec<Uuid>,
+    keywords: Vec<String>,
+    media: Vec<String>,
+}
+
+impl WatchlistInput {
+    fn criteria(self) -> Result<WatchlistCriteria, HttpError<'static>> {
+        let mut validation = Validation::default();
+        let name = self.name.trim();
+        validation.check(
+            !name.is_empty() && name.chars().count() <= 100,
+            field("", "name"),
+            FieldProblem::InvalidWatchlistName,
+        );
+        let mut speakers = Vec::with_capacity(self.speakers.len());
+        for (index, speaker) in self.speakers.iter().enumerate() {
+            let uid = Uuid::parse_str(speaker).ok();
+            validation.check(
+                uid.is_some(),
+                format!("speakers[{}]", index),
+                FieldProblem::InvalidSpeakerUid,
+            );
+            speakers.extend(uid);
+        }
+        let keywords = self
+            .keywords
+            .iter()
+            .map(|keyword| keyword.trim().to_owned())
+            .collect::<Vec<String>>();
+        for (index, keyword) in keywords.iter().enumerate() {
+            validation.check(
+                !keyword.is_empty(),
+                format!("keywords[{}]", index),
+                FieldProblem::EmptyKeyword,
+            );
+        }
+        validation.into_result()?;
+        Ok(WatchlistCriteria {
+            name: name.to_owned(),
+            speakers,
+            keywords,
+            media: self.media,
+        })
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetWatchlistOutput {
+    uid: String,
+    name: String,
+    speakers: Vec<String>,
+    keywords: Vec<String>,
+    media: Vec<String>,
+    last_checked_at: String,
+}
+
+impl From<Watchlist> for GetWatchlistOutput {
+    fn from(value: Watchlist) -> Self {
+        Self {
+            uid: value.uid().to_string(),
+            name: value.name().clone(),
+            speakers: value.speakers().iter().map(|s| s.to_string()).collect(),
+            keywords: value.keywords().clone(),
+            media: value.media().clone(),
+            last_checked_at: value.last_checked_at().to_rfc3339(),
+        }
+    }
+}
+
+/// Speeches stored after the ones sent by the previous checks of a watchlist.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GetWatchlistSpeechesOutput {
+    since: String,
+    checked_at: String,
+    speeches: Vec<GetSpeech>,
+    /// Whether more speeches are left for the next check.
+    more: bool,
+}
+
+fn parse_watchlist_uid(uid: &str) -> Result<Uuid, HttpError<'static>> {
+    Uuid::parse_str(uid).map_err(|_| HttpError::new(ErrorCode::InvalidUid))
+}
+
+/// Queries saved by the authenticated user, each one only reachable by its owner.
+pub async fn router(
+    path: &str,
+    query_params: &HashMap<String, String>,
+    method: &Method,
+    token: &AuthToken,
+    body: Value,
+    managers: &Managers,
+) -> Result<Value, HttpError<'static>> {
+    if !token.is_authenticated() || !token.permissions().contains(&Permissions::GetSpeech) {
+        return Err(ACCESS_DENIED_ERROR);
+    }
+    let watchlist_manager = &managers.watchlist_manager;
+    let owner = token.user_id();
+    let splitted_path = path.split("/").collect::<Vec<&str>>();
+    match (method, splitted_path.as_slice()) {
+        (&Method::GET, [""]) => {
+            let watchlists: Vec<GetWatchlistOutput> = watchlist_manager
+                .get_watchlists(&owner)
+                .await?
+                .into_iter()
+                .map(|w| w.into())
+                .collect();
+            Ok(value::to_value(watchlists).map_err(|e| {
+                println!(
+                    "An internal error occured while converting watchlists to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::POST, [""]) => {
+            let input: WatchlistInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let criteria = input.criteria()?;
+            // Only the speeches stored from now on are new to the watchlist.
+            let watchlist = Watchlist::new(
+                Uuid::new_v4(),
+                &owner,
+                &criteria.name,
+                criteria.speakers,
+                criteria.keywords,
+                criteria.media,
+                Utc::now(),
+            );
+            watchlist_manager
+                .create_watchlist(watchlist.clone())
+                .await?;
+            Ok(
+                value::to_value(GetWatchlistOutput::from(watchlist)).map_err(|e| {
+                    println!(
+                        "An internal error occured while converting watchlist to value: {:?}",
+                        e
+                    );
+                    INTERNAL_ERROR
+                })?,
+            )
+        }
+        (&Method::GET, [uid]) => {
+            let uid = parse_watchlist_uid(uid)?;
+            let watchlist: GetWatchlistOutput =
+                watchlist_manager.get_watchlist(&uid, &owner).await?.into();
+            Ok(value::to_value(watchlist).map_err(|e| {
+                println!(
+                    "An internal error occured while converting watchlist to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (&Method::PUT, [uid]) => {
+            let uid = parse_watchlist_uid(uid)?;
+            let input: WatchlistInput = serde_json::from_value(body)
+                .map_err(|_| HttpError::new(ErrorCode::InvalidFormat))?;
+            let criteria = input.criteria()?;
+            // The last check is not updated, the repository keeps the stored one.
+            let watchlist = Watchlist::new(
+                uid,
+                &owner,
+                &criteria.name,
+                criteria.speakers,
+                criteria.keywords,
+                criteria.media,
+                Utc::now(),
+            );
+            watchlist_manager.update_watchlist(watchlist).await?;
+            Ok(Value::Null)
+        }
+        (&Method::DELETE, [uid]) => {
+            let uid = parse_watchlist_uid(uid)?;
+            watchlist_manager.delete_watchlist(&uid, &owner).await?;
+            Ok(Value::Null)
+        }
+        (&Method::GET, [uid, "speeches"]) => {
+            let uid = parse_watchlist_uid(uid)?;
+            let quantity = match query_params.get("quantity") {
+                Some(v) => v
+                    .parse::<u16>()
+                    .map_err(|_| HttpError::new(ErrorCode::InvalidQuantityParam))?
+                    .clamp(1, MAX_WATCHLIST_SPEECHES),
+                None => MAX_WATCHLIST_SPEECHES,
+            };
+            let watchlist = watchlist_manager.get_watchlist(&uid, &owner).await?;
+            // One more speech is read to tell whether some are left for the next check.
+            let mut speeches = managers
+                .speech_manager
+                .get_speech(
+                    0,
+                    quantity + 1,
+                    &watchlist.new_speeches_filter(),
+                    SpeechProjection::default(),
+                )
+                .await?;
+            let more = speeches.len() > quantity as usize;
+            speeches.truncate(quantity as usize);
+            // The check is recorded once the speeches are read, a check failing meanwhile
+            // leaving them to the next one.
+            let checked_at = watchlist_manager
+                .check_watchlist(&uid, &owner, speeches.last().map(|s| &s.position))
+                .await?;
+            Ok(value::to_value(GetWatchlistSpeechesOutput {
+                since: watchlist.last_checked_at().to_rfc3339(),
+                checked_at: checked_at.to_rfc3339(),
+                speeches: speeches.into_iter().map(GetSpeech::from).collect(),
+                more,
+            })
+            .map_err(|e| {
+                println!(
+                    "An internal error occured while converting watchlist speeches to value: {:?}",
+                    e
+                );
+                INTERNAL_ERROR
+            })?)
+        }
+        (_, _) => Err(NOT_FOUND_ERROR),
+    }
+}
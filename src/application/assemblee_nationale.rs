@@ -0,0 +1,430 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use quick_xml::{events::Event, Reader};
+use uuid::Uuid;
+
+use crate::domain::{
+    filter::{FilterCondition, FilterOperator, FilterSpec, FilterValue},
+    person::{
+        DeleteStrategy, Person, PersonField, PersonFilter, PersonManager, PersonRepositoryError,
+    },
+    speech::{
+        manager::SpeechManager, sentence::Sentence, speech_repository::SpeechRepositoryError,
+        Speech, SpeechStatus,
+    },
+};
+
+/// Media of the speeches imported from the compte-rendus.
+const ASSEMBLEE_NATIONALE_MEDIA: &str = "Assemblée nationale";
+
+/// Birth date of the persons created for the orators, the compte-rendu not giving it. An
+/// editor is expected to correct it.
+const UNKNOWN_BIRTH_DATE: (i32, u32, u32) = (1900, 1, 1);
+
+/// Civilities preceding the names of the orators.
+const CIVILITIES: &[&str] = &["M.", "Mme", "Mlle", "MM.", "Mmes"];
+
+/// Abbreviations ending with a dot which do not end a sentence.
+const ABBREVIATIONS: &[&str] = &["M.", "MM.", "Mme.", "art.", "n°.", "cf.", "p.", "etc."];
+
+#[derive(Debug)]
+pub enum AssembleeNationaleImportError {
+    /// The body is not a compte-rendu, or has no date or no intervention.
+    InvalidXml(String),
+    /// Orators matching no person, the import creating no person.
+    UnknownOrators(Vec<String>),
+    PersonError(PersonRepositoryError),
+    SpeechError(SpeechRepositoryError),
+}
+
+impl From<PersonRepositoryError> for AssembleeNationaleImportError {
+    fn from(value: PersonRepositoryError) -> Self {
+        Self::PersonError(value)
+    }
+}
+
+impl From<SpeechRepositoryError> for AssembleeNationaleImportError {
+    fn from(value: SpeechRepositoryError) -> Self {
+        Self::SpeechError(value)
+    }
+}
+
+/// Intervention of an orator during a sitting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intervention {
+    /// Name of the orator as written in the compte-rendu, e.g. `M. Jean Dupont`.
+    pub orator: String,
+    pub text: String,
+}
+
+/// Sitting read from a compte-rendu, in the order of its interventions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sitting {
+    pub date: NaiveDateTime,
+    /// Day of the sitting as written in the compte-rendu, e.g. `lundi 02 octobre 2023`.
+    pub day: Option<String>,
+    pub interventions: Vec<Intervention>,
+}
+
+/// Outcome of the import of a sitting.
+#[derive(Debug, Clone)]
+pub struct AssembleeNationaleReport {
+    pub speech: Uuid,
+    pub interventions: usize,
+    pub sentences: usize,
+    /// Orators mapped to a person already stored, with the uid of the person.
+    pub matched: Vec<(String, Uuid)>,
+    /// Orators a person was created for, with the uid of the person.
+    pub created: Vec<(String, Uuid)>,
+}
+
+/// Reads the sitting of a compte-rendu of the Assemblée nationale open data (`compteRendu`
+/// documents). The paragraphs without an orator, e.g. the summaries, are left out.
+pub fn parse_sitting(xml: &str) -> Result<Sitting, AssembleeNationaleImportError> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    let mut path: Vec<String> = Vec::new();
+    let mut date = None;
+    let mut day = None;
+    let mut interventions = Vec::new();
+    // Orator and text of the paragraph being read.
+    let mut paragraph: Option<(Option<String>, String)> = None;
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| AssembleeNationaleImportError::InvalidXml(e.to_string()))?;
+        match event {
+            Event::Start(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                if name == "paragraphe" {
+                    paragraph = Some((None, String::new()));
+                }
+                path.push(name);
+            }
+            Event::End(_) => {
+                if path.pop().as_deref() == Some("paragraphe") {
+                    if let Some((Some(orator), text)) = paragraph.take() {
+                        let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+                        if !text.is_empty() {
+                            interventions.push(Intervention { orator, text });
+                        }
+                    }
+                }
+            }
+            // Line breaks within a text.
+            Event::Empty(_) => {
+                if let Some((_, text)) = &mut paragraph {
+                    text.push(' ');
+                }
+            }
+            Event::Text(content) => {
+                let content = content
+                    .unescape()
+                    .map_err(|e| AssembleeNationaleImportError::InvalidXml(e.to_string()))?;
+                let parent = path.last().map(String::as_str);
+                match (&mut paragraph, parent) {
+                    (_, Some("dateSeance")) => date = Some(parse_sitting_date(&content)?),
+                    (_, Some("dateSeanceJour")) => day = Some(content.trim().to_owned()),
+                    (Some((orator, _)), Some("nom"))
+                        if path.iter().any(|p| p == "orateur") && orator.is_none() =>
+                    {
+                        *orator = Some(content.trim().to_owned());
+                    }
+                    (Some((_, text)), _) if path.iter().any(|p| p == "texte") => {
+                        text.push(' ');
+                        text.push_str(&content);
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let date = date.ok_or(AssembleeNationaleImportError::InvalidXml(
+        "The compte-rendu has no dateSeance".to_owned(),
+    ))?;
+    if interventions.is_empty() {
+        return Err(AssembleeNationaleImportError::InvalidXml(
+            "The compte-rendu has no intervention".to_owned(),
+        ));
+    }
+    Ok(Sitting {
+        date,
+        day,
+        interventions,
+    })
+}
+
+/// Reads a date of a compte-rendu, e.g. `20231002150000000` for 2 October 2023 at 15:00.
+fn parse_sitting_date(value: &str) -> Result<NaiveDateTime, AssembleeNationaleImportError> {
+    let value = value.trim();
+    value
+        .get(..14)
+        .and_then(|date| NaiveDateTime::parse_from_str(date, "%Y%m%d%H%M%S").ok())
+        .ok_or(AssembleeNationaleImportError::InvalidXml(format!(
+            "The dateSeance {} is not a date",
+            value
+        )))
+}
+
+/// Splits the text of an intervention into sentences, on the final punctuation followed by
+/// a space. The stage directions between parentheses, e.g. `(Applaudissements.)`, are
+/// left out.
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let words = text.split_whitespace().collect::<Vec<&str>>();
+    for (index, word) in words.iter().enumerate() {
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+        let ends_sentence = word.ends_with(['.', '!', '?', '…'])
+            || word.ends_with(".)")
+            || word.ends_with("!)")
+            || word.ends_with("?)");
+        let next_starts_sentence = words
+            .get(index + 1)
+            .and_then(|next| next.chars().find(|c| c.is_alphanumeric()))
+            .is_none_or(|c| !c.is_lowercase());
+        if ends_sentence && next_starts_sentence && !ABBREVIATIONS.contains(word) {
+            sentences.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        sentences.push(current);
+    }
+    sentences
+        .into_iter()
+        .filter(|sentence| !(sentence.starts_with('(') && sentence.ends_with(')')))
+        .collect()
+}
+
+/// First name and name of an orator, without its civility. An orator named by its office,
+/// e.g. `Mme la présidente`, has no first name.
+pub fn orator_identity(orator: &str) -> (String, String) {
+    let mut words = orator.split_whitespace().collect::<Vec<&str>>();
+    if words.len() > 1 && CIVILITIES.contains(&words[0]) {
+        words.remove(0);
+    }
+    let is_office = words
+        .first()
+        .and_then(|word| word.chars().next())
+        .is_some_and(|c| c.is_lowercase());
+    match words.split_first() {
+        Some((first_name, name)) if !is_office && !name.is_empty() => {
+            (first_name.to_string(), name.join(" "))
+        }
+        _ => (String::new(), words.join(" ")),
+    }
+}
+
+/// Creates the speech of the sitting, its orators speaking through the persons of the
+/// same name. The missing persons are created when `create_persons` is set, the import
+/// being refused otherwise, and deleted again when the speech cannot be created.
+pub async fn import_sitting(
+    xml: &str,
+    create_persons: bool,
+    person_manager: &PersonManager,
+    speech_manager: &SpeechManager,
+) -> Result<AssembleeNationaleReport, AssembleeNationaleImportError> {
+    let sitting = parse_sitting(xml)?;
+    let mut orators: Vec<(String, Option<Uuid>)> = Vec::new();
+    for intervention in &sitting.interventions {
+        if orators.iter().any(|(o, _)| *o == intervention.orator) {
+            continue;
+        }
+        let person = find_person(&intervention.orator, person_manager).await?;
+        orators.push((intervention.orator.clone(), person));
+    }
+    let unknown = orators
+        .iter()
+        .filter(|(_, person)| person.is_none())
+        .map(|(orator, _)| orator.clone())
+        .collect::<Vec<String>>();
+    if !unknown.is_empty() && !create_persons {
+        return Err(AssembleeNationaleImportError::UnknownOrators(unknown));
+    }
+    let mut report = AssembleeNationaleReport {
+        speech: Uuid::new_v4(),
+        interventions: sitting.interventions.len(),
+        sentences: 0,
+        matched: Vec::new(),
+        created: Vec::new(),
+    };
+    let (year, month, day) = UNKNOWN_BIRTH_DATE;
+    let birth_date = NaiveDate::from_ymd_opt(year, month, day).expect("Should be a valid date");
+    for (orator, person) in orators.iter_mut() {
+        match person {
+            Some(uid) => report.matched.push((orator.clone(), *uid)),
+            None => {
+                let (first_name, name) = orator_identity(orator);
+                let created = Person::new(Uuid::new_v4(), &name, &first_name, birth_date, 0, 0);
+                if let Err(e) = person_manager.create_person(created.clone()).await {
+                    delete_created_persons(&report.created, person_manager).await;
+                    return Err(e.into());
+                }
+                report.created.push((orator.clone(), *created.uid()));
+                *person = Some(*created.uid());
+            }
+        }
+    }
+    let speaker_of = |orator: &str| {
+        orators
+            .iter()
+            .find(|(o, _)| o == orator)
+            .and_then(|(_, person)| *person)
+            .expect("Every orator has a person")
+    };
+    let sentences = sitting
+        .interventions
+        .iter()
+        .flat_map(|intervention| {
+            let speaker = speaker_of(&intervention.orator);
+            split_sentences(&intervention.text)
+                .into_iter()
+                .map(move |text| Sentence::new(&Uuid::new_v4(), &speaker, &text, false))
+        })
+        .collect::<Vec<Sentence>>();
+    report.sentences = sentences.len();
+    let speakers = orators
+        .iter()
+        .filter_map(|(_, person)| *person)
+        .collect::<Vec<Uuid>>();
+    let name = match &sitting.day {
+        Some(day) => format!("Séance du {}", day),
+        None => format!("Séance du {}", sitting.date.format("%d/%m/%Y %H:%M")),
+    };
+    // The compte-rendu gives no offset, the time of the sitting is kept as is.
+    let speech = Speech::new(
+        &report.speech,
+        &name,
+        sitting.date.and_utc(),
+        &speakers,
+        &sentences,
+        ASSEMBLEE_NATIONALE_MEDIA,
+        SpeechStatus::Pending,
+    );
+    if let Err(e) = speech_manager.create_speech(speech).await {
+        delete_created_persons(&report.created, person_manager).await;
+        return Err(e.into());
+    }
+    println!(
+        "Imported the sitting {} of the Assemblée nationale: {} sentences, {} persons created",
+        report.speech,
+        report.sentences,
+        report.created.len()
+    );
+    Ok(report)
+}
+
+/// Person of the organization named as the orator, if any.
+async fn find_person(
+    orator: &str,
+    person_manager: &PersonManager,
+) -> Result<Option<Uuid>, PersonRepositoryError> {
+    let (first_name, name) = orator_identity(orator);
+    let condition = |field, value: String| FilterCondition {
+        field,
+        operator: FilterOperator::Eq,
+        value: FilterValue::Text(value),
+    };
+    let filter = PersonFilter {
+        spec: FilterSpec {
+            conditions: vec![
+                condition(PersonField::Name, name),
+                condition(PersonField::FirstName, first_name),
+            ],
+        },
+        ..Default::default()
+    };
+    Ok(person_manager
+        .get_people(0, 1, &filter)
+        .await?
+        .people
+        .first()
+        .map(|person| *person.uid()))
+}
+
+/// Deletes the persons created by an import which failed.
+async fn delete_created_persons(created: &[(String, Uuid)], person_manager: &PersonManager) {
+    for (orator, uid) in created {
+        // The speech was not created, nothing references the persons.
+        if let Err(e) = person_manager
+            .delete_person(uid, DeleteStrategy::Restrict)
+            .await
+        {
+            println!(
+                "An internal error occured while deleting the person {} created for {}: {:?}",
+                uid, orator, e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{orator_identity, parse_sitting, split_sentences};
+
+    #[test]
+    fn compte_rendu_is_read_as_interventions() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <compteRendu xmlns="http://schemas.assemblee-nationale.fr/referentiel">
+              <metadonnees>
+                <dateSeance>20231002150000000</dateSeance>
+                <dateSeanceJour>lundi 02 octobre 2023</dateSeanceJour>
+              </metadonnees>
+              <contenu>
+                <point>
+                  <paragraphe>
+                    <texte>Ordre du jour</texte>
+                  </paragraphe>
+                  <paragraphe>
+                    <orateurs><orateur><nom>Mme la présidente</nom></orateur></orateurs>
+                    <texte>La séance est ouverte.</texte>
+                  </paragraphe>
+                  <paragraphe>
+                    <orateurs><orateur><nom>M. Jean Dupont</nom></orateur></orateurs>
+                    <texte>Je vous remercie.<br/>Ce texte est <italique>essentiel</italique> !</texte>
+                  </paragraphe>
+                </point>
+              </contenu>
+            </compteRendu>"#;
+        let sitting = parse_sitting(xml).unwrap();
+        assert_eq!(sitting.day.as_deref(), Some("lundi 02 octobre 2023"));
+        assert_eq!(sitting.date.to_string(), "2023-10-02 15:00:00");
+        assert_eq!(sitting.interventions.len(), 2);
+        assert_eq!(sitting.interventions[1].orator, "M. Jean Dupont");
+        assert_eq!(
+            sitting.interventions[1].text,
+            "Je vous remercie. Ce texte est essentiel !"
+        );
+        assert!(parse_sitting("<compteRendu></compteRendu>").is_err());
+    }
+
+    #[test]
+    fn interventions_are_split_into_sentences() {
+        assert_eq!(
+            split_sentences(
+                "Je remercie M. Dupont. Est-ce bien raisonnable ? (Applaudissements.) Non… il faut voter."
+            ),
+            vec![
+                "Je remercie M. Dupont.",
+                "Est-ce bien raisonnable ?",
+                "Non… il faut voter.",
+            ]
+        );
+    }
+
+    #[test]
+    fn orators_are_named_without_civility() {
+        assert_eq!(
+            orator_identity("M. Jean-Luc Martin Durand"),
+            ("Jean-Luc".to_owned(), "Martin Durand".to_owned())
+        );
+        assert_eq!(
+            orator_identity("Mme la présidente"),
+            (String::new(), "la présidente".to_owned())
+        );
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::{
+    application::api::{
+        error::Language, person::person_router::CreatePersonInput, router::HttpError,
+        speech::speech_router::import_speeches_from_body, validation::Validation,
+    },
+    domain::{
+        person::{Person, PersonManager},
+        speech::{
+            import::ImportReport,
+            manager::SpeechManager,
+            speech_repository::{SpeechFilter, SpeechProjection, SpeechSummary},
+        },
+    },
+};
+
+/// Message of an error of the API, as its client reads it, for the operator running a
+/// command.
+fn error_message(error: HttpError<'static>) -> String {
+    let mut message = error.to_json(Language::En, "");
+    if let Some(envelope) = message.as_object_mut() {
+        envelope.remove("code");
+        envelope.remove("request_id");
+    }
+    message.to_string()
+}
+
+/// Creates a person of the default organization, checked as by `POST /api/person`.
+pub async fn create_person(
+    person_manager: &PersonManager,
+    name: &str,
+    first_name: &str,
+    birth_date: &str,
+) -> Result<Person, String> {
+    let input = CreatePersonInput::new(name, first_name, birth_date);
+    let mut validation = Validation::default();
+    input.validate(&mut validation, "");
+    validation.into_result().map_err(error_message)?;
+    let person: Person = input.try_into().map_err(error_message)?;
+    person_manager
+        .create_person(person.clone())
+        .await
+        .map_err(|e| error_message(e.into()))?;
+    Ok(person)
+}
+
+/// Imports the speeches of a JSON file into the default organization. The file holds the
+/// body of `POST /api/speech/imports`, the speeches that cannot be created being kept as
+/// conflicts of the import.
+pub async fn import_speech_file(
+    path: &Path,
+    speech_manager: &SpeechManager,
+    person_manager: &PersonManager,
+) -> Result<ImportReport, String> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let body: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("{} is not JSON: {}", path.display(), e))?;
+    let speeches = import_speeches_from_body(body).map_err(error_message)?;
+    speech_manager
+        .import_speeches(speeches, person_manager, None)
+        .await
+        .map_err(|e| error_message(e.into()))
+}
+
+/// Returns a page of the speeches of the default organization, the latest first.
+pub async fn list_speeches(
+    speech_manager: &SpeechManager,
+    page: u16,
+    quantity: u16,
+) -> Result<Vec<SpeechSummary>, String> {
+    speech_manager
+        .get_speech(
+            page,
+            quantity,
+            &SpeechFilter::default(),
+            SpeechProjection {
+                speakers: false,
+                sentence_summary: true,
+            },
+        )
+        .await
+        .map_err(|e| error_message(e.into()))
+}
+
+/// Line of a speech in the output of `list-speeches`, its fields separated by tabs.
+pub fn speech_line(summary: &SpeechSummary) -> String {
+    let speech = &summary.speech;
+    format!(
+        "{}\t{}\t{}\t{}\t{} sentences\t{}",
+        speech.uid(),
+        speech.date().format("%Y-%m-%d %H:%M"),
+        speech.speech_status(),
+        speech.media(),
+        summary.sentence_count,
+        speech.name()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::speech_line;
+    use crate::domain::speech::{
+        speech_repository::{SpeechSummary, StoragePosition},
+        Speech, SpeechStatus,
+    };
+
+    #[test]
+    fn speech_line_is_tab_separated() {
+        let uid = Uuid::new_v4();
+        let speech = Speech::new(
+            &uid,
+            "Débat",
+            "2024-03-02T20:30:00Z".parse().unwrap(),
+            &[],
+            &[],
+            "TF1",
+            SpeechStatus::Validated,
+        );
+        let summary = SpeechSummary {
+            speech,
+            sentence_count: 12,
+            preview: None,
+            position: StoragePosition {
+                transaction: 1,
+                speech: uid,
+            },
+        };
+        assert_eq!(
+            speech_line(&summary),
+            format!(
+                "{}\t2024-03-02 20:30\tVALIDATED\tTF1\t12 sentences\tDébat",
+                uid
+            )
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use crate::domain::{
+    job::JobProgress, organization::OrganizationManager, speech::manager::SpeechManager,
+};
+
+/// Time between two clusterings of the speeches by default, in seconds.
+pub const DEFAULT_SPEECH_CLUSTERING_INTERVAL: u64 = 60 * 60;
+
+/// Clusters the speeches of every organization, reporting the organizations done to the
+/// job when given. Returns the number of clusters found, the organizations failing being
+/// left out.
+pub(crate) async fn cluster_all_speeches(
+    speech_manager: &SpeechManager,
+    organization_manager: &OrganizationManager,
+    mut progress: Option<&mut JobProgress>,
+) -> usize {
+    let organizations = match organization_manager.get_organizations().await {
+        Ok(organizations) => organizations,
+        Err(e) => {
+            println!(
+                "An error occured while listing organizations to cluster speeches: {:?}",
+                e
+            );
+            return 0;
+        }
+    };
+    // The default organization comes first.
+    let total = organizations.len() as u64 + 1;
+    let organizations = std::iter::once(None).chain(
+        organizations
+            .iter()
+            .map(|organization| Some(*organization.uid())),
+    );
+    let mut clustered = 0;
+    for (done, organization) in organizations.enumerate() {
+        match speech_manager
+            .for_organization(organization)
+            .cluster_speeches()
+            .await
+        {
+            Ok(speeches) => clustered += speeches,
+            Err(e) => println!(
+                "An error occured while clustering the speeches of {:?}: {:?}",
+                organization, e
+            ),
+        }
+        if let Some(progress) = progress.as_deref_mut() {
+            progress.advance(done as u64 + 1, Some(total)).await;
+        }
+    }
+    clustered
+}
+
+/// Starts the background task clustering again the speeches every `interval` seconds, so
+/// the same stump speech delivered at several events is found. Nothing is started for a
+/// zero interval.
+pub fn start_speech_clustering(
+    speech_manager: SpeechManager,
+    organization_manager: OrganizationManager,
+    interval: u64,
+) {
+    if interval == 0 {
+        return;
+    }
+    tokio::spawn(async move {
+        loop {
+            cluster_all_speeches(&speech_manager, &organization_manager, None).await;
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+}
@@ -0,0 +1,955 @@
+mod profile;
+mod source;
+
+pub use profile::Profile;
+pub use source::ConfigSource;
+
+use std::{net::SocketAddr, str::FromStr};
+
+use hyper::{
+    header::{HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE, IF_MATCH},
+    Method,
+};
+
+use super::{
+    api::{
+        cache::CachePolicies,
+        deadline::RequestDeadlines,
+        http_log::{HttpLogConfig, HttpLogLevel},
+        load_shed::ConcurrencyLimits,
+        router::{DEFAULT_MAX_BODY_SIZE, DEFAULT_SERVER_ADDRESS},
+        token::Permissions,
+    },
+    clustering::DEFAULT_SPEECH_CLUSTERING_INTERVAL,
+    maintenance::DEFAULT_BACKUP_INTERVAL,
+    outbox::{DEFAULT_OUTBOX_RELAY_INTERVAL, DEFAULT_OUTBOX_RETENTION},
+};
+use crate::{
+    domain::{
+        attachment::{DEFAULT_ATTACHMENT_MAX_SIZE, DEFAULT_ATTACHMENT_URL_EXPIRY},
+        idempotency::DEFAULT_IDEMPOTENCY_KEY_TTL,
+    },
+    infrastructure::timeouts::DatabaseTimeouts,
+};
+
+/// Settings read at startup from the environment, the `.env` file and the optional TOML
+/// file of `CONFIG_FILE`, see `ConfigSource`.
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub profile: Profile,
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub auth: AuthConfig,
+    pub cors: CorsConfig,
+    /// `Cache-Control` values sent by the cached routes.
+    pub cache: CachePolicies,
+    /// Machine translation provider, translations are disabled when missing.
+    pub translation: Option<TranslationConfig>,
+    /// Screening of the sentences for personal data, disabled when missing.
+    pub pii_detection: Option<PiiDetectionConfig>,
+    /// Annotator of the sentences, the speeches are not annotated when missing.
+    pub annotation: Option<AnnotationConfig>,
+    /// Object storage of the speech attachments, attachments are disabled when missing.
+    pub attachment_storage: Option<AttachmentStorageConfig>,
+    /// Broker the domain events are published to, no event is written when missing.
+    pub event_publishing: Option<EventPublishingConfig>,
+    /// Time between two clusterings of the speeches, in seconds, zero disabling them.
+    pub speech_clustering_interval: u64,
+    /// Backups of the database, disabled when missing.
+    pub backup: Option<BackupConfig>,
+}
+
+/// Limits and logs of the HTTP server.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub address: SocketAddr,
+    /// Maximum size of a request body, in bytes.
+    pub max_body_size: usize,
+    /// Time the requests of each load class may take before a 504 is sent.
+    pub request_deadlines: RequestDeadlines,
+    /// Requests of each load class served at the same time.
+    pub concurrency_limits: ConcurrencyLimits,
+    /// Time during which an idempotency key is remembered, in seconds.
+    pub idempotency_key_ttl: u64,
+    /// Audit log of the requests, e.g. with the bodies in a staging environment only.
+    pub http_log: HttpLogConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatabaseConfig {
+    pub url: String,
+    /// Store of the persons and the speeches.
+    pub backend: DatabaseBackend,
+    /// Timeouts applied to the database operations of each class.
+    pub timeouts: DatabaseTimeouts,
+}
+
+/// Authentication of the requests by the Keycloak tokens.
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    pub keycloak_certs_url: String,
+    /// Redis sharing the Keycloak keys between the instances, only available with the
+    /// `redis` feature. Each instance fetches its own keys when missing.
+    pub keycloak_keys_redis_url: Option<String>,
+    /// Whether the validated speeches and the persons may be read without a token.
+    pub public_read_enabled: bool,
+    /// Permissions granted to the requests sent without a token in the public read mode,
+    /// none by default.
+    pub anonymous_permissions: Vec<Permissions>,
+}
+
+/// Database storing the persons and the speeches. The other entities are always stored
+/// in the Postgres database of `DATABASE_URL`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DatabaseBackend {
+    Postgres,
+    /// A MongoDB (or DocumentDB) database, only available with the `mongo` feature.
+    #[cfg(feature = "mongo")]
+    Mongo {
+        url: String,
+        database: String,
+    },
+}
+
+impl DatabaseBackend {
+    fn load(source: &ConfigSource) -> Result<Self, String> {
+        let backend = source
+            .get("DATABASE_BACKEND")
+            .unwrap_or("postgres".to_string());
+        match backend.to_lowercase().as_str() {
+            "postgres" => Ok(Self::Postgres),
+            #[cfg(feature = "mongo")]
+            "mongo" => Ok(Self::Mongo {
+                url: source
+                    .get("MONGO_URL")
+                    .ok_or("MONGO_URL not found in the configuration".to_owned())?,
+                database: source
+                    .get("MONGO_DATABASE")
+                    .unwrap_or("speech_analytics".to_string()),
+            }),
+            #[cfg(not(feature = "mongo"))]
+            "mongo" => {
+                Err("DATABASE_BACKEND=mongo requires a build with the mongo feature".to_owned())
+            }
+            _ => Err("DATABASE_BACKEND must be one of postgres or mongo".to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TranslationProvider {
+    DeepL,
+    LibreTranslate,
+}
+
+#[derive(Debug, Clone)]
+pub struct TranslationConfig {
+    pub provider: TranslationProvider,
+    pub url: String,
+    pub api_key: Option<String>,
+    /// Timeout applied to every call to the provider, in milliseconds.
+    pub timeout: u64,
+}
+
+impl TranslationConfig {
+    fn load(source: &ConfigSource) -> Result<Option<Self>, String> {
+        let provider = match source.get("TRANSLATION_PROVIDER") {
+            Some(provider) => match provider.to_lowercase().as_str() {
+                "deepl" => TranslationProvider::DeepL,
+                "libretranslate" => TranslationProvider::LibreTranslate,
+                _ => {
+                    return Err(
+                        "TRANSLATION_PROVIDER must be one of deepl or libretranslate".to_owned(),
+                    )
+                }
+            },
+            None => return Ok(None),
+        };
+        let url = match source.get("TRANSLATION_URL") {
+            Some(url) => url,
+            None if provider == TranslationProvider::DeepL => {
+                "https://api-free.deepl.com".to_owned()
+            }
+            None => return Err("TRANSLATION_URL not found in the configuration".to_owned()),
+        };
+        let api_key = source.get("TRANSLATION_API_KEY");
+        if provider == TranslationProvider::DeepL && api_key.is_none() {
+            return Err("TRANSLATION_API_KEY is required by DeepL".to_owned());
+        }
+        let timeout = source
+            .get("TRANSLATION_TIMEOUT")
+            .unwrap_or("10000".to_string())
+            .parse()
+            .map_err(|_| "TRANSLATION_TIMEOUT must be an u64".to_owned())?;
+        Ok(Some(Self {
+            provider,
+            url,
+            api_key,
+            timeout,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PiiDetectorProvider {
+    /// Patterns matched by the server itself.
+    Regex,
+    /// External service called with the sentences, see `HttpPiiDetector`.
+    Http {
+        url: String,
+        api_key: Option<String>,
+        /// Timeout applied to every call to the service, in milliseconds.
+        timeout: u64,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct PiiDetectionConfig {
+    pub provider: PiiDetectorProvider,
+    /// Whether the flags of a speech must all be reviewed before it is validated.
+    pub review_required: bool,
+}
+
+impl PiiDetectionConfig {
+    fn load(source: &ConfigSource) -> Result<Option<Self>, String> {
+        let provider = match source
+            .get("PII_DETECTOR")
+            .unwrap_or("regex".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "none" => return Ok(None),
+            "regex" => PiiDetectorProvider::Regex,
+            "http" => PiiDetectorProvider::Http {
+                url: source
+                    .get("PII_DETECTOR_URL")
+                    .ok_or("PII_DETECTOR_URL not found in the configuration".to_owned())?,
+                api_key: source.get("PII_DETECTOR_API_KEY"),
+                timeout: source
+                    .get("PII_DETECTOR_TIMEOUT")
+                    .unwrap_or("10000".to_string())
+                    .parse()
+                    .map_err(|_| "PII_DETECTOR_TIMEOUT must be an u64".to_owned())?,
+            },
+            _ => return Err("PII_DETECTOR must be one of regex, http or none".to_owned()),
+        };
+        let review_required = match source.get("PII_REVIEW_REQUIRED") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "PII_REVIEW_REQUIRED must be true or false".to_owned())?,
+            None => false,
+        };
+        Ok(Some(Self {
+            provider,
+            review_required,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotatorProvider {
+    /// Language detection and sentiment lexicon run by the server itself.
+    Lexicon,
+    /// External service called with the sentences, see `HttpAnnotator`.
+    Http {
+        url: String,
+        api_key: Option<String>,
+        /// Timeout applied to every call to the service, in milliseconds.
+        timeout: u64,
+    },
+}
+
+/// Annotation of the language and the sentiment of the sentences, run in the background
+/// once a speech is created or its sentences edited.
+#[derive(Debug, Clone)]
+pub struct AnnotationConfig {
+    pub provider: AnnotatorProvider,
+}
+
+impl AnnotationConfig {
+    fn load(source: &ConfigSource) -> Result<Option<Self>, String> {
+        let provider = match source
+            .get("ANNOTATOR")
+            .unwrap_or("lexicon".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "none" => return Ok(None),
+            "lexicon" => AnnotatorProvider::Lexicon,
+            "http" => AnnotatorProvider::Http {
+                url: source
+                    .get("ANNOTATOR_URL")
+                    .ok_or("ANNOTATOR_URL not found in the configuration".to_owned())?,
+                api_key: source.get("ANNOTATOR_API_KEY"),
+                timeout: source
+                    .get("ANNOTATOR_TIMEOUT")
+                    .unwrap_or("10000".to_string())
+                    .parse()
+                    .map_err(|_| "ANNOTATOR_TIMEOUT must be an u64".to_owned())?,
+            },
+            _ => return Err("ANNOTATOR must be one of lexicon, http or none".to_owned()),
+        };
+        Ok(Some(Self { provider }))
+    }
+}
+
+/// S3 compatible object storage (AWS S3, MinIO...) keeping the content of the
+/// attachments.
+#[derive(Debug, Clone)]
+pub struct AttachmentStorageConfig {
+    pub url: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key: String,
+    pub secret_key: String,
+    /// Timeout applied to every call to the storage, in milliseconds.
+    pub timeout: u64,
+    /// Largest attachment accepted, in bytes.
+    pub max_size: u64,
+    /// Time during which a download URL is valid, in seconds.
+    pub url_expiry: u64,
+}
+
+impl AttachmentStorageConfig {
+    fn load(source: &ConfigSource) -> Result<Option<Self>, String> {
+        let url = match source.get("ATTACHMENT_STORAGE_URL") {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+        let bucket = source
+            .get("ATTACHMENT_STORAGE_BUCKET")
+            .ok_or("ATTACHMENT_STORAGE_BUCKET not found in the configuration".to_owned())?;
+        let access_key = source
+            .get("ATTACHMENT_STORAGE_ACCESS_KEY")
+            .ok_or("ATTACHMENT_STORAGE_ACCESS_KEY not found in the configuration".to_owned())?;
+        let secret_key = source
+            .get("ATTACHMENT_STORAGE_SECRET_KEY")
+            .ok_or("ATTACHMENT_STORAGE_SECRET_KEY not found in the configuration".to_owned())?;
+        let region = source
+            .get("ATTACHMENT_STORAGE_REGION")
+            .unwrap_or("us-east-1".to_string());
+        let timeout = read_milliseconds(source, "ATTACHMENT_STORAGE_TIMEOUT", 30000)?;
+        let max_size = match source.get("ATTACHMENT_MAX_SIZE") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "ATTACHMENT_MAX_SIZE must be a number of bytes".to_owned())?,
+            None => DEFAULT_ATTACHMENT_MAX_SIZE,
+        };
+        let url_expiry = match source.get("ATTACHMENT_URL_EXPIRY") {
+            // S3 refuses the URLs valid for more than 7 days.
+            Some(v) => match v.parse() {
+                Ok(expiry) if expiry > 0 && expiry <= 7 * 24 * 3600 => expiry,
+                _ => {
+                    return Err(
+                        "ATTACHMENT_URL_EXPIRY must be a number of seconds, of at most 7 days"
+                            .to_owned(),
+                    )
+                }
+            },
+            None => DEFAULT_ATTACHMENT_URL_EXPIRY,
+        };
+        Ok(Some(Self {
+            url,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+            timeout,
+            max_size,
+            url_expiry,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackupFormat {
+    /// JSON lines written by the API itself, one row by line.
+    JsonLines,
+    /// Custom format of `pg_dump`, the program must be installed.
+    PgDump,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BackupDestination {
+    /// Directory of the server the backups are copied to.
+    Local { directory: String },
+    /// S3 compatible object storage, the backups being written under the prefix.
+    S3 {
+        url: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+        /// Prefix of the keys of the backups, e.g. `backups/`.
+        prefix: String,
+        /// Timeout applied to every call to the storage, in milliseconds.
+        timeout: u64,
+    },
+}
+
+/// Logical exports of the Postgres database, made on a schedule and on demand.
+#[derive(Debug, Clone)]
+pub struct BackupConfig {
+    pub format: BackupFormat,
+    pub destination: BackupDestination,
+    /// Time between two scheduled backups, in seconds, zero only allowing the backups on
+    /// demand.
+    pub interval: u64,
+}
+
+impl BackupConfig {
+    fn load(source: &ConfigSource) -> Result<Option<Self>, String> {
+        let destination = match (source.get("BACKUP_DIR"), source.get("BACKUP_S3_URL")) {
+            (Some(_), Some(_)) => {
+                return Err("Only one of BACKUP_DIR or BACKUP_S3_URL can be set".to_owned())
+            }
+            (Some(directory), None) => BackupDestination::Local { directory },
+            (None, Some(url)) => BackupDestination::S3 {
+                url,
+                bucket: source
+                    .get("BACKUP_S3_BUCKET")
+                    .ok_or("BACKUP_S3_BUCKET not found in the configuration".to_owned())?,
+                region: source
+                    .get("BACKUP_S3_REGION")
+                    .unwrap_or("us-east-1".to_string()),
+                access_key: source
+                    .get("BACKUP_S3_ACCESS_KEY")
+                    .ok_or("BACKUP_S3_ACCESS_KEY not found in the configuration".to_owned())?,
+                secret_key: source
+                    .get("BACKUP_S3_SECRET_KEY")
+                    .ok_or("BACKUP_S3_SECRET_KEY not found in the configuration".to_owned())?,
+                prefix: source
+                    .get("BACKUP_S3_PREFIX")
+                    .unwrap_or("backups/".to_string()),
+                timeout: read_milliseconds(source, "BACKUP_S3_TIMEOUT", 300000)?,
+            },
+            (None, None) => return Ok(None),
+        };
+        let format = match source
+            .get("BACKUP_FORMAT")
+            .unwrap_or("jsonl".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "jsonl" => BackupFormat::JsonLines,
+            "pg_dump" => BackupFormat::PgDump,
+            _ => return Err("BACKUP_FORMAT must be one of jsonl or pg_dump".to_owned()),
+        };
+        let interval = match source.get("BACKUP_INTERVAL") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "BACKUP_INTERVAL must be a number of seconds".to_owned())?,
+            None => DEFAULT_BACKUP_INTERVAL,
+        };
+        Ok(Some(Self {
+            format,
+            destination,
+            interval,
+        }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventBroker {
+    /// Kafka brokers, only available with the `kafka` feature.
+    Kafka {
+        /// Comma separated `host:port` of the bootstrap brokers.
+        brokers: String,
+        /// Time an event may wait to be sent, in milliseconds.
+        timeout: u64,
+    },
+    /// NATS server whose JetStream stores the events, only available with the `nats`
+    /// feature.
+    Nats { url: String },
+}
+
+/// Publication of the domain events of the persons and the speeches, written in an outbox
+/// by the Postgres repositories and relayed to the broker.
+#[derive(Debug, Clone)]
+pub struct EventPublishingConfig {
+    pub broker: EventBroker,
+    /// Prefix of the Kafka topics or the NATS subjects the events are published on.
+    pub topic_prefix: String,
+    /// Time between two relays of the outbox when it is empty, in milliseconds.
+    pub relay_interval: u64,
+    /// Time the published events are kept in the outbox, in seconds.
+    pub retention: u64,
+}
+
+impl EventPublishingConfig {
+    fn load(source: &ConfigSource) -> Result<Option<Self>, String> {
+        let broker = match source
+            .get("EVENT_BROKER")
+            .unwrap_or("none".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "none" => return Ok(None),
+            "kafka" => EventBroker::Kafka {
+                brokers: source
+                    .get("KAFKA_BROKERS")
+                    .ok_or("KAFKA_BROKERS not found in the configuration".to_owned())?,
+                timeout: read_milliseconds(source, "KAFKA_TIMEOUT", 10000)?,
+            },
+            "nats" => EventBroker::Nats {
+                url: source
+                    .get("NATS_URL")
+                    .ok_or("NATS_URL not found in the configuration".to_owned())?,
+            },
+            _ => return Err("EVENT_BROKER must be one of kafka, nats or none".to_owned()),
+        };
+        #[cfg(not(feature = "kafka"))]
+        if matches!(broker, EventBroker::Kafka { .. }) {
+            return Err("EVENT_BROKER=kafka requires a build with the kafka feature".to_owned());
+        }
+        #[cfg(not(feature = "nats"))]
+        if matches!(broker, EventBroker::Nats { .. }) {
+            return Err("EVENT_BROKER=nats requires a build with the nats feature".to_owned());
+        }
+        let topic_prefix = source
+            .get("EVENT_TOPIC_PREFIX")
+            .unwrap_or("speech_analytics".to_string());
+        let relay_interval = read_milliseconds(
+            source,
+            "OUTBOX_RELAY_INTERVAL",
+            DEFAULT_OUTBOX_RELAY_INTERVAL,
+        )?;
+        let retention = match source.get("OUTBOX_RETENTION") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "OUTBOX_RETENTION must be a number of seconds".to_owned())?,
+            None => DEFAULT_OUTBOX_RETENTION,
+        };
+        Ok(Some(Self {
+            broker,
+            topic_prefix,
+            relay_interval,
+            retention,
+        }))
+    }
+}
+
+impl HttpLogConfig {
+    fn load(source: &ConfigSource) -> Result<Self, String> {
+        let default = Self::default();
+        let level = match source
+            .get("HTTP_LOG")
+            .unwrap_or("off".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "off" => HttpLogLevel::Off,
+            "requests" => HttpLogLevel::Requests,
+            "bodies" => HttpLogLevel::Bodies,
+            _ => return Err("HTTP_LOG must be one of off, requests or bodies".to_owned()),
+        };
+        let max_body_length = match source.get("HTTP_LOG_MAX_BODY_LENGTH") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "HTTP_LOG_MAX_BODY_LENGTH must be a number of bytes".to_owned())?,
+            None => default.max_body_length,
+        };
+        Ok(Self {
+            level,
+            max_body_length,
+        })
+    }
+}
+
+/// Cross-origin requests accepted from the browsers.
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API, any origin when `None`.
+    pub allowed_origins: Option<Vec<HeaderValue>>,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    /// Time during which a browser may reuse a preflight response, in seconds.
+    pub max_age: Option<u64>,
+    /// Whether the browsers may send their cookies and credentials, only possible with
+    /// explicit origins.
+    pub allow_credentials: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: None,
+            allowed_methods: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::POST,
+                Method::PUT,
+                Method::PATCH,
+                Method::DELETE,
+                Method::OPTIONS,
+            ],
+            allowed_headers: vec![
+                CONTENT_TYPE,
+                AUTHORIZATION,
+                IF_MATCH,
+                HeaderName::from_static("idempotency-key"),
+                HeaderName::from_static("last-event-id"),
+                HeaderName::from_static("if-collection-version"),
+            ],
+            max_age: None,
+            allow_credentials: false,
+        }
+    }
+}
+
+/// Reads the permissions granted to the anonymous requests, either a comma separated list
+/// (`GetSpeech,GetPerson`) or a JSON array (`["GetSpeech", "GetPerson"]`). Only the read
+/// permissions may be granted, the public routes being reads.
+fn read_anonymous_permissions(source: &ConfigSource) -> Result<Vec<Permissions>, String> {
+    let invalid = "ANONYMOUS_PERMISSIONS must only contain GetSpeech and GetPerson";
+    let permissions = match source.get("ANONYMOUS_PERMISSIONS") {
+        Some(v) if v.trim_start().starts_with('[') => {
+            serde_json::from_str::<Vec<Permissions>>(&v).map_err(|_| invalid.to_owned())?
+        }
+        Some(_) => read_list(source, "ANONYMOUS_PERMISSIONS")
+            .unwrap_or_default()
+            .iter()
+            .map(|permission| Permissions::from_str(permission))
+            .collect::<Result<Vec<Permissions>, String>>()
+            .map_err(|_| invalid.to_owned())?,
+        None => Vec::new(),
+    };
+    if permissions
+        .iter()
+        .any(|p| !matches!(p, Permissions::GetSpeech | Permissions::GetPerson))
+    {
+        return Err(invalid.to_owned());
+    }
+    Ok(permissions)
+}
+
+/// Splits a comma separated list read from the configuration, the blank items being ignored.
+fn read_list(source: &ConfigSource, name: &str) -> Option<Vec<String>> {
+    source.get(name).map(|v| {
+        v.split(',')
+            .map(|item| item.trim().to_owned())
+            .filter(|item| !item.is_empty())
+            .collect()
+    })
+}
+
+impl CorsConfig {
+    fn load(source: &ConfigSource) -> Result<Self, String> {
+        let default = Self::default();
+        let allowed_origins = match read_list(source, "CORS_ALLOWED_ORIGINS") {
+            Some(origins) if !origins.iter().any(|origin| origin == "*") => Some(
+                origins
+                    .iter()
+                    .map(|origin| HeaderValue::from_str(origin))
+                    .collect::<Result<Vec<HeaderValue>, _>>()
+                    .map_err(|_| "CORS_ALLOWED_ORIGINS must be a list of origins".to_owned())?,
+            ),
+            _ => None,
+        };
+        let allowed_methods = match read_list(source, "CORS_ALLOWED_METHODS") {
+            Some(methods) => methods
+                .iter()
+                .map(|method| Method::from_str(&method.to_uppercase()))
+                .collect::<Result<Vec<Method>, _>>()
+                .map_err(|_| "CORS_ALLOWED_METHODS must be a list of HTTP methods".to_owned())?,
+            None => default.allowed_methods,
+        };
+        let allowed_headers = match read_list(source, "CORS_ALLOWED_HEADERS") {
+            Some(headers) => headers
+                .iter()
+                .map(|header| HeaderName::from_str(header))
+                .collect::<Result<Vec<HeaderName>, _>>()
+                .map_err(|_| "CORS_ALLOWED_HEADERS must be a list of header names".to_owned())?,
+            None => default.allowed_headers,
+        };
+        let max_age = match source.get("CORS_MAX_AGE") {
+            Some(v) => Some(
+                v.parse()
+                    .map_err(|_| "CORS_MAX_AGE must be a number of seconds".to_owned())?,
+            ),
+            None => None,
+        };
+        let allow_credentials = match source.get("CORS_ALLOW_CREDENTIALS") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "CORS_ALLOW_CREDENTIALS must be true or false".to_owned())?,
+            None => false,
+        };
+        if allow_credentials && allowed_origins.is_none() {
+            return Err("CORS_ALLOW_CREDENTIALS requires explicit CORS_ALLOWED_ORIGINS".to_owned());
+        }
+        Ok(Self {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers,
+            max_age,
+            allow_credentials,
+        })
+    }
+}
+
+/// Reads a `Cache-Control` value from the configuration, `default` being used when missing.
+fn read_cache_policy(
+    source: &ConfigSource,
+    name: &str,
+    default: HeaderValue,
+) -> Result<HeaderValue, String> {
+    match source.get(name) {
+        Some(v) => {
+            HeaderValue::from_str(&v).map_err(|_| format!("{} must be a valid header value", name))
+        }
+        None => Ok(default),
+    }
+}
+
+/// Reads a concurrency limit from the configuration, `default` being used when missing.
+fn read_concurrency_limit(
+    source: &ConfigSource,
+    name: &str,
+    default: usize,
+) -> Result<usize, String> {
+    match source.get(name) {
+        Some(v) => match v.parse() {
+            Ok(limit) if limit > 0 => Ok(limit),
+            _ => Err(format!("{} must be a positive number of requests", name)),
+        },
+        None => Ok(default),
+    }
+}
+
+/// Reads a number of milliseconds from the configuration, `default` being used when missing.
+fn read_milliseconds(source: &ConfigSource, name: &str, default: u64) -> Result<u64, String> {
+    match source.get(name) {
+        Some(v) => v
+            .parse()
+            .map_err(|_| format!("{} must be a number of milliseconds", name)),
+        None => Ok(default),
+    }
+}
+
+impl ServerConfig {
+    fn load(source: &ConfigSource) -> Result<Self, String> {
+        let address = match source.get("SERVER_ADDRESS") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "SERVER_ADDRESS must be an address such as 0.0.0.0:3000".to_owned())?,
+            None => SocketAddr::from(DEFAULT_SERVER_ADDRESS),
+        };
+        let max_body_size = match source.get("MAX_BODY_SIZE") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "MAX_BODY_SIZE must be a number of bytes".to_owned())?,
+            None => DEFAULT_MAX_BODY_SIZE,
+        };
+        let defaults = RequestDeadlines::default();
+        let request_deadlines = RequestDeadlines {
+            interactive: read_milliseconds(
+                source,
+                "REQUEST_DEADLINE_INTERACTIVE",
+                defaults.interactive,
+            )?,
+            analytics: read_milliseconds(source, "REQUEST_DEADLINE_ANALYTICS", defaults.analytics)?,
+            export: read_milliseconds(source, "REQUEST_DEADLINE_EXPORT", defaults.export)?,
+        };
+        let defaults = ConcurrencyLimits::default();
+        let concurrency_limits = ConcurrencyLimits {
+            interactive: read_concurrency_limit(
+                source,
+                "CONCURRENCY_LIMIT_INTERACTIVE",
+                defaults.interactive,
+            )?,
+            analytics: read_concurrency_limit(
+                source,
+                "CONCURRENCY_LIMIT_ANALYTICS",
+                defaults.analytics,
+            )?,
+            export: read_concurrency_limit(source, "CONCURRENCY_LIMIT_EXPORT", defaults.export)?,
+        };
+        let idempotency_key_ttl = match source.get("IDEMPOTENCY_KEY_TTL") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "IDEMPOTENCY_KEY_TTL must be a number of seconds".to_owned())?,
+            None => DEFAULT_IDEMPOTENCY_KEY_TTL,
+        };
+        Ok(Self {
+            address,
+            max_body_size,
+            request_deadlines,
+            concurrency_limits,
+            idempotency_key_ttl,
+            http_log: HttpLogConfig::load(source)?,
+        })
+    }
+}
+
+impl DatabaseConfig {
+    fn load(source: &ConfigSource) -> Result<Self, String> {
+        let url = source
+            .get("DATABASE_URL")
+            .ok_or("DATABASE_URL not found in the configuration".to_owned())?;
+        // DATABASE_TIMEOUT sets both the read and the write timeouts, each being
+        // overridable.
+        let defaults = DatabaseTimeouts::default();
+        let database_timeout = read_milliseconds(source, "DATABASE_TIMEOUT", defaults.read)?;
+        let timeouts = DatabaseTimeouts {
+            read: read_milliseconds(source, "DATABASE_READ_TIMEOUT", database_timeout)?,
+            write: read_milliseconds(source, "DATABASE_WRITE_TIMEOUT", database_timeout)?,
+            migration: read_milliseconds(source, "DATABASE_MIGRATION_TIMEOUT", defaults.migration)?,
+        };
+        Ok(Self {
+            url,
+            backend: DatabaseBackend::load(source)?,
+            timeouts,
+        })
+    }
+}
+
+impl AuthConfig {
+    fn load(source: &ConfigSource) -> Result<Self, String> {
+        let keycloak_certs_url = source
+            .get("KEYCLOAK_CERTS_URL")
+            .ok_or("KEYCLOAK_CERTS_URL not found in the configuration".to_owned())?;
+        let keycloak_keys_redis_url = source.get("KEYCLOAK_KEYS_REDIS_URL");
+        #[cfg(not(feature = "redis"))]
+        if keycloak_keys_redis_url.is_some() {
+            return Err(
+                "KEYCLOAK_KEYS_REDIS_URL requires a build with the redis feature".to_owned(),
+            );
+        }
+        let public_read_enabled = match source.get("PUBLIC_READ_ENABLED") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "PUBLIC_READ_ENABLED must be true or false".to_owned())?,
+            None => false,
+        };
+        let anonymous_permissions = read_anonymous_permissions(source)?;
+        if !anonymous_permissions.is_empty() && !public_read_enabled {
+            return Err("ANONYMOUS_PERMISSIONS requires PUBLIC_READ_ENABLED=true".to_owned());
+        }
+        Ok(Self {
+            keycloak_certs_url,
+            keycloak_keys_redis_url,
+            public_read_enabled,
+            anonymous_permissions,
+        })
+    }
+}
+
+impl AppConfig {
+    /// Reads the configuration of the server, see `ConfigSource` for where it is read.
+    pub fn from_env() -> Result<Self, String> {
+        Self::load(&ConfigSource::from_env()?)
+    }
+
+    pub fn load(source: &ConfigSource) -> Result<Self, String> {
+        let profile = match source.get("APP_PROFILE") {
+            Some(v) => v.parse()?,
+            None => Profile::default(),
+        };
+        let defaults = CachePolicies::default();
+        let cache = CachePolicies {
+            immutable: read_cache_policy(source, "CACHE_CONTROL_IMMUTABLE", defaults.immutable)?,
+            listing: read_cache_policy(source, "CACHE_CONTROL_LISTING", defaults.listing)?,
+            admin: read_cache_policy(source, "CACHE_CONTROL_ADMIN", defaults.admin)?,
+        };
+        let speech_clustering_interval = match source.get("SPEECH_CLUSTERING_INTERVAL") {
+            Some(v) => v
+                .parse()
+                .map_err(|_| "SPEECH_CLUSTERING_INTERVAL must be a number of seconds".to_owned())?,
+            None => DEFAULT_SPEECH_CLUSTERING_INTERVAL,
+        };
+        let config = Self {
+            profile,
+            server: ServerConfig::load(source)?,
+            database: DatabaseConfig::load(source)?,
+            auth: AuthConfig::load(source)?,
+            cors: CorsConfig::load(source)?,
+            cache,
+            translation: TranslationConfig::load(source)?,
+            pii_detection: PiiDetectionConfig::load(source)?,
+            annotation: AnnotationConfig::load(source)?,
+            attachment_storage: AttachmentStorageConfig::load(source)?,
+            event_publishing: EventPublishingConfig::load(source)?,
+            speech_clustering_interval,
+            backup: BackupConfig::load(source)?,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the constraints between the sections, and the stricter ones of production.
+    fn validate(&self) -> Result<(), String> {
+        // The events are written in the transactions of the changes, in Postgres.
+        if self.event_publishing.is_some() && self.database.backend != DatabaseBackend::Postgres {
+            return Err("EVENT_BROKER requires DATABASE_BACKEND=postgres".to_owned());
+        }
+        // The backups export the tables of Postgres.
+        if self.backup.is_some() && self.database.backend != DatabaseBackend::Postgres {
+            return Err(
+                "BACKUP_DIR and BACKUP_S3_URL require DATABASE_BACKEND=postgres".to_owned(),
+            );
+        }
+        if self.profile != Profile::Prod {
+            return Ok(());
+        }
+        if self.cors.allowed_origins.is_none() {
+            return Err("The prod profile requires explicit CORS_ALLOWED_ORIGINS".to_owned());
+        }
+        // The bodies hold the transcripts and the personal data of the persons.
+        if self.server.http_log.level == HttpLogLevel::Bodies {
+            return Err("The prod profile does not allow HTTP_LOG=bodies".to_owned());
+        }
+        if !self.auth.keycloak_certs_url.starts_with("https://") {
+            return Err("The prod profile requires an https KEYCLOAK_CERTS_URL".to_owned());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_anonymous_permissions, AppConfig, ConfigSource, Profile};
+    use crate::application::api::token::Permissions;
+
+    const CONFIG: &str = r#"
+        [database]
+        url = "postgres://localhost/speech_analytics"
+        [keycloak]
+        certs_url = "https://keycloak.example/certs"
+    "#;
+
+    #[test]
+    fn the_prod_profile_refuses_the_development_settings() {
+        let config = AppConfig::load(&ConfigSource::from_toml(CONFIG).unwrap()).unwrap();
+        assert_eq!(config.profile, Profile::Dev);
+        assert_eq!(config.database.url, "postgres://localhost/speech_analytics");
+        let prod = format!("app_profile = \"prod\"\n{}", CONFIG);
+        assert!(AppConfig::load(&ConfigSource::from_toml(&prod).unwrap()).is_err());
+        let prod = format!(
+            "app_profile = \"prod\"\n{}\n[cors]\nallowed_origins = [\"https://app.example\"]",
+            CONFIG
+        );
+        let config = AppConfig::load(&ConfigSource::from_toml(&prod).unwrap()).unwrap();
+        assert_eq!(config.profile, Profile::Prod);
+    }
+
+    #[test]
+    fn the_anonymous_permissions_are_read_as_a_list_or_as_json() {
+        let read = |value: &str| {
+            let source =
+                ConfigSource::from_toml(&format!("anonymous_permissions = '{}'", value)).unwrap();
+            read_anonymous_permissions(&source)
+        };
+        assert_eq!(
+            read("GetSpeech, GetPerson"),
+            Ok(vec![Permissions::GetSpeech, Permissions::GetPerson])
+        );
+        assert_eq!(read(r#"["GetPerson"]"#), Ok(vec![Permissions::GetPerson]));
+        assert!(read("GetSpeech,UpdateSpeech").is_err());
+        assert!(read(r#"["Admin"]"#).is_err());
+        assert_eq!(
+            read_anonymous_permissions(&ConfigSource::default()),
+            Ok(Vec::new())
+        );
+    }
+}
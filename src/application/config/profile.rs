@@ -0,0 +1,31 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Environment the server is deployed in, set by `APP_PROFILE`. The `prod` profile refuses
+/// the settings only acceptable on a development machine.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Profile {
+    #[default]
+    Dev,
+    Prod,
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_lowercase().as_str() {
+            "dev" => Ok(Self::Dev),
+            "prod" => Ok(Self::Prod),
+            _ => Err("APP_PROFILE must be one of dev or prod".to_owned()),
+        }
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Profile::Dev => f.write_str("dev"),
+            Profile::Prod => f.write_str("prod"),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+/// Values the configuration is read from: the environment (or the `.env` file) first,
+/// then the optional TOML file whose path is given by `CONFIG_FILE`.
+///
+/// The tables of the file prefix the names of their keys, `url` in `[database]` being read
+/// as `DATABASE_URL`, and an array is read as a comma separated list.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSource {
+    file: HashMap<String, String>,
+    /// Whether the environment is read before the file.
+    env: bool,
+}
+
+impl ConfigSource {
+    pub fn from_env() -> Result<Self, String> {
+        let file = match std::env::var("CONFIG_FILE") {
+            Ok(path) => {
+                let content = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Cannot read the CONFIG_FILE {}: {}", path, e))?;
+                Self::from_toml(&content)?.file
+            }
+            Err(_) => HashMap::new(),
+        };
+        Ok(Self { file, env: true })
+    }
+
+    /// Values of a TOML file alone, the environment being ignored.
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table = content
+            .parse::<toml::Table>()
+            .map_err(|e| format!("Invalid configuration file: {}", e))?;
+        let mut file = HashMap::new();
+        flatten("", &table, &mut file)?;
+        Ok(Self { file, env: false })
+    }
+
+    pub fn get(&self, name: &str) -> Option<String> {
+        if self.env {
+            if let Ok(value) = std::env::var(name) {
+                return Some(value);
+            }
+        }
+        self.file.get(name).cloned()
+    }
+}
+
+/// Names the values of the table after the environment variables, e.g. `DATABASE_URL`.
+fn flatten(
+    prefix: &str,
+    table: &toml::Table,
+    values: &mut HashMap<String, String>,
+) -> Result<(), String> {
+    for (key, value) in table {
+        let name = match prefix {
+            "" => key.to_uppercase(),
+            prefix => format!("{}_{}", prefix, key.to_uppercase()),
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&name, table, values)?,
+            toml::Value::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(|item| match item {
+                        toml::Value::String(item) => Ok(item.clone()),
+                        toml::Value::Array(_) | toml::Value::Table(_) => {
+                            Err(format!("{} must be a list of values", name))
+                        }
+                        item => Ok(item.to_string()),
+                    })
+                    .collect::<Result<Vec<String>, String>>()?;
+                values.insert(name, items.join(","));
+            }
+            toml::Value::String(value) => {
+                values.insert(name, value.clone());
+            }
+            value => {
+                values.insert(name, value.to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigSource;
+
+    #[test]
+    fn tables_prefix_the_names_of_their_keys() {
+        let source = ConfigSource::from_toml(
+            r#"
+            app_profile = "prod"
+            [database]
+            url = "postgres://localhost/speech_analytics"
+            read_timeout = 2000
+            [cors]
+            allowed_origins = ["https://a.example", "https://b.example"]
+            allow_credentials = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(source.get("APP_PROFILE").as_deref(), Some("prod"));
+        assert_eq!(
+            source.get("DATABASE_URL").as_deref(),
+            Some("postgres://localhost/speech_analytics")
+        );
+        assert_eq!(source.get("DATABASE_READ_TIMEOUT").as_deref(), Some("2000"));
+        assert_eq!(
+            source.get("CORS_ALLOWED_ORIGINS").as_deref(),
+            Some("https://a.example,https://b.example")
+        );
+        assert_eq!(
+            source.get("CORS_ALLOW_CREDENTIALS").as_deref(),
+            Some("true")
+        );
+        assert_eq!(source.get("CORS_MAX_AGE"), None);
+        assert!(ConfigSource::from_toml("[database\nurl = 1").is_err());
+    }
+}
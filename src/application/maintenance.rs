@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::domain::{
+    job::{Job, JobManager, JobProgress, JobRepositoryError},
+    maintenance::{MaintenanceAction, MaintenanceManager},
+    organization::OrganizationManager,
+    speech::manager::SpeechManager,
+};
+
+use super::clustering::cluster_all_speeches;
+
+/// Time between two backups of the database by default, in seconds.
+pub const DEFAULT_BACKUP_INTERVAL: u64 = 24 * 60 * 60;
+
+/// Runs the action, returning the number of rows deleted, clusters found or rows exported.
+async fn run_action(
+    action: MaintenanceAction,
+    maintenance_manager: &MaintenanceManager,
+    speech_manager: &SpeechManager,
+    organization_manager: &OrganizationManager,
+    progress: &mut JobProgress,
+) -> Result<u64, String> {
+    match action {
+        MaintenanceAction::ReindexSearch => maintenance_manager
+            .reindex_search()
+            .await
+            .map(|_| 0)
+            .map_err(|e| format!("{:?}", e)),
+        MaintenanceAction::RecomputeScores => {
+            let clusters =
+                cluster_all_speeches(speech_manager, organization_manager, Some(progress)).await;
+            Ok(clusters as u64)
+        }
+        MaintenanceAction::VacuumOrphans => maintenance_manager
+            .vacuum_orphans()
+            .await
+            .map_err(|e| format!("{:?}", e)),
+        MaintenanceAction::Backup => {
+            let report = maintenance_manager
+                .backup()
+                .await
+                .map_err(|e| format!("{:?}", e))?;
+            println!("Backed up {} rows to {}", report.rows, report.location);
+            Ok(report.rows)
+        }
+    }
+}
+
+/// Records the job of the action and runs it in a background task, returning the job
+/// still running. Its outcome is recorded once the action is done, the number of rows
+/// affected being its result.
+pub async fn start_maintenance(
+    action: MaintenanceAction,
+    job_manager: &JobManager,
+    maintenance_manager: MaintenanceManager,
+    speech_manager: SpeechManager,
+    organization_manager: OrganizationManager,
+) -> Result<Job, JobRepositoryError> {
+    let mut progress = job_manager.start_job(action.into()).await?;
+    let running = progress.job().clone();
+    tokio::spawn(async move {
+        let outcome = run_action(
+            action,
+            &maintenance_manager,
+            &speech_manager,
+            &organization_manager,
+            &mut progress,
+        )
+        .await;
+        let outcome = outcome.map(|affected| json!({ "affected": affected }));
+        if let Err(e) = progress.finish(outcome).await {
+            println!(
+                "An error occured while recording the outcome of a maintenance job: {:?}",
+                e
+            );
+        }
+    });
+    Ok(running)
+}
+
+/// Starts the background task backing up the database every `interval` seconds, the
+/// first backup one interval after the start. Each backup is recorded as a job of the
+/// default organization. Nothing is started for a zero interval or without a backup
+/// target.
+pub fn start_backups(
+    job_manager: JobManager,
+    maintenance_manager: MaintenanceManager,
+    speech_manager: SpeechManager,
+    organization_manager: OrganizationManager,
+    interval: u64,
+) {
+    if interval == 0 || !maintenance_manager.backups_enabled() {
+        return;
+    }
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+            if let Err(e) = start_maintenance(
+                MaintenanceAction::Backup,
+                &job_manager,
+                maintenance_manager.clone(),
+                speech_manager.clone(),
+                organization_manager.clone(),
+            )
+            .await
+            {
+                println!("An error occured while starting a backup: {:?}", e);
+            }
+        }
+    });
+}
@@ -1 +1,10 @@
+pub mod annotation;
 pub mod api;
+#[cfg(feature = "assemblee-nationale")]
+pub mod assemblee_nationale;
+pub mod cli;
+pub mod clustering;
+pub mod config;
+pub mod maintenance;
+pub mod outbox;
+pub mod seed;
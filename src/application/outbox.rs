@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::domain::outbox::OutboxManager;
+
+/// Time between two relays of the outbox by default, in milliseconds.
+pub const DEFAULT_OUTBOX_RELAY_INTERVAL: u64 = 1000;
+/// Time the published events are kept in the outbox by default, in seconds.
+pub const DEFAULT_OUTBOX_RETENTION: u64 = 7 * 24 * 60 * 60;
+/// Time between two deletions of the published events.
+const OUTBOX_PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Starts the background task publishing the events of the outbox to the broker. The
+/// outbox is relayed again right away while events are published, every `interval`
+/// milliseconds otherwise, and the events published more than `retention` seconds ago are
+/// deleted every hour.
+pub fn start_outbox_relay(outbox_manager: OutboxManager, interval: u64, retention: u64) {
+    tokio::spawn(async move {
+        let mut next_pruning = Instant::now();
+        loop {
+            let published = match outbox_manager.relay_events().await {
+                Ok(published) => published,
+                Err(e) => {
+                    println!(
+                        "An error occured while publishing the events of the outbox: {:?}",
+                        e
+                    );
+                    0
+                }
+            };
+            if Instant::now() >= next_pruning {
+                if let Err(e) = outbox_manager
+                    .prune_events(Duration::from_secs(retention))
+                    .await
+                {
+                    println!(
+                        "An error occured while deleting the published events of the outbox: {:?}",
+                        e
+                    );
+                }
+                next_pruning = Instant::now() + OUTBOX_PRUNING_INTERVAL;
+            }
+            if published == 0 {
+                tokio::time::sleep(Duration::from_millis(interval)).await;
+            }
+        }
+    });
+}
@@ -0,0 +1,297 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use uuid::Uuid;
+
+use crate::domain::{
+    person::{DeleteStrategy, Person, PersonManager, PersonRepositoryError},
+    speech::{
+        manager::SpeechManager,
+        sentence::{Sentence, SentenceTiming},
+        speech_repository::SpeechRepositoryError,
+        Speech, SpeechStatus,
+    },
+};
+
+/// Version of the generated datasets, to bump whenever the generated content changes so
+/// the trainers know which dataset a demo environment holds.
+pub const SEED_VERSION: u32 = 1;
+
+/// Average speaking pace of the generated sentences, in milliseconds per word.
+const MILLIS_PER_WORD: u32 = 400;
+
+const LAST_NAMES: &[&str] = &[
+    "Martin", "Bernard", "Dubois", "Thomas", "Robert", "Richard", "Petit", "Durand", "Leroy",
+    "Moreau", "Simon", "Laurent", "Lefebvre", "Michel", "Garcia", "David", "Bertrand", "Roux",
+    "Vincent", "Fournier",
+];
+const FIRST_NAMES: &[&str] = &[
+    "Camille", "Louis", "Léa", "Hugo", "Chloé", "Arthur", "Manon", "Jules", "Inès", "Paul",
+    "Sarah", "Lucas", "Emma", "Nathan", "Jeanne",
+];
+const MEDIAS: &[&str] = &[
+    "France 2",
+    "TF1",
+    "BFM TV",
+    "France Inter",
+    "LCP",
+    "Public Sénat",
+];
+const TOPICS: &[&str] = &[
+    "Pouvoir d'achat",
+    "Transition énergétique",
+    "Réforme des retraites",
+    "Éducation",
+    "Santé publique",
+    "Logement",
+    "Sécurité",
+    "Agriculture",
+];
+const SENTENCES: &[&str] = &[
+    "Je crois que les Français attendent des réponses concrètes.",
+    "Ce n'est pas ce que disent les chiffres de l'Insee.",
+    "Nous avons proposé un plan chiffré dès le mois de janvier.",
+    "Laissez-moi terminer, s'il vous plaît.",
+    "Vous avez voté contre cette mesure il y a deux ans.",
+    "La question est de savoir qui va payer.",
+    "Il faut investir massivement dans la formation.",
+    "Les collectivités locales n'ont pas les moyens de suivre.",
+    "C'est une proposition que nous assumons pleinement.",
+    "Votre programme n'est pas financé.",
+    "Les entreprises ont besoin de visibilité.",
+    "Nous devons protéger les plus fragiles.",
+    "Regardez ce qui se passe chez nos voisins européens.",
+    "Cette réforme a déjà fait ses preuves.",
+    "Je ne peux pas laisser dire une chose pareille.",
+    "Revenons au fond du sujet.",
+];
+
+/// Size of a demo dataset. The uids do not depend on the profile, so seeding another
+/// profile resets the environment in place.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeedProfile {
+    Small,
+    Medium,
+    Large,
+}
+
+impl FromStr for SeedProfile {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "small" => Ok(Self::Small),
+            "medium" => Ok(Self::Medium),
+            "large" => Ok(Self::Large),
+            _ => Err("The seed profile must be one of small, medium or large".to_owned()),
+        }
+    }
+}
+
+impl Display for SeedProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SeedProfile::Small => f.write_str("small"),
+            SeedProfile::Medium => f.write_str("medium"),
+            SeedProfile::Large => f.write_str("large"),
+        }
+    }
+}
+
+impl SeedProfile {
+    fn persons(&self) -> u64 {
+        match self {
+            SeedProfile::Small => 10,
+            SeedProfile::Medium => 50,
+            SeedProfile::Large => 200,
+        }
+    }
+
+    fn speeches(&self) -> u64 {
+        match self {
+            SeedProfile::Small => 5,
+            SeedProfile::Medium => 100,
+            SeedProfile::Large => 1000,
+        }
+    }
+
+    fn sentences_per_speech(&self) -> usize {
+        match self {
+            SeedProfile::Small => 20,
+            SeedProfile::Medium => 60,
+            SeedProfile::Large => 120,
+        }
+    }
+}
+
+/// Entities stored by a seeding.
+#[derive(Debug, Default)]
+pub struct SeedReport {
+    pub persons: u64,
+    pub speeches: u64,
+    /// Seeded entities of a larger profile deleted to match the requested one.
+    pub deleted: u64,
+}
+
+/// Uid of the seeded entity of kind `kind` at `index`, the same on every seeding so a
+/// demo environment can be reset as many times as needed.
+fn seeded_uid(kind: u16, index: u64) -> Uuid {
+    Uuid::from_fields(
+        0x5eed_0000,
+        kind,
+        0x4000,
+        &(0x8000_0000_0000_0000 | index).to_be_bytes(),
+    )
+}
+
+const PERSON_KIND: u16 = 1;
+const SPEECH_KIND: u16 = 2;
+const SENTENCE_KIND: u16 = 3;
+
+fn seeded_person(index: u64) -> Person {
+    let mut rng = StdRng::seed_from_u64(index);
+    let name = LAST_NAMES[index as usize % LAST_NAMES.len()];
+    let first_name = FIRST_NAMES[(index as usize / LAST_NAMES.len()) % FIRST_NAMES.len()];
+    // The day makes the identity unique past the name combinations.
+    let birth_date = NaiveDate::from_ymd_opt(1950, 1, 1).expect("Should not fail")
+        + Duration::days(index as i64 * 97);
+    Person::new(
+        seeded_uid(PERSON_KIND, index),
+        name,
+        first_name,
+        birth_date,
+        rng.gen_range(0..=100),
+        rng.gen_range(0..20),
+    )
+}
+
+fn seeded_speech(index: u64, profile: SeedProfile) -> Speech {
+    let mut rng = StdRng::seed_from_u64(u64::MAX - index);
+    let speaker_count = rng.gen_range(2..=4.min(profile.persons() as usize));
+    let speakers = (0..profile.persons())
+        .collect::<Vec<u64>>()
+        .choose_multiple(&mut rng, speaker_count)
+        .map(|p| seeded_uid(PERSON_KIND, *p))
+        .collect::<Vec<Uuid>>();
+    let mut elapsed = 0;
+    let sentences = (0..profile.sentences_per_speech())
+        .map(|position| {
+            let text = SENTENCES.choose(&mut rng).expect("Should not fail");
+            let speaker = speakers.choose(&mut rng).expect("Should not fail");
+            let duration = text.split_whitespace().count() as u32 * MILLIS_PER_WORD;
+            let timing = SentenceTiming {
+                start: elapsed,
+                end: elapsed + duration,
+            };
+            elapsed += duration;
+            Sentence::new(
+                &seeded_uid(SENTENCE_KIND, index << 16 | position as u64),
+                speaker,
+                text,
+                rng.gen_bool(0.1),
+            )
+            .with_timing(Some(timing))
+        })
+        .collect::<Vec<Sentence>>();
+    let topic = TOPICS[index as usize % TOPICS.len()];
+    let date = Utc
+        .with_ymd_and_hms(2024, 1, 1, 20, 0, 0)
+        .single()
+        .expect("Should not fail")
+        + Duration::days(index as i64);
+    // Some speeches are left to review during the training sessions.
+    let status = if index % 5 == 4 {
+        SpeechStatus::Pending
+    } else {
+        SpeechStatus::Validated
+    };
+    Speech::new(
+        &seeded_uid(SPEECH_KIND, index),
+        &format!("{} - débat n°{}", topic, index + 1),
+        date,
+        &speakers,
+        &sentences,
+        MEDIAS.choose(&mut rng).expect("Should not fail"),
+        status,
+    )
+}
+
+/// Stores the dataset of the profile in the default organization. The seeded persons and
+/// speeches are reset to their generated content, including when they have been edited or
+/// deleted since the last seeding, and the ones of a larger profile are deleted.
+pub async fn seed(
+    profile: SeedProfile,
+    person_manager: &PersonManager,
+    speech_manager: &SpeechManager,
+) -> Result<SeedReport, String> {
+    let mut report = SeedReport::default();
+    for index in 0..profile.persons() {
+        let person = seeded_person(index);
+        match person_manager.create_person(person).await {
+            Ok(()) => {}
+            // The seeded person may have been deleted or edited since.
+            Err(PersonRepositoryError::PersonAlreadyExists) => {
+                match person_manager
+                    .restore_person(&seeded_uid(PERSON_KIND, index))
+                    .await
+                {
+                    Ok(()) | Err(PersonRepositoryError::PersonNotFound) => {}
+                    Err(e) => return Err(format!("Cannot restore the person {}: {:?}", index, e)),
+                }
+                person_manager
+                    .update_person(seeded_person(index), None)
+                    .await
+                    .map_err(|e| format!("Cannot reset the person {}: {:?}", index, e))?;
+            }
+            Err(e) => return Err(format!("Cannot create the person {}: {:?}", index, e)),
+        }
+        report.persons += 1;
+    }
+    for index in 0..profile.speeches() {
+        let uid = seeded_uid(SPEECH_KIND, index);
+        match speech_manager
+            .create_speech(seeded_speech(index, profile))
+            .await
+        {
+            Ok(()) => {}
+            Err(SpeechRepositoryError::SpeechAlreadyExists) => {
+                match speech_manager.restore_speech(uid).await {
+                    Ok(()) | Err(SpeechRepositoryError::SpeechNotFound) => {}
+                    Err(e) => return Err(format!("Cannot restore the speech {}: {:?}", index, e)),
+                }
+                // The former content is kept as a revision.
+                speech_manager
+                    .update_speech(seeded_speech(index, profile), None)
+                    .await
+                    .map_err(|e| format!("Cannot reset the speech {}: {:?}", index, e))?;
+            }
+            Err(e) => return Err(format!("Cannot create the speech {}: {:?}", index, e)),
+        }
+        report.speeches += 1;
+    }
+    for index in profile.speeches()..SeedProfile::Large.speeches() {
+        match speech_manager
+            .delete_speech(seeded_uid(SPEECH_KIND, index))
+            .await
+        {
+            Ok(()) => report.deleted += 1,
+            Err(SpeechRepositoryError::SpeechNotFound) => {}
+            Err(e) => return Err(format!("Cannot delete the speech {}: {:?}", index, e)),
+        }
+    }
+    for index in profile.persons()..SeedProfile::Large.persons() {
+        match person_manager
+            .delete_person(&seeded_uid(PERSON_KIND, index), DeleteStrategy::Restrict)
+            .await
+        {
+            Ok(()) => report.deleted += 1,
+            // A person speaking in a speech not seeded is kept.
+            Err(
+                PersonRepositoryError::PersonNotFound | PersonRepositoryError::PersonReferenced(_),
+            ) => {}
+            Err(e) => return Err(format!("Cannot delete the person {}: {:?}", index, e)),
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Tone of a sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SentimentLabel {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+impl TryFrom<&str> for SentimentLabel {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "positive" => Self::Positive,
+            "neutral" => Self::Neutral,
+            "negative" => Self::Negative,
+            _ => return Err("Unexpected sentiment value".to_owned()),
+        })
+    }
+}
+
+impl Display for SentimentLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SentimentLabel::Positive => f.write_str("positive"),
+            SentimentLabel::Neutral => f.write_str("neutral"),
+            SentimentLabel::Negative => f.write_str("negative"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sentiment {
+    pub label: SentimentLabel,
+    /// Polarity of the sentence, from -1 (negative) to 1 (positive).
+    pub score: f64,
+}
+
+/// What an `Annotator` found in a sentence, each field being `None` when the annotator
+/// does not provide it or cannot tell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SentenceAnnotation {
+    /// Language of the sentence, as an ISO 639-3 code ("fra", "eng"...).
+    pub language: Option<String>,
+    pub sentiment: Option<Sentiment>,
+}
+
+/// Annotation stored for a sentence of a speech.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub sentence_uid: Uuid,
+    /// Position of the sentence in the speech when it was annotated, starting at 0.
+    pub sentence_index: u32,
+    pub language: Option<String>,
+    pub sentiment: Option<Sentiment>,
+    pub annotated_at: DateTime<Utc>,
+}
+
+impl Annotation {
+    pub fn new(sentence_uid: Uuid, sentence_index: u32, annotation: SentenceAnnotation) -> Self {
+        Self {
+            sentence_uid,
+            sentence_index,
+            language: annotation.language,
+            sentiment: annotation.sentiment,
+            annotated_at: Utc::now(),
+        }
+    }
+}
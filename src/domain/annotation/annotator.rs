@@ -0,0 +1,32 @@
+use super::annotation::SentenceAnnotation;
+
+#[derive(Debug, PartialEq)]
+pub enum AnnotatorError {
+    ProviderError(String),
+}
+
+/// Annotates the sentences of the transcripts, e.g. with their language and sentiment.
+#[async_trait::async_trait]
+pub trait Annotator: AnnotatorClone + Send + Sync {
+    /// Annotates every text, returning the annotation of each text in order.
+    async fn annotate(&self, texts: &[String]) -> Result<Vec<SentenceAnnotation>, AnnotatorError>;
+}
+
+pub trait AnnotatorClone {
+    fn clone_box(&self) -> Box<dyn Annotator>;
+}
+
+impl<T> AnnotatorClone for T
+where
+    T: 'static + Annotator + Clone,
+{
+    fn clone_box(&self) -> Box<dyn Annotator> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Annotator> {
+    fn clone(&self) -> Box<dyn Annotator> {
+        self.clone_box()
+    }
+}
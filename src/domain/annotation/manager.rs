@@ -0,0 +1,98 @@
+use uuid::Uuid;
+
+use crate::domain::speech::Speech;
+
+use super::{
+    annotation::Annotation,
+    annotator::Annotator,
+    repository::{AnnotationRepository, AnnotationRepositoryError},
+    sentiment::{SentimentFilter, SpeechSentiment},
+};
+
+/// Sentences sent to the annotator at once.
+const ANNOTATION_BATCH_SIZE: usize = 100;
+
+#[derive(Clone)]
+pub struct AnnotationManager {
+    repository: Box<dyn AnnotationRepository>,
+    /// Annotator of the sentences, the speeches are not annotated when missing.
+    annotator: Option<Box<dyn Annotator>>,
+}
+
+impl AnnotationManager {
+    pub fn new(repository: Box<dyn AnnotationRepository>) -> Self {
+        AnnotationManager {
+            repository,
+            annotator: None,
+        }
+    }
+
+    /// Sets the annotator of the sentences, enabling the annotation of the speeches.
+    pub fn with_annotator(mut self, annotator: Box<dyn Annotator>) -> Self {
+        self.annotator = Some(annotator);
+        self
+    }
+
+    /// Returns a manager whose operations only reach the annotations of the speeches of
+    /// the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            ..self.clone()
+        }
+    }
+
+    /// Annotates every sentence of the speech, replacing its previous annotations. Does
+    /// nothing without an annotator.
+    pub async fn annotate_speech(&self, speech: &Speech) -> Result<(), AnnotationRepositoryError> {
+        let annotator = match &self.annotator {
+            Some(annotator) => annotator,
+            None => return Ok(()),
+        };
+        let mut annotations: Vec<Annotation> = Vec::new();
+        for (batch, sentences) in speech.sentences().chunks(ANNOTATION_BATCH_SIZE).enumerate() {
+            let texts: Vec<String> = sentences.iter().map(|s| s.text().clone()).collect();
+            let found = annotator.annotate(&texts).await?;
+            // A sentence the annotator left out keeps the others at their position.
+            for (position, (sentence, annotation)) in sentences.iter().zip(found).enumerate() {
+                annotations.push(Annotation::new(
+                    *sentence.uid(),
+                    (batch * ANNOTATION_BATCH_SIZE + position) as u32,
+                    annotation,
+                ));
+            }
+        }
+        self.repository
+            .replace_annotations(speech.uid(), &annotations)
+            .await
+    }
+
+    /// Returns the speeches having a sentence not annotated, see
+    /// `AnnotationRepository::get_unannotated_speeches`.
+    pub async fn get_unannotated_speeches(
+        &self,
+        after: Option<&Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<Uuid>, AnnotationRepositoryError> {
+        self.repository
+            .get_unannotated_speeches(after, quantity)
+            .await
+    }
+
+    pub async fn get_annotations(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Annotation>, AnnotationRepositoryError> {
+        self.repository.get_annotations(speech_uid).await
+    }
+
+    /// Returns the sentiment of the sentences of the speech matching the filter.
+    pub async fn get_sentiment(
+        &self,
+        speech: &Speech,
+        filter: &SentimentFilter,
+    ) -> Result<SpeechSentiment, AnnotationRepositoryError> {
+        let annotations = self.repository.get_annotations(speech.uid()).await?;
+        Ok(SpeechSentiment::new(speech, &annotations, filter))
+    }
+}
@@ -0,0 +1,11 @@
+mod annotation;
+mod annotator;
+mod manager;
+mod repository;
+mod sentiment;
+
+pub use annotation::{Annotation, SentenceAnnotation, Sentiment, SentimentLabel};
+pub use annotator::{Annotator, AnnotatorError};
+pub use manager::AnnotationManager;
+pub use repository::{AnnotationRepository, AnnotationRepositoryError};
+pub use sentiment::{SentimentFilter, SentimentSummary, SpeechSentiment};
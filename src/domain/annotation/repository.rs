@@ -0,0 +1,60 @@
+use uuid::Uuid;
+
+use super::{annotation::Annotation, annotator::AnnotatorError};
+
+#[derive(Debug, PartialEq)]
+pub enum AnnotationRepositoryError {
+    SpeechNotFound,
+    AnnotatorError(AnnotatorError),
+    InternalError(String),
+}
+
+impl From<AnnotatorError> for AnnotationRepositoryError {
+    fn from(value: AnnotatorError) -> Self {
+        Self::AnnotatorError(value)
+    }
+}
+
+#[async_trait::async_trait]
+pub trait AnnotationRepository: AnnotationClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the annotations of the speeches of
+    /// the organization, `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn AnnotationRepository>;
+    /// Replaces the annotations of the sentences of the speech.
+    async fn replace_annotations(
+        &self,
+        speech_uid: &Uuid,
+        annotations: &[Annotation],
+    ) -> Result<(), AnnotationRepositoryError>;
+    /// Returns the speeches of the organization having a sentence not annotated, e.g. when
+    /// their events were missed, ordered by uid from the one after `after`.
+    async fn get_unannotated_speeches(
+        &self,
+        after: Option<&Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<Uuid>, AnnotationRepositoryError>;
+    /// Returns the annotations of the speech, ordered by sentence.
+    async fn get_annotations(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Annotation>, AnnotationRepositoryError>;
+}
+
+pub trait AnnotationClone {
+    fn clone_box(&self) -> Box<dyn AnnotationRepository>;
+}
+
+impl<T> AnnotationClone for T
+where
+    T: 'static + AnnotationRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn AnnotationRepository> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AnnotationRepository> {
+    fn clone(&self) -> Box<dyn AnnotationRepository> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::domain::speech::{sentence::Sentence, Speech};
+
+use super::annotation::{Annotation, Sentiment, SentimentLabel};
+
+/// Sentences of a speech the sentiment is aggregated over.
+#[derive(Debug, Clone, Default)]
+pub struct SentimentFilter {
+    /// Keeps the sentences of this speaker only.
+    pub speaker: Option<Uuid>,
+    /// Keeps the sentences annotated with this language only.
+    pub language: Option<String>,
+}
+
+impl SentimentFilter {
+    fn matches(&self, sentence: &Sentence, annotation: Option<&Annotation>) -> bool {
+        if self
+            .speaker
+            .is_some_and(|speaker| &speaker != sentence.speaker())
+        {
+            return false;
+        }
+        match &self.language {
+            Some(language) => annotation.and_then(|a| a.language.as_ref()) == Some(language),
+            None => true,
+        }
+    }
+}
+
+/// Sentiment of a set of sentences.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SentimentSummary {
+    pub sentences: u32,
+    /// Sentences whose sentiment is known, the others are not annotated yet or the
+    /// annotator does not tell their sentiment.
+    pub annotated: u32,
+    pub positive: u32,
+    pub neutral: u32,
+    pub negative: u32,
+    score_sum: f64,
+}
+
+impl SentimentSummary {
+    fn add(&mut self, sentiment: Option<&Sentiment>) {
+        self.sentences += 1;
+        let sentiment = match sentiment {
+            Some(sentiment) => sentiment,
+            None => return,
+        };
+        self.annotated += 1;
+        match sentiment.label {
+            SentimentLabel::Positive => self.positive += 1,
+            SentimentLabel::Neutral => self.neutral += 1,
+            SentimentLabel::Negative => self.negative += 1,
+        }
+        self.score_sum += sentiment.score;
+    }
+
+    /// Mean score of the annotated sentences, `None` when none is.
+    pub fn average_score(&self) -> Option<f64> {
+        match self.annotated {
+            0 => None,
+            annotated => Some(self.score_sum / annotated as f64),
+        }
+    }
+}
+
+/// Sentiment of the sentences of a speech matching a filter, overall and by speaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechSentiment {
+    pub overall: SentimentSummary,
+    /// Sentiment of each speaker, in the order of the speakers of the speech.
+    pub speakers: Vec<(Uuid, SentimentSummary)>,
+}
+
+impl SpeechSentiment {
+    pub fn new(speech: &Speech, annotations: &[Annotation], filter: &SentimentFilter) -> Self {
+        let annotations: HashMap<&Uuid, &Annotation> = annotations
+            .iter()
+            .map(|annotation| (&annotation.sentence_uid, annotation))
+            .collect();
+        let mut overall = SentimentSummary::default();
+        let mut speakers: Vec<(Uuid, SentimentSummary)> = speech
+            .speakers()
+            .iter()
+            .filter(|speaker| filter.speaker.is_none_or(|s| &s == *speaker))
+            .map(|speaker| (*speaker, SentimentSummary::default()))
+            .collect();
+        for sentence in speech.sentences() {
+            let annotation = annotations.get(sentence.uid()).copied();
+            if !filter.matches(sentence, annotation) {
+                continue;
+            }
+            let sentiment = annotation.and_then(|a| a.sentiment.as_ref());
+            overall.add(sentiment);
+            if let Some((_, summary)) = speakers
+                .iter_mut()
+                .find(|(speaker, _)| speaker == sentence.speaker())
+            {
+                summary.add(sentiment);
+            }
+        }
+        Self { overall, speakers }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{SentimentFilter, SpeechSentiment};
+    use crate::domain::{
+        annotation::{Annotation, SentenceAnnotation, Sentiment, SentimentLabel},
+        speech::{sentence::Sentence, Speech, SpeechStatus},
+    };
+
+    #[test]
+    fn sentiment_is_aggregated_over_the_sentences_matching_the_filter() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let sentences = [alice, bob, alice]
+            .iter()
+            .enumerate()
+            .map(|(i, speaker)| Sentence::new(&Uuid::new_v4(), speaker, &format!("S{}", i), false))
+            .collect::<Vec<Sentence>>();
+        let speech = Speech::new(
+            &Uuid::new_v4(),
+            "Debate",
+            Utc::now(),
+            &[alice, bob],
+            &sentences,
+            "TF1",
+            SpeechStatus::Validated,
+        );
+        let annotation = |index: usize, language: &str, label, score| {
+            Annotation::new(
+                *sentences[index].uid(),
+                index as u32,
+                SentenceAnnotation {
+                    language: Some(language.to_owned()),
+                    sentiment: Some(Sentiment { label, score }),
+                },
+            )
+        };
+        // The last sentence is not annotated yet.
+        let annotations = [
+            annotation(0, "fra", SentimentLabel::Positive, 0.8),
+            annotation(1, "eng", SentimentLabel::Negative, -0.4),
+        ];
+
+        let sentiment = SpeechSentiment::new(&speech, &annotations, &SentimentFilter::default());
+        assert_eq!(sentiment.overall.sentences, 3);
+        assert_eq!(sentiment.overall.annotated, 2);
+        assert_eq!(sentiment.overall.positive, 1);
+        assert_eq!(sentiment.overall.negative, 1);
+        assert!((sentiment.overall.average_score().unwrap() - 0.2).abs() < 1e-9);
+        assert_eq!(sentiment.speakers[0].0, alice);
+        assert_eq!(sentiment.speakers[0].1.sentences, 2);
+        assert_eq!(sentiment.speakers[0].1.annotated, 1);
+
+        let filter = SentimentFilter {
+            speaker: None,
+            language: Some("eng".to_owned()),
+        };
+        let sentiment = SpeechSentiment::new(&speech, &annotations, &filter);
+        assert_eq!(sentiment.overall.sentences, 1);
+        assert_eq!(sentiment.overall.negative, 1);
+        assert_eq!(sentiment.speakers[0].1.average_score(), None);
+
+        let filter = SentimentFilter {
+            speaker: Some(bob),
+            language: None,
+        };
+        let sentiment = SpeechSentiment::new(&speech, &annotations, &filter);
+        assert_eq!(sentiment.overall.sentences, 1);
+        assert_eq!(sentiment.speakers.len(), 1);
+    }
+}
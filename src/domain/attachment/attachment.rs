@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// File attached to a speech, e.g. the recording it was transcribed from. The content is
+/// kept in the attachment storage, only its metadata in the database.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    uid: Uuid,
+    speech_uid: Uuid,
+    file_name: String,
+    content_type: String,
+    /// Size of the content, in bytes.
+    size: u64,
+    /// Key of the content in the attachment storage.
+    storage_key: String,
+    created_at: DateTime<Utc>,
+}
+
+impl Attachment {
+    pub fn new(
+        uid: Uuid,
+        speech_uid: Uuid,
+        file_name: &str,
+        content_type: &str,
+        size: u64,
+        storage_key: &str,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            uid,
+            speech_uid,
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            size,
+            storage_key: storage_key.to_string(),
+            created_at,
+        }
+    }
+
+    /// Key under which the content of a new attachment is stored.
+    pub fn storage_key_of(speech_uid: &Uuid, uid: &Uuid) -> String {
+        format!("speeches/{}/{}", speech_uid, uid)
+    }
+
+    pub fn uid(&self) -> &Uuid {
+        &self.uid
+    }
+
+    pub fn speech_uid(&self) -> &Uuid {
+        &self.speech_uid
+    }
+
+    pub fn file_name(&self) -> &String {
+        &self.file_name
+    }
+
+    pub fn content_type(&self) -> &String {
+        &self.content_type
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn storage_key(&self) -> &String {
+        &self.storage_key
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+use super::{
+    attachment::Attachment,
+    repository::{AttachmentRepository, AttachmentRepositoryError},
+    storage::{AttachmentContent, AttachmentStorage, AttachmentStorageError},
+};
+
+/// Largest attachment accepted by default, in bytes.
+pub const DEFAULT_ATTACHMENT_MAX_SIZE: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Time during which a download URL is valid by default, in seconds.
+pub const DEFAULT_ATTACHMENT_URL_EXPIRY: u64 = 15 * 60;
+
+#[derive(Clone)]
+pub struct AttachmentManager {
+    repository: Box<dyn AttachmentRepository>,
+    /// Storage of the content of the attachments, the attachments are disabled when
+    /// missing.
+    storage: Option<Box<dyn AttachmentStorage>>,
+    /// Largest attachment accepted, in bytes.
+    max_size: u64,
+    /// Time during which a download URL is valid, in seconds.
+    url_expiry: u64,
+}
+
+impl AttachmentManager {
+    pub fn new(repository: Box<dyn AttachmentRepository>) -> Self {
+        AttachmentManager {
+            repository,
+            storage: None,
+            max_size: DEFAULT_ATTACHMENT_MAX_SIZE,
+            url_expiry: DEFAULT_ATTACHMENT_URL_EXPIRY,
+        }
+    }
+
+    /// Sets the storage of the content of the attachments, enabling them.
+    pub fn with_storage(mut self, storage: Box<dyn AttachmentStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Sets the largest attachment accepted, in bytes.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Sets the time during which a download URL is valid, in seconds.
+    pub fn with_url_expiry(mut self, url_expiry: u64) -> Self {
+        self.url_expiry = url_expiry;
+        self
+    }
+
+    /// Returns a manager whose operations only reach the attachments of the speeches of
+    /// the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            ..self.clone()
+        }
+    }
+
+    fn storage(&self) -> Result<&dyn AttachmentStorage, AttachmentRepositoryError> {
+        self.storage
+            .as_deref()
+            .ok_or(AttachmentStorageError::Unavailable.into())
+    }
+
+    /// Stores the content as a new attachment of the speech. The content is streamed to
+    /// the storage, and removed from it when the attachment cannot be recorded.
+    pub async fn upload_attachment(
+        &self,
+        speech_uid: &Uuid,
+        file_name: &str,
+        content_type: &str,
+        content: AttachmentContent<'_>,
+    ) -> Result<Attachment, AttachmentRepositoryError> {
+        let storage = self.storage()?;
+        let uid = Uuid::new_v4();
+        let key = Attachment::storage_key_of(speech_uid, &uid);
+        let max_size = self.max_size;
+        let mut received = 0;
+        let content = content
+            .map(move |chunk| {
+                let chunk = chunk?;
+                received += chunk.len() as u64;
+                if received > max_size {
+                    return Err(AttachmentStorageError::TooLarge);
+                }
+                Ok(chunk)
+            })
+            .boxed();
+        let size = storage.put(&key, content_type, content).await?;
+        let attachment = Attachment::new(
+            uid,
+            *speech_uid,
+            file_name,
+            content_type,
+            size,
+            &key,
+            Utc::now(),
+        );
+        if let Err(e) = self.repository.create_attachment(&attachment).await {
+            if let Err(e) = storage.delete(&key).await {
+                println!(
+                    "An internal error occured while deleting the unrecorded attachment {}: {:?}",
+                    key, e
+                );
+            }
+            return Err(e);
+        }
+        Ok(attachment)
+    }
+
+    pub async fn get_attachments(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Attachment>, AttachmentRepositoryError> {
+        self.repository.get_attachments(speech_uid).await
+    }
+
+    /// Returns the attachment with a URL its content can be downloaded from until the
+    /// date returned.
+    pub async fn get_download_url(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<(Attachment, String, DateTime<Utc>), AttachmentRepositoryError> {
+        let storage = self.storage()?;
+        let attachment = self.repository.get_attachment(speech_uid, uid).await?;
+        let expiry = Duration::from_secs(self.url_expiry);
+        let expires_at = Utc::now() + expiry;
+        let url = storage.download_url(attachment.storage_key(), attachment.file_name(), expiry)?;
+        Ok((attachment, url, expires_at))
+    }
+}
@@ -0,0 +1,9 @@
+mod attachment;
+mod manager;
+mod repository;
+mod storage;
+
+pub use attachment::Attachment;
+pub use manager::{AttachmentManager, DEFAULT_ATTACHMENT_MAX_SIZE, DEFAULT_ATTACHMENT_URL_EXPIRY};
+pub use repository::{AttachmentRepository, AttachmentRepositoryError};
+pub use storage::{AttachmentContent, AttachmentStorage, AttachmentStorageError};
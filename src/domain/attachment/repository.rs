@@ -0,0 +1,59 @@
+use uuid::Uuid;
+
+use super::{attachment::Attachment, storage::AttachmentStorageError};
+
+#[derive(Debug, PartialEq)]
+pub enum AttachmentRepositoryError {
+    SpeechNotFound,
+    AttachmentNotFound,
+    StorageError(AttachmentStorageError),
+    InternalError(String),
+}
+
+impl From<AttachmentStorageError> for AttachmentRepositoryError {
+    fn from(value: AttachmentStorageError) -> Self {
+        Self::StorageError(value)
+    }
+}
+
+/// Metadata of the attachments, their content being kept by an `AttachmentStorage`.
+#[async_trait::async_trait]
+pub trait AttachmentRepository: AttachmentClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the attachments of the speeches of
+    /// the organization, `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn AttachmentRepository>;
+    /// Stores the attachment, its speech must be a speech of the organization.
+    async fn create_attachment(
+        &self,
+        attachment: &Attachment,
+    ) -> Result<(), AttachmentRepositoryError>;
+    /// Returns the attachments of the speech, the oldest first.
+    async fn get_attachments(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Attachment>, AttachmentRepositoryError>;
+    async fn get_attachment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<Attachment, AttachmentRepositoryError>;
+}
+
+pub trait AttachmentClone {
+    fn clone_box(&self) -> Box<dyn AttachmentRepository>;
+}
+
+impl<T> AttachmentClone for T
+where
+    T: 'static + AttachmentRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn AttachmentRepository> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AttachmentRepository> {
+    fn clone(&self) -> Box<dyn AttachmentRepository> {
+        self.clone_box()
+    }
+}
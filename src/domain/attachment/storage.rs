@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::stream::BoxStream;
+
+#[derive(Debug, PartialEq)]
+pub enum AttachmentStorageError {
+    /// No attachment storage is configured.
+    Unavailable,
+    /// The content is larger than the attachments may be.
+    TooLarge,
+    /// The content could not be read from the client.
+    InvalidContent(String),
+    ProviderError(String),
+}
+
+/// Content of an attachment, read as it is stored.
+pub type AttachmentContent<'a> = BoxStream<'a, Result<Bytes, AttachmentStorageError>>;
+
+/// Object storage keeping the content of the attachments.
+#[async_trait::async_trait]
+pub trait AttachmentStorage: AttachmentStorageClone + Send + Sync {
+    /// Stores the content under the key, without holding it in memory as a whole. Returns
+    /// the size stored, in bytes. Nothing is kept when the content fails.
+    async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        content: AttachmentContent<'_>,
+    ) -> Result<u64, AttachmentStorageError>;
+    /// Returns a URL the content can be downloaded from without credentials until it
+    /// expires, named `file_name` when saved.
+    fn download_url(
+        &self,
+        key: &str,
+        file_name: &str,
+        expiry: Duration,
+    ) -> Result<String, AttachmentStorageError>;
+    async fn delete(&self, key: &str) -> Result<(), AttachmentStorageError>;
+}
+
+pub trait AttachmentStorageClone {
+    fn clone_box(&self) -> Box<dyn AttachmentStorage>;
+}
+
+impl<T> AttachmentStorageClone for T
+where
+    T: 'static + AttachmentStorage + Clone,
+{
+    fn clone_box(&self) -> Box<dyn AttachmentStorage> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn AttachmentStorage> {
+    fn clone(&self) -> Box<dyn AttachmentStorage> {
+        self.clone_box()
+    }
+}
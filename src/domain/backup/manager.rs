@@ -0,0 +1,58 @@
+use std::path::Path;
+
+use chrono::Utc;
+
+use super::{
+    provider::{BackupError, BackupProvider},
+    target::BackupTarget,
+};
+
+/// Backup written and kept by its target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupReport {
+    /// Where the target keeps the backup.
+    pub location: String,
+    /// Rows exported, when the provider counts them.
+    pub rows: u64,
+}
+
+#[derive(Clone)]
+pub struct BackupManager {
+    provider: Box<dyn BackupProvider>,
+    target: Box<dyn BackupTarget>,
+}
+
+impl BackupManager {
+    pub fn new(provider: Box<dyn BackupProvider>, target: Box<dyn BackupTarget>) -> Self {
+        BackupManager { provider, target }
+    }
+
+    /// Exports the database to a temporary file handed to the target, named after the
+    /// time of the backup, e.g. `backup-20240312T020000Z.jsonl`.
+    pub async fn backup(&self) -> Result<BackupReport, BackupError> {
+        let name = format!(
+            "backup-{}.{}",
+            Utc::now().format("%Y%m%dT%H%M%SZ"),
+            self.provider.extension()
+        );
+        let path = std::env::temp_dir().join(&name);
+        let result = self.export_and_store(&name, &path).await;
+        // The temporary file is removed whatever the outcome, the target keeps its copy.
+        if let Err(e) = tokio::fs::remove_file(&path).await {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                println!(
+                    "An error occured while removing the temporary backup {}: {:?}",
+                    path.display(),
+                    e
+                );
+            }
+        }
+        result
+    }
+
+    async fn export_and_store(&self, name: &str, path: &Path) -> Result<BackupReport, BackupError> {
+        let rows = self.provider.export(path).await?;
+        let location = self.target.store(name, path).await?;
+        Ok(BackupReport { location, rows })
+    }
+}
@@ -0,0 +1,7 @@
+mod manager;
+mod provider;
+mod target;
+
+pub use manager::{BackupManager, BackupReport};
+pub use provider::{BackupError, BackupProvider};
+pub use target::BackupTarget;
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+#[derive(Debug, PartialEq)]
+pub enum BackupError {
+    /// No backup is configured.
+    Unavailable,
+    /// A backup is only restored into a database without any row.
+    DatabaseNotEmpty,
+    /// The file is not a backup of this provider.
+    InvalidBackup(String),
+    InternalError(String),
+}
+
+/// Logical export of the whole database, across the organizations.
+#[async_trait::async_trait]
+pub trait BackupProvider: BackupProviderClone + Send + Sync {
+    /// Extension of the files written, e.g. `jsonl`.
+    fn extension(&self) -> &'static str;
+    /// Writes a consistent export of every table to the file, as of a single point in
+    /// time. Returns the number of rows exported, when known.
+    async fn export(&self, path: &Path) -> Result<u64, BackupError>;
+    /// Loads the export of the file into the migrated database, refusing a database
+    /// holding any row. Returns the number of rows loaded, when known.
+    async fn restore(&self, path: &Path) -> Result<u64, BackupError>;
+}
+
+pub trait BackupProviderClone {
+    fn clone_box(&self) -> Box<dyn BackupProvider>;
+}
+
+impl<T> BackupProviderClone for T
+where
+    T: 'static + BackupProvider + Clone,
+{
+    fn clone_box(&self) -> Box<dyn BackupProvider> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn BackupProvider> {
+    fn clone(&self) -> Box<dyn BackupProvider> {
+        self.clone_box()
+    }
+}
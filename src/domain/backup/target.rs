@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use super::provider::BackupError;
+
+/// Place the backups are kept in, e.g. a directory or an object storage.
+#[async_trait::async_trait]
+pub trait BackupTarget: BackupTargetClone + Send + Sync {
+    /// Keeps the file written by the provider under the name. Returns where it is kept.
+    async fn store(&self, name: &str, path: &Path) -> Result<String, BackupError>;
+}
+
+pub trait BackupTargetClone {
+    fn clone_box(&self) -> Box<dyn BackupTarget>;
+}
+
+impl<T> BackupTargetClone for T
+where
+    T: 'static + BackupTarget + Clone,
+{
+    fn clone_box(&self) -> Box<dyn BackupTarget> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn BackupTarget> {
+    fn clone(&self) -> Box<dyn BackupTarget> {
+        self.clone_box()
+    }
+}
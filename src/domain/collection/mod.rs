@@ -0,0 +1,5 @@
+mod repository;
+mod version;
+
+pub use repository::{CollectionVersionError, CollectionVersionRepository};
+pub use version::CollectionVersions;
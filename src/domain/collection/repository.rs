@@ -0,0 +1,37 @@
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub enum CollectionVersionError {
+    InternalError(String),
+}
+
+#[async_trait::async_trait]
+pub trait CollectionVersionRepository: CollectionVersionClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the version of the organization,
+    /// `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn CollectionVersionRepository>;
+    /// Current version of the collections, 0 while they were never changed.
+    async fn current(&self) -> Result<u64, CollectionVersionError>;
+    /// Increases the version, returning the new one.
+    async fn bump(&self) -> Result<u64, CollectionVersionError>;
+}
+
+pub trait CollectionVersionClone {
+    fn clone_box(&self) -> Box<dyn CollectionVersionRepository>;
+}
+
+impl<T> CollectionVersionClone for T
+where
+    T: 'static + CollectionVersionRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn CollectionVersionRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn CollectionVersionRepository> {
+    fn clone(&self) -> Box<dyn CollectionVersionRepository> {
+        self.clone_box()
+    }
+}
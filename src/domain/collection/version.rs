@@ -0,0 +1,35 @@
+use uuid::Uuid;
+
+use super::repository::{CollectionVersionError, CollectionVersionRepository};
+
+/// Version of the speeches and persons of each organization, increased by every change
+/// made to them so the clients can tell whether the lists they cached are stale.
+///
+/// The versions are stored in the database, shared by every instance, and are bumped by
+/// the managers once their change is stored, before it is answered.
+#[derive(Clone)]
+pub struct CollectionVersions {
+    repository: Box<dyn CollectionVersionRepository>,
+}
+
+impl CollectionVersions {
+    pub fn new(repository: Box<dyn CollectionVersionRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Returns the versions of the collections of the organization, `None` being the
+    /// default organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+        }
+    }
+
+    pub async fn current(&self) -> Result<u64, CollectionVersionError> {
+        self.repository.current().await
+    }
+
+    pub async fn bump(&self) -> Result<u64, CollectionVersionError> {
+        self.repository.bump().await
+    }
+}
@@ -0,0 +1,5 @@
+mod spec;
+
+pub use spec::{
+    FilterCondition, FilterField, FilterOperator, FilterSpec, FilterValue, FilterValueKind,
+};
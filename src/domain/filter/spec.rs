@@ -0,0 +1,72 @@
+use chrono::NaiveDate;
+
+/// Comparison made by a condition of a filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOperator {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+/// Value a field is compared to, of the kind of the field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    Text(String),
+    /// A day, the date times of the day all being equal to it.
+    Date(NaiveDate),
+    Number(i64),
+}
+
+/// Kind of the values of a field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterValueKind {
+    /// Text only compared with `Eq` and `Ne`.
+    Text,
+    Date,
+    Number,
+    /// One of a set of values, only compared with `Eq` and `Ne`.
+    Choice(&'static [&'static str]),
+}
+
+impl FilterValueKind {
+    /// Whether the values of the kind can be compared with the operator.
+    pub fn accepts(&self, operator: FilterOperator) -> bool {
+        match self {
+            FilterValueKind::Date | FilterValueKind::Number => true,
+            FilterValueKind::Text | FilterValueKind::Choice(_) => {
+                matches!(operator, FilterOperator::Eq | FilterOperator::Ne)
+            }
+        }
+    }
+}
+
+/// Field of an entity its lists can be filtered on.
+pub trait FilterField: Copy + Sized {
+    /// Field of the given name in the API, `None` when the entity has no such field.
+    fn from_name(name: &str) -> Option<Self>;
+    fn kind(&self) -> FilterValueKind;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterCondition<F> {
+    pub field: F,
+    pub operator: FilterOperator,
+    pub value: FilterValue,
+}
+
+/// Conditions on the fields of an entity, all met by the entities of a list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterSpec<F> {
+    pub conditions: Vec<FilterCondition<F>>,
+}
+
+impl<F> Default for FilterSpec<F> {
+    fn default() -> Self {
+        Self {
+            conditions: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use super::repository::{
+    IdempotencyClaim, IdempotencyRepository, IdempotencyRepositoryError, StoredResponse,
+};
+
+/// Time during which a key is remembered by default, in seconds.
+pub const DEFAULT_IDEMPOTENCY_KEY_TTL: u64 = 24 * 60 * 60;
+
+#[derive(Clone)]
+pub struct IdempotencyManager {
+    repository: Box<dyn IdempotencyRepository>,
+    /// Time during which a key is remembered, in seconds.
+    ttl: u64,
+}
+
+impl IdempotencyManager {
+    pub fn new(repository: Box<dyn IdempotencyRepository>) -> Self {
+        IdempotencyManager {
+            repository,
+            ttl: DEFAULT_IDEMPOTENCY_KEY_TTL,
+        }
+    }
+
+    /// Sets the time during which a key is remembered, in seconds.
+    pub fn with_ttl(mut self, ttl: u64) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns a manager whose operations only reach the keys of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            ttl: self.ttl,
+        }
+    }
+
+    /// Returns a manager whose operations only reach the keys sent by the user.
+    pub fn for_user(&self, user_id: &str) -> Self {
+        Self {
+            repository: self.repository.for_user(user_id),
+            ttl: self.ttl,
+        }
+    }
+
+    /// Claims the key for a request which cannot run for longer than `lease`.
+    pub async fn claim_key(
+        &self,
+        key: &str,
+        request: &[u8],
+        lease: Duration,
+    ) -> Result<IdempotencyClaim, IdempotencyRepositoryError> {
+        self.repository
+            .claim_key(key, request, self.ttl, lease)
+            .await
+    }
+
+    pub async fn complete_key(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+    ) -> Result<(), IdempotencyRepositoryError> {
+        self.repository.complete_key(key, response).await
+    }
+
+    pub async fn release_key(&self, key: &str) -> Result<(), IdempotencyRepositoryError> {
+        self.repository.release_key(key).await
+    }
+}
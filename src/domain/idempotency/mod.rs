@@ -0,0 +1,7 @@
+mod manager;
+mod repository;
+
+pub use manager::{IdempotencyManager, DEFAULT_IDEMPOTENCY_KEY_TTL};
+pub use repository::{
+    IdempotencyClaim, IdempotencyRepository, IdempotencyRepositoryError, StoredResponse,
+};
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq)]
+pub enum IdempotencyRepositoryError {
+    /// The key has already been used with another request.
+    KeyReused,
+    /// The first request sent with the key is still being processed, within its lease.
+    KeyInProgress,
+    InternalError(String),
+}
+
+/// Response sent to the first request of a key, sent again to the retries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum IdempotencyClaim {
+    /// The key is new, the request must be processed.
+    Claimed,
+    /// The request has already been processed.
+    Replay(StoredResponse),
+}
+
+#[async_trait::async_trait]
+pub trait IdempotencyRepository: IdempotencyClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the keys of the organization,
+    /// `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn IdempotencyRepository>;
+    /// Returns a copy of the repository reaching only the keys sent by the user.
+    fn for_user(&self, user_id: &str) -> Box<dyn IdempotencyRepository>;
+    /// Claims the key for the request, the keys older than `ttl` seconds being forgotten
+    /// first. `request` is the whole request, compared to the one the key was claimed for.
+    /// The claim holds for `lease`, a key still without a response is claimed again after.
+    async fn claim_key(
+        &self,
+        key: &str,
+        request: &[u8],
+        ttl: u64,
+        lease: Duration,
+    ) -> Result<IdempotencyClaim, IdempotencyRepositoryError>;
+    /// Stores the response of the request the key was claimed for.
+    async fn complete_key(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+    ) -> Result<(), IdempotencyRepositoryError>;
+    /// Forgets a key whose request failed, so it can be retried.
+    async fn release_key(&self, key: &str) -> Result<(), IdempotencyRepositoryError>;
+}
+
+pub trait IdempotencyClone {
+    fn clone_box(&self) -> Box<dyn IdempotencyRepository>;
+}
+
+impl<T> IdempotencyClone for T
+where
+    T: 'static + IdempotencyRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn IdempotencyRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn IdempotencyRepository> {
+    fn clone(&self) -> Box<dyn IdempotencyRepository> {
+        self.clone_box()
+    }
+}
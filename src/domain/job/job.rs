@@ -0,0 +1,190 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::maintenance::MaintenanceAction;
+
+/// Operation a job runs in the background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// Creates the speeches of a bulk import.
+    SpeechImport,
+    ReindexSearch,
+    RecomputeScores,
+    VacuumOrphans,
+    Backup,
+}
+
+impl JobKind {
+    /// Maintenance action run by the job, `None` for the operations of the users.
+    pub fn maintenance_action(&self) -> Option<MaintenanceAction> {
+        match self {
+            JobKind::SpeechImport => None,
+            JobKind::ReindexSearch => Some(MaintenanceAction::ReindexSearch),
+            JobKind::RecomputeScores => Some(MaintenanceAction::RecomputeScores),
+            JobKind::VacuumOrphans => Some(MaintenanceAction::VacuumOrphans),
+            JobKind::Backup => Some(MaintenanceAction::Backup),
+        }
+    }
+}
+
+impl From<MaintenanceAction> for JobKind {
+    fn from(value: MaintenanceAction) -> Self {
+        match value {
+            MaintenanceAction::ReindexSearch => JobKind::ReindexSearch,
+            MaintenanceAction::RecomputeScores => JobKind::RecomputeScores,
+            MaintenanceAction::VacuumOrphans => JobKind::VacuumOrphans,
+            MaintenanceAction::Backup => JobKind::Backup,
+        }
+    }
+}
+
+impl TryFrom<&str> for JobKind {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "speech_import" => Ok(Self::SpeechImport),
+            // The maintenance jobs are named after their action.
+            _ => MaintenanceAction::try_from(value)
+                .map(JobKind::from)
+                .map_err(|_| "Unexpected job kind value".to_owned()),
+        }
+    }
+}
+
+impl Display for JobKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.maintenance_action() {
+            Some(action) => action.fmt(f),
+            None => f.write_str("speech_import"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl TryFrom<&str> for JobStatus {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "running" => Self::Running,
+            "succeeded" => Self::Succeeded,
+            "failed" => Self::Failed,
+            _ => return Err("Unexpected job status value".to_owned()),
+        })
+    }
+}
+
+impl Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobStatus::Running => f.write_str("running"),
+            JobStatus::Succeeded => f.write_str("succeeded"),
+            JobStatus::Failed => f.write_str("failed"),
+        }
+    }
+}
+
+/// Run of an operation in the background, polled until it is finished.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub uid: Uuid,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    /// Items done so far, e.g. the speeches of an import.
+    pub processed: u64,
+    /// Items to do, when the operation knows it.
+    pub total: Option<u64>,
+    /// Outcome of the operation, once succeeded, e.g. the report of an import.
+    pub result: Option<Value>,
+    /// Reason of the failure, once failed.
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// Last time the job was known to run, a running job not updated for too long having
+    /// been left behind.
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    /// Job of the operation starting now.
+    pub fn start(kind: JobKind) -> Self {
+        let now = Utc::now();
+        Self {
+            uid: Uuid::new_v4(),
+            kind,
+            status: JobStatus::Running,
+            processed: 0,
+            total: None,
+            result: None,
+            error: None,
+            started_at: now,
+            updated_at: now,
+            finished_at: None,
+        }
+    }
+
+    /// Ends the job with the outcome of its operation.
+    pub fn finish(&mut self, outcome: Result<Value, String>) {
+        match outcome {
+            Ok(result) => {
+                self.status = JobStatus::Succeeded;
+                self.result = Some(result);
+                // Every item is done, whether the total was known or not.
+                self.processed = self.total.unwrap_or(self.processed).max(self.processed);
+                self.total = Some(self.processed);
+            }
+            Err(e) => {
+                self.status = JobStatus::Failed;
+                self.error = Some(e);
+            }
+        }
+        let now = Utc::now();
+        self.updated_at = now;
+        self.finished_at = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Job, JobKind, JobStatus};
+
+    #[test]
+    fn kinds_are_named_after_the_maintenance_actions() {
+        for name in [
+            "speech_import",
+            "reindex_search",
+            "recompute_scores",
+            "vacuum_orphans",
+            "backup",
+        ] {
+            assert_eq!(JobKind::try_from(name).unwrap().to_string(), name);
+        }
+        assert!(JobKind::try_from("import").is_err());
+        assert!(JobKind::SpeechImport.maintenance_action().is_none());
+    }
+
+    #[test]
+    fn finished_job_keeps_its_outcome() {
+        let mut job = Job::start(JobKind::SpeechImport);
+        assert_eq!(job.status, JobStatus::Running);
+        job.processed = 2;
+        job.total = Some(3);
+        job.finish(Ok(json!({ "created": 3 })));
+        assert_eq!(job.status, JobStatus::Succeeded);
+        assert_eq!((job.processed, job.total), (3, Some(3)));
+        let mut job = Job::start(JobKind::VacuumOrphans);
+        job.finish(Err("timed out".to_owned()));
+        assert_eq!(job.status, JobStatus::Failed);
+        assert!(job.result.is_none() && job.finished_at.is_some());
+    }
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{
+    job::{Job, JobKind, JobStatus},
+    progress::JobProgress,
+    repository::{JobRepository, JobRepositoryError},
+};
+
+/// Time after which a running job not updated is failed, four heartbeats having been
+/// missed.
+const JOB_STALE_AFTER: Duration = Duration::from_secs(2 * 60);
+
+#[derive(Clone)]
+pub struct JobManager {
+    repository: Box<dyn JobRepository>,
+}
+
+impl JobManager {
+    pub fn new(repository: Box<dyn JobRepository>) -> Self {
+        JobManager { repository }
+    }
+
+    /// Returns a manager whose operations only reach the jobs of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+        }
+    }
+
+    /// Records the job of the operation, running from now on. The returned progress is
+    /// handed to the operation, which reports through it until it is finished.
+    pub async fn start_job(&self, kind: JobKind) -> Result<JobProgress, JobRepositoryError> {
+        let job = Job::start(kind);
+        self.repository.save_job(&job).await?;
+        Ok(JobProgress::new(self.clone(), job))
+    }
+
+    /// Stores the progress of the job, still running.
+    pub async fn save_progress(&self, job: &Job) -> Result<(), JobRepositoryError> {
+        self.repository.save_job(job).await
+    }
+
+    /// Records that the job, still running, is alive.
+    pub async fn touch_job(&self, uid: &Uuid) -> Result<(), JobRepositoryError> {
+        self.repository.touch_job(uid).await
+    }
+
+    /// Fails the running jobs of every organization whose operation stopped without
+    /// recording its outcome, e.g. when an instance stopped. Returns how many were failed.
+    pub async fn fail_stale_jobs(&self) -> Result<u64, JobRepositoryError> {
+        self.repository.fail_stale_jobs(JOB_STALE_AFTER).await
+    }
+
+    /// Records the outcome of the job, its result or the failure.
+    pub async fn finish_job(
+        &self,
+        mut job: Job,
+        outcome: Result<Value, String>,
+    ) -> Result<Job, JobRepositoryError> {
+        job.finish(outcome);
+        self.repository.save_job(&job).await?;
+        Ok(job)
+    }
+
+    /// Returns the job, failed when its operation stopped, see `fail_stale_jobs`.
+    pub async fn get_job(&self, uid: &Uuid) -> Result<Job, JobRepositoryError> {
+        self.fail_stale_jobs().await?;
+        self.repository.get_job(uid).await
+    }
+
+    pub async fn get_jobs(
+        &self,
+        kinds: &[JobKind],
+        status: Option<JobStatus>,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        if kinds.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.fail_stale_jobs().await?;
+        self.repository
+            .get_jobs(kinds, status, page, quantity)
+            .await
+    }
+}
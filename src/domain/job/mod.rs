@@ -0,0 +1,9 @@
+mod job;
+mod manager;
+mod progress;
+mod repository;
+
+pub use job::{Job, JobKind, JobStatus};
+pub use manager::JobManager;
+pub use progress::JobProgress;
+pub use repository::{JobRepository, JobRepositoryError};
@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use super::{job::Job, manager::JobManager, repository::JobRepositoryError};
+
+/// Time between two saves of the progress of a job, the pollers seeing it this late.
+const PROGRESS_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+/// Time between two records that a running job is alive, whether it advanced or not.
+const JOB_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Job being run, through which its operation reports how far it is.
+pub struct JobProgress {
+    manager: JobManager,
+    job: Job,
+    /// Last time the progress was stored.
+    saved_at: Instant,
+    /// Task recording that the job is alive, stopped with the progress. A progress dropped
+    /// without finishing leaves the job running until it is failed as stale.
+    heartbeat: JoinHandle<()>,
+}
+
+impl JobProgress {
+    pub fn new(manager: JobManager, job: Job) -> Self {
+        let heartbeat = tokio::spawn({
+            let manager = manager.clone();
+            let uid = job.uid;
+            async move {
+                loop {
+                    tokio::time::sleep(JOB_HEARTBEAT_INTERVAL).await;
+                    if let Err(e) = manager.touch_job(&uid).await {
+                        println!(
+                            "An internal error occured while recording that the job {} is alive: {:?}",
+                            uid, e
+                        );
+                    }
+                }
+            }
+        });
+        Self {
+            manager,
+            job,
+            saved_at: Instant::now(),
+            heartbeat,
+        }
+    }
+
+    pub fn job(&self) -> &Job {
+        &self.job
+    }
+
+    /// Records that `processed` items out of `total` are done. The progress is stored at
+    /// most once a second and once every item is done; a failing save is only reported,
+    /// the operation going on.
+    pub async fn advance(&mut self, processed: u64, total: Option<u64>) {
+        self.job.processed = processed;
+        self.job.total = total;
+        let done = total.is_some_and(|total| processed >= total);
+        if !done && self.saved_at.elapsed() < PROGRESS_SAVE_INTERVAL {
+            return;
+        }
+        self.saved_at = Instant::now();
+        if let Err(e) = self.manager.save_progress(&self.job).await {
+            println!(
+                "An internal error occured while saving the progress of the job {}: {:?}",
+                self.job.uid, e
+            );
+        }
+    }
+
+    /// Records the outcome of the operation, ending the job.
+    pub async fn finish(self, outcome: Result<Value, String>) -> Result<Job, JobRepositoryError> {
+        self.heartbeat.abort();
+        self.manager.finish_job(self.job.clone(), outcome).await
+    }
+}
+
+impl Drop for JobProgress {
+    fn drop(&mut self) {
+        self.heartbeat.abort();
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use super::job::{Job, JobKind, JobStatus};
+
+#[derive(Debug, PartialEq)]
+pub enum JobRepositoryError {
+    JobNotFound,
+    InternalError(String),
+}
+
+#[async_trait::async_trait]
+pub trait JobRepository: JobClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the jobs of the organization, `None`
+    /// being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn JobRepository>;
+    /// Stores the job, or its progress and outcome once stored.
+    async fn save_job(&self, job: &Job) -> Result<(), JobRepositoryError>;
+    /// Records that the job, still running, is alive.
+    async fn touch_job(&self, uid: &Uuid) -> Result<(), JobRepositoryError>;
+    /// Fails the running jobs of every organization not updated for `stale_after`, their
+    /// operation having stopped without recording its outcome. Returns how many were failed.
+    async fn fail_stale_jobs(&self, stale_after: Duration) -> Result<u64, JobRepositoryError>;
+    async fn get_job(&self, uid: &Uuid) -> Result<Job, JobRepositoryError>;
+    /// Returns a page of the jobs of these kinds, the latest started first, only the ones
+    /// having the status when given.
+    async fn get_jobs(
+        &self,
+        kinds: &[JobKind],
+        status: Option<JobStatus>,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<Job>, JobRepositoryError>;
+}
+
+pub trait JobClone {
+    fn clone_box(&self) -> Box<dyn JobRepository>;
+}
+
+impl<T> JobClone for T
+where
+    T: 'static + JobRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn JobRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn JobRepository> {
+    fn clone(&self) -> Box<dyn JobRepository> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,36 @@
+use uuid::Uuid;
+
+/// Free-form label attached to speeches or persons. A label without owner is shared
+/// with every user, otherwise it is only visible to its owner.
+#[derive(Debug, Clone)]
+pub struct Label {
+    uid: Uuid,
+    name: String,
+    owner: Option<String>,
+}
+
+impl Label {
+    pub fn new(uid: Uuid, name: &str, owner: Option<String>) -> Self {
+        Self {
+            uid,
+            name: name.to_string(),
+            owner,
+        }
+    }
+
+    pub fn uid(&self) -> &Uuid {
+        &self.uid
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn owner(&self) -> Option<&String> {
+        self.owner.as_ref()
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.owner.is_none()
+    }
+}
@@ -0,0 +1,98 @@
+use uuid::Uuid;
+
+use crate::domain::collection::{CollectionVersionError, CollectionVersions};
+
+use super::{
+    label::Label,
+    repository::{LabelRepository, LabelRepositoryError, LabelTarget},
+};
+
+#[derive(Clone)]
+pub struct LabelManager {
+    repository: Box<dyn LabelRepository>,
+    collection_versions: Option<CollectionVersions>,
+}
+
+impl LabelManager {
+    pub fn new(repository: Box<dyn LabelRepository>) -> Self {
+        LabelManager {
+            repository,
+            collection_versions: None,
+        }
+    }
+
+    /// Returns a manager whose operations only reach the labels of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            collection_versions: self
+                .collection_versions
+                .as_ref()
+                .map(|versions| versions.for_organization(organization)),
+        }
+    }
+
+    /// Bumps the versions of the collections when a label is attached or detached, the
+    /// lists being filtered by their labels.
+    pub fn with_collection_versions(mut self, collection_versions: CollectionVersions) -> Self {
+        self.collection_versions = Some(collection_versions);
+        self
+    }
+
+    async fn bump_collection_version(&self) -> Result<(), LabelRepositoryError> {
+        if let Some(versions) = &self.collection_versions {
+            versions
+                .bump()
+                .await
+                .map_err(|CollectionVersionError::InternalError(e)| {
+                    LabelRepositoryError::InternalError(e)
+                })?;
+        }
+        Ok(())
+    }
+
+    pub async fn create_label(&self, label: Label) -> Result<(), LabelRepositoryError> {
+        self.repository.create_label(&label).await
+    }
+
+    pub async fn get_labels(
+        &self,
+        prefix: &str,
+        user_id: &str,
+        quantity: u16,
+    ) -> Result<Vec<Label>, LabelRepositoryError> {
+        self.repository.get_labels(prefix, user_id, quantity).await
+    }
+
+    pub async fn get_labels_of(
+        &self,
+        target: LabelTarget,
+        user_id: &str,
+    ) -> Result<Vec<Label>, LabelRepositoryError> {
+        self.repository.get_labels_of(target, user_id).await
+    }
+
+    pub async fn attach_label(
+        &self,
+        target: LabelTarget,
+        label_uid: &Uuid,
+        user_id: &str,
+    ) -> Result<(), LabelRepositoryError> {
+        self.repository
+            .attach_label(target, label_uid, user_id)
+            .await?;
+        self.bump_collection_version().await
+    }
+
+    pub async fn detach_label(
+        &self,
+        target: LabelTarget,
+        label_uid: &Uuid,
+        user_id: &str,
+    ) -> Result<(), LabelRepositoryError> {
+        self.repository
+            .detach_label(target, label_uid, user_id)
+            .await?;
+        self.bump_collection_version().await
+    }
+}
@@ -0,0 +1,7 @@
+mod label;
+mod manager;
+mod repository;
+
+pub use label::Label;
+pub use manager::LabelManager;
+pub use repository::{LabelRepository, LabelRepositoryError, LabelTarget};
@@ -0,0 +1,70 @@
+use uuid::Uuid;
+
+use super::label::Label;
+
+#[derive(Debug, PartialEq)]
+pub enum LabelRepositoryError {
+    LabelNotFound,
+    LabelAlreadyExists,
+    InternalError(String),
+}
+
+/// Where a label is attached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LabelTarget {
+    Speech(Uuid),
+    Person(Uuid),
+}
+
+#[async_trait::async_trait]
+pub trait LabelRepository: LabelClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the labels and labelled entities of
+    /// the organization, `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn LabelRepository>;
+    async fn create_label(&self, label: &Label) -> Result<(), LabelRepositoryError>;
+    /// Returns the labels visible to the user (shared ones and its own) starting with prefix.
+    async fn get_labels(
+        &self,
+        prefix: &str,
+        user_id: &str,
+        quantity: u16,
+    ) -> Result<Vec<Label>, LabelRepositoryError>;
+    async fn get_labels_of(
+        &self,
+        target: LabelTarget,
+        user_id: &str,
+    ) -> Result<Vec<Label>, LabelRepositoryError>;
+    /// Attaches a label visible to the user to the target.
+    async fn attach_label(
+        &self,
+        target: LabelTarget,
+        label_uid: &Uuid,
+        user_id: &str,
+    ) -> Result<(), LabelRepositoryError>;
+    async fn detach_label(
+        &self,
+        target: LabelTarget,
+        label_uid: &Uuid,
+        user_id: &str,
+    ) -> Result<(), LabelRepositoryError>;
+}
+
+pub trait LabelClone {
+    fn clone_box(&self) -> Box<dyn LabelRepository>;
+}
+
+impl<T> LabelClone for T
+where
+    T: 'static + LabelRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn LabelRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn LabelRepository> {
+    fn clone(&self) -> Box<dyn LabelRepository> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+/// Operation rebuilding derived data after a bulk import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceAction {
+    /// Rebuilds the indexes the searches and the lists of speeches go through, and
+    /// refreshes their statistics.
+    ReindexSearch,
+    /// Computes again the clusters of the speeches delivering the same text.
+    RecomputeScores,
+    /// Deletes the sentences whose speech no longer exists, and the annotations of the
+    /// deleted sentences.
+    VacuumOrphans,
+    /// Exports the whole database to the backup target.
+    Backup,
+}
+
+impl TryFrom<&str> for MaintenanceAction {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "reindex_search" => Self::ReindexSearch,
+            "recompute_scores" => Self::RecomputeScores,
+            "vacuum_orphans" => Self::VacuumOrphans,
+            "backup" => Self::Backup,
+            _ => return Err("Unexpected maintenance action value".to_owned()),
+        })
+    }
+}
+
+impl Display for MaintenanceAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MaintenanceAction::ReindexSearch => f.write_str("reindex_search"),
+            MaintenanceAction::RecomputeScores => f.write_str("recompute_scores"),
+            MaintenanceAction::VacuumOrphans => f.write_str("vacuum_orphans"),
+            MaintenanceAction::Backup => f.write_str("backup"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaintenanceAction;
+
+    #[test]
+    fn actions_are_parsed_from_their_name() {
+        let action = MaintenanceAction::try_from("vacuum_orphans").unwrap();
+        assert_eq!(action, MaintenanceAction::VacuumOrphans);
+        assert_eq!(action.to_string(), "vacuum_orphans");
+        assert!(MaintenanceAction::try_from("vacuum").is_err());
+    }
+}
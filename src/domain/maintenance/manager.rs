@@ -0,0 +1,45 @@
+use crate::domain::backup::{BackupError, BackupManager, BackupReport};
+
+use super::repository::{MaintenanceRepository, MaintenanceRepositoryError};
+
+#[derive(Clone)]
+pub struct MaintenanceManager {
+    repository: Box<dyn MaintenanceRepository>,
+    /// Backups of the database, disabled when missing.
+    backup_manager: Option<BackupManager>,
+}
+
+impl MaintenanceManager {
+    pub fn new(repository: Box<dyn MaintenanceRepository>) -> Self {
+        MaintenanceManager {
+            repository,
+            backup_manager: None,
+        }
+    }
+
+    /// Sets where and how the database is backed up, enabling the backups.
+    pub fn with_backup(mut self, backup_manager: BackupManager) -> Self {
+        self.backup_manager = Some(backup_manager);
+        self
+    }
+
+    pub async fn reindex_search(&self) -> Result<(), MaintenanceRepositoryError> {
+        self.repository.reindex_search().await
+    }
+
+    pub async fn vacuum_orphans(&self) -> Result<u64, MaintenanceRepositoryError> {
+        self.repository.vacuum_orphans().await
+    }
+
+    pub fn backups_enabled(&self) -> bool {
+        self.backup_manager.is_some()
+    }
+
+    pub async fn backup(&self) -> Result<BackupReport, BackupError> {
+        self.backup_manager
+            .as_ref()
+            .ok_or(BackupError::Unavailable)?
+            .backup()
+            .await
+    }
+}
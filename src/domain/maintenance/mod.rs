@@ -0,0 +1,7 @@
+mod action;
+mod manager;
+mod repository;
+
+pub use action::MaintenanceAction;
+pub use manager::MaintenanceManager;
+pub use repository::{MaintenanceRepository, MaintenanceRepositoryError};
@@ -0,0 +1,36 @@
+#[derive(Debug, PartialEq)]
+pub enum MaintenanceRepositoryError {
+    InternalError(String),
+}
+
+/// Batch operations on the whole database, across the organizations. Their runs are
+/// recorded as jobs.
+#[async_trait::async_trait]
+pub trait MaintenanceRepository: MaintenanceClone + Send + Sync {
+    /// Rebuilds the indexes of the speeches and the sentences without locking their
+    /// writes.
+    async fn reindex_search(&self) -> Result<(), MaintenanceRepositoryError>;
+    /// Deletes the sentences left without a speech, and the annotations of the sentences
+    /// which no longer exist. Returns the number of rows deleted.
+    async fn vacuum_orphans(&self) -> Result<u64, MaintenanceRepositoryError>;
+}
+
+pub trait MaintenanceClone {
+    fn clone_box(&self) -> Box<dyn MaintenanceRepository>;
+}
+
+impl<T> MaintenanceClone for T
+where
+    T: 'static + MaintenanceRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn MaintenanceRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn MaintenanceRepository> {
+    fn clone(&self) -> Box<dyn MaintenanceRepository> {
+        self.clone_box()
+    }
+}
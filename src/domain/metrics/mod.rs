@@ -0,0 +1,6 @@
+mod repository_errors;
+
+pub use repository_errors::{
+    record_repository_error, repository_error_summary, RepositoryErrorClass,
+    RepositoryErrorSummary, MAX_SUMMARY_PERIOD,
+};
@@ -0,0 +1,166 @@
+use std::{collections::VecDeque, sync::Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use lazy_static::lazy_static;
+
+/// Cause of a failed repository call, telling an overloaded database from clients sending
+/// conflicting data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepositoryErrorClass {
+    /// The database did not answer within the repository timeout.
+    Timeout,
+    /// A write was refused by a unique, check or foreign key constraint.
+    ConstraintViolation,
+    /// The database could not be reached or the connection was lost.
+    Connection,
+    /// A value could not be converted from or to its stored form.
+    Serialization,
+    Other,
+}
+
+impl RepositoryErrorClass {
+    pub const ALL: [RepositoryErrorClass; 5] = [
+        RepositoryErrorClass::Timeout,
+        RepositoryErrorClass::ConstraintViolation,
+        RepositoryErrorClass::Connection,
+        RepositoryErrorClass::Serialization,
+        RepositoryErrorClass::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepositoryErrorClass::Timeout => "timeout",
+            RepositoryErrorClass::ConstraintViolation => "constraint_violation",
+            RepositoryErrorClass::Connection => "connection",
+            RepositoryErrorClass::Serialization => "serialization",
+            RepositoryErrorClass::Other => "other",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|class| class == self)
+            .unwrap_or_default()
+    }
+}
+
+/// Longest period a summary can cover, the older errors are forgotten.
+pub const MAX_SUMMARY_PERIOD: Duration = Duration::hours(24);
+
+/// Errors counted during one minute, by class.
+struct Bucket {
+    /// Minutes elapsed since the Unix epoch.
+    minute: i64,
+    counts: [u64; RepositoryErrorClass::ALL.len()],
+}
+
+/// Repository errors of the last `MAX_SUMMARY_PERIOD`, counted by minute so the memory
+/// used does not depend on the number of errors.
+#[derive(Default)]
+struct RepositoryErrorCounter {
+    buckets: VecDeque<Bucket>,
+}
+
+impl RepositoryErrorCounter {
+    fn record(&mut self, class: RepositoryErrorClass, at: DateTime<Utc>) {
+        let minute = at.timestamp().div_euclid(60);
+        if self.buckets.back().is_none_or(|b| b.minute != minute) {
+            self.buckets.push_back(Bucket {
+                minute,
+                counts: Default::default(),
+            });
+        }
+        if let Some(bucket) = self.buckets.back_mut() {
+            bucket.counts[class.index()] += 1;
+        }
+        let oldest = minute - MAX_SUMMARY_PERIOD.num_minutes();
+        while self.buckets.front().is_some_and(|b| b.minute <= oldest) {
+            self.buckets.pop_front();
+        }
+    }
+
+    /// Counts the errors of the minutes started within the period before `at`.
+    fn summary(&self, period: Duration, at: DateTime<Utc>) -> RepositoryErrorSummary {
+        let minute = at.timestamp().div_euclid(60);
+        let first = minute - period.num_minutes();
+        let mut counts = [0; RepositoryErrorClass::ALL.len()];
+        for bucket in self
+            .buckets
+            .iter()
+            .filter(|b| b.minute > first && b.minute <= minute)
+        {
+            for (count, bucket_count) in counts.iter_mut().zip(bucket.counts) {
+                *count += bucket_count;
+            }
+        }
+        RepositoryErrorSummary {
+            from: at - period,
+            to: at,
+            counts: RepositoryErrorClass::ALL.into_iter().zip(counts).collect(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref REPOSITORY_ERRORS: Mutex<RepositoryErrorCounter> =
+        Mutex::new(RepositoryErrorCounter::default());
+}
+
+/// Number of repository errors of each class over a period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepositoryErrorSummary {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    /// Every class, in the order of `RepositoryErrorClass::ALL`.
+    pub counts: Vec<(RepositoryErrorClass, u64)>,
+}
+
+/// Counts an error returned by a repository. Called by the repositories when they convert
+/// the errors of their database.
+pub fn record_repository_error(class: RepositoryErrorClass) {
+    if let Ok(mut errors) = REPOSITORY_ERRORS.lock() {
+        errors.record(class, Utc::now());
+    }
+}
+
+/// Counts the repository errors of the period ending now, `MAX_SUMMARY_PERIOD` at most.
+pub fn repository_error_summary(period: Duration) -> RepositoryErrorSummary {
+    let period = period.min(MAX_SUMMARY_PERIOD);
+    match REPOSITORY_ERRORS.lock() {
+        Ok(errors) => errors.summary(period, Utc::now()),
+        Err(_) => RepositoryErrorCounter::default().summary(period, Utc::now()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone, Utc};
+
+    use super::{RepositoryErrorClass, RepositoryErrorCounter};
+
+    #[test]
+    fn summary_counts_the_errors_of_the_period() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 1, 12, 0, 30).unwrap();
+        let mut counter = RepositoryErrorCounter::default();
+        counter.record(RepositoryErrorClass::Timeout, now - Duration::hours(2));
+        counter.record(RepositoryErrorClass::Timeout, now - Duration::minutes(10));
+        counter.record(RepositoryErrorClass::ConstraintViolation, now);
+        counter.record(RepositoryErrorClass::ConstraintViolation, now);
+        let summary = counter.summary(Duration::hours(1), now);
+        assert_eq!(
+            summary.counts,
+            vec![
+                (RepositoryErrorClass::Timeout, 1),
+                (RepositoryErrorClass::ConstraintViolation, 2),
+                (RepositoryErrorClass::Connection, 0),
+                (RepositoryErrorClass::Serialization, 0),
+                (RepositoryErrorClass::Other, 0),
+            ]
+        );
+        // Errors older than a day are forgotten.
+        counter.record(RepositoryErrorClass::Other, now + Duration::hours(23));
+        let summary = counter.summary(Duration::hours(24), now + Duration::hours(23));
+        assert_eq!(summary.counts[0], (RepositoryErrorClass::Timeout, 1));
+    }
+}
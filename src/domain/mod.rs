@@ -1,2 +1,22 @@
+pub mod annotation;
+pub mod attachment;
+pub mod backup;
+pub mod collection;
+pub mod filter;
+pub mod idempotency;
+pub mod job;
+pub mod label;
+pub mod maintenance;
+pub mod metrics;
+pub mod organization;
+pub mod outbox;
 pub mod person;
+pub mod pii;
+pub mod segment;
 pub mod speech;
+pub mod stats;
+pub mod tag;
+pub mod text;
+pub mod translation;
+pub mod upsert;
+pub mod watchlist;
@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use super::{
+    organization::Organization,
+    repository::{OrganizationRepository, OrganizationRepositoryError},
+};
+
+#[derive(Clone)]
+pub struct OrganizationManager {
+    repository: Box<dyn OrganizationRepository>,
+}
+
+impl OrganizationManager {
+    pub fn new(repository: Box<dyn OrganizationRepository>) -> Self {
+        OrganizationManager { repository }
+    }
+
+    pub async fn create_organization(
+        &self,
+        organization: Organization,
+    ) -> Result<(), OrganizationRepositoryError> {
+        self.repository.create_organization(&organization).await
+    }
+
+    pub async fn get_organizations(
+        &self,
+    ) -> Result<Vec<Organization>, OrganizationRepositoryError> {
+        self.repository.get_organizations().await
+    }
+
+    pub async fn get_organization_by_id(
+        &self,
+        uid: &Uuid,
+    ) -> Result<Organization, OrganizationRepositoryError> {
+        self.repository.get_organization_by_id(uid).await
+    }
+
+    pub async fn rename_organization(
+        &self,
+        uid: &Uuid,
+        name: &str,
+    ) -> Result<(), OrganizationRepositoryError> {
+        self.repository.rename_organization(uid, name).await
+    }
+
+    pub async fn delete_organization(&self, uid: &Uuid) -> Result<(), OrganizationRepositoryError> {
+        self.repository.delete_organization(uid).await
+    }
+}
@@ -0,0 +1,7 @@
+mod manager;
+mod organization;
+mod repository;
+
+pub use manager::OrganizationManager;
+pub use organization::Organization;
+pub use repository::{OrganizationRepository, OrganizationRepositoryError};
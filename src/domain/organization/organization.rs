@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Newsroom hosted on the deployment. Persons, speeches and labels belong to one
+/// organization and are never visible from another one.
+#[derive(Debug, Clone)]
+pub struct Organization {
+    uid: Uuid,
+    name: String,
+    created_at: DateTime<Utc>,
+}
+
+impl Organization {
+    pub fn new(uid: Uuid, name: &str, created_at: DateTime<Utc>) -> Self {
+        Self {
+            uid,
+            name: name.to_string(),
+            created_at,
+        }
+    }
+
+    pub fn uid(&self) -> &Uuid {
+        &self.uid
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
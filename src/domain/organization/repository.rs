@@ -0,0 +1,52 @@
+use uuid::Uuid;
+
+use super::organization::Organization;
+
+#[derive(Debug, PartialEq)]
+pub enum OrganizationRepositoryError {
+    OrganizationNotFound,
+    OrganizationAlreadyExists,
+    /// The organization still owns persons, speeches or labels.
+    OrganizationNotEmpty,
+    InternalError(String),
+}
+
+#[async_trait::async_trait]
+pub trait OrganizationRepository: OrganizationClone + Send + Sync {
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+    ) -> Result<(), OrganizationRepositoryError>;
+    async fn get_organizations(&self) -> Result<Vec<Organization>, OrganizationRepositoryError>;
+    async fn get_organization_by_id(
+        &self,
+        uid: &Uuid,
+    ) -> Result<Organization, OrganizationRepositoryError>;
+    async fn rename_organization(
+        &self,
+        uid: &Uuid,
+        name: &str,
+    ) -> Result<(), OrganizationRepositoryError>;
+    /// Deletes an organization which does not own any data anymore.
+    async fn delete_organization(&self, uid: &Uuid) -> Result<(), OrganizationRepositoryError>;
+}
+
+pub trait OrganizationClone {
+    fn clone_box(&self) -> Box<dyn OrganizationRepository>;
+}
+
+impl<T> OrganizationClone for T
+where
+    T: 'static + OrganizationRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn OrganizationRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn OrganizationRepository> {
+    fn clone(&self) -> Box<dyn OrganizationRepository> {
+        self.clone_box()
+    }
+}
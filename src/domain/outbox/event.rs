@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Place of an event in the outbox. Events are published in the order of their positions,
+/// the transaction first so an event committed later is never placed before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OutboxPosition {
+    pub transaction: i64,
+    pub event: i64,
+}
+
+/// Domain event written in the outbox with the change it describes, waiting to be
+/// published to the broker.
+#[derive(Debug, Clone)]
+pub struct OutboxEvent {
+    position: OutboxPosition,
+    /// Kind of entity changed, e.g. `speech` or `person`.
+    topic: String,
+    entity: Uuid,
+    /// Change made, e.g. `created` or `validated`.
+    kind: String,
+    payload: Value,
+    organization: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl OutboxEvent {
+    pub fn new(
+        position: OutboxPosition,
+        topic: &str,
+        entity: Uuid,
+        kind: &str,
+        payload: Value,
+        organization: Option<Uuid>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            position,
+            topic: topic.to_string(),
+            entity,
+            kind: kind.to_string(),
+            payload,
+            organization,
+            created_at,
+        }
+    }
+
+    pub fn position(&self) -> OutboxPosition {
+        self.position
+    }
+
+    /// Unique id of the event, a consumer may receive an event more than once and tell
+    /// the copies by it.
+    pub fn id(&self) -> i64 {
+        self.position.event
+    }
+
+    pub fn topic(&self) -> &String {
+        &self.topic
+    }
+
+    pub fn entity(&self) -> &Uuid {
+        &self.entity
+    }
+
+    pub fn kind(&self) -> &String {
+        &self.kind
+    }
+
+    pub fn payload(&self) -> &Value {
+        &self.payload
+    }
+
+    pub fn organization(&self) -> Option<&Uuid> {
+        self.organization.as_ref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    /// Message sent to the broker, whatever the broker.
+    pub fn message(&self) -> Value {
+        json!({
+            "id": self.id(),
+            "topic": self.topic,
+            "kind": self.kind,
+            "entity": self.entity.to_string(),
+            "organization": self.organization.map(|o| o.to_string()),
+            "createdAt": self.created_at.to_rfc3339(),
+            "data": self.payload,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::{OutboxEvent, OutboxPosition};
+
+    #[test]
+    fn positions_are_ordered_by_transaction_first() {
+        let earlier = OutboxPosition {
+            transaction: 10,
+            event: 42,
+        };
+        let later = OutboxPosition {
+            transaction: 11,
+            event: 7,
+        };
+        assert!(earlier < later);
+        assert!(OutboxPosition::default() < earlier);
+    }
+
+    #[test]
+    fn message_wraps_the_payload() {
+        let entity = Uuid::new_v4();
+        let event = OutboxEvent::new(
+            OutboxPosition {
+                transaction: 3,
+                event: 12,
+            },
+            "speech",
+            entity,
+            "validated",
+            json!({ "status": "VALIDATED" }),
+            None,
+            Utc::now(),
+        );
+        let message = event.message();
+        assert_eq!(message["id"], 12);
+        assert_eq!(message["entity"], entity.to_string());
+        assert_eq!(message["organization"], serde_json::Value::Null);
+        assert_eq!(message["data"]["status"], "VALIDATED");
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{
+    publisher::EventPublisher,
+    repository::{OutboxRepository, OutboxRepositoryError},
+};
+
+/// Events published at most by a relay.
+const OUTBOX_BATCH_SIZE: u16 = 100;
+/// Time an instance runs the relay before another may take it over, if it stops renewing
+/// its lease.
+const RELAY_LEASE: Duration = Duration::from_secs(30);
+/// Relay publishing the events to the broker.
+const RELAY: &str = "broker";
+
+/// Publishes the events of the outbox to the broker, at least once each and in order.
+/// A single instance runs the relay at a time, the others wait for its lease to end.
+#[derive(Clone)]
+pub struct OutboxManager {
+    repository: Box<dyn OutboxRepository>,
+    publisher: Box<dyn EventPublisher>,
+    /// Holder of the relay lease, unique to the instance.
+    holder: String,
+}
+
+impl OutboxManager {
+    pub fn new(repository: Box<dyn OutboxRepository>, publisher: Box<dyn EventPublisher>) -> Self {
+        OutboxManager {
+            repository,
+            publisher,
+            holder: Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Publishes the next events of the outbox. Returns the number of events published,
+    /// zero when another instance runs the relay. The events published before a failure of
+    /// the broker are recorded, the next relay starts again from the failed one.
+    pub async fn relay_events(&self) -> Result<usize, OutboxRepositoryError> {
+        let start = match self
+            .repository
+            .claim_relay(RELAY, &self.holder, RELAY_LEASE)
+            .await?
+        {
+            Some(position) => position,
+            None => return Ok(0),
+        };
+        let events = self
+            .repository
+            .get_events_after(start, OUTBOX_BATCH_SIZE)
+            .await?;
+        let mut published = start;
+        let mut result = Ok(events.len());
+        for event in &events {
+            if let Err(e) = self.publisher.publish(event).await {
+                result = Err(e.into());
+                break;
+            }
+            published = event.position();
+        }
+        if published != start {
+            self.repository
+                .save_position(RELAY, &self.holder, published)
+                .await?;
+        }
+        result
+    }
+
+    /// Deletes the published events written more than `retention` ago. Only the instance
+    /// running the relay deletes them.
+    pub async fn prune_events(&self, retention: Duration) -> Result<u64, OutboxRepositoryError> {
+        let position = match self
+            .repository
+            .claim_relay(RELAY, &self.holder, RELAY_LEASE)
+            .await?
+        {
+            Some(position) => position,
+            None => return Ok(0),
+        };
+        let before = Utc::now()
+            - chrono::Duration::from_std(retention)
+                .map_err(|e| OutboxRepositoryError::InternalError(e.to_string()))?;
+        self.repository.delete_events(position, before).await
+    }
+}
@@ -0,0 +1,9 @@
+mod event;
+mod manager;
+mod publisher;
+mod repository;
+
+pub use event::{OutboxEvent, OutboxPosition};
+pub use manager::OutboxManager;
+pub use publisher::{EventPublisher, EventPublisherError};
+pub use repository::{OutboxRepository, OutboxRepositoryError};
@@ -0,0 +1,32 @@
+use super::event::OutboxEvent;
+
+#[derive(Debug, PartialEq)]
+pub enum EventPublisherError {
+    BrokerError(String),
+}
+
+/// Broker the domain events are published to, e.g. Kafka or NATS.
+#[async_trait::async_trait]
+pub trait EventPublisher: EventPublisherClone + Send + Sync {
+    /// Publishes the event, only returning once the broker has stored it.
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), EventPublisherError>;
+}
+
+pub trait EventPublisherClone {
+    fn clone_box(&self) -> Box<dyn EventPublisher>;
+}
+
+impl<T> EventPublisherClone for T
+where
+    T: 'static + EventPublisher + Clone,
+{
+    fn clone_box(&self) -> Box<dyn EventPublisher> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn EventPublisher> {
+    fn clone(&self) -> Box<dyn EventPublisher> {
+        self.clone_box()
+    }
+}
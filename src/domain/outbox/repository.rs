@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::{
+    event::{OutboxEvent, OutboxPosition},
+    publisher::EventPublisherError,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum OutboxRepositoryError {
+    PublisherError(EventPublisherError),
+    InternalError(String),
+}
+
+impl From<EventPublisherError> for OutboxRepositoryError {
+    fn from(value: EventPublisherError) -> Self {
+        Self::PublisherError(value)
+    }
+}
+
+/// Events written by the repositories of the entities, in the transactions of the changes
+/// they describe.
+#[async_trait::async_trait]
+pub trait OutboxRepository: OutboxClone + Send + Sync {
+    /// Leases the relay to `holder` for `lease`, unless another holder leases it. Returns the
+    /// position of the last event published by the relay, `None` when leased by another.
+    async fn claim_relay(
+        &self,
+        relay: &str,
+        holder: &str,
+        lease: Duration,
+    ) -> Result<Option<OutboxPosition>, OutboxRepositoryError>;
+    /// Returns the first `quantity` events after the position, among the events whose
+    /// transactions are committed and cannot be followed by an older one.
+    async fn get_events_after(
+        &self,
+        position: OutboxPosition,
+        quantity: u16,
+    ) -> Result<Vec<OutboxEvent>, OutboxRepositoryError>;
+    /// Records the last event published by the relay, as long as `holder` leases it.
+    async fn save_position(
+        &self,
+        relay: &str,
+        holder: &str,
+        position: OutboxPosition,
+    ) -> Result<(), OutboxRepositoryError>;
+    /// Deletes the events at or before the position written before `before`.
+    async fn delete_events(
+        &self,
+        position: OutboxPosition,
+        before: DateTime<Utc>,
+    ) -> Result<u64, OutboxRepositoryError>;
+}
+
+pub trait OutboxClone {
+    fn clone_box(&self) -> Box<dyn OutboxRepository>;
+}
+
+impl<T> OutboxClone for T
+where
+    T: 'static + OutboxRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn OutboxRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn OutboxRepository> {
+    fn clone(&self) -> Box<dyn OutboxRepository> {
+        self.clone_box()
+    }
+}
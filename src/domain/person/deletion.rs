@@ -0,0 +1,46 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::person::Person;
+
+/// What the deletion of a person does to the speeches the person speaks in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeleteStrategy {
+    /// The person is not deleted while a speech not deleted references it.
+    #[default]
+    Restrict,
+    /// The speeches and sentences of the person become the unknown speaker's, see
+    /// `unknown_speaker`.
+    Anonymize,
+    /// The sentences of the person are removed from the speeches, and the person from
+    /// their speakers.
+    Cascade,
+}
+
+impl TryFrom<&str> for DeleteStrategy {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "restrict" => Self::Restrict,
+            "anonymize" => Self::Anonymize,
+            "cascade" => Self::Cascade,
+            _ => return Err("Unexpected delete strategy value".to_owned()),
+        })
+    }
+}
+
+pub const UNKNOWN_SPEAKER_NAME: &str = "Speaker";
+pub const UNKNOWN_SPEAKER_FIRST_NAME: &str = "Unknown";
+
+/// Person the sentences of the anonymized persons are given to, one by organization. It
+/// is found by its identity: the uid of this one is only used when none is stored yet.
+pub fn unknown_speaker() -> Person {
+    Person::new(
+        Uuid::new_v4(),
+        UNKNOWN_SPEAKER_NAME,
+        UNKNOWN_SPEAKER_FIRST_NAME,
+        NaiveDate::from_ymd_opt(1, 1, 1).expect("Should not fail"),
+        0,
+        0,
+    )
+}
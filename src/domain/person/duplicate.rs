@@ -0,0 +1,94 @@
+use super::person::Person;
+
+/// Largest gap, in days, between the birth dates of two persons still reported as
+/// probable duplicates.
+pub const DUPLICATE_BIRTH_DATE_WINDOW: i64 = 365;
+
+/// Two persons probably recording the same individual. The person has the smaller uid,
+/// the duplicate is the one to merge into it.
+#[derive(Debug)]
+pub struct PersonDuplicate {
+    pub person: Person,
+    pub duplicate: Person,
+    /// Between 0.5 and 1, 1 being the same identity written differently.
+    pub score: f64,
+}
+
+/// Name compared between the persons: lowercase, without accents, spaces, hyphens nor
+/// apostrophes. Postgres stores it with each person to compare them.
+pub fn normalize_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'â' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'î' | 'ï' => 'i',
+            'ô' | 'ö' => 'o',
+            'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            c => c,
+        })
+        .filter(|c| c.is_ascii_lowercase())
+        .collect()
+}
+
+/// Scores the two persons as duplicates, `None` when they are not. Their normalized names
+/// must be equal, their first names equal or sharing their initial and their birth dates
+/// at most `DUPLICATE_BIRTH_DATE_WINDOW` days apart. The name counts for 0.5, an equal
+/// first name for 0.3 (0.15 for a shared initial) and close birth dates for up to 0.2.
+pub fn duplicate_score(person: &Person, other: &Person) -> Option<f64> {
+    if normalize_name(person.name()) != normalize_name(other.name()) {
+        return None;
+    }
+    let first_name = normalize_name(person.first_name());
+    let other_first_name = normalize_name(other.first_name());
+    let first_name_score = if first_name == other_first_name {
+        0.3
+    } else if first_name.chars().next() == other_first_name.chars().next() {
+        0.15
+    } else {
+        return None;
+    };
+    let days = (*person.birth_date() - *other.birth_date())
+        .num_days()
+        .abs();
+    if days > DUPLICATE_BIRTH_DATE_WINDOW {
+        return None;
+    }
+    let birth_date_score = 0.2 * (1.0 - days as f64 / DUPLICATE_BIRTH_DATE_WINDOW as f64);
+    Some(0.5 + first_name_score + birth_date_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::{duplicate_score, normalize_name, Person};
+
+    fn person(name: &str, first_name: &str, birth_date: &str) -> Person {
+        Person::new(
+            Uuid::new_v4(),
+            name,
+            first_name,
+            birth_date.parse::<NaiveDate>().unwrap(),
+            50,
+            0,
+        )
+    }
+
+    #[test]
+    fn duplicates_are_scored_on_name_first_name_and_birth_date() {
+        assert_eq!(normalize_name("Le Pen-Maréchal"), "lepenmarechal");
+        let person_a = person("Mélenchon", "Jean-Luc", "1951-08-19");
+        let same = person("MELENCHON", "Jean Luc", "1951-08-19");
+        assert_eq!(duplicate_score(&person_a, &same), Some(1.0));
+        let initial = person("Melenchon", "J.", "1951-08-19");
+        let score = duplicate_score(&person_a, &initial).unwrap();
+        assert!((score - 0.85).abs() < 1e-9);
+        let far = person("Mélenchon", "Jean-Luc", "1953-08-19");
+        assert_eq!(duplicate_score(&person_a, &far), None);
+        let other = person("Mélenchon", "Paul", "1951-08-19");
+        assert_eq!(duplicate_score(&person_a, &other), None);
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PersonEventKind {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+}
+
+impl Display for PersonEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersonEventKind::Created => f.write_str("created"),
+            PersonEventKind::Updated => f.write_str("updated"),
+            PersonEventKind::Deleted => f.write_str("deleted"),
+            PersonEventKind::Restored => f.write_str("restored"),
+        }
+    }
+}
+
+/// Change made to a person, published to the services following the persons.
+#[derive(Debug, Clone)]
+pub struct PersonEvent {
+    kind: PersonEventKind,
+    person: Uuid,
+    organization: Option<Uuid>,
+    at: DateTime<Utc>,
+}
+
+impl PersonEvent {
+    pub fn new(kind: PersonEventKind, person: &Uuid, organization: Option<Uuid>) -> Self {
+        Self {
+            kind,
+            person: *person,
+            organization,
+            at: Utc::now(),
+        }
+    }
+
+    pub fn kind(&self) -> PersonEventKind {
+        self.kind
+    }
+
+    pub fn person(&self) -> &Uuid {
+        &self.person
+    }
+
+    pub fn organization(&self) -> Option<&Uuid> {
+        self.organization.as_ref()
+    }
+
+    pub fn at(&self) -> &DateTime<Utc> {
+        &self.at
+    }
+}
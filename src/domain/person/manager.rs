@@ -1,25 +1,142 @@
+use tokio::sync::broadcast;
+
 use super::{
+    deletion::DeleteStrategy,
+    duplicate::PersonDuplicate,
+    event::{PersonEvent, PersonEventKind},
     person::Person,
-    repository::{GetPeopleResponse, PersonRepository, PersonRepositoryError},
+    repository::{GetPeopleResponse, PersonFilter, PersonRepository, PersonRepositoryError},
+};
+use crate::domain::{
+    collection::{CollectionVersionError, CollectionVersions},
+    upsert::{OnConflict, UpsertOutcome},
 };
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct PersonManager {
     repository: Box<dyn PersonRepository>,
+    events: Option<broadcast::Sender<PersonEvent>>,
+    collection_versions: Option<CollectionVersions>,
+    /// Organization the repository is restricted to, stamped on the published events.
+    organization: Option<Uuid>,
 }
 
 impl PersonManager {
     pub fn new(repository: Box<dyn PersonRepository>) -> Self {
-        return PersonManager { repository };
+        return PersonManager {
+            repository,
+            events: None,
+            collection_versions: None,
+            organization: None,
+        };
+    }
+
+    /// Returns a manager whose operations only reach the persons of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            events: self.events.clone(),
+            collection_versions: self
+                .collection_versions
+                .as_ref()
+                .map(|versions| versions.for_organization(organization)),
+            organization,
+        }
+    }
+
+    /// Publishes the changes made to the persons on the channel.
+    pub fn with_events(mut self, events: broadcast::Sender<PersonEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Bumps the versions of the collections on every change made to the persons.
+    pub fn with_collection_versions(mut self, collection_versions: CollectionVersions) -> Self {
+        self.collection_versions = Some(collection_versions);
+        self
+    }
+
+    /// Bumps the version of the collections of the organization once a change is stored.
+    async fn bump_collection_version(&self) -> Result<(), PersonRepositoryError> {
+        if let Some(versions) = &self.collection_versions {
+            versions
+                .bump()
+                .await
+                .map_err(|CollectionVersionError::InternalError(e)| {
+                    PersonRepositoryError::InternalError(e)
+                })?;
+        }
+        Ok(())
+    }
+
+    fn publish(&self, kind: PersonEventKind, uid: &Uuid) {
+        if let Some(events) = &self.events {
+            // Sending only fails when nobody follows the persons.
+            let _ = events.send(PersonEvent::new(kind, uid, self.organization));
+        }
     }
 
     pub async fn create_person(&self, person: Person) -> Result<(), PersonRepositoryError> {
-        self.repository.create_person(&person).await
+        self.repository.create_person(&person).await?;
+        self.bump_collection_version().await?;
+        self.publish(PersonEventKind::Created, person.uid());
+        Ok(())
     }
 
-    pub async fn _update_person(&self, person: Person) -> Result<(), PersonRepositoryError> {
-        self.repository.update_person(&person).await
+    /// Creates the person, or resolves the conflict with the person of the same identity,
+    /// see `PersonRepository::upsert_person`.
+    pub async fn upsert_person(
+        &self,
+        person: Person,
+        on_conflict: OnConflict,
+    ) -> Result<UpsertOutcome, PersonRepositoryError> {
+        let outcome = self.repository.upsert_person(&person, on_conflict).await?;
+        if !matches!(outcome, UpsertOutcome::Skipped(_)) {
+            self.bump_collection_version().await?;
+        }
+        match outcome {
+            UpsertOutcome::Created(uid) => self.publish(PersonEventKind::Created, &uid),
+            UpsertOutcome::Updated(uid) => self.publish(PersonEventKind::Updated, &uid),
+            UpsertOutcome::Skipped(_) => {}
+        }
+        Ok(outcome)
+    }
+
+    /// Creates the persons whose identity is not taken yet, see
+    /// `PersonRepository::create_people`.
+    pub async fn create_people(
+        &self,
+        people: &[Person],
+    ) -> Result<Vec<Option<Uuid>>, PersonRepositoryError> {
+        let stored = self.repository.create_people(people).await?;
+        let created = people
+            .iter()
+            .zip(&stored)
+            .filter(|(person, uid)| uid.as_ref() == Some(person.uid()))
+            .map(|(person, _)| person.uid())
+            .collect::<Vec<&Uuid>>();
+        if !created.is_empty() {
+            self.bump_collection_version().await?;
+        }
+        for uid in created {
+            self.publish(PersonEventKind::Created, uid);
+        }
+        Ok(stored)
+    }
+
+    /// Updates the person, only if it still has the version expected when one is given.
+    pub async fn update_person(
+        &self,
+        person: Person,
+        expected_version: Option<u32>,
+    ) -> Result<(), PersonRepositoryError> {
+        self.repository
+            .update_person(&person, expected_version)
+            .await?;
+        self.bump_collection_version().await?;
+        self.publish(PersonEventKind::Updated, person.uid());
+        Ok(())
     }
 
     pub async fn get_person_by_id(&self, uid: &Uuid) -> Result<Person, PersonRepositoryError> {
@@ -30,11 +147,63 @@ impl PersonManager {
         &self,
         page: u16,
         quantity: u16,
+        filter: &PersonFilter,
     ) -> Result<GetPeopleResponse, PersonRepositoryError> {
-        self.repository.get_people(page, quantity).await
+        self.repository.get_people(page, quantity, filter).await
+    }
+
+    pub async fn count_people(&self, filter: &PersonFilter) -> Result<u64, PersonRepositoryError> {
+        self.repository.count_people(filter).await
+    }
+
+    pub async fn get_people_with_speech_count(
+        &self,
+        after: Option<Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<(Person, u64)>, PersonRepositoryError> {
+        self.repository
+            .get_people_with_speech_count(after, quantity)
+            .await
+    }
+
+    /// Deletes the person, see `PersonRepository::delete_person`.
+    pub async fn delete_person(
+        &self,
+        uid: &Uuid,
+        strategy: DeleteStrategy,
+    ) -> Result<(), PersonRepositoryError> {
+        self.repository.delete_person(uid, strategy).await?;
+        self.bump_collection_version().await?;
+        self.publish(PersonEventKind::Deleted, uid);
+        Ok(())
+    }
+
+    /// Merges the duplicate identity into the person, see `PersonRepository::merge_person`.
+    /// Returns the speeches whose speakers changed, to be announced by the speech manager.
+    pub async fn merge_person(
+        &self,
+        uid: &Uuid,
+        duplicate: &Uuid,
+    ) -> Result<Vec<Uuid>, PersonRepositoryError> {
+        let speeches = self.repository.merge_person(uid, duplicate).await?;
+        self.bump_collection_version().await?;
+        self.publish(PersonEventKind::Updated, uid);
+        self.publish(PersonEventKind::Deleted, duplicate);
+        Ok(speeches)
+    }
+
+    /// Reports the probable duplicate persons, to be merged by an administrator.
+    pub async fn find_duplicate_people(
+        &self,
+        quantity: u16,
+    ) -> Result<Vec<PersonDuplicate>, PersonRepositoryError> {
+        self.repository.find_duplicate_people(quantity).await
     }
 
-    pub async fn delete_person(&self, uid: &Uuid) -> Result<(), PersonRepositoryError> {
-        self.repository.delete_person(uid).await
+    pub async fn restore_person(&self, uid: &Uuid) -> Result<(), PersonRepositoryError> {
+        self.repository.restore_person(uid).await?;
+        self.bump_collection_version().await?;
+        self.publish(PersonEventKind::Restored, uid);
+        Ok(())
     }
 }
@@ -1,7 +1,19 @@
+mod deletion;
+mod duplicate;
+mod event;
 mod manager;
 mod person;
 mod repository;
 
+pub use deletion::{
+    unknown_speaker, DeleteStrategy, UNKNOWN_SPEAKER_FIRST_NAME, UNKNOWN_SPEAKER_NAME,
+};
+pub use duplicate::{
+    duplicate_score, normalize_name, PersonDuplicate, DUPLICATE_BIRTH_DATE_WINDOW,
+};
+pub use event::{PersonEvent, PersonEventKind};
 pub use manager::PersonManager;
 pub use person::Person;
-pub use repository::{GetPeopleResponse, PersonRepository, PersonRepositoryError};
+pub use repository::{
+    GetPeopleResponse, PersonField, PersonFilter, PersonRepository, PersonRepositoryError,
+};
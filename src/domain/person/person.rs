@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Person {
     uid: Uuid,
     name: String,
@@ -9,6 +9,8 @@ pub struct Person {
     birth_date: NaiveDate,
     trust_score: u8,
     lie_quantity: u64,
+    /// Incremented by every update of the stored person.
+    version: u32,
 }
 
 impl Person {
@@ -27,9 +29,15 @@ impl Person {
             birth_date,
             trust_score,
             lie_quantity,
+            version: 1,
         }
     }
 
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
     pub fn uid(&self) -> &Uuid {
         &self.uid
     }
@@ -48,4 +56,7 @@ impl Person {
     pub fn lie_quantity(&self) -> u64 {
         self.lie_quantity
     }
+    pub fn version(&self) -> u32 {
+        self.version
+    }
 }
@@ -1,10 +1,18 @@
-use super::person::Person;
+use super::{deletion::DeleteStrategy, duplicate::PersonDuplicate, person::Person};
+use crate::domain::{
+    filter::{FilterField, FilterSpec, FilterValueKind},
+    upsert::{OnConflict, UpsertOutcome},
+};
 use uuid::Uuid;
 
 #[derive(Debug, PartialEq)]
 pub enum PersonRepositoryError {
     PersonNotFound,
     PersonAlreadyExists,
+    /// The person was updated since the version given by the client was read.
+    VersionMismatch,
+    /// The person still speaks in speeches not deleted, their number given.
+    PersonReferenced(u64),
     InternalError(String),
 }
 
@@ -13,17 +21,118 @@ pub struct GetPeopleResponse {
     pub nb_person: u64,
 }
 
+/// Criteria shared by the person list and count queries.
+#[derive(Debug, Default, Clone)]
+pub struct PersonFilter {
+    /// Persons tagged with at least one of these labels.
+    pub labels: Vec<Uuid>,
+    /// User reading the list, only the public labels and the labels of this user
+    /// matching `labels`.
+    pub label_reader: String,
+    /// Conditions on the fields of the persons.
+    pub spec: FilterSpec<PersonField>,
+    pub include_deleted: bool,
+}
+
+/// Fields of a person its lists can be filtered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersonField {
+    Name,
+    FirstName,
+    BirthDate,
+    TrustScore,
+    LieQuantity,
+}
+
+impl FilterField for PersonField {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "name" => Self::Name,
+            "firstName" => Self::FirstName,
+            "birthDate" => Self::BirthDate,
+            "trustScore" => Self::TrustScore,
+            "lieQuantity" => Self::LieQuantity,
+            _ => return None,
+        })
+    }
+
+    fn kind(&self) -> FilterValueKind {
+        match self {
+            PersonField::Name | PersonField::FirstName => FilterValueKind::Text,
+            PersonField::BirthDate => FilterValueKind::Date,
+            PersonField::TrustScore | PersonField::LieQuantity => FilterValueKind::Number,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait PersonRepository: PersonClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the rows of the organization,
+    /// `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn PersonRepository>;
     async fn create_person(&self, person: &Person) -> Result<(), PersonRepositoryError>;
-    async fn update_person(&self, person: &Person) -> Result<(), PersonRepositoryError>;
+    /// Creates in batches the persons whose identity (name, first name and birth date) is
+    /// not taken yet. Returns for each person the uid stored with its identity: its own uid
+    /// when it is created, the uid of the person already stored otherwise, and `None` when
+    /// the identity belongs to a deleted person, which must be restored instead.
+    async fn create_people(
+        &self,
+        people: &[Person],
+    ) -> Result<Vec<Option<Uuid>>, PersonRepositoryError>;
+    /// Creates the person unless its identity is taken, the person stored with it being
+    /// then kept or given the trust score and the lie quantity of the person. A soft
+    /// deleted person is kept.
+    async fn upsert_person(
+        &self,
+        person: &Person,
+        on_conflict: OnConflict,
+    ) -> Result<UpsertOutcome, PersonRepositoryError>;
+    /// Replaces the identity of the person, its trust score and its lie quantity. When a
+    /// version is expected, the person is only updated if it still has this version.
+    async fn update_person(
+        &self,
+        person: &Person,
+        expected_version: Option<u32>,
+    ) -> Result<(), PersonRepositoryError>;
     async fn get_person_by_id(&self, uid: &Uuid) -> Result<Person, PersonRepositoryError>;
     async fn get_people(
         &self,
         page: u16,
         quantity: u16,
+        filter: &PersonFilter,
     ) -> Result<GetPeopleResponse, PersonRepositoryError>;
-    async fn delete_person(&self, uid: &Uuid) -> Result<(), PersonRepositoryError>;
+    async fn count_people(&self, filter: &PersonFilter) -> Result<u64, PersonRepositoryError>;
+    /// Returns up to `quantity` persons ordered by uid, starting after the `after` uid,
+    /// along with the number of speeches they speak in.
+    async fn get_people_with_speech_count(
+        &self,
+        after: Option<Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<(Person, u64)>, PersonRepositoryError>;
+    /// Soft deletes the person: the row is kept but excluded from every read. The
+    /// speeches referencing the person, deleted ones included, are handled in the same
+    /// transaction as told by the strategy.
+    async fn delete_person(
+        &self,
+        uid: &Uuid,
+        strategy: DeleteStrategy,
+    ) -> Result<(), PersonRepositoryError>;
+    async fn restore_person(&self, uid: &Uuid) -> Result<(), PersonRepositoryError>;
+    /// Merges the duplicate into the person: the speeches and sentences of the duplicate
+    /// become the person's, their lie quantities are summed and the duplicate is soft
+    /// deleted. The trust score of the person is kept. Returns the speeches whose speakers
+    /// changed.
+    async fn merge_person(
+        &self,
+        uid: &Uuid,
+        duplicate: &Uuid,
+    ) -> Result<Vec<Uuid>, PersonRepositoryError>;
+    /// Returns up to `quantity` pairs of persons probably recording the same individual,
+    /// the most probable first. See `duplicate_score` for the comparison.
+    async fn find_duplicate_people(
+        &self,
+        quantity: u16,
+    ) -> Result<Vec<PersonDuplicate>, PersonRepositoryError>;
 }
 pub trait PersonClone {
     fn clone_box(&self) -> Box<dyn PersonRepository>;
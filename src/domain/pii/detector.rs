@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+pub enum PiiDetectorError {
+    ProviderError(String),
+}
+
+/// Personal data of a private individual which should not be published as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PiiKind {
+    PhoneNumber,
+    EmailAddress,
+    PostalAddress,
+    /// Name of a child, e.g. "my daughter Emma".
+    MinorName,
+}
+
+impl TryFrom<&str> for PiiKind {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "phone_number" => Self::PhoneNumber,
+            "email_address" => Self::EmailAddress,
+            "postal_address" => Self::PostalAddress,
+            "minor_name" => Self::MinorName,
+            _ => return Err("Unexpected personal data kind value".to_owned()),
+        })
+    }
+}
+
+impl Display for PiiKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PiiKind::PhoneNumber => f.write_str("phone_number"),
+            PiiKind::EmailAddress => f.write_str("email_address"),
+            PiiKind::PostalAddress => f.write_str("postal_address"),
+            PiiKind::MinorName => f.write_str("minor_name"),
+        }
+    }
+}
+
+/// Personal data found in a text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiFinding {
+    pub kind: PiiKind,
+    /// Part of the text holding the data.
+    pub excerpt: String,
+}
+
+/// Finds the personal data of private individuals in the transcripts.
+#[async_trait::async_trait]
+pub trait PiiDetector: PiiDetectorClone + Send + Sync {
+    /// Scans every text, returning the findings of each text in order.
+    async fn detect(&self, texts: &[String]) -> Result<Vec<Vec<PiiFinding>>, PiiDetectorError>;
+}
+
+pub trait PiiDetectorClone {
+    fn clone_box(&self) -> Box<dyn PiiDetector>;
+}
+
+impl<T> PiiDetectorClone for T
+where
+    T: 'static + PiiDetector + Clone,
+{
+    fn clone_box(&self) -> Box<dyn PiiDetector> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn PiiDetector> {
+    fn clone(&self) -> Box<dyn PiiDetector> {
+        self.clone_box()
+    }
+}
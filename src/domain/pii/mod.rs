@@ -0,0 +1,3 @@
+mod detector;
+
+pub use detector::{PiiDetector, PiiDetectorError, PiiFinding, PiiKind};
@@ -0,0 +1,56 @@
+use uuid::Uuid;
+
+use super::{
+    repository::{SegmentRepository, SegmentRepositoryError},
+    segment::Segment,
+};
+
+#[derive(Clone)]
+pub struct SegmentManager {
+    repository: Box<dyn SegmentRepository>,
+}
+
+impl SegmentManager {
+    pub fn new(repository: Box<dyn SegmentRepository>) -> Self {
+        SegmentManager { repository }
+    }
+
+    /// Returns a manager whose operations only reach the segments of the speeches of the
+    /// organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+        }
+    }
+
+    pub async fn create_segment(&self, segment: Segment) -> Result<(), SegmentRepositoryError> {
+        self.repository.create_segment(&segment).await
+    }
+
+    pub async fn update_segment(&self, segment: Segment) -> Result<(), SegmentRepositoryError> {
+        self.repository.update_segment(&segment).await
+    }
+
+    pub async fn delete_segment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<(), SegmentRepositoryError> {
+        self.repository.delete_segment(speech_uid, uid).await
+    }
+
+    pub async fn get_segments(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Segment>, SegmentRepositoryError> {
+        self.repository.get_segments(speech_uid).await
+    }
+
+    pub async fn get_segment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<Segment, SegmentRepositoryError> {
+        self.repository.get_segment(speech_uid, uid).await
+    }
+}
@@ -0,0 +1,7 @@
+mod manager;
+mod repository;
+mod segment;
+
+pub use manager::SegmentManager;
+pub use repository::{SegmentRepository, SegmentRepositoryError};
+pub use segment::{check_segment, Segment};
@@ -0,0 +1,58 @@
+use uuid::Uuid;
+
+use super::segment::Segment;
+
+#[derive(Debug, PartialEq)]
+pub enum SegmentRepositoryError {
+    SpeechNotFound,
+    SegmentNotFound,
+    /// The segment is empty or goes past the last sentence of the speech.
+    OutOfBounds,
+    /// The segment shares sentences with the segment of this uid.
+    Overlap(Uuid),
+    InternalError(String),
+}
+
+/// Segments of the speeches. The segments of a speech are checked against its sentences
+/// and against each other before being stored.
+#[async_trait::async_trait]
+pub trait SegmentRepository: SegmentClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the segments of the speeches of the
+    /// organization, `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn SegmentRepository>;
+    /// Stores the segment, its speech must be a speech of the organization.
+    async fn create_segment(&self, segment: &Segment) -> Result<(), SegmentRepositoryError>;
+    async fn update_segment(&self, segment: &Segment) -> Result<(), SegmentRepositoryError>;
+    async fn delete_segment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<(), SegmentRepositoryError>;
+    /// Returns the segments of the speech, in the order of their sentences.
+    async fn get_segments(&self, speech_uid: &Uuid)
+        -> Result<Vec<Segment>, SegmentRepositoryError>;
+    async fn get_segment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<Segment, SegmentRepositoryError>;
+}
+
+pub trait SegmentClone {
+    fn clone_box(&self) -> Box<dyn SegmentRepository>;
+}
+
+impl<T> SegmentClone for T
+where
+    T: 'static + SegmentRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn SegmentRepository> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn SegmentRepository> {
+    fn clone(&self) -> Box<dyn SegmentRepository> {
+        self.clone_box()
+    }
+}
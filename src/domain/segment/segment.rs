@@ -0,0 +1,105 @@
+use uuid::Uuid;
+
+use super::repository::SegmentRepositoryError;
+
+/// Named range of consecutive sentences of a speech, e.g. a chapter of a long session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    uid: Uuid,
+    speech_uid: Uuid,
+    title: String,
+    /// Index of the first sentence of the segment.
+    start_index: u32,
+    /// Index of the last sentence of the segment, included.
+    end_index: u32,
+}
+
+impl Segment {
+    pub fn new(uid: Uuid, speech_uid: Uuid, title: &str, start_index: u32, end_index: u32) -> Self {
+        Self {
+            uid,
+            speech_uid,
+            title: title.to_string(),
+            start_index,
+            end_index,
+        }
+    }
+
+    pub fn uid(&self) -> &Uuid {
+        &self.uid
+    }
+
+    pub fn speech_uid(&self) -> &Uuid {
+        &self.speech_uid
+    }
+
+    pub fn title(&self) -> &String {
+        &self.title
+    }
+
+    pub fn start_index(&self) -> u32 {
+        self.start_index
+    }
+
+    pub fn end_index(&self) -> u32 {
+        self.end_index
+    }
+
+    /// Whether both segments contain a same sentence.
+    pub fn overlaps(&self, other: &Segment) -> bool {
+        self.start_index <= other.end_index && other.start_index <= self.end_index
+    }
+}
+
+/// Checks the segment is within the `sentences` of its speech and shares no sentence with
+/// the other segments of the speech, its stored version being ignored.
+pub fn check_segment(
+    segment: &Segment,
+    sentences: u32,
+    segments: &[Segment],
+) -> Result<(), SegmentRepositoryError> {
+    if segment.start_index > segment.end_index || segment.end_index >= sentences {
+        return Err(SegmentRepositoryError::OutOfBounds);
+    }
+    match segments
+        .iter()
+        .find(|other| other.uid != segment.uid && other.overlaps(segment))
+    {
+        Some(other) => Err(SegmentRepositoryError::Overlap(other.uid)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{check_segment, Segment};
+    use crate::domain::segment::SegmentRepositoryError;
+
+    #[test]
+    fn segments_stay_within_the_sentences_without_overlapping() {
+        let speech_uid = Uuid::new_v4();
+        let opening = Segment::new(Uuid::new_v4(), speech_uid, "Opening", 0, 4);
+        let segments = vec![opening.clone()];
+        let debate = Segment::new(Uuid::new_v4(), speech_uid, "Debate", 5, 9);
+        assert_eq!(check_segment(&debate, 10, &segments), Ok(()));
+        assert_eq!(
+            check_segment(&debate, 9, &segments),
+            Err(SegmentRepositoryError::OutOfBounds)
+        );
+        let reversed = Segment::new(Uuid::new_v4(), speech_uid, "Debate", 6, 5);
+        assert_eq!(
+            check_segment(&reversed, 10, &segments),
+            Err(SegmentRepositoryError::OutOfBounds)
+        );
+        let overlapping = Segment::new(Uuid::new_v4(), speech_uid, "Debate", 4, 9);
+        assert_eq!(
+            check_segment(&overlapping, 10, &segments),
+            Err(SegmentRepositoryError::Overlap(*opening.uid()))
+        );
+        // A segment moved over its own range is not overlapping itself.
+        let extended = Segment::new(*opening.uid(), speech_uid, "Opening", 0, 6);
+        assert_eq!(check_segment(&extended, 10, &segments), Ok(()));
+    }
+}
@@ -0,0 +1,332 @@
+use std::fmt::Display;
+
+use chrono::{Datelike, Days, NaiveDate};
+use uuid::Uuid;
+
+/// Talk-time aggregates of one speaker within a speech.
+///
+/// A sentence flagged as interrupted counts as an interruption made by the speaker of the
+/// following sentence and received by its own speaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerAnalytics {
+    speaker: Uuid,
+    sentences: u64,
+    words: u64,
+    share: f64,
+    interruptions_made: u64,
+    interruptions_received: u64,
+    spoken_duration: Option<u64>,
+    duration_share: Option<f64>,
+}
+
+impl SpeakerAnalytics {
+    pub fn new(
+        speaker: Uuid,
+        sentences: u64,
+        words: u64,
+        share: f64,
+        interruptions_made: u64,
+        interruptions_received: u64,
+    ) -> Self {
+        Self {
+            speaker,
+            sentences,
+            words,
+            share,
+            interruptions_made,
+            interruptions_received,
+            spoken_duration: None,
+            duration_share: None,
+        }
+    }
+
+    /// Sets the time spent speaking, computed from the timed sentences only.
+    pub fn with_spoken_duration(
+        mut self,
+        spoken_duration: Option<u64>,
+        duration_share: Option<f64>,
+    ) -> Self {
+        self.spoken_duration = spoken_duration;
+        self.duration_share = duration_share;
+        self
+    }
+
+    pub fn speaker(&self) -> &Uuid {
+        &self.speaker
+    }
+
+    pub fn sentences(&self) -> u64 {
+        self.sentences
+    }
+
+    pub fn words(&self) -> u64 {
+        self.words
+    }
+
+    /// Share of the words of the speech spoken by the speaker, between 0 and 1.
+    pub fn share(&self) -> f64 {
+        self.share
+    }
+
+    pub fn interruptions_made(&self) -> u64 {
+        self.interruptions_made
+    }
+
+    pub fn interruptions_received(&self) -> u64 {
+        self.interruptions_received
+    }
+
+    /// Time spent speaking in milliseconds, `None` when no sentence of the speaker is timed.
+    pub fn spoken_duration(&self) -> Option<u64> {
+        self.spoken_duration
+    }
+
+    /// Share of the timed speech spent speaking, between 0 and 1.
+    pub fn duration_share(&self) -> Option<f64> {
+        self.duration_share
+    }
+}
+
+/// Talk-time aggregates of one person across all the speeches they speak in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerStats {
+    speeches: u64,
+    sentences: u64,
+    words: u64,
+    spoken_duration: Option<u64>,
+}
+
+impl SpeakerStats {
+    pub fn new(speeches: u64, sentences: u64, words: u64, spoken_duration: Option<u64>) -> Self {
+        Self {
+            speeches,
+            sentences,
+            words,
+            spoken_duration,
+        }
+    }
+
+    pub fn speeches(&self) -> u64 {
+        self.speeches
+    }
+
+    pub fn sentences(&self) -> u64 {
+        self.sentences
+    }
+
+    pub fn words(&self) -> u64 {
+        self.words
+    }
+
+    /// Time spent speaking in milliseconds, summed over the timed sentences only.
+    pub fn spoken_duration(&self) -> Option<u64> {
+        self.spoken_duration
+    }
+}
+
+/// Number of speeches aired by a media during a month. Carries no personal data, it is
+/// published as open data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthlySpeechCount {
+    media: String,
+    /// First day of the month.
+    month: NaiveDate,
+    speeches: u64,
+}
+
+impl MonthlySpeechCount {
+    pub fn new(media: &str, month: NaiveDate, speeches: u64) -> Self {
+        Self {
+            media: media.to_string(),
+            month,
+            speeches,
+        }
+    }
+
+    pub fn media(&self) -> &String {
+        &self.media
+    }
+
+    pub fn month(&self) -> &NaiveDate {
+        &self.month
+    }
+
+    pub fn speeches(&self) -> u64 {
+        self.speeches
+    }
+}
+
+/// What the speeches of a list are counted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechGrouping {
+    Media,
+    /// A speech counts once for each of its speakers.
+    Speaker,
+    /// Month of the date of the speech, in UTC.
+    Month,
+}
+
+impl TryFrom<&str> for SpeechGrouping {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "media" => Self::Media,
+            "speaker" => Self::Speaker,
+            "month" => Self::Month,
+            _ => return Err("Unexpected speech grouping value".to_owned()),
+        })
+    }
+}
+
+impl Display for SpeechGrouping {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpeechGrouping::Media => f.write_str("media"),
+            SpeechGrouping::Speaker => f.write_str("speaker"),
+            SpeechGrouping::Month => f.write_str("month"),
+        }
+    }
+}
+
+/// Number of speeches of a list sharing a media, a speaker or a month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechGroupCount {
+    /// The media, the uid of the speaker or the month as `2024-03`.
+    key: String,
+    speeches: u64,
+}
+
+impl SpeechGroupCount {
+    pub fn new(key: &str, speeches: u64) -> Self {
+        Self {
+            key: key.to_string(),
+            speeches,
+        }
+    }
+
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    pub fn speeches(&self) -> u64 {
+        self.speeches
+    }
+}
+
+/// Length of the periods of a timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimelineGranularity {
+    /// Weeks starting on Monday.
+    Week,
+    #[default]
+    Month,
+    Quarter,
+    Year,
+}
+
+impl TimelineGranularity {
+    /// First day of the period holding the date.
+    pub fn period_start(&self, date: NaiveDate) -> NaiveDate {
+        let first_month = match self {
+            TimelineGranularity::Week => {
+                return date - Days::new(date.weekday().num_days_from_monday() as u64)
+            }
+            TimelineGranularity::Month => date.month(),
+            TimelineGranularity::Quarter => (date.month() - 1) / 3 * 3 + 1,
+            TimelineGranularity::Year => 1,
+        };
+        NaiveDate::from_ymd_opt(date.year(), first_month, 1).unwrap_or(date)
+    }
+}
+
+impl TryFrom<&str> for TimelineGranularity {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "week" => Self::Week,
+            "month" => Self::Month,
+            "quarter" => Self::Quarter,
+            "year" => Self::Year,
+            _ => return Err("Unexpected timeline granularity value".to_owned()),
+        })
+    }
+}
+
+impl Display for TimelineGranularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimelineGranularity::Week => f.write_str("week"),
+            TimelineGranularity::Month => f.write_str("month"),
+            TimelineGranularity::Quarter => f.write_str("quarter"),
+            TimelineGranularity::Year => f.write_str("year"),
+        }
+    }
+}
+
+/// Activity of a person during a period of their timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelinePeriod {
+    /// First day of the period.
+    start: NaiveDate,
+    speeches: u64,
+    sentences: u64,
+}
+
+impl TimelinePeriod {
+    pub fn new(start: NaiveDate, speeches: u64, sentences: u64) -> Self {
+        Self {
+            start,
+            speeches,
+            sentences,
+        }
+    }
+
+    pub fn start(&self) -> &NaiveDate {
+        &self.start
+    }
+
+    /// Speeches the person speaks in, dated within the period.
+    pub fn speeches(&self) -> u64 {
+        self.speeches
+    }
+
+    /// Sentences spoken by the person in the speeches dated within the period.
+    pub fn sentences(&self) -> u64 {
+        self.sentences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn truncates_dates_to_the_start_of_their_period() {
+        // 2024-08-15 is a Thursday.
+        let day = date(2024, 8, 15);
+        assert_eq!(
+            TimelineGranularity::Week.period_start(day),
+            date(2024, 8, 12)
+        );
+        assert_eq!(
+            TimelineGranularity::Month.period_start(day),
+            date(2024, 8, 1)
+        );
+        assert_eq!(
+            TimelineGranularity::Quarter.period_start(day),
+            date(2024, 7, 1)
+        );
+        assert_eq!(
+            TimelineGranularity::Year.period_start(day),
+            date(2024, 1, 1)
+        );
+        assert_eq!(
+            TimelineGranularity::Week.period_start(date(2024, 8, 12)),
+            date(2024, 8, 12)
+        );
+    }
+}
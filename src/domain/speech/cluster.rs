@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// Number of consecutive words of a shingle.
+const SHINGLE_SIZE: usize = 5;
+
+/// Number of hash functions of a signature.
+const SIGNATURE_SIZE: usize = 64;
+
+/// Signatures sharing every value of one band are compared, `BANDS * ROWS` being the size
+/// of a signature. With 16 bands of 4 rows, speeches sharing half of their shingles are
+/// compared with a probability over 60%, those sharing 80% almost always.
+const BANDS: usize = 16;
+const ROWS: usize = SIGNATURE_SIZE / BANDS;
+
+/// Estimated share of shingles two speeches must have in common to be clustered.
+const SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Speeches delivering the same text, e.g. a stump speech given at several events.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechCluster {
+    /// Smallest uid of the speeches, so the cluster keeps its uid from one clustering to the
+    /// next as long as this speech stays in it.
+    pub uid: Uuid,
+    /// At least two speeches, by uid.
+    pub speeches: Vec<Uuid>,
+}
+
+/// MinHash signature of the word shingles of a text.
+#[derive(Debug, Clone)]
+pub struct Signature([u64; SIGNATURE_SIZE]);
+
+/// FNV-1a, stable across builds unlike the hasher of the standard library.
+fn fnv1a(words: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for word in words {
+        for byte in word.bytes().chain([b' ']) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+/// Mixes the shingle hash with the seed of a hash function (SplitMix64 finalizer).
+fn mix(value: u64, seed: u64) -> u64 {
+    let mut z = value ^ seed.wrapping_mul(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+impl Signature {
+    /// Signs the words of the text, ignoring case and punctuation. `None` is returned for
+    /// a text without words.
+    pub fn of_text(text: &str) -> Option<Self> {
+        let words = text
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<String>>();
+        if words.is_empty() {
+            return None;
+        }
+        let mut values = [u64::MAX; SIGNATURE_SIZE];
+        for shingle in words.windows(SHINGLE_SIZE.min(words.len())) {
+            let hash = fnv1a(shingle);
+            for (seed, value) in values.iter_mut().enumerate() {
+                *value = (*value).min(mix(hash, seed as u64));
+            }
+        }
+        Some(Self(values))
+    }
+
+    /// Estimated share of shingles the two texts have in common.
+    pub fn similarity(&self, other: &Signature) -> f64 {
+        let equal = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .filter(|(a, b)| a == b)
+            .count();
+        equal as f64 / SIGNATURE_SIZE as f64
+    }
+}
+
+fn find(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Groups the speeches whose texts are similar, directly or through other speeches. The
+/// speeches similar to no other are left out.
+pub fn cluster_speeches(signatures: &[(Uuid, Signature)]) -> Vec<SpeechCluster> {
+    let mut parents = (0..signatures.len()).collect::<Vec<usize>>();
+    for band in 0..BANDS {
+        let mut buckets: HashMap<&[u64], Vec<usize>> = HashMap::new();
+        for (index, (_, signature)) in signatures.iter().enumerate() {
+            buckets
+                .entry(&signature.0[band * ROWS..(band + 1) * ROWS])
+                .or_default()
+                .push(index);
+        }
+        for candidates in buckets.values().filter(|c| c.len() > 1) {
+            for (position, &a) in candidates.iter().enumerate() {
+                for &b in &candidates[position + 1..] {
+                    if find(&mut parents, a) != find(&mut parents, b)
+                        && signatures[a].1.similarity(&signatures[b].1) >= SIMILARITY_THRESHOLD
+                    {
+                        let (root_a, root_b) = (find(&mut parents, a), find(&mut parents, b));
+                        parents[root_b] = root_a;
+                    }
+                }
+            }
+        }
+    }
+    let mut groups: HashMap<usize, Vec<Uuid>> = HashMap::new();
+    for (index, (uid, _)) in signatures.iter().enumerate() {
+        let root = find(&mut parents, index);
+        groups.entry(root).or_default().push(*uid);
+    }
+    let mut clusters = groups
+        .into_values()
+        .filter(|speeches| speeches.len() > 1)
+        .map(|mut speeches| {
+            speeches.sort();
+            SpeechCluster {
+                uid: speeches[0],
+                speeches,
+            }
+        })
+        .collect::<Vec<SpeechCluster>>();
+    clusters.sort_by_key(|cluster| cluster.uid);
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{cluster_speeches, Signature};
+
+    #[test]
+    fn similar_speeches_are_clustered() {
+        let stump = "My friends, we will bring jobs back to this town, we will fix the roads \
+            and the schools, and we will make sure every family can afford to live here.";
+        let speeches = [
+            (
+                Uuid::from_u128(3),
+                format!("Thank you Springfield! {}", stump),
+            ),
+            (
+                Uuid::from_u128(1),
+                format!("{} Thank you Shelbyville!", stump),
+            ),
+            (
+                Uuid::from_u128(2),
+                "The budget debate is about taxes on fuel and the pension reform.".to_owned(),
+            ),
+            (Uuid::from_u128(4), stump.to_uppercase()),
+        ];
+        let signatures = speeches
+            .iter()
+            .map(|(uid, text)| (*uid, Signature::of_text(text).unwrap()))
+            .collect::<Vec<_>>();
+        let clusters = cluster_speeches(&signatures);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].uid, Uuid::from_u128(1));
+        assert_eq!(
+            clusters[0].speeches,
+            vec![Uuid::from_u128(1), Uuid::from_u128(3), Uuid::from_u128(4)]
+        );
+        assert!(Signature::of_text(" ... ").is_none());
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::Speech;
+
+/// Shared terms reported by default, the most frequent first.
+const DEFAULT_MAX_SHARED_TERMS: usize = 50;
+
+/// Words shorter than this are left out of the vocabulary, most of them being articles
+/// or prepositions.
+const DEFAULT_MIN_TERM_LENGTH: usize = 4;
+
+/// Figures of one of the compared speeches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparedSpeech {
+    /// Time from the start of the first timed sentence to the end of the last one, in
+    /// milliseconds. `None` when no sentence is timed.
+    pub duration: Option<u64>,
+    pub sentences: u64,
+    pub words: u64,
+    /// Number of sentences of each speaker, in the order they first speak.
+    pub sentences_by_speaker: Vec<(Uuid, u64)>,
+}
+
+/// Term used in both speeches, with its number of occurrences in each.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedTerm {
+    pub term: String,
+    pub count_a: u64,
+    pub count_b: u64,
+}
+
+/// Figures of two speeches aligned side by side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechComparison {
+    pub a: ComparedSpeech,
+    pub b: ComparedSpeech,
+    /// Terms used in both speeches, the most frequent overall first.
+    pub shared_vocabulary: Vec<SharedTerm>,
+    /// Persons speaking in both speeches.
+    pub shared_speakers: Vec<Uuid>,
+}
+
+/// Compares what was said in two speeches, e.g. two politicians on the same topic.
+#[derive(Debug, Clone)]
+pub struct ComparisonService {
+    max_shared_terms: usize,
+    min_term_length: usize,
+}
+
+impl Default for ComparisonService {
+    fn default() -> Self {
+        Self {
+            max_shared_terms: DEFAULT_MAX_SHARED_TERMS,
+            min_term_length: DEFAULT_MIN_TERM_LENGTH,
+        }
+    }
+}
+
+impl ComparisonService {
+    pub fn compare(&self, a: &Speech, b: &Speech) -> SpeechComparison {
+        let vocabulary_a = self.vocabulary(a);
+        let vocabulary_b = self.vocabulary(b);
+        let mut shared_vocabulary = vocabulary_a
+            .iter()
+            .filter_map(|(term, count_a)| {
+                Some(SharedTerm {
+                    term: term.clone(),
+                    count_a: *count_a,
+                    count_b: *vocabulary_b.get(term)?,
+                })
+            })
+            .collect::<Vec<SharedTerm>>();
+        shared_vocabulary.sort_by(|x, y| {
+            (y.count_a + y.count_b)
+                .cmp(&(x.count_a + x.count_b))
+                .then_with(|| x.term.cmp(&y.term))
+        });
+        shared_vocabulary.truncate(self.max_shared_terms);
+        let speakers_b = speakers(b);
+        let shared_speakers = speakers(a)
+            .into_iter()
+            .filter(|speaker| speakers_b.contains(speaker))
+            .collect();
+        SpeechComparison {
+            a: compared_speech(a),
+            b: compared_speech(b),
+            shared_vocabulary,
+            shared_speakers,
+        }
+    }
+
+    /// Occurrences of the terms of the speech, ignoring case and punctuation.
+    fn vocabulary(&self, speech: &Speech) -> HashMap<String, u64> {
+        let mut vocabulary = HashMap::new();
+        for sentence in speech.sentences() {
+            let terms = sentence
+                .text()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| word.chars().count() >= self.min_term_length)
+                .map(|word| word.to_lowercase());
+            for term in terms {
+                *vocabulary.entry(term).or_insert(0) += 1;
+            }
+        }
+        vocabulary
+    }
+}
+
+/// Speakers of the speech, declared or speaking, in the order they appear.
+fn speakers(speech: &Speech) -> Vec<Uuid> {
+    let mut speakers: Vec<Uuid> = Vec::new();
+    let all = speech
+        .speakers()
+        .iter()
+        .chain(speech.sentences().iter().map(|s| s.speaker()));
+    for speaker in all {
+        if !speakers.contains(speaker) {
+            speakers.push(*speaker);
+        }
+    }
+    speakers
+}
+
+fn compared_speech(speech: &Speech) -> ComparedSpeech {
+    let mut sentences_by_speaker: Vec<(Uuid, u64)> = Vec::new();
+    for sentence in speech.sentences() {
+        match sentences_by_speaker
+            .iter_mut()
+            .find(|(speaker, _)| speaker == sentence.speaker())
+        {
+            Some((_, count)) => *count += 1,
+            None => sentences_by_speaker.push((*sentence.speaker(), 1)),
+        }
+    }
+    let timings = speech.sentences().iter().filter_map(|s| s.timing());
+    let start = timings.clone().map(|t| t.start).min();
+    let end = timings.map(|t| t.end).max();
+    ComparedSpeech {
+        duration: start
+            .zip(end)
+            .map(|(start, end)| end.saturating_sub(start) as u64),
+        sentences: speech.sentences().len() as u64,
+        words: speech
+            .sentences()
+            .iter()
+            .map(|s| s.text().split_whitespace().count() as u64)
+            .sum(),
+        sentences_by_speaker,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::ComparisonService;
+    use crate::domain::speech::{
+        sentence::{Sentence, SentenceTiming},
+        Speech, SpeechStatus,
+    };
+
+    fn speech(sentences: &[(Uuid, &str)]) -> Speech {
+        let sentences = sentences
+            .iter()
+            .enumerate()
+            .map(|(index, (speaker, text))| {
+                Sentence::new(&Uuid::new_v4(), speaker, text, false).with_timing(Some(
+                    SentenceTiming {
+                        start: index as u32 * 1000,
+                        end: index as u32 * 1000 + 800,
+                    },
+                ))
+            })
+            .collect::<Vec<Sentence>>();
+        Speech::new(
+            &Uuid::new_v4(),
+            "Debate",
+            Utc::now(),
+            &[],
+            &sentences,
+            "TV",
+            SpeechStatus::Validated,
+        )
+    }
+
+    #[test]
+    fn speeches_are_aligned_on_their_terms_and_speakers() {
+        let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let a = speech(&[
+            (alice, "Les retraites doivent changer."),
+            (bob, "Les retraites, jamais !"),
+        ]);
+        let b = speech(&[
+            (carol, "Retraites et salaires."),
+            (bob, "Salaires d'abord."),
+        ]);
+        let comparison = ComparisonService::default().compare(&a, &b);
+        assert_eq!(comparison.a.sentences, 2);
+        assert_eq!(comparison.a.duration, Some(1800));
+        assert_eq!(
+            comparison.a.sentences_by_speaker,
+            vec![(alice, 1), (bob, 1)]
+        );
+        assert_eq!(comparison.shared_speakers, vec![bob]);
+        assert_eq!(comparison.shared_vocabulary.len(), 1);
+        assert_eq!(comparison.shared_vocabulary[0].term, "retraites");
+        assert_eq!(comparison.shared_vocabulary[0].count_a, 2);
+        assert_eq!(comparison.shared_vocabulary[0].count_b, 1);
+    }
+}
@@ -0,0 +1,101 @@
+use super::sentence::{Sentence, SentenceTiming};
+
+/// Cleans up the sentences of a live transcript, whose captions arrive in short and
+/// sometimes repeated segments:
+/// - whitespace is normalized and the empty segments are dropped,
+/// - a segment repeating the previous segment of the same speaker is dropped,
+/// - the consecutive segments of a speaker are merged until one ends a sentence or is
+///   interrupted.
+pub fn consolidate(sentences: &[Sentence]) -> Vec<Sentence> {
+    let mut consolidated: Vec<Sentence> = Vec::new();
+    let mut last_segment: Option<(&Sentence, String)> = None;
+    for sentence in sentences {
+        let text = sentence
+            .text()
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if text.is_empty() {
+            continue;
+        }
+        let repeated = last_segment.as_ref().is_some_and(|(last, last_text)| {
+            last.speaker() == sentence.speaker() && last_text.to_lowercase() == text.to_lowercase()
+        });
+        last_segment = Some((sentence, text.clone()));
+        if repeated {
+            continue;
+        }
+        match consolidated.last_mut() {
+            Some(previous)
+                if previous.speaker() == sentence.speaker()
+                    && !previous.interrupted()
+                    && !ends_sentence(previous.text()) =>
+            {
+                let timing = match (previous.timing(), sentence.timing()) {
+                    (Some(first), Some(last)) => Some(SentenceTiming {
+                        start: first.start,
+                        end: first.end.max(last.end),
+                    }),
+                    _ => None,
+                };
+                *previous = Sentence::new(
+                    previous.uid(),
+                    previous.speaker(),
+                    &format!("{} {}", previous.text(), text),
+                    sentence.interrupted(),
+                )
+                .with_timing(timing)
+                .with_language(previous.language().cloned());
+            }
+            _ => consolidated.push(
+                Sentence::new(
+                    sentence.uid(),
+                    sentence.speaker(),
+                    &text,
+                    sentence.interrupted(),
+                )
+                .with_timing(sentence.timing())
+                .with_language(sentence.language().cloned()),
+            ),
+        }
+    }
+    consolidated
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.ends_with(['.', '!', '?', '…'])
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::consolidate;
+    use crate::domain::speech::sentence::{Sentence, SentenceTiming};
+
+    fn segment(speaker: &Uuid, text: &str, start: u32, end: u32) -> Sentence {
+        Sentence::new(&Uuid::new_v4(), speaker, text, false)
+            .with_timing(Some(SentenceTiming { start, end }))
+    }
+
+    #[test]
+    fn consolidate_merges_segments_and_drops_repeats() {
+        let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let sentences = consolidate(&[
+            segment(&alice, "Bonsoir  à", 0, 500),
+            segment(&alice, "tous.", 500, 900),
+            segment(&alice, "Tous.", 900, 1000),
+            segment(&bob, "  ", 1000, 1100),
+            segment(&bob, "Merci.", 1100, 1500),
+        ]);
+        let texts = sentences
+            .iter()
+            .map(|s| s.text().as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(texts, ["Bonsoir à tous.", "Merci."]);
+        assert_eq!(
+            sentences[0].timing(),
+            Some(SentenceTiming { start: 0, end: 900 })
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{Speech, SpeechStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpeechEventKind {
+    Created,
+    /// The content of the speech has been replaced, by an edition, a revert or the
+    /// finalization of a live speech.
+    SentencesEdited,
+    /// The role of a speaker has changed.
+    SpeakerRoleChanged,
+    /// A speaker has been added or removed, or sentences given to another speaker.
+    SpeakersChanged,
+    Validated,
+    Deleted,
+    Restored,
+}
+
+impl Display for SpeechEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpeechEventKind::Created => f.write_str("created"),
+            SpeechEventKind::SentencesEdited => f.write_str("sentence-edited"),
+            SpeechEventKind::SpeakerRoleChanged => f.write_str("speaker-role-changed"),
+            SpeechEventKind::SpeakersChanged => f.write_str("speakers-changed"),
+            SpeechEventKind::Validated => f.write_str("validated"),
+            SpeechEventKind::Deleted => f.write_str("deleted"),
+            SpeechEventKind::Restored => f.write_str("restored"),
+        }
+    }
+}
+
+/// Change in the lifecycle of a speech, published to the clients watching the speeches.
+#[derive(Debug, Clone)]
+pub struct SpeechEvent {
+    kind: SpeechEventKind,
+    speech: Uuid,
+    /// Status of the speech once the change is made.
+    status: SpeechStatus,
+    speakers: Vec<Uuid>,
+    organization: Option<Uuid>,
+    at: DateTime<Utc>,
+}
+
+impl SpeechEvent {
+    pub fn new(kind: SpeechEventKind, speech: &Speech, organization: Option<Uuid>) -> Self {
+        Self {
+            kind,
+            speech: *speech.uid(),
+            status: speech.speech_status().clone(),
+            speakers: speech.speakers().clone(),
+            organization,
+            at: Utc::now(),
+        }
+    }
+
+    pub fn kind(&self) -> SpeechEventKind {
+        self.kind
+    }
+
+    pub fn speech(&self) -> &Uuid {
+        &self.speech
+    }
+
+    pub fn status(&self) -> &SpeechStatus {
+        &self.status
+    }
+
+    pub fn speakers(&self) -> &Vec<Uuid> {
+        &self.speakers
+    }
+
+    pub fn organization(&self) -> Option<&Uuid> {
+        self.organization.as_ref()
+    }
+
+    pub fn at(&self) -> &DateTime<Utc> {
+        &self.at
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::Utc;
+use tokio::{
+    sync::{
+        broadcast::{error::RecvError, Receiver},
+        Notify,
+    },
+    time,
+};
+
+use super::event::SpeechEvent;
+
+/// Events kept for the clients polling them, the older ones are forgotten.
+const EVENT_LOG_CAPACITY: usize = 1024;
+
+struct Log {
+    /// Cursor of the last event logged.
+    cursor: u64,
+    /// Cursor of the oldest event still kept, the events before it are forgotten.
+    first: u64,
+    events: VecDeque<(u64, SpeechEvent)>,
+}
+
+/// Events found after a cursor.
+pub struct EventBatch {
+    /// Events logged after the cursor, with their own cursor.
+    pub events: Vec<(u64, SpeechEvent)>,
+    /// Cursor to send back to get the next events.
+    pub cursor: u64,
+    /// Whether events after the cursor have been forgotten, the client must reload what
+    /// it follows instead of relying on the events.
+    pub missed: bool,
+}
+
+/// Last speech lifecycle events, numbered by a cursor so the clients which cannot keep a
+/// connection open can poll the events published since their previous request.
+///
+/// The events are kept in memory. The cursors start from the time the server started, in
+/// milliseconds, so a cursor sent before a restart is seen as missing events.
+#[derive(Clone)]
+pub struct SpeechEventLog {
+    log: Arc<Mutex<Log>>,
+    /// Notified when an event is logged.
+    logged: Arc<Notify>,
+}
+
+impl Default for SpeechEventLog {
+    fn default() -> Self {
+        let cursor = Utc::now().timestamp_millis() as u64;
+        Self {
+            log: Arc::new(Mutex::new(Log {
+                cursor,
+                first: cursor + 1,
+                events: VecDeque::new(),
+            })),
+            logged: Arc::new(Notify::new()),
+        }
+    }
+}
+
+impl SpeechEventLog {
+    /// Cursor of the last event logged.
+    pub fn cursor(&self) -> u64 {
+        match self.log.lock() {
+            Ok(log) => log.cursor,
+            Err(e) => e.get_ref().cursor,
+        }
+    }
+
+    fn push(&self, event: SpeechEvent) {
+        if let Ok(mut log) = self.log.lock() {
+            log.cursor += 1;
+            let cursor = log.cursor;
+            log.events.push_back((cursor, event));
+            if log.events.len() > EVENT_LOG_CAPACITY {
+                log.events.pop_front();
+                log.first += 1;
+            }
+        }
+        self.logged.notify_waiters();
+    }
+
+    /// Skips a cursor for events missed by the log, the clients behind it have to reload.
+    fn skip(&self) {
+        if let Ok(mut log) = self.log.lock() {
+            log.cursor += 1;
+            log.first = log.cursor + 1;
+            log.events.clear();
+        }
+        self.logged.notify_waiters();
+    }
+
+    /// Events logged after the cursor that `visible` keeps.
+    pub fn after(&self, since: u64, visible: impl Fn(&SpeechEvent) -> bool) -> EventBatch {
+        let log = match self.log.lock() {
+            Ok(log) => log,
+            Err(e) => e.into_inner(),
+        };
+        EventBatch {
+            events: log
+                .events
+                .iter()
+                .filter(|(cursor, event)| *cursor > since && visible(event))
+                .cloned()
+                .collect(),
+            cursor: log.cursor,
+            missed: since + 1 < log.first || since > log.cursor,
+        }
+    }
+
+    /// Waits up to `timeout` for events after the cursor that `visible` keeps. The batch
+    /// returned is empty when none was logged in time.
+    pub async fn wait(
+        &self,
+        since: u64,
+        timeout: Duration,
+        visible: impl Fn(&SpeechEvent) -> bool,
+    ) -> EventBatch {
+        let deadline = time::Instant::now() + timeout;
+        loop {
+            // Registered before reading the log so an event logged meanwhile is not missed.
+            let logged = self.logged.notified();
+            let batch = self.after(since, &visible);
+            if !batch.events.is_empty() || batch.missed {
+                return batch;
+            }
+            if time::timeout_at(deadline, logged).await.is_err() {
+                return self.after(since, &visible);
+            }
+        }
+    }
+
+    /// Logs the events received until the channel is closed.
+    pub async fn follow(self, mut events: Receiver<SpeechEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(event) => self.push(event),
+                Err(RecvError::Lagged(_)) => self.skip(),
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{SpeechEventLog, EVENT_LOG_CAPACITY};
+    use crate::domain::speech::{
+        event::{SpeechEvent, SpeechEventKind},
+        Speech, SpeechStatus,
+    };
+
+    #[test]
+    fn after_returns_the_events_past_the_cursor() {
+        let speech = Speech::new(
+            &Uuid::new_v4(),
+            "Speech",
+            Utc::now(),
+            &[],
+            &[],
+            "Radio",
+            SpeechStatus::Validated,
+        );
+        let log = SpeechEventLog::default();
+        let start = log.cursor();
+        log.push(SpeechEvent::new(SpeechEventKind::Created, &speech, None));
+        log.push(SpeechEvent::new(SpeechEventKind::Deleted, &speech, None));
+        let batch = log.after(start + 1, |_| true);
+        assert_eq!(batch.cursor, start + 2);
+        assert!(!batch.missed);
+        assert_eq!(batch.events.len(), 1);
+        assert_eq!(batch.events[0].1.kind(), SpeechEventKind::Deleted);
+        // A client behind the forgotten events must reload.
+        for _ in 0..EVENT_LOG_CAPACITY {
+            log.push(SpeechEvent::new(SpeechEventKind::Created, &speech, None));
+        }
+        assert!(log.after(start, |_| true).missed);
+        assert!(!log.after(start + 2, |_| true).missed);
+    }
+}
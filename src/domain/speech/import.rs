@@ -0,0 +1,123 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::Speech;
+
+/// Reason a speech of an import could not be created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportConflictKind {
+    /// A speech with the same name, date and media is already stored.
+    DuplicateSpeech,
+    /// A speaker of the speech is not a person of the organization.
+    UnknownSpeaker,
+}
+
+impl TryFrom<&str> for ImportConflictKind {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "duplicate_speech" => Self::DuplicateSpeech,
+            "unknown_speaker" => Self::UnknownSpeaker,
+            _ => return Err("Unexpected import conflict kind value".to_owned()),
+        })
+    }
+}
+
+impl Display for ImportConflictKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportConflictKind::DuplicateSpeech => f.write_str("duplicate_speech"),
+            ImportConflictKind::UnknownSpeaker => f.write_str("unknown_speaker"),
+        }
+    }
+}
+
+/// Decision taken on a conflict, applied when the import is applied again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportResolution {
+    /// Keeps the speech already stored instead of the duplicate, or speaks through an
+    /// existing person instead of the unknown speaker.
+    UseExisting(Uuid),
+    /// Creates the duplicate under another name, or creates the unknown speaker.
+    CreateNew,
+    /// Leaves the speech out of the import.
+    Skip,
+}
+
+impl ImportResolution {
+    /// Rebuilds a resolution from its stored action and uid.
+    pub fn new(action: &str, uid: Option<Uuid>) -> Result<Self, String> {
+        Ok(match (action, uid) {
+            ("use_existing", Some(uid)) => Self::UseExisting(uid),
+            ("create_new", _) => Self::CreateNew,
+            ("skip", _) => Self::Skip,
+            _ => return Err("Unexpected import resolution value".to_owned()),
+        })
+    }
+
+    /// Existing speech or person chosen, if any.
+    pub fn uid(&self) -> Option<&Uuid> {
+        match self {
+            ImportResolution::UseExisting(uid) => Some(uid),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ImportResolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportResolution::UseExisting(_) => f.write_str("use_existing"),
+            ImportResolution::CreateNew => f.write_str("create_new"),
+            ImportResolution::Skip => f.write_str("skip"),
+        }
+    }
+}
+
+/// Speech of a bulk import that could not be created, kept until it is resolved and the
+/// import applied again.
+#[derive(Clone)]
+pub struct ImportConflict {
+    pub uid: Uuid,
+    /// Import the speech was sent with.
+    pub import_uid: Uuid,
+    /// Position of the speech in the import.
+    pub position: u32,
+    pub kind: ImportConflictKind,
+    /// Speech already stored for a duplicate, when visible to the organization, or the
+    /// unknown speaker.
+    pub reference: Option<Uuid>,
+    /// Speech to create, updated by the resolutions.
+    pub speech: Speech,
+    pub resolution: Option<ImportResolution>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ImportConflict {
+    /// Resolution suggested to the user: keeping the stored speech for a visible
+    /// duplicate, creating the unknown speaker otherwise.
+    pub fn proposed_resolution(&self) -> ImportResolution {
+        match (self.kind, self.reference) {
+            (ImportConflictKind::DuplicateSpeech, Some(existing)) => {
+                ImportResolution::UseExisting(existing)
+            }
+            (ImportConflictKind::DuplicateSpeech, None) => ImportResolution::Skip,
+            (ImportConflictKind::UnknownSpeaker, _) => ImportResolution::CreateNew,
+        }
+    }
+}
+
+/// Outcome of an import, or of applying it again once conflicts are resolved.
+pub struct ImportReport {
+    pub import_uid: Uuid,
+    /// Speeches created.
+    pub created: Vec<Uuid>,
+    /// Stored speeches kept instead of their duplicate.
+    pub existing: Vec<Uuid>,
+    /// Number of speeches left out of the import.
+    pub skipped: u32,
+    /// Conflicts still waiting for a resolution, by position.
+    pub conflicts: Vec<ImportConflict>,
+}
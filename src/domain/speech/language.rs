@@ -0,0 +1,171 @@
+use whatlang::Lang;
+
+use super::sentence::Sentence;
+
+/// ISO 639-1 codes of the languages with their ISO 639-3 code: the official languages of
+/// the European Union first, then the other languages with a two letters code known to
+/// the detection.
+const LANGUAGE_CODES: &[(&str, &str)] = &[
+    ("bg", "bul"),
+    ("cs", "ces"),
+    ("da", "dan"),
+    ("de", "deu"),
+    ("el", "ell"),
+    ("en", "eng"),
+    ("es", "spa"),
+    ("et", "est"),
+    ("fi", "fin"),
+    ("fr", "fra"),
+    ("ga", "gle"),
+    ("hr", "hrv"),
+    ("hu", "hun"),
+    ("it", "ita"),
+    ("lt", "lit"),
+    ("lv", "lav"),
+    ("mt", "mlt"),
+    ("nl", "nld"),
+    ("pl", "pol"),
+    ("pt", "por"),
+    ("ro", "ron"),
+    ("sk", "slk"),
+    ("sl", "slv"),
+    ("sv", "swe"),
+    ("af", "afr"),
+    ("ar", "ara"),
+    ("be", "bel"),
+    ("ca", "cat"),
+    ("he", "heb"),
+    ("hi", "hin"),
+    ("hy", "hye"),
+    ("id", "ind"),
+    ("ja", "jpn"),
+    ("ka", "kat"),
+    ("ko", "kor"),
+    ("la", "lat"),
+    ("mk", "mkd"),
+    ("nb", "nob"),
+    ("ru", "rus"),
+    ("sr", "srp"),
+    ("tr", "tur"),
+    ("uk", "ukr"),
+    ("zh", "cmn"),
+];
+
+/// Reads a BCP 47 language tag ("fr", "pt-BR", "de-AT-1996"...) or an ISO 639-3 code as
+/// the ISO 639-3 code of its language, the region and the other subtags being dropped.
+/// Returns `None` if the tag is malformed or its language unknown.
+pub fn language_code(tag: &str) -> Option<String> {
+    let tag = tag.to_lowercase();
+    let mut subtags = tag.split('-');
+    let primary = subtags.next()?;
+    let well_formed = subtags.all(|subtag| {
+        (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+    if !well_formed || !primary.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    match primary.len() {
+        2 => LANGUAGE_CODES
+            .iter()
+            .find(|(short, _)| *short == primary)
+            .map(|(_, code)| code.to_string()),
+        3 if LANGUAGE_CODES.iter().any(|(_, code)| *code == primary) => Some(primary.to_owned()),
+        3 => Lang::from_code(primary).map(|lang| lang.code().to_owned()),
+        _ => None,
+    }
+}
+
+/// Shortest BCP 47 tag of the language of an ISO 639-3 code: its ISO 639-1 code when it
+/// has one, the code itself otherwise.
+pub fn language_tag(code: &str) -> String {
+    LANGUAGE_CODES
+        .iter()
+        .find(|(_, long)| *long == code)
+        .map_or(code, |(short, _)| short)
+        .to_owned()
+}
+
+/// Language of a speech, as an ISO 639-3 code ("fra", "eng"...).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechLanguage {
+    code: String,
+    /// Confidence of the detection between 0 and 1, `None` when the language was provided.
+    confidence: Option<f64>,
+    /// Whether some sentences are in another language than the speech.
+    mixed: bool,
+}
+
+impl SpeechLanguage {
+    pub fn new(code: &str, confidence: Option<f64>, mixed: bool) -> Self {
+        Self {
+            code: code.to_string(),
+            confidence,
+            mixed,
+        }
+    }
+
+    /// Language provided with the speech, as a BCP 47 tag or an ISO 639-3 code. Returns
+    /// `None` if the language is not known, see `language_code`.
+    pub fn provided(tag: &str) -> Option<Self> {
+        Some(Self::new(&language_code(tag)?, None, false))
+    }
+
+    /// Detects the language of the whole transcript. Returns `None` when there is not
+    /// enough text to guess one.
+    pub fn detect(sentences: &[Sentence]) -> Option<Self> {
+        let text = sentences
+            .iter()
+            .map(|s| s.text().as_str())
+            .collect::<Vec<&str>>()
+            .join("\n");
+        let info = whatlang::detect(&text)?;
+        // Short sentences are too ambiguous, only the reliable guesses and the languages
+        // provided with the sentences can flag the transcript as mixed.
+        let mixed = sentences.iter().any(|s| match s.language() {
+            Some(code) => code != info.lang().code(),
+            None => whatlang::detect(s.text())
+                .is_some_and(|sentence| sentence.is_reliable() && sentence.lang() != info.lang()),
+        });
+        Some(Self::new(
+            info.lang().code(),
+            Some(info.confidence()),
+            mixed,
+        ))
+    }
+
+    pub fn code(&self) -> &String {
+        &self.code
+    }
+
+    /// BCP 47 tag of the language, see `language_tag`.
+    pub fn tag(&self) -> String {
+        language_tag(&self.code)
+    }
+
+    pub fn confidence(&self) -> Option<f64> {
+        self.confidence
+    }
+
+    pub fn mixed(&self) -> bool {
+        self.mixed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{language_code, language_tag};
+
+    #[test]
+    fn tags_are_read_as_iso_639_3_codes() {
+        assert_eq!(language_code("fr"), Some("fra".to_owned()));
+        assert_eq!(language_code("pt-BR"), Some("por".to_owned()));
+        assert_eq!(language_code("de-AT-1996"), Some("deu".to_owned()));
+        assert_eq!(language_code("FRA"), Some("fra".to_owned()));
+        assert_eq!(language_code("mt"), Some("mlt".to_owned()));
+        assert_eq!(language_code("xx"), None);
+        assert_eq!(language_code("fr-"), None);
+        assert_eq!(language_code("fr_FR"), None);
+        assert_eq!(language_tag("deu"), "de");
+        assert_eq!(language_tag("epo"), "epo");
+    }
+}
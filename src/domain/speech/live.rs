@@ -0,0 +1,213 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use tokio::sync::{broadcast, oneshot, Notify};
+use uuid::Uuid;
+
+use super::{sentence::Sentence, speech_repository::SpeechRepositoryError};
+
+/// Sentences kept for a viewer which falls behind, it is disconnected past this number.
+const VIEWER_BACKLOG: usize = 1024;
+
+/// Sentence appended to a live speech, `seq` being its position in the speech.
+#[derive(Clone)]
+pub struct LiveSentence {
+    seq: u32,
+    sentence: Sentence,
+}
+
+impl LiveSentence {
+    pub fn new(seq: u32, sentence: Sentence) -> Self {
+        Self { seq, sentence }
+    }
+
+    pub fn seq(&self) -> u32 {
+        self.seq
+    }
+
+    pub fn sentence(&self) -> &Sentence {
+        &self.sentence
+    }
+}
+
+struct LiveChannel {
+    sender: broadcast::Sender<LiveSentence>,
+    /// Sentences sent to the viewers but not flushed to the repository yet.
+    pending: Vec<LiveSentence>,
+    streaming: bool,
+    /// Notified to ask the producer to stop streaming.
+    stop: Arc<Notify>,
+    /// Waiting for the producer to release the speech.
+    on_release: Vec<oneshot::Sender<()>>,
+}
+
+impl LiveChannel {
+    fn new() -> Self {
+        Self {
+            sender: broadcast::channel(VIEWER_BACKLOG).0,
+            pending: Vec::new(),
+            streaming: false,
+            stop: Arc::new(Notify::new()),
+            on_release: Vec::new(),
+        }
+    }
+}
+
+/// Live transcripts in progress, shared by every clone.
+#[derive(Clone, Default)]
+pub struct LiveTranscripts {
+    channels: Arc<Mutex<HashMap<Uuid, LiveChannel>>>,
+}
+
+impl LiveTranscripts {
+    fn lock(&self) -> MutexGuard<'_, HashMap<Uuid, LiveChannel>> {
+        let mut channels = self
+            .channels
+            .lock()
+            .expect("Live transcripts lock poisoned");
+        // Channels nobody streams to nor watches are dropped.
+        channels.retain(|_, c| c.streaming || c.sender.receiver_count() > 0);
+        channels
+    }
+
+    /// Registers the producer of the speech, a speech is streamed by one producer at a time.
+    pub(super) fn open(
+        &self,
+        uid: Uuid,
+        next_seq: u32,
+        speakers: &[Uuid],
+    ) -> Result<LiveSession, SpeechRepositoryError> {
+        let mut channels = self.lock();
+        let channel = channels.entry(uid).or_insert_with(LiveChannel::new);
+        if channel.streaming {
+            return Err(SpeechRepositoryError::LiveStreamInProgress);
+        }
+        channel.streaming = true;
+        // A stop asked to a former producer must not reach this one.
+        channel.stop = Arc::new(Notify::new());
+        Ok(LiveSession {
+            uid,
+            next_seq,
+            speakers: speakers.to_vec(),
+            stop: channel.stop.clone(),
+            transcripts: self.clone(),
+        })
+    }
+
+    /// Asks the producer of the speech, if any, to stop and waits until it has flushed its
+    /// sentences and released the speech.
+    pub(super) async fn close(&self, uid: Uuid) {
+        let released = {
+            let mut channels = self.lock();
+            match channels.get_mut(&uid) {
+                Some(channel) if channel.streaming => {
+                    let (sender, receiver) = oneshot::channel();
+                    channel.on_release.push(sender);
+                    channel.stop.notify_one();
+                    receiver
+                }
+                _ => return,
+            }
+        };
+        let _ = released.await;
+    }
+
+    /// Subscribes to the sentences of the speech. The sentences received but not flushed
+    /// yet are returned along with the receiver, as they cannot be read from the repository.
+    pub(super) fn subscribe(
+        &self,
+        uid: Uuid,
+    ) -> (Vec<LiveSentence>, broadcast::Receiver<LiveSentence>) {
+        let mut channels = self.lock();
+        let channel = channels.entry(uid).or_insert_with(LiveChannel::new);
+        (channel.pending.clone(), channel.sender.subscribe())
+    }
+}
+
+/// Producer of a live speech. The speech is released when the session is dropped, the
+/// sentences not flushed by then are lost.
+pub struct LiveSession {
+    uid: Uuid,
+    next_seq: u32,
+    speakers: Vec<Uuid>,
+    stop: Arc<Notify>,
+    transcripts: LiveTranscripts,
+}
+
+impl LiveSession {
+    pub fn uid(&self) -> Uuid {
+        self.uid
+    }
+
+    /// Position of the next sentence expected from the producer.
+    pub fn next_seq(&self) -> u32 {
+        self.next_seq
+    }
+
+    /// Completes once the producer is asked to stop streaming.
+    pub async fn stopped(&self) {
+        self.stop.notified().await
+    }
+
+    /// Sends the sentence to the viewers and keeps it until the next flush. A sentence
+    /// already received is ignored so a producer may resend its last sentences after a
+    /// reconnection, returns whether the sentence has been appended.
+    pub fn push(&mut self, seq: u32, sentence: Sentence) -> Result<bool, SpeechRepositoryError> {
+        if seq < self.next_seq {
+            return Ok(false);
+        }
+        if seq > self.next_seq {
+            return Err(SpeechRepositoryError::SentenceOutOfOrder(self.next_seq));
+        }
+        if !self.speakers.contains(sentence.speaker()) {
+            return Err(SpeechRepositoryError::SpeakerNotFound);
+        }
+        let sentence = LiveSentence::new(seq, sentence);
+        let mut channels = self.transcripts.lock();
+        if let Some(channel) = channels.get_mut(&self.uid) {
+            // Sending only fails when nobody watches.
+            let _ = channel.sender.send(sentence.clone());
+            channel.pending.push(sentence);
+        }
+        self.next_seq += 1;
+        Ok(true)
+    }
+
+    /// Sentences received since the last flush, oldest first.
+    pub fn pending(&self) -> Vec<LiveSentence> {
+        self.transcripts
+            .lock()
+            .get(&self.uid)
+            .map(|c| c.pending.clone())
+            .unwrap_or_default()
+    }
+
+    /// Number of sentences received since the last flush.
+    pub fn pending_len(&self) -> usize {
+        self.transcripts
+            .lock()
+            .get(&self.uid)
+            .map_or(0, |c| c.pending.len())
+    }
+
+    /// Forgets the `count` oldest pending sentences once they are stored.
+    pub(super) fn flushed(&self, count: usize) {
+        if let Some(channel) = self.transcripts.lock().get_mut(&self.uid) {
+            channel.pending.drain(..count.min(channel.pending.len()));
+        }
+    }
+}
+
+impl Drop for LiveSession {
+    fn drop(&mut self) {
+        if let Some(channel) = self.transcripts.lock().get_mut(&self.uid) {
+            channel.streaming = false;
+            channel.pending.clear();
+            for sender in channel.on_release.drain(..) {
+                let _ = sender.send(());
+            }
+        }
+    }
+}
@@ -1,38 +1,1308 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::Utc;
+
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
+use crate::domain::{
+    collection::{CollectionVersionError, CollectionVersions},
+    job::JobProgress,
+    person::{Person, PersonManager, PersonRepositoryError},
+    pii::PiiDetector,
+    text::{extract_keywords, Keyword, TextLanguage},
+    translation::{Translator, TranslatorError},
+    upsert::{OnConflict, UpsertOutcome},
+};
+
 use super::{
-    speech_repository::{SpeechRepository, SpeechRepositoryError},
-    Speech,
+    analytics::{
+        MonthlySpeechCount, SpeakerAnalytics, SpeakerStats, SpeechGroupCount, SpeechGrouping,
+        TimelineGranularity, TimelinePeriod,
+    },
+    cluster::{cluster_speeches, Signature, SpeechCluster},
+    comparison::{ComparisonService, SpeechComparison},
+    consolidation::consolidate,
+    event::{SpeechEvent, SpeechEventKind},
+    event_log::{EventBatch, SpeechEventLog},
+    import::{ImportConflict, ImportConflictKind, ImportReport, ImportResolution},
+    language::SpeechLanguage,
+    live::{LiveSentence, LiveSession, LiveTranscripts},
+    pii_flag::PiiFlag,
+    progress::ReadProgress,
+    quote::Quote,
+    revision::SpeechRevision,
+    sentence::{ReviewStatus, Sentence, SentenceReview},
+    speech_repository::{
+        SpeechDuplicate, SpeechFilter, SpeechIdentity, SpeechProjection, SpeechRepository,
+        SpeechRepositoryError, SpeechSummary,
+    },
+    statement::{Statement, StatementQuery},
+    SpeakerRole, Speech, SpeechStatus,
 };
 
+/// Number of speech texts read at once by the clustering.
+const CLUSTERING_BATCH_SIZE: u16 = 200;
+
+/// Number of first sentences the language of a streamed speech is detected from.
+const LANGUAGE_SAMPLE_SIZE: u16 = 500;
+
+/// Result of an attempt to create a speech of an import.
+enum ImportAttempt {
+    Created,
+    /// The speech conflicts with stored data, the reference of the conflict being given.
+    Conflict(ImportConflictKind, Option<Uuid>),
+}
+
 #[derive(Clone)]
 pub struct SpeechManager {
     repository: Box<dyn SpeechRepository>,
+    translator: Option<Box<dyn Translator>>,
+    pii_detector: Option<Box<dyn PiiDetector>>,
+    /// Whether the personal data flags must all be reviewed before validating a speech.
+    pii_review_required: bool,
+    live: LiveTranscripts,
+    events: Option<broadcast::Sender<SpeechEvent>>,
+    event_log: Option<SpeechEventLog>,
+    collection_versions: Option<CollectionVersions>,
+    /// Organization the repository is restricted to, stamped on the published events.
+    organization: Option<Uuid>,
 }
 
 impl SpeechManager {
     pub fn new(repository: Box<dyn SpeechRepository>) -> Self {
-        return SpeechManager { repository };
+        return SpeechManager {
+            repository,
+            translator: None,
+            pii_detector: None,
+            pii_review_required: false,
+            live: LiveTranscripts::default(),
+            events: None,
+            event_log: None,
+            collection_versions: None,
+            organization: None,
+        };
+    }
+
+    /// Returns a manager whose operations only reach the speeches of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            translator: self.translator.clone(),
+            pii_detector: self.pii_detector.clone(),
+            pii_review_required: self.pii_review_required,
+            live: self.live.clone(),
+            events: self.events.clone(),
+            event_log: self.event_log.clone(),
+            collection_versions: self
+                .collection_versions
+                .as_ref()
+                .map(|versions| versions.for_organization(organization)),
+            organization,
+        }
+    }
+
+    /// Returns a manager whose reads only reach the validated speeches.
+    pub fn validated_only(&self) -> Self {
+        Self {
+            repository: self.repository.validated_only(),
+            ..self.clone()
+        }
+    }
+
+    /// Publishes the lifecycle events of the speeches on the channel.
+    pub fn with_events(mut self, events: broadcast::Sender<SpeechEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Subscribes to the lifecycle events of the speeches of the organization. Returns
+    /// `None` when the events are not published.
+    pub fn subscribe_events(&self) -> Option<broadcast::Receiver<SpeechEvent>> {
+        self.events.as_ref().map(|events| events.subscribe())
+    }
+
+    /// Keeps the last events published in the log so they can be polled.
+    pub fn with_event_log(mut self, event_log: SpeechEventLog) -> Self {
+        self.event_log = Some(event_log);
+        self
+    }
+
+    /// Bumps the versions of the collections on every change made to the speeches.
+    pub fn with_collection_versions(mut self, collection_versions: CollectionVersions) -> Self {
+        self.collection_versions = Some(collection_versions);
+        self
+    }
+
+    /// Bumps the version of the collections of the organization once a change is stored,
+    /// so the lists cached by the clients are stale as soon as the change is answered.
+    async fn bump_collection_version(&self) -> Result<(), SpeechRepositoryError> {
+        if let Some(versions) = &self.collection_versions {
+            versions
+                .bump()
+                .await
+                .map_err(|CollectionVersionError::InternalError(e)| {
+                    SpeechRepositoryError::InternalError(e)
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Waits up to `timeout` for the events of the organization after the cursor, from the
+    /// current cursor when none is given. Returns `None` when the events are not logged.
+    pub async fn poll_events(&self, since: Option<u64>, timeout: Duration) -> Option<EventBatch> {
+        let event_log = self.event_log.as_ref()?;
+        let since = since.unwrap_or_else(|| event_log.cursor());
+        let organization = self.organization;
+        Some(
+            event_log
+                .wait(since, timeout, |e| {
+                    e.organization() == organization.as_ref()
+                })
+                .await,
+        )
+    }
+
+    /// Organization the manager is restricted to, `None` being the default organization.
+    pub fn organization(&self) -> Option<&Uuid> {
+        self.organization.as_ref()
+    }
+
+    fn publish(&self, kind: SpeechEventKind, speech: &Speech) {
+        if let Some(events) = &self.events {
+            // Sending only fails when nobody watches.
+            let _ = events.send(SpeechEvent::new(kind, speech, self.organization));
+        }
+    }
+
+    /// Enables the translation of speeches through the given provider.
+    pub fn with_translator(mut self, translator: Box<dyn Translator>) -> Self {
+        self.translator = Some(translator);
+        self
+    }
+
+    /// Screens the sentences of the stored speeches for personal data through the detector.
+    pub fn with_pii_detector(mut self, detector: Box<dyn PiiDetector>) -> Self {
+        self.pii_detector = Some(detector);
+        self
+    }
+
+    /// Refuses to validate the speeches while some of their personal data flags are not
+    /// reviewed.
+    pub fn with_pii_review_required(mut self, required: bool) -> Self {
+        self.pii_review_required = required;
+        self
+    }
+
+    /// Flags the personal data found in the sentences, `None` being returned when no
+    /// detector is set. The detection runs before storing the speech, so a speech is
+    /// never stored without its flags.
+    async fn detect_pii(
+        &self,
+        speech: &Speech,
+    ) -> Result<Option<Vec<PiiFlag>>, SpeechRepositoryError> {
+        self.detect_sentences_pii(speech.sentences(), 0).await
+    }
+
+    /// Flags the personal data found in sentences placed from `first_index` in their
+    /// speech, each excerpt being flagged once.
+    async fn detect_sentences_pii(
+        &self,
+        sentences: &[Sentence],
+        first_index: u32,
+    ) -> Result<Option<Vec<PiiFlag>>, SpeechRepositoryError> {
+        let detector = match &self.pii_detector {
+            Some(detector) => detector,
+            None => return Ok(None),
+        };
+        let texts = sentences
+            .iter()
+            .map(|s| s.text().clone())
+            .collect::<Vec<String>>();
+        let findings = detector
+            .detect(&texts)
+            .await
+            .map_err(SpeechRepositoryError::PiiDetectionError)?;
+        let mut flags: Vec<PiiFlag> = Vec::new();
+        for (index, findings) in findings.into_iter().enumerate() {
+            for finding in findings {
+                if !flags
+                    .iter()
+                    .any(|f| f.kind == finding.kind && f.excerpt == finding.excerpt)
+                {
+                    flags.push(PiiFlag::new(first_index + index as u32, finding));
+                }
+            }
+        }
+        Ok(Some(flags))
+    }
+
+    /// Checks every speaker of the speech and of its sentences is a person of the
+    /// organization, in a single query.
+    async fn check_speakers(&self, speech: &Speech) -> Result<(), SpeechRepositoryError> {
+        let mut speakers: Vec<Uuid> = Vec::new();
+        for speaker in speech
+            .speakers()
+            .iter()
+            .chain(speech.sentences().iter().map(|s| s.speaker()))
+        {
+            if !speakers.contains(speaker) {
+                speakers.push(*speaker);
+            }
+        }
+        let unknown = self.repository.get_unknown_speakers(&speakers).await?;
+        if !unknown.is_empty() {
+            return Err(SpeechRepositoryError::UnknownSpeakers(unknown));
+        }
+        Ok(())
+    }
+
+    /// Stores the speech. Its language is detected from the sentences when not provided.
+    pub async fn create_speech(&self, mut speech: Speech) -> Result<(), SpeechRepositoryError> {
+        self.check_speakers(&speech).await?;
+        detect_missing_language(&mut speech);
+        let flags = self.detect_pii(&speech).await?;
+        self.repository
+            .create_speech(&speech, flags.as_deref())
+            .await?;
+        self.bump_collection_version().await?;
+        self.publish(SpeechEventKind::Created, &speech);
+        Ok(())
+    }
+
+    /// Creates the speech, or resolves the conflict with the speech of the same name, date
+    /// and media, see `SpeechRepository::upsert_speech`. The speech is checked as on its
+    /// creation.
+    pub async fn upsert_speech(
+        &self,
+        mut speech: Speech,
+        on_conflict: OnConflict,
+    ) -> Result<UpsertOutcome, SpeechRepositoryError> {
+        self.check_speakers(&speech).await?;
+        detect_missing_language(&mut speech);
+        let flags = self.detect_pii(&speech).await?;
+        let outcome = self
+            .repository
+            .upsert_speech(&speech, on_conflict, flags.as_deref())
+            .await?;
+        match outcome {
+            UpsertOutcome::Created(_) => {
+                self.bump_collection_version().await?;
+                self.publish(SpeechEventKind::Created, &speech);
+            }
+            UpsertOutcome::Updated(uid) => {
+                self.bump_collection_version().await?;
+                let stored = self.repository.get_speech_by_id(uid).await?;
+                self.publish(SpeechEventKind::SentencesEdited, &stored);
+            }
+            UpsertOutcome::Skipped(_) => {}
+        }
+        Ok(outcome)
+    }
+
+    /// Replaces the content of the speech, only if it still has the version expected when
+    /// one is given.
+    pub async fn update_speech(
+        &self,
+        mut speech: Speech,
+        expected_version: Option<u32>,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.check_speakers(&speech).await?;
+        detect_missing_language(&mut speech);
+        let flags = self.detect_pii(&speech).await?;
+        self.repository
+            .update_speech(&speech, expected_version, flags.as_deref())
+            .await?;
+        self.bump_collection_version().await?;
+        self.publish(SpeechEventKind::SentencesEdited, &speech);
+        Ok(())
+    }
+
+    /// Marks a speech pending review as validated.
+    pub async fn validate_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError> {
+        let mut speech = self.repository.get_speech_by_id(uid).await?;
+        if *speech.speech_status() != SpeechStatus::Pending {
+            return Err(SpeechRepositoryError::SpeechNotPending);
+        }
+        let flagged = speech
+            .sentences()
+            .iter()
+            .filter(|s| s.review().status == ReviewStatus::Flagged)
+            .count();
+        if flagged > 0 {
+            return Err(SpeechRepositoryError::SentencesFlagged(flagged as u32));
+        }
+        if self.pii_review_required {
+            let pending = self
+                .repository
+                .get_pii_flags(uid)
+                .await?
+                .iter()
+                .filter(|flag| !flag.is_reviewed())
+                .count();
+            if pending > 0 {
+                return Err(SpeechRepositoryError::PiiNotReviewed(pending as u32));
+            }
+        }
+        self.repository
+            .update_speech_status(uid, SpeechStatus::Validated)
+            .await?;
+        self.bump_collection_version().await?;
+        speech.update_speech_status(SpeechStatus::Validated);
+        self.publish(SpeechEventKind::Validated, &speech);
+        Ok(())
+    }
+
+    /// Records the review of the sentence by the user, returning the sentence.
+    pub async fn review_sentence(
+        &self,
+        uid: Uuid,
+        sentence: Uuid,
+        status: ReviewStatus,
+        comment: Option<String>,
+        reviewed_by: &str,
+    ) -> Result<Sentence, SpeechRepositoryError> {
+        let review = SentenceReview {
+            status,
+            comment,
+            reviewed_by: Some(reviewed_by.to_string()),
+            reviewed_at: Some(Utc::now()),
+        };
+        let sentence = self
+            .repository
+            .review_sentence(uid, sentence, &review)
+            .await?;
+        self.bump_collection_version().await?;
+        Ok(sentence)
+    }
+
+    pub async fn get_speech_revisions(
+        &self,
+        uid: Uuid,
+    ) -> Result<Vec<SpeechRevision>, SpeechRepositoryError> {
+        self.repository.get_speech_revisions(uid).await
+    }
+
+    pub async fn get_speech_revision(
+        &self,
+        uid: Uuid,
+        revision: u32,
+    ) -> Result<SpeechRevision, SpeechRepositoryError> {
+        self.repository.get_speech_revision(uid, revision).await
+    }
+
+    pub async fn get_speech_analytics(
+        &self,
+        uid: Uuid,
+        include_moderators: bool,
+    ) -> Result<Vec<SpeakerAnalytics>, SpeechRepositoryError> {
+        self.repository
+            .get_speech_analytics(uid, include_moderators)
+            .await
+    }
+
+    /// Most frequent terms of the transcript, stemmed and without stopwords in the language
+    /// of each sentence. With a language, only the sentences spoken in it are read.
+    pub async fn get_speech_keywords(
+        &self,
+        uid: Uuid,
+        quantity: usize,
+        language: Option<&str>,
+    ) -> Result<Vec<Keyword>, SpeechRepositoryError> {
+        let speech = self.get_speech_by_id(uid).await?;
+        Ok(extract_keywords(
+            speech
+                .sentences()
+                .iter()
+                .map(|s| (s, speech.sentence_language(s).map(String::as_str)))
+                .filter(|(_, spoken)| language.is_none_or(|language| *spoken == Some(language)))
+                .map(|(s, spoken)| (s.text().as_str(), spoken.and_then(TextLanguage::from_code))),
+            quantity,
+        ))
+    }
+
+    pub async fn get_speaker_stats(
+        &self,
+        person_uid: Uuid,
+        include_moderators: bool,
+    ) -> Result<SpeakerStats, SpeechRepositoryError> {
+        self.repository
+            .get_speaker_stats(person_uid, include_moderators)
+            .await
+    }
+
+    pub async fn get_speaker_timeline(
+        &self,
+        person_uid: Uuid,
+        granularity: TimelineGranularity,
+        include_moderators: bool,
+    ) -> Result<Vec<TimelinePeriod>, SpeechRepositoryError> {
+        self.repository
+            .get_speaker_timeline(person_uid, granularity, include_moderators)
+            .await
+    }
+
+    pub async fn search_statements(
+        &self,
+        person_uid: Uuid,
+        query: &StatementQuery,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<Statement>, SpeechRepositoryError> {
+        self.repository
+            .search_statements(person_uid, query, page, quantity)
+            .await
+    }
+
+    /// Clusters again the speeches of the organization by the similarity of their texts,
+    /// returning the number of clusters found.
+    pub async fn cluster_speeches(&self) -> Result<usize, SpeechRepositoryError> {
+        let mut signatures = Vec::new();
+        let mut after = None;
+        loop {
+            let texts = self
+                .repository
+                .get_speech_texts(after, CLUSTERING_BATCH_SIZE)
+                .await?;
+            after = texts.last().map(|(uid, _)| *uid);
+            let last_batch = texts.len() < CLUSTERING_BATCH_SIZE as usize;
+            // Only the signatures are kept, the texts of a batch are dropped once signed.
+            signatures.extend(
+                texts
+                    .into_iter()
+                    .filter_map(|(uid, text)| Some((uid, Signature::of_text(&text)?))),
+            );
+            if last_batch {
+                break;
+            }
+        }
+        let clusters = cluster_speeches(&signatures);
+        self.repository.replace_speech_clusters(&clusters).await?;
+        Ok(clusters.len())
+    }
+
+    pub async fn get_speech_cluster(
+        &self,
+        uid: Uuid,
+    ) -> Result<Option<SpeechCluster>, SpeechRepositoryError> {
+        self.repository.get_speech_cluster(uid).await
+    }
+
+    pub async fn get_speech_clusters(
+        &self,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<SpeechCluster>, SpeechRepositoryError> {
+        self.repository.get_speech_clusters(page, quantity).await
+    }
+
+    pub async fn count_speech_by_media_month(
+        &self,
+    ) -> Result<Vec<MonthlySpeechCount>, SpeechRepositoryError> {
+        self.repository.count_speech_by_media_month().await
+    }
+
+    pub async fn update_speaker_role(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+        role: SpeakerRole,
+        expected_version: Option<u32>,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.repository
+            .update_speaker_role(uid, speaker, role, expected_version)
+            .await?;
+        self.bump_collection_version().await?;
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        self.publish(SpeechEventKind::SpeakerRoleChanged, &speech);
+        Ok(())
+    }
+
+    pub async fn add_speaker(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+        role: SpeakerRole,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.repository.add_speaker(uid, speaker, role).await?;
+        self.bump_collection_version().await?;
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        self.publish(SpeechEventKind::SpeakersChanged, &speech);
+        Ok(())
+    }
+
+    /// Removes a speaker from the speech, their sentences must be reassigned first.
+    pub async fn remove_speaker(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.repository.remove_speaker(uid, speaker).await?;
+        self.bump_collection_version().await?;
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        self.publish(SpeechEventKind::SpeakersChanged, &speech);
+        Ok(())
+    }
+
+    /// Gives the sentences of a speaker to another person, e.g. once a speaker imported
+    /// as unknown has been identified. Returns the number of sentences reassigned.
+    pub async fn reassign_sentences(
+        &self,
+        uid: Uuid,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<u64, SpeechRepositoryError> {
+        let reassigned = self.repository.reassign_sentences(uid, from, to).await?;
+        self.bump_collection_version().await?;
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        self.publish(SpeechEventKind::SpeakersChanged, &speech);
+        Ok(reassigned)
+    }
+
+    /// Announces the speeches whose speakers were changed outside of this manager, e.g. by
+    /// the merge of two persons. The speeches deleted since are skipped.
+    pub async fn speakers_changed(&self, uids: &[Uuid]) -> Result<(), SpeechRepositoryError> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        self.bump_collection_version().await?;
+        if self.events.is_none() {
+            return Ok(());
+        }
+        for uid in uids {
+            match self.repository.get_speech_by_id(*uid).await {
+                Ok(speech) => self.publish(SpeechEventKind::SpeakersChanged, &speech),
+                Err(SpeechRepositoryError::SpeechNotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
     }
 
-    pub async fn create_speech(&self, speech: Speech) -> Result<(), SpeechRepositoryError> {
-        self.repository.create_speech(&speech).await
+    /// Starts streaming sentences to the end of the live speech. Only the speakers of the
+    /// speech may speak in the streamed sentences.
+    pub async fn open_live_session(&self, uid: Uuid) -> Result<LiveSession, SpeechRepositoryError> {
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        if *speech.speech_status() != SpeechStatus::Live {
+            return Err(SpeechRepositoryError::SpeechNotLive);
+        }
+        self.live
+            .open(uid, speech.sentences().len() as u32, speech.speakers())
+    }
+
+    /// Ends the live transcript of the speech: the producer still streaming is stopped, the
+    /// sentences are consolidated, the language is detected again and the speech waits
+    /// for review. Returns the number of sentences before and after the consolidation.
+    pub async fn finalize_live_speech(
+        &self,
+        uid: Uuid,
+    ) -> Result<(usize, usize), SpeechRepositoryError> {
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        if *speech.speech_status() != SpeechStatus::Live {
+            return Err(SpeechRepositoryError::SpeechNotLive);
+        }
+        self.live.close(uid).await;
+        // Read again to get the sentences flushed by the producer when stopping.
+        let mut speech = self.repository.get_speech_by_id(uid).await?;
+        let received = speech.sentences().len();
+        let sentences = consolidate(speech.sentences());
+        speech.update_sentences(&sentences);
+        speech.update_speech_status(SpeechStatus::Pending);
+        speech.update_language(None);
+        self.update_speech(speech, None).await?;
+        Ok((received, sentences.len()))
+    }
+
+    /// Ends the ingestion of a speech whose sentences were streamed at its creation: the
+    /// speech waits for review, its language being detected from its first sentences when
+    /// it was not provided. Unlike a live transcript, the sentences are kept as received
+    /// and are not written again.
+    pub async fn finish_speech_ingestion(&self, uid: Uuid) -> Result<(), SpeechRepositoryError> {
+        let (mut speech, _) = self
+            .repository
+            .get_speech_page(uid, 0, LANGUAGE_SAMPLE_SIZE, None)
+            .await?;
+        if *speech.speech_status() != SpeechStatus::Live {
+            return Err(SpeechRepositoryError::SpeechNotLive);
+        }
+        let language = match speech.language() {
+            Some(_) => None,
+            None => SpeechLanguage::detect(speech.sentences()),
+        };
+        self.repository
+            .finish_speech_ingestion(uid, language.as_ref())
+            .await?;
+        self.bump_collection_version().await?;
+        speech.update_speech_status(SpeechStatus::Pending);
+        self.publish(SpeechEventKind::SentencesEdited, &speech);
+        Ok(())
+    }
+
+    /// Stores the sentences received by the session since the last flush, along with the
+    /// personal data flags found in them.
+    pub async fn flush_live_session(
+        &self,
+        session: &LiveSession,
+    ) -> Result<(), SpeechRepositoryError> {
+        let pending = session.pending();
+        let first = match pending.first() {
+            Some(first) => first.seq(),
+            None => return Ok(()),
+        };
+        let sentences = pending
+            .iter()
+            .map(|s| s.sentence().clone())
+            .collect::<Vec<Sentence>>();
+        let flags = self
+            .detect_sentences_pii(&sentences, first)
+            .await?
+            .unwrap_or_default();
+        self.repository
+            .append_sentences(session.uid(), first, &sentences, &flags)
+            .await?;
+        session.flushed(pending.len());
+        // The appended sentences change the sentence count and the preview of the speech.
+        self.bump_collection_version().await?;
+        Ok(())
+    }
+
+    /// Watches the sentences appended to the speech. The sentences placed after `after`
+    /// are returned first, then the receiver gets the sentences as they are streamed and
+    /// may repeat some of the returned ones.
+    pub async fn watch_live_sentences(
+        &self,
+        uid: Uuid,
+        after: Option<u32>,
+    ) -> Result<(Vec<LiveSentence>, broadcast::Receiver<LiveSentence>), SpeechRepositoryError> {
+        // Subscribing before reading the speech, a sentence flushed in between is both
+        // stored and pending but none is missed.
+        let (pending, receiver) = self.live.subscribe(uid);
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        let stored = speech.sentences().len() as u32;
+        let sentences = speech
+            .sentences()
+            .iter()
+            .enumerate()
+            .map(|(idx, s)| LiveSentence::new(idx as u32, s.clone()))
+            .chain(pending.into_iter().filter(|s| s.seq() >= stored))
+            .filter(|s| after.is_none_or(|after| s.seq() > after))
+            .collect();
+        Ok((sentences, receiver))
+    }
+
+    /// Restores the content of a previous revision. The restoration is itself recorded as
+    /// a new revision so the history is never rewritten.
+    pub async fn revert_speech(
+        &self,
+        uid: Uuid,
+        revision: u32,
+    ) -> Result<(), SpeechRepositoryError> {
+        let mut speech = self
+            .repository
+            .get_speech_revision(uid, revision)
+            .await?
+            .into_speech();
+        // Only the content is restored, the speech keeps its current status. The version is
+        // expected so a status changed meanwhile fails the revert instead of being undone.
+        let (current, _) = self.repository.get_speech_page(uid, 0, 1, None).await?;
+        speech.update_speech_status(current.speech_status().clone());
+        let flags = self.detect_pii(&speech).await?;
+        self.repository
+            .update_speech(&speech, Some(current.version()), flags.as_deref())
+            .await?;
+        self.bump_collection_version().await?;
+        self.publish(SpeechEventKind::SentencesEdited, &speech);
+        Ok(())
+    }
+
+    pub async fn get_pii_flags(&self, uid: Uuid) -> Result<Vec<PiiFlag>, SpeechRepositoryError> {
+        self.repository.get_pii_flags(uid).await
+    }
+
+    pub async fn review_pii_flag(
+        &self,
+        uid: Uuid,
+        flag: Uuid,
+        reviewed_by: &str,
+    ) -> Result<PiiFlag, SpeechRepositoryError> {
+        let flag = self
+            .repository
+            .review_pii_flag(uid, flag, reviewed_by)
+            .await?;
+        self.bump_collection_version().await?;
+        Ok(flag)
     }
 
     pub async fn get_speech_by_id(&self, uid: Uuid) -> Result<Speech, SpeechRepositoryError> {
         self.repository.get_speech_by_id(uid).await
     }
 
+    /// Returns the speech with the `quantity` sentences of the page, starting at page 0,
+    /// along with its total number of sentences.
+    pub async fn get_speech_page(
+        &self,
+        uid: Uuid,
+        page: u16,
+        quantity: u16,
+        review_status: Option<ReviewStatus>,
+    ) -> Result<(Speech, u64), SpeechRepositoryError> {
+        self.repository
+            .get_speech_page(uid, page, quantity, review_status)
+            .await
+    }
+
+    /// Returns the speech along with the quote of its sentence, surrounded by at most
+    /// `context` sentences on each side.
+    pub async fn get_quote(
+        &self,
+        uid: Uuid,
+        sentence_uid: Uuid,
+        context: usize,
+    ) -> Result<(Speech, Quote), SpeechRepositoryError> {
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        let quote = Quote::from_speech(&speech, &sentence_uid, context)
+            .ok_or(SpeechRepositoryError::SentenceNotFound)?;
+        Ok((speech, quote))
+    }
+
+    /// Returns both speeches along with their comparison.
+    pub async fn compare_speeches(
+        &self,
+        a: Uuid,
+        b: Uuid,
+    ) -> Result<(Speech, Speech, SpeechComparison), SpeechRepositoryError> {
+        let a = self.repository.get_speech_by_id(a).await?;
+        let b = self.repository.get_speech_by_id(b).await?;
+        let comparison = ComparisonService::default().compare(&a, &b);
+        Ok((a, b, comparison))
+    }
+
+    pub async fn speech_exists(&self, uid: Uuid) -> Result<bool, SpeechRepositoryError> {
+        self.repository.speech_exists(uid).await
+    }
+
+    /// Returns the page of the speech with its sentences translated to the language (ISO
+    /// 639-1 code), along with its total number of sentences. Translations are stored, so
+    /// only the sentences of the page never translated to this language are sent to the
+    /// translator.
+    pub async fn get_translated_speech(
+        &self,
+        uid: Uuid,
+        language: &str,
+        page: u16,
+        quantity: u16,
+        review_status: Option<ReviewStatus>,
+    ) -> Result<(Speech, u64), SpeechRepositoryError> {
+        let (mut speech, total_sentences) = self
+            .repository
+            .get_speech_page(uid, page, quantity, review_status)
+            .await?;
+        let mut translations = self
+            .repository
+            .get_sentence_translations(uid, language)
+            .await?;
+        let missing = speech
+            .sentences()
+            .iter()
+            .filter(|s| !translations.contains_key(s.uid()))
+            .collect::<Vec<&Sentence>>();
+        if !missing.is_empty() {
+            let translator =
+                self.translator
+                    .as_ref()
+                    .ok_or(SpeechRepositoryError::TranslationError(
+                        TranslatorError::Unavailable,
+                    ))?;
+            let texts = missing
+                .iter()
+                .map(|s| s.text().clone())
+                .collect::<Vec<String>>();
+            let translated = translator
+                .translate(&texts, language)
+                .await
+                .map_err(SpeechRepositoryError::TranslationError)?;
+            if translated.len() != missing.len() {
+                return Err(SpeechRepositoryError::TranslationError(
+                    TranslatorError::ProviderError(format!(
+                        "{} translations received for {} sentences",
+                        translated.len(),
+                        missing.len()
+                    )),
+                ));
+            }
+            let new_translations = missing
+                .iter()
+                .map(|s| *s.uid())
+                .zip(translated)
+                .collect::<Vec<(Uuid, String)>>();
+            self.repository
+                .save_sentence_translations(language, &new_translations)
+                .await?;
+            translations.extend(new_translations);
+        }
+        let sentences = speech
+            .sentences()
+            .iter()
+            .map(|s| {
+                let text = translations.get(s.uid()).unwrap_or(s.text());
+                Sentence::new(s.uid(), s.speaker(), text, s.interrupted())
+                    .with_timing(s.timing())
+                    .with_language(s.language().cloned())
+                    .with_review(s.review().clone())
+            })
+            .collect::<Vec<Sentence>>();
+        speech.update_sentences(&sentences);
+        Ok((speech, total_sentences))
+    }
+
+    /// Returns the page of the speech reachable through the slug along with its total
+    /// number of sentences and its current slug, which differs from the one given when the
+    /// speech has been renamed since.
+    pub async fn get_speech_by_slug(
+        &self,
+        slug: &str,
+        page: u16,
+        quantity: u16,
+    ) -> Result<(Speech, u64, String), SpeechRepositoryError> {
+        let (uid, current_slug) = self.repository.resolve_speech_slug(slug).await?;
+        let (speech, total_sentences) = self
+            .repository
+            .get_speech_page(uid, page, quantity, None)
+            .await?;
+        Ok((speech, total_sentences, current_slug))
+    }
+
     pub async fn get_speech(
         &self,
         page: u16,
         quantity: u16,
-        speakers: &[Uuid],
-    ) -> Result<Vec<Speech>, SpeechRepositoryError> {
-        self.repository.get_speech(page, quantity, speakers).await
+        filter: &SpeechFilter,
+        projection: SpeechProjection,
+    ) -> Result<Vec<SpeechSummary>, SpeechRepositoryError> {
+        self.repository
+            .get_speech(page, quantity, filter, projection)
+            .await
+    }
+
+    pub async fn count_speech(&self, filter: &SpeechFilter) -> Result<u64, SpeechRepositoryError> {
+        self.repository.count_speech(filter).await
+    }
+
+    pub async fn count_speech_by_group(
+        &self,
+        filter: &SpeechFilter,
+        grouping: SpeechGrouping,
+    ) -> Result<Vec<SpeechGroupCount>, SpeechRepositoryError> {
+        self.repository
+            .count_speech_by_group(filter, grouping)
+            .await
+    }
+
+    pub async fn set_read_progress(
+        &self,
+        user_id: &str,
+        uid: Uuid,
+        sentence_index: u32,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.repository
+            .set_read_progress(user_id, uid, sentence_index)
+            .await?;
+        // The lists carry the read progress of the user.
+        self.bump_collection_version().await
+    }
+
+    pub async fn get_read_progress(
+        &self,
+        user_id: &str,
+        uids: &[Uuid],
+    ) -> Result<HashMap<Uuid, ReadProgress>, SpeechRepositoryError> {
+        self.repository.get_read_progress(user_id, uids).await
+    }
+
+    pub async fn find_duplicate_speeches(
+        &self,
+        identities: &[SpeechIdentity],
+    ) -> Result<Vec<SpeechDuplicate>, SpeechRepositoryError> {
+        self.repository.find_duplicate_speeches(identities).await
+    }
+
+    /// Creates the speeches of a bulk import. The speeches that cannot be created are
+    /// stored as conflicts of the import instead of failing the whole import. The speeches
+    /// done are reported to the job of the import when given.
+    pub async fn import_speeches(
+        &self,
+        speeches: Vec<Speech>,
+        person_manager: &PersonManager,
+        mut progress: Option<&mut JobProgress>,
+    ) -> Result<ImportReport, SpeechRepositoryError> {
+        let mut report = ImportReport {
+            import_uid: Uuid::new_v4(),
+            created: Vec::new(),
+            existing: Vec::new(),
+            skipped: 0,
+            conflicts: Vec::new(),
+        };
+        let total = speeches.len() as u64;
+        for (position, speech) in speeches.into_iter().enumerate() {
+            if let Some(progress) = progress.as_deref_mut() {
+                progress.advance(position as u64, Some(total)).await;
+            }
+            match self.attempt_import(&speech, person_manager).await? {
+                ImportAttempt::Created => report.created.push(*speech.uid()),
+                ImportAttempt::Conflict(kind, reference) => {
+                    let conflict = ImportConflict {
+                        uid: Uuid::new_v4(),
+                        import_uid: report.import_uid,
+                        position: position as u32,
+                        kind,
+                        reference,
+                        speech,
+                        resolution: None,
+                        created_at: Utc::now(),
+                    };
+                    // Saved at once, an error on a later speech must not lose it while the
+                    // speeches created before it are kept.
+                    self.repository
+                        .save_import_conflicts(std::slice::from_ref(&conflict))
+                        .await?;
+                    report.conflicts.push(conflict);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Tries to create a speech of an import, telling why it conflicts when it cannot.
+    async fn attempt_import(
+        &self,
+        speech: &Speech,
+        person_manager: &PersonManager,
+    ) -> Result<ImportAttempt, SpeechRepositoryError> {
+        match self.create_speech(speech.clone()).await {
+            Ok(()) => Ok(ImportAttempt::Created),
+            Err(SpeechRepositoryError::SpeechAlreadyExists) => {
+                let identity = SpeechIdentity {
+                    name: speech.name().clone(),
+                    date: *speech.date(),
+                    media: speech.media().clone(),
+                };
+                let existing = self
+                    .repository
+                    .find_duplicate_speeches(&[identity])
+                    .await?
+                    .pop()
+                    .and_then(|duplicate| duplicate.uid);
+                Ok(ImportAttempt::Conflict(
+                    ImportConflictKind::DuplicateSpeech,
+                    existing,
+                ))
+            }
+            Err(SpeechRepositoryError::UnknownSpeakers(unknown)) => Ok(ImportAttempt::Conflict(
+                ImportConflictKind::UnknownSpeaker,
+                unknown.first().copied(),
+            )),
+            Err(SpeechRepositoryError::PersonError(PersonRepositoryError::PersonNotFound)) => {
+                let mut persons = speech.speakers().clone();
+                persons.extend(speech.sentences().iter().map(|s| *s.speaker()));
+                for person in persons {
+                    match person_manager.get_person_by_id(&person).await {
+                        Ok(_) => {}
+                        Err(PersonRepositoryError::PersonNotFound) => {
+                            return Ok(ImportAttempt::Conflict(
+                                ImportConflictKind::UnknownSpeaker,
+                                Some(person),
+                            ))
+                        }
+                        Err(e) => return Err(SpeechRepositoryError::PersonError(e)),
+                    }
+                }
+                Err(SpeechRepositoryError::PersonError(
+                    PersonRepositoryError::PersonNotFound,
+                ))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn get_import_conflicts(
+        &self,
+        import_uid: Uuid,
+    ) -> Result<Vec<ImportConflict>, SpeechRepositoryError> {
+        self.repository.get_import_conflicts(import_uid).await
+    }
+
+    /// Records the resolution of a conflict and updates the speech to import accordingly:
+    /// a duplicate created anyway takes `new_name`, an unknown speaker is replaced by the
+    /// existing person or by `new_person`, created here.
+    pub async fn resolve_import_conflict(
+        &self,
+        import_uid: Uuid,
+        uid: Uuid,
+        resolution: ImportResolution,
+        new_name: Option<String>,
+        new_person: Option<Person>,
+        person_manager: &PersonManager,
+    ) -> Result<ImportConflict, SpeechRepositoryError> {
+        let mut conflict = self.repository.get_import_conflict(import_uid, uid).await?;
+        match (conflict.kind, resolution) {
+            (_, ImportResolution::Skip) => {}
+            (ImportConflictKind::DuplicateSpeech, ImportResolution::UseExisting(existing)) => {
+                if conflict.reference != Some(existing) {
+                    return Err(SpeechRepositoryError::InvalidImportResolution(
+                        "Only the speech the import duplicates can be kept".to_owned(),
+                    ));
+                }
+            }
+            (ImportConflictKind::DuplicateSpeech, ImportResolution::CreateNew) => {
+                let name = new_name
+                    .filter(|name| !name.is_empty() && name != conflict.speech.name())
+                    .ok_or(SpeechRepositoryError::InvalidImportResolution(
+                        "A new name is required to create a duplicate speech".to_owned(),
+                    ))?;
+                conflict.speech.update_name(&name);
+            }
+            (ImportConflictKind::UnknownSpeaker, ImportResolution::UseExisting(person)) => {
+                person_manager
+                    .get_person_by_id(&person)
+                    .await
+                    .map_err(SpeechRepositoryError::PersonError)?;
+                if let Some(speaker) = conflict.reference {
+                    conflict.speech.replace_speaker(&speaker, &person);
+                }
+            }
+            (ImportConflictKind::UnknownSpeaker, ImportResolution::CreateNew) => {
+                let person = new_person.ok_or(SpeechRepositoryError::InvalidImportResolution(
+                    "The person to create is required to replace an unknown speaker".to_owned(),
+                ))?;
+                let person_uid = *person.uid();
+                person_manager
+                    .create_person(person)
+                    .await
+                    .map_err(SpeechRepositoryError::PersonError)?;
+                if let Some(speaker) = conflict.reference {
+                    conflict.speech.replace_speaker(&speaker, &person_uid);
+                }
+            }
+        }
+        conflict.resolution = Some(resolution);
+        self.repository
+            .save_import_conflicts(std::slice::from_ref(&conflict))
+            .await?;
+        Ok(conflict)
+    }
+
+    /// Applies the resolved conflicts of an import: the speeches are created, kept or left
+    /// out. A speech conflicting again gets its conflict updated, waiting for a new
+    /// resolution, as do the conflicts not resolved yet.
+    pub async fn apply_import(
+        &self,
+        import_uid: Uuid,
+        person_manager: &PersonManager,
+    ) -> Result<ImportReport, SpeechRepositoryError> {
+        let mut report = ImportReport {
+            import_uid,
+            created: Vec::new(),
+            existing: Vec::new(),
+            skipped: 0,
+            conflicts: Vec::new(),
+        };
+        let mut applied = Vec::new();
+        let mut updated = Vec::new();
+        for mut conflict in self.repository.get_import_conflicts(import_uid).await? {
+            match (conflict.kind, conflict.resolution) {
+                (_, None) => {
+                    report.conflicts.push(conflict);
+                    continue;
+                }
+                (_, Some(ImportResolution::Skip)) => report.skipped += 1,
+                (ImportConflictKind::DuplicateSpeech, Some(ImportResolution::UseExisting(uid))) => {
+                    report.existing.push(uid)
+                }
+                _ => match self
+                    .attempt_import(&conflict.speech, person_manager)
+                    .await?
+                {
+                    ImportAttempt::Created => report.created.push(*conflict.speech.uid()),
+                    ImportAttempt::Conflict(kind, reference) => {
+                        conflict.kind = kind;
+                        conflict.reference = reference;
+                        conflict.resolution = None;
+                        updated.push(conflict.clone());
+                        report.conflicts.push(conflict);
+                        continue;
+                    }
+                },
+            }
+            applied.push(conflict.uid);
+        }
+        self.repository.save_import_conflicts(&updated).await?;
+        self.repository.delete_import_conflicts(&applied).await?;
+        Ok(report)
     }
 
     pub async fn delete_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError> {
-        self.repository.delete_speech(uid).await
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        self.repository.delete_speech(uid).await?;
+        self.bump_collection_version().await?;
+        self.publish(SpeechEventKind::Deleted, &speech);
+        Ok(())
+    }
+
+    pub async fn restore_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError> {
+        self.repository.restore_speech(uid).await?;
+        self.bump_collection_version().await?;
+        let speech = self.repository.get_speech_by_id(uid).await?;
+        self.publish(SpeechEventKind::Restored, &speech);
+        Ok(())
+    }
+}
+
+fn detect_missing_language(speech: &mut Speech) {
+    if speech.language().is_none() {
+        let language = SpeechLanguage::detect(speech.sentences());
+        speech.update_language(language);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        domain::{
+            pii::{PiiDetector, PiiDetectorError, PiiFinding},
+            speech::{import::ImportConflictKind, speech_repository::SpeechRepositoryError},
+            translation::{Translator, TranslatorError},
+        },
+        test_support::{test_database, PersonBuilder, SpeechBuilder},
+    };
+
+    /// Translates by upper-casing the texts.
+    #[derive(Clone)]
+    struct UppercaseTranslator;
+
+    #[async_trait::async_trait]
+    impl Translator for UppercaseTranslator {
+        async fn translate(
+            &self,
+            texts: &[String],
+            _target: &str,
+        ) -> Result<Vec<String>, TranslatorError> {
+            Ok(texts.iter().map(|t| t.to_uppercase()).collect())
+        }
+    }
+
+    /// Fails on the texts mentioning a breakdown, finds nothing in the others.
+    #[derive(Clone)]
+    struct FailingPiiDetector;
+
+    #[async_trait::async_trait]
+    impl PiiDetector for FailingPiiDetector {
+        async fn detect(&self, texts: &[String]) -> Result<Vec<Vec<PiiFinding>>, PiiDetectorError> {
+            match texts.iter().any(|t| t.contains("panne")) {
+                true => Err(PiiDetectorError::ProviderError("panne".to_owned())),
+                false => Ok(vec![Vec::new(); texts.len()]),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_import_conflicts_are_kept_when_the_import_fails() {
+        let database = test_database().await;
+        let managers = database.managers();
+        let speech_manager = managers
+            .speech_manager
+            .with_pii_detector(Box::new(FailingPiiDetector));
+        let speaker = database.create_person(PersonBuilder::new()).await;
+        let created = SpeechBuilder::new()
+            .with_sentence(speaker.uid(), "Bonjour.")
+            .build();
+        let with_unknown = SpeechBuilder::new()
+            .with_sentence(&Uuid::new_v4(), "Merci.")
+            .build();
+        let failing = SpeechBuilder::new()
+            .with_sentence(speaker.uid(), "Le réseau est en panne.")
+            .build();
+        let result = speech_manager
+            .import_speeches(
+                vec![created.clone(), created.clone(), with_unknown, failing],
+                &managers.person_manager,
+                None,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(SpeechRepositoryError::PiiDetectionError(_))
+        ));
+        let pool = sqlx::PgPool::connect(database.url()).await.unwrap();
+        let kinds: Vec<String> =
+            sqlx::query_scalar("SELECT kind FROM import_conflict ORDER BY position")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(kinds, vec!["duplicate_speech", "unknown_speaker"]);
+    }
+
+    #[tokio::test]
+    async fn test_translated_speech_keeps_sentence_languages() {
+        let database = test_database().await;
+        let speaker = database.create_person(PersonBuilder::new()).await;
+        let speech = database
+            .create_speech(
+                SpeechBuilder::new()
+                    .with_language("fra")
+                    .with_sentence(speaker.uid(), "Bonjour.")
+                    .with_sentence_in(speaker.uid(), "Thank you.", "eng"),
+            )
+            .await;
+        let manager = database
+            .managers()
+            .speech_manager
+            .with_translator(Box::new(UppercaseTranslator));
+        let (translated, total) = manager
+            .get_translated_speech(*speech.uid(), "de", 0, 10, None)
+            .await
+            .unwrap();
+        assert_eq!(total, 2);
+        let sentences = translated.sentences();
+        assert_eq!(sentences[0].text(), "BONJOUR.");
+        assert_eq!(sentences[0].language(), None);
+        assert_eq!(sentences[1].text(), "THANK YOU.");
+        assert_eq!(sentences[1].language(), Some(&"eng".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn test_import_speeches_with_unknown_speaker() {
+        let database = test_database().await;
+        let managers = database.managers();
+        let speaker = database.create_person(PersonBuilder::new()).await;
+        let unknown = Uuid::new_v4();
+        let known = SpeechBuilder::new()
+            .with_sentence(speaker.uid(), "Bonjour.")
+            .build();
+        let with_unknown = SpeechBuilder::new()
+            .with_sentence(speaker.uid(), "Bonjour.")
+            .with_sentence(&unknown, "Merci.")
+            .build();
+        let report = managers
+            .speech_manager
+            .import_speeches(
+                vec![known.clone(), with_unknown],
+                &managers.person_manager,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.created, vec![*known.uid()]);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].kind, ImportConflictKind::UnknownSpeaker);
+        assert_eq!(report.conflicts[0].reference, Some(unknown));
+        assert_eq!(report.conflicts[0].position, 1);
+        let stored = managers
+            .speech_manager
+            .get_import_conflicts(report.import_uid)
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
     }
 }
@@ -1,5 +1,20 @@
+pub mod analytics;
+pub mod cluster;
+pub mod comparison;
+pub mod consolidation;
+pub mod event;
+pub mod event_log;
+pub mod import;
+pub mod language;
+pub mod live;
 pub mod manager;
+pub mod pii_flag;
+pub mod progress;
+pub mod quote;
+pub mod revision;
 pub mod sentence;
+pub mod slug;
 mod speech;
 pub mod speech_repository;
+pub mod statement;
 pub use speech::*;
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::pii::{PiiFinding, PiiKind};
+
+/// Personal data found in the sentences of a speech, to be reviewed by an editor before
+/// the speech is published.
+///
+/// A speech has a single flag by kind and excerpt, found at its first occurrence. Once
+/// reviewed, the same excerpt is not flagged again when the sentences are edited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiFlag {
+    pub uid: Uuid,
+    /// Position of the sentence holding the data when it was found, starting at 0.
+    pub sentence_index: u32,
+    pub kind: PiiKind,
+    pub excerpt: String,
+    pub created_at: DateTime<Utc>,
+    /// User who reviewed the flag, `None` while it waits for review.
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl PiiFlag {
+    /// Flag waiting for review of data found in a sentence.
+    pub fn new(sentence_index: u32, finding: PiiFinding) -> Self {
+        Self {
+            uid: Uuid::new_v4(),
+            sentence_index,
+            kind: finding.kind,
+            excerpt: finding.excerpt,
+            created_at: Utc::now(),
+            reviewed_by: None,
+            reviewed_at: None,
+        }
+    }
+
+    pub fn is_reviewed(&self) -> bool {
+        self.reviewed_at.is_some()
+    }
+}
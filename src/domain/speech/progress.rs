@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+
+/// Sentence of a speech a user has read up to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadProgress {
+    sentence_index: u32,
+    updated_at: DateTime<Utc>,
+}
+
+impl ReadProgress {
+    pub fn new(sentence_index: u32, updated_at: DateTime<Utc>) -> Self {
+        Self {
+            sentence_index,
+            updated_at,
+        }
+    }
+
+    /// Position of the sentence in the speech, starting at 0.
+    pub fn sentence_index(&self) -> u32 {
+        self.sentence_index
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
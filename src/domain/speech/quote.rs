@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{sentence::Sentence, Speech};
+
+/// Sentence of a speech quoted along with the sentences surrounding it.
+#[derive(Clone)]
+pub struct Quote {
+    /// Position of the sentence in the speech, starting at 0.
+    index: usize,
+    sentence: Sentence,
+    before: Vec<Sentence>,
+    after: Vec<Sentence>,
+}
+
+impl Quote {
+    /// Quotes the sentence of the speech with at most `context` sentences before and after
+    /// it, `None` being returned when the speech has no sentence with this uid.
+    pub fn from_speech(speech: &Speech, sentence_uid: &Uuid, context: usize) -> Option<Self> {
+        let sentences = speech.sentences();
+        let index = sentences.iter().position(|s| s.uid() == sentence_uid)?;
+        Some(Self {
+            index,
+            sentence: sentences[index].clone(),
+            before: sentences[index.saturating_sub(context)..index].to_vec(),
+            after: sentences[index + 1..(index + 1 + context).min(sentences.len())].to_vec(),
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn sentence(&self) -> &Sentence {
+        &self.sentence
+    }
+
+    /// Sentences preceding the quote, the earliest first.
+    pub fn before(&self) -> &Vec<Sentence> {
+        &self.before
+    }
+
+    /// Sentences following the quote.
+    pub fn after(&self) -> &Vec<Sentence> {
+        &self.after
+    }
+}
+
+/// Builds the canonical citation of a quote:
+/// `Jean Dupont, "text", Speech name, TF1, 2024-03-12`.
+pub fn citation(
+    speaker_name: &str,
+    text: &str,
+    speech_name: &str,
+    media: &str,
+    date: &DateTime<Utc>,
+) -> String {
+    format!(
+        "{}, \"{}\", {}, {}, {}",
+        speaker_name,
+        text.trim(),
+        speech_name,
+        media,
+        date.format("%Y-%m-%d")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    use super::{citation, Quote};
+    use crate::domain::speech::{sentence::Sentence, Speech, SpeechStatus};
+
+    #[test]
+    fn quote_keeps_the_surrounding_sentences_within_the_speech() {
+        let speaker = Uuid::new_v4();
+        let sentences = (0..4)
+            .map(|i| Sentence::new(&Uuid::new_v4(), &speaker, &format!("S{}", i), false))
+            .collect::<Vec<Sentence>>();
+        let speech = Speech::new(
+            &Uuid::new_v4(),
+            "Debate",
+            Utc::now(),
+            &[speaker],
+            &sentences,
+            "TF1",
+            SpeechStatus::Validated,
+        );
+        let quote = Quote::from_speech(&speech, sentences[1].uid(), 2).unwrap();
+        assert_eq!(quote.index(), 1);
+        assert_eq!(quote.before().len(), 1);
+        assert_eq!(quote.after().len(), 2);
+        assert!(Quote::from_speech(&speech, &Uuid::new_v4(), 2).is_none());
+        assert_eq!(
+            citation(
+                "Jean Dupont",
+                " Hello ",
+                "Debate",
+                "TF1",
+                &Utc.with_ymd_and_hms(2024, 3, 12, 20, 0, 0).unwrap()
+            ),
+            "Jean Dupont, \"Hello\", Debate, TF1, 2024-03-12"
+        );
+    }
+}
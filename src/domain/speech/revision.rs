@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+
+use super::Speech;
+
+/// Full snapshot of a speech as saved by one of its writes (creation or update).
+pub struct SpeechRevision {
+    revision: u32,
+    created_at: DateTime<Utc>,
+    speech: Speech,
+}
+
+impl SpeechRevision {
+    pub fn new(revision: u32, created_at: DateTime<Utc>, speech: Speech) -> Self {
+        Self {
+            revision,
+            created_at,
+            speech,
+        }
+    }
+
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn speech(&self) -> &Speech {
+        &self.speech
+    }
+
+    pub fn into_speech(self) -> Speech {
+        self.speech
+    }
+}
@@ -1,11 +1,76 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+/// Position of a sentence in the media, in milliseconds from the start of the media.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SentenceTiming {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl SentenceTiming {
+    pub fn duration(&self) -> u32 {
+        self.end.saturating_sub(self.start)
+    }
+}
+
+/// Whether a reviewer checked the transcription of a sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReviewStatus {
+    #[default]
+    Unreviewed,
+    Approved,
+    /// The transcription is wrong, the speech cannot be validated until it is fixed.
+    Flagged,
+}
+
+impl TryFrom<&str> for ReviewStatus {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "unreviewed" => Self::Unreviewed,
+            "approved" => Self::Approved,
+            "flagged" => Self::Flagged,
+            _ => return Err("Unexpected review status value".to_owned()),
+        })
+    }
+}
+
+impl Display for ReviewStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewStatus::Unreviewed => f.write_str("unreviewed"),
+            ReviewStatus::Approved => f.write_str("approved"),
+            ReviewStatus::Flagged => f.write_str("flagged"),
+        }
+    }
+}
+
+/// Last review of the transcription of a sentence. A sentence replaced by an update of
+/// the speech is a new sentence, unreviewed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SentenceReview {
+    pub status: ReviewStatus,
+    /// Note of the reviewer, e.g. what was actually said.
+    pub comment: Option<String>,
+    /// User who reviewed the sentence, `None` while it was never reviewed.
+    pub reviewed_by: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Clone)]
 pub struct Sentence {
     uid: Uuid,
     speaker: Uuid,
     text: String,
     interrupted: bool,
+    timing: Option<SentenceTiming>,
+    review: SentenceReview,
+    /// ISO 639-3 code of the language of the sentence, `None` when it is spoken in the
+    /// language of the speech.
+    language: Option<String>,
 }
 
 impl Sentence {
@@ -15,9 +80,27 @@ impl Sentence {
             speaker: speaker.clone(),
             text: text.to_string(),
             interrupted,
+            timing: None,
+            review: SentenceReview::default(),
+            language: None,
         }
     }
 
+    pub fn with_timing(mut self, timing: Option<SentenceTiming>) -> Self {
+        self.timing = timing;
+        self
+    }
+
+    pub fn with_review(mut self, review: SentenceReview) -> Self {
+        self.review = review;
+        self
+    }
+
+    pub fn with_language(mut self, language: Option<String>) -> Self {
+        self.language = language;
+        self
+    }
+
     pub fn uid(&self) -> &Uuid {
         &self.uid
     }
@@ -33,4 +116,16 @@ impl Sentence {
     pub fn interrupted(&self) -> bool {
         self.interrupted
     }
+
+    pub fn timing(&self) -> Option<SentenceTiming> {
+        self.timing
+    }
+
+    pub fn review(&self) -> &SentenceReview {
+        &self.review
+    }
+
+    pub fn language(&self) -> Option<&String> {
+        self.language.as_ref()
+    }
 }
@@ -0,0 +1,47 @@
+/// Builds the URL slug of a text: lowercase ASCII words separated by dashes.
+/// Accented latin letters are folded to their base letter ("Débat" gives "debat").
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars().flat_map(|c| c.to_lowercase()) {
+        let folded = match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+            'æ' => "ae",
+            'ç' => "c",
+            'è' | 'é' | 'ê' | 'ë' => "e",
+            'ì' | 'í' | 'î' | 'ï' => "i",
+            'ñ' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+            'œ' => "oe",
+            'ù' | 'ú' | 'û' | 'ü' => "u",
+            'ý' | 'ÿ' => "y",
+            'ß' => "ss",
+            c if c.is_ascii_alphanumeric() => {
+                slug.push(c);
+                continue;
+            }
+            _ => "-",
+        };
+        if folded == "-" && (slug.is_empty() || slug.ends_with('-')) {
+            continue;
+        }
+        slug.push_str(folded);
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::slugify;
+
+    #[test]
+    fn slugify_folds_accents_and_separators() {
+        assert_eq!(
+            slugify("Jean Dupont - Débat sur l'Écologie !"),
+            "jean-dupont-debat-sur-l-ecologie"
+        );
+        assert_eq!(slugify("  --  "), "");
+    }
+}
@@ -1,10 +1,12 @@
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SpeechStatus {
+    /// Sentences are still being streamed by a live captioning pipeline.
+    Live,
     Pending,
     Validated,
 }
@@ -13,6 +15,7 @@ impl TryFrom<&str> for SpeechStatus {
     type Error = String;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         Ok(match value {
+            "LIVE" => Self::Live,
             "PENDING" => Self::Pending,
             "VALIDATED" => Self::Validated,
             _ => return Err("Unexpected speech status value".to_owned()),
@@ -23,21 +26,60 @@ impl TryFrom<&str> for SpeechStatus {
 impl Display for SpeechStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            SpeechStatus::Live => f.write_str("LIVE"),
             SpeechStatus::Pending => f.write_str("PENDING"),
             SpeechStatus::Validated => f.write_str("VALIDATED"),
         }
     }
 }
 
-use super::sentence::Sentence;
+/// Part a speaker plays in a speech. Moderators are left out of the talk-time
+/// statistics by default so they do not skew the balance between the debaters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpeakerRole {
+    Moderator,
+    #[default]
+    Panelist,
+    Guest,
+}
+
+impl TryFrom<&str> for SpeakerRole {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "moderator" => Self::Moderator,
+            "panelist" => Self::Panelist,
+            "guest" => Self::Guest,
+            _ => return Err("Unexpected speaker role value".to_owned()),
+        })
+    }
+}
+
+impl Display for SpeakerRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpeakerRole::Moderator => f.write_str("moderator"),
+            SpeakerRole::Panelist => f.write_str("panelist"),
+            SpeakerRole::Guest => f.write_str("guest"),
+        }
+    }
+}
+
+use super::{language::SpeechLanguage, sentence::Sentence};
+#[derive(Clone)]
 pub struct Speech {
     uid: Uuid,
     name: String,
     date: DateTime<Utc>,
     speakers: Vec<Uuid>,
+    /// Roles of the speakers, those missing are panelists.
+    speaker_roles: HashMap<Uuid, SpeakerRole>,
     sentences: Vec<Sentence>,
     media: String,
     speech_status: SpeechStatus,
+    language: Option<SpeechLanguage>,
+    /// Incremented by every update of the stored speech.
+    version: u32,
 }
 
 impl Speech {
@@ -55,9 +97,12 @@ impl Speech {
             name: name.to_string(),
             date: date,
             speakers: speakers.to_vec(),
+            speaker_roles: HashMap::new(),
             sentences: sentences.to_vec(),
             media: media.to_string(),
             speech_status,
+            language: None,
+            version: 1,
         };
     }
 
@@ -65,10 +110,19 @@ impl Speech {
         &self.uid
     }
 
+    /// Gives the speech the uid of the stored speech it replaces.
+    pub fn update_uid(&mut self, uid: &Uuid) {
+        self.uid = *uid;
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
 
+    pub fn update_name(&mut self, name: &str) {
+        self.name = name.to_string();
+    }
+
     pub fn date(&self) -> &DateTime<Utc> {
         &self.date
     }
@@ -81,10 +135,49 @@ impl Speech {
         self.speakers = speakers.to_vec();
     }
 
+    /// Makes another person speak in place of the speaker, in the speakers, the roles and
+    /// the sentences.
+    pub fn replace_speaker(&mut self, speaker: &Uuid, person: &Uuid) {
+        for s in self.speakers.iter_mut().filter(|s| *s == speaker) {
+            *s = *person;
+        }
+        if let Some(role) = self.speaker_roles.remove(speaker) {
+            self.speaker_roles.insert(*person, role);
+        }
+        for sentence in self.sentences.iter_mut().filter(|s| s.speaker() == speaker) {
+            *sentence = Sentence::new(
+                sentence.uid(),
+                person,
+                sentence.text(),
+                sentence.interrupted(),
+            )
+            .with_timing(sentence.timing())
+            .with_review(sentence.review().clone())
+            .with_language(sentence.language().cloned());
+        }
+    }
+
+    /// Roles explicitly given to the speakers.
+    pub fn speaker_roles(&self) -> &HashMap<Uuid, SpeakerRole> {
+        &self.speaker_roles
+    }
+
+    pub fn speaker_role(&self, speaker: &Uuid) -> SpeakerRole {
+        self.speaker_roles.get(speaker).copied().unwrap_or_default()
+    }
+
+    pub fn update_speaker_role(&mut self, speaker: &Uuid, role: SpeakerRole) {
+        self.speaker_roles.insert(*speaker, role);
+    }
+
     pub fn sentences(&self) -> &Vec<Sentence> {
         &self.sentences
     }
 
+    pub fn update_sentences(&mut self, sentences: &[Sentence]) {
+        self.sentences = sentences.to_vec();
+    }
+
     pub fn media(&self) -> &String {
         &self.media
     }
@@ -92,4 +185,32 @@ impl Speech {
     pub fn speech_status(&self) -> &SpeechStatus {
         &self.speech_status
     }
+
+    pub fn update_speech_status(&mut self, speech_status: SpeechStatus) {
+        self.speech_status = speech_status;
+    }
+
+    pub fn language(&self) -> Option<&SpeechLanguage> {
+        self.language.as_ref()
+    }
+
+    /// ISO 639-3 code of the language a sentence of the speech is spoken in: its own, or
+    /// else the language of the speech.
+    pub fn sentence_language<'a>(&'a self, sentence: &'a Sentence) -> Option<&'a String> {
+        sentence
+            .language()
+            .or_else(|| self.language.as_ref().map(|language| language.code()))
+    }
+
+    pub fn update_language(&mut self, language: Option<SpeechLanguage>) {
+        self.language = language;
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn update_version(&mut self, version: u32) {
+        self.version = version;
+    }
 }
@@ -1,28 +1,457 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::domain::person::PersonRepositoryError;
+use crate::domain::{
+    filter::{FilterField, FilterSpec, FilterValueKind},
+    person::PersonRepositoryError,
+    pii::PiiDetectorError,
+    translation::TranslatorError,
+    upsert::{OnConflict, UpsertOutcome},
+};
 
-use super::speech::Speech;
+use super::{
+    analytics::{
+        MonthlySpeechCount, SpeakerAnalytics, SpeakerStats, SpeechGroupCount, SpeechGrouping,
+        TimelineGranularity, TimelinePeriod,
+    },
+    cluster::SpeechCluster,
+    import::ImportConflict,
+    language::SpeechLanguage,
+    pii_flag::PiiFlag,
+    progress::ReadProgress,
+    revision::SpeechRevision,
+    sentence::{ReviewStatus, Sentence, SentenceReview},
+    speech::{SpeakerRole, Speech, SpeechStatus},
+    statement::{Statement, StatementQuery},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum SpeechRepositoryError {
     PersonError(PersonRepositoryError),
     SpeechNotFound,
+    /// The speech was updated since the version given by the client was read.
+    VersionMismatch,
+    SpeakerNotFound,
+    /// The person already speaks in the speech.
+    SpeakerAlreadyExists,
+    /// The speaker still speaks some sentences of the speech.
+    SpeakerHasSentences,
+    RevisionNotFound,
+    /// The speech is not live, its sentences cannot be streamed.
+    SpeechNotLive,
+    /// The speech is not waiting for review.
+    SpeechNotPending,
+    /// Another producer already streams the sentences of the speech.
+    LiveStreamInProgress,
+    /// A sentence is not the next one of the speech, which is given.
+    SentenceOutOfOrder(u32),
+    /// The speech has no sentence at the index given.
+    SentenceNotFound,
     SpeechAlreadyExists,
+    /// Speakers of the speech which are not persons of the organization, whose uids are
+    /// given.
+    UnknownSpeakers(Vec<Uuid>),
+    /// The import has no conflict with this uid.
+    ImportConflictNotFound,
+    /// The resolution does not fit the conflict, for the reason given.
+    InvalidImportResolution(String),
+    TranslationError(TranslatorError),
+    /// The speech has personal data flags waiting for review, whose number is given.
+    PiiNotReviewed(u32),
+    PiiFlagNotFound,
+    PiiDetectionError(PiiDetectorError),
+    /// The speech has sentences flagged by a reviewer, whose number is given.
+    SentencesFlagged(u32),
     InternalError(String),
 }
 
+/// Criteria shared by the speech list and count queries.
+#[derive(Debug, Default, Clone)]
+pub struct SpeechFilter {
+    /// Speeches where at least one of these persons speaks.
+    pub speakers: Vec<Uuid>,
+    /// Speeches tagged with at least one of these labels.
+    pub labels: Vec<Uuid>,
+    /// User reading the list, only the public labels and the labels of this user
+    /// matching `labels`.
+    pub label_reader: String,
+    /// Speeches tagged with at least one of these tags or of their narrower tags.
+    pub tags: Vec<Uuid>,
+    /// Speeches where a speaker, one of `speakers` if any, plays this role.
+    pub role: Option<SpeakerRole>,
+    /// Conditions on the fields of the speeches.
+    pub spec: FilterSpec<SpeechField>,
+    /// Speeches broadcast by one of these media.
+    pub media: Vec<String>,
+    /// Speeches whose name or one of the sentences contains one of these keywords, whatever
+    /// their case.
+    pub keywords: Vec<String>,
+    /// Speeches in this language, or with sentences in it, as an ISO 639-3 code.
+    pub language: Option<String>,
+    /// Speeches stored after this position, the list being then in the order the speeches
+    /// were stored. Only the speeches whose storage is over for good are listed, so a speech
+    /// stored meanwhile is never placed before the last one listed.
+    pub stored_after: Option<StoragePosition>,
+    pub include_deleted: bool,
+}
+
+/// Position of a speech in the order the speeches were stored: the transaction which
+/// stored it, then its uid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StoragePosition {
+    pub transaction: i64,
+    pub speech: Uuid,
+}
+
+/// Fields of a speech its lists can be filtered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeechField {
+    Name,
+    Media,
+    /// Day of the speech, in UTC.
+    Date,
+    Status,
+    /// ISO 639-3 code of the language of the speech.
+    Language,
+}
+
+impl FilterField for SpeechField {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "name" => Self::Name,
+            "media" => Self::Media,
+            "date" => Self::Date,
+            "status" => Self::Status,
+            "language" => Self::Language,
+            _ => return None,
+        })
+    }
+
+    fn kind(&self) -> FilterValueKind {
+        match self {
+            SpeechField::Name | SpeechField::Media | SpeechField::Language => FilterValueKind::Text,
+            SpeechField::Date => FilterValueKind::Date,
+            SpeechField::Status => FilterValueKind::Choice(&["LIVE", "PENDING", "VALIDATED"]),
+        }
+    }
+}
+
+/// Fields a speech is identified by: two speeches cannot share all of them.
+#[derive(Debug, Clone)]
+pub struct SpeechIdentity {
+    pub name: String,
+    pub date: DateTime<Utc>,
+    pub media: String,
+}
+
+/// Speech already stored with a given identity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechDuplicate {
+    /// Whether a speech of the organization has the identity.
+    pub exists: bool,
+    /// Uid of the speech having the identity.
+    pub uid: Option<Uuid>,
+}
+
+/// Longest preview of a speech in the lists, in characters.
+pub const SENTENCE_PREVIEW_LENGTH: usize = 200;
+
+/// Speech of a list, loaded without its sentences.
+#[derive(Clone)]
+pub struct SpeechSummary {
+    pub speech: Speech,
+    pub sentence_count: u32,
+    /// Start of the first sentence, at most `SENTENCE_PREVIEW_LENGTH` characters.
+    pub preview: Option<String>,
+    pub position: StoragePosition,
+}
+
+/// Parts of the speeches of a list the caller uses, the others being left out of the
+/// queries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechProjection {
+    /// Speakers of the speeches, with their roles.
+    pub speakers: bool,
+    /// Sentence count and preview of the speeches, left at zero and `None` otherwise.
+    pub sentence_summary: bool,
+}
+
+impl Default for SpeechProjection {
+    fn default() -> Self {
+        Self {
+            speakers: true,
+            sentence_summary: true,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 pub trait SpeechRepository: SpeechClone + Send + Sync {
-    async fn create_speech(&self, speech: &Speech) -> Result<(), SpeechRepositoryError>;
+    /// Returns a copy of the repository reaching only the speeches of the organization,
+    /// `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn SpeechRepository>;
+    /// Returns a copy of the repository whose reads only reach the validated speeches,
+    /// e.g. for the anonymous readers of the public read mode.
+    fn validated_only(&self) -> Box<dyn SpeechRepository>;
+    /// Creates the speech along with the personal data flags of its sentences, when they
+    /// were screened.
+    async fn create_speech(
+        &self,
+        speech: &Speech,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Creates the speech unless a speech of the same name, date and media is stored, the
+    /// stored speech being then kept or given the content of the speech, with a revision,
+    /// its status and uid staying the same. A soft deleted speech is kept. The flags
+    /// replace those of the speech written, see `update_speech`.
+    async fn upsert_speech(
+        &self,
+        speech: &Speech,
+        on_conflict: OnConflict,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<UpsertOutcome, SpeechRepositoryError>;
+    /// Replaces the content of the speech and records the new content as a revision. When
+    /// a version is expected, the speech is only replaced if it still has this version.
+    /// Every update of the speech increments its version. The personal data flags of the
+    /// new sentences replace the flags waiting for review, the flags of an excerpt already
+    /// reviewed being left out, unless the sentences were not screened.
+    async fn update_speech(
+        &self,
+        speech: &Speech,
+        expected_version: Option<u32>,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<(), SpeechRepositoryError>;
     async fn get_speech_by_id(&self, uid: Uuid) -> Result<Speech, SpeechRepositoryError>;
-    async fn get_speech(
+    /// Returns the speech with only the `quantity` sentences of the page, by index and
+    /// starting at page 0, along with its total number of sentences. With a review status,
+    /// only the sentences having it are paged and counted.
+    async fn get_speech_page(
         &self,
+        uid: Uuid,
         page: u16,
         quantity: u16,
+        review_status: Option<ReviewStatus>,
+    ) -> Result<(Speech, u64), SpeechRepositoryError>;
+    /// Whether the speech is visible to the organization, without loading its content.
+    async fn speech_exists(&self, uid: Uuid) -> Result<bool, SpeechRepositoryError>;
+    /// Returns the uids among `speakers` of no person of the organization, in order.
+    async fn get_unknown_speakers(
+        &self,
         speakers: &[Uuid],
-    ) -> Result<Vec<Speech>, SpeechRepositoryError>;
+    ) -> Result<Vec<Uuid>, SpeechRepositoryError>;
+    /// Resolves a current or former slug of a speech, returning the speech uid and its
+    /// current slug.
+    async fn resolve_speech_slug(
+        &self,
+        slug: &str,
+    ) -> Result<(Uuid, String), SpeechRepositoryError>;
+    async fn get_speech(
+        &self,
+        page: u16,
+        quantity: u16,
+        filter: &SpeechFilter,
+        projection: SpeechProjection,
+    ) -> Result<Vec<SpeechSummary>, SpeechRepositoryError>;
+    async fn count_speech(&self, filter: &SpeechFilter) -> Result<u64, SpeechRepositoryError>;
+    /// Records the sentence the user has read the speech up to.
+    async fn set_read_progress(
+        &self,
+        user_id: &str,
+        uid: Uuid,
+        sentence_index: u32,
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Returns the reading positions of the user in the speeches, the speeches not read
+    /// yet being missing.
+    async fn get_read_progress(
+        &self,
+        user_id: &str,
+        uids: &[Uuid],
+    ) -> Result<HashMap<Uuid, ReadProgress>, SpeechRepositoryError>;
+    /// Looks for the speeches having one of the identities, the duplicates being returned
+    /// in the order of the identities.
+    async fn find_duplicate_speeches(
+        &self,
+        identities: &[SpeechIdentity],
+    ) -> Result<Vec<SpeechDuplicate>, SpeechRepositoryError>;
+    /// Stores the conflicts of an import, replacing those having the same uid.
+    async fn save_import_conflicts(
+        &self,
+        conflicts: &[ImportConflict],
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Lists the conflicts of the import still waiting to be applied, by position.
+    async fn get_import_conflicts(
+        &self,
+        import_uid: Uuid,
+    ) -> Result<Vec<ImportConflict>, SpeechRepositoryError>;
+    async fn get_import_conflict(
+        &self,
+        import_uid: Uuid,
+        uid: Uuid,
+    ) -> Result<ImportConflict, SpeechRepositoryError>;
+    /// Forgets the conflicts once their speech is imported or left out.
+    async fn delete_import_conflicts(&self, uids: &[Uuid]) -> Result<(), SpeechRepositoryError>;
+    /// Lists the personal data flags of the speech, by sentence.
+    async fn get_pii_flags(&self, uid: Uuid) -> Result<Vec<PiiFlag>, SpeechRepositoryError>;
+    /// Marks the flag as reviewed by the user, returning it.
+    async fn review_pii_flag(
+        &self,
+        uid: Uuid,
+        flag: Uuid,
+        reviewed_by: &str,
+    ) -> Result<PiiFlag, SpeechRepositoryError>;
+    /// Replaces the review of the sentence of the speech, returning the sentence.
+    async fn review_sentence(
+        &self,
+        uid: Uuid,
+        sentence: Uuid,
+        review: &SentenceReview,
+    ) -> Result<Sentence, SpeechRepositoryError>;
+    /// Soft deletes the speech: the speech, its sentences and speakers are kept but the
+    /// speech is excluded from every read.
     async fn delete_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError>;
+    async fn restore_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError>;
+    /// Changes the processing status of the speech without recording a revision.
+    async fn update_speech_status(
+        &self,
+        uid: Uuid,
+        status: SpeechStatus,
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Changes the role of one speaker without recording a revision, only if the speech
+    /// still has the version expected when one is given.
+    async fn update_speaker_role(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+        role: SpeakerRole,
+        expected_version: Option<u32>,
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Adds a person of the organization to the speakers of the speech without recording
+    /// a revision.
+    async fn add_speaker(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+        role: SpeakerRole,
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Removes a speaker who speaks no sentence from the speech without recording a
+    /// revision.
+    async fn remove_speaker(&self, uid: Uuid, speaker: Uuid) -> Result<(), SpeechRepositoryError>;
+    /// Gives every sentence of a speaker of the speech to another person of the
+    /// organization, added to the speakers as a panelist when needed, without recording a
+    /// revision. Returns the number of sentences reassigned.
+    async fn reassign_sentences(
+        &self,
+        uid: Uuid,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<u64, SpeechRepositoryError>;
+    /// Appends sentences to the speech without recording a revision, `first_index` being
+    /// the number of sentences the speech is expected to have so far. The personal data
+    /// flags of the sentences are added along with them.
+    async fn append_sentences(
+        &self,
+        uid: Uuid,
+        first_index: u32,
+        sentences: &[Sentence],
+        flags: &[PiiFlag],
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Sets the live speech pending review without recording a revision, its sentences
+    /// being left as they are. The language is stored when given.
+    async fn finish_speech_ingestion(
+        &self,
+        uid: Uuid,
+        language: Option<&SpeechLanguage>,
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Lists the revisions of the speech, oldest first.
+    async fn get_speech_revisions(
+        &self,
+        uid: Uuid,
+    ) -> Result<Vec<SpeechRevision>, SpeechRepositoryError>;
+    async fn get_speech_revision(
+        &self,
+        uid: Uuid,
+        revision: u32,
+    ) -> Result<SpeechRevision, SpeechRepositoryError>;
+    /// Returns the stored translations of the sentences of the speech, by sentence uid.
+    async fn get_sentence_translations(
+        &self,
+        speech_uid: Uuid,
+        language: &str,
+    ) -> Result<HashMap<Uuid, String>, SpeechRepositoryError>;
+    async fn save_sentence_translations(
+        &self,
+        language: &str,
+        translations: &[(Uuid, String)],
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Computes the talk-time aggregates of every speaker of the speech, most words first.
+    /// Moderators are left out, shares included, unless `include_moderators` is set.
+    async fn get_speech_analytics(
+        &self,
+        uid: Uuid,
+        include_moderators: bool,
+    ) -> Result<Vec<SpeakerAnalytics>, SpeechRepositoryError>;
+    /// Sums the talk-time of a person over the speeches that are not deleted. The
+    /// speeches the person moderates are left out unless `include_moderators` is set.
+    async fn get_speaker_stats(
+        &self,
+        person_uid: Uuid,
+        include_moderators: bool,
+    ) -> Result<SpeakerStats, SpeechRepositoryError>;
+    /// Counts the speeches a person speaks in and the sentences they speak by period of
+    /// the speech dates, oldest period first, the periods without any being left out. The
+    /// speeches the person moderates are left out unless `include_moderators` is set.
+    async fn get_speaker_timeline(
+        &self,
+        person_uid: Uuid,
+        granularity: TimelineGranularity,
+        include_moderators: bool,
+    ) -> Result<Vec<TimelinePeriod>, SpeechRepositoryError>;
+    /// Sentences of the person containing the text across the speeches that are not
+    /// deleted, the latest speech first and in the order of each speech.
+    async fn search_statements(
+        &self,
+        person_uid: Uuid,
+        query: &StatementQuery,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<Statement>, SpeechRepositoryError>;
+    /// Counts the speeches matching the filter by group, the months in order and the
+    /// other groups with the most speeches first.
+    async fn count_speech_by_group(
+        &self,
+        filter: &SpeechFilter,
+        grouping: SpeechGrouping,
+    ) -> Result<Vec<SpeechGroupCount>, SpeechRepositoryError>;
+    /// Counts the speeches that are not deleted by media and month, oldest month first.
+    async fn count_speech_by_media_month(
+        &self,
+    ) -> Result<Vec<MonthlySpeechCount>, SpeechRepositoryError>;
+    /// Texts of the speeches that are not deleted and have sentences, the sentences being
+    /// joined in order. Ordered by uid, starting after `after`.
+    async fn get_speech_texts(
+        &self,
+        after: Option<Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<(Uuid, String)>, SpeechRepositoryError>;
+    /// Replaces every cluster of the organization.
+    async fn replace_speech_clusters(
+        &self,
+        clusters: &[SpeechCluster],
+    ) -> Result<(), SpeechRepositoryError>;
+    /// Cluster of the speech, `None` when the speech is similar to no other.
+    async fn get_speech_cluster(
+        &self,
+        uid: Uuid,
+    ) -> Result<Option<SpeechCluster>, SpeechRepositoryError>;
+    /// Clusters of the organization, largest first. The deleted speeches are left out, as
+    /// are the clusters they leave with a single speech.
+    async fn get_speech_clusters(
+        &self,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<SpeechCluster>, SpeechRepositoryError>;
 }
 
 pub trait SpeechClone {
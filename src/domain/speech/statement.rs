@@ -0,0 +1,88 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use super::{sentence::Sentence, SpeakerRole};
+
+/// Sentences of a person searched across the speeches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementQuery {
+    /// Text the sentences contain, whatever its case.
+    pub text: String,
+    /// First day of the speeches searched, in UTC.
+    pub from: Option<NaiveDate>,
+    /// Last day of the speeches searched, included, in UTC.
+    pub to: Option<NaiveDate>,
+    /// ISO 639-3 code of the language of the sentences searched.
+    pub language: Option<String>,
+}
+
+impl StatementQuery {
+    /// Whether a speech of this day is searched.
+    pub fn covers(&self, day: NaiveDate) -> bool {
+        self.from.is_none_or(|from| day >= from) && self.to.is_none_or(|to| day <= to)
+    }
+}
+
+/// Sentence spoken by a person, with the speech it was spoken in.
+#[derive(Clone)]
+pub struct Statement {
+    sentence: Sentence,
+    /// Position of the sentence in the speech, starting at 0.
+    index: u32,
+    speech_uid: Uuid,
+    speech_name: String,
+    speech_date: DateTime<Utc>,
+    media: String,
+    /// Part the person plays in the speech.
+    role: SpeakerRole,
+}
+
+impl Statement {
+    pub fn new(
+        sentence: Sentence,
+        index: u32,
+        speech_uid: Uuid,
+        speech_name: &str,
+        speech_date: DateTime<Utc>,
+        media: &str,
+        role: SpeakerRole,
+    ) -> Self {
+        Self {
+            sentence,
+            index,
+            speech_uid,
+            speech_name: speech_name.to_string(),
+            speech_date,
+            media: media.to_string(),
+            role,
+        }
+    }
+
+    pub fn sentence(&self) -> &Sentence {
+        &self.sentence
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn speech_uid(&self) -> &Uuid {
+        &self.speech_uid
+    }
+
+    pub fn speech_name(&self) -> &String {
+        &self.speech_name
+    }
+
+    pub fn speech_date(&self) -> &DateTime<Utc> {
+        &self.speech_date
+    }
+
+    pub fn media(&self) -> &String {
+        &self.media
+    }
+
+    pub fn role(&self) -> SpeakerRole {
+        self.role
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::{Days, Utc};
+use uuid::Uuid;
+
+use super::{
+    repository::{StatsRepository, StatsRepositoryError},
+    stats::{fill_days, SystemStats, STATS_DAYS, TOP_SPEAKERS},
+};
+
+#[derive(Clone)]
+pub struct StatsManager {
+    repository: Box<dyn StatsRepository>,
+}
+
+impl StatsManager {
+    pub fn new(repository: Box<dyn StatsRepository>) -> Self {
+        StatsManager { repository }
+    }
+
+    /// Returns a manager whose aggregates only count the rows of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+        }
+    }
+
+    /// Reads the aggregates of the organization, the speeches being counted on each of the
+    /// last `STATS_DAYS` days.
+    pub async fn get_stats(&self) -> Result<SystemStats, StatsRepositoryError> {
+        let today = Utc::now().date_naive();
+        let first = today
+            .checked_sub_days(Days::new(STATS_DAYS - 1))
+            .unwrap_or(today);
+        let (persons, speeches_by_status, sentences, per_day, top_speakers) = tokio::try_join!(
+            self.repository.count_persons(),
+            self.repository.count_speeches_by_status(),
+            self.repository.count_sentences(),
+            self.repository.count_speeches_per_day(first),
+            self.repository.get_top_speakers(TOP_SPEAKERS),
+        )?;
+        Ok(SystemStats {
+            persons,
+            speeches_by_status,
+            sentences,
+            speeches_per_day: fill_days(&per_day, today, STATS_DAYS),
+            top_speakers,
+        })
+    }
+}
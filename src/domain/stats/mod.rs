@@ -0,0 +1,7 @@
+mod manager;
+mod repository;
+mod stats;
+
+pub use manager::StatsManager;
+pub use repository::{StatsRepository, StatsRepositoryError};
+pub use stats::{fill_days, ActiveSpeaker, DailyCount, SystemStats, STATS_DAYS, TOP_SPEAKERS};
@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::stats::{ActiveSpeaker, DailyCount};
+use crate::domain::speech::SpeechStatus;
+
+#[derive(Debug, PartialEq)]
+pub enum StatsRepositoryError {
+    InternalError(String),
+}
+
+/// Aggregates of the database for the administrators of an organization. The soft
+/// deleted persons and speeches are never counted.
+#[async_trait::async_trait]
+pub trait StatsRepository: StatsClone + Send + Sync {
+    /// Returns a copy of the repository counting only the rows of the organization, `None`
+    /// being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn StatsRepository>;
+    async fn count_persons(&self) -> Result<u64, StatsRepositoryError>;
+    async fn count_speeches_by_status(
+        &self,
+    ) -> Result<Vec<(SpeechStatus, u64)>, StatsRepositoryError>;
+    /// Counts the sentences of the speeches.
+    async fn count_sentences(&self) -> Result<u64, StatsRepositoryError>;
+    /// Counts the speeches created on each day since `first`, in UTC. The days without
+    /// speeches are left out.
+    async fn count_speeches_per_day(
+        &self,
+        first: NaiveDate,
+    ) -> Result<Vec<DailyCount>, StatsRepositoryError>;
+    /// Returns the `quantity` persons speaking the most sentences, the most active first.
+    async fn get_top_speakers(
+        &self,
+        quantity: u32,
+    ) -> Result<Vec<ActiveSpeaker>, StatsRepositoryError>;
+}
+
+pub trait StatsClone {
+    fn clone_box(&self) -> Box<dyn StatsRepository>;
+}
+
+impl<T> StatsClone for T
+where
+    T: 'static + StatsRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn StatsRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn StatsRepository> {
+    fn clone(&self) -> Box<dyn StatsRepository> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{Days, NaiveDate};
+use uuid::Uuid;
+
+use crate::domain::speech::SpeechStatus;
+
+/// Days counted by the speeches created per day, today included.
+pub const STATS_DAYS: u64 = 30;
+
+/// Speakers listed among the most active.
+pub const TOP_SPEAKERS: u32 = 10;
+
+/// Number of speeches created on a day, in UTC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyCount {
+    pub day: NaiveDate,
+    pub count: u64,
+}
+
+/// Person with the speeches and the sentences spoken in them, the most sentences making
+/// the most active speakers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveSpeaker {
+    pub uid: Uuid,
+    pub name: String,
+    pub first_name: String,
+    pub speeches: u64,
+    pub sentences: u64,
+}
+
+/// Aggregates of the database for one organization. The soft deleted persons and speeches
+/// are left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemStats {
+    pub persons: u64,
+    /// Number of speeches of each status, the statuses without speeches being left out.
+    pub speeches_by_status: Vec<(SpeechStatus, u64)>,
+    pub sentences: u64,
+    /// Speeches created on each of the last `STATS_DAYS` days, the oldest first.
+    pub speeches_per_day: Vec<DailyCount>,
+    pub top_speakers: Vec<ActiveSpeaker>,
+}
+
+impl SystemStats {
+    pub fn speeches(&self) -> u64 {
+        self.speeches_by_status.iter().map(|(_, count)| count).sum()
+    }
+
+    /// Average number of sentences of a speech, 0 without speeches.
+    pub fn average_sentences(&self) -> f64 {
+        match self.speeches() {
+            0 => 0.0,
+            speeches => self.sentences as f64 / speeches as f64,
+        }
+    }
+}
+
+/// Counts of each of the `days` days ending on `last`, the oldest first, the days missing
+/// from the counts having none.
+pub fn fill_days(counts: &[DailyCount], last: NaiveDate, days: u64) -> Vec<DailyCount> {
+    (0..days)
+        .rev()
+        .filter_map(|ago| last.checked_sub_days(Days::new(ago)))
+        .map(|day| DailyCount {
+            day,
+            count: counts
+                .iter()
+                .find(|count| count.day == day)
+                .map_or(0, |count| count.count),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{fill_days, DailyCount};
+
+    #[test]
+    fn missing_days_have_no_speech() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let counts = [
+            DailyCount {
+                day: day(2),
+                count: 4,
+            },
+            DailyCount {
+                day: day(4),
+                count: 1,
+            },
+        ];
+        let filled = fill_days(&counts, day(4), 4);
+        assert_eq!(
+            filled
+                .iter()
+                .map(|count| (count.day, count.count))
+                .collect::<Vec<(NaiveDate, u64)>>(),
+            [(day(1), 0), (day(2), 4), (day(3), 0), (day(4), 1)]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use uuid::Uuid;
+
+use crate::domain::collection::{CollectionVersionError, CollectionVersions};
+
+use super::{
+    repository::{TagRepository, TagRepositoryError},
+    tag::Tag,
+};
+
+#[derive(Clone)]
+pub struct TagManager {
+    repository: Box<dyn TagRepository>,
+    collection_versions: Option<CollectionVersions>,
+}
+
+impl TagManager {
+    pub fn new(repository: Box<dyn TagRepository>) -> Self {
+        TagManager {
+            repository,
+            collection_versions: None,
+        }
+    }
+
+    /// Returns a manager whose operations only reach the tags of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+            collection_versions: self
+                .collection_versions
+                .as_ref()
+                .map(|versions| versions.for_organization(organization)),
+        }
+    }
+
+    /// Bumps the versions of the collections when a tag is attached or detached, the
+    /// lists being filtered by their tags.
+    pub fn with_collection_versions(mut self, collection_versions: CollectionVersions) -> Self {
+        self.collection_versions = Some(collection_versions);
+        self
+    }
+
+    async fn bump_collection_version(&self) -> Result<(), TagRepositoryError> {
+        if let Some(versions) = &self.collection_versions {
+            versions
+                .bump()
+                .await
+                .map_err(|CollectionVersionError::InternalError(e)| {
+                    TagRepositoryError::InternalError(e)
+                })?;
+        }
+        Ok(())
+    }
+
+    pub async fn create_tag(&self, tag: Tag) -> Result<(), TagRepositoryError> {
+        self.repository.create_tag(&tag).await
+    }
+
+    pub async fn get_tags(&self) -> Result<Vec<Tag>, TagRepositoryError> {
+        self.repository.get_tags().await
+    }
+
+    pub async fn get_speech_tags(&self, speech_uid: &Uuid) -> Result<Vec<Tag>, TagRepositoryError> {
+        self.repository.get_speech_tags(speech_uid).await
+    }
+
+    pub async fn attach_tag(
+        &self,
+        speech_uid: &Uuid,
+        tag_uid: &Uuid,
+    ) -> Result<(), TagRepositoryError> {
+        self.repository.attach_tag(speech_uid, tag_uid).await?;
+        self.bump_collection_version().await
+    }
+
+    pub async fn detach_tag(
+        &self,
+        speech_uid: &Uuid,
+        tag_uid: &Uuid,
+    ) -> Result<(), TagRepositoryError> {
+        self.repository.detach_tag(speech_uid, tag_uid).await?;
+        self.bump_collection_version().await
+    }
+}
@@ -0,0 +1,7 @@
+mod manager;
+mod repository;
+mod tag;
+
+pub use manager::TagManager;
+pub use repository::{TagRepository, TagRepositoryError};
+pub use tag::Tag;
@@ -0,0 +1,48 @@
+use uuid::Uuid;
+
+use super::tag::Tag;
+
+#[derive(Debug, PartialEq)]
+pub enum TagRepositoryError {
+    TagNotFound,
+    ParentTagNotFound,
+    SpeechNotFound,
+    TagAlreadyExists,
+    InternalError(String),
+}
+
+#[async_trait::async_trait]
+pub trait TagRepository: TagClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the tags and tagged speeches of the
+    /// organization, `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn TagRepository>;
+    /// Creates the tag, its parent must be a tag of the same organization.
+    async fn create_tag(&self, tag: &Tag) -> Result<(), TagRepositoryError>;
+    /// Returns the whole taxonomy, ordered by name.
+    async fn get_tags(&self) -> Result<Vec<Tag>, TagRepositoryError>;
+    async fn get_speech_tags(&self, speech_uid: &Uuid) -> Result<Vec<Tag>, TagRepositoryError>;
+    async fn attach_tag(&self, speech_uid: &Uuid, tag_uid: &Uuid)
+        -> Result<(), TagRepositoryError>;
+    async fn detach_tag(&self, speech_uid: &Uuid, tag_uid: &Uuid)
+        -> Result<(), TagRepositoryError>;
+}
+
+pub trait TagClone {
+    fn clone_box(&self) -> Box<dyn TagRepository>;
+}
+
+impl<T> TagClone for T
+where
+    T: 'static + TagRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn TagRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn TagRepository> {
+    fn clone(&self) -> Box<dyn TagRepository> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+/// Topic of the taxonomy speeches are browsed by, such as "immigration" or "budget".
+/// A tag with a parent narrows down the topic of its parent.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    uid: Uuid,
+    name: String,
+    parent: Option<Uuid>,
+}
+
+impl Tag {
+    pub fn new(uid: Uuid, name: &str, parent: Option<Uuid>) -> Self {
+        Self {
+            uid,
+            name: name.to_string(),
+            parent,
+        }
+    }
+
+    pub fn uid(&self) -> &Uuid {
+        &self.uid
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn parent(&self) -> Option<&Uuid> {
+        self.parent.as_ref()
+    }
+}
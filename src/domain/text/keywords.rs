@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use super::stopwords::{
+    ENGLISH_STOPWORDS, FRENCH_STOPWORDS, GERMAN_STOPWORDS, ITALIAN_STOPWORDS, SPANISH_STOPWORDS,
+};
+
+/// Shortest word counted as a keyword, shorter ones being articles or abbreviations.
+const MIN_WORD_LENGTH: usize = 3;
+
+/// Suffixes removed by the French stemming with their replacement, the first matching one
+/// only.
+const FRENCH_SUFFIXES: &[(&str, &str)] = &[
+    ("issements", ""),
+    ("issement", ""),
+    ("ements", ""),
+    ("ement", ""),
+    ("ations", ""),
+    ("ation", ""),
+    ("ités", ""),
+    ("ité", ""),
+    ("euses", "eu"),
+    ("euse", "eu"),
+    ("eux", "eu"),
+    ("aux", "al"),
+    ("es", ""),
+    ("s", ""),
+    ("e", ""),
+];
+
+/// Suffixes removed by the English stemming with their replacement, the first matching
+/// one only.
+const ENGLISH_SUFFIXES: &[(&str, &str)] = &[
+    ("sses", "ss"),
+    ("ies", "y"),
+    ("ss", "ss"),
+    ("ches", "ch"),
+    ("shes", "sh"),
+    ("xes", "x"),
+    ("ing", ""),
+    ("ed", ""),
+    ("s", ""),
+];
+
+/// Suffixes removed by the German stemming with their replacement, the first matching
+/// one only.
+const GERMAN_SUFFIXES: &[(&str, &str)] = &[
+    ("ungen", "ung"),
+    ("heiten", "heit"),
+    ("keiten", "keit"),
+    ("ern", ""),
+    ("en", ""),
+    ("er", ""),
+    ("es", ""),
+    ("e", ""),
+    ("s", ""),
+];
+
+/// Suffixes removed by the Spanish stemming with their replacement, the first matching
+/// one only.
+const SPANISH_SUFFIXES: &[(&str, &str)] = &[
+    ("aciones", "ación"),
+    ("ciones", "ción"),
+    ("idades", "idad"),
+    ("mente", ""),
+    ("es", ""),
+    ("s", ""),
+];
+
+/// Suffixes removed by the Italian stemming with their replacement, the first matching
+/// one only.
+const ITALIAN_SUFFIXES: &[(&str, &str)] = &[
+    ("azioni", "azione"),
+    ("zioni", "zione"),
+    ("mente", ""),
+    ("i", ""),
+    ("e", ""),
+    ("o", ""),
+    ("a", ""),
+];
+
+/// Languages whose stopwords and suffixes are known to the text analysis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextLanguage {
+    French,
+    English,
+    German,
+    Spanish,
+    Italian,
+}
+
+impl TextLanguage {
+    const ALL: [TextLanguage; 5] = [
+        Self::French,
+        Self::English,
+        Self::German,
+        Self::Spanish,
+        Self::Italian,
+    ];
+
+    /// Reads the ISO 639-3 code of a speech language, `None` for the other languages.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "fra" => Some(Self::French),
+            "eng" => Some(Self::English),
+            "deu" => Some(Self::German),
+            "spa" => Some(Self::Spanish),
+            "ita" => Some(Self::Italian),
+            _ => None,
+        }
+    }
+
+    fn stopwords(&self) -> &'static [&'static str] {
+        match self {
+            Self::French => FRENCH_STOPWORDS,
+            Self::English => ENGLISH_STOPWORDS,
+            Self::German => GERMAN_STOPWORDS,
+            Self::Spanish => SPANISH_STOPWORDS,
+            Self::Italian => ITALIAN_STOPWORDS,
+        }
+    }
+
+    fn suffixes(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::French => FRENCH_SUFFIXES,
+            Self::English => ENGLISH_SUFFIXES,
+            Self::German => GERMAN_SUFFIXES,
+            Self::Spanish => SPANISH_SUFFIXES,
+            Self::Italian => ITALIAN_SUFFIXES,
+        }
+    }
+}
+
+/// Term of a text with the number of times it, or a word of the same stem, occurs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyword {
+    term: String,
+    count: u64,
+}
+
+impl Keyword {
+    pub fn new(term: &str, count: u64) -> Self {
+        Self {
+            term: term.to_string(),
+            count,
+        }
+    }
+
+    /// Most frequent form of the words counted together.
+    pub fn term(&self) -> &String {
+        &self.term
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Replaces the first known suffix of the word, keeping at least `MIN_WORD_LENGTH`
+/// characters, e.g. "taxes" becomes "tax".
+fn stem(word: &str, language: TextLanguage) -> String {
+    for (suffix, replacement) in language.suffixes() {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            let stem = format!("{}{}", stem, replacement);
+            if stem.chars().count() >= MIN_WORD_LENGTH {
+                return stem;
+            }
+        }
+    }
+    word.to_string()
+}
+
+/// Without a known language, a word is left out when it is a stopword of any language.
+fn is_stopword(word: &str, language: Option<TextLanguage>) -> bool {
+    match language {
+        Some(language) => language.stopwords().contains(&word),
+        None => TextLanguage::ALL
+            .iter()
+            .any(|language| language.stopwords().contains(&word)),
+    }
+}
+
+/// Returns the `quantity` most frequent terms of the texts, each given with its language,
+/// the most frequent first. The stopwords of the language of a text are left out and the
+/// words sharing a stem are counted together. Without a known language, the stopwords of
+/// every language are left out and the words are not stemmed.
+pub fn extract_keywords<'a>(
+    texts: impl IntoIterator<Item = (&'a str, Option<TextLanguage>)>,
+    quantity: usize,
+) -> Vec<Keyword> {
+    // Occurrences of each form of the words, by stem.
+    let mut stems: HashMap<String, HashMap<String, u64>> = HashMap::new();
+    for (text, language) in texts {
+        for word in text.split(|c: char| !c.is_alphanumeric()) {
+            let word = word.to_lowercase();
+            if word.chars().count() < MIN_WORD_LENGTH
+                || word.chars().all(|c| c.is_numeric())
+                || is_stopword(&word, language)
+            {
+                continue;
+            }
+            let stem = match language {
+                Some(language) => stem(&word, language),
+                None => word.clone(),
+            };
+            *stems.entry(stem).or_default().entry(word).or_default() += 1;
+        }
+    }
+    let mut keywords: Vec<Keyword> = stems
+        .into_values()
+        .filter_map(|forms| {
+            let count = forms.values().sum();
+            // The most frequent form names the stem, the shortest on a tie.
+            let (term, _) = forms.iter().max_by(|(a, a_count), (b, b_count)| {
+                a_count
+                    .cmp(b_count)
+                    .then_with(|| b.chars().count().cmp(&a.chars().count()))
+                    .then_with(|| b.cmp(a))
+            })?;
+            Some(Keyword::new(term, count))
+        })
+        .collect();
+    keywords.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.term.cmp(&b.term)));
+    keywords.truncate(quantity);
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_keywords, Keyword, TextLanguage};
+
+    #[test]
+    fn keywords_are_counted_by_stem_without_stopwords() {
+        let texts = [
+            "Nous voulons une réforme des retraites.",
+            "La réforme de la retraite est injuste, cette réforme doit être retirée.",
+        ];
+        let keywords = extract_keywords(texts.map(|t| (t, Some(TextLanguage::French))), 2);
+        assert_eq!(
+            keywords,
+            vec![Keyword::new("réforme", 3), Keyword::new("retraite", 2)]
+        );
+        let keywords = extract_keywords([("The taxes and the tax cuts", None)], 10);
+        assert_eq!(
+            keywords,
+            vec![
+                Keyword::new("cuts", 1),
+                Keyword::new("tax", 1),
+                Keyword::new("taxes", 1)
+            ]
+        );
+        let keywords = extract_keywords(
+            [("The taxes and the tax cuts", Some(TextLanguage::English))],
+            1,
+        );
+        assert_eq!(keywords, vec![Keyword::new("tax", 2)]);
+    }
+
+    #[test]
+    fn each_text_is_analysed_in_its_language() {
+        let keywords = extract_keywords(
+            [
+                ("Die Reformen der Rente", Some(TextLanguage::German)),
+                ("Die Reform ist nicht gerecht", Some(TextLanguage::German)),
+                ("The reforms", Some(TextLanguage::English)),
+            ],
+            1,
+        );
+        assert_eq!(keywords, vec![Keyword::new("reform", 3)]);
+    }
+}
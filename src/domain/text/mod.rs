@@ -0,0 +1,4 @@
+mod keywords;
+mod stopwords;
+
+pub use keywords::{extract_keywords, Keyword, TextLanguage};
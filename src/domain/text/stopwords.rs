@@ -0,0 +1,162 @@
+/// Words too common in French to tell anything about a speech. The words of the text are
+/// compared lowercased, their accents kept.
+pub const FRENCH_STOPWORDS: &[&str] = &[
+    "afin",
+    "ainsi",
+    "alors",
+    "après",
+    "aucun",
+    "aucune",
+    "aujourd",
+    "auquel",
+    "aussi",
+    "autre",
+    "autres",
+    "aux",
+    "avant",
+    "avec",
+    "avez",
+    "avoir",
+    "avons",
+    "bien",
+    "car",
+    "ce",
+    "ceci",
+    "cela",
+    "celle",
+    "celles",
+    "celui",
+    "cependant",
+    "ces",
+    "cet",
+    "cette",
+    "ceux",
+    "chaque",
+    "chez",
+    "comme",
+    "comment",
+    "dans",
+    "des",
+    "depuis",
+    "donc",
+    "dont",
+    "elle",
+    "elles",
+    "encore",
+    "entre",
+    "est",
+    "été",
+    "étaient",
+    "était",
+    "êtes",
+    "être",
+    "eux",
+    "fait",
+    "faire",
+    "faut",
+    "fois",
+    "font",
+    "hui",
+    "ici",
+    "ils",
+    "jamais",
+    "leur",
+    "leurs",
+    "lui",
+    "mais",
+    "même",
+    "mêmes",
+    "mes",
+    "moi",
+    "moins",
+    "mon",
+    "nos",
+    "notre",
+    "nous",
+    "ont",
+    "oui",
+    "par",
+    "parce",
+    "pas",
+    "peu",
+    "peut",
+    "plus",
+    "pour",
+    "pourquoi",
+    "quand",
+    "que",
+    "quel",
+    "quelle",
+    "quelles",
+    "quels",
+    "qui",
+    "quoi",
+    "sans",
+    "sera",
+    "serait",
+    "ses",
+    "son",
+    "sont",
+    "sous",
+    "sur",
+    "tant",
+    "tous",
+    "tout",
+    "toute",
+    "toutes",
+    "très",
+    "une",
+    "vos",
+    "votre",
+    "vous",
+];
+
+/// Words too common in English to tell anything about a speech.
+pub const ENGLISH_STOPWORDS: &[&str] = &[
+    "about", "after", "again", "all", "also", "and", "any", "are", "because", "been", "before",
+    "being", "between", "both", "but", "can", "could", "did", "does", "doing", "down", "during",
+    "each", "few", "for", "from", "further", "had", "has", "have", "having", "her", "here", "hers",
+    "him", "his", "how", "into", "its", "just", "more", "most", "not", "now", "off", "once",
+    "only", "other", "our", "ours", "out", "over", "own", "same", "she", "should", "some", "such",
+    "than", "that", "the", "their", "theirs", "them", "then", "there", "these", "they", "this",
+    "those", "through", "too", "under", "until", "very", "was", "were", "what", "when", "where",
+    "which", "while", "who", "whom", "why", "will", "with", "would", "you", "your", "yours",
+];
+
+/// Words too common in German to tell anything about a speech.
+pub const GERMAN_STOPWORDS: &[&str] = &[
+    "aber", "alle", "allem", "allen", "aller", "alles", "als", "also", "andere", "anderen", "auch",
+    "auf", "aus", "bei", "beim", "bin", "bis", "bist", "damit", "dann", "das", "dass", "dem",
+    "den", "denn", "der", "des", "dessen", "die", "dies", "diese", "diesem", "diesen", "dieser",
+    "dieses", "doch", "dort", "durch", "ein", "eine", "einem", "einen", "einer", "eines", "er",
+    "es", "etwas", "euch", "für", "gegen", "haben", "hat", "hatte", "hier", "ich", "ihr", "ihre",
+    "ihren", "ihrer", "ihm", "ihn", "ihnen", "immer", "ist", "jetzt", "kann", "kein", "keine",
+    "man", "mehr", "mich", "mir", "mit", "muss", "nach", "nicht", "noch", "nur", "oder", "ohne",
+    "sehr", "sein", "seine", "seinen", "sich", "sie", "sind", "soll", "sondern", "über", "um",
+    "und", "uns", "unser", "unsere", "unter", "vom", "von", "vor", "war", "waren", "was", "weil",
+    "wenn", "werden", "wie", "wir", "wird", "wurde", "zum", "zur", "zwischen",
+];
+
+/// Words too common in Spanish to tell anything about a speech.
+pub const SPANISH_STOPWORDS: &[&str] = &[
+    "algo", "algunos", "ante", "antes", "aquí", "así", "aunque", "cada", "como", "con", "contra",
+    "cual", "cuando", "del", "desde", "donde", "durante", "ella", "ellas", "ellos", "entre", "era",
+    "eran", "esa", "esas", "ese", "eso", "esos", "esta", "está", "están", "estas", "este", "esto",
+    "estos", "fue", "fueron", "hay", "hace", "hacer", "han", "hasta", "las", "les", "los", "más",
+    "mismo", "mucho", "muy", "nada", "nos", "nosotros", "nuestra", "nuestro", "otra", "otro",
+    "otros", "para", "pero", "poco", "por", "porque", "que", "qué", "quien", "sea", "ser", "señor",
+    "señora", "sido", "sin", "sobre", "son", "sus", "también", "tan", "tanto", "tiene", "tienen",
+    "todo", "todos", "una", "unas", "uno", "unos", "usted", "ustedes", "vez", "ya",
+];
+
+/// Words too common in Italian to tell anything about a speech.
+pub const ITALIAN_STOPWORDS: &[&str] = &[
+    "abbiamo", "alla", "alle", "allo", "anche", "ancora", "avere", "che", "chi", "come", "con",
+    "contro", "cosa", "così", "dal", "dalla", "dalle", "degli", "dei", "del", "della", "delle",
+    "dello", "dopo", "dove", "essere", "fra", "gli", "hanno", "lei", "loro", "lui", "mai", "molto",
+    "negli", "nei", "nel", "nella", "nelle", "noi", "non", "nostra", "nostro", "ogni", "oggi",
+    "per", "perché", "più", "poi", "proprio", "quale", "quando", "quella", "quelle", "quelli",
+    "quello", "questa", "queste", "questi", "questo", "sarà", "se", "sia", "siamo", "signor",
+    "signora", "sono", "sopra", "stato", "sua", "sue", "sui", "sul", "sulla", "suo", "tra",
+    "tutti", "tutto", "una", "uno", "vi", "voi",
+];
@@ -0,0 +1,3 @@
+mod translator;
+
+pub use translator::{Translator, TranslatorError};
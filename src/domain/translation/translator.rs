@@ -0,0 +1,39 @@
+#[derive(Debug, PartialEq)]
+pub enum TranslatorError {
+    /// No translation provider is configured.
+    Unavailable,
+    /// The provider does not support the requested target language.
+    UnsupportedLanguage,
+    ProviderError(String),
+}
+
+/// Machine translation provider.
+#[async_trait::async_trait]
+pub trait Translator: TranslatorClone + Send + Sync {
+    /// Translates every text to the target language (ISO 639-1 code, e.g. "en"). The
+    /// source language is detected by the provider. Returns the translations in order.
+    async fn translate(
+        &self,
+        texts: &[String],
+        target: &str,
+    ) -> Result<Vec<String>, TranslatorError>;
+}
+
+pub trait TranslatorClone {
+    fn clone_box(&self) -> Box<dyn Translator>;
+}
+
+impl<T> TranslatorClone for T
+where
+    T: 'static + Translator + Clone,
+{
+    fn clone_box(&self) -> Box<dyn Translator> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Translator> {
+    fn clone(&self) -> Box<dyn Translator> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+/// What the creation of an entity already stored does, the entity being found by its
+/// natural key: the identity of a person, the name, date and media of a speech.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnConflict {
+    /// The stored entity is kept as it is.
+    Ignore,
+    /// The stored entity is replaced by the one created, keeping its uid.
+    Update,
+}
+
+impl TryFrom<&str> for OnConflict {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(match value {
+            "ignore" => Self::Ignore,
+            "update" => Self::Update,
+            _ => return Err("Unexpected on conflict value".to_owned()),
+        })
+    }
+}
+
+/// What the creation of an entity did, with the uid of the entity stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    Created(Uuid),
+    Updated(Uuid),
+    /// The entity was already stored and kept, or is soft deleted.
+    Skipped(Uuid),
+}
+
+impl UpsertOutcome {
+    pub fn uid(&self) -> &Uuid {
+        match self {
+            UpsertOutcome::Created(uid)
+            | UpsertOutcome::Updated(uid)
+            | UpsertOutcome::Skipped(uid) => uid,
+        }
+    }
+}
+
+impl Display for UpsertOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpsertOutcome::Created(_) => f.write_str("created"),
+            UpsertOutcome::Updated(_) => f.write_str("updated"),
+            UpsertOutcome::Skipped(_) => f.write_str("skipped"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{
+    repository::{WatchlistRepository, WatchlistRepositoryError},
+    watchlist::Watchlist,
+};
+use crate::domain::speech::speech_repository::StoragePosition;
+
+#[derive(Clone)]
+pub struct WatchlistManager {
+    repository: Box<dyn WatchlistRepository>,
+}
+
+impl WatchlistManager {
+    pub fn new(repository: Box<dyn WatchlistRepository>) -> Self {
+        WatchlistManager { repository }
+    }
+
+    /// Returns a manager whose operations only reach the watchlists of the organization.
+    pub fn for_organization(&self, organization: Option<Uuid>) -> Self {
+        Self {
+            repository: self.repository.for_organization(organization),
+        }
+    }
+
+    pub async fn create_watchlist(
+        &self,
+        watchlist: Watchlist,
+    ) -> Result<(), WatchlistRepositoryError> {
+        self.repository.create_watchlist(&watchlist).await
+    }
+
+    pub async fn get_watchlists(
+        &self,
+        owner: &str,
+    ) -> Result<Vec<Watchlist>, WatchlistRepositoryError> {
+        self.repository.get_watchlists(owner).await
+    }
+
+    pub async fn get_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+    ) -> Result<Watchlist, WatchlistRepositoryError> {
+        self.repository.get_watchlist(uid, owner).await
+    }
+
+    pub async fn update_watchlist(
+        &self,
+        watchlist: Watchlist,
+    ) -> Result<(), WatchlistRepositoryError> {
+        self.repository.update_watchlist(&watchlist).await
+    }
+
+    pub async fn delete_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+    ) -> Result<(), WatchlistRepositoryError> {
+        self.repository.delete_watchlist(uid, owner).await
+    }
+
+    /// Records the check of the watchlist once its new speeches are read, see
+    /// `WatchlistRepository::check_watchlist`.
+    pub async fn check_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+        reported: Option<&StoragePosition>,
+    ) -> Result<DateTime<Utc>, WatchlistRepositoryError> {
+        self.repository.check_watchlist(uid, owner, reported).await
+    }
+}
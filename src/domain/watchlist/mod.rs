@@ -0,0 +1,7 @@
+mod manager;
+mod repository;
+mod watchlist;
+
+pub use manager::WatchlistManager;
+pub use repository::{WatchlistRepository, WatchlistRepositoryError};
+pub use watchlist::Watchlist;
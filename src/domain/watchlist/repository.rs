@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::watchlist::Watchlist;
+use crate::domain::speech::speech_repository::StoragePosition;
+
+#[derive(Debug, PartialEq)]
+pub enum WatchlistRepositoryError {
+    WatchlistNotFound,
+    /// The owner already has a watchlist with this name.
+    WatchlistAlreadyExists,
+    InternalError(String),
+}
+
+#[async_trait::async_trait]
+pub trait WatchlistRepository: WatchlistClone + Send + Sync {
+    /// Returns a copy of the repository reaching only the watchlists of the organization,
+    /// `None` being the default organization.
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn WatchlistRepository>;
+    /// Stores the watchlist, which reports the speeches stored from now on.
+    async fn create_watchlist(&self, watchlist: &Watchlist)
+        -> Result<(), WatchlistRepositoryError>;
+    /// Returns the watchlists of the owner, ordered by name.
+    async fn get_watchlists(&self, owner: &str)
+        -> Result<Vec<Watchlist>, WatchlistRepositoryError>;
+    async fn get_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+    ) -> Result<Watchlist, WatchlistRepositoryError>;
+    /// Replaces the name and the criteria of the watchlist, its last check is kept.
+    async fn update_watchlist(&self, watchlist: &Watchlist)
+        -> Result<(), WatchlistRepositoryError>;
+    async fn delete_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+    ) -> Result<(), WatchlistRepositoryError>;
+    /// Records that the owner checked the watchlist now, the speeches up to the position
+    /// being reported. The position only moves forward, whatever the order concurrent
+    /// checks end in. Returns the time of the check.
+    async fn check_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+        reported: Option<&StoragePosition>,
+    ) -> Result<DateTime<Utc>, WatchlistRepositoryError>;
+}
+
+pub trait WatchlistClone {
+    fn clone_box(&self) -> Box<dyn WatchlistRepository>;
+}
+
+impl<T> WatchlistClone for T
+where
+    T: 'static + WatchlistRepository + Clone,
+{
+    fn clone_box(&self) -> Box<dyn WatchlistRepository> {
+        Box::new(self.clone())
+    }
+}
+
+// We can now implement Clone manually by forwarding to clone_box.
+impl Clone for Box<dyn WatchlistRepository> {
+    fn clone(&self) -> Box<dyn WatchlistRepository> {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::speech::speech_repository::{SpeechFilter, StoragePosition};
+
+/// Query saved by a user to follow the speeches matching it, such as the speeches of a
+/// few persons mentioning "budget". Every criterion left empty matches any speech.
+#[derive(Debug, Clone)]
+pub struct Watchlist {
+    uid: Uuid,
+    owner: String,
+    name: String,
+    speakers: Vec<Uuid>,
+    keywords: Vec<String>,
+    media: Vec<String>,
+    /// Last time the owner fetched the new speeches of the watchlist.
+    last_checked_at: DateTime<Utc>,
+    /// Position of the last speech reported, `None` until the watchlist is stored.
+    position: Option<StoragePosition>,
+}
+
+impl Watchlist {
+    pub fn new(
+        uid: Uuid,
+        owner: &str,
+        name: &str,
+        speakers: Vec<Uuid>,
+        keywords: Vec<String>,
+        media: Vec<String>,
+        last_checked_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            uid,
+            owner: owner.to_string(),
+            name: name.to_string(),
+            speakers,
+            keywords,
+            media,
+            last_checked_at,
+            position: None,
+        }
+    }
+
+    /// Reports the speeches stored after the position from now on.
+    pub fn with_position(mut self, position: StoragePosition) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn uid(&self) -> &Uuid {
+        &self.uid
+    }
+
+    pub fn owner(&self) -> &String {
+        &self.owner
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn speakers(&self) -> &Vec<Uuid> {
+        &self.speakers
+    }
+
+    pub fn keywords(&self) -> &Vec<String> {
+        &self.keywords
+    }
+
+    pub fn media(&self) -> &Vec<String> {
+        &self.media
+    }
+
+    pub fn last_checked_at(&self) -> &DateTime<Utc> {
+        &self.last_checked_at
+    }
+
+    pub fn position(&self) -> Option<&StoragePosition> {
+        self.position.as_ref()
+    }
+
+    /// Filter of the speeches matching the watchlist stored after the last one reported.
+    pub fn new_speeches_filter(&self) -> SpeechFilter {
+        SpeechFilter {
+            speakers: self.speakers.clone(),
+            keywords: self.keywords.clone(),
+            media: self.media.clone(),
+            stored_after: self.position,
+            ..Default::default()
+        }
+    }
+}
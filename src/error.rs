@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+use crate::{application::api::router::APIError, infrastructure::error::InfrastructureError};
+
+/// Failure stopping the program, its message telling what to fix.
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("Invalid configuration: {0}")]
+    Configuration(String),
+    #[error("Cannot start the runtime: {0}")]
+    Runtime(std::io::Error),
+    #[error("Cannot restore the database: {0}")]
+    Restore(String),
+    #[error("Cannot seed the database: {0}")]
+    Seed(String),
+    /// A one-off command failed, e.g. a speech of an import is invalid.
+    #[error("The command failed: {0}")]
+    Command(String),
+    #[error(transparent)]
+    Infrastructure(#[from] InfrastructureError),
+    #[error(transparent)]
+    Api(#[from] APIError),
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::domain::annotation::{
+    Annotator, AnnotatorError, SentenceAnnotation, Sentiment, SentimentLabel,
+};
+
+#[derive(Deserialize)]
+struct AnnotateResponse {
+    /// Annotation of each text, in order.
+    results: Vec<AnnotateResult>,
+}
+
+#[derive(Deserialize)]
+struct AnnotateResult {
+    language: Option<String>,
+    sentiment: Option<String>,
+    score: Option<f64>,
+}
+
+/// Annotator backed by an external service, e.g. a sentiment model, answering
+/// `POST {url}/annotate` with `{"texts": [...]}` by
+/// `{"results": [{"language": "fra", "sentiment": "positive", "score": 0.8}]}`. The
+/// sentiments it may return are `positive`, `neutral` and `negative` with a score from -1
+/// to 1, the other sentiments are ignored.
+#[derive(Debug, Clone)]
+pub struct HttpAnnotator {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl HttpAnnotator {
+    pub fn new(url: &str, api_key: Option<&str>, timeout: u64) -> Result<Self, AnnotatorError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout))
+            .build()
+            .map_err(|e| AnnotatorError::ProviderError(e.to_string()))?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key: api_key.map(|k| k.to_string()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Annotator for HttpAnnotator {
+    async fn annotate(&self, texts: &[String]) -> Result<Vec<SentenceAnnotation>, AnnotatorError> {
+        let mut request = self
+            .client
+            .post(format!("{}/annotate", self.url))
+            .json(&json!({ "texts": texts }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response: AnnotateResponse = request
+            .send()
+            .await
+            .map_err(|e| AnnotatorError::ProviderError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| AnnotatorError::ProviderError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| AnnotatorError::ProviderError(e.to_string()))?;
+        if response.results.len() != texts.len() {
+            return Err(AnnotatorError::ProviderError(format!(
+                "{} results received for {} texts",
+                response.results.len(),
+                texts.len()
+            )));
+        }
+        Ok(response
+            .results
+            .into_iter()
+            .map(|result| SentenceAnnotation {
+                language: result.language,
+                sentiment: result
+                    .sentiment
+                    .and_then(|label| SentimentLabel::try_from(label.as_str()).ok())
+                    .map(|label| Sentiment {
+                        label,
+                        score: result.score.unwrap_or_default().clamp(-1.0, 1.0),
+                    }),
+            })
+            .collect())
+    }
+}
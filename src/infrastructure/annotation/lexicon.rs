@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+
+use crate::domain::annotation::{
+    Annotator, AnnotatorError, SentenceAnnotation, Sentiment, SentimentLabel,
+};
+
+/// Words carrying a positive tone in French and English transcripts.
+const POSITIVE_WORDS: &[&str] = &[
+    "bien",
+    "bon",
+    "bonne",
+    "bonnes",
+    "bons",
+    "excellent",
+    "excellente",
+    "réussite",
+    "succès",
+    "progrès",
+    "espoir",
+    "confiance",
+    "fier",
+    "fière",
+    "heureux",
+    "heureuse",
+    "merci",
+    "formidable",
+    "meilleur",
+    "meilleure",
+    "solidarité",
+    "victoire",
+    "améliorer",
+    "good",
+    "great",
+    "success",
+    "progress",
+    "hope",
+    "confidence",
+    "proud",
+    "happy",
+    "thanks",
+    "thank",
+    "wonderful",
+    "better",
+    "best",
+    "solidarity",
+    "victory",
+    "improve",
+    "strong",
+];
+
+/// Words carrying a negative tone in French and English transcripts.
+const NEGATIVE_WORDS: &[&str] = &[
+    "mal",
+    "mauvais",
+    "mauvaise",
+    "échec",
+    "crise",
+    "peur",
+    "danger",
+    "dangereux",
+    "honte",
+    "colère",
+    "grave",
+    "pire",
+    "menace",
+    "scandale",
+    "injustice",
+    "chômage",
+    "violence",
+    "catastrophe",
+    "inacceptable",
+    "bad",
+    "failure",
+    "crisis",
+    "fear",
+    "dangerous",
+    "shame",
+    "anger",
+    "worse",
+    "worst",
+    "threat",
+    "scandal",
+    "unemployment",
+    "disaster",
+    "unacceptable",
+    "weak",
+];
+
+/// Words reversing the tone of the words following them.
+const NEGATIONS: &[&str] = &[
+    "pas", "jamais", "aucun", "aucune", "sans", "not", "never", "no", "without",
+];
+
+/// Words following a negation whose tone it reverses.
+const NEGATION_SCOPE: usize = 3;
+
+/// Polarity past which a sentence is positive or negative rather than neutral.
+const SENTIMENT_THRESHOLD: f64 = 0.25;
+
+/// Default annotator, detecting the language of the sentences and scoring their tone
+/// with a short French and English lexicon. It misses what a sentiment model would get,
+/// such as irony or a tone carried by a whole phrase.
+#[derive(Debug, Clone)]
+pub struct LexiconAnnotator {
+    positive: HashSet<&'static str>,
+    negative: HashSet<&'static str>,
+    negations: HashSet<&'static str>,
+}
+
+impl LexiconAnnotator {
+    pub fn new() -> Self {
+        Self {
+            positive: POSITIVE_WORDS.iter().copied().collect(),
+            negative: NEGATIVE_WORDS.iter().copied().collect(),
+            negations: NEGATIONS.iter().copied().collect(),
+        }
+    }
+
+    fn annotate_text(&self, text: &str) -> SentenceAnnotation {
+        // Short sentences are too ambiguous, only the reliable guesses are kept.
+        let language = whatlang::detect(text)
+            .filter(|info| info.is_reliable())
+            .map(|info| info.lang().code().to_owned());
+        let (mut positive, mut negative) = (0, 0);
+        // Words left that the last negation applies to.
+        let mut negated = 0;
+        for word in text
+            .split(|c: char| !c.is_alphanumeric() && c != '-')
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+        {
+            let polarity = if self.positive.contains(word.as_str()) {
+                1
+            } else if self.negative.contains(word.as_str()) {
+                -1
+            } else {
+                negated = match self.negations.contains(word.as_str()) {
+                    true => NEGATION_SCOPE,
+                    false => negated.saturating_sub(1),
+                };
+                continue;
+            };
+            match (polarity, negated > 0) {
+                (1, false) | (-1, true) => positive += 1,
+                _ => negative += 1,
+            }
+            negated = 0;
+        }
+        let score = match positive + negative {
+            0 => 0.0,
+            total => (positive - negative) as f64 / total as f64,
+        };
+        let label = if score > SENTIMENT_THRESHOLD {
+            SentimentLabel::Positive
+        } else if score < -SENTIMENT_THRESHOLD {
+            SentimentLabel::Negative
+        } else {
+            SentimentLabel::Neutral
+        };
+        SentenceAnnotation {
+            language,
+            sentiment: Some(Sentiment { label, score }),
+        }
+    }
+}
+
+impl Default for LexiconAnnotator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl Annotator for LexiconAnnotator {
+    async fn annotate(&self, texts: &[String]) -> Result<Vec<SentenceAnnotation>, AnnotatorError> {
+        Ok(texts.iter().map(|text| self.annotate_text(text)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LexiconAnnotator;
+    use crate::domain::annotation::SentimentLabel;
+
+    #[test]
+    fn scores_the_tone_of_the_sentences() {
+        let annotator = LexiconAnnotator::new();
+        let label = |text: &str| annotator.annotate_text(text).sentiment.unwrap().label;
+        assert_eq!(
+            label("Nous sommes fiers de ce succès, merci à tous."),
+            SentimentLabel::Positive
+        );
+        assert_eq!(
+            label("This crisis is a disaster for the workers."),
+            SentimentLabel::Negative
+        );
+        assert_eq!(
+            label("Ce n'est pas une bonne réforme."),
+            SentimentLabel::Negative
+        );
+        assert_eq!(
+            label("The meeting starts at noon."),
+            SentimentLabel::Neutral
+        );
+        assert_eq!(
+            annotator
+                .annotate_text(
+                    "Le gouvernement présentera demain son projet de loi sur les retraites."
+                )
+                .language,
+            Some("fra".to_owned())
+        );
+    }
+}
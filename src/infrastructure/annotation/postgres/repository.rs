@@ -0,0 +1,254 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Error, PgPool, Postgres, QueryBuilder, Row};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::annotation::{
+    Annotation, AnnotationRepository, AnnotationRepositoryError, Sentiment, SentimentLabel,
+};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+/// Annotations inserted by statement.
+const ANNOTATION_INSERT_BATCH_SIZE: usize = 500;
+
+impl From<Error> for AnnotationRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::RowNotFound => Self::SpeechNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Annotation {
+    type Error = AnnotationRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let sentence_uid: Uuid = value.try_get("sentence_uid")?;
+        let sentence_index: i32 = value.try_get("sentence_index")?;
+        let language: Option<String> = value.try_get("language")?;
+        let sentiment: Option<&str> = value.try_get("sentiment")?;
+        let sentiment_score: Option<f64> = value.try_get("sentiment_score")?;
+        let annotated_at: DateTime<Utc> = value.try_get("annotated_at")?;
+        let sentiment = match sentiment {
+            Some(label) => Some(Sentiment {
+                label: SentimentLabel::try_from(label)
+                    .map_err(AnnotationRepositoryError::InternalError)?,
+                score: sentiment_score.unwrap_or_default(),
+            }),
+            None => None,
+        };
+        Ok(Annotation {
+            sentence_uid,
+            sentence_index: sentence_index as u32,
+            language,
+            sentiment,
+            annotated_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresAnnotationRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresAnnotationRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, AnnotationRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| AnnotationRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl AnnotationRepository for PostgresAnnotationRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn AnnotationRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn replace_annotations(
+        &self,
+        speech_uid: &Uuid,
+        annotations: &[Annotation],
+    ) -> Result<(), AnnotationRepositoryError> {
+        let connection = self.connect().await?;
+        let write_timeout = Duration::from_millis(self.timeouts.write);
+        let mut tx = connection.begin().await?;
+        // Only the speeches of the organization are annotated.
+        time::timeout(
+            write_timeout,
+            sqlx::query(
+                "SELECT 1 FROM speech WHERE uid = $1 AND deleted_at IS NULL AND org_uid IS NOT DISTINCT FROM $2;",
+            )
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_one(&mut *tx),
+        )
+        .await
+        .map_err(|e| AnnotationRepositoryError::InternalError(timed_out(e)))??;
+        time::timeout(
+            write_timeout,
+            sqlx::query("DELETE FROM sentence_annotation WHERE speech_uid = $1;")
+                .bind(speech_uid)
+                .execute(&mut *tx),
+        )
+        .await
+        .map_err(|e| AnnotationRepositoryError::InternalError(timed_out(e)))??;
+        for annotations in annotations.chunks(ANNOTATION_INSERT_BATCH_SIZE) {
+            let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO sentence_annotation (sentence_uid, speech_uid, sentence_index, language, sentiment, sentiment_score, annotated_at) ",
+            );
+            builder.push_values(annotations, |mut row, annotation| {
+                row.push_bind(annotation.sentence_uid)
+                    .push_bind(speech_uid)
+                    .push_bind(annotation.sentence_index as i32)
+                    .push_bind(&annotation.language)
+                    .push_bind(annotation.sentiment.map(|s| s.label.to_string()))
+                    .push_bind(annotation.sentiment.map(|s| s.score))
+                    .push_bind(annotation.annotated_at);
+            });
+            time::timeout(write_timeout, builder.build().execute(&mut *tx))
+                .await
+                .map_err(|e| AnnotationRepositoryError::InternalError(timed_out(e)))??;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_unannotated_speeches(
+        &self,
+        after: Option<&Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<Uuid>, AnnotationRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT s.uid FROM speech s \
+                WHERE s.org_uid IS NOT DISTINCT FROM $1 AND s.deleted_at IS NULL AND ($2::UUID IS NULL OR s.uid > $2) \
+                AND EXISTS (SELECT 1 FROM sentence se LEFT JOIN sentence_annotation a ON a.sentence_uid = se.uid WHERE se.speech_uid = s.uid AND a.sentence_uid IS NULL) \
+                ORDER BY s.uid LIMIT $3;",
+            )
+            .bind(self.organization)
+            .bind(after)
+            .bind(quantity as i64)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| AnnotationRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter()
+            .map(|row| Ok(row.try_get("uid")?))
+            .collect()
+    }
+
+    async fn get_annotations(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Annotation>, AnnotationRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT a.sentence_uid, a.sentence_index, a.language, a.sentiment, a.sentiment_score, a.annotated_at \
+                FROM sentence_annotation a JOIN speech s ON s.uid = a.speech_uid \
+                WHERE a.speech_uid = $1 AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $2 \
+                ORDER BY a.sentence_index;",
+            )
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| AnnotationRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Annotation::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::PostgresAnnotationRepository;
+    use crate::{
+        domain::annotation::{Annotation, AnnotationRepository, SentenceAnnotation},
+        test_support::{test_database, PersonBuilder, SpeechBuilder},
+    };
+
+    #[tokio::test]
+    async fn test_postgres_unannotated_speeches() {
+        let database = test_database().await;
+        let repository = PostgresAnnotationRepository::new(database.url(), database.timeouts());
+        let speaker = database.create_person(PersonBuilder::new()).await;
+        let speech = database
+            .create_speech(
+                SpeechBuilder::new()
+                    .with_sentence(speaker.uid(), "Bonjour.")
+                    .with_sentence(speaker.uid(), "Merci."),
+            )
+            .await;
+        assert_eq!(
+            repository.get_unannotated_speeches(None, 10).await.unwrap(),
+            vec![*speech.uid()]
+        );
+        assert!(repository
+            .get_unannotated_speeches(Some(speech.uid()), 10)
+            .await
+            .unwrap()
+            .is_empty());
+        // A sentence still without annotation keeps the speech to annotate.
+        let first = Annotation::new(
+            *speech.sentences()[0].uid(),
+            0,
+            SentenceAnnotation::default(),
+        );
+        repository
+            .replace_annotations(speech.uid(), &[first.clone()])
+            .await
+            .unwrap();
+        assert_eq!(
+            repository.get_unannotated_speeches(None, 10).await.unwrap(),
+            vec![*speech.uid()]
+        );
+        let second = Annotation::new(
+            *speech.sentences()[1].uid(),
+            1,
+            SentenceAnnotation::default(),
+        );
+        repository
+            .replace_annotations(speech.uid(), &[first, second])
+            .await
+            .unwrap();
+        assert!(repository
+            .get_unannotated_speeches(None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
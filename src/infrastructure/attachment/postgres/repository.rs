@@ -0,0 +1,163 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Error, PgPool, Row};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::attachment::{Attachment, AttachmentRepository, AttachmentRepositoryError};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for AttachmentRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::RowNotFound => Self::AttachmentNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Attachment {
+    type Error = AttachmentRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let uid: Uuid = value.try_get("uid")?;
+        let speech_uid: Uuid = value.try_get("speech_uid")?;
+        let file_name: &str = value.try_get("file_name")?;
+        let content_type: &str = value.try_get("content_type")?;
+        let size: i64 = value.try_get("size")?;
+        let storage_key: &str = value.try_get("storage_key")?;
+        let created_at: DateTime<Utc> = value.try_get("created_at")?;
+        Ok(Attachment::new(
+            uid,
+            speech_uid,
+            file_name,
+            content_type,
+            size as u64,
+            storage_key,
+            created_at,
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresAttachmentRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresAttachmentRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, AttachmentRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| AttachmentRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl AttachmentRepository for PostgresAttachmentRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn AttachmentRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn create_attachment(
+        &self,
+        attachment: &Attachment,
+    ) -> Result<(), AttachmentRepositoryError> {
+        let connection = self.connect().await?;
+        // Attachments are only added to the speeches of their own organization.
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO speech_attachment (uid, speech_uid, file_name, content_type, size, storage_key, created_at) \
+                SELECT $1, $2, $3, $4, $5, $6, $7 \
+                WHERE EXISTS (SELECT 1 FROM speech WHERE uid = $2 AND deleted_at IS NULL AND org_uid IS NOT DISTINCT FROM $8);",
+            )
+            .bind(attachment.uid())
+            .bind(attachment.speech_uid())
+            .bind(attachment.file_name())
+            .bind(attachment.content_type())
+            .bind(attachment.size() as i64)
+            .bind(attachment.storage_key())
+            .bind(attachment.created_at())
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| AttachmentRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(AttachmentRepositoryError::SpeechNotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_attachments(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Attachment>, AttachmentRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT a.uid, a.speech_uid, a.file_name, a.content_type, a.size, a.storage_key, a.created_at \
+                FROM speech_attachment a JOIN speech s ON s.uid = a.speech_uid \
+                WHERE a.speech_uid = $1 AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $2 \
+                ORDER BY a.created_at, a.uid;",
+            )
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| AttachmentRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Attachment::try_from).collect()
+    }
+
+    async fn get_attachment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<Attachment, AttachmentRepositoryError> {
+        let connection = self.connect().await?;
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT a.uid, a.speech_uid, a.file_name, a.content_type, a.size, a.storage_key, a.created_at \
+                FROM speech_attachment a JOIN speech s ON s.uid = a.speech_uid \
+                WHERE a.uid = $1 AND a.speech_uid = $2 AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $3;",
+            )
+            .bind(uid)
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| AttachmentRepositoryError::InternalError(timed_out(e)))??;
+        Attachment::try_from(row)
+    }
+}
@@ -0,0 +1,458 @@
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use futures_util::StreamExt;
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Method, RequestBuilder, Url};
+use sha2::{Digest, Sha256};
+
+use crate::domain::attachment::{AttachmentContent, AttachmentStorage, AttachmentStorageError};
+
+/// Size of the parts a large content is uploaded by, the content is never held in memory
+/// beyond one part. S3 requires at least 5 MiB for every part but the last.
+const PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Payload hash of the requests whose body is not signed.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Attachment storage backed by an S3 compatible object storage (AWS S3, MinIO...). The
+/// objects are addressed path-style (`{url}/{bucket}/{key}`) and the requests signed with
+/// AWS Signature Version 4.
+#[derive(Debug, Clone)]
+pub struct S3AttachmentStorage {
+    client: Client,
+    url: Url,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+impl S3AttachmentStorage {
+    pub fn new(
+        url: &str,
+        bucket: &str,
+        region: &str,
+        access_key: &str,
+        secret_key: &str,
+        timeout: u64,
+    ) -> Result<Self, AttachmentStorageError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout))
+            .build()
+            .map_err(|e| AttachmentStorageError::ProviderError(e.to_string()))?;
+        let url = Url::parse(url.trim_end_matches('/'))
+            .map_err(|e| AttachmentStorageError::ProviderError(e.to_string()))?;
+        if url.host_str().is_none() {
+            return Err(AttachmentStorageError::ProviderError(format!(
+                "The storage URL {} has no host",
+                url
+            )));
+        }
+        Ok(Self {
+            client,
+            url,
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            access_key: access_key.to_string(),
+            secret_key: secret_key.to_string(),
+        })
+    }
+
+    /// Value of the `Host` header, as sent by the client.
+    fn host(&self) -> String {
+        let host = self.url.host_str().unwrap_or_default();
+        match self.url.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        }
+    }
+
+    fn object_path(&self, key: &str) -> String {
+        let base = self.url.path().trim_end_matches('/');
+        format!(
+            "{}/{}/{}",
+            base,
+            uri_encode(&self.bucket, true),
+            uri_encode(key, false)
+        )
+    }
+
+    fn credential_scope(&self, date: &str) -> String {
+        format!("{}/{}/s3/aws4_request", date, self.region)
+    }
+
+    fn signature(&self, date: &str, string_to_sign: &str) -> String {
+        let key = hmac_sha256(format!("AWS4{}", self.secret_key).as_bytes(), date);
+        let key = hmac_sha256(&key, &self.region);
+        let key = hmac_sha256(&key, "s3");
+        let key = hmac_sha256(&key, "aws4_request");
+        hex::encode(hmac_sha256(&key, string_to_sign))
+    }
+
+    /// Builds a request on the object signed in its `Authorization` header, its body
+    /// being left unsigned.
+    fn signed_request(&self, method: Method, key: &str, query: &[(&str, &str)]) -> RequestBuilder {
+        let now = Utc::now();
+        let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let path = self.object_path(key);
+        let query = canonical_query(query);
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method,
+            path,
+            query,
+            self.host(),
+            UNSIGNED_PAYLOAD,
+            timestamp,
+            signed_headers,
+            UNSIGNED_PAYLOAD
+        );
+        let scope = self.credential_scope(&date);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            timestamp,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key,
+            scope,
+            signed_headers,
+            self.signature(&date, &string_to_sign)
+        );
+        let mut url = self.url.clone();
+        url.set_path(&path);
+        url.set_query((!query.is_empty()).then_some(query.as_str()));
+        self.client
+            .request(method, url)
+            .header("x-amz-content-sha256", UNSIGNED_PAYLOAD)
+            .header("x-amz-date", timestamp)
+            .header("Authorization", authorization)
+    }
+
+    async fn send(
+        &self,
+        request: RequestBuilder,
+    ) -> Result<reqwest::Response, AttachmentStorageError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AttachmentStorageError::ProviderError(e.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AttachmentStorageError::ProviderError(format!(
+                "The storage answered {}: {}",
+                status, body
+            )));
+        }
+        Ok(response)
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        content_type: &str,
+        content: Bytes,
+    ) -> Result<(), AttachmentStorageError> {
+        let request = self
+            .signed_request(Method::PUT, key, &[])
+            .header("Content-Type", content_type)
+            .body(content);
+        self.send(request).await?;
+        Ok(())
+    }
+
+    async fn create_multipart_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> Result<String, AttachmentStorageError> {
+        let request = self
+            .signed_request(Method::POST, key, &[("uploads", "")])
+            .header("Content-Type", content_type);
+        let body = self
+            .send(request)
+            .await?
+            .text()
+            .await
+            .map_err(|e| AttachmentStorageError::ProviderError(e.to_string()))?;
+        xml_element(&body, "UploadId").ok_or_else(|| {
+            AttachmentStorageError::ProviderError(format!(
+                "The storage sent no upload id: {}",
+                body
+            ))
+        })
+    }
+
+    /// Uploads a part of a multipart upload, returning its ETag.
+    async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: usize,
+        content: Bytes,
+    ) -> Result<String, AttachmentStorageError> {
+        let part_number = part_number.to_string();
+        let request = self
+            .signed_request(
+                Method::PUT,
+                key,
+                &[("partNumber", &part_number), ("uploadId", upload_id)],
+            )
+            .body(content);
+        let response = self.send(request).await?;
+        response
+            .headers()
+            .get("ETag")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned)
+            .ok_or(AttachmentStorageError::ProviderError(
+                "The storage sent no ETag for an uploaded part".to_owned(),
+            ))
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        etags: &[String],
+    ) -> Result<(), AttachmentStorageError> {
+        let parts = etags
+            .iter()
+            .enumerate()
+            .map(|(i, etag)| {
+                format!(
+                    "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                    i + 1,
+                    etag
+                )
+            })
+            .collect::<String>();
+        let request = self
+            .signed_request(Method::POST, key, &[("uploadId", upload_id)])
+            .header("Content-Type", "application/xml")
+            .body(format!(
+                "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
+                parts
+            ));
+        // The storage may answer 200 and report the failure in the body.
+        let body = self
+            .send(request)
+            .await?
+            .text()
+            .await
+            .map_err(|e| AttachmentStorageError::ProviderError(e.to_string()))?;
+        if body.contains("<Error>") {
+            return Err(AttachmentStorageError::ProviderError(format!(
+                "The storage could not complete the upload: {}",
+                body
+            )));
+        }
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+    ) -> Result<(), AttachmentStorageError> {
+        let request = self.signed_request(Method::DELETE, key, &[("uploadId", upload_id)]);
+        self.send(request).await?;
+        Ok(())
+    }
+
+    /// Uploads the parts following the first one, already read.
+    async fn upload_parts(
+        &self,
+        key: &str,
+        upload_id: &str,
+        first_part: Bytes,
+        mut content: AttachmentContent<'_>,
+    ) -> Result<u64, AttachmentStorageError> {
+        let mut size = first_part.len() as u64;
+        let mut etags = vec![self.upload_part(key, upload_id, 1, first_part).await?];
+        let mut buffer = BytesMut::with_capacity(PART_SIZE);
+        loop {
+            let chunk = content.next().await.transpose()?;
+            let end = chunk.is_none();
+            if let Some(chunk) = chunk {
+                size += chunk.len() as u64;
+                buffer.extend_from_slice(&chunk);
+            }
+            if buffer.len() >= PART_SIZE || (end && !buffer.is_empty()) {
+                let part = buffer.split().freeze();
+                let etag = self
+                    .upload_part(key, upload_id, etags.len() + 1, part)
+                    .await?;
+                etags.push(etag);
+            }
+            if end {
+                break;
+            }
+        }
+        self.complete_multipart_upload(key, upload_id, &etags)
+            .await?;
+        Ok(size)
+    }
+}
+
+#[async_trait::async_trait]
+impl AttachmentStorage for S3AttachmentStorage {
+    async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        mut content: AttachmentContent<'_>,
+    ) -> Result<u64, AttachmentStorageError> {
+        // A content smaller than a part is stored by a single request.
+        let mut buffer = BytesMut::with_capacity(PART_SIZE);
+        while buffer.len() < PART_SIZE {
+            match content.next().await.transpose()? {
+                Some(chunk) => buffer.extend_from_slice(&chunk),
+                None => {
+                    let size = buffer.len() as u64;
+                    self.put_object(key, content_type, buffer.freeze()).await?;
+                    return Ok(size);
+                }
+            }
+        }
+        let upload_id = self.create_multipart_upload(key, content_type).await?;
+        let result = self
+            .upload_parts(key, &upload_id, buffer.freeze(), content)
+            .await;
+        if result.is_err() {
+            if let Err(e) = self.abort_multipart_upload(key, &upload_id).await {
+                println!(
+                    "An internal error occured while aborting the upload of {}: {:?}",
+                    key, e
+                );
+            }
+        }
+        result
+    }
+
+    fn download_url(
+        &self,
+        key: &str,
+        file_name: &str,
+        expiry: Duration,
+    ) -> Result<String, AttachmentStorageError> {
+        let now = Utc::now();
+        let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let path = self.object_path(key);
+        let scope = self.credential_scope(&date);
+        let credential = format!("{}/{}", self.access_key, scope);
+        let expires = expiry.as_secs().to_string();
+        let disposition = format!(
+            "attachment; filename=\"{}\"",
+            file_name.replace(['"', '\\'], "_")
+        );
+        let query = canonical_query(&[
+            ("X-Amz-Algorithm", "AWS4-HMAC-SHA256"),
+            ("X-Amz-Credential", &credential),
+            ("X-Amz-Date", &timestamp),
+            ("X-Amz-Expires", &expires),
+            ("X-Amz-SignedHeaders", "host"),
+            ("response-content-disposition", &disposition),
+        ]);
+        let canonical_request = format!(
+            "GET\n{}\n{}\nhost:{}\n\nhost\n{}",
+            path,
+            query,
+            self.host(),
+            UNSIGNED_PAYLOAD
+        );
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            timestamp,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let mut url = self.url.clone();
+        url.set_path(&path);
+        url.set_query(Some(&format!(
+            "{}&X-Amz-Signature={}",
+            query,
+            self.signature(&date, &string_to_sign)
+        )));
+        Ok(url.to_string())
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AttachmentStorageError> {
+        self.send(self.signed_request(Method::DELETE, key, &[]))
+            .await?;
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key size");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encodes every byte but the unreserved characters, as required by the
+/// signature. The slashes are kept unless `encode_slash`.
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Encodes the query parameters sorted by name, as they are signed.
+fn canonical_query(params: &[(&str, &str)]) -> String {
+    let mut params = params
+        .iter()
+        .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true)))
+        .collect::<Vec<(String, String)>>();
+    params.sort();
+    params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+/// Reads the text of the first element named `name` of an XML document.
+fn xml_element(document: &str, name: &str) -> Option<String> {
+    let start = document.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = start + document[start..].find(&format!("</{}>", name))?;
+    Some(document[start..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canonical_query, uri_encode, xml_element};
+
+    #[test]
+    fn query_is_encoded_and_sorted_as_signed() {
+        assert_eq!(
+            uri_encode("speeches/a b~.mp3", false),
+            "speeches/a%20b~.mp3"
+        );
+        assert_eq!(uri_encode("a/b", true), "a%2Fb");
+        assert_eq!(
+            canonical_query(&[("uploadId", "x/y"), ("partNumber", "2"), ("uploads", "")]),
+            "partNumber=2&uploadId=x%2Fy&uploads="
+        );
+        assert_eq!(
+            xml_element("<Result><UploadId>abc</UploadId></Result>", "UploadId"),
+            Some("abc".to_string())
+        );
+    }
+}
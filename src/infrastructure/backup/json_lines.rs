@@ -0,0 +1,205 @@
+use std::{path::Path, time::Duration};
+
+use futures_util::TryStreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::PgConnection;
+use tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter},
+    time,
+};
+
+use super::{connect, ensure_empty, ordered_tables, quote_identifier};
+use crate::{
+    domain::backup::{BackupError, BackupProvider},
+    infrastructure::{error_metrics::timed_out, timeouts::DatabaseTimeouts},
+};
+
+/// Rows inserted by a single statement during a restore.
+const RESTORE_BATCH_SIZE: usize = 1000;
+
+/// Columns filled by a sequence, set past the restored values once the rows are loaded.
+const SEQUENCE_COLUMNS_QUERY: &str = "SELECT table_name::TEXT, column_name::TEXT FROM information_schema.columns WHERE table_schema = 'public' AND (column_default LIKE 'nextval(%' OR is_identity = 'YES');";
+
+/// Tables with a foreign key referencing the table itself, e.g. the parent of a tag.
+const SELF_REFERENCING_QUERY: &str = "SELECT DISTINCT c.relname::TEXT FROM pg_constraint co JOIN pg_class c ON c.oid = co.conrelid JOIN pg_namespace n ON n.oid = c.relnamespace WHERE co.contype = 'f' AND co.conrelid = co.confrelid AND n.nspname = 'public';";
+
+/// Line of a backup, holding a row of a table.
+#[derive(Deserialize)]
+struct BackupLine {
+    table: String,
+    row: Value,
+}
+
+/// Backup of the Postgres database written as JSON lines, one row by line, e.g.
+/// `{"table":"person","row":{"uid":"...","name":"..."}}`. The tables are written after
+/// the tables they reference, so the restore loads them in the order of the file.
+#[derive(Debug, Clone)]
+pub struct JsonLinesBackupProvider {
+    url: String,
+    timeouts: DatabaseTimeouts,
+}
+
+impl JsonLinesBackupProvider {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+        }
+    }
+
+    /// Inserts the rows of a table by a single statement. A table referencing itself is
+    /// loaded whole by one statement: its foreign keys are only checked at the end of it.
+    async fn insert_rows(
+        &self,
+        connection: &mut PgConnection,
+        table: &str,
+        rows: Vec<Value>,
+    ) -> Result<u64, BackupError> {
+        let table = quote_identifier(table);
+        let inserted = time::timeout(
+            Duration::from_millis(self.timeouts.migration),
+            sqlx::query(&format!(
+                "INSERT INTO {} SELECT * FROM json_populate_recordset(NULL::{}, $1::JSON);",
+                table, table
+            ))
+            .bind(Value::Array(rows).to_string())
+            .execute(&mut *connection),
+        )
+        .await
+        .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+        Ok(inserted.rows_affected())
+    }
+
+    /// Sets the sequences after the largest value restored, the next rows not reusing it.
+    async fn reset_sequences(&self, connection: &mut PgConnection) -> Result<(), BackupError> {
+        let columns: Vec<(String, String)> = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query_as(SEQUENCE_COLUMNS_QUERY).fetch_all(&mut *connection),
+        )
+        .await
+        .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+        for (table, column) in columns {
+            time::timeout(
+                Duration::from_millis(self.timeouts.migration),
+                sqlx::query(&format!(
+                    "SELECT setval(pg_get_serial_sequence($1, $2), COALESCE((SELECT MAX({}) FROM {}), 0) + 1, false);",
+                    quote_identifier(&column),
+                    quote_identifier(&table)
+                ))
+                .bind(quote_identifier(&table))
+                .bind(&column)
+                .execute(&mut *connection),
+            )
+            .await
+            .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl BackupProvider for JsonLinesBackupProvider {
+    fn extension(&self) -> &'static str {
+        "jsonl"
+    }
+
+    async fn export(&self, path: &Path) -> Result<u64, BackupError> {
+        let connection = connect(&self.url, &self.timeouts).await?;
+        // Every table is read from the same snapshot, the writes going on meanwhile.
+        let mut tx = connection.begin().await?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY;")
+            .execute(&mut *tx)
+            .await?;
+        let tables = ordered_tables(&mut tx, &self.timeouts).await?;
+        let mut file = BufWriter::new(File::create(path).await?);
+        let mut exported = 0;
+        for table in &tables {
+            let query = format!(
+                "SELECT row_to_json(t)::TEXT FROM {} t;",
+                quote_identifier(table)
+            );
+            let table_name = Value::from(table.as_str()).to_string();
+            let written = time::timeout(Duration::from_millis(self.timeouts.migration), async {
+                let mut rows = sqlx::query_scalar::<_, String>(&query).fetch(&mut *tx);
+                let mut written = 0;
+                while let Some(row) = rows.try_next().await? {
+                    file.write_all(
+                        format!("{{\"table\":{},\"row\":{}}}\n", table_name, row).as_bytes(),
+                    )
+                    .await?;
+                    written += 1;
+                }
+                Ok::<u64, BackupError>(written)
+            })
+            .await
+            .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+            exported += written;
+        }
+        file.flush().await?;
+        tx.rollback().await?;
+        Ok(exported)
+    }
+
+    async fn restore(&self, path: &Path) -> Result<u64, BackupError> {
+        let connection = connect(&self.url, &self.timeouts).await?;
+        // Nothing is kept of a restore failing midway.
+        let mut tx = connection.begin().await?;
+        let tables = ordered_tables(&mut tx, &self.timeouts).await?;
+        ensure_empty(&mut tx, &tables, &self.timeouts).await?;
+        let self_referencing: Vec<String> = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query_scalar(SELF_REFERENCING_QUERY).fetch_all(&mut *tx),
+        )
+        .await
+        .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+        let mut lines = BufReader::new(File::open(path).await?).lines();
+        let mut restored = 0;
+        // Rows of the table read last, not inserted yet.
+        let mut pending: Option<(String, Vec<Value>)> = None;
+        let mut number = 0;
+        while let Some(line) = lines.next_line().await? {
+            number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line: BackupLine = serde_json::from_str(&line).map_err(|e| {
+                BackupError::InvalidBackup(format!("Line {} is not a row: {}", number, e))
+            })?;
+            if !tables.contains(&line.table) {
+                return Err(BackupError::InvalidBackup(format!(
+                    "Line {} holds a row of the unknown table {}",
+                    number, line.table
+                )));
+            }
+            match &mut pending {
+                Some((table, rows)) if *table == line.table => rows.push(line.row),
+                _ => {
+                    if let Some((table, rows)) = pending.take() {
+                        restored += self.insert_rows(&mut tx, &table, rows).await?;
+                    }
+                    pending = Some((line.table, vec![line.row]));
+                }
+            }
+            // The batches only split the tables not referencing themselves.
+            let full = match &pending {
+                Some((table, rows)) => {
+                    rows.len() >= RESTORE_BATCH_SIZE && !self_referencing.contains(table)
+                }
+                None => false,
+            };
+            if full {
+                if let Some((table, rows)) = pending.take() {
+                    restored += self.insert_rows(&mut tx, &table, rows).await?;
+                }
+            }
+        }
+        if let Some((table, rows)) = pending.take() {
+            restored += self.insert_rows(&mut tx, &table, rows).await?;
+        }
+        self.reset_sequences(&mut tx).await?;
+        tx.commit().await?;
+        Ok(restored)
+    }
+}
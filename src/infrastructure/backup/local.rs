@@ -0,0 +1,28 @@
+use std::path::{Path, PathBuf};
+
+use crate::domain::backup::{BackupError, BackupTarget};
+
+/// Backups kept as files of a local directory, e.g. a mounted volume.
+#[derive(Debug, Clone)]
+pub struct LocalBackupTarget {
+    directory: PathBuf,
+}
+
+impl LocalBackupTarget {
+    pub fn new(directory: &str) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BackupTarget for LocalBackupTarget {
+    async fn store(&self, name: &str, path: &Path) -> Result<String, BackupError> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+        let destination = self.directory.join(name);
+        // Copied rather than renamed, the temporary directory may be on another device.
+        tokio::fs::copy(path, &destination).await?;
+        Ok(destination.display().to_string())
+    }
+}
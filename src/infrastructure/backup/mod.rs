@@ -0,0 +1,159 @@
+pub mod json_lines;
+pub mod local;
+pub mod pg_dump;
+pub mod s3;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use sqlx::{Error, PgConnection, PgPool};
+use tokio::time;
+
+use crate::{
+    domain::backup::BackupError,
+    infrastructure::{
+        error_metrics::{record_sqlx_error, timed_out},
+        timeouts::DatabaseTimeouts,
+    },
+};
+
+impl From<Error> for BackupError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        Self::InternalError(value.to_string())
+    }
+}
+
+impl From<std::io::Error> for BackupError {
+    fn from(value: std::io::Error) -> Self {
+        Self::InternalError(value.to_string())
+    }
+}
+
+/// Tables of the application, the history of the migrations being left out: it is
+/// written by the migrations run before a restore.
+const TABLES_QUERY: &str = "SELECT c.relname::TEXT FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace WHERE n.nspname = 'public' AND c.relkind = 'r' AND c.relname <> '_sqlx_migrations' ORDER BY c.relname;";
+
+/// Foreign keys between two tables of the application, as (referencing, referenced).
+const REFERENCES_QUERY: &str = "SELECT cl.relname::TEXT, pa.relname::TEXT FROM pg_constraint co JOIN pg_class cl ON cl.oid = co.conrelid JOIN pg_class pa ON pa.oid = co.confrelid JOIN pg_namespace n ON n.oid = cl.relnamespace WHERE co.contype = 'f' AND n.nspname = 'public';";
+
+async fn connect(url: &str, timeouts: &DatabaseTimeouts) -> Result<PgPool, BackupError> {
+    Ok(
+        time::timeout(Duration::from_millis(timeouts.read), PgPool::connect(url))
+            .await
+            .map_err(|e| BackupError::InternalError(timed_out(e)))??,
+    )
+}
+
+/// Quotes the name of a table read from the catalog, to use it in a statement.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Orders the tables so every table comes after the tables it references, the tables
+/// referencing each other coming last in the order of their names.
+fn dependency_order(tables: &[String], references: &[(String, String)]) -> Vec<String> {
+    let mut referenced: BTreeMap<&str, BTreeSet<&str>> = tables
+        .iter()
+        .map(|table| (table.as_str(), BTreeSet::new()))
+        .collect();
+    for (table, reference) in references {
+        // A table referencing itself is loaded by a single statement, see the restores.
+        if table != reference && referenced.contains_key(reference.as_str()) {
+            if let Some(dependencies) = referenced.get_mut(table.as_str()) {
+                dependencies.insert(reference);
+            }
+        }
+    }
+    let mut ordered: Vec<String> = Vec::with_capacity(tables.len());
+    while !referenced.is_empty() {
+        let ready = referenced
+            .iter()
+            .filter(|(_, dependencies)| dependencies.is_empty())
+            .map(|(table, _)| *table)
+            .collect::<Vec<&str>>();
+        if ready.is_empty() {
+            ordered.extend(referenced.keys().map(|table| table.to_string()));
+            break;
+        }
+        for table in ready {
+            referenced.remove(table);
+            for dependencies in referenced.values_mut() {
+                dependencies.remove(table);
+            }
+            ordered.push(table.to_string());
+        }
+    }
+    ordered
+}
+
+/// Tables of the application, every table after the tables it references.
+async fn ordered_tables(
+    connection: &mut PgConnection,
+    timeouts: &DatabaseTimeouts,
+) -> Result<Vec<String>, BackupError> {
+    let tables: Vec<String> = time::timeout(
+        Duration::from_millis(timeouts.read),
+        sqlx::query_scalar(TABLES_QUERY).fetch_all(&mut *connection),
+    )
+    .await
+    .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+    let references: Vec<(String, String)> = time::timeout(
+        Duration::from_millis(timeouts.read),
+        sqlx::query_as(REFERENCES_QUERY).fetch_all(&mut *connection),
+    )
+    .await
+    .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+    Ok(dependency_order(&tables, &references))
+}
+
+/// Refuses a database where any of the tables holds a row, a restore never merging a
+/// backup with other data.
+async fn ensure_empty(
+    connection: &mut PgConnection,
+    tables: &[String],
+    timeouts: &DatabaseTimeouts,
+) -> Result<(), BackupError> {
+    for table in tables {
+        let filled: bool = time::timeout(
+            Duration::from_millis(timeouts.read),
+            sqlx::query_scalar(&format!(
+                "SELECT EXISTS (SELECT 1 FROM {});",
+                quote_identifier(table)
+            ))
+            .fetch_one(&mut *connection),
+        )
+        .await
+        .map_err(|e| BackupError::InternalError(timed_out(e)))??;
+        if filled {
+            return Err(BackupError::DatabaseNotEmpty);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dependency_order;
+
+    #[test]
+    fn referenced_tables_come_first() {
+        let tables = ["sentence", "person", "speech", "tag", "speech_tag"]
+            .map(str::to_owned)
+            .to_vec();
+        let references = [
+            ("sentence", "speech"),
+            ("sentence", "person"),
+            ("speech_tag", "tag"),
+            ("speech_tag", "speech"),
+            ("tag", "tag"),
+        ]
+        .map(|(table, reference)| (table.to_owned(), reference.to_owned()));
+        assert_eq!(
+            dependency_order(&tables, &references),
+            ["person", "speech", "tag", "sentence", "speech_tag"]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::{path::Path, process::Stdio, time::Duration};
+
+use tokio::{process::Command, time};
+
+use super::{connect, ensure_empty, ordered_tables};
+use crate::{
+    domain::backup::{BackupError, BackupProvider},
+    infrastructure::{error_metrics::timed_out, timeouts::DatabaseTimeouts},
+};
+
+/// Backup of the Postgres database written by `pg_dump` in its custom format, the data
+/// only: the schema is created by the migrations run before a restore. The `pg_dump` and
+/// `pg_restore` programs of the server version must be installed.
+#[derive(Debug, Clone)]
+pub struct PgDumpBackupProvider {
+    url: String,
+    timeouts: DatabaseTimeouts,
+}
+
+impl PgDumpBackupProvider {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+        }
+    }
+
+    /// Runs the program under the migration timeout, failing with its error output.
+    async fn run(&self, command: &mut Command) -> Result<(), BackupError> {
+        let program = command.as_std().get_program().to_string_lossy().to_string();
+        let output = time::timeout(
+            Duration::from_millis(self.timeouts.migration),
+            command.stdin(Stdio::null()).kill_on_drop(true).output(),
+        )
+        .await
+        .map_err(|e| BackupError::InternalError(timed_out(e)))?
+        .map_err(|e| BackupError::InternalError(format!("Cannot run {}: {}", program, e)))?;
+        if !output.status.success() {
+            return Err(BackupError::InternalError(format!(
+                "{} failed with {}: {}",
+                program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl BackupProvider for PgDumpBackupProvider {
+    fn extension(&self) -> &'static str {
+        "dump"
+    }
+
+    async fn export(&self, path: &Path) -> Result<u64, BackupError> {
+        // pg_dump reads every table from the same snapshot.
+        self.run(
+            Command::new("pg_dump")
+                .arg("--format=custom")
+                .arg("--data-only")
+                .arg("--exclude-table=_sqlx_migrations")
+                .arg("--file")
+                .arg(path)
+                .arg("--dbname")
+                .arg(&self.url),
+        )
+        .await?;
+        // The rows are not counted by pg_dump.
+        Ok(0)
+    }
+
+    async fn restore(&self, path: &Path) -> Result<u64, BackupError> {
+        let connection = connect(&self.url, &self.timeouts).await?;
+        let mut database = connection.acquire().await?;
+        let tables = ordered_tables(&mut database, &self.timeouts).await?;
+        ensure_empty(&mut database, &tables, &self.timeouts).await?;
+        drop(database);
+        connection.close().await;
+        // pg_restore orders the tables after their foreign keys and sets the sequences.
+        self.run(
+            Command::new("pg_restore")
+                .arg("--data-only")
+                .arg("--single-transaction")
+                .arg("--exit-on-error")
+                .arg("--dbname")
+                .arg(&self.url)
+                .arg(path),
+        )
+        .await?;
+        Ok(0)
+    }
+}
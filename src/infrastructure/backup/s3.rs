@@ -0,0 +1,58 @@
+use std::path::Path;
+
+use bytes::BytesMut;
+use futures_util::{stream, StreamExt};
+use tokio::{fs::File, io::AsyncReadExt};
+
+use crate::{
+    domain::{
+        attachment::{AttachmentStorage, AttachmentStorageError},
+        backup::{BackupError, BackupTarget},
+    },
+    infrastructure::attachment::s3::S3AttachmentStorage,
+};
+
+/// Size of the chunks a backup is read by while it is uploaded.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Backups kept in an S3 compatible object storage, under a prefix of the bucket.
+#[derive(Debug, Clone)]
+pub struct S3BackupTarget {
+    storage: S3AttachmentStorage,
+    bucket: String,
+    /// Prefix of the keys of the backups, e.g. `backups/`.
+    prefix: String,
+}
+
+impl S3BackupTarget {
+    pub fn new(storage: S3AttachmentStorage, bucket: &str, prefix: &str) -> Self {
+        Self {
+            storage,
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BackupTarget for S3BackupTarget {
+    async fn store(&self, name: &str, path: &Path) -> Result<String, BackupError> {
+        let file = File::open(path).await?;
+        // The backup is streamed, never held in memory as a whole.
+        let content = stream::try_unfold(file, |mut file| async move {
+            let mut buffer = BytesMut::with_capacity(CHUNK_SIZE);
+            let read = file
+                .read_buf(&mut buffer)
+                .await
+                .map_err(|e| AttachmentStorageError::InvalidContent(e.to_string()))?;
+            Ok((read > 0).then(|| (buffer.freeze(), file)))
+        })
+        .boxed();
+        let key = format!("{}{}", self.prefix, name);
+        self.storage
+            .put(&key, "application/octet-stream", content)
+            .await
+            .map_err(|e| BackupError::InternalError(format!("{:?}", e)))?;
+        Ok(format!("s3://{}/{}", self.bucket, key))
+    }
+}
@@ -0,0 +1,131 @@
+use std::{sync::Arc, time::Duration};
+
+use sqlx::{Error, PgPool};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::collection::{CollectionVersionError, CollectionVersionRepository};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for CollectionVersionError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        Self::InternalError(value.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresCollectionVersionRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    /// Every list request reads the version, every change bumps it.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresCollectionVersionRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, CollectionVersionError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| CollectionVersionError::InternalError(timed_out(e)))??
+        .clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl CollectionVersionRepository for PostgresCollectionVersionRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn CollectionVersionRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn current(&self) -> Result<u64, CollectionVersionError> {
+        let connection = self.connect().await?;
+        let version: Option<i64> = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query_scalar(
+                "SELECT version FROM collection_version WHERE org_uid IS NOT DISTINCT FROM $1;",
+            )
+            .bind(self.organization)
+            .fetch_optional(&connection),
+        )
+        .await
+        .map_err(|e| CollectionVersionError::InternalError(timed_out(e)))??;
+        Ok(version.unwrap_or(0) as u64)
+    }
+
+    async fn bump(&self) -> Result<u64, CollectionVersionError> {
+        let connection = self.connect().await?;
+        let version: i64 = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query_scalar(
+                "INSERT INTO collection_version (org_uid, version) VALUES ($1, 1) \
+                ON CONFLICT (COALESCE(org_uid, '00000000-0000-0000-0000-000000000000')) \
+                DO UPDATE SET version = collection_version.version + 1 RETURNING version;",
+            )
+            .bind(self.organization)
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| CollectionVersionError::InternalError(timed_out(e)))??;
+        Ok(version as u64)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::PostgresCollectionVersionRepository;
+    use crate::{
+        domain::{
+            collection::CollectionVersionRepository,
+            organization::{Organization, OrganizationRepository},
+        },
+        infrastructure::organization::postgres::repository::PostgresOrganizationRepository,
+        test_support::test_database,
+    };
+
+    #[tokio::test]
+    async fn test_postgres_collection_versions() {
+        let database = test_database().await;
+        let organization = Organization::new(Uuid::new_v4(), "Newsroom", Utc::now());
+        PostgresOrganizationRepository::new(database.url(), database.timeouts())
+            .create_organization(&organization)
+            .await
+            .unwrap();
+        let repository =
+            PostgresCollectionVersionRepository::new(database.url(), database.timeouts());
+        let organization_versions = repository.for_organization(Some(*organization.uid()));
+        assert_eq!(repository.current().await.unwrap(), 0);
+        assert_eq!(organization_versions.bump().await.unwrap(), 1);
+        assert_eq!(organization_versions.bump().await.unwrap(), 2);
+        assert_eq!(organization_versions.current().await.unwrap(), 2);
+        assert_eq!(repository.current().await.unwrap(), 0);
+        // Another instance reads the version bumped by this one.
+        let other = PostgresCollectionVersionRepository::new(database.url(), database.timeouts());
+        assert_eq!(other.bump().await.unwrap(), 1);
+        assert_eq!(repository.current().await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+/// Failure of a service the program relies on, found while it starts.
+#[derive(Debug, Error)]
+pub enum InfrastructureError {
+    #[error("Cannot migrate the database, check DATABASE_URL: {0}")]
+    Migration(String),
+    #[error("Cannot create the MongoDB indexes, check MONGO_URL: {0}")]
+    MongoIndexes(String),
+    /// The client of a service cannot be built from its settings, e.g. an invalid URL.
+    #[error("Cannot create the {service}, check {setting}: {message}")]
+    Client {
+        service: &'static str,
+        setting: &'static str,
+        message: String,
+    },
+}
@@ -0,0 +1,41 @@
+use sqlx::Error;
+use tokio::time::error::Elapsed;
+
+use crate::domain::metrics::{record_repository_error, RepositoryErrorClass};
+
+/// Counts a Postgres error in the repository error metrics. A missing row is an expected
+/// outcome rather than a failure, it is not counted.
+pub fn record_sqlx_error(error: &Error) {
+    let class = match error {
+        Error::RowNotFound => return,
+        Error::Database(e)
+            if e.is_unique_violation()
+                || e.is_check_violation()
+                || e.is_foreign_key_violation() =>
+        {
+            RepositoryErrorClass::ConstraintViolation
+        }
+        Error::PoolTimedOut => RepositoryErrorClass::Timeout,
+        Error::Io(_)
+        | Error::Tls(_)
+        | Error::Protocol(_)
+        | Error::Configuration(_)
+        | Error::PoolClosed
+        | Error::WorkerCrashed => RepositoryErrorClass::Connection,
+        Error::ColumnDecode { .. }
+        | Error::ColumnNotFound(_)
+        | Error::ColumnIndexOutOfBounds { .. }
+        | Error::TypeNotFound { .. }
+        | Error::Decode(_)
+        | Error::Encode(_) => RepositoryErrorClass::Serialization,
+        _ => RepositoryErrorClass::Other,
+    };
+    record_repository_error(class);
+}
+
+/// Counts a call which exceeded the repository timeout, returning the message of the
+/// internal error reported instead.
+pub fn timed_out(error: Elapsed) -> String {
+    record_repository_error(RepositoryErrorClass::Timeout);
+    error.to_string()
+}
@@ -0,0 +1,35 @@
+use sqlx::{Postgres, QueryBuilder};
+
+use crate::domain::filter::{FilterOperator, FilterSpec, FilterValue};
+
+fn sql_operator(operator: FilterOperator) -> &'static str {
+    match operator {
+        FilterOperator::Eq => " = ",
+        // The rows without a value differ from any value.
+        FilterOperator::Ne => " IS DISTINCT FROM ",
+        FilterOperator::Gt => " > ",
+        FilterOperator::Ge => " >= ",
+        FilterOperator::Lt => " < ",
+        FilterOperator::Le => " <= ",
+    }
+}
+
+/// Appends an ` AND` condition for every condition of the filter, `column` giving the SQL
+/// expression of each field. The values are always bound.
+pub fn push_filter_conditions<F: Copy>(
+    query_builder: &mut QueryBuilder<'_, Postgres>,
+    spec: &FilterSpec<F>,
+    column: impl Fn(F) -> &'static str,
+) {
+    for condition in &spec.conditions {
+        query_builder
+            .push(" AND ")
+            .push(column(condition.field))
+            .push(sql_operator(condition.operator));
+        match &condition.value {
+            FilterValue::Text(text) => query_builder.push_bind(text.clone()),
+            FilterValue::Date(date) => query_builder.push_bind(*date),
+            FilterValue::Number(number) => query_builder.push_bind(*number),
+        };
+    }
+}
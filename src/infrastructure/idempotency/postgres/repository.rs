@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use sqlx::{Error, PgPool, Row};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::domain::idempotency::{
+    IdempotencyClaim, IdempotencyRepository, IdempotencyRepositoryError, StoredResponse,
+};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for IdempotencyRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        Self::InternalError(value.to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresIdempotencyRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+    /// User every query is restricted to.
+    user_id: String,
+}
+
+impl PostgresIdempotencyRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            organization: None,
+            user_id: String::new(),
+        }
+    }
+
+    async fn connect(&self) -> Result<PgPool, IdempotencyRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            PgPool::connect(&self.url),
+        )
+        .await
+        .map_err(|e| IdempotencyRepositoryError::InternalError(timed_out(e)))??)
+    }
+}
+
+#[async_trait::async_trait]
+impl IdempotencyRepository for PostgresIdempotencyRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn IdempotencyRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    fn for_user(&self, user_id: &str) -> Box<dyn IdempotencyRepository> {
+        Box::new(Self {
+            user_id: user_id.to_owned(),
+            ..self.clone()
+        })
+    }
+
+    async fn claim_key(
+        &self,
+        key: &str,
+        request: &[u8],
+        ttl: u64,
+        lease: Duration,
+    ) -> Result<IdempotencyClaim, IdempotencyRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "DELETE FROM idempotency_key WHERE created_at < NOW() - make_interval(secs => $1)",
+            )
+            .bind(ttl as f64)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| IdempotencyRepositoryError::InternalError(timed_out(e)))??;
+        // A key whose lease is over is claimed again by the same request, its first request
+        // having stopped before storing the response.
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO idempotency_key (key, org_uid, user_id, request_hash) \
+                VALUES ($1, $2, $3, encode(sha256($4), 'hex')) \
+                ON CONFLICT (key, user_id, COALESCE(org_uid, '00000000-0000-0000-0000-000000000000')) \
+                DO UPDATE SET locked_at = NOW() \
+                WHERE idempotency_key.response_status IS NULL \
+                AND idempotency_key.request_hash = EXCLUDED.request_hash \
+                AND idempotency_key.locked_at < NOW() - make_interval(secs => $5)",
+            )
+            .bind(key)
+            .bind(self.organization)
+            .bind(&self.user_id)
+            .bind(request)
+            .bind(lease.as_secs_f64())
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| IdempotencyRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 1 {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "SELECT request_hash = encode(sha256($4), 'hex') AS same_request, response_status, response_body \
+                FROM idempotency_key WHERE key = $1 AND org_uid IS NOT DISTINCT FROM $2 AND user_id = $3",
+            )
+            .bind(key)
+            .bind(self.organization)
+            .bind(&self.user_id)
+            .bind(request)
+            .fetch_optional(&connection),
+        )
+        .await
+        .map_err(|e| IdempotencyRepositoryError::InternalError(timed_out(e)))??;
+        // The key has been released in between, the client may retry.
+        let row = row.ok_or(IdempotencyRepositoryError::KeyInProgress)?;
+        let same_request: bool = row.try_get("same_request")?;
+        if !same_request {
+            return Err(IdempotencyRepositoryError::KeyReused);
+        }
+        let status: Option<i16> = row.try_get("response_status")?;
+        let body: Option<String> = row.try_get("response_body")?;
+        match (status, body) {
+            (Some(status), Some(body)) => Ok(IdempotencyClaim::Replay(StoredResponse {
+                status: status as u16,
+                body,
+            })),
+            _ => Err(IdempotencyRepositoryError::KeyInProgress),
+        }
+    }
+
+    async fn complete_key(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+    ) -> Result<(), IdempotencyRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "UPDATE idempotency_key SET response_status = $4, response_body = $5 \
+                WHERE key = $1 AND org_uid IS NOT DISTINCT FROM $2 AND user_id = $3",
+            )
+            .bind(key)
+            .bind(self.organization)
+            .bind(&self.user_id)
+            .bind(response.status as i16)
+            .bind(&response.body)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| IdempotencyRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+
+    async fn release_key(&self, key: &str) -> Result<(), IdempotencyRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "DELETE FROM idempotency_key WHERE key = $1 AND org_uid IS NOT DISTINCT FROM $2 \
+                AND user_id = $3 AND response_status IS NULL",
+            )
+            .bind(key)
+            .bind(self.organization)
+            .bind(&self.user_id)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| IdempotencyRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::PostgresIdempotencyRepository;
+    use crate::{
+        domain::idempotency::{
+            IdempotencyClaim, IdempotencyRepository, IdempotencyRepositoryError, StoredResponse,
+        },
+        test_support::test_database,
+    };
+
+    #[tokio::test]
+    async fn test_postgres_idempotency_leases() {
+        let database = test_database().await;
+        let repository = PostgresIdempotencyRepository::new(database.url(), database.timeouts());
+        let alice = repository.for_user("alice");
+        let bob = repository.for_user("bob");
+        let lease = Duration::from_secs(60);
+        assert_eq!(
+            alice.claim_key("key", b"request", 3600, lease).await,
+            Ok(IdempotencyClaim::Claimed)
+        );
+        assert_eq!(
+            alice.claim_key("key", b"request", 3600, lease).await,
+            Err(IdempotencyRepositoryError::KeyInProgress)
+        );
+        assert_eq!(
+            bob.claim_key("key", b"request", 3600, lease).await,
+            Ok(IdempotencyClaim::Claimed)
+        );
+        // The first request stopped without storing its response, its lease is over.
+        assert_eq!(
+            alice
+                .claim_key("key", b"request", 3600, Duration::ZERO)
+                .await,
+            Ok(IdempotencyClaim::Claimed)
+        );
+        assert_eq!(
+            alice
+                .claim_key("key", b"other request", 3600, Duration::ZERO)
+                .await,
+            Err(IdempotencyRepositoryError::KeyReused)
+        );
+        let response = StoredResponse {
+            status: 200,
+            body: "{}".to_owned(),
+        };
+        alice.complete_key("key", &response).await.unwrap();
+        assert_eq!(
+            alice
+                .claim_key("key", b"request", 3600, Duration::ZERO)
+                .await,
+            Ok(IdempotencyClaim::Replay(response))
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{postgres::PgRow, types::Json, Error, PgPool, Row};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::job::{Job, JobKind, JobRepository, JobRepositoryError, JobStatus};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for JobRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::RowNotFound => Self::JobNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Job {
+    type Error = JobRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let kind: &str = value.try_get("kind")?;
+        let status: &str = value.try_get("status")?;
+        let processed: i64 = value.try_get("processed")?;
+        let total: Option<i64> = value.try_get("total")?;
+        let result: Option<Json<Value>> = value.try_get("result")?;
+        let started_at: DateTime<Utc> = value.try_get("started_at")?;
+        let updated_at: DateTime<Utc> = value.try_get("updated_at")?;
+        let finished_at: Option<DateTime<Utc>> = value.try_get("finished_at")?;
+        Ok(Job {
+            uid: value.try_get("uid")?,
+            kind: JobKind::try_from(kind).map_err(JobRepositoryError::InternalError)?,
+            status: JobStatus::try_from(status).map_err(JobRepositoryError::InternalError)?,
+            processed: processed as u64,
+            total: total.map(|total| total as u64),
+            result: result.map(|result| result.0),
+            error: value.try_get("error")?,
+            started_at,
+            updated_at,
+            finished_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresJobRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    /// Every running job saves its heartbeat through them.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresJobRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, JobRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| JobRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl JobRepository for PostgresJobRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn JobRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn save_job(&self, job: &Job) -> Result<(), JobRepositoryError> {
+        let connection = self.connect().await?;
+        // A job of another organization is left as it is.
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO job (uid, kind, status, processed, total, result, error, org_uid, started_at, updated_at, finished_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), $10) \
+                ON CONFLICT (uid) DO UPDATE SET status = EXCLUDED.status, processed = EXCLUDED.processed, total = EXCLUDED.total, result = EXCLUDED.result, error = EXCLUDED.error, updated_at = EXCLUDED.updated_at, finished_at = EXCLUDED.finished_at \
+                WHERE job.org_uid IS NOT DISTINCT FROM EXCLUDED.org_uid;",
+            )
+            .bind(job.uid)
+            .bind(job.kind.to_string())
+            .bind(job.status.to_string())
+            .bind(job.processed as i64)
+            .bind(job.total.map(|total| total as i64))
+            .bind(job.result.as_ref().map(Json))
+            .bind(&job.error)
+            .bind(self.organization)
+            .bind(job.started_at)
+            .bind(job.finished_at)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| JobRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+
+    async fn touch_job(&self, uid: &Uuid) -> Result<(), JobRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "UPDATE job SET updated_at = NOW() WHERE uid = $1 AND org_uid IS NOT DISTINCT FROM $2 AND status = 'running';",
+            )
+            .bind(uid)
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| JobRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+
+    async fn fail_stale_jobs(&self, stale_after: Duration) -> Result<u64, JobRepositoryError> {
+        let connection = self.connect().await?;
+        // The job ended at the latest when it was last known to run.
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "UPDATE job SET status = 'failed', error = 'The job stopped without recording its outcome', finished_at = updated_at \
+                WHERE status = 'running' AND updated_at < NOW() - make_interval(secs => $1);",
+            )
+            .bind(stale_after.as_secs_f64())
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| JobRepositoryError::InternalError(timed_out(e)))??;
+        Ok(result.rows_affected())
+    }
+
+    async fn get_job(&self, uid: &Uuid) -> Result<Job, JobRepositoryError> {
+        let connection = self.connect().await?;
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT uid, kind, status, processed, total, result, error, started_at, updated_at, finished_at FROM job WHERE uid = $1 AND org_uid IS NOT DISTINCT FROM $2;",
+            )
+            .bind(uid)
+            .bind(self.organization)
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| JobRepositoryError::InternalError(timed_out(e)))??;
+        row.try_into()
+    }
+
+    async fn get_jobs(
+        &self,
+        kinds: &[JobKind],
+        status: Option<JobStatus>,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<Job>, JobRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT uid, kind, status, processed, total, result, error, started_at, updated_at, finished_at FROM job \
+                WHERE org_uid IS NOT DISTINCT FROM $1 AND kind = ANY($2) AND ($3::VARCHAR IS NULL OR status = $3) \
+                ORDER BY started_at DESC, uid LIMIT $4 OFFSET $5;",
+            )
+            .bind(self.organization)
+            .bind(
+                kinds
+                    .iter()
+                    .map(|kind| kind.to_string())
+                    .collect::<Vec<String>>(),
+            )
+            .bind(status.map(|status| status.to_string()))
+            .bind(quantity as i64)
+            .bind(page as i64 * quantity as i64)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| JobRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Job::try_from).collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::PostgresJobRepository;
+    use crate::{
+        domain::job::{Job, JobKind, JobRepository, JobRepositoryError, JobStatus},
+        test_support::test_database,
+    };
+
+    #[tokio::test]
+    async fn test_postgres_jobs() {
+        let database = test_database().await;
+        let repository = PostgresJobRepository::new(database.url(), database.timeouts());
+        let mut import = Job::start(JobKind::SpeechImport);
+        import.processed = 2;
+        import.total = Some(10);
+        repository.save_job(&import).await.unwrap();
+        let mut backup = Job::start(JobKind::Backup);
+        backup.finish(Ok(json!({ "affected": 12 })));
+        repository.save_job(&backup).await.unwrap();
+        let stored = repository.get_job(&import.uid).await.unwrap();
+        assert_eq!(stored.status, JobStatus::Running);
+        assert_eq!((stored.processed, stored.total), (2, Some(10)));
+        let running = repository
+            .get_jobs(
+                &[JobKind::SpeechImport, JobKind::Backup],
+                Some(JobStatus::Running),
+                0,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].uid, import.uid);
+        let backups = repository
+            .get_jobs(&[JobKind::Backup], None, 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].result, Some(json!({ "affected": 12 })));
+        assert_eq!(
+            repository.get_job(&Uuid::new_v4()).await.err(),
+            Some(JobRepositoryError::JobNotFound)
+        );
+        repository.touch_job(&import.uid).await.unwrap();
+        assert_eq!(
+            repository
+                .fail_stale_jobs(Duration::from_secs(60))
+                .await
+                .unwrap(),
+            0
+        );
+        // Every running job is stale right away.
+        assert_eq!(repository.fail_stale_jobs(Duration::ZERO).await.unwrap(), 1);
+        let stale = repository.get_job(&import.uid).await.unwrap();
+        assert_eq!(stale.status, JobStatus::Failed);
+        assert!(stale.error.is_some());
+        assert_eq!(stale.finished_at, Some(stale.updated_at));
+    }
+}
@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use sqlx::{postgres::PgRow, Error, PgPool, Row};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::domain::label::{Label, LabelRepository, LabelRepositoryError, LabelTarget};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for LabelRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::Database(database_error) => {
+                if database_error.is_unique_violation() {
+                    return Self::LabelAlreadyExists;
+                }
+                Self::InternalError(database_error.to_string())
+            }
+            Error::RowNotFound => Self::LabelNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Label {
+    type Error = LabelRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let uid: Uuid = value.try_get("uid")?;
+        let name: &str = value.try_get("name")?;
+        let owner_id: Option<String> = value.try_get("owner_id")?;
+        Ok(Label::new(uid, name, owner_id))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresLabelRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresLabelRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            organization: None,
+        }
+    }
+
+    async fn connect(&self) -> Result<PgPool, LabelRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            PgPool::connect(&self.url),
+        )
+        .await
+        .map_err(|e| LabelRepositoryError::InternalError(timed_out(e)))??)
+    }
+}
+
+/// Returns the link table, its column referencing the labelled entity and the table of
+/// the entity.
+fn link_table(target: &LabelTarget) -> (&'static str, &'static str, &'static str, Uuid) {
+    match target {
+        LabelTarget::Speech(uid) => ("speech_label", "speech_uid", "speech", *uid),
+        LabelTarget::Person(uid) => ("person_label", "person_uid", "person", *uid),
+    }
+}
+
+#[async_trait::async_trait]
+impl LabelRepository for PostgresLabelRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn LabelRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn create_label(&self, label: &Label) -> Result<(), LabelRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO label (uid, name, owner_id, org_uid) VALUES ($1, $2, $3, $4);",
+            )
+            .bind(label.uid())
+            .bind(label.name())
+            .bind(label.owner())
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| LabelRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+
+    async fn get_labels(
+        &self,
+        prefix: &str,
+        user_id: &str,
+        quantity: u16,
+    ) -> Result<Vec<Label>, LabelRepositoryError> {
+        let connection = self.connect().await?;
+        // Escape LIKE wildcards so the prefix is matched literally.
+        let pattern = format!(
+            "{}%",
+            prefix
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT uid, name, owner_id FROM label WHERE name ILIKE $1 AND (owner_id IS NULL OR owner_id = $2) AND org_uid IS NOT DISTINCT FROM $4 ORDER BY name LIMIT $3;",
+            )
+            .bind(pattern)
+            .bind(user_id)
+            .bind(quantity as i64)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| LabelRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Label::try_from).collect()
+    }
+
+    async fn get_labels_of(
+        &self,
+        target: LabelTarget,
+        user_id: &str,
+    ) -> Result<Vec<Label>, LabelRepositoryError> {
+        let connection = self.connect().await?;
+        let (table, column, _, target_uid) = link_table(&target);
+        let query = format!(
+            "SELECT l.uid, l.name, l.owner_id FROM label l JOIN {table} t ON t.label_uid = l.uid WHERE t.{column} = $1 AND (l.owner_id IS NULL OR l.owner_id = $2) AND l.org_uid IS NOT DISTINCT FROM $3 ORDER BY l.name;"
+        );
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(&query)
+                .bind(target_uid)
+                .bind(user_id)
+                .bind(self.organization)
+                .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| LabelRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Label::try_from).collect()
+    }
+
+    async fn attach_label(
+        &self,
+        target: LabelTarget,
+        label_uid: &Uuid,
+        user_id: &str,
+    ) -> Result<(), LabelRepositoryError> {
+        let connection = self.connect().await?;
+        let (table, column, entity_table, target_uid) = link_table(&target);
+        // Only labels visible to the user can be attached, to entities of the same
+        // organization.
+        let query = format!(
+            "WITH visible AS (SELECT uid FROM label WHERE uid = $2 AND (owner_id IS NULL OR owner_id = $3) AND org_uid IS NOT DISTINCT FROM $4 \
+                AND EXISTS (SELECT 1 FROM {entity_table} WHERE uid = $1 AND org_uid IS NOT DISTINCT FROM $4)), \
+            inserted AS (INSERT INTO {table} ({column}, label_uid) SELECT $1, uid FROM visible ON CONFLICT DO NOTHING) \
+            SELECT COUNT(*) AS found FROM visible;"
+        );
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(&query)
+                .bind(target_uid)
+                .bind(label_uid)
+                .bind(user_id)
+                .bind(self.organization)
+                .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| LabelRepositoryError::InternalError(timed_out(e)))?;
+        match result {
+            Ok(row) if row.get::<i64, _>("found") == 0 => Err(LabelRepositoryError::LabelNotFound),
+            Ok(_) => Ok(()),
+            // The labelled entity does not exist.
+            Err(Error::Database(e)) if e.is_foreign_key_violation() => {
+                Err(LabelRepositoryError::LabelNotFound)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn detach_label(
+        &self,
+        target: LabelTarget,
+        label_uid: &Uuid,
+        user_id: &str,
+    ) -> Result<(), LabelRepositoryError> {
+        let connection = self.connect().await?;
+        let (table, column, _, target_uid) = link_table(&target);
+        let query = format!(
+            "DELETE FROM {table} t USING label l WHERE t.label_uid = l.uid AND t.{column} = $1 AND l.uid = $2 AND (l.owner_id IS NULL OR l.owner_id = $3) AND l.org_uid IS NOT DISTINCT FROM $4;"
+        );
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(&query)
+                .bind(target_uid)
+                .bind(label_uid)
+                .bind(user_id)
+                .bind(self.organization)
+                .execute(&connection),
+        )
+        .await
+        .map_err(|e| LabelRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(LabelRepositoryError::LabelNotFound);
+        }
+        Ok(())
+    }
+}
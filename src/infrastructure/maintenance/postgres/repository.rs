@@ -0,0 +1,100 @@
+use std::{sync::Arc, time::Duration};
+
+use sqlx::{Error, PgPool};
+use tokio::{sync::OnceCell, time};
+
+use crate::domain::maintenance::{MaintenanceRepository, MaintenanceRepositoryError};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+/// Tables whose indexes serve the searches and the lists of speeches.
+const SEARCH_TABLES: &[&str] = &["speech", "sentence", "speech_person"];
+
+impl From<Error> for MaintenanceRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        Self::InternalError(value.to_string())
+    }
+}
+
+/// Maintenance of the Postgres database. The actions reach every organization, they run
+/// under the migration timeout.
+#[derive(Debug, Clone)]
+pub struct PostgresMaintenanceRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    pool: Arc<OnceCell<PgPool>>,
+}
+
+impl PostgresMaintenanceRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, MaintenanceRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| MaintenanceRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl MaintenanceRepository for PostgresMaintenanceRepository {
+    async fn reindex_search(&self) -> Result<(), MaintenanceRepositoryError> {
+        let connection = self.connect().await?;
+        // REINDEX CONCURRENTLY cannot run in a transaction, each table is rebuilt on its
+        // own then analyzed for the planner.
+        for table in SEARCH_TABLES {
+            for statement in [
+                format!("REINDEX TABLE CONCURRENTLY {};", table),
+                format!("ANALYZE {};", table),
+            ] {
+                time::timeout(
+                    Duration::from_millis(self.timeouts.migration),
+                    sqlx::query(&statement).execute(&connection),
+                )
+                .await
+                .map_err(|e| MaintenanceRepositoryError::InternalError(timed_out(e)))??;
+            }
+        }
+        Ok(())
+    }
+
+    async fn vacuum_orphans(&self) -> Result<u64, MaintenanceRepositoryError> {
+        let connection = self.connect().await?;
+        let mut tx = connection.begin().await?;
+        // The translations of the sentences are deleted with them.
+        let sentences = time::timeout(
+            Duration::from_millis(self.timeouts.migration),
+            sqlx::query(
+                "DELETE FROM sentence se WHERE se.speech_uid IS NULL OR NOT EXISTS (SELECT 1 FROM speech s WHERE s.uid = se.speech_uid);",
+            )
+            .execute(&mut *tx),
+        )
+        .await
+        .map_err(|e| MaintenanceRepositoryError::InternalError(timed_out(e)))??;
+        let annotations = time::timeout(
+            Duration::from_millis(self.timeouts.migration),
+            sqlx::query(
+                "DELETE FROM sentence_annotation a WHERE NOT EXISTS (SELECT 1 FROM sentence se WHERE se.uid = a.sentence_uid);",
+            )
+            .execute(&mut *tx),
+        )
+        .await
+        .map_err(|e| MaintenanceRepositoryError::InternalError(timed_out(e)))??;
+        tx.commit().await?;
+        Ok(sentences.rows_affected() + annotations.rows_affected())
+    }
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::time;
+
+/// Applies the migrations of the `migrations` directory that are not applied yet.
+/// Run once at startup, before any repository is used. `timeout` bounds the connection
+/// and the migrations together.
+pub async fn run_migrations(url: &str, timeout: u64) -> Result<(), String> {
+    time::timeout(Duration::from_millis(timeout), async {
+        let connection = PgPool::connect(url).await.map_err(|e| e.to_string())?;
+        sqlx::migrate!()
+            .run(&connection)
+            .await
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -1,2 +1,25 @@
+pub mod annotation;
+pub mod attachment;
+pub mod backup;
+pub mod collection;
+pub mod error;
+pub mod error_metrics;
+pub mod filter;
+pub mod idempotency;
+pub mod job;
+pub mod label;
+pub mod maintenance;
+pub mod migrations;
+#[cfg(feature = "mongo")]
+pub mod mongo;
+pub mod organization;
+pub mod outbox;
 pub mod person;
+pub mod pii;
+pub mod segment;
 pub mod speech;
+pub mod stats;
+pub mod tag;
+pub mod timeouts;
+pub mod translation;
+pub mod watchlist;
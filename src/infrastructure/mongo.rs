@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveTime, Utc};
+use mongodb::{
+    bson::{self, doc, spec::BinarySubtype, Bson, Document},
+    error::{Error, ErrorKind, WriteFailure},
+    options::IndexOptions,
+    Client, Database, IndexModel,
+};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        filter::{FilterOperator, FilterSpec, FilterValue},
+        metrics::{record_repository_error, RepositoryErrorClass},
+    },
+    infrastructure::error_metrics::timed_out,
+};
+
+/// Error code of MongoDB when a unique index is violated.
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+/// Connects to the database of the deployment, each repository call using its own
+/// connection as the Postgres repositories do.
+pub async fn connect(url: &str, database: &str, timeout: u64) -> Result<Database, String> {
+    let client = time::timeout(Duration::from_millis(timeout), Client::with_uri_str(url))
+        .await
+        .map_err(timed_out)?
+        .map_err(|e| {
+            record_mongo_error(&e);
+            e.to_string()
+        })?;
+    Ok(client.database(database))
+}
+
+/// Creates the indexes the repositories rely on, the unique ones playing the part of the
+/// Postgres constraints. Run once at startup, before any repository is used.
+pub async fn create_indexes(url: &str, database: &str, timeout: u64) -> Result<(), String> {
+    let database = connect(url, database, timeout).await?;
+    let unique = || IndexOptions::builder().unique(true).build();
+    // The same person or speech may exist once in every organization.
+    let indexes = [
+        (
+            "person",
+            IndexModel::builder()
+                .keys(doc! { "name": 1, "first_name": 1, "birth_date": 1, "org_uid": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "speech",
+            IndexModel::builder()
+                .keys(doc! { "name": 1, "date": 1, "media": 1, "org_uid": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "speech",
+            IndexModel::builder()
+                .keys(doc! { "date": -1, "_id": 1 })
+                .build(),
+        ),
+        (
+            "speech",
+            IndexModel::builder()
+                .keys(doc! { "speakers.uid": 1 })
+                .build(),
+        ),
+        (
+            "speech",
+            IndexModel::builder()
+                .keys(doc! { "sentences.uid": 1 })
+                .build(),
+        ),
+        (
+            "speech_revision",
+            IndexModel::builder()
+                .keys(doc! { "speech_uid": 1, "revision": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "speech_slug",
+            IndexModel::builder().keys(doc! { "speech_uid": 1 }).build(),
+        ),
+        (
+            "speech_read_progress",
+            IndexModel::builder()
+                .keys(doc! { "user_id": 1, "speech_uid": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "speech_cluster",
+            IndexModel::builder()
+                .keys(doc! { "org_uid": 1, "cluster_uid": 1 })
+                .build(),
+        ),
+        (
+            "pii_flag",
+            IndexModel::builder()
+                .keys(doc! { "speech_uid": 1, "kind": 1, "excerpt": 1 })
+                .options(unique())
+                .build(),
+        ),
+        (
+            "import_conflict",
+            IndexModel::builder()
+                .keys(doc! { "import_uid": 1, "position": 1 })
+                .build(),
+        ),
+    ];
+    for (collection, index) in indexes {
+        time::timeout(
+            Duration::from_millis(timeout),
+            database
+                .collection::<Document>(collection)
+                .create_index(index),
+        )
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Whether the write failed on a unique index.
+pub fn is_duplicate_key(error: &Error) -> bool {
+    match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(e)) => e.code == DUPLICATE_KEY_CODE,
+        ErrorKind::InsertMany(e) => e
+            .write_errors
+            .as_ref()
+            .is_some_and(|errors| errors.iter().any(|e| e.code == DUPLICATE_KEY_CODE)),
+        _ => false,
+    }
+}
+
+/// Counts a MongoDB error in the repository error metrics.
+pub fn record_mongo_error(error: &Error) {
+    let class = match error.kind.as_ref() {
+        _ if is_duplicate_key(error) => RepositoryErrorClass::ConstraintViolation,
+        ErrorKind::Io(_)
+        | ErrorKind::ServerSelection { .. }
+        | ErrorKind::ConnectionPoolCleared { .. }
+        | ErrorKind::DnsResolve { .. }
+        | ErrorKind::Authentication { .. } => RepositoryErrorClass::Connection,
+        ErrorKind::BsonDeserialization(_) | ErrorKind::BsonSerialization(_) => {
+            RepositoryErrorClass::Serialization
+        }
+        _ => RepositoryErrorClass::Other,
+    };
+    record_repository_error(class);
+}
+
+/// Stores the uid as a standard BSON UUID.
+pub fn uid_to_bson(uid: &Uuid) -> Bson {
+    Bson::from(bson::Uuid::from_bytes(*uid.as_bytes()))
+}
+
+/// Stores the organization, `None` being the default organization.
+pub fn organization_to_bson(organization: Option<Uuid>) -> Bson {
+    organization
+        .map(|uid| uid_to_bson(&uid))
+        .unwrap_or(Bson::Null)
+}
+
+pub fn uid_from_bson(value: Option<&Bson>) -> Result<Uuid, String> {
+    match value {
+        Some(Bson::Binary(binary)) if binary.subtype == BinarySubtype::Uuid => {
+            Uuid::from_slice(&binary.bytes).map_err(|e| e.to_string())
+        }
+        _ => Err("Unexpected uid value".to_owned()),
+    }
+}
+
+pub fn date_time_to_bson(date: &DateTime<Utc>) -> Bson {
+    Bson::DateTime(bson::DateTime::from_millis(date.timestamp_millis()))
+}
+
+pub fn date_time_from_bson(value: Option<&Bson>) -> Result<DateTime<Utc>, String> {
+    match value {
+        Some(Bson::DateTime(date)) => DateTime::from_timestamp_millis(date.timestamp_millis())
+            .ok_or_else(|| "Unexpected date value".to_owned()),
+        _ => Err("Unexpected date value".to_owned()),
+    }
+}
+
+/// Reads an integer stored either as an `int32` or an `int64`.
+pub fn integer_from_bson(value: Option<&Bson>) -> Result<i64, String> {
+    match value {
+        Some(Bson::Int32(value)) => Ok(*value as i64),
+        Some(Bson::Int64(value)) => Ok(*value),
+        _ => Err("Unexpected integer value".to_owned()),
+    }
+}
+
+/// Reads the version of a speech or a person, the documents stored before the versions
+/// existed having the version 0. Each update increments it with `$inc`, which starts a
+/// missing version at 1.
+pub fn version_from_bson(value: Option<&Bson>) -> u32 {
+    integer_from_bson(value).map_or(0, |version| version as u32)
+}
+
+/// Condition matching the documents having the version.
+pub fn version_condition(version: u32) -> Bson {
+    match version {
+        0 => Bson::Document(doc! { "$in": [0, Bson::Null] }),
+        version => Bson::Int64(version as i64),
+    }
+}
+
+fn mongo_operator(operator: FilterOperator) -> &'static str {
+    match operator {
+        FilterOperator::Eq => "$eq",
+        FilterOperator::Ne => "$ne",
+        FilterOperator::Gt => "$gt",
+        FilterOperator::Ge => "$gte",
+        FilterOperator::Lt => "$lt",
+        FilterOperator::Le => "$lte",
+    }
+}
+
+/// Conditions of the filter, to be met together under `$and`. `field` gives the document
+/// field of each filter field. The dates are compared to date times when
+/// `dates_as_date_times`, a day then matching all its date times, and to `YYYY-MM-DD`
+/// strings otherwise.
+pub fn filter_conditions<F: Copy>(
+    spec: &FilterSpec<F>,
+    field: impl Fn(F) -> &'static str,
+    dates_as_date_times: bool,
+) -> Vec<Document> {
+    spec.conditions
+        .iter()
+        .map(|condition| {
+            let name = field(condition.field);
+            let value = match &condition.value {
+                FilterValue::Date(date) if dates_as_date_times => {
+                    let start = date.and_time(NaiveTime::default()).and_utc();
+                    let (start, end) = (
+                        date_time_to_bson(&start),
+                        date_time_to_bson(&(start + chrono::Duration::days(1))),
+                    );
+                    let day = doc! { "$gte": start.clone(), "$lt": end.clone() };
+                    let range = match condition.operator {
+                        FilterOperator::Eq => day,
+                        FilterOperator::Ne => doc! { "$not": day },
+                        FilterOperator::Gt => doc! { "$gte": end },
+                        FilterOperator::Ge => doc! { "$gte": start },
+                        FilterOperator::Lt => doc! { "$lt": start },
+                        FilterOperator::Le => doc! { "$lt": end },
+                    };
+                    return doc! { name: range };
+                }
+                FilterValue::Date(date) => Bson::String(date.to_string()),
+                FilterValue::Text(text) => Bson::String(text.clone()),
+                FilterValue::Number(number) => Bson::Int64(*number),
+            };
+            doc! { name: { mongo_operator(condition.operator): value } }
+        })
+        .collect()
+}
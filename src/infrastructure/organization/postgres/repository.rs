@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Error, PgPool, Row};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::domain::organization::{
+    Organization, OrganizationRepository, OrganizationRepositoryError,
+};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for OrganizationRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::Database(database_error) => {
+                if database_error.is_unique_violation() {
+                    return Self::OrganizationAlreadyExists;
+                }
+                if database_error.is_foreign_key_violation() {
+                    return Self::OrganizationNotEmpty;
+                }
+                Self::InternalError(database_error.to_string())
+            }
+            Error::RowNotFound => Self::OrganizationNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Organization {
+    type Error = OrganizationRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let uid: Uuid = value.try_get("uid")?;
+        let name: &str = value.try_get("name")?;
+        let created_at: DateTime<Utc> = value.try_get("created_at")?;
+        Ok(Organization::new(uid, name, created_at))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresOrganizationRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+}
+
+impl PostgresOrganizationRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+        }
+    }
+
+    async fn connect(&self) -> Result<PgPool, OrganizationRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            PgPool::connect(&self.url),
+        )
+        .await
+        .map_err(|e| OrganizationRepositoryError::InternalError(timed_out(e)))??)
+    }
+}
+
+#[async_trait::async_trait]
+impl OrganizationRepository for PostgresOrganizationRepository {
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+    ) -> Result<(), OrganizationRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query("INSERT INTO organization (uid, name, created_at) VALUES ($1, $2, $3);")
+                .bind(organization.uid())
+                .bind(organization.name())
+                .bind(organization.created_at())
+                .execute(&connection),
+        )
+        .await
+        .map_err(|e| OrganizationRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+
+    async fn get_organizations(&self) -> Result<Vec<Organization>, OrganizationRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query("SELECT uid, name, created_at FROM organization ORDER BY name;")
+                .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| OrganizationRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Organization::try_from).collect()
+    }
+
+    async fn get_organization_by_id(
+        &self,
+        uid: &Uuid,
+    ) -> Result<Organization, OrganizationRepositoryError> {
+        let connection = self.connect().await?;
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query("SELECT uid, name, created_at FROM organization WHERE uid = $1;")
+                .bind(uid)
+                .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| OrganizationRepositoryError::InternalError(timed_out(e)))??;
+        row.try_into()
+    }
+
+    async fn rename_organization(
+        &self,
+        uid: &Uuid,
+        name: &str,
+    ) -> Result<(), OrganizationRepositoryError> {
+        let connection = self.connect().await?;
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query("UPDATE organization SET name = $2 WHERE uid = $1;")
+                .bind(uid)
+                .bind(name)
+                .execute(&connection),
+        )
+        .await
+        .map_err(|e| OrganizationRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(OrganizationRepositoryError::OrganizationNotFound);
+        }
+        Ok(())
+    }
+
+    async fn delete_organization(&self, uid: &Uuid) -> Result<(), OrganizationRepositoryError> {
+        let connection = self.connect().await?;
+        // The references from persons, speeches and labels reject the deletion of an
+        // organization which still owns data.
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query("DELETE FROM organization WHERE uid = $1;")
+                .bind(uid)
+                .execute(&connection),
+        )
+        .await
+        .map_err(|e| OrganizationRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(OrganizationRepositoryError::OrganizationNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use rdkafka::{
+    config::ClientConfig,
+    error::KafkaError,
+    message::{Header, OwnedHeaders},
+    producer::{FutureProducer, FutureRecord},
+};
+
+use crate::domain::outbox::{EventPublisher, EventPublisherError, OutboxEvent};
+
+/// Publishes the events to Kafka on the topic `{prefix}.{topic}`, e.g.
+/// `speech_analytics.speech`. The events are keyed by their entity so the events of an
+/// entity stay in order on their partition.
+#[derive(Clone)]
+pub struct KafkaEventPublisher {
+    producer: FutureProducer,
+    topic_prefix: String,
+    /// Time an event may wait to be sent, in milliseconds.
+    timeout: u64,
+}
+
+impl KafkaEventPublisher {
+    pub fn new(brokers: &str, topic_prefix: &str, timeout: u64) -> Result<Self, KafkaError> {
+        // Every replica must store an event before it is acknowledged, the producer
+        // retrying without duplicating it.
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("acks", "all")
+            .set("enable.idempotence", "true")
+            .create()?;
+        Ok(Self {
+            producer,
+            topic_prefix: topic_prefix.to_string(),
+            timeout,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for KafkaEventPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), EventPublisherError> {
+        let topic = format!("{}.{}", self.topic_prefix, event.topic());
+        let key = event.entity().to_string();
+        let payload = event.message().to_string();
+        let id = event.id().to_string();
+        let record = FutureRecord::to(&topic)
+            .key(&key)
+            .payload(&payload)
+            .headers(OwnedHeaders::new().insert(Header {
+                key: "event-id",
+                value: Some(id.as_str()),
+            }));
+        self.producer
+            .send(record, Duration::from_millis(self.timeout))
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| EventPublisherError::BrokerError(e.to_string()))
+    }
+}
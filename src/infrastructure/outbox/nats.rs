@@ -0,0 +1,43 @@
+use async_nats::{
+    jetstream::{self, Context},
+    ConnectError, HeaderMap,
+};
+
+use crate::domain::outbox::{EventPublisher, EventPublisherError, OutboxEvent};
+
+/// Publishes the events to NATS JetStream on the subject `{prefix}.{topic}.{kind}`, e.g.
+/// `speech_analytics.speech.validated`. A stream must capture the subjects, an event is
+/// only published once the stream stores it. The id of the event is sent as
+/// `Nats-Msg-Id` so the stream drops the copies published again.
+#[derive(Clone)]
+pub struct NatsEventPublisher {
+    jetstream: Context,
+    subject_prefix: String,
+}
+
+impl NatsEventPublisher {
+    pub async fn new(url: &str, subject_prefix: &str) -> Result<Self, ConnectError> {
+        let client = async_nats::connect(url).await?;
+        Ok(Self {
+            jetstream: jetstream::new(client),
+            subject_prefix: subject_prefix.to_string(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for NatsEventPublisher {
+    async fn publish(&self, event: &OutboxEvent) -> Result<(), EventPublisherError> {
+        let subject = format!("{}.{}.{}", self.subject_prefix, event.topic(), event.kind());
+        let mut headers = HeaderMap::new();
+        headers.insert("Nats-Msg-Id", event.id().to_string().as_str());
+        let ack = self
+            .jetstream
+            .publish_with_headers(subject, headers, event.message().to_string().into())
+            .await
+            .map_err(|e| EventPublisherError::BrokerError(e.to_string()))?;
+        ack.await
+            .map_err(|e| EventPublisherError::BrokerError(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::{postgres::PgRow, Error, PgPool, Row};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::outbox::{OutboxEvent, OutboxPosition, OutboxRepository, OutboxRepositoryError};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for OutboxRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        Self::InternalError(value.to_string())
+    }
+}
+
+impl TryFrom<PgRow> for OutboxEvent {
+    type Error = OutboxRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let position = OutboxPosition {
+            transaction: value.try_get("transaction_id")?,
+            event: value.try_get("id")?,
+        };
+        let topic: &str = value.try_get("topic")?;
+        let entity: Uuid = value.try_get("entity_uid")?;
+        let kind: &str = value.try_get("kind")?;
+        let payload: Value = value.try_get("payload")?;
+        let organization: Option<Uuid> = value.try_get("org_uid")?;
+        let created_at: DateTime<Utc> = value.try_get("created_at")?;
+        Ok(OutboxEvent::new(
+            position,
+            topic,
+            entity,
+            kind,
+            payload,
+            organization,
+            created_at,
+        ))
+    }
+}
+
+/// Outbox of the events written by the Postgres repositories of the persons and the
+/// speeches. The outbox is shared by every organization.
+#[derive(Debug, Clone)]
+pub struct PostgresOutboxRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query. The
+    /// relay polls the outbox, the connections are kept between the polls.
+    pool: Arc<OnceCell<PgPool>>,
+}
+
+impl PostgresOutboxRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn pool(&self) -> Result<PgPool, OutboxRepositoryError> {
+        let pool = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| OutboxRepositoryError::InternalError(timed_out(e)))??;
+        Ok(pool.clone())
+    }
+
+    /// Applies the write timeout to a query.
+    async fn with_write_timeout<T>(
+        &self,
+        query: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, OutboxRepositoryError> {
+        time::timeout(Duration::from_millis(self.timeouts.write), query)
+            .await
+            .map_err(|e| OutboxRepositoryError::InternalError(timed_out(e)))?
+            .map_err(|e| e.into())
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxRepository for PostgresOutboxRepository {
+    async fn claim_relay(
+        &self,
+        relay: &str,
+        holder: &str,
+        lease: Duration,
+    ) -> Result<Option<OutboxPosition>, OutboxRepositoryError> {
+        let connection = self.pool().await?;
+        let row = self
+            .with_write_timeout(
+                sqlx::query(
+                    "INSERT INTO event_outbox_offset (relay, leased_by, leased_until) VALUES ($1, $2, NOW() + make_interval(secs => $3)) \
+                    ON CONFLICT (relay) DO UPDATE SET leased_by = $2, leased_until = EXCLUDED.leased_until \
+                    WHERE event_outbox_offset.leased_by = $2 OR event_outbox_offset.leased_until IS NULL OR event_outbox_offset.leased_until < NOW() \
+                    RETURNING transaction_id, event_id;",
+                )
+                .bind(relay)
+                .bind(holder)
+                .bind(lease.as_secs_f64())
+                .fetch_optional(&connection),
+            )
+            .await?;
+        match row {
+            Some(row) => Ok(Some(OutboxPosition {
+                transaction: row.try_get("transaction_id")?,
+                event: row.try_get("event_id")?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_events_after(
+        &self,
+        position: OutboxPosition,
+        quantity: u16,
+    ) -> Result<Vec<OutboxEvent>, OutboxRepositoryError> {
+        let connection = self.pool().await?;
+        // The transactions older than the oldest running one are all committed or rolled
+        // back, no event can be written behind them anymore.
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT id, transaction_id, topic, entity_uid, kind, payload, org_uid, created_at FROM event_outbox \
+                WHERE (transaction_id, id) > ($1, $2) AND transaction_id < pg_snapshot_xmin(pg_current_snapshot())::TEXT::BIGINT \
+                ORDER BY transaction_id, id LIMIT $3;",
+            )
+            .bind(position.transaction)
+            .bind(position.event)
+            .bind(quantity as i64)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| OutboxRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(OutboxEvent::try_from).collect()
+    }
+
+    async fn save_position(
+        &self,
+        relay: &str,
+        holder: &str,
+        position: OutboxPosition,
+    ) -> Result<(), OutboxRepositoryError> {
+        let connection = self.pool().await?;
+        let result = self
+            .with_write_timeout(
+                sqlx::query(
+                    "UPDATE event_outbox_offset SET transaction_id = $3, event_id = $4 WHERE relay = $1 AND leased_by = $2;",
+                )
+                .bind(relay)
+                .bind(holder)
+                .bind(position.transaction)
+                .bind(position.event)
+                .execute(&connection),
+            )
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(OutboxRepositoryError::InternalError(format!(
+                "The relay {} is leased by another instance",
+                relay
+            )));
+        }
+        Ok(())
+    }
+
+    async fn delete_events(
+        &self,
+        position: OutboxPosition,
+        before: DateTime<Utc>,
+    ) -> Result<u64, OutboxRepositoryError> {
+        let connection = self.pool().await?;
+        let result = self
+            .with_write_timeout(
+                sqlx::query(
+                    "DELETE FROM event_outbox WHERE (transaction_id, id) <= ($1, $2) AND created_at < $3;",
+                )
+                .bind(position.transaction)
+                .bind(position.event)
+                .bind(before)
+                .execute(&connection),
+            )
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1 +1,3 @@
+#[cfg(feature = "mongo")]
+pub mod mongo;
 pub mod postgres;
@@ -0,0 +1,753 @@
+use std::{collections::HashMap, future::IntoFuture, time::Duration};
+
+use chrono::NaiveDate;
+use futures_util::TryStreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document},
+    error::Error,
+    Collection,
+};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        person::{
+            duplicate_score, normalize_name, unknown_speaker, DeleteStrategy, GetPeopleResponse,
+            Person, PersonDuplicate, PersonField, PersonFilter, PersonRepository,
+            PersonRepositoryError,
+        },
+        upsert::{OnConflict, UpsertOutcome},
+    },
+    infrastructure::{
+        error_metrics::timed_out,
+        mongo::{
+            connect, filter_conditions, integer_from_bson, is_duplicate_key, organization_to_bson,
+            record_mongo_error, uid_from_bson, uid_to_bson, version_condition, version_from_bson,
+        },
+        timeouts::DatabaseTimeouts,
+    },
+};
+
+impl From<Error> for PersonRepositoryError {
+    fn from(value: Error) -> Self {
+        record_mongo_error(&value);
+        if is_duplicate_key(&value) {
+            return Self::PersonAlreadyExists;
+        }
+        Self::InternalError(value.to_string())
+    }
+}
+
+impl TryFrom<Document> for Person {
+    type Error = PersonRepositoryError;
+
+    fn try_from(value: Document) -> Result<Self, Self::Error> {
+        let uid = uid_from_bson(value.get("_id")).map_err(PersonRepositoryError::InternalError)?;
+        let name = value
+            .get_str("name")
+            .map_err(|e| PersonRepositoryError::InternalError(e.to_string()))?;
+        let first_name = value
+            .get_str("first_name")
+            .map_err(|e| PersonRepositoryError::InternalError(e.to_string()))?;
+        let birth_date = value
+            .get_str("birth_date")
+            .map_err(|e| PersonRepositoryError::InternalError(e.to_string()))?
+            .parse::<NaiveDate>()
+            .map_err(|e| PersonRepositoryError::InternalError(e.to_string()))?;
+        let trust_score = integer_from_bson(value.get("trust_score"))
+            .map_err(PersonRepositoryError::InternalError)?;
+        let lie_quantity = integer_from_bson(value.get("lie_quantity"))
+            .map_err(PersonRepositoryError::InternalError)?;
+        Ok(Person::new(
+            uid,
+            name,
+            first_name,
+            birth_date,
+            trust_score as u8,
+            lie_quantity as u64,
+        )
+        .with_version(version_from_bson(value.get("version"))))
+    }
+}
+
+/// Persons stored in the `person` collection of a MongoDB (or DocumentDB) database.
+#[derive(Debug, Clone)]
+pub struct MongoPersonRepository {
+    url: String,
+    database: String,
+    timeouts: DatabaseTimeouts,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl MongoPersonRepository {
+    pub fn new(url: &str, database: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            database: database.to_string(),
+            timeouts,
+            organization: None,
+        }
+    }
+
+    async fn collection(&self, name: &str) -> Result<Collection<Document>, PersonRepositoryError> {
+        let database = connect(&self.url, &self.database, self.timeouts.read)
+            .await
+            .map_err(PersonRepositoryError::InternalError)?;
+        Ok(database.collection(name))
+    }
+
+    /// Applies the read timeout to a query.
+    async fn with_read_timeout<T>(
+        &self,
+        query: impl IntoFuture<Output = Result<T, Error>>,
+    ) -> Result<T, PersonRepositoryError> {
+        time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            query.into_future(),
+        )
+        .await
+        .map_err(|e| PersonRepositoryError::InternalError(timed_out(e)))?
+        .map_err(|e| e.into())
+    }
+
+    /// Applies the write timeout to a query.
+    async fn with_write_timeout<T>(
+        &self,
+        query: impl IntoFuture<Output = Result<T, Error>>,
+    ) -> Result<T, PersonRepositoryError> {
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            query.into_future(),
+        )
+        .await
+        .map_err(|e| PersonRepositoryError::InternalError(timed_out(e)))?
+        .map_err(|e| e.into())
+    }
+
+    fn person_document(&self, person: &Person) -> Document {
+        doc! {
+            "_id": uid_to_bson(person.uid()),
+            "name": person.name(),
+            "first_name": person.first_name(),
+            "birth_date": person.birth_date().to_string(),
+            "trust_score": person.trust_score() as i32,
+            "lie_quantity": person.lie_quantity() as i64,
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+            "version": 1,
+        }
+    }
+
+    /// Soft deletes the person, whatever the speeches referencing it.
+    async fn soft_delete(&self, uid: &Uuid) -> Result<(), PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let result = self
+            .with_write_timeout(collection.update_one(
+                doc! {
+                    "_id": uid_to_bson(uid),
+                    "org_uid": organization_to_bson(self.organization),
+                    "deleted_at": Bson::Null,
+                },
+                doc! { "$currentDate": { "deleted_at": true } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            return Err(PersonRepositoryError::PersonNotFound);
+        }
+        Ok(())
+    }
+
+    /// Gives the speeches and sentences of the person to another one, a speech where both
+    /// speak keeping the other one.
+    async fn move_speeches(&self, from: &Uuid, to: &Uuid) -> Result<(), PersonRepositoryError> {
+        let speeches = self.collection("speech").await?;
+        self.with_write_timeout(speeches.update_many(
+            doc! { "$and": [
+                { "speakers.uid": uid_to_bson(from) },
+                { "speakers.uid": uid_to_bson(to) },
+            ] },
+            doc! { "$pull": { "speakers": { "uid": uid_to_bson(from) } } },
+        ))
+        .await?;
+        self.with_write_timeout(
+            speeches
+                .update_many(
+                    doc! { "speakers.uid": uid_to_bson(from) },
+                    doc! { "$set": { "speakers.$[s].uid": uid_to_bson(to) } },
+                )
+                .array_filters(vec![doc! { "s.uid": uid_to_bson(from) }]),
+        )
+        .await?;
+        self.with_write_timeout(
+            speeches
+                .update_many(
+                    doc! { "sentences.speaker": uid_to_bson(from) },
+                    doc! { "$set": { "sentences.$[s].speaker": uid_to_bson(to) } },
+                )
+                .array_filters(vec![doc! { "s.speaker": uid_to_bson(from) }]),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the unknown speaker of the organization, created or restored when needed.
+    async fn unknown_speaker_uid(&self) -> Result<Uuid, PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let person = unknown_speaker();
+        let identity = doc! {
+            "name": person.name(),
+            "first_name": person.first_name(),
+            "birth_date": person.birth_date().to_string(),
+            "org_uid": organization_to_bson(self.organization),
+        };
+        match self
+            .with_write_timeout(
+                collection
+                    .update_one(
+                        identity.clone(),
+                        doc! { "$setOnInsert": self.person_document(&person) },
+                    )
+                    .upsert(true),
+            )
+            .await
+        {
+            Ok(result) if result.upserted_id.is_some() => return Ok(*person.uid()),
+            // A concurrent creation of the unknown speaker was first.
+            Ok(_) | Err(PersonRepositoryError::PersonAlreadyExists) => {}
+            Err(e) => return Err(e),
+        }
+        let stored = self
+            .with_write_timeout(
+                collection
+                    .find_one_and_update(identity, doc! { "$set": { "deleted_at": Bson::Null } }),
+            )
+            .await?
+            .ok_or(PersonRepositoryError::PersonNotFound)?;
+        uid_from_bson(stored.get("_id")).map_err(PersonRepositoryError::InternalError)
+    }
+
+    /// Builds the query matching the filter, shared by the list and count queries so
+    /// they always agree.
+    fn person_filter(&self, filter: &PersonFilter) -> Result<Document, PersonRepositoryError> {
+        // The labels are stored in Postgres, they cannot be joined to the documents.
+        if !filter.labels.is_empty() {
+            return Err(PersonRepositoryError::InternalError(
+                "Filtering persons by label is not supported by the MongoDB backend".to_owned(),
+            ));
+        }
+        let mut query = doc! { "org_uid": organization_to_bson(self.organization) };
+        if !filter.include_deleted {
+            query.insert("deleted_at", Bson::Null);
+        }
+        let conditions = filter_conditions(
+            &filter.spec,
+            |field| match field {
+                PersonField::Name => "name",
+                PersonField::FirstName => "first_name",
+                PersonField::BirthDate => "birth_date",
+                PersonField::TrustScore => "trust_score",
+                PersonField::LieQuantity => "lie_quantity",
+            },
+            false,
+        );
+        if !conditions.is_empty() {
+            query.insert("$and", conditions);
+        }
+        Ok(query)
+    }
+}
+
+#[async_trait::async_trait]
+impl PersonRepository for MongoPersonRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn PersonRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn create_person(&self, person: &Person) -> Result<(), PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        self.with_write_timeout(collection.insert_one(self.person_document(person)))
+            .await?;
+        Ok(())
+    }
+
+    async fn upsert_person(
+        &self,
+        person: &Person,
+        on_conflict: OnConflict,
+    ) -> Result<UpsertOutcome, PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let identity = doc! {
+            "name": person.name(),
+            "first_name": person.first_name(),
+            "birth_date": person.birth_date().to_string(),
+            "org_uid": organization_to_bson(self.organization),
+        };
+        // Inserts the person only when its identity is free.
+        let upserted = match self
+            .with_write_timeout(
+                collection
+                    .update_one(
+                        identity.clone(),
+                        doc! { "$setOnInsert": self.person_document(person) },
+                    )
+                    .upsert(true),
+            )
+            .await
+        {
+            Ok(result) => result.upserted_id.is_some(),
+            // A concurrent creation of the same identity was first.
+            Err(PersonRepositoryError::PersonAlreadyExists) => false,
+            Err(e) => return Err(e),
+        };
+        if upserted {
+            return Ok(UpsertOutcome::Created(*person.uid()));
+        }
+        let stored = self
+            .with_write_timeout(
+                collection
+                    .find_one(identity)
+                    .projection(doc! { "_id": 1, "deleted_at": 1 }),
+            )
+            .await?
+            .ok_or(PersonRepositoryError::PersonNotFound)?;
+        let uid = uid_from_bson(stored.get("_id")).map_err(PersonRepositoryError::InternalError)?;
+        let deleted = !matches!(stored.get("deleted_at"), Some(Bson::Null) | None);
+        if on_conflict == OnConflict::Ignore || deleted {
+            return Ok(UpsertOutcome::Skipped(uid));
+        }
+        let result = self
+            .with_write_timeout(collection.update_one(
+                doc! { "_id": uid_to_bson(&uid), "deleted_at": Bson::Null },
+                doc! {
+                    "$set": {
+                        "trust_score": person.trust_score() as i32,
+                        "lie_quantity": person.lie_quantity() as i64,
+                    },
+                    "$inc": { "version": 1 },
+                },
+            ))
+            .await?;
+        Ok(match result.matched_count {
+            0 => UpsertOutcome::Skipped(uid),
+            _ => UpsertOutcome::Updated(uid),
+        })
+    }
+
+    async fn create_people(
+        &self,
+        people: &[Person],
+    ) -> Result<Vec<Option<Uuid>>, PersonRepositoryError> {
+        if people.is_empty() {
+            return Ok(Vec::new());
+        }
+        let collection = self.collection("person").await?;
+        let mut stored: HashMap<(String, String, String), Uuid> = HashMap::new();
+        for chunk in people.chunks(1000) {
+            let documents = chunk
+                .iter()
+                .map(|person| self.person_document(person))
+                .collect::<Vec<Document>>();
+            // The identities already taken conflict on the unique index, the other persons
+            // are still inserted.
+            match self
+                .with_write_timeout(collection.insert_many(documents).ordered(false))
+                .await
+            {
+                Ok(_) | Err(PersonRepositoryError::PersonAlreadyExists) => {}
+                Err(e) => return Err(e),
+            }
+            // Finds the person stored with each identity, created above or before.
+            let identities = chunk
+                .iter()
+                .map(|person| {
+                    doc! {
+                        "name": person.name(),
+                        "first_name": person.first_name(),
+                        "birth_date": person.birth_date().to_string(),
+                    }
+                })
+                .collect::<Vec<Document>>();
+            let documents: Vec<Document> = self
+                .with_write_timeout(async {
+                    collection
+                        .find(doc! {
+                            "org_uid": organization_to_bson(self.organization),
+                            "deleted_at": Bson::Null,
+                            "$or": identities,
+                        })
+                        .await?
+                        .try_collect()
+                        .await
+                })
+                .await?;
+            for document in documents {
+                let identity = (
+                    document.get_str("name").unwrap_or_default().to_owned(),
+                    document
+                        .get_str("first_name")
+                        .unwrap_or_default()
+                        .to_owned(),
+                    document
+                        .get_str("birth_date")
+                        .unwrap_or_default()
+                        .to_owned(),
+                );
+                let uid = uid_from_bson(document.get("_id"))
+                    .map_err(PersonRepositoryError::InternalError)?;
+                stored.insert(identity, uid);
+            }
+        }
+        Ok(people
+            .iter()
+            .map(|person| {
+                let identity = (
+                    person.name().clone(),
+                    person.first_name().clone(),
+                    person.birth_date().to_string(),
+                );
+                stored.get(&identity).copied()
+            })
+            .collect())
+    }
+
+    async fn update_person(
+        &self,
+        person: &Person,
+        expected_version: Option<u32>,
+    ) -> Result<(), PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let query = doc! {
+            "_id": uid_to_bson(person.uid()),
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+        };
+        let mut versioned_query = query.clone();
+        if let Some(version) = expected_version {
+            versioned_query.insert("version", version_condition(version));
+        }
+        let result = self
+            .with_write_timeout(collection.update_one(
+                versioned_query,
+                doc! {
+                    "$set": {
+                        "name": person.name(),
+                        "first_name": person.first_name(),
+                        "birth_date": person.birth_date().to_string(),
+                        "trust_score": person.trust_score() as i32,
+                        "lie_quantity": person.lie_quantity() as i64,
+                    },
+                    "$inc": { "version": 1 },
+                },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            // Telling a missing person from a stale version.
+            self.with_write_timeout(collection.find_one(query).projection(doc! { "_id": 1 }))
+                .await?
+                .ok_or(PersonRepositoryError::PersonNotFound)?;
+            return Err(PersonRepositoryError::VersionMismatch);
+        }
+        Ok(())
+    }
+
+    async fn get_person_by_id(&self, uid: &Uuid) -> Result<Person, PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        self.with_read_timeout(collection.find_one(doc! {
+            "_id": uid_to_bson(uid),
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+        }))
+        .await?
+        .ok_or(PersonRepositoryError::PersonNotFound)?
+        .try_into()
+    }
+
+    async fn get_people(
+        &self,
+        page: u16,
+        quantity: u16,
+        filter: &PersonFilter,
+    ) -> Result<GetPeopleResponse, PersonRepositoryError> {
+        let query = self.person_filter(filter)?;
+        let collection = self.collection("person").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(query)
+                    .sort(doc! { "_id": 1 })
+                    .skip(page as u64 * quantity as u64)
+                    .limit(quantity as i64)
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let people = documents
+            .into_iter()
+            .filter_map(|document| document.try_into().ok())
+            .collect();
+        let nb_person = self.count_people(filter).await?;
+        Ok(GetPeopleResponse { people, nb_person })
+    }
+
+    async fn count_people(&self, filter: &PersonFilter) -> Result<u64, PersonRepositoryError> {
+        let query = self.person_filter(filter)?;
+        let collection = self.collection("person").await?;
+        self.with_read_timeout(collection.count_documents(query))
+            .await
+    }
+
+    async fn get_people_with_speech_count(
+        &self,
+        after: Option<Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<(Person, u64)>, PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let mut query = doc! {
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+        };
+        // Keyset pagination, skipping would rescan every previous document at each page.
+        if let Some(after) = after {
+            query.insert("_id", doc! { "$gt": uid_to_bson(&after) });
+        }
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(query)
+                    .sort(doc! { "_id": 1 })
+                    .limit(quantity as i64)
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let people = documents
+            .into_iter()
+            .map(Person::try_from)
+            .collect::<Result<Vec<Person>, PersonRepositoryError>>()?;
+        let uids = people
+            .iter()
+            .map(|person| uid_to_bson(person.uid()))
+            .collect::<Vec<Bson>>();
+        let speeches = self.collection("speech").await?;
+        let counts: Vec<Document> = self
+            .with_read_timeout(async {
+                speeches
+                    .aggregate([
+                        doc! { "$match": { "deleted_at": Bson::Null, "speakers.uid": { "$in": &uids } } },
+                        doc! { "$unwind": "$speakers" },
+                        doc! { "$match": { "speakers.uid": { "$in": &uids } } },
+                        doc! { "$group": { "_id": "$speakers.uid", "speech_count": { "$sum": 1 } } },
+                    ])
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let counts = counts
+            .iter()
+            .map(|count| {
+                Ok((
+                    uid_from_bson(count.get("_id"))?,
+                    integer_from_bson(count.get("speech_count"))? as u64,
+                ))
+            })
+            .collect::<Result<HashMap<Uuid, u64>, String>>()
+            .map_err(PersonRepositoryError::InternalError)?;
+        Ok(people
+            .into_iter()
+            .map(|person| {
+                let speech_count = counts.get(person.uid()).copied().unwrap_or_default();
+                (person, speech_count)
+            })
+            .collect())
+    }
+
+    async fn delete_person(
+        &self,
+        uid: &Uuid,
+        strategy: DeleteStrategy,
+    ) -> Result<(), PersonRepositoryError> {
+        let persons = self.collection("person").await?;
+        let found = self
+            .with_write_timeout(persons.count_documents(doc! {
+                "_id": uid_to_bson(uid),
+                "org_uid": organization_to_bson(self.organization),
+                "deleted_at": Bson::Null,
+            }))
+            .await?;
+        if found == 0 {
+            return Err(PersonRepositoryError::PersonNotFound);
+        }
+        // Not atomic without a replica set: the person is deleted last, so a deletion
+        // interrupted midway can be run again.
+        let speeches = self.collection("speech").await?;
+        let referencing = doc! { "$or": [
+            { "speakers.uid": uid_to_bson(uid) },
+            { "sentences.speaker": uid_to_bson(uid) },
+        ] };
+        let mut live = referencing.clone();
+        live.insert("deleted_at", Bson::Null);
+        let referenced = self
+            .with_write_timeout(speeches.count_documents(live))
+            .await?;
+        match strategy {
+            DeleteStrategy::Restrict if referenced > 0 => {
+                return Err(PersonRepositoryError::PersonReferenced(referenced));
+            }
+            DeleteStrategy::Restrict => {}
+            DeleteStrategy::Anonymize => {
+                let unknown = self.unknown_speaker_uid().await?;
+                // The unknown speaker cannot be anonymized into itself.
+                if unknown == *uid && referenced > 0 {
+                    return Err(PersonRepositoryError::PersonReferenced(referenced));
+                }
+                if unknown != *uid {
+                    self.with_write_timeout(
+                        speeches.update_many(referencing, doc! { "$inc": { "version": 1 } }),
+                    )
+                    .await?;
+                    self.move_speeches(uid, &unknown).await?;
+                }
+            }
+            DeleteStrategy::Cascade => {
+                // The sentences left keep their order, their indexes are their positions.
+                self.with_write_timeout(speeches.update_many(
+                    referencing,
+                    doc! {
+                        "$pull": {
+                            "speakers": { "uid": uid_to_bson(uid) },
+                            "sentences": { "speaker": uid_to_bson(uid) },
+                        },
+                        "$inc": { "version": 1 },
+                    },
+                ))
+                .await?;
+            }
+        }
+        self.soft_delete(uid).await
+    }
+
+    async fn restore_person(&self, uid: &Uuid) -> Result<(), PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let result = self
+            .with_write_timeout(collection.update_one(
+                doc! {
+                    "_id": uid_to_bson(uid),
+                    "org_uid": organization_to_bson(self.organization),
+                    "deleted_at": { "$ne": Bson::Null },
+                },
+                doc! { "$set": { "deleted_at": Bson::Null } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            return Err(PersonRepositoryError::PersonNotFound);
+        }
+        Ok(())
+    }
+    async fn merge_person(
+        &self,
+        uid: &Uuid,
+        duplicate: &Uuid,
+    ) -> Result<Vec<Uuid>, PersonRepositoryError> {
+        let persons = self.collection("person").await?;
+        let found = self
+            .with_write_timeout(persons.count_documents(doc! {
+                "_id": { "$in": [uid_to_bson(uid), uid_to_bson(duplicate)] },
+                "org_uid": organization_to_bson(self.organization),
+                "deleted_at": Bson::Null,
+            }))
+            .await?;
+        if found != 2 {
+            return Err(PersonRepositoryError::PersonNotFound);
+        }
+        // Not atomic without a replica set: the duplicate is deleted last, so a merge
+        // interrupted midway can be run again. The labels, stored in Postgres, stay on
+        // the duplicate.
+        let speeches = self
+            .with_write_timeout(self.collection("speech").await?.distinct(
+                "_id",
+                doc! { "$or": [
+                    { "speakers.uid": uid_to_bson(duplicate) },
+                    { "sentences.speaker": uid_to_bson(duplicate) },
+                ] },
+            ))
+            .await?
+            .iter()
+            .map(|uid| uid_from_bson(Some(uid)))
+            .collect::<Result<Vec<Uuid>, String>>()
+            .map_err(PersonRepositoryError::InternalError)?;
+        self.move_speeches(duplicate, uid).await?;
+        let lie_quantity = self
+            .with_write_timeout(persons.find_one(doc! { "_id": uid_to_bson(duplicate) }))
+            .await?
+            .map(|person| integer_from_bson(person.get("lie_quantity")))
+            .transpose()
+            .map_err(PersonRepositoryError::InternalError)?
+            .unwrap_or_default();
+        self.with_write_timeout(persons.update_one(
+            doc! { "_id": uid_to_bson(uid) },
+            doc! { "$inc": { "lie_quantity": lie_quantity, "version": 1 } },
+        ))
+        .await?;
+        self.soft_delete(duplicate).await?;
+        Ok(speeches)
+    }
+
+    async fn find_duplicate_people(
+        &self,
+        quantity: u16,
+    ) -> Result<Vec<PersonDuplicate>, PersonRepositoryError> {
+        let collection = self.collection("person").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "org_uid": organization_to_bson(self.organization),
+                        "deleted_at": Bson::Null,
+                    })
+                    .sort(doc! { "_id": 1 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        // Without the SQL self join, the persons are compared within their normalized name.
+        let mut by_name: HashMap<String, Vec<Person>> = HashMap::new();
+        for document in documents {
+            let person = Person::try_from(document)?;
+            by_name
+                .entry(normalize_name(person.name()))
+                .or_default()
+                .push(person);
+        }
+        let mut duplicates = Vec::new();
+        for people in by_name.values() {
+            for (index, person) in people.iter().enumerate() {
+                for duplicate in &people[index + 1..] {
+                    if let Some(score) = duplicate_score(person, duplicate) {
+                        duplicates.push(PersonDuplicate {
+                            person: person.clone(),
+                            duplicate: duplicate.clone(),
+                            score,
+                        });
+                    }
+                }
+            }
+        }
+        duplicates.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.person.uid().cmp(b.person.uid()))
+                .then_with(|| a.duplicate.uid().cmp(b.duplicate.uid()))
+        });
+        duplicates.truncate(quantity as usize);
+        Ok(duplicates)
+    }
+}
@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::domain::pii::{PiiDetector, PiiDetectorError, PiiFinding, PiiKind};
+
+#[derive(Deserialize)]
+struct DetectResponse {
+    /// Findings of each text, in order.
+    results: Vec<Vec<DetectFinding>>,
+}
+
+#[derive(Deserialize)]
+struct DetectFinding {
+    kind: String,
+    excerpt: String,
+}
+
+/// Detector backed by an external service, e.g. an entity recognition model, answering
+/// `POST {url}/detect` with `{"texts": [...]}` by
+/// `{"results": [[{"kind": "phone_number", "excerpt": "..."}]]}`. The kinds it may
+/// return are those of `PiiKind`, the other kinds are ignored.
+#[derive(Debug, Clone)]
+pub struct HttpPiiDetector {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl HttpPiiDetector {
+    pub fn new(url: &str, api_key: Option<&str>, timeout: u64) -> Result<Self, PiiDetectorError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout))
+            .build()
+            .map_err(|e| PiiDetectorError::ProviderError(e.to_string()))?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key: api_key.map(|k| k.to_string()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl PiiDetector for HttpPiiDetector {
+    async fn detect(&self, texts: &[String]) -> Result<Vec<Vec<PiiFinding>>, PiiDetectorError> {
+        let mut request = self
+            .client
+            .post(format!("{}/detect", self.url))
+            .json(&json!({ "texts": texts }));
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let response: DetectResponse = request
+            .send()
+            .await
+            .map_err(|e| PiiDetectorError::ProviderError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| PiiDetectorError::ProviderError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| PiiDetectorError::ProviderError(e.to_string()))?;
+        if response.results.len() != texts.len() {
+            return Err(PiiDetectorError::ProviderError(format!(
+                "{} results received for {} texts",
+                response.results.len(),
+                texts.len()
+            )));
+        }
+        Ok(response
+            .results
+            .into_iter()
+            .map(|findings| {
+                findings
+                    .into_iter()
+                    .filter_map(|finding| {
+                        Some(PiiFinding {
+                            kind: PiiKind::try_from(finding.kind.as_str()).ok()?,
+                            excerpt: finding.excerpt,
+                        })
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
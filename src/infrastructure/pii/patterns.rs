@@ -0,0 +1,163 @@
+use regex::Regex;
+
+use crate::domain::pii::{PiiDetector, PiiDetectorError, PiiFinding, PiiKind};
+
+const EMAIL_ADDRESS: &str = r"[\w.%+-]+@[\w-]+(?:\.[\w-]+)*\.\p{L}{2,}";
+
+/// Groups of digits with optional country and area codes, e.g. `06 12 34 56 78`,
+/// `+44 20 7946 0958` or `(555) 123-4567`.
+const PHONE_NUMBER: &str =
+    r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,4}\)[ .-]?)?\d{2,4}(?:[ .-]?\d{2,4}){2,4}";
+
+/// A street number followed by a French or English street name.
+const POSTAL_ADDRESS: &str = r"\b\d{1,5}(?: ?(?i:bis|ter))?,? (?:(?i:rue|avenue|boulevard|bd|chemin|allée|impasse|place|route|quai) (?:de la |de l'|du |des |de |d')?\p{Lu}[\p{L}'-]*(?: \p{Lu}[\p{L}'-]*)*|(?:\p{Lu}[\p{L}'-]* ){1,3}(?:Street|St|Avenue|Ave|Road|Rd|Boulevard|Blvd|Lane|Ln|Drive|Dr|Court|Ct|Way)\b)";
+
+/// A child named by a relative, e.g. "my daughter Emma" or "mon fils Lucas".
+const MINOR_BY_RELATION: &str = r"\b(?i:my|our|his|her|their|mon|ma|notre|son|sa|leur) (?i:son|daughter|child|grandson|granddaughter|little boy|little girl|fils|fille|petit-fils|petite-fille|enfant),? \p{Lu}\p{Ll}+";
+
+/// A name followed by an age, e.g. "Emma, 12 years old" or "Lucas, 9 ans".
+const NAME_WITH_AGE: &str = r"\b\p{Lu}\p{Ll}+,? (?:aged )?(\d{1,2}) ?(?:years old|year-old|ans)\b";
+
+/// Shortest and longest phone numbers, in digits.
+const PHONE_DIGITS: std::ops::RangeInclusive<usize> = 9..=15;
+
+/// Age under which a named person is a minor.
+const AGE_OF_MAJORITY: u32 = 18;
+
+/// Default detector, matching the usual shapes of personal data in French and English
+/// transcripts. It misses what an entity recognition service would find, such as an
+/// address spelled out or a child named without a relative.
+#[derive(Debug, Clone)]
+pub struct RegexPiiDetector {
+    email_address: Regex,
+    phone_number: Regex,
+    postal_address: Regex,
+    minor_by_relation: Regex,
+    name_with_age: Regex,
+}
+
+impl RegexPiiDetector {
+    pub fn new() -> Self {
+        Self {
+            email_address: Regex::new(EMAIL_ADDRESS).unwrap(),
+            phone_number: Regex::new(PHONE_NUMBER).unwrap(),
+            postal_address: Regex::new(POSTAL_ADDRESS).unwrap(),
+            minor_by_relation: Regex::new(MINOR_BY_RELATION).unwrap(),
+            name_with_age: Regex::new(NAME_WITH_AGE).unwrap(),
+        }
+    }
+
+    fn detect_text(&self, text: &str) -> Vec<PiiFinding> {
+        let finding = |kind, excerpt: &str| PiiFinding {
+            kind,
+            excerpt: excerpt.to_owned(),
+        };
+        let mut findings = Vec::new();
+        findings.extend(
+            self.email_address
+                .find_iter(text)
+                .map(|m| finding(PiiKind::EmailAddress, m.as_str())),
+        );
+        findings.extend(
+            self.phone_number
+                .find_iter(text)
+                .filter(|m| is_phone_number(&text[..m.start()], m.as_str()))
+                .map(|m| finding(PiiKind::PhoneNumber, m.as_str())),
+        );
+        findings.extend(
+            self.postal_address
+                .find_iter(text)
+                .map(|m| finding(PiiKind::PostalAddress, m.as_str())),
+        );
+        findings.extend(
+            self.minor_by_relation
+                .find_iter(text)
+                .map(|m| finding(PiiKind::MinorName, m.as_str())),
+        );
+        findings.extend(
+            self.name_with_age
+                .captures_iter(text)
+                .filter(|c| c[1].parse::<u32>().is_ok_and(|age| age < AGE_OF_MAJORITY))
+                .map(|c| finding(PiiKind::MinorName, &c[0])),
+        );
+        findings
+    }
+}
+
+impl Default for RegexPiiDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tells a phone number from the other numbers of a speech, such as amounts written with
+/// thousands separators (`3 000 000`) or years.
+fn is_phone_number(before: &str, candidate: &str) -> bool {
+    let digits = candidate.chars().filter(|c| c.is_ascii_digit()).count();
+    if !PHONE_DIGITS.contains(&digits) {
+        return false;
+    }
+    // The end of a longer number, e.g. the `000 000 000` of `3 000 000 000`.
+    let mut preceding = before.chars().rev();
+    if let (Some(' ' | '.' | ','), Some(c)) = (preceding.next(), preceding.next()) {
+        if c.is_ascii_digit() {
+            return false;
+        }
+    }
+    let groups = candidate
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|group| !group.is_empty())
+        .collect::<Vec<&str>>();
+    !(groups.len() > 1 && groups[1..].iter().all(|group| group.len() == 3))
+}
+
+#[async_trait::async_trait]
+impl PiiDetector for RegexPiiDetector {
+    async fn detect(&self, texts: &[String]) -> Result<Vec<Vec<PiiFinding>>, PiiDetectorError> {
+        Ok(texts.iter().map(|text| self.detect_text(text)).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RegexPiiDetector;
+    use crate::domain::pii::PiiKind;
+
+    #[test]
+    fn finds_personal_data_but_not_amounts() {
+        let detector = RegexPiiDetector::new();
+        let found = |text: &str| {
+            detector
+                .detect_text(text)
+                .into_iter()
+                .map(|f| (f.kind, f.excerpt))
+                .collect::<Vec<(PiiKind, String)>>()
+        };
+        assert_eq!(
+            found("Call me at 06 12 34 56 78 or write to jane.doe@example.org."),
+            vec![
+                (PiiKind::EmailAddress, "jane.doe@example.org".to_owned()),
+                (PiiKind::PhoneNumber, "06 12 34 56 78".to_owned()),
+            ]
+        );
+        assert_eq!(
+            found("Elle habite au 12 rue de la Paix avec ma fille Léa, 9 ans."),
+            vec![
+                (PiiKind::PostalAddress, "12 rue de la Paix".to_owned()),
+                (PiiKind::MinorName, "ma fille Léa".to_owned()),
+                (PiiKind::MinorName, "Léa, 9 ans".to_owned()),
+            ]
+        );
+        assert_eq!(
+            found("He moved to 221 Baker Street with his son, Tom."),
+            vec![
+                (PiiKind::PostalAddress, "221 Baker Street".to_owned()),
+                (PiiKind::MinorName, "his son, Tom".to_owned()),
+            ]
+        );
+        assert!(
+            found("We will invest 3 000 000 000 euros from 2024 to 2030, Paul, 45 ans.").is_empty()
+        );
+        assert!(found("The budget grew by 123 456 789 dollars.").is_empty());
+    }
+}
@@ -0,0 +1,238 @@
+use std::{sync::Arc, time::Duration};
+
+use sqlx::{postgres::PgRow, Error, PgPool, Postgres, Row, Transaction};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::segment::{check_segment, Segment, SegmentRepository, SegmentRepositoryError};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for SegmentRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::RowNotFound => Self::SegmentNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Segment {
+    type Error = SegmentRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let uid: Uuid = value.try_get("uid")?;
+        let speech_uid: Uuid = value.try_get("speech_uid")?;
+        let title: &str = value.try_get("title")?;
+        let start_index: i32 = value.try_get("start_index")?;
+        let end_index: i32 = value.try_get("end_index")?;
+        Ok(Segment::new(
+            uid,
+            speech_uid,
+            title,
+            start_index as u32,
+            end_index as u32,
+        ))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresSegmentRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresSegmentRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, SegmentRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+
+    /// Locks the speech of the segment and checks the segment against its sentences and
+    /// its other segments. The lock is held until the end of the transaction, so two
+    /// segments written concurrently cannot overlap.
+    async fn check_segment(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        segment: &Segment,
+    ) -> Result<Vec<Segment>, SegmentRepositoryError> {
+        let sentences: i64 = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "SELECT (SELECT COUNT(*) FROM sentence WHERE speech_uid = s.uid) AS sentences FROM speech s \
+                WHERE s.uid = $1 AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $2 FOR UPDATE;",
+            )
+            .bind(segment.speech_uid())
+            .bind(self.organization)
+            .fetch_optional(&mut **tx),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??
+        .ok_or(SegmentRepositoryError::SpeechNotFound)?
+        .try_get("sentences")?;
+        let segments = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "SELECT uid, speech_uid, title, start_index, end_index FROM speech_segment WHERE speech_uid = $1;",
+            )
+            .bind(segment.speech_uid())
+            .fetch_all(&mut **tx),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??
+        .into_iter()
+        .map(Segment::try_from)
+        .collect::<Result<Vec<Segment>, SegmentRepositoryError>>()?;
+        check_segment(segment, sentences as u32, &segments)?;
+        Ok(segments)
+    }
+}
+
+#[async_trait::async_trait]
+impl SegmentRepository for PostgresSegmentRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn SegmentRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn create_segment(&self, segment: &Segment) -> Result<(), SegmentRepositoryError> {
+        let connection = self.connect().await?;
+        let mut tx = connection.begin().await?;
+        self.check_segment(&mut tx, segment).await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO speech_segment (uid, speech_uid, title, start_index, end_index) VALUES ($1, $2, $3, $4, $5);",
+            )
+            .bind(segment.uid())
+            .bind(segment.speech_uid())
+            .bind(segment.title())
+            .bind(segment.start_index() as i32)
+            .bind(segment.end_index() as i32)
+            .execute(&mut *tx),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_segment(&self, segment: &Segment) -> Result<(), SegmentRepositoryError> {
+        let connection = self.connect().await?;
+        let mut tx = connection.begin().await?;
+        let segments = self.check_segment(&mut tx, segment).await?;
+        if !segments.iter().any(|s| s.uid() == segment.uid()) {
+            return Err(SegmentRepositoryError::SegmentNotFound);
+        }
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "UPDATE speech_segment SET title = $3, start_index = $4, end_index = $5 WHERE uid = $1 AND speech_uid = $2;",
+            )
+            .bind(segment.uid())
+            .bind(segment.speech_uid())
+            .bind(segment.title())
+            .bind(segment.start_index() as i32)
+            .bind(segment.end_index() as i32)
+            .execute(&mut *tx),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_segment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<(), SegmentRepositoryError> {
+        let connection = self.connect().await?;
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "DELETE FROM speech_segment g USING speech s \
+                WHERE g.uid = $1 AND g.speech_uid = $2 AND s.uid = g.speech_uid AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $3;",
+            )
+            .bind(uid)
+            .bind(speech_uid)
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(SegmentRepositoryError::SegmentNotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_segments(
+        &self,
+        speech_uid: &Uuid,
+    ) -> Result<Vec<Segment>, SegmentRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT g.uid, g.speech_uid, g.title, g.start_index, g.end_index \
+                FROM speech_segment g JOIN speech s ON s.uid = g.speech_uid \
+                WHERE g.speech_uid = $1 AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $2 \
+                ORDER BY g.start_index;",
+            )
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Segment::try_from).collect()
+    }
+
+    async fn get_segment(
+        &self,
+        speech_uid: &Uuid,
+        uid: &Uuid,
+    ) -> Result<Segment, SegmentRepositoryError> {
+        let connection = self.connect().await?;
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT g.uid, g.speech_uid, g.title, g.start_index, g.end_index \
+                FROM speech_segment g JOIN speech s ON s.uid = g.speech_uid \
+                WHERE g.uid = $1 AND g.speech_uid = $2 AND s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $3;",
+            )
+            .bind(uid)
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| SegmentRepositoryError::InternalError(timed_out(e)))??;
+        Segment::try_from(row)
+    }
+}
@@ -0,0 +1,2315 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::IntoFuture,
+    time::Duration,
+};
+
+use chrono::{NaiveDate, Utc};
+use futures_util::TryStreamExt;
+use mongodb::{
+    bson::{doc, Bson, Document, Regex},
+    error::Error,
+    options::ReturnDocument,
+    Collection,
+};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::{
+    domain::{
+        person::PersonRepositoryError,
+        speech::{
+            analytics::{
+                MonthlySpeechCount, SpeakerAnalytics, SpeakerStats, SpeechGroupCount,
+                SpeechGrouping, TimelineGranularity, TimelinePeriod,
+            },
+            cluster::SpeechCluster,
+            import::{ImportConflict, ImportResolution},
+            language::SpeechLanguage,
+            pii_flag::PiiFlag,
+            progress::ReadProgress,
+            revision::SpeechRevision,
+            sentence::{ReviewStatus, Sentence, SentenceReview, SentenceTiming},
+            slug::slugify,
+            speech_repository::{
+                SpeechDuplicate, SpeechField, SpeechFilter, SpeechIdentity, SpeechProjection,
+                SpeechRepository, SpeechRepositoryError, SpeechSummary, StoragePosition,
+                SENTENCE_PREVIEW_LENGTH,
+            },
+            statement::{Statement, StatementQuery},
+            SpeakerRole, Speech, SpeechStatus,
+        },
+        upsert::{OnConflict, UpsertOutcome},
+    },
+    infrastructure::{
+        error_metrics::timed_out,
+        mongo::{
+            connect, date_time_from_bson, date_time_to_bson, filter_conditions, integer_from_bson,
+            is_duplicate_key, organization_to_bson, record_mongo_error, uid_from_bson, uid_to_bson,
+            version_condition, version_from_bson,
+        },
+        timeouts::DatabaseTimeouts,
+    },
+};
+
+impl From<Error> for SpeechRepositoryError {
+    fn from(value: Error) -> Self {
+        record_mongo_error(&value);
+        if is_duplicate_key(&value) {
+            return Self::SpeechAlreadyExists;
+        }
+        Self::InternalError(value.to_string())
+    }
+}
+
+fn sentence_document(sentence: &Sentence) -> Document {
+    doc! {
+        "uid": uid_to_bson(sentence.uid()),
+        "speaker": uid_to_bson(sentence.speaker()),
+        "text": sentence.text(),
+        "interrupted": sentence.interrupted(),
+        "start_ms": sentence.timing().map(|t| t.start as i64),
+        "end_ms": sentence.timing().map(|t| t.end as i64),
+        "review_status": sentence.review().status.to_string(),
+        "review_comment": sentence.review().comment.as_deref(),
+        "reviewed_by": sentence.review().reviewed_by.as_deref(),
+        "reviewed_at": sentence.review().reviewed_at.as_ref().map(date_time_to_bson),
+        "language": sentence.language().map(|language| language.as_str()),
+    }
+}
+
+/// Content of the speech, stored both in the speech document and in its revisions. The
+/// sentences and the speakers are embedded so a write is atomic.
+fn speech_content(speech: &Speech) -> Document {
+    doc! {
+        "name": speech.name(),
+        "date": date_time_to_bson(speech.date()),
+        "media": speech.media(),
+        "status": speech.speech_status().to_string(),
+        "language": speech.language().map(|l| doc! {
+            "code": l.code(),
+            "confidence": l.confidence(),
+            "mixed": l.mixed(),
+        }),
+        "speakers": speech
+            .speakers()
+            .iter()
+            .map(|speaker| doc! {
+                "uid": uid_to_bson(speaker),
+                "role": speech.speaker_role(speaker).to_string(),
+            })
+            .collect::<Vec<Document>>(),
+        "sentences": speech
+            .sentences()
+            .iter()
+            .map(sentence_document)
+            .collect::<Vec<Document>>(),
+    }
+}
+
+fn sentence_from_document(value: &Document) -> Result<Sentence, SpeechRepositoryError> {
+    let uid = uid_from_bson(value.get("uid")).map_err(SpeechRepositoryError::InternalError)?;
+    let speaker =
+        uid_from_bson(value.get("speaker")).map_err(SpeechRepositoryError::InternalError)?;
+    let text = value
+        .get_str("text")
+        .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+    let interrupted = value
+        .get_bool("interrupted")
+        .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+    let timing = match (
+        integer_from_bson(value.get("start_ms")),
+        integer_from_bson(value.get("end_ms")),
+    ) {
+        (Ok(start), Ok(end)) => Some(SentenceTiming {
+            start: start as u32,
+            end: end as u32,
+        }),
+        _ => None,
+    };
+    // The sentences stored before the reviews are unreviewed.
+    let review = SentenceReview {
+        status: match value.get_str("review_status") {
+            Ok(status) => status
+                .try_into()
+                .map_err(SpeechRepositoryError::InternalError)?,
+            Err(_) => ReviewStatus::Unreviewed,
+        },
+        comment: value.get_str("review_comment").ok().map(|c| c.to_owned()),
+        reviewed_by: value.get_str("reviewed_by").ok().map(|by| by.to_owned()),
+        reviewed_at: match value.get("reviewed_at") {
+            Some(Bson::Null) | None => None,
+            at => Some(date_time_from_bson(at).map_err(SpeechRepositoryError::InternalError)?),
+        },
+    };
+    Ok(Sentence::new(&uid, &speaker, text, interrupted)
+        .with_timing(timing)
+        .with_review(review)
+        .with_language(value.get_str("language").ok().map(|l| l.to_owned())))
+}
+
+/// Reads the content of a speech document or of a revision, the sentences being left
+/// out when they are not projected.
+fn speech_from_document(uid: &Uuid, value: &Document) -> Result<Speech, SpeechRepositoryError> {
+    let internal = |e: mongodb::bson::document::ValueAccessError| {
+        SpeechRepositoryError::InternalError(e.to_string())
+    };
+    let status = value
+        .get_str("status")
+        .map_err(internal)?
+        .try_into()
+        .map_err(SpeechRepositoryError::InternalError)?;
+    let mut speakers = Vec::new();
+    let mut roles = Vec::new();
+    for speaker in value.get_array("speakers").map_err(internal)? {
+        let speaker = speaker.as_document().ok_or_else(|| {
+            SpeechRepositoryError::InternalError("Unexpected speaker value".to_owned())
+        })?;
+        let uid =
+            uid_from_bson(speaker.get("uid")).map_err(SpeechRepositoryError::InternalError)?;
+        let role: SpeakerRole = speaker
+            .get_str("role")
+            .map_err(internal)?
+            .try_into()
+            .map_err(SpeechRepositoryError::InternalError)?;
+        speakers.push(uid);
+        roles.push((uid, role));
+    }
+    let sentences = match value.get_array("sentences") {
+        Ok(sentences) => sentences
+            .iter()
+            .map(|sentence| match sentence.as_document() {
+                Some(sentence) => sentence_from_document(sentence),
+                None => Err(SpeechRepositoryError::InternalError(
+                    "Unexpected sentence value".to_owned(),
+                )),
+            })
+            .collect::<Result<Vec<Sentence>, SpeechRepositoryError>>()?,
+        Err(_) => Vec::new(),
+    };
+    let mut speech = Speech::new(
+        uid,
+        value.get_str("name").map_err(internal)?,
+        date_time_from_bson(value.get("date")).map_err(SpeechRepositoryError::InternalError)?,
+        &speakers,
+        &sentences,
+        value.get_str("media").map_err(internal)?,
+        status,
+    );
+    for (speaker, role) in roles {
+        speech.update_speaker_role(&speaker, role);
+    }
+    if let Ok(language) = value.get_document("language") {
+        speech.update_language(Some(SpeechLanguage::new(
+            language.get_str("code").map_err(internal)?,
+            language.get_f64("confidence").ok(),
+            language.get_bool("mixed").map_err(internal)?,
+        )));
+    }
+    speech.update_version(version_from_bson(value.get("version")));
+    Ok(speech)
+}
+
+fn revision_from_document(value: &Document) -> Result<SpeechRevision, SpeechRepositoryError> {
+    let uid =
+        uid_from_bson(value.get("speech_uid")).map_err(SpeechRepositoryError::InternalError)?;
+    let revision =
+        integer_from_bson(value.get("revision")).map_err(SpeechRepositoryError::InternalError)?;
+    let created_at = date_time_from_bson(value.get("created_at"))
+        .map_err(SpeechRepositoryError::InternalError)?;
+    let content = value
+        .get_document("speech")
+        .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+    Ok(SpeechRevision::new(
+        revision as u32,
+        created_at,
+        speech_from_document(&uid, content)?,
+    ))
+}
+
+fn import_conflict_document(conflict: &ImportConflict, organization: Option<Uuid>) -> Document {
+    doc! {
+        "_id": uid_to_bson(&conflict.uid),
+        "import_uid": uid_to_bson(&conflict.import_uid),
+        "position": conflict.position as i64,
+        "kind": conflict.kind.to_string(),
+        "reference": conflict.reference.map(|uid| uid_to_bson(&uid)),
+        "speech_uid": uid_to_bson(conflict.speech.uid()),
+        "speech": speech_content(&conflict.speech),
+        "resolution": conflict.resolution.map(|r| r.to_string()),
+        "resolution_uid": conflict.resolution.as_ref().and_then(|r| r.uid()).map(uid_to_bson),
+        "org_uid": organization_to_bson(organization),
+        "created_at": date_time_to_bson(&conflict.created_at),
+    }
+}
+
+fn import_conflict_from_document(
+    value: &Document,
+) -> Result<ImportConflict, SpeechRepositoryError> {
+    let internal = |e: mongodb::bson::document::ValueAccessError| {
+        SpeechRepositoryError::InternalError(e.to_string())
+    };
+    let optional_uid = |key: &str| match value.get(key) {
+        Some(Bson::Null) | None => Ok(None),
+        uid => uid_from_bson(uid).map(Some),
+    };
+    let speech_uid =
+        uid_from_bson(value.get("speech_uid")).map_err(SpeechRepositoryError::InternalError)?;
+    let resolution = match value.get_str("resolution") {
+        Ok(action) => Some(
+            ImportResolution::new(
+                action,
+                optional_uid("resolution_uid").map_err(SpeechRepositoryError::InternalError)?,
+            )
+            .map_err(SpeechRepositoryError::InternalError)?,
+        ),
+        Err(_) => None,
+    };
+    Ok(ImportConflict {
+        uid: uid_from_bson(value.get("_id")).map_err(SpeechRepositoryError::InternalError)?,
+        import_uid: uid_from_bson(value.get("import_uid"))
+            .map_err(SpeechRepositoryError::InternalError)?,
+        position: integer_from_bson(value.get("position"))
+            .map_err(SpeechRepositoryError::InternalError)? as u32,
+        kind: value
+            .get_str("kind")
+            .map_err(internal)?
+            .try_into()
+            .map_err(SpeechRepositoryError::InternalError)?,
+        reference: optional_uid("reference").map_err(SpeechRepositoryError::InternalError)?,
+        speech: speech_from_document(&speech_uid, value.get_document("speech").map_err(internal)?)?,
+        resolution,
+        created_at: date_time_from_bson(value.get("created_at"))
+            .map_err(SpeechRepositoryError::InternalError)?,
+    })
+}
+
+fn pii_flag_from_document(value: &Document) -> Result<PiiFlag, SpeechRepositoryError> {
+    let internal = |e: mongodb::bson::document::ValueAccessError| {
+        SpeechRepositoryError::InternalError(e.to_string())
+    };
+    let reviewed_at = match value.get("reviewed_at") {
+        Some(Bson::Null) | None => None,
+        at => Some(date_time_from_bson(at).map_err(SpeechRepositoryError::InternalError)?),
+    };
+    Ok(PiiFlag {
+        uid: uid_from_bson(value.get("_id")).map_err(SpeechRepositoryError::InternalError)?,
+        sentence_index: integer_from_bson(value.get("sentence_index"))
+            .map_err(SpeechRepositoryError::InternalError)? as u32,
+        kind: value
+            .get_str("kind")
+            .map_err(internal)?
+            .try_into()
+            .map_err(SpeechRepositoryError::InternalError)?,
+        excerpt: value.get_str("excerpt").map_err(internal)?.to_owned(),
+        created_at: date_time_from_bson(value.get("created_at"))
+            .map_err(SpeechRepositoryError::InternalError)?,
+        reviewed_by: value.get_str("reviewed_by").ok().map(|by| by.to_owned()),
+        reviewed_at,
+    })
+}
+
+fn word_count(text: &str) -> u64 {
+    text.split_whitespace().count() as u64
+}
+
+/// Computes the talk-time aggregates of the speakers of the speech, as the Postgres
+/// repository does in SQL.
+fn speaker_analytics(speech: &Speech, include_moderators: bool) -> Vec<SpeakerAnalytics> {
+    let sentences = speech.sentences();
+    // An interrupted sentence is interrupted by the speaker of the next sentence. The
+    // interruptions made by a moderator are still counted for the other speakers.
+    let interruptions = sentences
+        .windows(2)
+        .filter(|pair| pair[0].interrupted() && pair[1].speaker() != pair[0].speaker())
+        .map(|pair| (*pair[0].speaker(), *pair[1].speaker()))
+        .collect::<Vec<(Uuid, Uuid)>>();
+    let mut speakers = Vec::new();
+    for speaker in sentences
+        .iter()
+        .map(|s| s.speaker())
+        .chain(speech.speakers().iter())
+    {
+        let excluded = !include_moderators
+            && speech.speakers().contains(speaker)
+            && speech.speaker_role(speaker) == SpeakerRole::Moderator;
+        if !excluded && !speakers.contains(speaker) {
+            speakers.push(*speaker);
+        }
+    }
+    let totals = speakers
+        .iter()
+        .map(|speaker| {
+            let said = sentences
+                .iter()
+                .filter(|s| s.speaker() == speaker)
+                .collect::<Vec<&Sentence>>();
+            let duration = said
+                .iter()
+                .filter_map(|s| s.timing())
+                .map(|t| t.duration() as u64)
+                .reduce(|a, b| a + b);
+            (
+                *speaker,
+                said.len() as u64,
+                said.iter().map(|s| word_count(s.text())).sum::<u64>(),
+                duration,
+            )
+        })
+        .collect::<Vec<(Uuid, u64, u64, Option<u64>)>>();
+    let total_words = totals.iter().map(|(_, _, words, _)| words).sum::<u64>();
+    let total_duration = totals
+        .iter()
+        .filter_map(|(_, _, _, duration)| *duration)
+        .sum::<u64>();
+    let mut analytics = totals
+        .into_iter()
+        .map(|(speaker, said, words, duration)| {
+            let share = if total_words == 0 {
+                0.0
+            } else {
+                words as f64 / total_words as f64
+            };
+            let duration_share = duration
+                .filter(|_| total_duration > 0)
+                .map(|duration| duration as f64 / total_duration as f64);
+            SpeakerAnalytics::new(
+                speaker,
+                said,
+                words,
+                share,
+                interruptions
+                    .iter()
+                    .filter(|(_, by)| *by == speaker)
+                    .count() as u64,
+                interruptions
+                    .iter()
+                    .filter(|(of, _)| *of == speaker)
+                    .count() as u64,
+            )
+            .with_spoken_duration(duration, duration_share)
+        })
+        .collect::<Vec<SpeakerAnalytics>>();
+    analytics.sort_by(|a, b| {
+        b.words()
+            .cmp(&a.words())
+            .then(b.sentences().cmp(&a.sentences()))
+    });
+    analytics
+}
+
+/// Speeches, with their sentences and speakers, stored in the `speech` collection of a
+/// MongoDB (or DocumentDB) database.
+#[derive(Debug, Clone)]
+pub struct MongoSpeechRepository {
+    url: String,
+    database: String,
+    timeouts: DatabaseTimeouts,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+    /// Whether the reads only reach the validated speeches.
+    validated_only: bool,
+}
+
+impl MongoSpeechRepository {
+    pub fn new(url: &str, database: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            database: database.to_string(),
+            timeouts,
+            organization: None,
+            validated_only: false,
+        }
+    }
+
+    async fn collection(&self, name: &str) -> Result<Collection<Document>, SpeechRepositoryError> {
+        let database = connect(&self.url, &self.database, self.timeouts.read)
+            .await
+            .map_err(SpeechRepositoryError::InternalError)?;
+        Ok(database.collection(name))
+    }
+
+    /// Applies the read timeout to a query.
+    async fn with_read_timeout<T>(
+        &self,
+        query: impl IntoFuture<Output = Result<T, Error>>,
+    ) -> Result<T, SpeechRepositoryError> {
+        time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            query.into_future(),
+        )
+        .await
+        .map_err(|e| SpeechRepositoryError::InternalError(timed_out(e)))?
+        .map_err(|e| e.into())
+    }
+
+    /// Applies the write timeout to a query.
+    async fn with_write_timeout<T>(
+        &self,
+        query: impl IntoFuture<Output = Result<T, Error>>,
+    ) -> Result<T, SpeechRepositoryError> {
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            query.into_future(),
+        )
+        .await
+        .map_err(|e| SpeechRepositoryError::InternalError(timed_out(e)))?
+        .map_err(|e| e.into())
+    }
+
+    /// Inserts the personal data flags of the speech. The excerpts already flagged
+    /// conflict with their flag on the unique index, the other flags are still inserted.
+    async fn insert_pii_flags(
+        &self,
+        uid: Uuid,
+        flags: &[PiiFlag],
+    ) -> Result<(), SpeechRepositoryError> {
+        if flags.is_empty() {
+            return Ok(());
+        }
+        let collection = self.collection("pii_flag").await?;
+        let documents = flags
+            .iter()
+            .map(|flag| {
+                doc! {
+                    "_id": uid_to_bson(&flag.uid),
+                    "speech_uid": uid_to_bson(&uid),
+                    "sentence_index": flag.sentence_index as i64,
+                    "kind": flag.kind.to_string(),
+                    "excerpt": &flag.excerpt,
+                    "created_at": date_time_to_bson(&flag.created_at),
+                    "reviewed_by": Bson::Null,
+                    "reviewed_at": Bson::Null,
+                }
+            })
+            .collect::<Vec<Document>>();
+        match self
+            .with_write_timeout(collection.insert_many(documents).ordered(false))
+            .await
+        {
+            Ok(_) | Err(SpeechRepositoryError::SpeechAlreadyExists) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replaces the personal data flags of the speech waiting for review, when the
+    /// sentences were screened. The flags of an excerpt already reviewed are left out.
+    async fn replace_pii_flags(
+        &self,
+        uid: Uuid,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<(), SpeechRepositoryError> {
+        let flags = match flags {
+            Some(flags) => flags,
+            None => return Ok(()),
+        };
+        let collection = self.collection("pii_flag").await?;
+        self.with_write_timeout(collection.delete_many(doc! {
+            "speech_uid": uid_to_bson(&uid),
+            "reviewed_at": Bson::Null,
+        }))
+        .await?;
+        self.insert_pii_flags(uid, flags).await
+    }
+
+    /// Matches the speech when it is visible to the organization.
+    fn speech_query(&self, uid: &Uuid) -> Document {
+        let mut query = doc! {
+            "_id": uid_to_bson(uid),
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+        };
+        if self.validated_only {
+            query.insert("status", SpeechStatus::Validated.to_string());
+        }
+        query
+    }
+
+    /// Version of the speech, read to tell a missing speech from a stale version once an
+    /// update matched nothing.
+    async fn stored_version(&self, uid: &Uuid) -> Result<u32, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let speech = self
+            .with_write_timeout(
+                collection
+                    .find_one(self.speech_query(uid))
+                    .projection(doc! { "version": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        Ok(version_from_bson(speech.get("version")))
+    }
+
+    /// Clusters of the speech_cluster documents matching the query, largest first, without
+    /// the deleted speeches.
+    async fn speech_clusters(
+        &self,
+        query: Document,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<SpeechCluster>, SpeechRepositoryError> {
+        let collection = self.collection("speech_cluster").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .aggregate([
+                        doc! { "$match": query },
+                        doc! { "$lookup": {
+                            "from": "speech",
+                            "localField": "_id",
+                            "foreignField": "_id",
+                            "as": "speech",
+                        } },
+                        doc! { "$match": { "speech": { "$elemMatch": { "deleted_at": Bson::Null } } } },
+                        doc! { "$sort": { "_id": 1 } },
+                        doc! { "$group": {
+                            "_id": "$cluster_uid",
+                            "speeches": { "$push": "$_id" },
+                            "count": { "$sum": 1 },
+                        } },
+                        doc! { "$match": { "count": { "$gt": 1 } } },
+                        doc! { "$sort": { "count": -1, "_id": 1 } },
+                        doc! { "$skip": page as i64 * quantity as i64 },
+                        doc! { "$limit": quantity as i64 },
+                    ])
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents
+            .iter()
+            .map(|document| {
+                let uid = uid_from_bson(document.get("_id"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                let speeches = document
+                    .get_array("speeches")
+                    .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?
+                    .iter()
+                    .map(|speech| {
+                        uid_from_bson(Some(speech)).map_err(SpeechRepositoryError::InternalError)
+                    })
+                    .collect::<Result<Vec<Uuid>, SpeechRepositoryError>>()?;
+                Ok(SpeechCluster { uid, speeches })
+            })
+            .collect()
+    }
+
+    /// Builds the query matching the filter, shared by the list and count queries so
+    /// they always agree.
+    fn speech_filter(&self, filter: &SpeechFilter) -> Result<Document, SpeechRepositoryError> {
+        // The labels and the tags are stored in Postgres, they cannot be joined to the
+        // documents.
+        if !filter.labels.is_empty() || !filter.tags.is_empty() {
+            return Err(SpeechRepositoryError::InternalError(
+                "Filtering speeches by label or tag is not supported by the MongoDB backend"
+                    .to_owned(),
+            ));
+        }
+        let mut query = doc! { "org_uid": organization_to_bson(self.organization) };
+        if self.validated_only {
+            query.insert("status", SpeechStatus::Validated.to_string());
+        }
+        if !filter.include_deleted {
+            query.insert("deleted_at", Bson::Null);
+        }
+        if !filter.speakers.is_empty() || filter.role.is_some() {
+            let mut speaker = Document::new();
+            if !filter.speakers.is_empty() {
+                speaker.insert(
+                    "uid",
+                    doc! { "$in": filter.speakers.iter().map(uid_to_bson).collect::<Vec<Bson>>() },
+                );
+            }
+            if let Some(role) = filter.role {
+                speaker.insert("role", role.to_string());
+            }
+            query.insert("speakers", doc! { "$elemMatch": speaker });
+        }
+        if !filter.media.is_empty() {
+            query.insert("media", doc! { "$in": filter.media.clone() });
+        }
+        let mut conditions = filter_conditions(
+            &filter.spec,
+            |field| match field {
+                SpeechField::Name => "name",
+                SpeechField::Media => "media",
+                SpeechField::Date => "date",
+                SpeechField::Status => "status",
+                SpeechField::Language => "language.code",
+            },
+            true,
+        );
+        if !filter.keywords.is_empty() {
+            let keywords = filter
+                .keywords
+                .iter()
+                .flat_map(|keyword| {
+                    let pattern = Regex {
+                        pattern: regex::escape(keyword),
+                        options: "i".to_owned(),
+                    };
+                    [
+                        doc! { "name": pattern.clone() },
+                        doc! { "sentences.text": pattern },
+                    ]
+                })
+                .collect::<Vec<Document>>();
+            conditions.push(doc! { "$or": keywords });
+        }
+        if let Some(language) = &filter.language {
+            conditions.push(doc! { "$or": [
+                { "language.code": language },
+                { "sentences.language": language },
+            ] });
+        }
+        if let Some(position) = &filter.stored_after {
+            // The position of a speech is the time it was stored at, in milliseconds. A
+            // write lasting at most the write timeout, the speeches stored before the
+            // timeout elapsed are all listed.
+            let after = Bson::DateTime(mongodb::bson::DateTime::from_millis(position.transaction));
+            let settled = Utc::now() - Duration::from_millis(self.timeouts.write);
+            conditions.push(doc! { "$or": [
+                { "created_at": { "$gt": after.clone() } },
+                { "created_at": after, "_id": { "$gt": uid_to_bson(&position.speech) } },
+            ] });
+            conditions.push(doc! { "created_at": { "$lt": date_time_to_bson(&settled) } });
+        }
+        if !conditions.is_empty() {
+            query.insert("$and", conditions);
+        }
+        Ok(query)
+    }
+
+    /// Checks that the persons of the speech exist, a speech may only reference the persons
+    /// of its own organization which are not deleted.
+    async fn check_speech_persons(&self, speech: &Speech) -> Result<(), SpeechRepositoryError> {
+        let mut persons = speech
+            .speakers()
+            .iter()
+            .chain(speech.sentences().iter().map(|s| s.speaker()))
+            .copied()
+            .collect::<Vec<Uuid>>();
+        persons.sort();
+        persons.dedup();
+        let collection = self.collection("person").await?;
+        let found = self
+            .with_write_timeout(collection.count_documents(doc! {
+                "_id": { "$in": persons.iter().map(uid_to_bson).collect::<Vec<Bson>>() },
+                "org_uid": organization_to_bson(self.organization),
+                "deleted_at": Bson::Null,
+            }))
+            .await?;
+        if found as usize != persons.len() {
+            return Err(SpeechRepositoryError::PersonError(
+                PersonRepositoryError::PersonNotFound,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks the person may speak in the speeches of the organization.
+    async fn check_speaker_person(&self, person: &Uuid) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("person").await?;
+        self.with_write_timeout(
+            collection
+                .find_one(doc! {
+                    "_id": uid_to_bson(person),
+                    "org_uid": organization_to_bson(self.organization),
+                    "deleted_at": Bson::Null,
+                })
+                .projection(doc! { "_id": 1 }),
+        )
+        .await?
+        .ok_or(SpeechRepositoryError::PersonError(
+            PersonRepositoryError::PersonNotFound,
+        ))?;
+        Ok(())
+    }
+
+    /// Records the saved content of the speech as its next revision.
+    async fn insert_speech_revision(&self, speech: &Speech) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech_revision").await?;
+        let last = self
+            .with_write_timeout(
+                collection
+                    .find_one(doc! { "speech_uid": uid_to_bson(speech.uid()) })
+                    .sort(doc! { "revision": -1 })
+                    .projection(doc! { "revision": 1 }),
+            )
+            .await?;
+        let revision = match last {
+            Some(last) => {
+                integer_from_bson(last.get("revision"))
+                    .map_err(SpeechRepositoryError::InternalError)?
+                    + 1
+            }
+            None => 1,
+        };
+        // Two concurrent writes cannot record the same revision, the unique index rejects
+        // the second one.
+        self.with_write_timeout(collection.insert_one(doc! {
+            "speech_uid": uid_to_bson(speech.uid()),
+            "revision": revision,
+            "created_at": mongodb::bson::DateTime::now(),
+            "speech": speech_content(speech),
+        }))
+        .await?;
+        Ok(())
+    }
+
+    /// Makes the slug built from the first speaker and the name of the speech the current
+    /// slug of the speech. Former slugs are kept so they still resolve after a rename.
+    async fn assign_speech_slug(&self, speech: &Speech) -> Result<(), SpeechRepositoryError> {
+        let speaker = match speech.speakers().first() {
+            Some(speaker) => {
+                let persons = self.collection("person").await?;
+                self.with_write_timeout(persons.find_one(doc! { "_id": uid_to_bson(speaker) }))
+                    .await?
+                    .map(|person| {
+                        format!(
+                            "{} {}",
+                            person.get_str("first_name").unwrap_or_default(),
+                            person.get_str("name").unwrap_or_default()
+                        )
+                    })
+            }
+            None => None,
+        };
+        let mut base = slugify(&format!(
+            "{} {}",
+            speaker.unwrap_or_default(),
+            speech.name()
+        ));
+        if base.is_empty() {
+            base = "speech".to_owned();
+        }
+        let uid = *speech.uid();
+        let collection = self.collection("speech_slug").await?;
+        let mut slug = base.clone();
+        let mut suffix = 1;
+        // Another speech may already use (or have used) the slug.
+        while let Some(found) = self
+            .with_write_timeout(collection.find_one(doc! { "_id": &slug }))
+            .await?
+        {
+            if uid_from_bson(found.get("speech_uid"))
+                .map_err(SpeechRepositoryError::InternalError)?
+                == uid
+            {
+                break;
+            }
+            suffix += 1;
+            slug = format!("{}-{}", base, suffix);
+        }
+        self.with_write_timeout(collection.update_many(
+            doc! { "speech_uid": uid_to_bson(&uid), "_id": { "$ne": &slug } },
+            doc! { "$set": { "current": false } },
+        ))
+        .await?;
+        self.with_write_timeout(
+            collection
+                .update_one(
+                    doc! { "_id": &slug },
+                    doc! { "$set": { "speech_uid": uid_to_bson(&uid), "current": true } },
+                )
+                .upsert(true),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl SpeechRepository for MongoSpeechRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn SpeechRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    fn validated_only(&self) -> Box<dyn SpeechRepository> {
+        Box::new(Self {
+            validated_only: true,
+            ..self.clone()
+        })
+    }
+
+    async fn create_speech(
+        &self,
+        speech: &Speech,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.check_speech_persons(speech).await?;
+        let collection = self.collection("speech").await?;
+        let mut document = doc! {
+            "_id": uid_to_bson(speech.uid()),
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+            "created_at": date_time_to_bson(&Utc::now()),
+            "version": 1,
+        };
+        document.extend(speech_content(speech));
+        self.with_write_timeout(collection.insert_one(document))
+            .await?;
+        self.insert_pii_flags(*speech.uid(), flags.unwrap_or_default())
+            .await?;
+        self.insert_speech_revision(speech).await?;
+        self.assign_speech_slug(speech).await?;
+        Ok(())
+    }
+
+    async fn upsert_speech(
+        &self,
+        speech: &Speech,
+        on_conflict: OnConflict,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<UpsertOutcome, SpeechRepositoryError> {
+        self.check_speech_persons(speech).await?;
+        let collection = self.collection("speech").await?;
+        let identity = doc! {
+            "name": speech.name(),
+            "date": date_time_to_bson(speech.date()),
+            "media": speech.media(),
+            "org_uid": organization_to_bson(self.organization),
+        };
+        let mut document = doc! {
+            "_id": uid_to_bson(speech.uid()),
+            "deleted_at": Bson::Null,
+            "created_at": date_time_to_bson(&Utc::now()),
+            "version": 1,
+        };
+        document.extend(speech_content(speech));
+        // Inserts the speech only when no speech has its name, date and media.
+        let upserted = match self
+            .with_write_timeout(
+                collection
+                    .update_one(identity.clone(), doc! { "$setOnInsert": document })
+                    .upsert(true),
+            )
+            .await
+        {
+            Ok(result) => result.upserted_id.is_some(),
+            // A concurrent creation of the same speech was first.
+            Err(SpeechRepositoryError::SpeechAlreadyExists) => false,
+            Err(e) => return Err(e),
+        };
+        if upserted {
+            self.insert_pii_flags(*speech.uid(), flags.unwrap_or_default())
+                .await?;
+            self.insert_speech_revision(speech).await?;
+            self.assign_speech_slug(speech).await?;
+            return Ok(UpsertOutcome::Created(*speech.uid()));
+        }
+        let stored = self
+            .with_write_timeout(
+                collection
+                    .find_one(identity)
+                    .projection(doc! { "_id": 1, "status": 1, "deleted_at": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let uid = uid_from_bson(stored.get("_id")).map_err(SpeechRepositoryError::InternalError)?;
+        let deleted = !matches!(stored.get("deleted_at"), Some(Bson::Null) | None);
+        if on_conflict == OnConflict::Ignore || deleted {
+            return Ok(UpsertOutcome::Skipped(uid));
+        }
+        // The content replaces the content of the stored speech, under its uid and status.
+        let mut replacement = speech.clone();
+        replacement.update_uid(&uid);
+        if let Ok(status) = stored.get_str("status") {
+            replacement.update_speech_status(
+                status
+                    .try_into()
+                    .map_err(SpeechRepositoryError::InternalError)?,
+            );
+        }
+        let mut content = speech_content(&replacement);
+        content.remove("status");
+        let result = self
+            .with_write_timeout(collection.update_one(
+                doc! { "_id": uid_to_bson(&uid), "deleted_at": Bson::Null },
+                doc! { "$set": content, "$inc": { "version": 1 } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            return Ok(UpsertOutcome::Skipped(uid));
+        }
+        self.replace_pii_flags(uid, flags).await?;
+        self.insert_speech_revision(&replacement).await?;
+        self.assign_speech_slug(&replacement).await?;
+        Ok(UpsertOutcome::Updated(uid))
+    }
+
+    async fn update_speech(
+        &self,
+        speech: &Speech,
+        expected_version: Option<u32>,
+        flags: Option<&[PiiFlag]>,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.check_speech_persons(speech).await?;
+        let collection = self.collection("speech").await?;
+        let mut query = self.speech_query(speech.uid());
+        if let Some(version) = expected_version {
+            query.insert("version", version_condition(version));
+        }
+        // The translations of the former sentences go with them.
+        let result = self
+            .with_write_timeout(collection.update_one(
+                query,
+                doc! { "$set": speech_content(speech), "$inc": { "version": 1 } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            self.stored_version(speech.uid()).await?;
+            return Err(SpeechRepositoryError::VersionMismatch);
+        }
+        self.replace_pii_flags(*speech.uid(), flags).await?;
+        self.insert_speech_revision(speech).await?;
+        self.assign_speech_slug(speech).await?;
+        Ok(())
+    }
+
+    async fn get_speech_by_id(&self, uid: Uuid) -> Result<Speech, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let document = self
+            .with_read_timeout(
+                collection
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "sentences.translations": 0 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        speech_from_document(&uid, &document)
+    }
+
+    async fn get_speech_page(
+        &self,
+        uid: Uuid,
+        page: u16,
+        quantity: u16,
+        review_status: Option<ReviewStatus>,
+    ) -> Result<(Speech, u64), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let sentences = match review_status {
+            Some(status) => doc! { "$filter": {
+                "input": { "$ifNull": ["$sentences", []] },
+                "as": "sentence",
+                "cond": { "$eq": [
+                    { "$ifNull": ["$$sentence.review_status", ReviewStatus::Unreviewed.to_string()] },
+                    status.to_string(),
+                ] },
+            } },
+            None => doc! { "$ifNull": ["$sentences", []] },
+        };
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .aggregate([
+                        doc! { "$match": self.speech_query(&uid) },
+                        doc! { "$addFields": { "sentences": sentences } },
+                        doc! { "$addFields": {
+                            "total_sentences": { "$size": { "$ifNull": ["$sentences", []] } },
+                            "sentences": { "$slice": [
+                                { "$ifNull": ["$sentences", []] },
+                                page as i64 * quantity as i64,
+                                quantity.max(1) as i64,
+                            ] },
+                        } },
+                        doc! { "$project": { "sentences.translations": 0 } },
+                    ])
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let document = documents
+            .first()
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let total_sentences = integer_from_bson(document.get("total_sentences"))
+            .map_err(SpeechRepositoryError::InternalError)?;
+        Ok((
+            speech_from_document(&uid, document)?,
+            total_sentences as u64,
+        ))
+    }
+
+    async fn speech_exists(&self, uid: Uuid) -> Result<bool, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        Ok(self
+            .with_read_timeout(
+                collection
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "_id": 1 }),
+            )
+            .await?
+            .is_some())
+    }
+
+    async fn get_unknown_speakers(
+        &self,
+        speakers: &[Uuid],
+    ) -> Result<Vec<Uuid>, SpeechRepositoryError> {
+        let collection = self.collection("person").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "_id": { "$in": speakers.iter().map(uid_to_bson).collect::<Vec<Bson>>() },
+                        "org_uid": organization_to_bson(self.organization),
+                        "deleted_at": Bson::Null,
+                    })
+                    .projection(doc! { "_id": 1 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let found = documents
+            .iter()
+            .map(|person| uid_from_bson(person.get("_id")))
+            .collect::<Result<Vec<Uuid>, String>>()
+            .map_err(SpeechRepositoryError::InternalError)?;
+        Ok(speakers
+            .iter()
+            .filter(|speaker| !found.contains(speaker))
+            .copied()
+            .collect())
+    }
+
+    async fn resolve_speech_slug(
+        &self,
+        slug: &str,
+    ) -> Result<(Uuid, String), SpeechRepositoryError> {
+        let slugs = self.collection("speech_slug").await?;
+        let found = self
+            .with_read_timeout(slugs.find_one(doc! { "_id": slug }))
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let uid =
+            uid_from_bson(found.get("speech_uid")).map_err(SpeechRepositoryError::InternalError)?;
+        let speeches = self.collection("speech").await?;
+        self.with_read_timeout(
+            speeches
+                .find_one(self.speech_query(&uid))
+                .projection(doc! { "_id": 1 }),
+        )
+        .await?
+        .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let current = self
+            .with_read_timeout(
+                slugs.find_one(doc! { "speech_uid": uid_to_bson(&uid), "current": true }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let current = current
+            .get_str("_id")
+            .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+        Ok((uid, current.to_owned()))
+    }
+
+    async fn get_speech(
+        &self,
+        page: u16,
+        quantity: u16,
+        filter: &SpeechFilter,
+        projection: SpeechProjection,
+    ) -> Result<Vec<SpeechSummary>, SpeechRepositoryError> {
+        let query = self.speech_filter(filter)?;
+        let collection = self.collection("speech").await?;
+        let mut pipeline = vec![
+            doc! { "$match": query },
+            // Same order as the Postgres repository, the uid breaking the ties.
+            match filter.stored_after {
+                Some(_) => doc! { "$sort": { "created_at": 1, "_id": 1 } },
+                None => doc! { "$sort": { "date": -1, "_id": 1 } },
+            },
+            doc! { "$skip": page as i64 * quantity as i64 },
+            doc! { "$limit": quantity as i64 },
+        ];
+        // The speakers are stored in the speech document, they cost no more to read.
+        if projection.sentence_summary {
+            pipeline.push(doc! { "$addFields": {
+                "sentence_count": { "$size": { "$ifNull": ["$sentences", []] } },
+                "preview": { "$arrayElemAt": ["$sentences.text", 0] },
+            } });
+        }
+        pipeline.push(doc! { "$project": { "sentences": 0 } });
+        let documents: Vec<Document> = self
+            .with_read_timeout(async { collection.aggregate(pipeline).await?.try_collect().await })
+            .await?;
+        documents
+            .iter()
+            .map(|document| {
+                let uid = uid_from_bson(document.get("_id"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                let sentence_count = if projection.sentence_summary {
+                    integer_from_bson(document.get("sentence_count"))
+                        .map_err(SpeechRepositoryError::InternalError)?
+                } else {
+                    0
+                };
+                let preview = document.get_str("preview").ok().map(|text| {
+                    text.chars()
+                        .take(SENTENCE_PREVIEW_LENGTH)
+                        .collect::<String>()
+                });
+                let created_at = date_time_from_bson(document.get("created_at"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                Ok(SpeechSummary {
+                    speech: speech_from_document(&uid, document)?,
+                    sentence_count: sentence_count as u32,
+                    preview,
+                    position: StoragePosition {
+                        transaction: created_at.timestamp_millis(),
+                        speech: uid,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    async fn count_speech(&self, filter: &SpeechFilter) -> Result<u64, SpeechRepositoryError> {
+        let query = self.speech_filter(filter)?;
+        let collection = self.collection("speech").await?;
+        self.with_read_timeout(collection.count_documents(query))
+            .await
+    }
+
+    async fn count_speech_by_group(
+        &self,
+        filter: &SpeechFilter,
+        grouping: SpeechGrouping,
+    ) -> Result<Vec<SpeechGroupCount>, SpeechRepositoryError> {
+        let query = self.speech_filter(filter)?;
+        let collection = self.collection("speech").await?;
+        let mut pipeline = vec![doc! { "$match": query }];
+        let key = match grouping {
+            SpeechGrouping::Media => Bson::from("$media"),
+            SpeechGrouping::Speaker => {
+                pipeline.push(doc! { "$unwind": "$speakers" });
+                Bson::from("$speakers.uid")
+            }
+            SpeechGrouping::Month => {
+                Bson::from(doc! { "$dateToString": { "format": "%Y-%m", "date": "$date" } })
+            }
+        };
+        pipeline.push(doc! { "$group": { "_id": key, "speeches": { "$sum": 1 } } });
+        pipeline.push(match grouping {
+            SpeechGrouping::Month => doc! { "$sort": { "_id": 1 } },
+            _ => doc! { "$sort": { "speeches": -1, "_id": 1 } },
+        });
+        let documents: Vec<Document> = self
+            .with_read_timeout(async { collection.aggregate(pipeline).await?.try_collect().await })
+            .await?;
+        documents
+            .iter()
+            .map(|document| {
+                let key = match grouping {
+                    SpeechGrouping::Speaker => uid_from_bson(document.get("_id"))
+                        .map_err(SpeechRepositoryError::InternalError)?
+                        .to_string(),
+                    _ => document.get_str("_id").unwrap_or_default().to_owned(),
+                };
+                let speeches = integer_from_bson(document.get("speeches"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                Ok(SpeechGroupCount::new(&key, speeches as u64))
+            })
+            .collect()
+    }
+
+    async fn set_read_progress(
+        &self,
+        user_id: &str,
+        uid: Uuid,
+        sentence_index: u32,
+    ) -> Result<(), SpeechRepositoryError> {
+        let speeches = self.collection("speech").await?;
+        let speech = self
+            .with_write_timeout(
+                speeches
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "sentences.uid": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let sentences = speech
+            .get_array("sentences")
+            .map(|sentences| sentences.len())
+            .unwrap_or_default();
+        if sentence_index as usize >= sentences {
+            return Err(SpeechRepositoryError::SentenceNotFound);
+        }
+        let collection = self.collection("speech_read_progress").await?;
+        self.with_write_timeout(
+            collection
+                .update_one(
+                    doc! { "user_id": user_id, "speech_uid": uid_to_bson(&uid) },
+                    doc! {
+                        "$set": { "sentence_index": sentence_index as i64 },
+                        "$currentDate": { "updated_at": true },
+                    },
+                )
+                .upsert(true),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_read_progress(
+        &self,
+        user_id: &str,
+        uids: &[Uuid],
+    ) -> Result<HashMap<Uuid, ReadProgress>, SpeechRepositoryError> {
+        let collection = self.collection("speech_read_progress").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "user_id": user_id,
+                        "speech_uid": { "$in": uids.iter().map(uid_to_bson).collect::<Vec<Bson>>() },
+                    })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents
+            .iter()
+            .map(|document| {
+                Ok((
+                    uid_from_bson(document.get("speech_uid"))?,
+                    ReadProgress::new(
+                        integer_from_bson(document.get("sentence_index"))? as u32,
+                        date_time_from_bson(document.get("updated_at"))?,
+                    ),
+                ))
+            })
+            .collect::<Result<HashMap<Uuid, ReadProgress>, String>>()
+            .map_err(SpeechRepositoryError::InternalError)
+    }
+
+    async fn find_duplicate_speeches(
+        &self,
+        identities: &[SpeechIdentity],
+    ) -> Result<Vec<SpeechDuplicate>, SpeechRepositoryError> {
+        if identities.is_empty() {
+            return Ok(Vec::new());
+        }
+        let collection = self.collection("speech").await?;
+        // Matches the unique index of the speech collection, at most one speech per identity.
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "org_uid": organization_to_bson(self.organization),
+                        "deleted_at": Bson::Null,
+                        "$or": identities
+                            .iter()
+                            .map(|identity| doc! {
+                                "name": &identity.name,
+                                "date": date_time_to_bson(&identity.date),
+                                "media": &identity.media,
+                            })
+                            .collect::<Vec<Document>>(),
+                    })
+                    .projection(doc! { "name": 1, "date": 1, "media": 1 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        identities
+            .iter()
+            .map(|identity| {
+                let found = documents.iter().find(|document| {
+                    document.get_str("name") == Ok(identity.name.as_str())
+                        && document.get_str("media") == Ok(identity.media.as_str())
+                        && date_time_from_bson(document.get("date"))
+                            .is_ok_and(|date| date == identity.date)
+                });
+                let uid = found
+                    .map(|found| uid_from_bson(found.get("_id")))
+                    .transpose()
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                Ok(SpeechDuplicate {
+                    exists: uid.is_some(),
+                    uid,
+                })
+            })
+            .collect()
+    }
+
+    async fn save_import_conflicts(
+        &self,
+        conflicts: &[ImportConflict],
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("import_conflict").await?;
+        for conflict in conflicts {
+            self.with_write_timeout(
+                collection
+                    .replace_one(
+                        doc! { "_id": uid_to_bson(&conflict.uid) },
+                        import_conflict_document(conflict, self.organization),
+                    )
+                    .upsert(true),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_import_conflicts(
+        &self,
+        import_uid: Uuid,
+    ) -> Result<Vec<ImportConflict>, SpeechRepositoryError> {
+        let collection = self.collection("import_conflict").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "import_uid": uid_to_bson(&import_uid),
+                        "org_uid": organization_to_bson(self.organization),
+                    })
+                    .sort(doc! { "position": 1 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents
+            .iter()
+            .map(import_conflict_from_document)
+            .collect()
+    }
+
+    async fn get_import_conflict(
+        &self,
+        import_uid: Uuid,
+        uid: Uuid,
+    ) -> Result<ImportConflict, SpeechRepositoryError> {
+        let collection = self.collection("import_conflict").await?;
+        let document = self
+            .with_read_timeout(collection.find_one(doc! {
+                "_id": uid_to_bson(&uid),
+                "import_uid": uid_to_bson(&import_uid),
+                "org_uid": organization_to_bson(self.organization),
+            }))
+            .await?
+            .ok_or(SpeechRepositoryError::ImportConflictNotFound)?;
+        import_conflict_from_document(&document)
+    }
+
+    async fn delete_import_conflicts(&self, uids: &[Uuid]) -> Result<(), SpeechRepositoryError> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        let collection = self.collection("import_conflict").await?;
+        self.with_write_timeout(collection.delete_many(doc! {
+            "_id": { "$in": uids.iter().map(uid_to_bson).collect::<Vec<Bson>>() },
+            "org_uid": organization_to_bson(self.organization),
+        }))
+        .await?;
+        Ok(())
+    }
+
+    async fn get_pii_flags(&self, uid: Uuid) -> Result<Vec<PiiFlag>, SpeechRepositoryError> {
+        if !self.speech_exists(uid).await? {
+            return Err(SpeechRepositoryError::SpeechNotFound);
+        }
+        let collection = self.collection("pii_flag").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! { "speech_uid": uid_to_bson(&uid) })
+                    .sort(doc! { "sentence_index": 1, "kind": 1, "excerpt": 1 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents.iter().map(pii_flag_from_document).collect()
+    }
+
+    async fn review_pii_flag(
+        &self,
+        uid: Uuid,
+        flag: Uuid,
+        reviewed_by: &str,
+    ) -> Result<PiiFlag, SpeechRepositoryError> {
+        if !self.speech_exists(uid).await? {
+            return Err(SpeechRepositoryError::PiiFlagNotFound);
+        }
+        let collection = self.collection("pii_flag").await?;
+        let document = self
+            .with_write_timeout(
+                collection
+                    .find_one_and_update(
+                        doc! { "_id": uid_to_bson(&flag), "speech_uid": uid_to_bson(&uid) },
+                        doc! {
+                            "$set": { "reviewed_by": reviewed_by },
+                            "$currentDate": { "reviewed_at": true },
+                        },
+                    )
+                    .return_document(ReturnDocument::After),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::PiiFlagNotFound)?;
+        pii_flag_from_document(&document)
+    }
+
+    async fn review_sentence(
+        &self,
+        uid: Uuid,
+        sentence: Uuid,
+        review: &SentenceReview,
+    ) -> Result<Sentence, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let mut query = self.speech_query(&uid);
+        query.insert("sentences.uid", uid_to_bson(&sentence));
+        let document = self
+            .with_write_timeout(
+                collection
+                    .find_one_and_update(
+                        query,
+                        doc! { "$set": {
+                            "sentences.$.review_status": review.status.to_string(),
+                            "sentences.$.review_comment": review.comment.as_deref(),
+                            "sentences.$.reviewed_by": review.reviewed_by.as_deref(),
+                            "sentences.$.reviewed_at": review.reviewed_at.as_ref().map(date_time_to_bson),
+                        } },
+                    )
+                    .projection(doc! {
+                        "sentences": { "$elemMatch": { "uid": uid_to_bson(&sentence) } },
+                    })
+                    .return_document(ReturnDocument::After),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SentenceNotFound)?;
+        match document
+            .get_array("sentences")
+            .ok()
+            .and_then(|sentences| sentences.first())
+            .and_then(|sentence| sentence.as_document())
+        {
+            Some(sentence) => sentence_from_document(sentence),
+            None => Err(SpeechRepositoryError::SentenceNotFound),
+        }
+    }
+
+    async fn delete_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let result = self
+            .with_write_timeout(collection.update_one(
+                self.speech_query(&uid),
+                doc! { "$currentDate": { "deleted_at": true } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            return Err(SpeechRepositoryError::SpeechNotFound);
+        }
+        Ok(())
+    }
+
+    async fn restore_speech(&self, uid: Uuid) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let result = self
+            .with_write_timeout(collection.update_one(
+                doc! {
+                    "_id": uid_to_bson(&uid),
+                    "org_uid": organization_to_bson(self.organization),
+                    "deleted_at": { "$ne": Bson::Null },
+                },
+                doc! { "$set": { "deleted_at": Bson::Null } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            return Err(SpeechRepositoryError::SpeechNotFound);
+        }
+        Ok(())
+    }
+
+    async fn update_speech_status(
+        &self,
+        uid: Uuid,
+        status: SpeechStatus,
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let result = self
+            .with_write_timeout(collection.update_one(
+                self.speech_query(&uid),
+                doc! { "$set": { "status": status.to_string() }, "$inc": { "version": 1 } },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            return Err(SpeechRepositoryError::SpeechNotFound);
+        }
+        Ok(())
+    }
+
+    async fn update_speaker_role(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+        role: SpeakerRole,
+        expected_version: Option<u32>,
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let mut query = self.speech_query(&uid);
+        query.insert("speakers.uid", uid_to_bson(&speaker));
+        if let Some(version) = expected_version {
+            query.insert("version", version_condition(version));
+        }
+        let result = self
+            .with_write_timeout(collection.update_one(
+                query,
+                doc! {
+                    "$set": { "speakers.$.role": role.to_string() },
+                    "$inc": { "version": 1 },
+                },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            // Telling a missing speech or a stale version from a missing speaker.
+            let version = self.stored_version(&uid).await?;
+            if expected_version.is_some_and(|expected| expected != version) {
+                return Err(SpeechRepositoryError::VersionMismatch);
+            }
+            return Err(SpeechRepositoryError::SpeakerNotFound);
+        }
+        Ok(())
+    }
+
+    async fn add_speaker(
+        &self,
+        uid: Uuid,
+        speaker: Uuid,
+        role: SpeakerRole,
+    ) -> Result<(), SpeechRepositoryError> {
+        self.check_speaker_person(&speaker).await?;
+        let collection = self.collection("speech").await?;
+        let mut query = self.speech_query(&uid);
+        query.insert("speakers.uid", doc! { "$ne": uid_to_bson(&speaker) });
+        let result = self
+            .with_write_timeout(collection.update_one(
+                query,
+                doc! {
+                    "$push": { "speakers": {
+                        "uid": uid_to_bson(&speaker),
+                        "role": role.to_string(),
+                    } },
+                    "$inc": { "version": 1 },
+                },
+            ))
+            .await?;
+        if result.matched_count == 0 {
+            // Telling a missing speech from a speaker already added.
+            self.with_write_timeout(
+                collection
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "_id": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+            return Err(SpeechRepositoryError::SpeakerAlreadyExists);
+        }
+        Ok(())
+    }
+
+    async fn remove_speaker(&self, uid: Uuid, speaker: Uuid) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let mut query = self.speech_query(&uid);
+        query.insert("speakers.uid", doc! { "$eq": uid_to_bson(&speaker) });
+        query.insert("sentences.speaker", doc! { "$ne": uid_to_bson(&speaker) });
+        let result = self
+            .with_write_timeout(collection.update_one(
+                query,
+                doc! {
+                    "$pull": { "speakers": { "uid": uid_to_bson(&speaker) } },
+                    "$inc": { "version": 1 },
+                },
+            ))
+            .await?;
+        if result.matched_count > 0 {
+            return Ok(());
+        }
+        let speech = self
+            .with_write_timeout(
+                collection
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "speakers.uid": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let is_speaker = speech.get_array("speakers").is_ok_and(|speakers| {
+            speakers.iter().any(|s| {
+                s.as_document()
+                    .is_some_and(|s| uid_from_bson(s.get("uid")) == Ok(speaker))
+            })
+        });
+        if !is_speaker {
+            return Err(SpeechRepositoryError::SpeakerNotFound);
+        }
+        Err(SpeechRepositoryError::SpeakerHasSentences)
+    }
+
+    async fn reassign_sentences(
+        &self,
+        uid: Uuid,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<u64, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let speech = self
+            .with_write_timeout(
+                collection
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "speakers.uid": 1, "sentences.speaker": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        let speakers = speech
+            .get_array("speakers")
+            .map(|speakers| {
+                speakers
+                    .iter()
+                    .filter_map(|s| s.as_document())
+                    .filter_map(|s| uid_from_bson(s.get("uid")).ok())
+                    .collect::<Vec<Uuid>>()
+            })
+            .unwrap_or_default();
+        if !speakers.contains(&from) {
+            return Err(SpeechRepositoryError::SpeakerNotFound);
+        }
+        if from == to {
+            return Ok(0);
+        }
+        self.check_speaker_person(&to).await?;
+        let reassigned = speech
+            .get_array("sentences")
+            .map(|sentences| {
+                sentences
+                    .iter()
+                    .filter_map(|s| s.as_document())
+                    .filter(|s| uid_from_bson(s.get("speaker")) == Ok(from))
+                    .count()
+            })
+            .unwrap_or_default();
+        // The speech document is written at once, matching the speakers read keeps the
+        // speakers and the sentences consistent with a concurrent change.
+        let mut query = self.speech_query(&uid);
+        query.insert("speakers.uid", doc! { "$eq": uid_to_bson(&from) });
+        let mut update = doc! {
+            "$set": { "sentences.$[s].speaker": uid_to_bson(&to) },
+            "$inc": { "version": 1 },
+        };
+        if speakers.contains(&to) {
+            query.insert("$and", vec![doc! { "speakers.uid": uid_to_bson(&to) }]);
+        } else {
+            query.insert(
+                "$and",
+                vec![doc! { "speakers.uid": { "$ne": uid_to_bson(&to) } }],
+            );
+            update.insert(
+                "$push",
+                doc! { "speakers": {
+                    "uid": uid_to_bson(&to),
+                    "role": SpeakerRole::Panelist.to_string(),
+                } },
+            );
+        }
+        let result = self
+            .with_write_timeout(
+                collection
+                    .update_one(query, update)
+                    .array_filters(vec![doc! { "s.speaker": uid_to_bson(&from) }]),
+            )
+            .await?;
+        if result.matched_count == 0 {
+            return Err(SpeechRepositoryError::InternalError(
+                "The speakers of the speech changed during the reassignment".to_owned(),
+            ));
+        }
+        Ok(reassigned as u64)
+    }
+
+    async fn append_sentences(
+        &self,
+        uid: Uuid,
+        first_index: u32,
+        sentences: &[Sentence],
+        flags: &[PiiFlag],
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        // Matching the number of sentences so a concurrent write cannot reorder them.
+        let mut query = self.speech_query(&uid);
+        query.insert("status", SpeechStatus::Live.to_string());
+        query.insert("sentences", doc! { "$size": first_index as i64 });
+        let result = self
+            .with_write_timeout(collection.update_one(
+                query,
+                doc! {
+                    "$push": { "sentences": {
+                        "$each": sentences.iter().map(sentence_document).collect::<Vec<Document>>(),
+                    } },
+                    "$inc": { "version": 1 },
+                },
+            ))
+            .await?;
+        if result.matched_count > 0 {
+            return self.insert_pii_flags(uid, flags).await;
+        }
+        let speech = self
+            .with_write_timeout(
+                collection
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "status": 1, "sentences.uid": 1 }),
+            )
+            .await?
+            .ok_or(SpeechRepositoryError::SpeechNotFound)?;
+        if speech.get_str("status") != Ok(SpeechStatus::Live.to_string().as_str()) {
+            return Err(SpeechRepositoryError::SpeechNotLive);
+        }
+        let stored = speech
+            .get_array("sentences")
+            .map(|sentences| sentences.len())
+            .unwrap_or_default();
+        Err(SpeechRepositoryError::SentenceOutOfOrder(stored as u32))
+    }
+
+    async fn finish_speech_ingestion(
+        &self,
+        uid: Uuid,
+        language: Option<&SpeechLanguage>,
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let mut query = self.speech_query(&uid);
+        query.insert("status", SpeechStatus::Live.to_string());
+        let mut set = doc! { "status": SpeechStatus::Pending.to_string() };
+        if let Some(language) = language {
+            set.insert(
+                "language",
+                doc! {
+                    "code": language.code(),
+                    "confidence": language.confidence(),
+                    "mixed": language.mixed(),
+                },
+            );
+        }
+        let result = self
+            .with_write_timeout(
+                collection.update_one(query, doc! { "$set": set, "$inc": { "version": 1 } }),
+            )
+            .await?;
+        if result.matched_count > 0 {
+            return Ok(());
+        }
+        match self.speech_exists(uid).await? {
+            true => Err(SpeechRepositoryError::SpeechNotLive),
+            false => Err(SpeechRepositoryError::SpeechNotFound),
+        }
+    }
+
+    async fn get_speech_revisions(
+        &self,
+        uid: Uuid,
+    ) -> Result<Vec<SpeechRevision>, SpeechRepositoryError> {
+        let speeches = self.collection("speech").await?;
+        if self
+            .with_read_timeout(
+                speeches
+                    .find_one(self.speech_query(&uid))
+                    .projection(doc! { "_id": 1 }),
+            )
+            .await?
+            .is_none()
+        {
+            return Ok(Vec::new());
+        }
+        let collection = self.collection("speech_revision").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! { "speech_uid": uid_to_bson(&uid) })
+                    .sort(doc! { "revision": 1 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents.iter().map(revision_from_document).collect()
+    }
+
+    async fn get_speech_revision(
+        &self,
+        uid: Uuid,
+        revision: u32,
+    ) -> Result<SpeechRevision, SpeechRepositoryError> {
+        let speeches = self.collection("speech").await?;
+        self.with_read_timeout(
+            speeches
+                .find_one(self.speech_query(&uid))
+                .projection(doc! { "_id": 1 }),
+        )
+        .await?
+        .ok_or(SpeechRepositoryError::RevisionNotFound)?;
+        let collection = self.collection("speech_revision").await?;
+        let document = self
+            .with_read_timeout(collection.find_one(doc! {
+                "speech_uid": uid_to_bson(&uid),
+                "revision": revision as i64,
+            }))
+            .await?
+            .ok_or(SpeechRepositoryError::RevisionNotFound)?;
+        revision_from_document(&document)
+    }
+
+    async fn get_sentence_translations(
+        &self,
+        speech_uid: Uuid,
+        language: &str,
+    ) -> Result<HashMap<Uuid, String>, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let translation = format!("sentences.translations.{}", language);
+        let speech = self
+            .with_read_timeout(
+                collection
+                    .find_one(doc! {
+                        "_id": uid_to_bson(&speech_uid),
+                        "org_uid": organization_to_bson(self.organization),
+                    })
+                    .projection(doc! { "sentences.uid": 1, &translation: 1 }),
+            )
+            .await?;
+        let mut translations = HashMap::new();
+        let sentences = match &speech {
+            Some(speech) => speech.get_array("sentences").ok(),
+            None => None,
+        };
+        for sentence in sentences.into_iter().flatten() {
+            let sentence = match sentence.as_document() {
+                Some(sentence) => sentence,
+                None => continue,
+            };
+            if let Some(text) = sentence
+                .get_document("translations")
+                .ok()
+                .and_then(|t| t.get_str(language).ok())
+            {
+                translations.insert(
+                    uid_from_bson(sentence.get("uid"))
+                        .map_err(SpeechRepositoryError::InternalError)?,
+                    text.to_owned(),
+                );
+            }
+        }
+        Ok(translations)
+    }
+
+    async fn save_sentence_translations(
+        &self,
+        language: &str,
+        translations: &[(Uuid, String)],
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let translation = format!("sentences.$.translations.{}", language);
+        // Sentences replaced by a concurrent update are skipped.
+        for (uid, text) in translations {
+            self.with_write_timeout(collection.update_one(
+                doc! {
+                    "sentences.uid": uid_to_bson(uid),
+                    "org_uid": organization_to_bson(self.organization),
+                },
+                doc! { "$set": { &translation: text } },
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_speech_analytics(
+        &self,
+        uid: Uuid,
+        include_moderators: bool,
+    ) -> Result<Vec<SpeakerAnalytics>, SpeechRepositoryError> {
+        let speech = self.get_speech_by_id(uid).await?;
+        Ok(speaker_analytics(&speech, include_moderators))
+    }
+
+    async fn get_speaker_stats(
+        &self,
+        person_uid: Uuid,
+        include_moderators: bool,
+    ) -> Result<SpeakerStats, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let person = uid_to_bson(&person_uid);
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "org_uid": organization_to_bson(self.organization),
+                        "deleted_at": Bson::Null,
+                        "$or": [{ "speakers.uid": &person }, { "sentences.speaker": &person }],
+                    })
+                    .projection(doc! { "sentences.translations": 0 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let (mut speeches, mut sentences, mut words, mut duration) = (0, 0, 0, None);
+        for document in &documents {
+            let uid =
+                uid_from_bson(document.get("_id")).map_err(SpeechRepositoryError::InternalError)?;
+            let speech = speech_from_document(&uid, document)?;
+            let speaks = speech.speakers().contains(&person_uid);
+            // The speeches the person moderates are left out unless asked.
+            if speaks
+                && speech.speaker_role(&person_uid) == SpeakerRole::Moderator
+                && !include_moderators
+            {
+                continue;
+            }
+            if speaks {
+                speeches += 1;
+            }
+            for sentence in speech
+                .sentences()
+                .iter()
+                .filter(|s| *s.speaker() == person_uid)
+            {
+                sentences += 1;
+                words += word_count(sentence.text());
+                if let Some(timing) = sentence.timing() {
+                    duration = Some(duration.unwrap_or_default() + timing.duration() as u64);
+                }
+            }
+        }
+        Ok(SpeakerStats::new(speeches, sentences, words, duration))
+    }
+
+    async fn get_speaker_timeline(
+        &self,
+        person_uid: Uuid,
+        granularity: TimelineGranularity,
+        include_moderators: bool,
+    ) -> Result<Vec<TimelinePeriod>, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let person = uid_to_bson(&person_uid);
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(doc! {
+                        "org_uid": organization_to_bson(self.organization),
+                        "deleted_at": Bson::Null,
+                        "$or": [{ "speakers.uid": &person }, { "sentences.speaker": &person }],
+                    })
+                    .projection(doc! { "sentences.translations": 0 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        // Ordered by the start of the periods.
+        let mut periods: BTreeMap<NaiveDate, (u64, u64)> = BTreeMap::new();
+        for document in &documents {
+            let uid =
+                uid_from_bson(document.get("_id")).map_err(SpeechRepositoryError::InternalError)?;
+            let speech = speech_from_document(&uid, document)?;
+            let speaks = speech.speakers().contains(&person_uid);
+            // The speeches the person moderates are left out unless asked.
+            if speaks
+                && speech.speaker_role(&person_uid) == SpeakerRole::Moderator
+                && !include_moderators
+            {
+                continue;
+            }
+            let sentences = speech
+                .sentences()
+                .iter()
+                .filter(|s| *s.speaker() == person_uid)
+                .count() as u64;
+            let period = periods
+                .entry(granularity.period_start(speech.date().date_naive()))
+                .or_default();
+            if speaks {
+                period.0 += 1;
+            }
+            period.1 += sentences;
+        }
+        Ok(periods
+            .into_iter()
+            .map(|(start, (speeches, sentences))| TimelinePeriod::new(start, speeches, sentences))
+            .collect())
+    }
+
+    async fn search_statements(
+        &self,
+        person_uid: Uuid,
+        query: &StatementQuery,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<Statement>, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let person = uid_to_bson(&person_uid);
+        let pattern = Regex {
+            pattern: regex::escape(&query.text),
+            options: "i".to_owned(),
+        };
+        let mut filter = doc! {
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+            "speakers.uid": &person,
+            "sentences": { "$elemMatch": { "speaker": &person, "text": pattern } },
+        };
+        if self.validated_only {
+            filter.insert("status", SpeechStatus::Validated.to_string());
+        }
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(filter)
+                    .projection(doc! { "sentences.translations": 0 })
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        let mut speeches = documents
+            .iter()
+            .map(|document| {
+                let uid = uid_from_bson(document.get("_id"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                speech_from_document(&uid, document)
+            })
+            .collect::<Result<Vec<Speech>, SpeechRepositoryError>>()?;
+        // Same order as the Postgres repository.
+        speeches.sort_by(|a, b| b.date().cmp(a.date()).then_with(|| a.uid().cmp(b.uid())));
+        let text = query.text.to_lowercase();
+        Ok(speeches
+            .iter()
+            .filter(|speech| query.covers(speech.date().date_naive()))
+            .flat_map(|speech| {
+                speech
+                    .sentences()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, sentence)| {
+                        *sentence.speaker() == person_uid
+                            && sentence.text().to_lowercase().contains(&text)
+                            && query.language.as_ref().is_none_or(|language| {
+                                speech.sentence_language(sentence) == Some(language)
+                            })
+                    })
+                    .map(|(index, sentence)| {
+                        Statement::new(
+                            sentence.clone(),
+                            index as u32,
+                            *speech.uid(),
+                            speech.name(),
+                            *speech.date(),
+                            speech.media(),
+                            speech.speaker_role(&person_uid),
+                        )
+                    })
+            })
+            .skip(page as usize * quantity as usize)
+            .take(quantity as usize)
+            .collect())
+    }
+
+    async fn count_speech_by_media_month(
+        &self,
+    ) -> Result<Vec<MonthlySpeechCount>, SpeechRepositoryError> {
+        let collection = self.collection("speech").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .aggregate([
+                        doc! { "$match": {
+                            "org_uid": organization_to_bson(self.organization),
+                            "deleted_at": Bson::Null,
+                        } },
+                        doc! { "$group": {
+                            "_id": {
+                                "media": "$media",
+                                "month": { "$dateToString": { "format": "%Y-%m-01", "date": "$date" } },
+                            },
+                            "speeches": { "$sum": 1 },
+                        } },
+                        doc! { "$sort": { "_id.month": 1, "_id.media": 1 } },
+                    ])
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents
+            .iter()
+            .map(|document| {
+                let group = document
+                    .get_document("_id")
+                    .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+                let media = group
+                    .get_str("media")
+                    .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+                let month = group
+                    .get_str("month")
+                    .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?
+                    .parse::<NaiveDate>()
+                    .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?;
+                let speeches = integer_from_bson(document.get("speeches"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                Ok(MonthlySpeechCount::new(media, month, speeches as u64))
+            })
+            .collect()
+    }
+
+    async fn get_speech_texts(
+        &self,
+        after: Option<Uuid>,
+        quantity: u16,
+    ) -> Result<Vec<(Uuid, String)>, SpeechRepositoryError> {
+        let mut query = doc! {
+            "org_uid": organization_to_bson(self.organization),
+            "deleted_at": Bson::Null,
+            "sentences.0": { "$exists": true },
+        };
+        if let Some(after) = after {
+            query.insert("_id", doc! { "$gt": uid_to_bson(&after) });
+        }
+        let collection = self.collection("speech").await?;
+        let documents: Vec<Document> = self
+            .with_read_timeout(async {
+                collection
+                    .find(query)
+                    .projection(doc! { "sentences.text": 1 })
+                    .sort(doc! { "_id": 1 })
+                    .limit(quantity as i64)
+                    .await?
+                    .try_collect()
+                    .await
+            })
+            .await?;
+        documents
+            .iter()
+            .map(|document| {
+                let uid = uid_from_bson(document.get("_id"))
+                    .map_err(SpeechRepositoryError::InternalError)?;
+                let text = document
+                    .get_array("sentences")
+                    .map_err(|e| SpeechRepositoryError::InternalError(e.to_string()))?
+                    .iter()
+                    .filter_map(|sentence| sentence.as_document()?.get_str("text").ok())
+                    .collect::<Vec<&str>>()
+                    .join(" ");
+                Ok((uid, text))
+            })
+            .collect()
+    }
+
+    async fn replace_speech_clusters(
+        &self,
+        clusters: &[SpeechCluster],
+    ) -> Result<(), SpeechRepositoryError> {
+        let collection = self.collection("speech_cluster").await?;
+        // Not atomic without a replica set, the clusters are missing until inserted again.
+        self.with_write_timeout(collection.delete_many(doc! {
+            "org_uid": organization_to_bson(self.organization),
+        }))
+        .await?;
+        let documents = clusters
+            .iter()
+            .flat_map(|cluster| {
+                cluster.speeches.iter().map(|speech| {
+                    doc! {
+                        "_id": uid_to_bson(speech),
+                        "cluster_uid": uid_to_bson(&cluster.uid),
+                        "org_uid": organization_to_bson(self.organization),
+                        "computed_at": date_time_to_bson(&Utc::now()),
+                    }
+                })
+            })
+            .collect::<Vec<Document>>();
+        if !documents.is_empty() {
+            self.with_write_timeout(collection.insert_many(documents))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn get_speech_cluster(
+        &self,
+        uid: Uuid,
+    ) -> Result<Option<SpeechCluster>, SpeechRepositoryError> {
+        if !self.speech_exists(uid).await? {
+            return Err(SpeechRepositoryError::SpeechNotFound);
+        }
+        let collection = self.collection("speech_cluster").await?;
+        let cluster_uid = match self
+            .with_read_timeout(collection.find_one(doc! { "_id": uid_to_bson(&uid) }))
+            .await?
+        {
+            Some(document) => document.get("cluster_uid").cloned().unwrap_or(Bson::Null),
+            None => return Ok(None),
+        };
+        let clusters = self
+            .speech_clusters(doc! { "cluster_uid": cluster_uid }, 0, 1)
+            .await?;
+        Ok(clusters.into_iter().next())
+    }
+
+    async fn get_speech_clusters(
+        &self,
+        page: u16,
+        quantity: u16,
+    ) -> Result<Vec<SpeechCluster>, SpeechRepositoryError> {
+        self.speech_clusters(
+            doc! { "org_uid": organization_to_bson(self.organization) },
+            page,
+            quantity,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use crate::domain::speech::{sentence::Sentence, SpeakerRole, Speech, SpeechStatus};
+
+    use super::speaker_analytics;
+
+    #[test]
+    fn speaker_analytics_leaves_moderators_out() {
+        let moderator = Uuid::new_v4();
+        let panelist = Uuid::new_v4();
+        let sentences = vec![
+            Sentence::new(&Uuid::new_v4(), &moderator, "Bonsoir à tous", false),
+            Sentence::new(&Uuid::new_v4(), &panelist, "Je voulais dire que", true),
+            Sentence::new(&Uuid::new_v4(), &moderator, "Merci", false),
+        ];
+        let mut speech = Speech::new(
+            &Uuid::new_v4(),
+            "Débat",
+            Utc::now(),
+            &[moderator, panelist],
+            &sentences,
+            "TF1",
+            SpeechStatus::Validated,
+        );
+        speech.update_speaker_role(&moderator, SpeakerRole::Moderator);
+        let analytics = speaker_analytics(&speech, false);
+        assert_eq!(analytics.len(), 1);
+        assert_eq!(*analytics[0].speaker(), panelist);
+        assert_eq!(analytics[0].words(), 4);
+        assert_eq!(analytics[0].share(), 1.0);
+        assert_eq!(analytics[0].interruptions_received(), 1);
+        let analytics = speaker_analytics(&speech, true);
+        assert_eq!(*analytics[0].speaker(), moderator);
+        assert_eq!(analytics[0].interruptions_made(), 1);
+    }
+}
@@ -0,0 +1,291 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::NaiveDate;
+use sqlx::{Error, PgPool, Row};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::{
+    speech::SpeechStatus,
+    stats::{ActiveSpeaker, DailyCount, StatsRepository, StatsRepositoryError},
+};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for StatsRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        Self::InternalError(value.to_string())
+    }
+}
+
+/// Aggregates of the Postgres database.
+#[derive(Debug, Clone)]
+pub struct PostgresStatsRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresStatsRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, StatsRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| StatsRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+
+    /// Runs a query returning a single count, the organization being bound to `$1`.
+    async fn count(&self, query: &str) -> Result<u64, StatsRepositoryError> {
+        let connection = self.connect().await?;
+        let count: i64 = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query_scalar(query)
+                .bind(self.organization)
+                .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| StatsRepositoryError::InternalError(timed_out(e)))??;
+        Ok(count as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl StatsRepository for PostgresStatsRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn StatsRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn count_persons(&self) -> Result<u64, StatsRepositoryError> {
+        self.count("SELECT COUNT(*) FROM person WHERE deleted_at IS NULL AND org_uid IS NOT DISTINCT FROM $1;")
+            .await
+    }
+
+    async fn count_speeches_by_status(
+        &self,
+    ) -> Result<Vec<(SpeechStatus, u64)>, StatsRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT status, COUNT(*) AS speeches FROM speech WHERE deleted_at IS NULL AND org_uid IS NOT DISTINCT FROM $1 GROUP BY status ORDER BY status;",
+            )
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| StatsRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter()
+            .map(|row| {
+                let status: &str = row.try_get("status")?;
+                let speeches: i64 = row.try_get("speeches")?;
+                Ok((
+                    SpeechStatus::try_from(status).map_err(StatsRepositoryError::InternalError)?,
+                    speeches as u64,
+                ))
+            })
+            .collect()
+    }
+
+    async fn count_sentences(&self) -> Result<u64, StatsRepositoryError> {
+        self.count(
+            "SELECT COUNT(*) FROM sentence se JOIN speech s ON s.uid = se.speech_uid WHERE s.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $1;",
+        )
+        .await
+    }
+
+    async fn count_speeches_per_day(
+        &self,
+        first: NaiveDate,
+    ) -> Result<Vec<DailyCount>, StatsRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT (created_at AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS speeches FROM speech \
+                WHERE deleted_at IS NULL AND org_uid IS NOT DISTINCT FROM $2 AND created_at >= $1::DATE::TIMESTAMP AT TIME ZONE 'UTC' GROUP BY day ORDER BY day;",
+            )
+            .bind(first)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| StatsRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter()
+            .map(|row| {
+                let speeches: i64 = row.try_get("speeches")?;
+                Ok(DailyCount {
+                    day: row.try_get("day")?,
+                    count: speeches as u64,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_top_speakers(
+        &self,
+        quantity: u32,
+    ) -> Result<Vec<ActiveSpeaker>, StatsRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT p.uid, p.name, p.first_name, COUNT(DISTINCT se.speech_uid) AS speeches, COUNT(*) AS sentences \
+                FROM sentence se JOIN speech s ON s.uid = se.speech_uid JOIN person p ON p.uid = se.speaker \
+                WHERE s.deleted_at IS NULL AND p.deleted_at IS NULL AND s.org_uid IS NOT DISTINCT FROM $2 \
+                GROUP BY p.uid, p.name, p.first_name ORDER BY sentences DESC, speeches DESC, p.uid LIMIT $1;",
+            )
+            .bind(quantity as i64)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| StatsRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter()
+            .map(|row| {
+                let speeches: i64 = row.try_get("speeches")?;
+                let sentences: i64 = row.try_get("sentences")?;
+                Ok(ActiveSpeaker {
+                    uid: row.try_get("uid")?,
+                    name: row.try_get("name")?,
+                    first_name: row.try_get("first_name")?,
+                    speeches: speeches as u64,
+                    sentences: sentences as u64,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::PostgresStatsRepository;
+    use crate::{
+        domain::{
+            organization::{Organization, OrganizationRepository},
+            person::PersonRepository,
+            speech::{speech_repository::SpeechRepository, SpeechStatus},
+            stats::StatsRepository,
+        },
+        infrastructure::organization::postgres::repository::PostgresOrganizationRepository,
+        test_support::{test_database, PersonBuilder, SpeechBuilder},
+    };
+
+    #[tokio::test]
+    async fn test_postgres_stats() {
+        let database = test_database().await;
+        let repository = PostgresStatsRepository::new(database.url(), database.timeouts());
+        let talkative = database.create_person(PersonBuilder::new()).await;
+        let quiet = database.create_person(PersonBuilder::new()).await;
+        database
+            .create_speech(
+                SpeechBuilder::new()
+                    .with_sentence(talkative.uid(), "Les retraites.")
+                    .with_sentence(quiet.uid(), "Les salaires.")
+                    .with_sentence(talkative.uid(), "Les impôts."),
+            )
+            .await;
+        database
+            .create_speech(
+                SpeechBuilder::new()
+                    .with_status(SpeechStatus::Validated)
+                    .with_sentence(talkative.uid(), "La santé."),
+            )
+            .await;
+        assert_eq!(repository.count_persons().await, Ok(2));
+        assert_eq!(
+            repository.count_speeches_by_status().await,
+            Ok(vec![
+                (SpeechStatus::Pending, 1),
+                (SpeechStatus::Validated, 1)
+            ])
+        );
+        assert_eq!(repository.count_sentences().await, Ok(4));
+        let top_speakers = repository.get_top_speakers(1).await.unwrap();
+        assert_eq!(top_speakers.len(), 1);
+        assert_eq!(top_speakers[0].uid, *talkative.uid());
+        assert_eq!(top_speakers[0].speeches, 2);
+        assert_eq!(top_speakers[0].sentences, 3);
+    }
+
+    #[tokio::test]
+    async fn test_postgres_stats_of_organization() {
+        let database = test_database().await;
+        let organization = Organization::new(Uuid::new_v4(), "Newsroom", Utc::now());
+        PostgresOrganizationRepository::new(database.url(), database.timeouts())
+            .create_organization(&organization)
+            .await
+            .unwrap();
+        let organization = Some(*organization.uid());
+        let speaker = database.create_person(PersonBuilder::new()).await;
+        database
+            .create_speech(SpeechBuilder::new().with_sentence(speaker.uid(), "Les retraites."))
+            .await;
+        let other_speaker = PersonBuilder::new().build();
+        database
+            .person_repository()
+            .for_organization(organization)
+            .create_person(&other_speaker)
+            .await
+            .unwrap();
+        database
+            .speech_repository()
+            .for_organization(organization)
+            .create_speech(
+                &SpeechBuilder::new()
+                    .with_status(SpeechStatus::Validated)
+                    .with_sentence(other_speaker.uid(), "Les salaires.")
+                    .with_sentence(other_speaker.uid(), "Les impôts.")
+                    .build(),
+                None,
+            )
+            .await
+            .unwrap();
+        let repository = PostgresStatsRepository::new(database.url(), database.timeouts());
+        let organization_stats = repository.for_organization(organization);
+        assert_eq!(repository.count_persons().await, Ok(1));
+        assert_eq!(organization_stats.count_persons().await, Ok(1));
+        assert_eq!(
+            repository.count_speeches_by_status().await,
+            Ok(vec![(SpeechStatus::Pending, 1)])
+        );
+        assert_eq!(
+            organization_stats.count_speeches_by_status().await,
+            Ok(vec![(SpeechStatus::Validated, 1)])
+        );
+        assert_eq!(repository.count_sentences().await, Ok(1));
+        assert_eq!(organization_stats.count_sentences().await, Ok(2));
+        let first = Utc::now().date_naive();
+        assert_eq!(
+            repository.count_speeches_per_day(first).await.unwrap()[0].count,
+            1
+        );
+        let top_speakers = organization_stats.get_top_speakers(10).await.unwrap();
+        assert_eq!(top_speakers.len(), 1);
+        assert_eq!(top_speakers[0].uid, *other_speaker.uid());
+    }
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use sqlx::{postgres::PgRow, Error, PgPool, Row};
+use tokio::time;
+use uuid::Uuid;
+
+use crate::domain::tag::{Tag, TagRepository, TagRepositoryError};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for TagRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::Database(database_error) => {
+                if database_error.is_unique_violation() {
+                    return Self::TagAlreadyExists;
+                }
+                Self::InternalError(database_error.to_string())
+            }
+            Error::RowNotFound => Self::TagNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Tag {
+    type Error = TagRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let uid: Uuid = value.try_get("uid")?;
+        let name: &str = value.try_get("name")?;
+        let parent: Option<Uuid> = value.try_get("parent_uid")?;
+        Ok(Tag::new(uid, name, parent))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresTagRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresTagRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            organization: None,
+        }
+    }
+
+    async fn connect(&self) -> Result<PgPool, TagRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            PgPool::connect(&self.url),
+        )
+        .await
+        .map_err(|e| TagRepositoryError::InternalError(timed_out(e)))??)
+    }
+}
+
+#[async_trait::async_trait]
+impl TagRepository for PostgresTagRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn TagRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn create_tag(&self, tag: &Tag) -> Result<(), TagRepositoryError> {
+        let connection = self.connect().await?;
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO tag (uid, name, parent_uid, org_uid) SELECT $1, $2, $3, $4 \
+                WHERE $3::UUID IS NULL OR EXISTS (SELECT 1 FROM tag WHERE uid = $3 AND org_uid IS NOT DISTINCT FROM $4);",
+            )
+            .bind(tag.uid())
+            .bind(tag.name())
+            .bind(tag.parent())
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| TagRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(TagRepositoryError::ParentTagNotFound);
+        }
+        Ok(())
+    }
+
+    async fn get_tags(&self) -> Result<Vec<Tag>, TagRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT uid, name, parent_uid FROM tag WHERE org_uid IS NOT DISTINCT FROM $1 ORDER BY name;",
+            )
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| TagRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Tag::try_from).collect()
+    }
+
+    async fn get_speech_tags(&self, speech_uid: &Uuid) -> Result<Vec<Tag>, TagRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT t.uid, t.name, t.parent_uid FROM tag t JOIN speech_tag st ON st.tag_uid = t.uid WHERE st.speech_uid = $1 AND t.org_uid IS NOT DISTINCT FROM $2 ORDER BY t.name;",
+            )
+            .bind(speech_uid)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| TagRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Tag::try_from).collect()
+    }
+
+    async fn attach_tag(
+        &self,
+        speech_uid: &Uuid,
+        tag_uid: &Uuid,
+    ) -> Result<(), TagRepositoryError> {
+        let connection = self.connect().await?;
+        // Tags are only attached to the speeches of their own organization.
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "WITH speech_found AS (SELECT uid FROM speech WHERE uid = $1 AND deleted_at IS NULL AND org_uid IS NOT DISTINCT FROM $3), \
+                tag_found AS (SELECT uid FROM tag WHERE uid = $2 AND org_uid IS NOT DISTINCT FROM $3), \
+                inserted AS (INSERT INTO speech_tag (speech_uid, tag_uid) SELECT s.uid, t.uid FROM speech_found s, tag_found t ON CONFLICT DO NOTHING) \
+                SELECT (SELECT COUNT(*) FROM speech_found) AS speeches, (SELECT COUNT(*) FROM tag_found) AS tags;",
+            )
+            .bind(speech_uid)
+            .bind(tag_uid)
+            .bind(self.organization)
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| TagRepositoryError::InternalError(timed_out(e)))??;
+        if row.try_get::<i64, _>("speeches")? == 0 {
+            return Err(TagRepositoryError::SpeechNotFound);
+        }
+        if row.try_get::<i64, _>("tags")? == 0 {
+            return Err(TagRepositoryError::TagNotFound);
+        }
+        Ok(())
+    }
+
+    async fn detach_tag(
+        &self,
+        speech_uid: &Uuid,
+        tag_uid: &Uuid,
+    ) -> Result<(), TagRepositoryError> {
+        let connection = self.connect().await?;
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "DELETE FROM speech_tag st USING tag t WHERE st.tag_uid = t.uid AND st.speech_uid = $1 AND t.uid = $2 AND t.org_uid IS NOT DISTINCT FROM $3;",
+            )
+            .bind(speech_uid)
+            .bind(tag_uid)
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| TagRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(TagRepositoryError::TagNotFound);
+        }
+        Ok(())
+    }
+}
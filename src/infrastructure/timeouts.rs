@@ -0,0 +1,32 @@
+/// Timeouts applied to the database operations, in milliseconds, each class of operation
+/// having its own: a long write must not force a timeout tolerating slow reads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatabaseTimeouts {
+    /// Queries reading the entities, and the connections to the database.
+    pub read: u64,
+    /// Queries and transactions storing the entities.
+    pub write: u64,
+    /// Migrations applied at startup, and the maintenance actions rebuilding the data.
+    pub migration: u64,
+}
+
+impl DatabaseTimeouts {
+    /// Same timeout for every operation, e.g. in the tests.
+    pub fn uniform(timeout: u64) -> Self {
+        Self {
+            read: timeout,
+            write: timeout,
+            migration: timeout,
+        }
+    }
+}
+
+impl Default for DatabaseTimeouts {
+    fn default() -> Self {
+        Self {
+            read: 100,
+            write: 100,
+            migration: 60000,
+        }
+    }
+}
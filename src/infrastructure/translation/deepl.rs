@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::domain::translation::{Translator, TranslatorError};
+
+#[derive(Deserialize)]
+struct DeepLResponse {
+    translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeepLTranslation {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct DeepLError {
+    #[serde(default)]
+    message: String,
+}
+
+/// Most texts DeepL translates in one request.
+const MAX_TEXTS_PER_REQUEST: usize = 50;
+/// Largest body DeepL accepts for one request, in bytes.
+const MAX_REQUEST_SIZE: usize = 128 * 1024;
+/// Bytes of the body besides the texts, the target language and the JSON around them.
+const REQUEST_OVERHEAD: usize = 64;
+
+/// Splits the texts into the batches of the requests, each one within the limits of DeepL.
+/// A text too large on its own is sent alone, DeepL refusing it.
+fn batches(texts: &[String]) -> Vec<&[String]> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut size = REQUEST_OVERHEAD;
+    for (position, text) in texts.iter().enumerate() {
+        // The text as a JSON string, escaped and quoted, and the comma after it.
+        let text_size = serde_json::to_string(text).map_or(text.len(), |json| json.len()) + 1;
+        if position > start
+            && (position - start == MAX_TEXTS_PER_REQUEST || size + text_size > MAX_REQUEST_SIZE)
+        {
+            batches.push(&texts[start..position]);
+            start = position;
+            size = REQUEST_OVERHEAD;
+        }
+        size += text_size;
+    }
+    if start < texts.len() {
+        batches.push(&texts[start..]);
+    }
+    batches
+}
+
+/// Translator backed by the DeepL API (`https://api-free.deepl.com` or
+/// `https://api.deepl.com` depending on the plan).
+#[derive(Debug, Clone)]
+pub struct DeepLTranslator {
+    client: Client,
+    url: String,
+    api_key: String,
+}
+
+impl DeepLTranslator {
+    pub fn new(url: &str, api_key: &str, timeout: u64) -> Result<Self, TranslatorError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout))
+            .build()
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Translator for DeepLTranslator {
+    async fn translate(
+        &self,
+        texts: &[String],
+        target: &str,
+    ) -> Result<Vec<String>, TranslatorError> {
+        let mut translations = Vec::with_capacity(texts.len());
+        for batch in batches(texts) {
+            translations.extend(self.translate_batch(batch, target).await?);
+        }
+        Ok(translations)
+    }
+}
+
+impl DeepLTranslator {
+    /// Translates the texts in one request, within the limits of DeepL.
+    async fn translate_batch(
+        &self,
+        texts: &[String],
+        target: &str,
+    ) -> Result<Vec<String>, TranslatorError> {
+        let response = self
+            .client
+            .post(format!("{}/v2/translate", self.url))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&json!({ "text": texts, "target_lang": target.to_uppercase() }))
+            .send()
+            .await
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+        if response.status() == StatusCode::BAD_REQUEST {
+            // Only a refused target language is the language of the client, e.g.
+            // "Value for 'target_lang' not supported."
+            let error: DeepLError = response
+                .json()
+                .await
+                .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+            if error.message.contains("target_lang") {
+                return Err(TranslatorError::UnsupportedLanguage);
+            }
+            return Err(TranslatorError::ProviderError(error.message));
+        }
+        let response: DeepLResponse = response
+            .error_for_status()
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+        Ok(response.translations.into_iter().map(|t| t.text).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{batches, MAX_REQUEST_SIZE, MAX_TEXTS_PER_REQUEST};
+
+    #[test]
+    fn texts_are_sent_in_batches_within_the_limits() {
+        let texts = vec!["Bonjour.".to_owned(); 120];
+        let sizes = batches(&texts)
+            .iter()
+            .map(|batch| batch.len())
+            .collect::<Vec<usize>>();
+        assert_eq!(
+            sizes,
+            vec![MAX_TEXTS_PER_REQUEST, MAX_TEXTS_PER_REQUEST, 20]
+        );
+        let long = "a".repeat(MAX_REQUEST_SIZE / 3);
+        let texts = vec![long.clone(), long.clone(), long.clone(), "b".to_owned()];
+        let sizes = batches(&texts)
+            .iter()
+            .map(|batch| batch.len())
+            .collect::<Vec<usize>>();
+        assert_eq!(sizes, vec![2, 2]);
+        let texts = vec!["a".repeat(MAX_REQUEST_SIZE + 1), "b".to_owned()];
+        assert_eq!(batches(&texts).len(), 2);
+        assert!(batches(&[]).is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::domain::translation::{Translator, TranslatorError};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LibreTranslateResponse {
+    translated_text: Vec<String>,
+}
+
+/// Translator backed by a LibreTranslate instance, self hosted or public.
+#[derive(Debug, Clone)]
+pub struct LibreTranslateTranslator {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+}
+
+impl LibreTranslateTranslator {
+    pub fn new(url: &str, api_key: Option<&str>, timeout: u64) -> Result<Self, TranslatorError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout))
+            .build()
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+        Ok(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            api_key: api_key.map(|k| k.to_string()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Translator for LibreTranslateTranslator {
+    async fn translate(
+        &self,
+        texts: &[String],
+        target: &str,
+    ) -> Result<Vec<String>, TranslatorError> {
+        let response = self
+            .client
+            .post(format!("{}/translate", self.url))
+            .json(&json!({
+                "q": texts,
+                "source": "auto",
+                "target": target,
+                "format": "text",
+                "api_key": self.api_key,
+            }))
+            .send()
+            .await
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+        if response.status() == StatusCode::BAD_REQUEST {
+            return Err(TranslatorError::UnsupportedLanguage);
+        }
+        let response: LibreTranslateResponse = response
+            .error_for_status()
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| TranslatorError::ProviderError(e.to_string()))?;
+        Ok(response.translated_text)
+    }
+}
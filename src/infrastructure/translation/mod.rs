@@ -0,0 +1,2 @@
+pub mod deepl;
+pub mod libre_translate;
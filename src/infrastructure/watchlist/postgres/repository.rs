@@ -0,0 +1,362 @@
+use std::{sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, Error, PgPool, Row};
+use tokio::{sync::OnceCell, time};
+use uuid::Uuid;
+
+use crate::domain::{
+    speech::speech_repository::StoragePosition,
+    watchlist::{Watchlist, WatchlistRepository, WatchlistRepositoryError},
+};
+use crate::infrastructure::{
+    error_metrics::{record_sqlx_error, timed_out},
+    timeouts::DatabaseTimeouts,
+};
+
+impl From<Error> for WatchlistRepositoryError {
+    fn from(value: Error) -> Self {
+        record_sqlx_error(&value);
+        match value {
+            Error::Database(database_error) => {
+                if database_error.is_unique_violation() {
+                    return Self::WatchlistAlreadyExists;
+                }
+                Self::InternalError(database_error.to_string())
+            }
+            Error::RowNotFound => Self::WatchlistNotFound,
+            _ => Self::InternalError(value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<PgRow> for Watchlist {
+    type Error = WatchlistRepositoryError;
+
+    fn try_from(value: PgRow) -> Result<Self, Self::Error> {
+        let uid: Uuid = value.try_get("uid")?;
+        let owner: &str = value.try_get("owner_id")?;
+        let name: &str = value.try_get("name")?;
+        let speakers: Vec<Uuid> = value.try_get("speakers")?;
+        let keywords: Vec<String> = value.try_get("keywords")?;
+        let media: Vec<String> = value.try_get("media")?;
+        let last_checked_at: DateTime<Utc> = value.try_get("last_checked_at")?;
+        let position = StoragePosition {
+            transaction: value.try_get("last_transaction_id")?,
+            speech: value.try_get("last_speech_uid")?,
+        };
+        Ok(
+            Watchlist::new(uid, owner, name, speakers, keywords, media, last_checked_at)
+                .with_position(position),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PostgresWatchlistRepository {
+    url: String,
+    timeouts: DatabaseTimeouts,
+    /// Connections shared by every copy of the repository, opened by the first query.
+    pool: Arc<OnceCell<PgPool>>,
+    /// Organization every query is restricted to, `None` is the default organization.
+    organization: Option<Uuid>,
+}
+
+impl PostgresWatchlistRepository {
+    pub fn new(url: &str, timeouts: DatabaseTimeouts) -> Self {
+        Self {
+            url: url.to_string(),
+            timeouts,
+            pool: Arc::new(OnceCell::new()),
+            organization: None,
+        }
+    }
+
+    /// Returns the pool of the repository, connecting it on the first call.
+    async fn connect(&self) -> Result<PgPool, WatchlistRepositoryError> {
+        Ok(time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            self.pool.get_or_try_init(|| PgPool::connect(&self.url)),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??
+        .clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl WatchlistRepository for PostgresWatchlistRepository {
+    fn for_organization(&self, organization: Option<Uuid>) -> Box<dyn WatchlistRepository> {
+        Box::new(Self {
+            organization,
+            ..self.clone()
+        })
+    }
+
+    async fn create_watchlist(
+        &self,
+        watchlist: &Watchlist,
+    ) -> Result<(), WatchlistRepositoryError> {
+        let connection = self.connect().await?;
+        time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "INSERT INTO watchlist (uid, owner_id, name, speakers, keywords, media, last_checked_at, org_uid) VALUES ($1, $2, $3, $4, $5, $6, $7, $8);",
+            )
+            .bind(watchlist.uid())
+            .bind(watchlist.owner())
+            .bind(watchlist.name())
+            .bind(watchlist.speakers())
+            .bind(watchlist.keywords())
+            .bind(watchlist.media())
+            .bind(watchlist.last_checked_at())
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??;
+        Ok(())
+    }
+
+    async fn get_watchlists(
+        &self,
+        owner: &str,
+    ) -> Result<Vec<Watchlist>, WatchlistRepositoryError> {
+        let connection = self.connect().await?;
+        let rows = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT uid, owner_id, name, speakers, keywords, media, last_checked_at, last_transaction_id, last_speech_uid FROM watchlist WHERE owner_id = $1 AND org_uid IS NOT DISTINCT FROM $2 ORDER BY name;",
+            )
+            .bind(owner)
+            .bind(self.organization)
+            .fetch_all(&connection),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??;
+        rows.into_iter().map(Watchlist::try_from).collect()
+    }
+
+    async fn get_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+    ) -> Result<Watchlist, WatchlistRepositoryError> {
+        let connection = self.connect().await?;
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.read),
+            sqlx::query(
+                "SELECT uid, owner_id, name, speakers, keywords, media, last_checked_at, last_transaction_id, last_speech_uid FROM watchlist WHERE uid = $1 AND owner_id = $2 AND org_uid IS NOT DISTINCT FROM $3;",
+            )
+            .bind(uid)
+            .bind(owner)
+            .bind(self.organization)
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??;
+        Watchlist::try_from(row)
+    }
+
+    async fn update_watchlist(
+        &self,
+        watchlist: &Watchlist,
+    ) -> Result<(), WatchlistRepositoryError> {
+        let connection = self.connect().await?;
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "UPDATE watchlist SET name = $3, speakers = $4, keywords = $5, media = $6 WHERE uid = $1 AND owner_id = $2 AND org_uid IS NOT DISTINCT FROM $7;",
+            )
+            .bind(watchlist.uid())
+            .bind(watchlist.owner())
+            .bind(watchlist.name())
+            .bind(watchlist.speakers())
+            .bind(watchlist.keywords())
+            .bind(watchlist.media())
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(WatchlistRepositoryError::WatchlistNotFound);
+        }
+        Ok(())
+    }
+
+    async fn delete_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+    ) -> Result<(), WatchlistRepositoryError> {
+        let connection = self.connect().await?;
+        let result = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "DELETE FROM watchlist WHERE uid = $1 AND owner_id = $2 AND org_uid IS NOT DISTINCT FROM $3;",
+            )
+            .bind(uid)
+            .bind(owner)
+            .bind(self.organization)
+            .execute(&connection),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??;
+        if result.rows_affected() == 0 {
+            return Err(WatchlistRepositoryError::WatchlistNotFound);
+        }
+        Ok(())
+    }
+
+    async fn check_watchlist(
+        &self,
+        uid: &Uuid,
+        owner: &str,
+        reported: Option<&StoragePosition>,
+    ) -> Result<DateTime<Utc>, WatchlistRepositoryError> {
+        let connection = self.connect().await?;
+        let row = time::timeout(
+            Duration::from_millis(self.timeouts.write),
+            sqlx::query(
+                "UPDATE watchlist SET last_checked_at = NOW(), \
+                last_transaction_id = CASE WHEN (last_transaction_id, last_speech_uid) < ($4, $5) THEN $4 ELSE last_transaction_id END, \
+                last_speech_uid = CASE WHEN (last_transaction_id, last_speech_uid) < ($4, $5) THEN $5 ELSE last_speech_uid END \
+                WHERE uid = $1 AND owner_id = $2 AND org_uid IS NOT DISTINCT FROM $3 RETURNING last_checked_at;",
+            )
+            .bind(uid)
+            .bind(owner)
+            .bind(self.organization)
+            .bind(reported.map(|position| position.transaction))
+            .bind(reported.map(|position| position.speech))
+            .fetch_one(&connection),
+        )
+        .await
+        .map_err(|e| WatchlistRepositoryError::InternalError(timed_out(e)))??;
+        Ok(row.try_get("last_checked_at")?)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::PostgresWatchlistRepository;
+    use crate::{
+        domain::{
+            speech::speech_repository::{SpeechProjection, SpeechRepository},
+            watchlist::{Watchlist, WatchlistRepository, WatchlistRepositoryError},
+        },
+        test_support::{test_database, PersonBuilder, SpeechBuilder},
+    };
+
+    #[tokio::test]
+    async fn test_postgres_watchlist_positions() {
+        let database = test_database().await;
+        let repository = PostgresWatchlistRepository::new(database.url(), database.timeouts());
+        let speeches = database.speech_repository();
+        let speaker = database.create_person(PersonBuilder::new()).await;
+        let before = database
+            .create_speech(SpeechBuilder::new().with_speaker(speaker.uid()))
+            .await;
+        let watchlist = Watchlist::new(
+            Uuid::new_v4(),
+            "owner",
+            "Tous les discours",
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Utc::now(),
+        );
+        repository.create_watchlist(&watchlist).await.unwrap();
+        let first = database
+            .create_speech(SpeechBuilder::new().with_speaker(speaker.uid()))
+            .await;
+        let second = database
+            .create_speech(SpeechBuilder::new().with_speaker(speaker.uid()))
+            .await;
+        let mut reported = Vec::new();
+        loop {
+            let stored = repository
+                .get_watchlist(watchlist.uid(), "owner")
+                .await
+                .unwrap();
+            let page = speeches
+                .get_speech(
+                    0,
+                    1,
+                    &stored.new_speeches_filter(),
+                    SpeechProjection::default(),
+                )
+                .await
+                .unwrap();
+            repository
+                .check_watchlist(watchlist.uid(), "owner", page.last().map(|s| &s.position))
+                .await
+                .unwrap();
+            match page.first() {
+                Some(summary) => reported.push(*summary.speech.uid()),
+                None => break,
+            }
+        }
+        // The speeches are reported once, in the order they were stored.
+        assert_eq!(reported, [*first.uid(), *second.uid()]);
+        assert!(!reported.contains(before.uid()));
+    }
+
+    #[tokio::test]
+    async fn test_postgres_watchlist_of_owner() {
+        let database = test_database().await;
+        let repository = PostgresWatchlistRepository::new(database.url(), database.timeouts());
+        let speeches = database.speech_repository();
+        let followed = database.create_person(PersonBuilder::new()).await;
+        let other = database.create_person(PersonBuilder::new()).await;
+        let watchlist = |name: &str| {
+            Watchlist::new(
+                Uuid::new_v4(),
+                "owner",
+                name,
+                vec![*followed.uid()],
+                Vec::new(),
+                Vec::new(),
+                Utc::now(),
+            )
+        };
+        let created = watchlist("Suivi");
+        repository.create_watchlist(&created).await.unwrap();
+        assert_eq!(
+            repository.create_watchlist(&watchlist("Suivi")).await,
+            Err(WatchlistRepositoryError::WatchlistAlreadyExists)
+        );
+        assert_eq!(
+            repository
+                .get_watchlist(created.uid(), "intruder")
+                .await
+                .unwrap_err(),
+            WatchlistRepositoryError::WatchlistNotFound
+        );
+        assert_eq!(
+            repository.delete_watchlist(created.uid(), "intruder").await,
+            Err(WatchlistRepositoryError::WatchlistNotFound)
+        );
+        database
+            .create_speech(SpeechBuilder::new().with_speaker(followed.uid()))
+            .await;
+        database
+            .create_speech(SpeechBuilder::new().with_speaker(other.uid()))
+            .await;
+        let stored = repository
+            .get_watchlist(created.uid(), "owner")
+            .await
+            .unwrap();
+        // Only the speeches of the persons followed are reported.
+        assert_eq!(
+            speeches
+                .count_speech(&stored.new_speeches_filter())
+                .await
+                .unwrap(),
+            1
+        );
+    }
+}
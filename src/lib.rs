@@ -0,0 +1,18 @@
+pub mod application;
+pub mod domain;
+pub mod error;
+pub mod infrastructure;
+/// Harness of the tests reaching a real Postgres database: a database started once with
+/// testcontainers, emptied before each test, and builders of the fixture entities.
+#[cfg(test)]
+pub mod test_support;
+
+pub use application::{
+    api::router::{APIError, MainRouter},
+    config::AppConfig,
+};
+pub use domain::{
+    person::{PersonManager, PersonRepository},
+    speech::{manager::SpeechManager, speech_repository::SpeechRepository},
+};
+pub use error::AppError;
@@ -1,36 +1,567 @@
-use application::api::router::MainRouter;
-use domain::{person::PersonManager, speech::manager::SpeechManager};
+use clap::{Parser, Subcommand};
 use dotenv::dotenv;
-use infrastructure::{
-    person::postgres::postgres_repository::PostgresPersonRepository,
-    speech::postgres::repository::PostgresSpeechRepository,
+use speech_analytics_api::{
+    application::{
+        annotation::start_sentence_annotation,
+        api::{keycloak::KeycloakKeyProvider, router::Managers},
+        cli::{create_person, import_speech_file, list_speeches, speech_line},
+        clustering::start_speech_clustering,
+        config::{
+            AnnotatorProvider, BackupConfig, BackupDestination, BackupFormat, DatabaseBackend,
+            EventPublishingConfig, PiiDetectorProvider, TranslationProvider,
+        },
+        maintenance::start_backups,
+        outbox::start_outbox_relay,
+        seed::{seed, SeedProfile, SEED_VERSION},
+    },
+    domain::{
+        annotation::{AnnotationManager, Annotator},
+        attachment::AttachmentManager,
+        backup::{BackupManager, BackupProvider, BackupTarget},
+        collection::CollectionVersions,
+        idempotency::IdempotencyManager,
+        job::JobManager,
+        label::LabelManager,
+        maintenance::MaintenanceManager,
+        organization::OrganizationManager,
+        outbox::{EventPublisher, OutboxManager},
+        person::PersonRepository,
+        pii::PiiDetector,
+        segment::SegmentManager,
+        speech::{event_log::SpeechEventLog, speech_repository::SpeechRepository},
+        stats::StatsManager,
+        tag::TagManager,
+        translation::Translator,
+        watchlist::WatchlistManager,
+    },
+    infrastructure::label::postgres::repository::PostgresLabelRepository,
+    infrastructure::{
+        annotation::{
+            http::HttpAnnotator, lexicon::LexiconAnnotator,
+            postgres::repository::PostgresAnnotationRepository,
+        },
+        attachment::{postgres::repository::PostgresAttachmentRepository, s3::S3AttachmentStorage},
+        backup::{
+            json_lines::JsonLinesBackupProvider, local::LocalBackupTarget,
+            pg_dump::PgDumpBackupProvider, s3::S3BackupTarget,
+        },
+        collection::postgres::repository::PostgresCollectionVersionRepository,
+        error::InfrastructureError,
+        idempotency::postgres::repository::PostgresIdempotencyRepository,
+        job::postgres::repository::PostgresJobRepository,
+        maintenance::postgres::repository::PostgresMaintenanceRepository,
+        migrations::run_migrations,
+        organization::postgres::repository::PostgresOrganizationRepository,
+        outbox::postgres::repository::PostgresOutboxRepository,
+        person::postgres::postgres_repository::PostgresPersonRepository,
+        pii::{http::HttpPiiDetector, patterns::RegexPiiDetector},
+        segment::postgres::repository::PostgresSegmentRepository,
+        speech::postgres::repository::PostgresSpeechRepository,
+        stats::postgres::repository::PostgresStatsRepository,
+        tag::postgres::repository::PostgresTagRepository,
+        translation::{deepl::DeepLTranslator, libre_translate::LibreTranslateTranslator},
+        watchlist::postgres::repository::PostgresWatchlistRepository,
+    },
+    AppConfig, AppError, MainRouter, PersonManager, SpeechManager,
 };
-use tokio::runtime::Runtime;
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+};
+use tokio::{runtime::Runtime, sync::broadcast};
+
+/// Speech analytics API, and the commands operating its database.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// What the program does, serving the API when left out.
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Serves the API.
+    Serve,
+    /// Migrates the database, then stops.
+    Migrate,
+    /// Stores a demo dataset.
+    Seed {
+        /// small, medium or large.
+        #[arg(long, default_value = "small")]
+        profile: SeedProfile,
+    },
+    /// Loads a `.jsonl` or `.dump` backup into the empty database.
+    Restore { file: String },
+    /// Creates a person of the default organization.
+    CreatePerson {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        first_name: String,
+        /// As `1970-01-31`.
+        #[arg(long)]
+        birth_date: String,
+    },
+    /// Imports into the default organization the speeches of a JSON file holding the body
+    /// of `POST /api/speech/imports`.
+    ImportSpeech { file: PathBuf },
+    /// Lists the speeches of the default organization, the latest first.
+    ListSpeeches {
+        #[arg(long, default_value_t = 0)]
+        page: u16,
+        #[arg(long, default_value_t = 20)]
+        quantity: u16,
+    },
+}
+
+/// Reports the client of a service that cannot be built from the setting named.
+fn client_error<E: Debug>(
+    service: &'static str,
+    setting: &'static str,
+) -> impl FnOnce(E) -> InfrastructureError {
+    move |e| InfrastructureError::Client {
+        service,
+        setting,
+        message: format!("{:?}", e),
+    }
+}
 
-mod application;
-mod domain;
-mod infrastructure;
-fn main() {
+/// Reads the backups of the configured format from the Postgres database.
+fn backup_provider(config: &AppConfig, format: BackupFormat) -> Box<dyn BackupProvider> {
+    match format {
+        BackupFormat::JsonLines => Box::new(JsonLinesBackupProvider::new(
+            &config.database.url,
+            config.database.timeouts,
+        )),
+        BackupFormat::PgDump => Box::new(PgDumpBackupProvider::new(
+            &config.database.url,
+            config.database.timeouts,
+        )),
+    }
+}
+
+/// Builds the manager writing the backups to the configured target.
+fn backup_manager(
+    config: &AppConfig,
+    backup: &BackupConfig,
+) -> Result<BackupManager, InfrastructureError> {
+    let target: Box<dyn BackupTarget> = match &backup.destination {
+        BackupDestination::Local { directory } => Box::new(LocalBackupTarget::new(directory)),
+        BackupDestination::S3 {
+            url,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+            prefix,
+            timeout,
+        } => Box::new(S3BackupTarget::new(
+            S3AttachmentStorage::new(url, bucket, region, access_key, secret_key, *timeout)
+                .map_err(client_error("backup storage", "BACKUP_S3_URL"))?,
+            bucket,
+            prefix,
+        )),
+    };
+    Ok(BackupManager::new(
+        backup_provider(config, backup.format),
+        target,
+    ))
+}
+
+/// Loads the backup of the file into the database, its format read from its extension.
+async fn restore(config: &AppConfig, file: &str) -> Result<u64, String> {
+    if config.database.backend != DatabaseBackend::Postgres {
+        return Err("Only a Postgres database can be restored".to_owned());
+    }
+    let format = match Path::new(file).extension().and_then(|e| e.to_str()) {
+        Some("jsonl") => BackupFormat::JsonLines,
+        Some("dump") => BackupFormat::PgDump,
+        _ => return Err(format!("{} is neither a .jsonl nor a .dump backup", file)),
+    };
+    backup_provider(config, format)
+        .restore(Path::new(file))
+        .await
+        .map_err(|e| format!("{:?}", e))
+}
+
+/// Builds the person and speech repositories of the configured backend.
+async fn person_and_speech_repositories(
+    config: &AppConfig,
+) -> Result<(Box<dyn PersonRepository>, Box<dyn SpeechRepository>), InfrastructureError> {
+    let outbox = config.event_publishing.is_some();
+    Ok(match &config.database.backend {
+        DatabaseBackend::Postgres => (
+            Box::new(
+                PostgresPersonRepository::new(&config.database.url, config.database.timeouts)
+                    .with_outbox(outbox),
+            ),
+            Box::new(
+                PostgresSpeechRepository::new(&config.database.url, config.database.timeouts)
+                    .with_outbox(outbox),
+            ),
+        ),
+        #[cfg(feature = "mongo")]
+        DatabaseBackend::Mongo { url, database } => {
+            use speech_analytics_api::infrastructure::{
+                mongo::create_indexes, person::mongo::repository::MongoPersonRepository,
+                speech::mongo::repository::MongoSpeechRepository,
+            };
+            create_indexes(url, database, config.database.timeouts.migration)
+                .await
+                .map_err(InfrastructureError::MongoIndexes)?;
+            (
+                Box::new(MongoPersonRepository::new(
+                    url,
+                    database,
+                    config.database.timeouts,
+                )),
+                Box::new(MongoSpeechRepository::new(
+                    url,
+                    database,
+                    config.database.timeouts,
+                )),
+            )
+        }
+    })
+}
+
+/// Connects the broker the domain events are published to.
+async fn event_publisher(
+    config: &EventPublishingConfig,
+) -> Result<Box<dyn EventPublisher>, InfrastructureError> {
+    #[cfg(any(feature = "kafka", feature = "nats"))]
+    use speech_analytics_api::application::config::EventBroker;
+    #[cfg(feature = "kafka")]
+    if let EventBroker::Kafka { brokers, timeout } = &config.broker {
+        use speech_analytics_api::infrastructure::outbox::kafka::KafkaEventPublisher;
+        return Ok(Box::new(
+            KafkaEventPublisher::new(brokers, &config.topic_prefix, *timeout)
+                .map_err(client_error("Kafka producer", "KAFKA_BROKERS"))?,
+        ));
+    }
+    #[cfg(feature = "nats")]
+    if let EventBroker::Nats { url } = &config.broker {
+        use speech_analytics_api::infrastructure::outbox::nats::NatsEventPublisher;
+        return Ok(Box::new(
+            NatsEventPublisher::new(url, &config.topic_prefix)
+                .await
+                .map_err(client_error("NATS connection", "NATS_URL"))?,
+        ));
+    }
+    // The configuration refuses the brokers whose feature is not built.
+    unreachable!("The {:?} broker is not built", config.broker)
+}
+
+fn main() -> ExitCode {
     dotenv().ok();
+    match start() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // The message names the setting or the service to fix.
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn start() -> Result<(), AppError> {
+    // An invalid argument or `--help` stops the program before the configuration is read.
+    let command = Cli::parse().command.unwrap_or(Command::Serve);
     // Check of env variables before starting the app.
-    let db_url = std::env::var("DATABASE_URL").expect("DATABASE_URL not found in env file");
-    let _ = std::env::var("KEYCLOAK_CERTS_URL").expect("KEYCLOAK_CERTS_URL not found in env file");
-    let database_timeout: u64 = std::env::var("DATABASE_TIMEOUT")
-        .unwrap_or("100".to_string())
-        .parse()
-        .expect("DATABASE_TIMEOUT must be an u64");
-
-    let rt = Runtime::new().unwrap();
-    rt.block_on(async {
-        let person_repository = PostgresPersonRepository::new(&db_url, database_timeout)
-            .await
-            .expect("Cannot connect to the DB");
-        let speech_repository = PostgresSpeechRepository::new(&db_url, database_timeout)
-            .await
-            .expect("Cannot connect to the DB");
-        let speech_manager = SpeechManager::new(Box::new(speech_repository));
-        let person_manager = PersonManager::new(Box::new(person_repository));
-        let main_router = MainRouter::new(person_manager, speech_manager);
-        let _ = main_router.run().await.expect("An error occured");
+    let config = AppConfig::from_env().map_err(AppError::Configuration)?;
+    // Writes the audit log of the requests, the other logs are printed.
+    tracing_subscriber::fmt().init();
+
+    let rt = Runtime::new().map_err(AppError::Runtime)?;
+    rt.block_on(run(config, command))
+}
+
+/// Runs a one-off command against the persons and the speeches of the database.
+async fn run_operation(
+    command: Command,
+    person_manager: &PersonManager,
+    speech_manager: &SpeechManager,
+) -> Result<(), AppError> {
+    match command {
+        Command::Seed { profile } => {
+            let report = seed(profile, person_manager, speech_manager)
+                .await
+                .map_err(AppError::Seed)?;
+            println!(
+                "Seeded the {} dataset v{}: {} persons, {} speeches, {} entities of a larger dataset deleted",
+                profile, SEED_VERSION, report.persons, report.speeches, report.deleted
+            );
+        }
+        Command::CreatePerson {
+            name,
+            first_name,
+            birth_date,
+        } => {
+            let person = create_person(person_manager, &name, &first_name, &birth_date)
+                .await
+                .map_err(AppError::Command)?;
+            println!("Created the person {}", person.uid());
+        }
+        Command::ImportSpeech { file } => {
+            let report = import_speech_file(&file, speech_manager, person_manager)
+                .await
+                .map_err(AppError::Command)?;
+            println!(
+                "Imported {} speeches from {}, {} conflicts kept in the import {}",
+                report.created.len(),
+                file.display(),
+                report.conflicts.len(),
+                report.import_uid
+            );
+        }
+        Command::ListSpeeches { page, quantity } => {
+            for summary in list_speeches(speech_manager, page, quantity)
+                .await
+                .map_err(AppError::Command)?
+            {
+                println!("{}", speech_line(&summary));
+            }
+        }
+        // Handled by `run`, without the persons and the speeches.
+        Command::Serve | Command::Migrate | Command::Restore { .. } => {}
+    }
+    Ok(())
+}
+
+/// Builds the managers of every entity, with the providers of the configuration, for the
+/// API as for the one-off commands.
+async fn managers(config: &AppConfig) -> Result<Managers, AppError> {
+    let (person_repository, speech_repository) = person_and_speech_repositories(config).await?;
+    let label_repository =
+        PostgresLabelRepository::new(&config.database.url, config.database.timeouts);
+    let tag_repository = PostgresTagRepository::new(&config.database.url, config.database.timeouts);
+    let organization_repository =
+        PostgresOrganizationRepository::new(&config.database.url, config.database.timeouts);
+    let idempotency_repository =
+        PostgresIdempotencyRepository::new(&config.database.url, config.database.timeouts);
+    let attachment_repository =
+        PostgresAttachmentRepository::new(&config.database.url, config.database.timeouts);
+    let annotation_repository =
+        PostgresAnnotationRepository::new(&config.database.url, config.database.timeouts);
+    let watchlist_repository =
+        PostgresWatchlistRepository::new(&config.database.url, config.database.timeouts);
+    let segment_repository =
+        PostgresSegmentRepository::new(&config.database.url, config.database.timeouts);
+    let mut speech_manager = SpeechManager::new(speech_repository);
+    if let Some(translation) = &config.translation {
+        let translator: Box<dyn Translator> = match translation.provider {
+            TranslationProvider::DeepL => Box::new(
+                DeepLTranslator::new(
+                    &translation.url,
+                    translation.api_key.as_deref().unwrap_or_default(),
+                    translation.timeout,
+                )
+                .map_err(client_error("translator", "TRANSLATION_URL"))?,
+            ),
+            TranslationProvider::LibreTranslate => Box::new(
+                LibreTranslateTranslator::new(
+                    &translation.url,
+                    translation.api_key.as_deref(),
+                    translation.timeout,
+                )
+                .map_err(client_error("translator", "TRANSLATION_URL"))?,
+            ),
+        };
+        speech_manager = speech_manager.with_translator(translator);
+    }
+    if let Some(pii_detection) = &config.pii_detection {
+        let detector: Box<dyn PiiDetector> = match &pii_detection.provider {
+            PiiDetectorProvider::Regex => Box::new(RegexPiiDetector::new()),
+            PiiDetectorProvider::Http {
+                url,
+                api_key,
+                timeout,
+            } => Box::new(
+                HttpPiiDetector::new(url, api_key.as_deref(), *timeout)
+                    .map_err(client_error("personal data detector", "PII_DETECTOR_URL"))?,
+            ),
+        };
+        speech_manager = speech_manager
+            .with_pii_detector(detector)
+            .with_pii_review_required(pii_detection.review_required);
+    }
+    let person_manager = PersonManager::new(person_repository);
+    let label_manager = LabelManager::new(Box::new(label_repository));
+    let tag_manager = TagManager::new(Box::new(tag_repository));
+    let watchlist_manager = WatchlistManager::new(Box::new(watchlist_repository));
+    let segment_manager = SegmentManager::new(Box::new(segment_repository));
+    let stats_manager = StatsManager::new(Box::new(PostgresStatsRepository::new(
+        &config.database.url,
+        config.database.timeouts,
+    )));
+    let mut maintenance_manager = MaintenanceManager::new(Box::new(
+        PostgresMaintenanceRepository::new(&config.database.url, config.database.timeouts),
+    ));
+    if let Some(backup) = &config.backup {
+        maintenance_manager = maintenance_manager.with_backup(backup_manager(config, backup)?);
+    }
+    let job_manager = JobManager::new(Box::new(PostgresJobRepository::new(
+        &config.database.url,
+        config.database.timeouts,
+    )));
+    let organization_manager = OrganizationManager::new(Box::new(organization_repository));
+    let idempotency_manager = IdempotencyManager::new(Box::new(idempotency_repository))
+        .with_ttl(config.server.idempotency_key_ttl);
+    let mut attachment_manager = AttachmentManager::new(Box::new(attachment_repository));
+    if let Some(storage) = &config.attachment_storage {
+        attachment_manager = attachment_manager
+            .with_storage(Box::new(
+                S3AttachmentStorage::new(
+                    &storage.url,
+                    &storage.bucket,
+                    &storage.region,
+                    &storage.access_key,
+                    &storage.secret_key,
+                    storage.timeout,
+                )
+                .map_err(client_error("attachment storage", "ATTACHMENT_STORAGE_URL"))?,
+            ))
+            .with_max_size(storage.max_size)
+            .with_url_expiry(storage.url_expiry);
+    }
+    let mut annotation_manager = AnnotationManager::new(Box::new(annotation_repository));
+    if let Some(annotation) = &config.annotation {
+        let annotator: Box<dyn Annotator> = match &annotation.provider {
+            AnnotatorProvider::Lexicon => Box::new(LexiconAnnotator::new()),
+            AnnotatorProvider::Http {
+                url,
+                api_key,
+                timeout,
+            } => Box::new(
+                HttpAnnotator::new(url, api_key.as_deref(), *timeout)
+                    .map_err(client_error("annotator", "ANNOTATOR_URL"))?,
+            ),
+        };
+        annotation_manager = annotation_manager.with_annotator(annotator);
+    }
+    Ok(Managers {
+        person_manager,
+        speech_manager,
+        label_manager,
+        tag_manager,
+        organization_manager,
+        idempotency_manager,
+        attachment_manager,
+        annotation_manager,
+        watchlist_manager,
+        maintenance_manager,
+        job_manager,
+        segment_manager,
+        stats_manager,
+        collection_versions: CollectionVersions::new(Box::new(
+            PostgresCollectionVersionRepository::new(
+                &config.database.url,
+                config.database.timeouts,
+            ),
+        )),
     })
 }
+
+async fn run(config: AppConfig, command: Command) -> Result<(), AppError> {
+    run_migrations(&config.database.url, config.database.timeouts.migration)
+        .await
+        .map_err(InfrastructureError::Migration)?;
+    match &command {
+        Command::Migrate => {
+            println!("The database is migrated");
+            return Ok(());
+        }
+        Command::Restore { file } => {
+            let rows = restore(&config, file).await.map_err(AppError::Restore)?;
+            println!("Restored {} rows from {}", rows, file);
+            return Ok(());
+        }
+        _ => {}
+    }
+    if !matches!(command, Command::Serve) {
+        // Nothing follows the events in this process, the outbox keeps them for the relay
+        // of the API.
+        let (speech_events, _) = broadcast::channel(1);
+        let managers = managers(&config)
+            .await?
+            .with_events(speech_events, SpeechEventLog::default());
+        return run_operation(command, &managers.person_manager, &managers.speech_manager).await;
+    }
+    if !config.auth.anonymous_permissions.is_empty() {
+        println!(
+            "Warning: the requests without a token are granted {:?}",
+            config.auth.anonymous_permissions
+        );
+    } else if config.auth.public_read_enabled {
+        println!("Warning: public read is enabled but ANONYMOUS_PERMISSIONS is empty, the requests without a token are refused");
+    }
+    #[allow(unused_mut)]
+    let mut key_provider = KeycloakKeyProvider::new(&config.auth.keycloak_certs_url);
+    #[cfg(feature = "redis")]
+    if let Some(redis_url) = &config.auth.keycloak_keys_redis_url {
+        key_provider = key_provider.with_shared_cache(Box::new(
+            speech_analytics_api::application::api::redis_keys::RedisKeyCache::new(redis_url)
+                .map_err(client_error(
+                    "Keycloak keys cache",
+                    "KEYCLOAK_KEYS_REDIS_URL",
+                ))?,
+        ));
+    }
+    let key_provider = Arc::new(key_provider);
+    key_provider.clone().start_refresh();
+    let managers = managers(&config).await?;
+    // The jobs left running by a previous run of the instances are failed.
+    match managers.job_manager.fail_stale_jobs().await {
+        Ok(0) => {}
+        Ok(failed) => println!("Failed {} jobs which stopped without an outcome", failed),
+        Err(e) => println!("An error occured while failing the stale jobs: {:?}", e),
+    }
+    if let Some(event_publishing) = &config.event_publishing {
+        let outbox_manager = OutboxManager::new(
+            Box::new(PostgresOutboxRepository::new(
+                &config.database.url,
+                config.database.timeouts,
+            )),
+            event_publisher(event_publishing).await?,
+        );
+        start_outbox_relay(
+            outbox_manager,
+            event_publishing.relay_interval,
+            event_publishing.retention,
+        );
+    }
+    start_speech_clustering(
+        managers.speech_manager.clone(),
+        managers.organization_manager.clone(),
+        config.speech_clustering_interval,
+    );
+    if let Some(backup) = &config.backup {
+        start_backups(
+            managers.job_manager.clone(),
+            managers.maintenance_manager.clone(),
+            managers.speech_manager.clone(),
+            managers.organization_manager.clone(),
+            backup.interval,
+        );
+    }
+    // The annotation only reads the speeches, the events of the router are not needed.
+    let annotated_speeches = managers.speech_manager.clone();
+    let annotation_manager = managers.annotation_manager.clone();
+    let organization_manager = managers.organization_manager.clone();
+    let main_router = MainRouter::new(managers)
+        .with_config(&config)
+        .with_key_provider(key_provider);
+    if config.annotation.is_some() {
+        start_sentence_annotation(
+            annotated_speeches,
+            annotation_manager,
+            organization_manager,
+            main_router.subscribe_speech_events(),
+        );
+    }
+    main_router.run().await?;
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::domain::{
+    person::Person,
+    speech::{
+        language::SpeechLanguage,
+        sentence::{Sentence, SentenceTiming},
+        Speech, SpeechStatus,
+    },
+};
+
+/// Person of a test, its identity unique unless given.
+pub struct PersonBuilder {
+    uid: Uuid,
+    name: String,
+    first_name: String,
+    birth_date: NaiveDate,
+    trust_score: u8,
+    lie_quantity: u64,
+}
+
+impl Default for PersonBuilder {
+    fn default() -> Self {
+        let uid = Uuid::new_v4();
+        Self {
+            uid,
+            name: format!("Person {}", &uid.to_string()[..8]),
+            first_name: "Test".to_owned(),
+            birth_date: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            trust_score: 50,
+            lie_quantity: 0,
+        }
+    }
+}
+
+impl PersonBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_uid(mut self, uid: Uuid) -> Self {
+        self.uid = uid;
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_owned();
+        self
+    }
+
+    pub fn with_first_name(mut self, first_name: &str) -> Self {
+        self.first_name = first_name.to_owned();
+        self
+    }
+
+    pub fn with_birth_date(mut self, birth_date: &str) -> Self {
+        self.birth_date = birth_date.parse().expect("Invalid fixture birth date");
+        self
+    }
+
+    pub fn with_trust_score(mut self, trust_score: u8) -> Self {
+        self.trust_score = trust_score;
+        self
+    }
+
+    pub fn with_lie_quantity(mut self, lie_quantity: u64) -> Self {
+        self.lie_quantity = lie_quantity;
+        self
+    }
+
+    pub fn build(self) -> Person {
+        Person::new(
+            self.uid,
+            &self.name,
+            &self.first_name,
+            self.birth_date,
+            self.trust_score,
+            self.lie_quantity,
+        )
+    }
+}
+
+/// Speech of a test, pending and without sentences unless given.
+pub struct SpeechBuilder {
+    uid: Uuid,
+    name: String,
+    date: DateTime<Utc>,
+    speakers: Vec<Uuid>,
+    sentences: Vec<Sentence>,
+    media: String,
+    status: SpeechStatus,
+    language: Option<SpeechLanguage>,
+}
+
+impl Default for SpeechBuilder {
+    fn default() -> Self {
+        let uid = Uuid::new_v4();
+        Self {
+            uid,
+            name: format!("Speech {}", &uid.to_string()[..8]),
+            date: Utc::now(),
+            speakers: Vec::new(),
+            sentences: Vec::new(),
+            media: "Test".to_owned(),
+            status: SpeechStatus::Pending,
+            language: None,
+        }
+    }
+}
+
+impl SpeechBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_uid(mut self, uid: Uuid) -> Self {
+        self.uid = uid;
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_owned();
+        self
+    }
+
+    pub fn with_date(mut self, date: DateTime<Utc>) -> Self {
+        self.date = date;
+        self
+    }
+
+    pub fn with_media(mut self, media: &str) -> Self {
+        self.media = media.to_owned();
+        self
+    }
+
+    pub fn with_status(mut self, status: SpeechStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Language provided with the speech, as an ISO 639-3 code.
+    pub fn with_language(mut self, code: &str) -> Self {
+        self.language = Some(SpeechLanguage::new(code, None, false));
+        self
+    }
+
+    /// Adds a speaker without any sentence.
+    pub fn with_speaker(mut self, speaker: &Uuid) -> Self {
+        if !self.speakers.contains(speaker) {
+            self.speakers.push(*speaker);
+        }
+        self
+    }
+
+    /// Adds a sentence at the end of the transcript, its speaker becoming a speaker of
+    /// the speech.
+    pub fn with_sentence(self, speaker: &Uuid, text: &str) -> Self {
+        let mut builder = self.with_speaker(speaker);
+        builder
+            .sentences
+            .push(Sentence::new(&Uuid::new_v4(), speaker, text, false));
+        builder
+    }
+
+    /// Adds a sentence positioned in the media, in milliseconds.
+    pub fn with_timed_sentence(self, speaker: &Uuid, text: &str, start: u32, end: u32) -> Self {
+        let mut builder = self.with_sentence(speaker, text);
+        if let Some(sentence) = builder.sentences.pop() {
+            builder
+                .sentences
+                .push(sentence.with_timing(Some(SentenceTiming { start, end })));
+        }
+        builder
+    }
+
+    /// Adds a sentence spoken in another language than the speech, as an ISO 639-3 code.
+    pub fn with_sentence_in(self, speaker: &Uuid, text: &str, code: &str) -> Self {
+        let mut builder = self.with_sentence(speaker, text);
+        if let Some(sentence) = builder.sentences.pop() {
+            builder
+                .sentences
+                .push(sentence.with_language(Some(code.to_owned())));
+        }
+        builder
+    }
+
+    pub fn build(self) -> Speech {
+        let mut speech = Speech::new(
+            &self.uid,
+            &self.name,
+            self.date,
+            &self.speakers,
+            &self.sentences,
+            &self.media,
+            self.status,
+        );
+        speech.update_language(self.language);
+        speech
+    }
+}
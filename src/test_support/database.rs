@@ -0,0 +1,218 @@
+use sqlx::PgPool;
+use testcontainers_modules::{
+    postgres::Postgres,
+    testcontainers::{runners::AsyncRunner, ContainerAsync},
+};
+use tokio::sync::{Mutex, MutexGuard, OnceCell};
+
+use super::builders::{PersonBuilder, SpeechBuilder};
+use crate::{
+    application::api::router::Managers,
+    domain::{
+        annotation::AnnotationManager,
+        attachment::AttachmentManager,
+        collection::CollectionVersions,
+        idempotency::IdempotencyManager,
+        job::JobManager,
+        label::LabelManager,
+        maintenance::MaintenanceManager,
+        organization::OrganizationManager,
+        person::{Person, PersonManager, PersonRepository},
+        segment::SegmentManager,
+        speech::{manager::SpeechManager, speech_repository::SpeechRepository, Speech},
+        stats::StatsManager,
+        tag::TagManager,
+        watchlist::WatchlistManager,
+    },
+    infrastructure::{
+        annotation::postgres::repository::PostgresAnnotationRepository,
+        attachment::postgres::repository::PostgresAttachmentRepository,
+        collection::postgres::repository::PostgresCollectionVersionRepository,
+        idempotency::postgres::repository::PostgresIdempotencyRepository,
+        job::postgres::repository::PostgresJobRepository,
+        label::postgres::repository::PostgresLabelRepository,
+        maintenance::postgres::repository::PostgresMaintenanceRepository,
+        migrations::run_migrations,
+        organization::postgres::repository::PostgresOrganizationRepository,
+        person::postgres::postgres_repository::PostgresPersonRepository,
+        segment::postgres::repository::PostgresSegmentRepository,
+        speech::postgres::repository::PostgresSpeechRepository,
+        stats::postgres::repository::PostgresStatsRepository,
+        tag::postgres::repository::PostgresTagRepository, timeouts::DatabaseTimeouts,
+        watchlist::postgres::repository::PostgresWatchlistRepository,
+    },
+};
+
+/// Timeout of the operations of the tests, a container being slower than a local database.
+const TEST_TIMEOUT: u64 = 10000;
+
+/// Postgres database shared by the tests of the process, migrated once.
+struct SharedDatabase {
+    url: String,
+    /// Kept for the whole process, the container is removed once the tests are done.
+    _container: Option<ContainerAsync<Postgres>>,
+}
+
+static DATABASE: OnceCell<SharedDatabase> = OnceCell::const_new();
+
+/// Held by the test using the database, the tests using it one after the other.
+static RESERVATION: Mutex<()> = Mutex::const_new(());
+
+impl SharedDatabase {
+    async fn start() -> Self {
+        // A database already running, e.g. a service of the CI, spares the container.
+        let (url, container) = match std::env::var("TEST_DATABASE_URL") {
+            Ok(url) => (url, None),
+            Err(_) => {
+                let container = Postgres::default()
+                    .start()
+                    .await
+                    .expect("Cannot start the Postgres container");
+                let url = format!(
+                    "postgres://postgres:postgres@{}:{}/postgres",
+                    container
+                        .get_host()
+                        .await
+                        .expect("Cannot read the container host"),
+                    container
+                        .get_host_port_ipv4(5432)
+                        .await
+                        .expect("Cannot read the container port")
+                );
+                (url, Some(container))
+            }
+        };
+        run_migrations(&url, TEST_TIMEOUT)
+            .await
+            .expect("Cannot migrate the test database");
+        Self {
+            url,
+            _container: container,
+        }
+    }
+}
+
+/// Reserves the test database, empty, until the returned value is dropped.
+pub async fn test_database() -> TestDatabase {
+    let reservation = RESERVATION.lock().await;
+    let database = DATABASE.get_or_init(SharedDatabase::start).await;
+    let test_database = TestDatabase {
+        url: database.url.clone(),
+        _reservation: reservation,
+    };
+    test_database.truncate().await;
+    test_database
+}
+
+/// Empty migrated database reserved to a test, see `test_database`.
+pub struct TestDatabase {
+    url: String,
+    _reservation: MutexGuard<'static, ()>,
+}
+
+impl TestDatabase {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn timeouts(&self) -> DatabaseTimeouts {
+        DatabaseTimeouts::uniform(TEST_TIMEOUT)
+    }
+
+    pub fn person_repository(&self) -> PostgresPersonRepository {
+        PostgresPersonRepository::new(&self.url, self.timeouts())
+    }
+
+    pub fn speech_repository(&self) -> PostgresSpeechRepository {
+        PostgresSpeechRepository::new(&self.url, self.timeouts())
+    }
+
+    /// Managers of every entity stored in the database, to route requests with.
+    pub fn managers(&self) -> Managers {
+        let timeouts = self.timeouts();
+        Managers {
+            person_manager: PersonManager::new(Box::new(self.person_repository())),
+            speech_manager: SpeechManager::new(Box::new(self.speech_repository())),
+            label_manager: LabelManager::new(Box::new(PostgresLabelRepository::new(
+                &self.url, timeouts,
+            ))),
+            tag_manager: TagManager::new(Box::new(PostgresTagRepository::new(&self.url, timeouts))),
+            organization_manager: OrganizationManager::new(Box::new(
+                PostgresOrganizationRepository::new(&self.url, timeouts),
+            )),
+            idempotency_manager: IdempotencyManager::new(Box::new(
+                PostgresIdempotencyRepository::new(&self.url, timeouts),
+            )),
+            attachment_manager: AttachmentManager::new(Box::new(
+                PostgresAttachmentRepository::new(&self.url, timeouts),
+            )),
+            annotation_manager: AnnotationManager::new(Box::new(
+                PostgresAnnotationRepository::new(&self.url, timeouts),
+            )),
+            watchlist_manager: WatchlistManager::new(Box::new(PostgresWatchlistRepository::new(
+                &self.url, timeouts,
+            ))),
+            maintenance_manager: MaintenanceManager::new(Box::new(
+                PostgresMaintenanceRepository::new(&self.url, timeouts),
+            )),
+            job_manager: JobManager::new(Box::new(PostgresJobRepository::new(&self.url, timeouts))),
+            segment_manager: SegmentManager::new(Box::new(PostgresSegmentRepository::new(
+                &self.url, timeouts,
+            ))),
+            stats_manager: StatsManager::new(Box::new(PostgresStatsRepository::new(
+                &self.url, timeouts,
+            ))),
+            collection_versions: CollectionVersions::new(Box::new(
+                PostgresCollectionVersionRepository::new(&self.url, timeouts),
+            )),
+        }
+    }
+
+    /// Stores the person built, in the default organization.
+    pub async fn create_person(&self, builder: PersonBuilder) -> Person {
+        let person = builder.build();
+        self.person_repository()
+            .create_person(&person)
+            .await
+            .expect("Cannot create the fixture person");
+        person
+    }
+
+    /// Stores the speech built, in the default organization. Its speakers must be stored.
+    pub async fn create_speech(&self, builder: SpeechBuilder) -> Speech {
+        let speech = builder.build();
+        self.speech_repository()
+            .create_speech(&speech, None)
+            .await
+            .expect("Cannot create the fixture speech");
+        speech
+    }
+
+    /// Empties every table but the history of the migrations.
+    async fn truncate(&self) {
+        let pool = PgPool::connect(&self.url)
+            .await
+            .expect("Cannot connect to the test database");
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT tablename::TEXT FROM pg_tables WHERE schemaname = 'public' AND tablename <> '_sqlx_migrations'",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("Cannot list the tables of the test database");
+        if !tables.is_empty() {
+            let tables = tables
+                .iter()
+                .map(|table| format!("\"{}\"", table))
+                .collect::<Vec<String>>()
+                .join(", ");
+            sqlx::query(&format!(
+                "TRUNCATE TABLE {} RESTART IDENTITY CASCADE",
+                tables
+            ))
+            .execute(&pool)
+            .await
+            .expect("Cannot empty the test database");
+        }
+        pool.close().await;
+    }
+}
@@ -0,0 +1,5 @@
+mod builders;
+mod database;
+
+pub use builders::{PersonBuilder, SpeechBuilder};
+pub use database::{test_database, TestDatabase};